@@ -1,21 +1,49 @@
+use crate::commands::settings::{load_app_settings, load_export_presets};
 use crate::core::capture::metrics::parse_drop_rates;
-use crate::core::export::service::planned_progress;
-use crate::domain::models::{AppError, ExportProfile, ProjectStatus};
+use crate::core::capture::service::validate_frame_rate;
+use crate::core::export::presets::bitrate_for_size_limit;
+use crate::core::export::service::{
+    encoding_progress, finished_progress, queued_progress, variant_progress,
+};
+use crate::domain::events::AppEvent;
+use crate::domain::models::{
+    AnimationExportOptions, AppError, AspectRatio, ExportHistoryEntry, ExportProfile,
+    ExportProgressEvent, ExportUsage, MotionPreviewProgressEvent, ProjectManifest, ProjectStatus,
+    Resolution, TimeRange,
+};
 use crate::domain::state_machine::ExportState;
-use crate::infra::ffmpeg::capabilities::detect_hardware_encoder;
-use crate::infra::ffmpeg::export::{classify_export_error, export_with_fallback};
+use crate::infra::desktop::{open_with_default_app, reveal_in_file_manager};
+use crate::infra::events::emit_event;
+use crate::infra::ffmpeg::analysis::analyze_content;
+use crate::infra::ffmpeg::animation::normalize_animation_options;
+use crate::infra::ffmpeg::capabilities::{detect_hardware_encoder, SOFTWARE_CODEC};
+use crate::infra::ffmpeg::captions::write_sidecar_captions;
+use crate::infra::ffmpeg::export::{
+    classify_export_error, export_with_fallback, parse_benchmark_cpu_ms, render_animation,
+    render_motion_preview as render_preview_clip, render_poster, resolve_encoder_threads,
+    stitch_clip_paths, stitch_intro_outro, subtitle_path,
+};
 use crate::infra::ffmpeg::probe::{calc_av_offset_ms, probe_media};
 use crate::infra::storage::project_store::{
-    export_log_path, export_output_path, load_manifest, save_manifest,
+    animation_output_path, export_log_path, export_main_segment_path, export_output_path,
+    export_partial_path, export_variant_output_path, load_manifest, motion_preview_path,
+    poster_output_path, save_manifest,
 };
 use crate::state::{ExportTask, RuntimeState};
 use chrono::Utc;
 use serde::Serialize;
-use std::time::Duration;
-use tauri::{AppHandle, Emitter, Manager, State};
-use tokio::time::sleep;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{AppHandle, Manager, State};
 use uuid::Uuid;
 
+// 单次导出在主版本之外最多附加的版本数，避免一次任务占用编码器过久。
+const MAX_EXPORT_VARIANTS: usize = 3;
+// manifest 只保留最近的导出记录，够用来对比硬件与软件编码即可。
+const MAX_EXPORT_HISTORY: usize = 20;
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportTaskStatusSnapshot {
@@ -31,24 +59,16 @@ pub async fn get_export_task_status(
     state: State<'_, RuntimeState>,
     export_task_id: String,
 ) -> Result<ExportTaskStatusSnapshot, AppError> {
-    let tasks = state
-        .export_tasks
-        .lock()
-        .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock export tasks", None))?;
+    let tasks = state.export_tasks.read().await;
     let task = tasks.get(&export_task_id).ok_or_else(|| {
         AppError::new(
             "EXPORT_TASK_NOT_FOUND",
             format!("export task not found: {export_task_id}"),
             Some("请重新发起导出".to_string()),
         )
+        .with_param("taskId", &export_task_id)
     })?;
-    Ok(ExportTaskStatusSnapshot {
-        task_id: task.task_id.clone(),
-        project_id: task.project_id.clone(),
-        status: export_state_key(task.state).to_string(),
-        retries: task.retries,
-        last_error: task.last_error.clone(),
-    })
+    Ok(task_snapshot(task))
 }
 
 #[tauri::command]
@@ -57,13 +77,25 @@ pub async fn start_export(
     state: State<'_, RuntimeState>,
     project_id: String,
     profile: ExportProfile,
+    variants: Option<Vec<ExportProfile>>,
 ) -> Result<String, AppError> {
     ensure_valid_project_id(&project_id)?;
+    let variants = variants.unwrap_or_default();
+    if variants.len() > MAX_EXPORT_VARIANTS {
+        return Err(AppError::new(
+            "EXPORT_TOO_MANY_VARIANTS",
+            format!("at most {MAX_EXPORT_VARIANTS} extra variants per export"),
+            Some(format!("一次最多附加 {MAX_EXPORT_VARIANTS} 个导出版本")),
+        )
+        .with_param("max", MAX_EXPORT_VARIANTS));
+    }
+    for item in std::iter::once(&profile).chain(&variants) {
+        validate_frame_rate(item.fps)?;
+        ensure_stitch_clips_exist(item)?;
+        ensure_subtitle_usable(item)?;
+    }
     {
-        let tasks = state
-            .export_tasks
-            .lock()
-            .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock export tasks", None))?;
+        let tasks = state.export_tasks.read().await;
         if tasks.values().any(|task| {
             task.project_id == project_id
                 && (task.state == ExportState::Queued
@@ -80,7 +112,11 @@ pub async fn start_export(
 
     let mut manifest = load_manifest(&state.project_root, &project_id)?;
     manifest.status = ProjectStatus::Exporting;
-    manifest.export = profile.clone();
+    // 体积上限只对本次按预设导出生效，不写回项目设置，避免之后的手动导出沿用。
+    manifest.export = ExportProfile {
+        max_size_mb: None,
+        ..profile.clone()
+    };
     manifest.updated_at = Utc::now();
     save_manifest(&state.project_root, &project_id, &manifest)?;
 
@@ -89,15 +125,14 @@ pub async fn start_export(
         task_id: task_id.clone(),
         project_id: project_id.clone(),
         profile: profile.clone(),
+        variants,
         state: ExportState::Queued,
         retries: 0,
         last_error: None,
+        cancel_flag: Arc::new(AtomicBool::new(false)),
     };
     {
-        let mut tasks = state
-            .export_tasks
-            .lock()
-            .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock export tasks", None))?;
+        let mut tasks = state.export_tasks.write().await;
         if tasks.values().any(|item| {
             item.project_id == project_id
                 && (item.state == ExportState::Queued
@@ -113,27 +148,91 @@ pub async fn start_export(
         tasks.insert(task_id.clone(), task);
     }
 
-    schedule_export_pipeline(app, task_id.clone(), project_id, profile, 0);
+    enqueue_export(&app, &state, &task_id, &project_id).await?;
     Ok(task_id)
 }
 
+/// 按预设发起导出：编码参数取自预设，降噪、片头片尾、字幕等沿用项目上次的导出设置。
+#[tauri::command]
+pub async fn start_export_with_preset(
+    app: AppHandle,
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    preset_id: String,
+) -> Result<String, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let preset = load_export_presets(&state)?
+        .into_iter()
+        .find(|preset| preset.id == preset_id)
+        .ok_or_else(|| {
+            AppError::new(
+                "EXPORT_PRESET_NOT_FOUND",
+                format!("export preset not found: {preset_id}"),
+                Some("请刷新预设列表后重试".to_string()),
+            )
+            .with_param("presetId", &preset_id)
+        })?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let base = manifest.export.clone();
+    let profile = ExportProfile {
+        bitrate_mbps: bitrate_for_size_limit(
+            preset.profile.bitrate_mbps,
+            preset.max_size_mb,
+            trimmed_duration_ms(&manifest),
+        ),
+        audio_denoise: base.audio_denoise,
+        video_denoise: base.video_denoise,
+        frame_interpolation: base.frame_interpolation,
+        click_sound_enabled: base.click_sound_enabled,
+        click_sound_volume: base.click_sound_volume,
+        intro_path: base.intro_path,
+        outro_path: base.outro_path,
+        subtitle_path: base.subtitle_path,
+        subtitle_font_size: base.subtitle_font_size,
+        subtitle_position: base.subtitle_position,
+        low_priority: base.low_priority,
+        timecode: base.timecode,
+        timecode_corner: base.timecode_corner,
+        max_size_mb: preset.max_size_mb,
+        ..preset.profile
+    };
+    start_export(app, state, project_id, profile, None).await
+}
+
+/// 裁剪后的正片时长，未设置裁剪终点时探测原始录制；无法探测时返回 0。
+fn trimmed_duration_ms(manifest: &ProjectManifest) -> u64 {
+    let timeline = &manifest.timeline;
+    if timeline.trim_end_ms > timeline.trim_start_ms {
+        return timeline.trim_end_ms - timeline.trim_start_ms;
+    }
+    manifest
+        .artifacts
+        .raw_recording_path
+        .as_deref()
+        .and_then(|path| probe_media(std::path::Path::new(path)).ok())
+        .map(|summary| {
+            summary
+                .container_duration_ms
+                .saturating_sub(timeline.trim_start_ms)
+        })
+        .unwrap_or(0)
+}
+
 #[tauri::command]
 pub async fn retry_export(
     app: AppHandle,
     state: State<'_, RuntimeState>,
     export_task_id: String,
 ) -> Result<String, AppError> {
-    let (project_id, profile, retries) = {
-        let tasks = state
-            .export_tasks
-            .lock()
-            .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock export tasks", None))?;
+    let (project_id, profile, variants, retries) = {
+        let tasks = state.export_tasks.read().await;
         let task = tasks.get(&export_task_id).ok_or_else(|| {
             AppError::new(
                 "EXPORT_TASK_NOT_FOUND",
                 format!("export task not found: {export_task_id}"),
                 Some("请重新发起导出".to_string()),
             )
+            .with_param("taskId", &export_task_id)
         })?;
         if tasks.values().any(|item| {
             item.task_id != export_task_id
@@ -151,6 +250,7 @@ pub async fn retry_export(
         (
             task.project_id.clone(),
             task.profile.clone(),
+            task.variants.clone(),
             task.retries.saturating_add(1),
         )
     };
@@ -160,59 +260,470 @@ pub async fn retry_export(
         task_id: new_task_id.clone(),
         project_id: project_id.clone(),
         profile: profile.clone(),
+        variants,
         state: ExportState::Queued,
         retries,
         last_error: None,
+        cancel_flag: Arc::new(AtomicBool::new(false)),
     };
     state
         .export_tasks
-        .lock()
-        .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock export tasks", None))?
+        .write()
+        .await
         .insert(new_task_id.clone(), task);
 
-    schedule_export_pipeline(app, new_task_id.clone(), project_id, profile, retries);
+    enqueue_export(&app, &state, &new_task_id, &project_id).await?;
     Ok(new_task_id)
 }
 
-fn schedule_export_pipeline(
-    app: AppHandle,
-    task_id: String,
+/// 在文件管理器中定位最近一次导出的成片。
+#[tauri::command]
+pub async fn reveal_export_in_folder(
+    state: State<'_, RuntimeState>,
     project_id: String,
-    profile: ExportProfile,
-    retries: u8,
-) {
-    tauri::async_runtime::spawn(async move {
-        if let Err(error) =
-            run_export_pipeline(&app, &task_id, &project_id, &profile, retries).await
+) -> Result<(), AppError> {
+    let path = existing_export_path(&state, &project_id)?;
+    reveal_in_file_manager(&path)
+}
+
+/// 用系统默认播放器打开最近一次导出的成片。
+#[tauri::command]
+pub async fn open_export(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<(), AppError> {
+    let path = existing_export_path(&state, &project_id)?;
+    open_with_default_app(&path)
+}
+
+/// 最近一次导出的成片路径，文件已被移走时报错。
+pub fn existing_export_path(state: &RuntimeState, project_id: &str) -> Result<PathBuf, AppError> {
+    ensure_valid_project_id(project_id)?;
+    let manifest = load_manifest(&state.project_root, project_id)?;
+    let path = manifest
+        .artifacts
+        .last_export_path
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            AppError::new(
+                "EXPORT_NOT_FOUND",
+                "project has no export yet",
+                Some("请先完成一次导出".to_string()),
+            )
+        })?;
+    if !path.is_file() {
+        return Err(AppError::new(
+            "EXPORT_FILE_MISSING",
+            format!("export file not found: {}", path.display()),
+            Some("成片可能已被移动或删除，请重新导出".to_string()),
+        ));
+    }
+    Ok(path)
+}
+
+/// 取消导出：排队中的任务直接出队，运行中的任务终止 ffmpeg 并清理半成品。
+#[tauri::command]
+pub async fn cancel_export(
+    app: AppHandle,
+    state: State<'_, RuntimeState>,
+    export_task_id: String,
+) -> Result<(), AppError> {
+    let dequeued = state
+        .export_queue
+        .lock()
+        .await
+        .remove_pending(&export_task_id);
+    let project_id = {
+        let mut tasks = state.export_tasks.write().await;
+        let task = tasks.get_mut(&export_task_id).ok_or_else(|| {
+            AppError::new(
+                "EXPORT_TASK_NOT_FOUND",
+                format!("export task not found: {export_task_id}"),
+                Some("请重新发起导出".to_string()),
+            )
+            .with_param("taskId", &export_task_id)
+        })?;
+        if task.state != ExportState::Queued
+            && task.state != ExportState::Running
+            && task.state != ExportState::Fallback
         {
-            let _ = app.emit(
-                "export/progress",
-                serde_json::json!({
-                  "taskId": task_id,
-                  "status": "failed",
-                  "progress": 100,
-                  "detail": error.message
+            return Err(AppError::new(
+                "EXPORT_NOT_ACTIVE",
+                "export task is not running",
+                Some("导出任务已结束，无需取消".to_string()),
+            ));
+        }
+        task.cancel_flag.store(true, Ordering::SeqCst);
+        task.state = ExportState::Cancelled;
+        task.project_id.clone()
+    };
+    // 尚未出队的任务没有 ffmpeg 进程，由这里直接收尾；运行中的任务由管线收尾。
+    if dequeued {
+        emit_export_cancelled(&app, &export_task_id);
+        restore_project_after_cancel(&state, &project_id, false)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQueueSnapshot {
+    pub max_concurrent: usize,
+    pub running: Vec<ExportTaskStatusSnapshot>,
+    pub pending: Vec<ExportTaskStatusSnapshot>,
+}
+
+#[tauri::command]
+pub async fn list_export_queue(
+    state: State<'_, RuntimeState>,
+) -> Result<ExportQueueSnapshot, AppError> {
+    export_queue_snapshot(&state).await
+}
+
+/// 按给定顺序重排等待中的导出任务，列表需覆盖全部排队任务。
+#[tauri::command]
+pub async fn reorder_export_queue(
+    state: State<'_, RuntimeState>,
+    task_ids: Vec<String>,
+) -> Result<ExportQueueSnapshot, AppError> {
+    state.export_queue.lock().await.reorder(&task_ids)?;
+    export_queue_snapshot(&state).await
+}
+
+/// 预览区间上限，避免误选整段长录屏导致预览比正式导出还慢。
+const MAX_MOTION_PREVIEW_MS: u64 = 60_000;
+
+/// 渲染选定区间的低清镜头预览，完成后返回预览文件路径。
+#[tauri::command]
+pub async fn render_motion_preview(
+    app: AppHandle,
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    range: TimeRange,
+) -> Result<String, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    if range.end_ms <= range.start_ms {
+        return Err(AppError::new(
+            "PREVIEW_RANGE_INVALID",
+            "preview range end must be after start",
+            Some("请重新选择预览区间".to_string()),
+        ));
+    }
+    let end_ms = range.end_ms.min(range.start_ms + MAX_MOTION_PREVIEW_MS);
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let input_path = manifest
+        .artifacts
+        .raw_recording_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::new(
+                "PROJECT_ASSET_MISSING",
+                "recording asset file not found",
+                Some("请先完成录制后再预览镜头".to_string()),
+            )
+        })?;
+    let output_path = motion_preview_path(&state.project_root, &project_id);
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to create preview dir: {error}"),
+                None,
+            )
+        })?;
+    }
+
+    let emit_progress = {
+        let app = app.clone();
+        let project_id = project_id.clone();
+        move |status: &str, progress: u8| {
+            let _ = emit_event(
+                &app,
+                AppEvent::MotionPreviewProgress(MotionPreviewProgressEvent {
+                    project_id: project_id.clone(),
+                    status: status.to_string(),
+                    progress,
                 }),
             );
-            if let Some(state) = app.try_state::<RuntimeState>() {
-                if let Ok(mut tasks) = state.export_tasks.lock() {
-                    if let Some(task) = tasks.get_mut(&task_id) {
-                        task.state = ExportState::Failed;
-                        task.last_error = Some(error.clone());
-                    }
+        }
+    };
+    emit_progress("running", 0);
+    let render_output = output_path.clone();
+    let render_progress = emit_progress.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let mut last = 0u8;
+        render_preview_clip(
+            &manifest,
+            &input_path,
+            &render_output,
+            range.start_ms,
+            end_ms,
+            |progress| {
+                if progress > last {
+                    last = progress;
+                    render_progress("running", progress);
                 }
-                let _ = mark_project_export_failed(&state, &project_id, error);
-            }
+            },
+        )
+    })
+    .await
+    .map_err(|error| AppError::new("PREVIEW_TASK_FAIL", error.to_string(), None))??;
+    if !result.status.success() {
+        emit_progress("failed", 100);
+        return Err(classify_export_error(&result.stderr));
+    }
+    emit_progress("success", 100);
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+/// 把选定区间导出为循环动图（WebP/APNG），完成后返回文件路径。
+#[tauri::command]
+pub async fn export_animation(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    options: AnimationExportOptions,
+) -> Result<String, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let options = normalize_animation_options(&options)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let input_path = manifest
+        .artifacts
+        .raw_recording_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::new(
+                "PROJECT_ASSET_MISSING",
+                "recording asset file not found",
+                Some("请先完成录制后再导出动图".to_string()),
+            )
+        })?;
+    let output_path =
+        animation_output_path(&state.project_root, &project_id, options.format.extension());
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to create renders dir: {error}"),
+                None,
+            )
+        })?;
+    }
+    let render_output = output_path.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        render_animation(&manifest, &input_path, &render_output, &options)
+    })
+    .await
+    .map_err(|error| AppError::new("EXPORT_TASK_FAIL", error.to_string(), None))??;
+    if !result.status.success() {
+        let _ = std::fs::remove_file(&output_path);
+        return Err(classify_export_error(&result.stderr));
+    }
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+/// 导出 `t_ms` 处的封面图（与成片相同的裁剪与场景样式），`size` 决定输出分辨率，返回文件路径。
+#[tauri::command]
+pub async fn export_poster(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    t_ms: u64,
+    size: Resolution,
+) -> Result<String, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let input_path = manifest
+        .artifacts
+        .raw_recording_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::new(
+                "PROJECT_ASSET_MISSING",
+                "recording asset file not found",
+                Some("请先完成录制后再导出封面".to_string()),
+            )
+        })?;
+    let output_path = poster_output_path(&state.project_root, &project_id);
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to create renders dir: {error}"),
+                None,
+            )
+        })?;
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let duration_ms = probe_media(&input_path)?.container_duration_ms;
+        if duration_ms > 0 && t_ms >= duration_ms {
+            return Err(AppError::new(
+                "FRAME_OUT_OF_RANGE",
+                format!("t_ms {t_ms} exceeds recording duration {duration_ms}"),
+                Some("请选择录制时长以内的时间点".to_string()),
+            ));
         }
+        render_poster(&manifest, &input_path, &output_path, t_ms, size)?;
+        Ok(output_path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|error| AppError::new("POSTER_EXPORT_FAILED", error.to_string(), None))?
+}
+
+async fn enqueue_export(
+    app: &AppHandle,
+    state: &RuntimeState,
+    task_id: &str,
+    project_id: &str,
+) -> Result<(), AppError> {
+    let enqueued = state.export_queue.lock().await.enqueue(task_id, project_id);
+    if let Err(error) = enqueued {
+        state.export_tasks.write().await.remove(task_id);
+        return Err(error);
+    }
+    drain_export_queue(app).await;
+    Ok(())
+}
+
+/// 在并发上限内依次启动排队任务；每个任务结束后再次调用以推进队列。
+pub async fn drain_export_queue(app: &AppHandle) {
+    let Some(state) = app.try_state::<RuntimeState>() else {
+        return;
+    };
+    loop {
+        let next = state.export_queue.lock().await.start_next();
+        let Some(item) = next else {
+            break;
+        };
+        let task = state.export_tasks.read().await.get(&item.task_id).cloned();
+        match task {
+            Some(task) => spawn_export_worker(app.clone(), task),
+            None => state.export_queue.lock().await.finish(&item.task_id),
+        }
+    }
+}
+
+fn spawn_export_worker(app: AppHandle, task: ExportTask) {
+    tauri::async_runtime::spawn(async move {
+        let task_id = task.task_id;
+        let project_id = task.project_id;
+        let result =
+            run_export_pipeline(&app, &task_id, &project_id, &task.profile, &task.variants).await;
+        if let Err(error) = result {
+            handle_export_error(&app, &task_id, &project_id, error).await;
+        }
+        if let Some(state) = app.try_state::<RuntimeState>() {
+            state.export_queue.lock().await.finish(&task_id);
+        }
+        drain_export_queue(&app).await;
     });
 }
 
+async fn handle_export_error(app: &AppHandle, task_id: &str, project_id: &str, error: AppError) {
+    if error.code == "EXPORT_CANCELLED" {
+        emit_export_cancelled(app, task_id);
+        if let Some(state) = app.try_state::<RuntimeState>() {
+            let _ = restore_project_after_cancel(&state, project_id, true);
+        }
+        return;
+    }
+    let _ = emit_event(
+        app,
+        AppEvent::ExportProgress(finished_progress(
+            task_id,
+            "failed",
+            100,
+            error.message.clone(),
+        )),
+    );
+    if let Some(state) = app.try_state::<RuntimeState>() {
+        if let Some(task) = state.export_tasks.write().await.get_mut(task_id) {
+            task.state = ExportState::Failed;
+            task.last_error = Some(error.clone());
+        }
+        let _ = mark_project_export_failed(&state, project_id, error);
+    }
+}
+
+fn emit_export_cancelled(app: &AppHandle, task_id: &str) {
+    let _ = emit_event(
+        app,
+        AppEvent::ExportProgress(finished_progress(task_id, "cancelled", 0, "导出已取消")),
+    );
+}
+
+async fn export_queue_snapshot(state: &RuntimeState) -> Result<ExportQueueSnapshot, AppError> {
+    let (max_concurrent, running_ids, pending_ids) = {
+        let queue = state.export_queue.lock().await;
+        (
+            queue.max_concurrent(),
+            queue
+                .running()
+                .map(|item| item.task_id.clone())
+                .collect::<Vec<_>>(),
+            queue
+                .pending()
+                .map(|item| item.task_id.clone())
+                .collect::<Vec<_>>(),
+        )
+    };
+    let tasks = state.export_tasks.read().await;
+    let collect = |ids: &[String]| {
+        ids.iter()
+            .filter_map(|id| tasks.get(id))
+            .map(task_snapshot)
+            .collect::<Vec<_>>()
+    };
+    Ok(ExportQueueSnapshot {
+        max_concurrent,
+        running: collect(&running_ids),
+        pending: collect(&pending_ids),
+    })
+}
+
+fn task_snapshot(task: &ExportTask) -> ExportTaskStatusSnapshot {
+    ExportTaskStatusSnapshot {
+        task_id: task.task_id.clone(),
+        project_id: task.project_id.clone(),
+        status: export_state_key(task.state).to_string(),
+        retries: task.retries,
+        last_error: task.last_error.clone(),
+    }
+}
+
+/// 单个导出版本：主版本写入 output.partial.mp4，全部成功后改名为 output.mp4；
+/// 附加版本写入 renders/ 下的独立文件。
+struct ExportVariantJob {
+    manifest: ProjectManifest,
+    profile: ExportProfile,
+    output_path: PathBuf,
+    label: String,
+    index: usize,
+    count: usize,
+}
+
+/// 同一导出任务内各版本共享的上下文。
+struct ExportPipelineContext {
+    app: AppHandle,
+    task_id: String,
+    input_path: PathBuf,
+    main_path: PathBuf,
+    log_path: PathBuf,
+    cancel_flag: Arc<AtomicBool>,
+    hw_codec: String,
+    encoder_threads: u16,
+}
+
 async fn run_export_pipeline(
     app: &AppHandle,
     task_id: &str,
     project_id: &str,
     profile: &ExportProfile,
-    _retries: u8,
+    variants: &[ExportProfile],
 ) -> Result<(), AppError> {
     let state = app.state::<RuntimeState>();
     let manifest = load_manifest(&state.project_root, project_id)?;
@@ -228,7 +739,7 @@ async fn run_export_pipeline(
             )
         })?
         .clone();
-    let input_path = std::path::PathBuf::from(input_path);
+    let input_path = PathBuf::from(input_path);
     if !input_path.exists() {
         return Err(AppError::new(
             "PROJECT_ASSET_MISSING",
@@ -238,97 +749,327 @@ async fn run_export_pipeline(
     }
 
     let output_path = export_output_path(&state.project_root, project_id);
+    let partial_path = export_partial_path(&state.project_root, project_id);
     let log_path = export_log_path(&state.project_root, project_id, task_id);
+    let cancel_flag = state
+        .export_tasks
+        .read()
+        .await
+        .get(task_id)
+        .map(|task| task.cancel_flag.clone())
+        .unwrap_or_default();
 
     let hw = detect_hardware_encoder();
     tracing::info!("hardware encoder detect: {}", hw.detail);
-    let events = planned_progress(task_id, hw.clone());
-    for event in events.iter().take(3) {
-        sleep(Duration::from_millis(200)).await;
-        app.emit("export/progress", event)
-            .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))?;
-        update_task_status(app, task_id, &event.status)?;
+    let encoder_threads = load_app_settings(&state)
+        .map(|settings| settings.encoder_threads)
+        .unwrap_or_default();
+    let queued = queued_progress(task_id);
+    update_task_status(app, task_id, &queued.status).await;
+    emit_event(app, AppEvent::ExportProgress(queued))?;
+
+    let context = Arc::new(ExportPipelineContext {
+        app: app.clone(),
+        task_id: task_id.to_string(),
+        input_path,
+        main_path: export_main_segment_path(&state.project_root, project_id),
+        log_path: log_path.clone(),
+        cancel_flag,
+        hw_codec: hw.codec.to_string(),
+        encoder_threads,
+    });
+    let count = variants.len() + 1;
+    let jobs = std::iter::once(profile)
+        .chain(variants)
+        .enumerate()
+        .map(|(index, profile)| {
+            let mut manifest = manifest.clone();
+            if let Some(aspect_ratio) = profile.aspect_ratio.clone() {
+                manifest.timeline.aspect_ratio = aspect_ratio;
+            }
+            let label = variant_label(profile, &manifest.timeline.aspect_ratio);
+            let output_path = if index == 0 {
+                partial_path.clone()
+            } else {
+                export_variant_output_path(&state.project_root, project_id, index, &label)
+            };
+            ExportVariantJob {
+                manifest,
+                profile: profile.clone(),
+                output_path,
+                label,
+                index,
+                count,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut variant_paths = Vec::new();
+    let mut usage = Vec::new();
+    for job in jobs {
+        // 编码与拼接会阻塞到 ffmpeg 结束，放到阻塞线程池执行，避免占住异步运行时。
+        let worker_context = context.clone();
+        let (job, result) = tauri::async_runtime::spawn_blocking(move || {
+            let result = encode_variant(&worker_context, &job);
+            (job, result)
+        })
+        .await
+        .map_err(|error| AppError::new("EXPORT_TASK_FAIL", error.to_string(), None))?;
+        let variant_usage = match result {
+            Ok(variant_usage) => variant_usage,
+            Err(error) => {
+                let _ = std::fs::remove_file(&job.output_path);
+                // 取消时连同本任务已完成的附加版本一起清理，与主版本的处理保持一致。
+                if error.code == "EXPORT_CANCELLED" {
+                    for path in &variant_paths {
+                        let _ = std::fs::remove_file(path);
+                    }
+                }
+                return Err(error);
+            }
+        };
+        if job.index > 0 {
+            variant_paths.push(job.output_path.clone());
+        }
+        usage.push(variant_usage);
     }
+    std::fs::rename(&partial_path, &output_path).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to finalize export output: {error}"),
+            None,
+        )
+    })?;
+
+    update_task_status(app, task_id, "success").await;
+    let success_app = app.clone();
+    let success_project_id = project_id.to_string();
+    let success_entry = ExportHistoryEntry {
+        task_id: task_id.to_string(),
+        finished_at: Utc::now(),
+        variants: usage.clone(),
+    };
+    let caption_profile = profile.clone();
+    let caption_input = context.input_path.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        // 字幕是成片的附带产物，写出失败只记录日志，不影响导出结果。
+        let caption_paths =
+            write_sidecar_captions(&manifest, &caption_profile, &caption_input, &output_path)
+                .unwrap_or_else(|error| {
+                    tracing::warn!("sidecar captions failed: {}", error.message);
+                    Vec::new()
+                });
+        mark_project_export_success(
+            &success_app,
+            &success_project_id,
+            &output_path,
+            &variant_paths,
+            &caption_paths,
+            &log_path,
+            success_entry,
+        )
+    })
+    .await
+    .map_err(|error| AppError::new("EXPORT_TASK_FAIL", error.to_string(), None))??;
+
+    let mut finished = finished_progress(task_id, "success", 100, "导出完成");
+    finished.usage = usage;
+    emit_event(app, AppEvent::ExportProgress(finished))?;
+    Ok(())
+}
 
-    let result = export_with_fallback(&manifest, &input_path, &output_path, profile)?;
+/// 编码单个版本，需要时拼接片头片尾；日志按版本依次追加。
+fn encode_variant(
+    context: &ExportPipelineContext,
+    job: &ExportVariantJob,
+) -> Result<ExportUsage, AppError> {
+    let started = Instant::now();
+    let ExportPipelineContext {
+        app,
+        task_id,
+        cancel_flag,
+        ..
+    } = context;
+    let emit = |status: &str, progress: u8, detail: &str| -> Result<(), AppError> {
+        let event = variant_progress(
+            ExportProgressEvent {
+                task_id: task_id.to_string(),
+                status: status.to_string(),
+                progress,
+                detail: detail.to_string(),
+                variant: None,
+                usage: Vec::new(),
+            },
+            job.index,
+            job.count,
+            &job.label,
+        );
+        emit_event(app, AppEvent::ExportProgress(event))
+    };
+
+    // 有片头片尾时正片先编码到中间文件，拼接后再生成最终成片。
+    let stitching = !stitch_clip_paths(&job.profile).is_empty();
+    let main_path = if stitching {
+        &context.main_path
+    } else {
+        &job.output_path
+    };
+    let threads = resolve_encoder_threads(context.encoder_threads, job.profile.low_priority);
+    let result = export_with_fallback(
+        &job.manifest,
+        &context.input_path,
+        main_path,
+        &job.profile,
+        threads,
+        cancel_flag,
+        |codec, progress| {
+            let fallback = codec == SOFTWARE_CODEC && context.hw_codec != SOFTWARE_CODEC;
+            let event = variant_progress(
+                encoding_progress(task_id, fallback, progress),
+                job.index,
+                job.count,
+                &job.label,
+            );
+            update_task_status_blocking(app, task_id, &event.status);
+            let _ = emit_event(app, AppEvent::ExportProgress(event));
+        },
+    )?;
     let log_body = if result.stderr.is_empty() {
         "no stderr output".to_string()
     } else {
         result.stderr.clone()
     };
-    std::fs::write(&log_path, log_body.as_bytes()).map_err(|error| {
-        AppError::new(
-            "IO_ERROR",
-            format!("failed to write export log: {error}"),
-            None,
-        )
-    })?;
+    if job.index == 0 {
+        std::fs::write(&context.log_path, log_body.as_bytes()).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to write export log: {error}"),
+                None,
+            )
+        })?;
+    } else {
+        append_export_log(
+            &context.log_path,
+            &format!("variant {}", job.label),
+            &log_body,
+        )?;
+    }
 
     if !result.success {
         let app_error = classify_export_error(&result.stderr);
         return Err(app_error);
     }
 
-    let used_fallback = result.used_codec == "libx264" && hw.codec != "libx264";
+    let used_fallback = result.used_codec == SOFTWARE_CODEC && context.hw_codec != SOFTWARE_CODEC;
     if used_fallback {
-        app.emit(
-            "export/progress",
-            serde_json::json!({
-              "taskId": task_id,
-              "status": "fallback",
-              "progress": 62,
-              "detail": "硬件编码失败，已回退软件编码"
-            }),
-        )
-        .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))?;
-        update_task_status(app, task_id, "fallback")?;
-    }
-
-    app.emit(
-        "export/progress",
-        serde_json::json!({
-          "taskId": task_id,
-          "status": "running",
-          "progress": 85,
-          "detail": "正在封装 MP4"
-        }),
-    )
-    .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))?;
-    update_task_status(app, task_id, "running")?;
-
-    update_task_status(app, task_id, "success")?;
-    mark_project_export_success(app, project_id, &output_path, &log_path)?;
-
-    app.emit(
-        "export/progress",
-        serde_json::json!({
-          "taskId": task_id,
-          "status": "success",
-          "progress": 100,
-          "detail": "导出完成"
-        }),
-    )
-    .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))?;
-    Ok(())
+        emit("fallback", 99, "硬件编码失败，已回退软件编码")?;
+        update_task_status_blocking(app, task_id, "fallback");
+    }
+
+    let mut cpu_time_ms = parse_benchmark_cpu_ms(&result.stderr);
+    if stitching {
+        emit("running", 99, "正在拼接片头片尾")?;
+        let stitched = stitch_intro_outro(
+            &job.manifest,
+            &job.profile,
+            main_path,
+            &job.output_path,
+            &result.used_codec,
+            threads,
+            cancel_flag,
+        )?;
+        append_export_log(&context.log_path, "stitch", &stitched.stderr)?;
+        cpu_time_ms += parse_benchmark_cpu_ms(&stitched.stderr);
+        if cancel_flag.load(Ordering::SeqCst) {
+            return Err(AppError::new(
+                "EXPORT_CANCELLED",
+                "export cancelled by user",
+                None,
+            ));
+        }
+        if !stitched.status.success() {
+            return Err(classify_export_error(&stitched.stderr));
+        }
+        let _ = std::fs::remove_file(main_path);
+    }
+
+    emit("running", 99, "正在封装 MP4")?;
+    update_task_status_blocking(app, task_id, "running");
+    Ok(ExportUsage {
+        label: job.label.clone(),
+        encoder: result.used_codec,
+        cpu_time_ms,
+        wall_time_ms: started.elapsed().as_millis() as u64,
+        output_bytes: std::fs::metadata(&job.output_path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0),
+    })
+}
+
+/// 版本标签用于文件名与进度描述，如 `720p_9x16`。
+fn variant_label(profile: &ExportProfile, aspect_ratio: &AspectRatio) -> String {
+    let resolution = match profile.resolution {
+        Resolution::R1080p => "1080p",
+        Resolution::R720p => "720p",
+    };
+    let ratio = match aspect_ratio {
+        AspectRatio::Widescreen => "16x9",
+        AspectRatio::Vertical => "9x16",
+        AspectRatio::Square => "1x1",
+    };
+    format!("{resolution}_{ratio}")
 }
 
-fn update_task_status(app: &AppHandle, task_id: &str, status: &str) -> Result<(), AppError> {
+fn append_export_log(
+    log_path: &std::path::Path,
+    section: &str,
+    stderr: &str,
+) -> Result<(), AppError> {
+    use std::io::Write;
+    std::fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(log_path)
+        .and_then(|mut file| write!(file, "\n---- {section} ----\n{stderr}"))
+        .map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to write export log: {error}"),
+                None,
+            )
+        })
+}
+
+async fn update_task_status(app: &AppHandle, task_id: &str, status: &str) {
     let state = app.state::<RuntimeState>();
-    let mut tasks = state
-        .export_tasks
-        .lock()
-        .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock export tasks", None))?;
+    let mut tasks = state.export_tasks.write().await;
     if let Some(task) = tasks.get_mut(task_id) {
-        task.state = match status {
-            "queued" => ExportState::Queued,
-            "running" => ExportState::Running,
-            "fallback" => ExportState::Fallback,
-            "success" => ExportState::Success,
-            "failed" => ExportState::Failed,
-            _ => task.state,
-        };
+        apply_task_status(task, status);
     }
-    Ok(())
+}
+
+/// 供阻塞线程池中的编码回调使用；不可在异步上下文中调用。
+fn update_task_status_blocking(app: &AppHandle, task_id: &str, status: &str) {
+    let state = app.state::<RuntimeState>();
+    let mut tasks = state.export_tasks.blocking_write();
+    if let Some(task) = tasks.get_mut(task_id) {
+        apply_task_status(task, status);
+    }
+}
+
+fn apply_task_status(task: &mut ExportTask, status: &str) {
+    // 已取消的任务不再被编码进度覆盖回运行态。
+    if task.state == ExportState::Cancelled {
+        return;
+    }
+    task.state = match status {
+        "queued" => ExportState::Queued,
+        "running" => ExportState::Running,
+        "fallback" => ExportState::Fallback,
+        "success" => ExportState::Success,
+        "failed" => ExportState::Failed,
+        _ => task.state,
+    };
 }
 
 fn export_state_key(state: ExportState) -> &'static str {
@@ -338,6 +1079,7 @@ fn export_state_key(state: ExportState) -> &'static str {
         ExportState::Fallback => "fallback",
         ExportState::Success => "success",
         ExportState::Failed => "failed",
+        ExportState::Cancelled => "cancelled",
     }
 }
 
@@ -345,13 +1087,30 @@ fn mark_project_export_success(
     app: &AppHandle,
     project_id: &str,
     output_path: &std::path::Path,
+    variant_paths: &[PathBuf],
+    caption_paths: &[PathBuf],
     log_path: &std::path::Path,
+    history_entry: ExportHistoryEntry,
 ) -> Result<(), AppError> {
     let state = app.state::<RuntimeState>();
     let mut manifest = load_manifest(&state.project_root, project_id)?;
     manifest.status = ProjectStatus::ExportSucceeded;
+    manifest.export_history.push(history_entry);
+    let overflow = manifest
+        .export_history
+        .len()
+        .saturating_sub(MAX_EXPORT_HISTORY);
+    manifest.export_history.drain(..overflow);
     manifest.updated_at = Utc::now();
     manifest.artifacts.last_export_path = Some(output_path.to_string_lossy().to_string());
+    manifest.artifacts.variant_export_paths = variant_paths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    manifest.artifacts.caption_paths = caption_paths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
     manifest.artifacts.export_log_path = Some(log_path.to_string_lossy().to_string());
 
     if let Ok(summary) = probe_media(output_path) {
@@ -360,6 +1119,16 @@ fn mark_project_export_success(
         if manifest.timeline.trim_end_ms == 0 {
             manifest.timeline.trim_end_ms = summary.container_duration_ms;
         }
+        let has_audio = summary.audio_duration_ms.is_some();
+        match analyze_content(output_path, summary.container_duration_ms, has_audio) {
+            Ok(analysis) => {
+                manifest.quality.black_ranges = analysis.black_ranges;
+                manifest.quality.frozen_ranges = analysis.frozen_ranges;
+                manifest.quality.silent_ranges = analysis.silent_ranges;
+                manifest.quality.analyzed_duration_ms = summary.container_duration_ms;
+            }
+            Err(error) => tracing::warn!("export content analysis failed: {}", error.message),
+        }
     }
     if let Ok(log_raw) = std::fs::read_to_string(log_path) {
         if log_raw.contains("drop=") {
@@ -386,12 +1155,86 @@ fn mark_project_export_failed(
     save_manifest(&state.project_root, project_id, &manifest)
 }
 
+/// 取消后恢复项目状态。主版本编码到 output.partial.mp4，`discard_output` 时只删除
+/// 半成品与正片中间文件，上一次的 output.mp4 与导出记录保持不变。
+fn restore_project_after_cancel(
+    state: &RuntimeState,
+    project_id: &str,
+    discard_output: bool,
+) -> Result<(), AppError> {
+    let mut manifest = load_manifest(&state.project_root, project_id)?;
+    if discard_output {
+        let _ = std::fs::remove_file(export_main_segment_path(&state.project_root, project_id));
+        let partial_path = export_partial_path(&state.project_root, project_id);
+        if partial_path.exists() {
+            std::fs::remove_file(&partial_path).map_err(|error| {
+                AppError::new(
+                    "IO_ERROR",
+                    format!("failed to remove partial export: {error}"),
+                    None,
+                )
+            })?;
+        }
+    }
+    manifest.status = if manifest.artifacts.last_export_path.is_some() {
+        ProjectStatus::ExportSucceeded
+    } else {
+        ProjectStatus::ReadyToEdit
+    };
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, project_id, &manifest)
+}
+
+fn ensure_subtitle_usable(profile: &ExportProfile) -> Result<(), AppError> {
+    let Some(path) = subtitle_path(profile) else {
+        return Ok(());
+    };
+    if !path.is_file() {
+        return Err(AppError::new(
+            "EXPORT_SUBTITLE_MISSING",
+            format!("subtitle file not found: {}", path.display()),
+            Some("请重新选择字幕文件".to_string()),
+        ));
+    }
+    let supported = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ["srt", "vtt", "ass", "ssa"]
+                .iter()
+                .any(|item| ext.eq_ignore_ascii_case(item))
+        });
+    if !supported {
+        return Err(AppError::new(
+            "EXPORT_SUBTITLE_UNSUPPORTED",
+            format!("unsupported subtitle format: {}", path.display()),
+            Some("请使用 SRT、VTT 或 ASS 字幕".to_string()),
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_stitch_clips_exist(profile: &ExportProfile) -> Result<(), AppError> {
+    if let Some(missing) = stitch_clip_paths(profile)
+        .into_iter()
+        .find(|path| !path.is_file())
+    {
+        return Err(AppError::new(
+            "EXPORT_CLIP_MISSING",
+            format!("intro/outro clip not found: {}", missing.display()),
+            Some("请重新选择片头/片尾文件".to_string()),
+        ));
+    }
+    Ok(())
+}
+
 fn ensure_valid_project_id(project_id: &str) -> Result<(), AppError> {
     let trimmed = project_id.trim();
     if trimmed.is_empty()
         || trimmed.contains('/')
         || trimmed.contains('\\')
         || trimmed.contains("..")
+        || trimmed.starts_with('.')
     {
         return Err(AppError::new(
             "INVALID_PROJECT_ID",
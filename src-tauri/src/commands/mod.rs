@@ -2,3 +2,5 @@ pub mod export;
 pub mod project;
 pub mod recording;
 pub mod settings;
+pub mod share;
+pub mod upload;
@@ -1,230 +1,1715 @@
-use crate::core::export::quality::validate_mvp_quality;
-use crate::core::motion::tracker::{compute_motion_path, evaluate_metrics, CursorSample};
-use crate::core::recovery::service::scan_recoverable_projects;
-use crate::core::timeline::service::apply_timeline_patch;
+use crate::core::capture::app_track::load_app_track;
+use crate::core::export::quality::{
+    export_drop_rate, validate_content_ranges, validate_mvp_quality,
+};
+use crate::core::history::service::{apply_redo, apply_undo, history_status, record_edit};
+use crate::core::library::service::{
+    normalize_notes, normalize_tags, project_list_item, query_projects,
+};
+use crate::core::motion::refine::refine_track;
+use crate::core::motion::track_edit::apply_track_edits;
+use crate::core::motion::tracker::{
+    compute_motion_path, downsample_path, evaluate_metrics, CursorSample,
+};
+use crate::core::recovery::service::{extend_cursor_track, scan_recoverable_projects};
+use crate::core::timeline::chapters::suggest_chapters as suggest_chapter_markers;
+use crate::core::timeline::sensitive::{
+    find_sensitive_text, merge_hits, scan_times_ms, DEFAULT_SCAN_INTERVAL_MS, MAX_SCAN_INTERVAL_MS,
+    MIN_SCAN_INTERVAL_MS,
+};
+use crate::core::timeline::service::{
+    apply_timeline_patch, cut_segments, merge_mute_range, normalize_blur_region,
+    normalize_shape_annotation, normalize_text_annotation, subtract_mute_range, validate_segments,
+};
+use crate::core::transcript::service::{load_transcript, split_words, word_cut_ranges};
+use crate::domain::events::AppEvent;
 use crate::domain::models::{
-    AppError, CameraMotionPatch, CameraMotionProfile, ProjectManifest, RecoverableProject,
-    TimelinePatch,
+    AnnotationStyle, AppError, AudioMixPatch, AudioWaveform, BlurCandidate, BlurMode, BlurRegion,
+    CameraMotionPatch, CameraMotionProfile, ColorGradePatch, CursorRefineOptions, CursorTrackEdit,
+    CursorTrackSnapshot, EditHistory, EditHistoryStatus, EditSnapshot, KeystrokeOverlayPatch,
+    LoadedProject, ManifestRevision, NormalizedPoint, NormalizedRect, ProjectArchiveProgressEvent,
+    ProjectArtifacts, ProjectListPage, ProjectListQuery, ProjectManifest, ProjectSearchResult,
+    ProjectStatus, RecordingProfile, RecoverableProject, RecoveryEvent, Resolution,
+    SceneBackground, SceneStylePatch, ShapeAnnotation, ShapeKind, ShapeStyle, TextAnnotation,
+    TimeRange, TimelineConfig, TimelineMarker, TimelinePatch, TimelineSegment, TimelineThumbnail,
+    TranscriptWord, TranscriptWordRange, TrashedProject,
+};
+use crate::infra::events::emit_event;
+use crate::infra::ffmpeg::command::probe_input_dimensions;
+use crate::infra::ffmpeg::probe::probe_media;
+use crate::infra::ffmpeg::repair::{finalize_capture, remux_recording};
+use crate::infra::ffmpeg::scene::parse_hex_color;
+use crate::infra::ffmpeg::silence::{detect_silence, SILENCE_MIN_DURATION_MS, SILENCE_NOISE_DB};
+use crate::infra::ffmpeg::thumbnails::{
+    extract_frame_png, extract_thumbnails, thumbnail_times_ms, MAX_THUMBNAILS, THUMBNAIL_WIDTH,
+};
+use crate::infra::ffmpeg::waveform::{extract_waveform_peaks, MAX_WAVEFORM_SAMPLES_PER_SECOND};
+use crate::infra::ocr::{ensure_ocr_available, recognize_lines};
+use crate::infra::storage::artifact_cache::{load_cached, save_cached, source_fingerprint};
+use crate::infra::storage::index::{query_index, rebuild_index, search_index};
+use crate::infra::storage::project_archive::{
+    extract_project_archive, rebase_artifact_path, write_project_archive, ARCHIVE_EXTENSION,
+};
+use crate::infra::storage::project_store::{
+    audio_waveform_path, clear_recovery_marker, create_project_manifest, cursor_track_path,
+    cursor_track_revision_path, duplicate_project_assets, ensure_project_dirs, frame_still_path,
+    link_or_copy, list_manifest_revision_ids, load_cursor_track_points, load_edit_history,
+    load_manifest, load_manifest_from_file, manifest_path, manifest_revision_path,
+    partial_recording_backup_path, project_dir, raw_recording_path, recording_capture_path,
+    refined_cursor_track_path, save_edit_history, save_manifest, sensitive_scan_dir,
+    timeline_thumbnails_dir, write_cursor_track_points, write_manifest_revision,
 };
-use crate::infra::storage::project_store::{load_manifest, project_dir, save_manifest};
-use crate::state::RuntimeState;
+use crate::infra::storage::trash::{
+    list_trash, move_to_trash, purge_trash as purge_trash_items, restore_from_trash,
+};
+use crate::state::{CachedProjectListItem, RuntimeState};
 use chrono::Utc;
 use serde::Serialize;
-use tauri::State;
+use tauri::{AppHandle, State};
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ProjectListItem {
-    pub project_id: String,
-    pub title: Option<String>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-    pub status: crate::domain::models::ProjectStatus,
-    pub duration_ms: u64,
-    pub has_export: bool,
-    pub export_path: Option<String>,
-    pub raw_path: Option<String>,
+#[tauri::command]
+pub async fn load_project(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<LoadedProject, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let app_track = manifest
+        .artifacts
+        .app_track_path
+        .as_deref()
+        .map(|path| load_app_track(std::path::Path::new(path)))
+        .unwrap_or_default();
+    Ok(LoadedProject {
+        manifest,
+        app_track,
+    })
+}
+
+/// 列出项目，支持状态过滤、标题搜索、排序与分页。
+/// 优先查询项目索引；索引不可用时扫描清单，清单按修改时间缓存，未变化的项目不重复解析。
+#[tauri::command]
+pub async fn list_projects(
+    state: State<'_, RuntimeState>,
+    query: Option<ProjectListQuery>,
+) -> Result<ProjectListPage, AppError> {
+    let query = query.unwrap_or_default();
+    let indexed = tauri::async_runtime::spawn_blocking({
+        let project_root = state.project_root.clone();
+        let query = query.clone();
+        move || query_index(&project_root, &query)
+    })
+    .await
+    .map_err(|error| AppError::new("PROJECT_INDEX_ERROR", error.to_string(), None))
+    .and_then(|result| result);
+    match indexed {
+        Ok(page) => return Ok(page),
+        Err(error) => tracing::warn!("project index unavailable: {}", error.message),
+    }
+
+    let entries = std::fs::read_dir(&state.project_root).map_err(|error| {
+        AppError::new(
+            "PROJECT_LIST_READ_FAIL",
+            format!("failed to read project root: {error}"),
+            Some("请检查项目目录是否可读".to_string()),
+        )
+    })?;
+    let mut cache = state.project_list_cache.lock().await;
+    let mut projects = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for entry in entries {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if !file_type.is_dir() {
+            continue;
+        }
+        let project_id = entry.file_name().to_string_lossy().to_string();
+        if project_id.trim().is_empty() {
+            continue;
+        }
+        let Ok(manifest_modified) =
+            std::fs::metadata(manifest_path(&state.project_root, &project_id))
+                .and_then(|metadata| metadata.modified())
+        else {
+            continue;
+        };
+        seen.insert(project_id.clone());
+        if let Some(cached) = cache.get(&project_id) {
+            if cached.manifest_modified == manifest_modified {
+                projects.push(cached.item.clone());
+                continue;
+            }
+        }
+        let manifest = match load_manifest(&state.project_root, &project_id) {
+            Ok(manifest) => manifest,
+            Err(_) => continue,
+        };
+        let item = project_list_item(&project_id, &manifest);
+        cache.insert(
+            project_id,
+            CachedProjectListItem {
+                manifest_modified,
+                item: item.clone(),
+            },
+        );
+        projects.push(item);
+    }
+    cache.retain(|project_id, _| seen.contains(project_id));
+    Ok(query_projects(projects, &query))
+}
+
+/// 按标题、备注与文字稿全文搜索项目，返回命中的项目与带时间的文字稿片段。
+#[tauri::command]
+pub async fn search_projects(
+    state: State<'_, RuntimeState>,
+    query: String,
+) -> Result<Vec<ProjectSearchResult>, AppError> {
+    let project_root = state.project_root.clone();
+    tauri::async_runtime::spawn_blocking(move || search_index(&project_root, &query))
+        .await
+        .map_err(|error| AppError::new("PROJECT_INDEX_ERROR", error.to_string(), None))?
+}
+
+/// 丢弃项目索引并按磁盘上的清单重建，返回收录的项目数。
+/// 用于手动改动项目目录或索引与清单不一致之后。
+#[tauri::command]
+pub async fn rebuild_project_index(state: State<'_, RuntimeState>) -> Result<usize, AppError> {
+    let project_root = state.project_root.clone();
+    tauri::async_runtime::spawn_blocking(move || rebuild_index(&project_root))
+        .await
+        .map_err(|error| AppError::new("PROJECT_INDEX_ERROR", error.to_string(), None))?
+}
+
+#[tauri::command]
+pub async fn update_project_tags(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    tags: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let tags = normalize_tags(tags)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    manifest.tags = tags.clone();
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    Ok(tags)
+}
+
+#[tauri::command]
+pub async fn update_project_notes(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    notes: String,
+) -> Result<(), AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let notes = normalize_notes(&notes)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    manifest.notes = notes;
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)
+}
+
+#[tauri::command]
+pub async fn list_manifest_revisions(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<Vec<ManifestRevision>, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let revisions = list_manifest_revision_ids(&state.project_root, &project_id)
+        .into_iter()
+        .filter_map(|revision_id| {
+            let path =
+                manifest_revision_path(&state.project_root, &project_id, &revision_id).ok()?;
+            let manifest = load_manifest_from_file(&path).ok()?;
+            Some(ManifestRevision {
+                revision_id,
+                saved_at: manifest.updated_at,
+                title: manifest.title,
+                trim_start_ms: manifest.timeline.trim_start_ms,
+                trim_end_ms: manifest.timeline.trim_end_ms,
+            })
+        })
+        .collect();
+    Ok(revisions)
+}
+
+/// 回滚到指定修订的编辑配置；素材路径、状态等描述磁盘文件的字段保持当前值。
+/// 回滚本身也会生成新修订，可以再次撤回。
+#[tauri::command]
+pub async fn restore_manifest_revision(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    revision_id: String,
+) -> Result<ProjectManifest, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let path = manifest_revision_path(&state.project_root, &project_id, &revision_id)?;
+    if !path.exists() {
+        return Err(AppError::new(
+            "REVISION_NOT_FOUND",
+            format!("manifest revision not found: {revision_id}"),
+            Some("请刷新修订列表后重试".to_string()),
+        )
+        .with_param("revisionId", &revision_id));
+    }
+    let revision = load_manifest_from_file(&path)?;
+    let current = load_manifest(&state.project_root, &project_id)?;
+    let manifest = ProjectManifest {
+        created_at: current.created_at,
+        updated_at: Utc::now(),
+        artifacts: current.artifacts,
+        status: current.status,
+        last_error: current.last_error,
+        uploads: current.uploads,
+        ..revision
+    };
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    if let Err(error) = write_manifest_revision(&state.project_root, &project_id) {
+        tracing::warn!("failed to record manifest revision: {}", error.message);
+    }
+    Ok(manifest)
+}
+
+#[tauri::command]
+pub async fn update_project_title(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    title: String,
+) -> Result<(), AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let next_title = title.trim().to_string();
+    manifest.title = if next_title.is_empty() {
+        None
+    } else {
+        Some(next_title)
+    };
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)
+}
+
+/// 复制项目用于尝试不同的镜头与导出设置：新项目重置状态与导出记录。
+/// `copy_assets` 为 false 时副本直接引用原项目素材，删除原项目后副本将无法导出。
+#[tauri::command]
+pub async fn duplicate_project(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    copy_assets: bool,
+) -> Result<String, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let source = load_manifest(&state.project_root, &project_id)?;
+    if matches!(source.status, ProjectStatus::Recording) {
+        return Err(AppError::new(
+            "PROJECT_BUSY",
+            "项目正在录制中，无法复制",
+            Some("请先停止录制再复制项目".to_string()),
+        ));
+    }
+    let new_id = uuid::Uuid::new_v4().to_string();
+    ensure_project_dirs(&state.project_root, &new_id)?;
+    if copy_assets {
+        if let Err(error) = duplicate_project_assets(&state.project_root, &project_id, &new_id) {
+            let _ = std::fs::remove_dir_all(project_dir(&state.project_root, &new_id));
+            return Err(error);
+        }
+    }
+
+    let source_dir = project_dir(&state.project_root, &project_id);
+    let target_dir = project_dir(&state.project_root, &new_id);
+    let rebase = |path: &Option<String>| -> Option<String> {
+        let path = path.as_deref()?;
+        match std::path::Path::new(path).strip_prefix(&source_dir) {
+            Ok(relative) if copy_assets => {
+                Some(target_dir.join(relative).to_string_lossy().to_string())
+            }
+            _ => Some(path.to_string()),
+        }
+    };
+    let now = Utc::now();
+    let title = source
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Project_{}", &project_id[..project_id.len().min(8)]));
+    let artifacts = &source.artifacts;
+    let manifest = ProjectManifest {
+        title: Some(format!("{title} 副本")),
+        created_at: now,
+        updated_at: now,
+        artifacts: ProjectArtifacts {
+            raw_recording_path: rebase(&artifacts.raw_recording_path),
+            cursor_track_path: rebase(&artifacts.cursor_track_path),
+            key_track_path: rebase(&artifacts.key_track_path),
+            app_track_path: rebase(&artifacts.app_track_path),
+            refined_cursor_track_path: rebase(&artifacts.refined_cursor_track_path),
+            edited_cursor_track_path: rebase(&artifacts.edited_cursor_track_path),
+            cursor_track_revision: artifacts.cursor_track_revision,
+            ..ProjectArtifacts::default()
+        },
+        status: ProjectStatus::ReadyToEdit,
+        last_error: None,
+        uploads: Vec::new(),
+        blur_candidates: Vec::new(),
+        export_history: Vec::new(),
+        ..source
+    };
+    save_manifest(&state.project_root, &new_id, &manifest)?;
+    Ok(new_id)
+}
+
+/// 把项目打包为可移植的 `.focuslens` 文件，返回实际写入的路径。
+#[tauri::command]
+pub async fn archive_project(
+    app: AppHandle,
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    dest: String,
+    include_renders: Option<bool>,
+) -> Result<String, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    if matches!(manifest.status, ProjectStatus::Recording) {
+        return Err(AppError::new(
+            "PROJECT_BUSY",
+            "项目正在录制中，无法打包",
+            Some("请先停止录制再打包项目".to_string()),
+        ));
+    }
+    let mut dest = std::path::PathBuf::from(dest.trim());
+    if dest.as_os_str().is_empty() {
+        return Err(AppError::new(
+            "ARCHIVE_PATH_INVALID",
+            "打包路径为空",
+            Some("请选择 .focuslens 文件的保存位置".to_string()),
+        ));
+    }
+    if dest.extension().and_then(|ext| ext.to_str()) != Some(ARCHIVE_EXTENSION) {
+        let mut name = dest.as_os_str().to_os_string();
+        name.push(format!(".{ARCHIVE_EXTENSION}"));
+        dest = name.into();
+    }
+    if let Some(parent) = dest
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::new(
+                "ARCHIVE_IO_ERROR",
+                format!("failed to create archive dir: {error}"),
+                Some("确认磁盘空间和路径权限".to_string()),
+            )
+        })?;
+    }
+
+    let source_dir = project_dir(&state.project_root, &project_id);
+    let output = dest.clone();
+    let include_renders = include_renders.unwrap_or(false);
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let mut last = None;
+        write_project_archive(
+            &source_dir,
+            &project_id,
+            &output,
+            include_renders,
+            |done, total| {
+                let progress = (done * 100).checked_div(total).unwrap_or(100).min(100) as u8;
+                if last != Some(progress) {
+                    last = Some(progress);
+                    let _ = emit_event(
+                        &app,
+                        AppEvent::ArchiveProgress(ProjectArchiveProgressEvent {
+                            project_id: project_id.clone(),
+                            progress,
+                        }),
+                    );
+                }
+            },
+        )
+    })
+    .await
+    .map_err(|error| AppError::new("ARCHIVE_IO_ERROR", error.to_string(), None))?;
+    if let Err(error) = result {
+        let _ = std::fs::remove_file(&dest);
+        return Err(error);
+    }
+    Ok(dest.to_string_lossy().to_string())
+}
+
+/// 从 `.focuslens` 导入项目：校验格式与清单版本，分配新 id 并改写素材路径。
+#[tauri::command]
+pub async fn import_project_archive(
+    state: State<'_, RuntimeState>,
+    path: String,
+) -> Result<String, AppError> {
+    let archive_path = std::path::PathBuf::from(path.trim());
+    let new_id = uuid::Uuid::new_v4().to_string();
+    let target_dir = project_dir(&state.project_root, &new_id);
+    let result = tauri::async_runtime::spawn_blocking({
+        let target_dir = target_dir.clone();
+        move || {
+            extract_project_archive(&archive_path, &target_dir)?;
+            load_manifest_from_file(&target_dir.join("project.json"))
+        }
+    })
+    .await
+    .map_err(|error| AppError::new("ARCHIVE_IO_ERROR", error.to_string(), None))
+    .and_then(|result| result);
+    let source = match result {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            let _ = std::fs::remove_dir_all(&target_dir);
+            return Err(error);
+        }
+    };
+
+    let rebase = |path: &Option<String>| -> Option<String> {
+        rebase_artifact_path(path.as_deref()?, &target_dir)
+    };
+    let artifacts = &source.artifacts;
+    let last_export_path = rebase(&artifacts.last_export_path);
+    let status = match source.status {
+        ProjectStatus::ExportSucceeded if last_export_path.is_some() => {
+            ProjectStatus::ExportSucceeded
+        }
+        _ => ProjectStatus::ReadyToEdit,
+    };
+    let manifest = ProjectManifest {
+        updated_at: Utc::now(),
+        artifacts: ProjectArtifacts {
+            raw_recording_path: rebase(&artifacts.raw_recording_path),
+            cursor_track_path: rebase(&artifacts.cursor_track_path),
+            last_export_path,
+            export_log_path: rebase(&artifacts.export_log_path),
+            key_track_path: rebase(&artifacts.key_track_path),
+            app_track_path: rebase(&artifacts.app_track_path),
+            refined_cursor_track_path: rebase(&artifacts.refined_cursor_track_path),
+            edited_cursor_track_path: rebase(&artifacts.edited_cursor_track_path),
+            cursor_track_revision: artifacts.cursor_track_revision,
+            variant_export_paths: artifacts
+                .variant_export_paths
+                .iter()
+                .filter_map(|path| rebase_artifact_path(path, &target_dir))
+                .collect(),
+            caption_paths: artifacts
+                .caption_paths
+                .iter()
+                .filter_map(|path| rebase_artifact_path(path, &target_dir))
+                .collect(),
+            waveform_path: None,
+        },
+        status,
+        last_error: None,
+        ..source
+    };
+    if let Err(error) = save_manifest(&state.project_root, &new_id, &manifest) {
+        let _ = std::fs::remove_dir_all(&target_dir);
+        return Err(error);
+    }
+    Ok(new_id)
+}
+
+/// 把外部录制的 MP4 导入为新项目，沿用剪辑与导出流程。
+/// 没有光标数据，写入停在画面中心的静态轨迹，镜头保持全景。
+#[tauri::command]
+pub async fn import_video(
+    state: State<'_, RuntimeState>,
+    path: String,
+) -> Result<String, AppError> {
+    let source = std::path::PathBuf::from(path.trim());
+    let is_mp4 = source
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("mp4"));
+    if !is_mp4 || !source.is_file() {
+        return Err(AppError::new(
+            "IMPORT_VIDEO_INVALID",
+            format!("not an mp4 file: {}", source.display()),
+            Some("请选择本地的 .mp4 视频文件".to_string()),
+        ));
+    }
+    let new_id = uuid::Uuid::new_v4().to_string();
+    ensure_project_dirs(&state.project_root, &new_id)?;
+    let raw_path = raw_recording_path(&state.project_root, &new_id);
+    let cursor_path = cursor_track_path(&state.project_root, &new_id);
+    let result = tauri::async_runtime::spawn_blocking({
+        let raw_path = raw_path.clone();
+        let cursor_path = cursor_path.clone();
+        move || {
+            link_or_copy(&source, &raw_path)?;
+            let duration_ms = probe_media(&raw_path)?.container_duration_ms;
+            let (width, height) = probe_input_dimensions(&raw_path).ok_or_else(|| {
+                AppError::new(
+                    "IMPORT_VIDEO_INVALID",
+                    "video stream not found",
+                    Some("请确认文件包含视频轨".to_string()),
+                )
+            })?;
+            let mut points = Vec::new();
+            extend_cursor_track(&mut points, duration_ms, (width, height));
+            write_cursor_track_points(&cursor_path, &points)?;
+            let title = source
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string());
+            Ok::<_, AppError>((duration_ms, height, title))
+        }
+    })
+    .await
+    .map_err(|error| AppError::new("IMPORT_VIDEO_FAILED", error.to_string(), None))
+    .and_then(|result| result);
+    let (duration_ms, height, title) = match result {
+        Ok(probed) => probed,
+        Err(error) => {
+            let _ = std::fs::remove_dir_all(project_dir(&state.project_root, &new_id));
+            return Err(error);
+        }
+    };
+
+    let mut manifest = create_project_manifest(RecordingProfile {
+        resolution: if height <= 720 {
+            Resolution::R720p
+        } else {
+            Resolution::R1080p
+        },
+        ..RecordingProfile::default()
+    });
+    manifest.title = title;
+    manifest.timeline.trim_end_ms = duration_ms;
+    manifest.artifacts.raw_recording_path = Some(raw_path.to_string_lossy().to_string());
+    manifest.artifacts.cursor_track_path = Some(cursor_path.to_string_lossy().to_string());
+    if let Err(error) = save_manifest(&state.project_root, &new_id, &manifest) {
+        let _ = std::fs::remove_dir_all(project_dir(&state.project_root, &new_id));
+        return Err(error);
+    }
+    Ok(new_id)
+}
+
+#[tauri::command]
+pub async fn delete_project(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<(), AppError> {
+    ensure_valid_project_id(&project_id)?;
+    {
+        let sessions = state.recording_sessions.read().await;
+        if sessions
+            .values()
+            .any(|session| session.project_id == project_id)
+        {
+            return Err(AppError::new(
+                "PROJECT_BUSY",
+                "项目正在录制中，无法删除",
+                Some("请先停止录制再删除项目".to_string()),
+            ));
+        }
+    }
+    {
+        let tasks = state.export_tasks.read().await;
+        if tasks.values().any(|task| {
+            task.project_id == project_id
+                && matches!(
+                    task.state,
+                    crate::domain::state_machine::ExportState::Queued
+                        | crate::domain::state_machine::ExportState::Running
+                        | crate::domain::state_machine::ExportState::Fallback
+                )
+        }) {
+            return Err(AppError::new(
+                "PROJECT_BUSY",
+                "项目存在进行中的导出任务，无法删除",
+                Some("请等待导出完成后再删除项目".to_string()),
+            ));
+        }
+    }
+    state
+        .export_tasks
+        .write()
+        .await
+        .retain(|_, task| task.project_id != project_id);
+    if !project_dir(&state.project_root, &project_id).exists() {
+        return Ok(());
+    }
+    move_to_trash(&state.project_root, &project_id)
+}
+
+#[tauri::command]
+pub async fn list_trashed_projects(
+    state: State<'_, RuntimeState>,
+) -> Result<Vec<TrashedProject>, AppError> {
+    Ok(list_trash(&state.project_root))
+}
+
+#[tauri::command]
+pub async fn restore_project(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<(), AppError> {
+    ensure_valid_project_id(&project_id)?;
+    restore_from_trash(&state.project_root, &project_id)
+}
+
+/// 永久删除回收站中的项目；`older_than_days` 为空时清空回收站。
+#[tauri::command]
+pub async fn purge_trash(
+    state: State<'_, RuntimeState>,
+    older_than_days: Option<u32>,
+) -> Result<usize, AppError> {
+    let project_root = state.project_root.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        purge_trash_items(
+            &project_root,
+            older_than_days.map(|days| chrono::Duration::days(days.into())),
+        )
+    })
+    .await
+    .map_err(|error| AppError::new("TRASH_IO_ERROR", error.to_string(), None))?
+}
+
+#[tauri::command]
+pub async fn update_timeline(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    patch: TimelinePatch,
+) -> Result<(), AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let before = EditSnapshot::Timeline(manifest.timeline.clone());
+    apply_timeline_patch(&mut manifest, patch);
+    manifest.timeline.segments = validate_segments(&manifest.timeline.segments)?;
+    if manifest.timeline.trim_end_ms > 0
+        && manifest.timeline.trim_end_ms < manifest.timeline.trim_start_ms
+    {
+        return Err(AppError::new(
+            "INVALID_TIMELINE",
+            "trimEndMs must be greater than trimStartMs",
+            Some("请调整裁剪区间".to_string()),
+        ));
+    }
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    record_project_edit(&state, &project_id, before)
+}
+
+/// 按词列出项目文字稿（来自挂载的字幕文件），词的时间为源文件时间轴。
+#[tauri::command]
+pub async fn get_transcript_words(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<Vec<TranscriptWord>, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    Ok(split_words(&load_transcript(&manifest.export)))
+}
+
+/// 删除选中的词：对应的时间区间从保留片段中剪掉，可撤销。返回剪切后的保留片段。
+#[tauri::command]
+pub async fn cut_transcript_words(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    ranges: Vec<TranscriptWordRange>,
+) -> Result<Vec<TimelineSegment>, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let words = split_words(&load_transcript(&manifest.export));
+    if words.is_empty() {
+        return Err(AppError::new(
+            "TRANSCRIPT_NOT_FOUND",
+            format!("project {project_id} has no transcript"),
+            Some("请先在导出设置中挂载字幕文件".to_string()),
+        ));
+    }
+    let cuts = word_cut_ranges(&words, &ranges)?;
+    let before = EditSnapshot::Timeline(manifest.timeline.clone());
+    let timeline = &manifest.timeline;
+    let segments = validate_segments(&cut_segments(
+        &timeline.segments,
+        timeline.trim_start_ms,
+        timeline.trim_end_ms,
+        &cuts,
+    )?)?;
+    manifest.timeline.segments = segments.clone();
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    record_project_edit(&state, &project_id, before)?;
+    Ok(segments)
+}
+
+/// 新增隐私打码区域（源文件时间轴），返回分配了 id 的区域。
+#[tauri::command]
+pub async fn add_blur_region(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    rect: NormalizedRect,
+    start_ms: u64,
+    end_ms: u64,
+    mode: Option<BlurMode>,
+) -> Result<BlurRegion, AppError> {
+    let region = normalize_blur_region(BlurRegion {
+        id: uuid::Uuid::new_v4().to_string(),
+        rect,
+        start_ms,
+        end_ms,
+        mode: mode.unwrap_or_default(),
+    })?;
+    edit_timeline_items(&state, &project_id, |timeline| {
+        timeline.blur_regions.push(region.clone());
+        Ok(region)
+    })
+}
+
+#[tauri::command]
+pub async fn update_blur_region(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    region: BlurRegion,
+) -> Result<BlurRegion, AppError> {
+    let region = normalize_blur_region(region)?;
+    edit_timeline_items(&state, &project_id, |timeline| {
+        let existing = timeline
+            .blur_regions
+            .iter_mut()
+            .find(|item| item.id == region.id)
+            .ok_or_else(|| blur_region_not_found(&region.id))?;
+        *existing = region.clone();
+        Ok(region)
+    })
+}
+
+#[tauri::command]
+pub async fn remove_blur_region(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    region_id: String,
+) -> Result<(), AppError> {
+    edit_timeline_items(&state, &project_id, |timeline| {
+        let before = timeline.blur_regions.len();
+        timeline.blur_regions.retain(|item| item.id != region_id);
+        if timeline.blur_regions.len() == before {
+            return Err(blur_region_not_found(&region_id));
+        }
+        Ok(())
+    })
+}
+
+/// 新增文字标注（源文件时间轴），返回分配了 id 的标注。
+#[tauri::command]
+pub async fn add_text_annotation(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    text: String,
+    x: f64,
+    y: f64,
+    range: TimeRange,
+    style: Option<AnnotationStyle>,
+) -> Result<TextAnnotation, AppError> {
+    let annotation = normalize_text_annotation(TextAnnotation {
+        id: uuid::Uuid::new_v4().to_string(),
+        text,
+        x,
+        y,
+        start_ms: range.start_ms,
+        end_ms: range.end_ms,
+        style: style.unwrap_or_default(),
+    })?;
+    edit_timeline_items(&state, &project_id, |timeline| {
+        timeline.annotations.push(annotation.clone());
+        Ok(annotation)
+    })
+}
+
+#[tauri::command]
+pub async fn update_text_annotation(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    annotation: TextAnnotation,
+) -> Result<TextAnnotation, AppError> {
+    let annotation = normalize_text_annotation(annotation)?;
+    edit_timeline_items(&state, &project_id, |timeline| {
+        let existing = timeline
+            .annotations
+            .iter_mut()
+            .find(|item| item.id == annotation.id)
+            .ok_or_else(|| text_annotation_not_found(&annotation.id))?;
+        *existing = annotation.clone();
+        Ok(annotation)
+    })
+}
+
+#[tauri::command]
+pub async fn remove_text_annotation(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    annotation_id: String,
+) -> Result<(), AppError> {
+    edit_timeline_items(&state, &project_id, |timeline| {
+        let before = timeline.annotations.len();
+        timeline.annotations.retain(|item| item.id != annotation_id);
+        if timeline.annotations.len() == before {
+            return Err(text_annotation_not_found(&annotation_id));
+        }
+        Ok(())
+    })
+}
+
+/// 新增箭头或形状标注（源文件时间轴），返回分配了 id 的标注。
+#[tauri::command]
+pub async fn add_shape_annotation(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    kind: ShapeKind,
+    from: NormalizedPoint,
+    to: NormalizedPoint,
+    range: TimeRange,
+    style: Option<ShapeStyle>,
+) -> Result<ShapeAnnotation, AppError> {
+    let shape = normalize_shape_annotation(ShapeAnnotation {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind,
+        from,
+        to,
+        start_ms: range.start_ms,
+        end_ms: range.end_ms,
+        style: style.unwrap_or_default(),
+    })?;
+    edit_timeline_items(&state, &project_id, |timeline| {
+        timeline.shapes.push(shape.clone());
+        Ok(shape)
+    })
+}
+
+#[tauri::command]
+pub async fn update_shape_annotation(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    shape: ShapeAnnotation,
+) -> Result<ShapeAnnotation, AppError> {
+    let shape = normalize_shape_annotation(shape)?;
+    edit_timeline_items(&state, &project_id, |timeline| {
+        let existing = timeline
+            .shapes
+            .iter_mut()
+            .find(|item| item.id == shape.id)
+            .ok_or_else(|| shape_annotation_not_found(&shape.id))?;
+        *existing = shape.clone();
+        Ok(shape)
+    })
+}
+
+#[tauri::command]
+pub async fn remove_shape_annotation(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    shape_id: String,
+) -> Result<(), AppError> {
+    edit_timeline_items(&state, &project_id, |timeline| {
+        let before = timeline.shapes.len();
+        timeline.shapes.retain(|item| item.id != shape_id);
+        if timeline.shapes.len() == before {
+            return Err(shape_annotation_not_found(&shape_id));
+        }
+        Ok(())
+    })
+}
+
+/// 打码区域与各类标注属于时间线，修改记入撤销历史；保存前按起点排序。
+fn edit_timeline_items<T>(
+    state: &RuntimeState,
+    project_id: &str,
+    edit: impl FnOnce(&mut TimelineConfig) -> Result<T, AppError>,
+) -> Result<T, AppError> {
+    ensure_valid_project_id(project_id)?;
+    let mut manifest = load_manifest(&state.project_root, project_id)?;
+    let before = EditSnapshot::Timeline(manifest.timeline.clone());
+    let result = edit(&mut manifest.timeline)?;
+    manifest
+        .timeline
+        .blur_regions
+        .sort_by_key(|region| region.start_ms);
+    manifest
+        .timeline
+        .annotations
+        .sort_by_key(|annotation| annotation.start_ms);
+    manifest.timeline.shapes.sort_by_key(|shape| shape.start_ms);
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, project_id, &manifest)?;
+    record_project_edit(state, project_id, before)?;
+    Ok(result)
+}
+
+/// 静音一段录制音轨（源文件时间轴），与已有区间重叠时合并，返回全部静音区间。
+#[tauri::command]
+pub async fn add_mute_range(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    range: TimeRange,
+) -> Result<Vec<TimeRange>, AppError> {
+    edit_timeline_items(&state, &project_id, |timeline| {
+        timeline.mute_ranges = merge_mute_range(&timeline.mute_ranges, range)?;
+        Ok(timeline.mute_ranges.clone())
+    })
+}
+
+/// 恢复一段声音：从静音区间中扣除 `range`，返回剩余的静音区间。
+#[tauri::command]
+pub async fn remove_mute_range(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    range: TimeRange,
+) -> Result<Vec<TimeRange>, AppError> {
+    edit_timeline_items(&state, &project_id, |timeline| {
+        timeline.mute_ranges = subtract_mute_range(&timeline.mute_ranges, range);
+        Ok(timeline.mute_ranges.clone())
+    })
+}
+
+fn blur_region_not_found(region_id: &str) -> AppError {
+    AppError::new(
+        "BLUR_REGION_NOT_FOUND",
+        format!("blur region not found: {region_id}"),
+        Some("请刷新项目后重试".to_string()),
+    )
+    .with_param("regionId", region_id)
+}
+
+/// 对裁剪区间内的录制画面抽帧 OCR，查找邮箱、API 密钥与疑似卡号，
+/// 结果作为打码建议写入清单（替换上一次的建议），由用户逐条确认或忽略。
+#[tauri::command]
+pub async fn detect_sensitive_content(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    interval_ms: Option<u64>,
+) -> Result<Vec<BlurCandidate>, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let input_path = manifest
+        .artifacts
+        .raw_recording_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::new(
+                "PROJECT_ASSET_MISSING",
+                "recording asset file not found",
+                Some("请先完成录制后再扫描敏感内容".to_string()),
+            )
+        })?;
+    let interval_ms = interval_ms
+        .unwrap_or(DEFAULT_SCAN_INTERVAL_MS)
+        .clamp(MIN_SCAN_INTERVAL_MS, MAX_SCAN_INTERVAL_MS);
+    let scan_dir = sensitive_scan_dir(&state.project_root, &project_id);
+    let (trim_start_ms, trim_end_ms) = (
+        manifest.timeline.trim_start_ms,
+        manifest.timeline.trim_end_ms,
+    );
+    let candidates = tauri::async_runtime::spawn_blocking(move || {
+        ensure_ocr_available()?;
+        let duration_ms = probe_media(&input_path)?.container_duration_ms;
+        let (frame_w, frame_h) = probe_input_dimensions(&input_path).ok_or_else(|| {
+            AppError::new(
+                "SENSITIVE_SCAN_FAILED",
+                "video stream not found",
+                Some("请确认录制文件完整可播放".to_string()),
+            )
+        })?;
+        let end_ms = if trim_end_ms > trim_start_ms {
+            trim_end_ms.min(duration_ms)
+        } else {
+            duration_ms
+        };
+        std::fs::create_dir_all(&scan_dir).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to create scan dir: {error}"),
+                Some("检查路径权限".to_string()),
+            )
+        })?;
+        let frame_path = scan_dir.join("frame.png");
+        let samples = scan_times_ms(trim_start_ms, end_ms, interval_ms)
+            .into_iter()
+            .map(|t_ms| {
+                extract_frame_png(&input_path, t_ms, &frame_path)?;
+                let lines = recognize_lines(&frame_path)?;
+                Ok((t_ms, find_sensitive_text(&lines, frame_w, frame_h)))
+            })
+            .collect::<Result<Vec<_>, AppError>>();
+        // 抽出的帧可能含敏感内容，无论成败都不留在磁盘上。
+        let _ = std::fs::remove_dir_all(&scan_dir);
+        Ok::<_, AppError>(merge_hits(&samples?, interval_ms, trim_start_ms, end_ms))
+    })
+    .await
+    .map_err(|error| AppError::new("SENSITIVE_SCAN_FAILED", error.to_string(), None))??;
+
+    // 扫描期间清单可能已被其他编辑修改，重新读取后只更新建议列表。
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    manifest.blur_candidates = candidates.clone();
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    Ok(candidates)
+}
+
+/// 确认打码建议：转为打码区域（可撤销）并从建议列表移除。
+#[tauri::command]
+pub async fn accept_blur_candidate(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    candidate_id: String,
+    mode: Option<BlurMode>,
+) -> Result<BlurRegion, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let index = manifest
+        .blur_candidates
+        .iter()
+        .position(|candidate| candidate.id == candidate_id)
+        .ok_or_else(|| blur_candidate_not_found(&candidate_id))?;
+    let candidate = manifest.blur_candidates.remove(index);
+    let region = normalize_blur_region(BlurRegion {
+        id: uuid::Uuid::new_v4().to_string(),
+        rect: candidate.rect,
+        start_ms: candidate.start_ms,
+        end_ms: candidate.end_ms,
+        mode: mode.unwrap_or_default(),
+    })?;
+    let before = EditSnapshot::Timeline(manifest.timeline.clone());
+    manifest.timeline.blur_regions.push(region.clone());
+    manifest
+        .timeline
+        .blur_regions
+        .sort_by_key(|region| region.start_ms);
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    record_project_edit(&state, &project_id, before)?;
+    Ok(region)
+}
+
+/// 忽略打码建议（误报），不影响时间线。
+#[tauri::command]
+pub async fn dismiss_blur_candidate(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    candidate_id: String,
+) -> Result<(), AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let before = manifest.blur_candidates.len();
+    manifest
+        .blur_candidates
+        .retain(|candidate| candidate.id != candidate_id);
+    if manifest.blur_candidates.len() == before {
+        return Err(blur_candidate_not_found(&candidate_id));
+    }
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)
+}
+
+fn text_annotation_not_found(annotation_id: &str) -> AppError {
+    AppError::new(
+        "TEXT_ANNOTATION_NOT_FOUND",
+        format!("text annotation not found: {annotation_id}"),
+        Some("请刷新项目后重试".to_string()),
+    )
+    .with_param("annotationId", annotation_id)
+}
+
+fn shape_annotation_not_found(shape_id: &str) -> AppError {
+    AppError::new(
+        "SHAPE_ANNOTATION_NOT_FOUND",
+        format!("shape annotation not found: {shape_id}"),
+        Some("请刷新项目后重试".to_string()),
+    )
+    .with_param("shapeId", shape_id)
+}
+
+fn blur_candidate_not_found(candidate_id: &str) -> AppError {
+    AppError::new(
+        "BLUR_CANDIDATE_NOT_FOUND",
+        format!("blur candidate not found: {candidate_id}"),
+        Some("请刷新项目后重试".to_string()),
+    )
+    .with_param("candidateId", candidate_id)
+}
+
+#[tauri::command]
+pub async fn update_camera_motion(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    patch: CameraMotionPatch,
+) -> Result<(), AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let before = EditSnapshot::CameraMotion(manifest.camera_motion.clone());
+    if let Some(enabled) = patch.enabled {
+        manifest.camera_motion.enabled = enabled;
+    }
+    if let Some(intensity) = patch.intensity {
+        manifest.camera_motion.intensity = intensity;
+    }
+    if let Some(smoothing) = patch.smoothing {
+        manifest.camera_motion.smoothing = smoothing.clamp(0.0, 1.0);
+    }
+    if let Some(max_zoom) = patch.max_zoom {
+        manifest.camera_motion.max_zoom = max_zoom.clamp(1.0, 2.0);
+    }
+    if let Some(idle_threshold_ms) = patch.idle_threshold_ms {
+        manifest.camera_motion.idle_threshold_ms = idle_threshold_ms.clamp(120, 900);
+    }
+    if let Some(zoom_on_click) = patch.zoom_on_click {
+        manifest.camera_motion.zoom_on_click = zoom_on_click;
+    }
+    if let Some(click_hold_ms) = patch.click_hold_ms {
+        manifest.camera_motion.click_hold_ms = click_hold_ms.clamp(200, 5000);
+    }
+    if let Some(model) = patch.model {
+        manifest.camera_motion.model = model;
+    }
+    if let Some(easing) = patch.easing {
+        manifest.camera_motion.easing = easing;
+    }
+    if let Some(focus_mode) = patch.focus_mode {
+        manifest.camera_motion.focus_mode = focus_mode;
+    }
+    if let Some(mode) = patch.mode {
+        manifest.camera_motion.mode = mode;
+    }
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    record_project_edit(&state, &project_id, before)
+}
+
+/// 用户编辑保存后写入一份清单修订，并记入撤销历史。
+fn record_project_edit(
+    state: &RuntimeState,
+    project_id: &str,
+    before: EditSnapshot,
+) -> Result<(), AppError> {
+    if let Err(error) = write_manifest_revision(&state.project_root, project_id) {
+        tracing::warn!("failed to record manifest revision: {}", error.message);
+    }
+    let mut history = load_edit_history(&state.project_root, project_id);
+    record_edit(&mut history, before);
+    save_edit_history(&state.project_root, project_id, &history)
+}
+
+/// 撤销最近一次时间线或镜头参数编辑。
+#[tauri::command]
+pub async fn undo_edit(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<EditHistoryStatus, AppError> {
+    step_edit_history(&state, &project_id, apply_undo)
+}
+
+/// 重做最近一次撤销的编辑。
+#[tauri::command]
+pub async fn redo_edit(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<EditHistoryStatus, AppError> {
+    step_edit_history(&state, &project_id, apply_redo)
+}
+
+#[tauri::command]
+pub async fn get_edit_history_status(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<EditHistoryStatus, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    Ok(history_status(&load_edit_history(
+        &state.project_root,
+        &project_id,
+    )))
+}
+
+fn step_edit_history(
+    state: &RuntimeState,
+    project_id: &str,
+    step: fn(&mut EditHistory, &mut ProjectManifest) -> bool,
+) -> Result<EditHistoryStatus, AppError> {
+    ensure_valid_project_id(project_id)?;
+    let mut manifest = load_manifest(&state.project_root, project_id)?;
+    let mut history = load_edit_history(&state.project_root, project_id);
+    if !step(&mut history, &mut manifest) {
+        return Err(AppError::new(
+            "EDIT_HISTORY_EMPTY",
+            "no edit to undo or redo",
+            None,
+        ));
+    }
+    save_manifest(&state.project_root, project_id, &manifest)?;
+    save_edit_history(&state.project_root, project_id, &history)?;
+    Ok(history_status(&history))
+}
+
+#[tauri::command]
+pub async fn update_scene_style(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    patch: SceneStylePatch,
+) -> Result<(), AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    if let Some(background) = patch.background {
+        validate_scene_background(&background)?;
+        manifest.scene.background = background;
+    }
+    if let Some(enabled) = patch.enabled {
+        manifest.scene.enabled = enabled;
+    }
+    if let Some(corner_radius) = patch.corner_radius {
+        manifest.scene.corner_radius = corner_radius.min(96);
+    }
+    if let Some(padding) = patch.padding {
+        manifest.scene.padding = padding.min(240);
+    }
+    if let Some(shadow) = patch.shadow {
+        manifest.scene.shadow = shadow.clamp(0.0, 1.0);
+    }
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)
+}
+
+/// 分析录制音轨中的静音段并写入时间轴，供导出时自动剪除。
+#[tauri::command]
+pub async fn detect_silent_ranges(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<Vec<TimeRange>, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let input_path = manifest
+        .artifacts
+        .raw_recording_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::new(
+                "PROJECT_ASSET_MISSING",
+                "recording asset file not found",
+                Some("请先完成录制后再分析静音".to_string()),
+            )
+        })?;
+    let ranges = tauri::async_runtime::spawn_blocking(move || {
+        let duration_ms = probe_media(&input_path)?.container_duration_ms;
+        detect_silence(
+            &input_path,
+            SILENCE_NOISE_DB,
+            SILENCE_MIN_DURATION_MS,
+            duration_ms,
+        )
+    })
+    .await
+    .map_err(|error| AppError::new("SILENCE_DETECT_FAILED", error.to_string(), None))??;
+
+    // 分析期间清单可能已被其他编辑修改，重新读取后只更新静音区间。
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    manifest.timeline.silent_ranges = ranges.clone();
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    Ok(ranges)
+}
+
+/// 根据前台应用切换与文字稿话题变化生成章节建议，替换之前未确认的建议，可撤销。
+/// 返回更新后的全部章节标记。
+#[tauri::command]
+pub async fn suggest_chapters(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<Vec<TimelineMarker>, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let app_track = manifest
+        .artifacts
+        .app_track_path
+        .as_deref()
+        .map(|path| load_app_track(std::path::Path::new(path)))
+        .unwrap_or_default();
+    let cues = load_transcript(&manifest.export);
+    let before = EditSnapshot::Timeline(manifest.timeline.clone());
+    let timeline = &mut manifest.timeline;
+    timeline.markers.retain(|marker| marker.accepted);
+    let accepted_ms = timeline
+        .markers
+        .iter()
+        .map(|marker| marker.t_ms)
+        .collect::<Vec<_>>();
+    let suggestions = suggest_chapter_markers(
+        &app_track,
+        &cues,
+        timeline.trim_start_ms,
+        timeline.trim_end_ms,
+        &accepted_ms,
+    );
+    timeline
+        .markers
+        .extend(suggestions.into_iter().map(|suggestion| TimelineMarker {
+            id: uuid::Uuid::new_v4().to_string(),
+            t_ms: suggestion.t_ms,
+            title: suggestion.title,
+            source: suggestion.source,
+            accepted: false,
+        }));
+    timeline.markers.sort_by_key(|marker| marker.t_ms);
+    let markers = timeline.markers.clone();
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    record_project_edit(&state, &project_id, before)?;
+    Ok(markers)
 }
 
+/// 为时间线胶片条均匀截取缩略图；原始录制未变化且数量一致时直接返回缓存。
 #[tauri::command]
-pub async fn load_project(
+pub async fn generate_timeline_thumbnails(
     state: State<'_, RuntimeState>,
     project_id: String,
-) -> Result<ProjectManifest, AppError> {
+    count: u32,
+) -> Result<Vec<TimelineThumbnail>, AppError> {
     ensure_valid_project_id(&project_id)?;
-    load_manifest(&state.project_root, &project_id)
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let input_path = manifest
+        .artifacts
+        .raw_recording_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::new(
+                "PROJECT_ASSET_MISSING",
+                "recording asset file not found",
+                Some("请先完成录制后再生成缩略图".to_string()),
+            )
+        })?;
+    let count = count.clamp(1, MAX_THUMBNAILS);
+    let thumbs_dir = timeline_thumbnails_dir(&state.project_root, &project_id);
+    tauri::async_runtime::spawn_blocking(move || {
+        let index_path = thumbs_dir.join("index.json");
+        let source = source_fingerprint(&input_path)?;
+        let key = (count, THUMBNAIL_WIDTH);
+        if let Some(cached) = load_cached::<_, Vec<TimelineThumbnail>>(&index_path, source, &key) {
+            if cached
+                .iter()
+                .all(|thumb| std::path::Path::new(&thumb.path).exists())
+            {
+                return Ok(cached);
+            }
+        }
+
+        // 录制或数量变化后旧缩略图全部作废，整目录重建避免残留多余帧。
+        let _ = std::fs::remove_dir_all(&thumbs_dir);
+        std::fs::create_dir_all(&thumbs_dir).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to create thumbs dir: {error}"),
+                Some("检查路径权限".to_string()),
+            )
+        })?;
+        let duration_ms = probe_media(&input_path)?.container_duration_ms;
+        let times_ms = thumbnail_times_ms(duration_ms, count);
+        let paths = extract_thumbnails(&input_path, &thumbs_dir, &times_ms, THUMBNAIL_WIDTH)?;
+        let thumbnails = times_ms
+            .into_iter()
+            .zip(paths)
+            .map(|(t_ms, path)| TimelineThumbnail {
+                t_ms,
+                path: path.to_string_lossy().to_string(),
+            })
+            .collect::<Vec<_>>();
+        save_cached(&index_path, source, &key, &thumbnails)?;
+        Ok(thumbnails)
+    })
+    .await
+    .map_err(|error| AppError::new("THUMBNAIL_FAILED", error.to_string(), None))?
 }
 
+/// 截取原始录制在 `t_ms` 处的一帧 PNG，返回文件路径。
+/// 未指定 `output` 时写入 renders/stills/，供编辑器预览播放头画面或直接作为截图使用。
 #[tauri::command]
-pub async fn list_projects(
+pub async fn extract_frame(
     state: State<'_, RuntimeState>,
-) -> Result<Vec<ProjectListItem>, AppError> {
-    let entries = std::fs::read_dir(&state.project_root).map_err(|error| {
-        AppError::new(
-            "PROJECT_LIST_READ_FAIL",
-            format!("failed to read project root: {error}"),
-            Some("请检查项目目录是否可读".to_string()),
-        )
-    })?;
-    let mut projects = Vec::new();
-    for entry in entries {
-        let Ok(entry) = entry else {
-            continue;
-        };
-        let Ok(file_type) = entry.file_type() else {
-            continue;
-        };
-        if !file_type.is_dir() {
-            continue;
-        }
-        let project_id = entry.file_name().to_string_lossy().to_string();
-        if project_id.trim().is_empty() {
-            continue;
+    project_id: String,
+    t_ms: u64,
+    output: Option<String>,
+) -> Result<String, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let input_path = manifest
+        .artifacts
+        .raw_recording_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::new(
+                "PROJECT_ASSET_MISSING",
+                "recording asset file not found",
+                Some("请先完成录制后再截取画面".to_string()),
+            )
+        })?;
+    let output_path = match output.filter(|path| !path.trim().is_empty()) {
+        Some(path) => {
+            let path = std::path::PathBuf::from(path);
+            let is_png = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+            if !is_png {
+                return Err(AppError::new(
+                    "FRAME_OUTPUT_INVALID",
+                    format!("frame output must be a .png file: {}", path.display()),
+                    Some("请选择 .png 文件作为保存路径".to_string()),
+                ));
+            }
+            path
         }
-        let manifest = match load_manifest(&state.project_root, &project_id) {
-            Ok(manifest) => manifest,
-            Err(_) => continue,
-        };
-        let duration_ms = manifest
-            .timeline
-            .trim_end_ms
-            .saturating_sub(manifest.timeline.trim_start_ms);
-        projects.push(ProjectListItem {
-            project_id,
-            title: manifest.title,
-            created_at: manifest.created_at,
-            updated_at: manifest.updated_at,
-            status: manifest.status,
-            duration_ms,
-            has_export: manifest.artifacts.last_export_path.is_some(),
-            export_path: manifest.artifacts.last_export_path,
-            raw_path: manifest.artifacts.raw_recording_path,
-        });
+        None => frame_still_path(&state.project_root, &project_id, t_ms),
+    };
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to create frame dir: {error}"),
+                Some("检查路径权限".to_string()),
+            )
+        })?;
     }
-    projects.sort_by(|left, right| right.updated_at.cmp(&left.updated_at));
-    Ok(projects)
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let duration_ms = probe_media(&input_path)?.container_duration_ms;
+        if duration_ms > 0 && t_ms >= duration_ms {
+            return Err(AppError::new(
+                "FRAME_OUT_OF_RANGE",
+                format!("t_ms {t_ms} exceeds recording duration {duration_ms}"),
+                Some("请选择录制时长以内的时间点".to_string()),
+            ));
+        }
+        extract_frame_png(&input_path, t_ms, &output_path)?;
+        Ok(output_path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|error| AppError::new("FRAME_EXTRACT_FAILED", error.to_string(), None))?
 }
 
+/// 返回录制音轨的峰值波形，结果缓存在 assets/waveform.json，原始录制变化后重建。
 #[tauri::command]
-pub async fn update_project_title(
+pub async fn get_audio_waveform(
     state: State<'_, RuntimeState>,
     project_id: String,
-    title: String,
-) -> Result<(), AppError> {
+    samples_per_second: u32,
+) -> Result<AudioWaveform, AppError> {
     ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let input_path = manifest
+        .artifacts
+        .raw_recording_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::new(
+                "PROJECT_ASSET_MISSING",
+                "recording asset file not found",
+                Some("请先完成录制后再查看波形".to_string()),
+            )
+        })?;
+    let samples_per_second = samples_per_second.clamp(1, MAX_WAVEFORM_SAMPLES_PER_SECOND);
+    let cache_path = audio_waveform_path(&state.project_root, &project_id);
+    let source = source_fingerprint(&input_path)?;
+    if let Some(peaks) = load_cached::<_, Vec<f32>>(&cache_path, source, &samples_per_second) {
+        return Ok(AudioWaveform {
+            samples_per_second,
+            peaks,
+        });
+    }
+
+    let peaks = tauri::async_runtime::spawn_blocking(move || {
+        extract_waveform_peaks(&input_path, samples_per_second)
+    })
+    .await
+    .map_err(|error| AppError::new("WAVEFORM_FAILED", error.to_string(), None))??;
+    save_cached(&cache_path, source, &samples_per_second, &peaks)?;
+
     let mut manifest = load_manifest(&state.project_root, &project_id)?;
-    let next_title = title.trim().to_string();
-    manifest.title = if next_title.is_empty() {
-        None
-    } else {
-        Some(next_title)
-    };
-    manifest.updated_at = Utc::now();
-    save_manifest(&state.project_root, &project_id, &manifest)
+    let cache_path = cache_path.to_string_lossy().to_string();
+    if manifest.artifacts.waveform_path.as_deref() != Some(cache_path.as_str()) {
+        manifest.artifacts.waveform_path = Some(cache_path);
+        manifest.updated_at = Utc::now();
+        save_manifest(&state.project_root, &project_id, &manifest)?;
+    }
+    Ok(AudioWaveform {
+        samples_per_second,
+        peaks,
+    })
 }
 
 #[tauri::command]
-pub async fn delete_project(
+pub async fn update_keystroke_overlay(
     state: State<'_, RuntimeState>,
     project_id: String,
+    patch: KeystrokeOverlayPatch,
 ) -> Result<(), AppError> {
     ensure_valid_project_id(&project_id)?;
-    {
-        let sessions = state.recording_sessions.lock().map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording sessions",
-                None,
-            )
-        })?;
-        if sessions
-            .values()
-            .any(|session| session.project_id == project_id)
-        {
-            return Err(AppError::new(
-                "PROJECT_BUSY",
-                "项目正在录制中，无法删除",
-                Some("请先停止录制再删除项目".to_string()),
-            ));
-        }
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let overlay = &mut manifest.keystroke_overlay;
+    if let Some(enabled) = patch.enabled {
+        overlay.enabled = enabled;
     }
-    {
-        let tasks = state
-            .export_tasks
-            .lock()
-            .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock export tasks", None))?;
-        if tasks.values().any(|task| {
-            task.project_id == project_id
-                && matches!(
-                    task.state,
-                    crate::domain::state_machine::ExportState::Queued
-                        | crate::domain::state_machine::ExportState::Running
-                        | crate::domain::state_machine::ExportState::Fallback
-                )
-        }) {
-            return Err(AppError::new(
-                "PROJECT_BUSY",
-                "项目存在进行中的导出任务，无法删除",
-                Some("请等待导出完成后再删除项目".to_string()),
-            ));
-        }
+    if let Some(position) = patch.position {
+        overlay.position = position;
     }
-    if let Ok(mut tasks) = state.export_tasks.lock() {
-        tasks.retain(|_, task| task.project_id != project_id);
+    if let Some(font_size) = patch.font_size {
+        overlay.font_size = font_size.clamp(16, 96);
     }
-    let dir = project_dir(&state.project_root, &project_id);
-    if !dir.exists() {
-        return Ok(());
+    if let Some(opacity) = patch.opacity {
+        overlay.opacity = opacity.clamp(0.0, 1.0);
     }
-    std::fs::remove_dir_all(&dir).map_err(|error| {
-        AppError::new(
-            "PROJECT_DELETE_FAIL",
-            format!("failed to delete project {project_id}: {error}"),
-            Some("请关闭占用该项目文件的程序后重试".to_string()),
-        )
-    })
+    if let Some(hold_ms) = patch.hold_ms {
+        overlay.hold_ms = hold_ms.clamp(300, 5000);
+    }
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)
 }
 
+/// 更新导出混音；各音量限制在 0..2，背景音乐需为已存在的本地文件。
 #[tauri::command]
-pub async fn update_timeline(
+pub async fn update_audio_mix(
     state: State<'_, RuntimeState>,
     project_id: String,
-    patch: TimelinePatch,
+    patch: AudioMixPatch,
 ) -> Result<(), AppError> {
     ensure_valid_project_id(&project_id)?;
     let mut manifest = load_manifest(&state.project_root, &project_id)?;
-    apply_timeline_patch(&mut manifest, patch);
-    if manifest.timeline.trim_end_ms > 0
-        && manifest.timeline.trim_end_ms < manifest.timeline.trim_start_ms
-    {
-        return Err(AppError::new(
-            "INVALID_TIMELINE",
-            "trimEndMs must be greater than trimStartMs",
-            Some("请调整裁剪区间".to_string()),
-        ));
+    let mix = &mut manifest.audio_mix;
+    if let Some(master_volume) = patch.master_volume {
+        mix.master_volume = master_volume.clamp(0.0, 2.0);
+    }
+    if let Some(mic_volume) = patch.mic_volume {
+        mix.mic_volume = mic_volume.clamp(0.0, 2.0);
+    }
+    if let Some(system_volume) = patch.system_volume {
+        mix.system_volume = system_volume.clamp(0.0, 2.0);
+    }
+    if let Some(music_volume) = patch.music_volume {
+        mix.music_volume = music_volume.clamp(0.0, 2.0);
+    }
+    if let Some(music_path) = patch.music_path {
+        let music_path = music_path.trim();
+        if !music_path.is_empty() && !std::path::Path::new(music_path).is_file() {
+            return Err(AppError::new(
+                "MUSIC_FILE_NOT_FOUND",
+                format!("music file not found: {music_path}"),
+                Some("请确认背景音乐文件路径".to_string()),
+            )
+            .with_param("path", music_path));
+        }
+        mix.music_path = (!music_path.is_empty()).then(|| music_path.to_string());
     }
+    manifest.updated_at = Utc::now();
     save_manifest(&state.project_root, &project_id, &manifest)
 }
 
+/// 更新导出调色；参数限制在画面不至于失真的范围内，LUT 需为已存在的 .cube 文件。
 #[tauri::command]
-pub async fn update_camera_motion(
+pub async fn update_color_grade(
     state: State<'_, RuntimeState>,
     project_id: String,
-    patch: CameraMotionPatch,
+    patch: ColorGradePatch,
 ) -> Result<(), AppError> {
     ensure_valid_project_id(&project_id)?;
     let mut manifest = load_manifest(&state.project_root, &project_id)?;
-    if let Some(enabled) = patch.enabled {
-        manifest.camera_motion.enabled = enabled;
+    let grade = &mut manifest.color_grade;
+    if let Some(brightness) = patch.brightness {
+        grade.brightness = brightness.clamp(-0.5, 0.5);
     }
-    if let Some(intensity) = patch.intensity {
-        manifest.camera_motion.intensity = intensity;
+    if let Some(contrast) = patch.contrast {
+        grade.contrast = contrast.clamp(0.5, 2.0);
     }
-    if let Some(smoothing) = patch.smoothing {
-        manifest.camera_motion.smoothing = smoothing.clamp(0.0, 1.0);
+    if let Some(saturation) = patch.saturation {
+        grade.saturation = saturation.clamp(0.0, 2.0);
     }
-    if let Some(max_zoom) = patch.max_zoom {
-        manifest.camera_motion.max_zoom = max_zoom.clamp(1.0, 2.0);
+    if let Some(gamma) = patch.gamma {
+        grade.gamma = gamma.clamp(0.5, 2.0);
     }
-    if let Some(idle_threshold_ms) = patch.idle_threshold_ms {
-        manifest.camera_motion.idle_threshold_ms = idle_threshold_ms.clamp(120, 900);
+    if let Some(lut_path) = patch.lut_path {
+        let lut_path = lut_path.trim();
+        if !lut_path.is_empty() {
+            let path = std::path::Path::new(lut_path);
+            let is_cube = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("cube"));
+            if !is_cube || !path.is_file() {
+                return Err(AppError::new(
+                    "LUT_FILE_INVALID",
+                    format!("LUT file is missing or not a .cube file: {lut_path}"),
+                    Some("请选择已存在的 .cube LUT 文件".to_string()),
+                )
+                .with_param("path", lut_path));
+            }
+        }
+        grade.lut_path = (!lut_path.is_empty()).then(|| lut_path.to_string());
     }
     manifest.updated_at = Utc::now();
     save_manifest(&state.project_root, &project_id, &manifest)
 }
 
+fn validate_scene_background(background: &SceneBackground) -> Result<(), AppError> {
+    let invalid_color = |color: &str| {
+        AppError::new(
+            "INVALID_SCENE_COLOR",
+            format!("invalid scene color: {color}"),
+            Some("请使用 #RRGGBB 格式的颜色".to_string()),
+        )
+    };
+    match background {
+        SceneBackground::Color { color } => {
+            parse_hex_color(color).ok_or_else(|| invalid_color(color))?;
+        }
+        SceneBackground::Gradient { from, to } => {
+            parse_hex_color(from).ok_or_else(|| invalid_color(from))?;
+            parse_hex_color(to).ok_or_else(|| invalid_color(to))?;
+        }
+        SceneBackground::Wallpaper { path } => {
+            if !std::path::Path::new(path).is_file() {
+                return Err(AppError::new(
+                    "SCENE_WALLPAPER_MISSING",
+                    format!("wallpaper not found: {path}"),
+                    Some("请重新选择壁纸图片".to_string()),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CameraMotionQuality {
     pub transition_latency_ms: u64,
     pub idle_jitter_ratio: f32,
+    /// 抽样后的镜头路径，前端用于在时间轴上叠加绘制。
+    pub path: Vec<MotionPathPoint>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MotionPathPoint {
+    pub t_ms: u64,
+    pub x: f32,
+    pub y: f32,
+    pub zoom: f32,
 }
 
+const MAX_MOTION_PATH_POINTS: usize = 240;
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QualityGateStatus {
     pub passed: bool,
     pub reasons: Vec<String>,
+    pub black_ranges: Vec<TimeRange>,
+    pub frozen_ranges: Vec<TimeRange>,
+    pub silent_ranges: Vec<TimeRange>,
 }
 
 #[tauri::command]
@@ -236,14 +1721,17 @@ pub async fn evaluate_camera_motion(
     ensure_valid_project_id(&project_id)?;
     let manifest = load_manifest(&state.project_root, &project_id)?;
     let profile = override_profile.unwrap_or(manifest.camera_motion);
-    let cursor_path = manifest.artifacts.cursor_track_path.ok_or_else(|| {
-        AppError::new(
-            "CURSOR_TRACK_MISSING",
-            "cursor track path missing in project",
-            Some("请先完成录制后再评估镜头运动".to_string()),
-        )
-    })?;
-    let raw = std::fs::read_to_string(&cursor_path).map_err(|error| {
+    let cursor_path = manifest
+        .artifacts
+        .effective_cursor_track_path()
+        .ok_or_else(|| {
+            AppError::new(
+                "CURSOR_TRACK_MISSING",
+                "cursor track path missing in project",
+                Some("请先完成录制后再评估镜头运动".to_string()),
+            )
+        })?;
+    let raw = std::fs::read_to_string(cursor_path).map_err(|error| {
         AppError::new(
             "CURSOR_TRACK_READ_FAIL",
             format!("failed to read cursor track: {error}"),
@@ -269,12 +1757,114 @@ pub async fn evaluate_camera_motion(
         .collect::<Vec<_>>();
     let path = compute_motion_path(&samples, &profile);
     let metrics = evaluate_metrics(&samples, &path);
+    let path = downsample_path(&samples, &path, MAX_MOTION_PATH_POINTS)
+        .into_iter()
+        .map(|(t_ms, point)| MotionPathPoint {
+            t_ms,
+            x: point.x,
+            y: point.y,
+            zoom: point.zoom,
+        })
+        .collect();
     Ok(CameraMotionQuality {
         transition_latency_ms: metrics.transition_latency_ms,
         idle_jitter_ratio: metrics.idle_jitter_ratio,
+        path,
+    })
+}
+
+/// 对原始光标轨迹做后处理并写入 cursor_track_refined.json，导出时优先使用。
+#[tauri::command]
+pub async fn refine_cursor_track(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    options: Option<CursorRefineOptions>,
+) -> Result<ProjectManifest, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let source = manifest
+        .artifacts
+        .cursor_track_path
+        .clone()
+        .ok_or_else(|| {
+            AppError::new(
+                "CURSOR_TRACK_MISSING",
+                "cursor track path missing in project",
+                Some("请先完成录制后再处理光标轨迹".to_string()),
+            )
+        })?;
+    let points = load_cursor_track_points(std::path::Path::new(&source))?;
+    let refined = refine_track(&points, &options.unwrap_or_default());
+    let refined_path = refined_cursor_track_path(&state.project_root, &project_id);
+    write_cursor_track_points(&refined_path, &refined)?;
+    manifest.artifacts.refined_cursor_track_path = Some(refined_path.to_string_lossy().to_string());
+    manifest.artifacts.edited_cursor_track_path = None;
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    Ok(manifest)
+}
+
+#[tauri::command]
+pub async fn get_cursor_track(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    range: Option<TimeRange>,
+) -> Result<CursorTrackSnapshot, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let path = effective_cursor_track(&manifest)?;
+    let points = load_cursor_track_points(std::path::Path::new(path))?
+        .into_iter()
+        .filter(|point| {
+            range
+                .map(|range| point.t_ms >= range.start_ms && point.t_ms <= range.end_ms)
+                .unwrap_or(true)
+        })
+        .collect();
+    Ok(CursorTrackSnapshot {
+        revision: manifest.artifacts.cursor_track_revision,
+        points,
+    })
+}
+
+/// 编辑结果写入新的修订文件，旧修订保留在 assets 目录中。
+#[tauri::command]
+pub async fn patch_cursor_track(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    edits: Vec<CursorTrackEdit>,
+) -> Result<CursorTrackSnapshot, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let path = effective_cursor_track(&manifest)?;
+    let points = load_cursor_track_points(std::path::Path::new(path))?;
+    let edited = apply_track_edits(&points, &edits);
+    let revision = manifest.artifacts.cursor_track_revision + 1;
+    let revision_path = cursor_track_revision_path(&state.project_root, &project_id, revision);
+    write_cursor_track_points(&revision_path, &edited)?;
+    manifest.artifacts.edited_cursor_track_path = Some(revision_path.to_string_lossy().to_string());
+    manifest.artifacts.cursor_track_revision = revision;
+    manifest.updated_at = Utc::now();
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    Ok(CursorTrackSnapshot {
+        revision,
+        points: edited,
     })
 }
 
+fn effective_cursor_track(manifest: &ProjectManifest) -> Result<&str, AppError> {
+    manifest
+        .artifacts
+        .effective_cursor_track_path()
+        .ok_or_else(|| {
+            AppError::new(
+                "CURSOR_TRACK_MISSING",
+                "cursor track path missing in project",
+                Some("请先完成录制后再编辑光标轨迹".to_string()),
+            )
+        })
+}
+
 #[tauri::command]
 pub async fn validate_quality_gate(
     state: State<'_, RuntimeState>,
@@ -306,16 +1896,202 @@ pub async fn validate_quality_gate(
         reasons.push("缺少导出日志，无法校验掉帧率指标".to_string());
     }
 
+    let recording_fps = manifest.recording.frame_rate;
+    let export_fps = manifest.export.fps;
     let result = validate_mvp_quality(
         manifest.quality.av_offset_ms,
-        manifest.quality.avg_drop_rate,
-        manifest.quality.peak_drop_rate,
+        export_drop_rate(manifest.quality.avg_drop_rate, recording_fps, export_fps),
+        export_drop_rate(manifest.quality.peak_drop_rate, recording_fps, export_fps),
     );
     reasons.extend(result.reasons);
+    let content = validate_content_ranges(
+        &manifest.quality.black_ranges,
+        &manifest.quality.frozen_ranges,
+        &manifest.quality.silent_ranges,
+        manifest.quality.analyzed_duration_ms,
+        manifest.recording.microphone_device_id.is_some(),
+    );
+    reasons.extend(content.reasons);
     Ok(QualityGateStatus {
-        passed: reasons.is_empty() && result.passed,
+        passed: reasons.is_empty() && result.passed && content.passed,
         reasons,
+        black_ranges: manifest.quality.black_ranges,
+        frozen_ranges: manifest.quality.frozen_ranges,
+        silent_ranges: manifest.quality.silent_ranges,
+    })
+}
+
+/// 修复中断的录制：流复制重封装原始文件，按实际时长更新清单并清除恢复标记。
+#[tauri::command]
+pub async fn repair_recording(
+    app: AppHandle,
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<ProjectManifest, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    ensure_no_active_recording(&state, &project_id).await?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let raw_path = raw_recording_path(&state.project_root, &project_id);
+    // 录制中断时 MP4 尚未生成，从 MKV 修复。
+    let source_path = Some(raw_path.clone())
+        .filter(|path| path.exists())
+        .or_else(|| {
+            Some(recording_capture_path(&state.project_root, &project_id))
+                .filter(|path| path.exists())
+        })
+        .ok_or_else(|| {
+            AppError::new(
+                "RAW_RECORDING_MISSING",
+                "原始录制文件不存在",
+                Some("该项目没有可修复的录制文件".to_string()),
+            )
+        })?;
+    let backup_path = partial_recording_backup_path(&state.project_root, &project_id);
+    let duration_ms = tauri::async_runtime::spawn_blocking({
+        let raw_path = raw_path.clone();
+        move || {
+            let repaired_path = raw_path.with_extension("repaired.mp4");
+            remux_recording(&source_path, &repaired_path)?;
+            let duration_ms = match probe_media(&repaired_path) {
+                Ok(summary) if summary.container_duration_ms > 0 => summary.container_duration_ms,
+                _ => {
+                    let _ = std::fs::remove_file(&repaired_path);
+                    return Err(AppError::new(
+                        "RECORDING_UNRECOVERABLE",
+                        "repaired recording has no playable frames",
+                        Some("录制文件缺少索引或已损坏，无法修复".to_string()),
+                    ));
+                }
+            };
+            let io_error = |error: std::io::Error| {
+                AppError::new(
+                    "IO_ERROR",
+                    format!("failed to replace recording: {error}"),
+                    Some("确认磁盘空间和路径权限".to_string()),
+                )
+            };
+            let backup_path = match source_path.extension() {
+                Some(extension) => backup_path.with_extension(extension),
+                None => backup_path,
+            };
+            std::fs::rename(&source_path, &backup_path).map_err(io_error)?;
+            std::fs::rename(&repaired_path, &raw_path).map_err(io_error)?;
+            Ok(duration_ms)
+        }
+    })
+    .await
+    .map_err(|error| AppError::new("RECORDING_REPAIR_FAILED", error.to_string(), None))??;
+
+    finalize_recovered_manifest(&mut manifest, &raw_path, duration_ms);
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    clear_recovery_marker(&state.project_root, &project_id)?;
+    let _ = emit_event(
+        &app,
+        AppEvent::Recovery(RecoveryEvent {
+            project_id,
+            status: "repaired".to_string(),
+        }),
+    );
+    Ok(manifest)
+}
+
+/// 收尾标记为可恢复的项目：探测原始录制的真实时长，补齐光标轨迹，
+/// 更新裁剪区间并清除恢复标记。文件无法读取时提示先修复录制。
+#[tauri::command]
+pub async fn recover_project(
+    app: AppHandle,
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<ProjectManifest, AppError> {
+    ensure_valid_project_id(&project_id)?;
+    ensure_no_active_recording(&state, &project_id).await?;
+    let mut manifest = load_manifest(&state.project_root, &project_id)?;
+    let raw_path = raw_recording_path(&state.project_root, &project_id);
+    let cursor_path = manifest
+        .artifacts
+        .cursor_track_path
+        .as_deref()
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| cursor_track_path(&state.project_root, &project_id));
+    let capture_path = recording_capture_path(&state.project_root, &project_id);
+    let duration_ms = tauri::async_runtime::spawn_blocking({
+        let raw_path = raw_path.clone();
+        let cursor_path = cursor_path.clone();
+        move || {
+            if !raw_path.exists() && capture_path.exists() {
+                finalize_capture(&capture_path, &raw_path)?;
+            }
+            let duration_ms = probe_media(&raw_path)
+                .ok()
+                .map(|summary| summary.container_duration_ms)
+                .filter(|duration_ms| *duration_ms > 0)
+                .ok_or_else(|| {
+                    AppError::new(
+                        "RECORDING_NEEDS_REPAIR",
+                        "无法读取原始录制文件的时长",
+                        Some("请先执行“修复录制”后再恢复".to_string()),
+                    )
+                })?;
+            let frame = probe_input_dimensions(&raw_path).unwrap_or((1920, 1080));
+            let mut points = load_cursor_track_points(&cursor_path).unwrap_or_default();
+            extend_cursor_track(&mut points, duration_ms, frame);
+            write_cursor_track_points(&cursor_path, &points)?;
+            Ok::<_, AppError>(duration_ms)
+        }
     })
+    .await
+    .map_err(|error| AppError::new("RECOVERY_FAILED", error.to_string(), None))??;
+
+    finalize_recovered_manifest(&mut manifest, &raw_path, duration_ms);
+    manifest.artifacts.cursor_track_path = Some(cursor_path.to_string_lossy().to_string());
+    save_manifest(&state.project_root, &project_id, &manifest)?;
+    clear_recovery_marker(&state.project_root, &project_id)?;
+    let _ = emit_event(
+        &app,
+        AppEvent::Recovery(RecoveryEvent {
+            project_id,
+            status: "recovered".to_string(),
+        }),
+    );
+    Ok(manifest)
+}
+
+/// 按实际录制时长收尾：裁剪区间落在时长内，录制中状态转为待编辑。
+fn finalize_recovered_manifest(
+    manifest: &mut ProjectManifest,
+    raw_path: &std::path::Path,
+    duration_ms: u64,
+) {
+    let timeline = &mut manifest.timeline;
+    if timeline.trim_end_ms == 0 || timeline.trim_end_ms > duration_ms {
+        timeline.trim_end_ms = duration_ms;
+    }
+    if timeline.trim_start_ms >= timeline.trim_end_ms {
+        timeline.trim_start_ms = 0;
+    }
+    manifest.artifacts.raw_recording_path = Some(raw_path.to_string_lossy().to_string());
+    if matches!(manifest.status, ProjectStatus::Recording) {
+        manifest.status = ProjectStatus::ReadyToEdit;
+    }
+    manifest.updated_at = Utc::now();
+}
+
+async fn ensure_no_active_recording(
+    state: &RuntimeState,
+    project_id: &str,
+) -> Result<(), AppError> {
+    let sessions = state.recording_sessions.read().await;
+    if sessions
+        .values()
+        .any(|session| session.project_id == project_id)
+    {
+        return Err(AppError::new(
+            "PROJECT_BUSY",
+            "项目正在录制中",
+            Some("请先停止录制".to_string()),
+        ));
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -331,6 +2107,7 @@ fn ensure_valid_project_id(project_id: &str) -> Result<(), AppError> {
         || trimmed.contains('/')
         || trimmed.contains('\\')
         || trimmed.contains("..")
+        || trimmed.starts_with('.')
     {
         return Err(AppError::new(
             "INVALID_PROJECT_ID",
@@ -1,20 +1,50 @@
-use crate::core::capture::service::platform_capability;
+use crate::commands::export::start_export_with_preset;
+use crate::commands::settings::load_app_settings;
+use crate::core::capture::app_track::{
+    normalize_app_segments, poll_foreground_app, record_app_sample, write_app_track,
+};
+use crate::core::capture::display::{find_display_bounds, fullscreen_capture_bounds};
+use crate::core::capture::input::{
+    poll_pressed_keys, take_click, take_scroll_delta, write_key_track, KeyEvent, KeyStateTracker,
+    ScrollListener,
+};
+use crate::core::capture::metrics::compute_audio_level;
+use crate::core::capture::sampling::CursorSampler;
+use crate::core::capture::service::{
+    diff_audio_devices, platform_capability, probe_audio_devices, validate_frame_rate,
+};
+use crate::core::capture::window_follow::{
+    bounds_at, find_window_bounds, record_bounds_sample, WindowBoundsSample,
+};
+use crate::domain::events::AppEvent;
 use crate::domain::models::{
-    AppError, CaptureMode, ProjectStatus, RecordingProfile, RecordingStatusEvent,
+    AppError, AppFocusSegment, AudioLevelEvent, CaptureBounds, CaptureMode, CaptureTestReport,
+    DevicesChangedEvent, KeystrokeCaptureMode, PreviewFrameEvent, ProjectStatus, RecordingDevice,
+    RecordingProfile, RecordingStatusEvent, StopRecordingResult,
 };
 use crate::domain::state_machine::RecordingState;
-use crate::infra::ffmpeg::command::{ensure_ffmpeg_available, ffmpeg_bin};
+use crate::infra::do_not_disturb::{enable_do_not_disturb, restore_do_not_disturb};
+use crate::infra::events::emit_event;
+use crate::infra::ffmpeg::capture_test::record_capture_test;
+use crate::infra::ffmpeg::command::{ensure_ffmpeg_available, ffmpeg_bin, probe_input_dimensions};
+use crate::infra::ffmpeg::meter::{spawn_audio_meter_process, METER_WINDOW_BYTES};
+use crate::infra::ffmpeg::orphans::{clear_ffmpeg_pids, record_ffmpeg_pid};
+use crate::infra::ffmpeg::preview::JpegFrameSplitter;
 use crate::infra::ffmpeg::recording::{
     send_ffmpeg_stdin, spawn_recording_process, stop_ffmpeg_process,
 };
+use crate::infra::ffmpeg::repair::{finalize_capture, join_capture_parts};
+use crate::infra::hotkeys::HotkeyAction;
 use crate::infra::storage::project_store::{
-    clear_recovery_marker, create_project_manifest, cursor_track_path, ensure_project_dirs,
-    mark_recovery_marker, raw_recording_path, save_manifest,
+    app_track_path, clear_recovery_marker, create_project_manifest, cursor_track_path,
+    ensure_project_dirs, key_track_path, mark_recovery_marker, project_dir, raw_recording_path,
+    recording_capture_part_path, recording_capture_path, save_manifest,
 };
 use crate::state::{CursorTrackSample, RecordingProcess, RecordingSession, RuntimeState};
 use chrono::Utc;
-use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter, Manager, State};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 #[tauri::command]
@@ -24,6 +54,7 @@ pub async fn start_recording(
     mut profile: RecordingProfile,
 ) -> Result<String, AppError> {
     ensure_ffmpeg_available()?;
+    validate_frame_rate(profile.frame_rate)?;
     let capability = platform_capability();
     if !capability.supports_screen_capture {
         return Err(AppError::new(
@@ -32,30 +63,19 @@ pub async fn start_recording(
             Some("MVP 仅支持 Windows/macOS".to_string()),
         ));
     }
-    {
-        let sessions = state.recording_sessions.lock().map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording sessions",
-                None,
-            )
-        })?;
-        if sessions.values().any(|session| {
-            session.state == RecordingState::Recording || session.state == RecordingState::Paused
-        }) {
-            return Err(AppError::new(
-                "RECORDING_ALREADY_ACTIVE",
-                "已有进行中的录制会话，请先停止后再开始新录制",
-                Some("完成当前录制后再发起新的录制".to_string()),
-            ));
-        }
-    }
+    ensure_no_active_recording(&state).await?;
 
     let mut degrade_message = None;
     if profile.system_audio_enabled && !capability.supports_system_audio {
         profile.system_audio_enabled = false;
         degrade_message = capability.system_audio_degrade_message.clone();
     }
+    if profile.keystroke_capture != KeystrokeCaptureMode::Off
+        && !capability.supports_keystroke_capture
+    {
+        profile.keystroke_capture = KeystrokeCaptureMode::Off;
+        degrade_message = Some("当前平台暂不支持按键记录，已关闭按键采集".to_string());
+    }
     if matches!(
         profile.capture_mode,
         crate::domain::models::CaptureMode::Window
@@ -71,7 +91,8 @@ pub async fn start_recording(
 
     let session_id = Uuid::new_v4().to_string();
     let project_id = session_id.clone();
-    let output_path = raw_recording_path(&state.project_root, &project_id);
+    // 先写 MKV，ffmpeg 被强制结束时文件仍可读；停止后再重封装为 MP4。
+    let output_path = recording_capture_path(&state.project_root, &project_id);
     let cursor_path = cursor_track_path(&state.project_root, &project_id);
     ensure_project_dirs(&state.project_root, &project_id)?;
 
@@ -81,13 +102,71 @@ pub async fn start_recording(
     manifest.artifacts.cursor_track_path = Some(cursor_path.to_string_lossy().to_string());
     save_manifest(&state.project_root, &project_id, &manifest)?;
 
-    let spawn = spawn_recording_process(&ffmpeg_bin(), &profile, &output_path)?;
+    let display_bounds = match profile.capture_mode {
+        CaptureMode::Fullscreen => profile.display_id.as_deref().and_then(find_display_bounds),
+        CaptureMode::Window => None,
+    };
+    if profile.display_id.is_some()
+        && display_bounds.is_none()
+        && matches!(profile.capture_mode, CaptureMode::Fullscreen)
+    {
+        profile.display_id = None;
+        degrade_message = Some("未找到所选显示器，已改为录制全部屏幕".to_string());
+    }
+    // 启动时会短暂等待确认 ffmpeg 未立即退出，放到阻塞线程避免占用异步运行时。
+    let mut spawn = tauri::async_runtime::spawn_blocking({
+        let profile = profile.clone();
+        let output_path = output_path.clone();
+        move || {
+            spawn_recording_process(
+                &ffmpeg_bin(),
+                &profile,
+                display_bounds.as_ref(),
+                &output_path,
+            )
+        }
+    })
+    .await
+    .map_err(|error| AppError::new("RECORDING_START_FAIL", error.to_string(), None))??;
     if degrade_message.is_none() {
         degrade_message = spawn.degrade_message.clone();
     }
     mark_recovery_marker(&state.project_root, &project_id)?;
+    let project_path = project_dir(&state.project_root, &project_id);
+    // 录制进程确认启动后再开启勿扰，启动失败时不改动系统设置。
+    let do_not_disturb_restore = if profile.do_not_disturb {
+        match tauri::async_runtime::spawn_blocking(enable_do_not_disturb).await {
+            Ok(Ok(previous)) => previous,
+            Ok(Err(error)) => {
+                tracing::warn!("failed to enable do not disturb: {}", error.message);
+                degrade_message.get_or_insert_with(|| {
+                    "未能开启系统勿扰模式，通知可能会出现在录制画面中".to_string()
+                });
+                None
+            }
+            Err(error) => {
+                tracing::warn!("failed to enable do not disturb: {error}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Err(error) = record_ffmpeg_pid(&project_path, spawn.child.id()) {
+        tracing::warn!("failed to record recording pid: {}", error.message);
+    }
 
     let started_at = Utc::now();
+    if let Some(stdout) = spawn.child.stdout.take() {
+        spawn_preview_reader(app.clone(), session_id.clone(), started_at, stdout);
+    }
+    let window_bounds = match profile.capture_mode {
+        CaptureMode::Fullscreen => None,
+        CaptureMode::Window => profile
+            .window_target
+            .as_deref()
+            .and_then(find_window_bounds),
+    };
     let session = RecordingSession {
         session_id: session_id.clone(),
         project_id: project_id.clone(),
@@ -95,39 +174,69 @@ pub async fn start_recording(
         state: RecordingState::Recording,
         started_at,
         degrade_message: degrade_message.clone(),
+        capture_bounds: match profile.capture_mode {
+            CaptureMode::Fullscreen => display_bounds.or_else(fullscreen_capture_bounds),
+            CaptureMode::Window => window_bounds,
+        },
+        do_not_disturb_restore,
+        capture_parts: Vec::new(),
     };
 
     state
         .recording_sessions
-        .lock()
-        .map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording sessions",
-                None,
-            )
-        })?
+        .write()
+        .await
         .insert(session_id.clone(), session);
+    *state.last_recording_profile.lock().await = Some(profile.clone());
     state
         .recording_processes
         .lock()
-        .map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording processes",
-                None,
-            )
-        })?
+        .await
         .insert(session_id.clone(), RecordingProcess { child: spawn.child });
     state
         .cursor_tracks
         .lock()
-        .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock cursor tracks", None))?
+        .await
         .insert(session_id.clone(), Arc::new(Mutex::new(Vec::new())));
+    let keystroke_mode = profile.keystroke_capture;
+    if keystroke_mode != KeystrokeCaptureMode::Off {
+        state
+            .key_tracks
+            .lock()
+            .await
+            .insert(session_id.clone(), Arc::new(Mutex::new(Vec::new())));
+    }
+    state
+        .app_tracks
+        .lock()
+        .await
+        .insert(session_id.clone(), Arc::new(Mutex::new(Vec::new())));
+    if let Some(bounds) = window_bounds {
+        state.window_bounds_tracks.lock().await.insert(
+            session_id.clone(),
+            Arc::new(Mutex::new(vec![WindowBoundsSample { t_ms: 0, bounds }])),
+        );
+    }
+    match spawn_audio_meter_process(&ffmpeg_bin(), &profile) {
+        Ok(Some(mut child)) => {
+            let _ = record_ffmpeg_pid(&project_path, child.id());
+            if let Some(stdout) = child.stdout.take() {
+                spawn_audio_level_reader(app.clone(), session_id.clone(), started_at, stdout);
+            }
+            state
+                .audio_meters
+                .lock()
+                .await
+                .insert(session_id.clone(), RecordingProcess { child });
+        }
+        Ok(None) => {}
+        // 电平监测只是辅助能力，失败时不影响录制本身。
+        Err(error) => tracing::warn!("audio meter unavailable: {}", error.message),
+    }
 
-    app.emit(
-        "recording/status",
-        RecordingStatusEvent {
+    emit_event(
+        &app,
+        AppEvent::RecordingStatus(RecordingStatusEvent {
             session_id: session_id.clone(),
             status: "recording".to_string(),
             duration_ms: 0,
@@ -137,15 +246,77 @@ pub async fn start_recording(
             },
             detail: "录制已开始".to_string(),
             degrade_message: degrade_message.clone(),
-        },
-    )
-    .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))?;
+        }),
+    )?;
 
     schedule_recording_status_ticker(app.clone(), session_id.clone());
-    schedule_cursor_tracking_ticker(session_id.clone(), started_at, app.clone());
+    schedule_cursor_tracking_ticker(
+        session_id.clone(),
+        started_at,
+        CursorSampler::new(
+            profile.cursor_sample_interval_ms,
+            profile.adaptive_cursor_sampling,
+        ),
+        app.clone(),
+    );
+    if keystroke_mode != KeystrokeCaptureMode::Off {
+        schedule_key_tracking_ticker(session_id.clone(), started_at, keystroke_mode, app.clone());
+    }
+    schedule_app_tracking_ticker(session_id.clone(), started_at, app.clone());
+    schedule_device_watch_ticker(session_id.clone(), app.clone());
+    if let (Some(target), Some(_)) = (profile.window_target.clone(), window_bounds) {
+        schedule_window_follow_ticker(session_id.clone(), started_at, target, app.clone());
+    }
     Ok(session_id)
 }
 
+/// 用当前录制配置试录 3 秒并检查画面、帧率与音轨，在正式录制前发现权限、设备和编码器问题。
+#[tauri::command]
+pub async fn run_capture_test(
+    state: State<'_, RuntimeState>,
+    mut profile: RecordingProfile,
+) -> Result<CaptureTestReport, AppError> {
+    ensure_ffmpeg_available()?;
+    validate_frame_rate(profile.frame_rate)?;
+    // 采集设备同一时间只能被一个 ffmpeg 占用，试录会与正式录制互相抢占。
+    ensure_no_active_recording(&state).await?;
+    if profile.system_audio_enabled && !platform_capability().supports_system_audio {
+        profile.system_audio_enabled = false;
+    }
+    let display_bounds = match profile.capture_mode {
+        CaptureMode::Fullscreen => profile.display_id.as_deref().and_then(find_display_bounds),
+        CaptureMode::Window => None,
+    };
+    let output_path =
+        std::env::temp_dir().join(format!("focuslens-capture-test-{}.mkv", Uuid::new_v4()));
+    tauri::async_runtime::spawn_blocking(move || {
+        let report = record_capture_test(
+            &ffmpeg_bin(),
+            &profile,
+            display_bounds.as_ref(),
+            &output_path,
+        );
+        let _ = std::fs::remove_file(&output_path);
+        report
+    })
+    .await
+    .map_err(|error| AppError::new("RECORDING_START_FAIL", error.to_string(), None))
+}
+
+async fn ensure_no_active_recording(state: &RuntimeState) -> Result<(), AppError> {
+    let sessions = state.recording_sessions.read().await;
+    if sessions.values().any(|session| {
+        session.state == RecordingState::Recording || session.state == RecordingState::Paused
+    }) {
+        return Err(AppError::new(
+            "RECORDING_ALREADY_ACTIVE",
+            "已有进行中的录制会话，请先停止后再开始新录制",
+            Some("完成当前录制后再发起新的录制".to_string()),
+        ));
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn pause_recording(
     app: AppHandle,
@@ -153,13 +324,7 @@ pub async fn pause_recording(
     session_id: String,
 ) -> Result<(), AppError> {
     let (started_at, capture_mode, degrade_message) = {
-        let mut sessions = state.recording_sessions.lock().map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording sessions",
-                None,
-            )
-        })?;
+        let mut sessions = state.recording_sessions.write().await;
         let session = sessions.get_mut(&session_id).ok_or_else(|| {
             AppError::new(
                 "SESSION_NOT_FOUND",
@@ -182,13 +347,7 @@ pub async fn pause_recording(
         )
     };
 
-    let mut processes = state.recording_processes.lock().map_err(|_| {
-        AppError::new(
-            "STATE_LOCK_ERROR",
-            "failed to lock recording processes",
-            None,
-        )
-    })?;
+    let mut processes = state.recording_processes.lock().await;
     let process = processes.get_mut(&session_id).ok_or_else(|| {
         AppError::new(
             "SESSION_NOT_FOUND",
@@ -198,9 +357,9 @@ pub async fn pause_recording(
     })?;
     send_ffmpeg_stdin(&mut process.child, b"p\n")?;
 
-    app.emit(
-        "recording/status",
-        RecordingStatusEvent {
+    emit_event(
+        &app,
+        AppEvent::RecordingStatus(RecordingStatusEvent {
             session_id,
             status: "paused".to_string(),
             duration_ms: (Utc::now() - started_at).num_milliseconds().max(0) as u64,
@@ -210,9 +369,8 @@ pub async fn pause_recording(
             },
             detail: "录制已暂停".to_string(),
             degrade_message,
-        },
-    )
-    .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))?;
+        }),
+    )?;
 
     Ok(())
 }
@@ -224,13 +382,7 @@ pub async fn resume_recording(
     session_id: String,
 ) -> Result<(), AppError> {
     let (started_at, capture_mode, degrade_message) = {
-        let mut sessions = state.recording_sessions.lock().map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording sessions",
-                None,
-            )
-        })?;
+        let mut sessions = state.recording_sessions.write().await;
         let session = sessions.get_mut(&session_id).ok_or_else(|| {
             AppError::new(
                 "SESSION_NOT_FOUND",
@@ -253,13 +405,7 @@ pub async fn resume_recording(
         )
     };
 
-    let mut processes = state.recording_processes.lock().map_err(|_| {
-        AppError::new(
-            "STATE_LOCK_ERROR",
-            "failed to lock recording processes",
-            None,
-        )
-    })?;
+    let mut processes = state.recording_processes.lock().await;
     let process = processes.get_mut(&session_id).ok_or_else(|| {
         AppError::new(
             "SESSION_NOT_FOUND",
@@ -269,9 +415,9 @@ pub async fn resume_recording(
     })?;
     send_ffmpeg_stdin(&mut process.child, b"p\n")?;
 
-    app.emit(
-        "recording/status",
-        RecordingStatusEvent {
+    emit_event(
+        &app,
+        AppEvent::RecordingStatus(RecordingStatusEvent {
             session_id,
             status: "recording".to_string(),
             duration_ms: (Utc::now() - started_at).num_milliseconds().max(0) as u64,
@@ -281,9 +427,8 @@ pub async fn resume_recording(
             },
             detail: "录制已继续".to_string(),
             degrade_message,
-        },
-    )
-    .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))?;
+        }),
+    )?;
 
     Ok(())
 }
@@ -293,17 +438,11 @@ pub async fn stop_recording(
     app: AppHandle,
     state: State<'_, RuntimeState>,
     session_id: String,
-) -> Result<String, AppError> {
+) -> Result<StopRecordingResult, AppError> {
     let session = state
         .recording_sessions
-        .lock()
-        .map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording sessions",
-                None,
-            )
-        })?
+        .read()
+        .await
         .get(&session_id)
         .cloned()
         .ok_or_else(|| {
@@ -314,25 +453,65 @@ pub async fn stop_recording(
             )
         })?;
 
+    stop_audio_meter(&state, &session_id).await;
     {
-        let mut processes = state.recording_processes.lock().map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording processes",
-                None,
-            )
-        })?;
-        let process = processes.get_mut(&session_id).ok_or_else(|| {
-            AppError::new(
-                "SESSION_NOT_FOUND",
-                format!("recording process not found: {session_id}"),
-                None,
-            )
-        })?;
-        stop_ffmpeg_process(&mut process.child)?;
+        let process = state
+            .recording_processes
+            .lock()
+            .await
+            .remove(&session_id)
+            .ok_or_else(|| {
+                AppError::new(
+                    "SESSION_NOT_FOUND",
+                    format!("recording process not found: {session_id}"),
+                    None,
+                )
+            })?;
+        // 等待 ffmpeg 收尾最长数秒，在阻塞线程中进行；失败时放回进程表以便重试。
+        let (process, result) = tauri::async_runtime::spawn_blocking(move || {
+            let mut process = process;
+            let result = stop_ffmpeg_process(&mut process.child);
+            (process, result)
+        })
+        .await
+        .map_err(|error| AppError::new("RECORDING_STOP_FAIL", error.to_string(), None))?;
+        if let Err(error) = result {
+            state
+                .recording_processes
+                .lock()
+                .await
+                .insert(session_id.clone(), process);
+            return Err(error);
+        }
     }
 
-    let raw_path = raw_recording_path(&state.project_root, &session.project_id);
+    restore_session_do_not_disturb(&session);
+
+    let capture_path = recording_capture_path(&state.project_root, &session.project_id);
+    if !session.capture_parts.is_empty() {
+        let capture_path = capture_path.clone();
+        let parts = session.capture_parts.clone();
+        // 拼接失败时只保留麦克风断开前的首段，续录分段留在 assets 目录中可手工找回。
+        if let Ok(Err(error)) =
+            tauri::async_runtime::spawn_blocking(move || join_capture_parts(&capture_path, &parts))
+                .await
+        {
+            tracing::warn!("failed to join recording parts: {}", error.message);
+        }
+    }
+    let mp4_path = raw_recording_path(&state.project_root, &session.project_id);
+    // 重封装失败时保留 MKV 作为原始录制，ffmpeg 同样可以读取和导出。
+    let raw_path = tauri::async_runtime::spawn_blocking(move || {
+        match finalize_capture(&capture_path, &mp4_path) {
+            Ok(()) => mp4_path,
+            Err(error) => {
+                tracing::warn!("failed to remux recording to mp4: {}", error.message);
+                capture_path
+            }
+        }
+    })
+    .await
+    .map_err(|error| AppError::new("RECORDING_STOP_FAIL", error.to_string(), None))?;
     let raw_ok = std::fs::metadata(&raw_path)
         .map(|metadata| metadata.len() > 1024)
         .unwrap_or(false);
@@ -352,102 +531,314 @@ pub async fn stop_recording(
                 .to_string(),
         );
         let _ = save_manifest(&state.project_root, &session.project_id, &failed_manifest);
-        let _ = app.emit(
-            "recording/status",
-            RecordingStatusEvent {
+        let _ = emit_event(
+            &app,
+            AppEvent::RecordingStatus(RecordingStatusEvent {
                 session_id: session_id.clone(),
                 status: "error".to_string(),
                 duration_ms: 0,
                 source_label: "录制失败".to_string(),
                 detail: "录制输出文件缺失".to_string(),
                 degrade_message: session.degrade_message.clone(),
-            },
+            }),
         );
 
-        let _ = state
-            .recording_processes
-            .lock()
-            .map(|mut processes| processes.remove(&session_id));
-        let _ = state
-            .recording_sessions
-            .lock()
-            .map(|mut sessions| sessions.remove(&session_id));
-        let _ = state
-            .cursor_tracks
-            .lock()
-            .map(|mut tracks| tracks.remove(&session_id));
+        state.recording_processes.lock().await.remove(&session_id);
+        state.recording_sessions.write().await.remove(&session_id);
+        state.cursor_tracks.lock().await.remove(&session_id);
+        state.key_tracks.lock().await.remove(&session_id);
+        state.app_tracks.lock().await.remove(&session_id);
+        state.window_bounds_tracks.lock().await.remove(&session_id);
         return Err(error);
     }
 
     let duration_ms = (Utc::now() - session.started_at).num_milliseconds().max(0) as u64;
     let mut manifest = create_project_manifest(session.profile);
     manifest.status = ProjectStatus::ReadyToEdit;
+    manifest.recording_started_at = Some(session.started_at);
     manifest.timeline.trim_end_ms = duration_ms;
     manifest.artifacts.raw_recording_path = Some(raw_path.to_string_lossy().to_string());
     let cursor_path = cursor_track_path(&state.project_root, &session.project_id);
-    let cursor_samples = take_cursor_samples(&state, &session_id);
+    let frame = session
+        .capture_bounds
+        .and_then(|_| probe_input_dimensions(&raw_path));
+    let bounds_track = take_bounds_track(&state, &session_id, session.capture_bounds).await;
+    let cursor_samples = normalize_cursor_samples(
+        take_cursor_samples(&state, &session_id).await,
+        &bounds_track,
+        frame,
+    );
     write_cursor_track(&cursor_path, duration_ms, &cursor_samples)?;
     manifest.artifacts.cursor_track_path = Some(cursor_path.to_string_lossy().to_string());
+    if manifest.recording.keystroke_capture != KeystrokeCaptureMode::Off {
+        let key_path = key_track_path(&state.project_root, &session.project_id);
+        let key_events = take_key_events(&state, &session_id).await;
+        write_key_track(&key_path, duration_ms, &key_events)?;
+        manifest.artifacts.key_track_path = Some(key_path.to_string_lossy().to_string());
+    }
+    let app_path = app_track_path(&state.project_root, &session.project_id);
+    let app_segments = normalize_app_segments(
+        take_app_segments(&state, &session_id).await,
+        session.capture_bounds,
+        frame,
+    );
+    write_app_track(&app_path, duration_ms, &app_segments)?;
+    manifest.artifacts.app_track_path = Some(app_path.to_string_lossy().to_string());
     save_manifest(&state.project_root, &session.project_id, &manifest)?;
     clear_recovery_marker(&state.project_root, &session.project_id)?;
+    clear_ffmpeg_pids(&project_dir(&state.project_root, &session.project_id));
 
-    state
-        .recording_processes
-        .lock()
-        .map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording processes",
-                None,
-            )
-        })?
-        .remove(&session_id);
-    state
-        .recording_sessions
-        .lock()
-        .map_err(|_| {
-            AppError::new(
-                "STATE_LOCK_ERROR",
-                "failed to lock recording sessions",
-                None,
-            )
-        })?
-        .remove(&session_id);
-    state
-        .cursor_tracks
-        .lock()
-        .map_err(|_| AppError::new("STATE_LOCK_ERROR", "failed to lock cursor tracks", None))?
-        .remove(&session_id);
+    state.recording_processes.lock().await.remove(&session_id);
+    state.recording_sessions.write().await.remove(&session_id);
+    state.cursor_tracks.lock().await.remove(&session_id);
+    state.key_tracks.lock().await.remove(&session_id);
+    state.app_tracks.lock().await.remove(&session_id);
+    state.window_bounds_tracks.lock().await.remove(&session_id);
 
-    app.emit(
-        "recording/status",
-        RecordingStatusEvent {
+    emit_event(
+        &app,
+        AppEvent::RecordingStatus(RecordingStatusEvent {
             session_id,
             status: "stopped".to_string(),
             duration_ms,
             source_label: "录制完成".to_string(),
             detail: "录制已停止，进入编辑".to_string(),
             degrade_message: session.degrade_message,
-        },
-    )
-    .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))?;
+        }),
+    )?;
+
+    let project_id = session.project_id;
+    let (export_task_id, export_error) = match auto_export_preset_id(&state) {
+        Some(preset_id) => {
+            match start_export_with_preset(app, state, project_id.clone(), preset_id).await {
+                Ok(task_id) => (Some(task_id), None),
+                Err(error) => {
+                    tracing::warn!("auto export failed to start: {}", error.message);
+                    (None, Some(error))
+                }
+            }
+        }
+        None => (None, None),
+    };
+    Ok(StopRecordingResult {
+        project_id,
+        export_task_id,
+        export_error,
+    })
+}
+
+/// 开启“录制后自动导出”时返回要使用的预设：优先默认预设，否则取第一个。
+fn auto_export_preset_id(state: &RuntimeState) -> Option<String> {
+    let settings = load_app_settings(state)
+        .inspect_err(|error| tracing::warn!("failed to load settings: {}", error.message))
+        .ok()?;
+    if !settings.auto_export_after_recording {
+        return None;
+    }
+    settings.default_export_preset_id.or_else(|| {
+        settings
+            .export_presets
+            .first()
+            .map(|preset| preset.id.clone())
+    })
+}
+
+async fn take_cursor_samples(state: &RuntimeState, session_id: &str) -> Vec<CursorTrackSample> {
+    let tracker = state.cursor_tracks.lock().await.get(session_id).cloned();
+    let Some(tracker) = tracker else {
+        return Vec::new();
+    };
+    let items = tracker.lock().await.clone();
+    items
+}
+
+/// 全局快捷键回调：有进行中的会话时停止/暂停/继续，否则沿用上次的录制配置开始录制，
+/// 本次启动尚未录制过时使用设置中的默认录制配置。
+pub fn handle_hotkey_action(app: &AppHandle, action: HotkeyAction) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<RuntimeState>();
+        let active = state
+            .recording_sessions
+            .read()
+            .await
+            .values()
+            .find(|session| {
+                session.state == RecordingState::Recording
+                    || session.state == RecordingState::Paused
+            })
+            .map(|session| (session.session_id.clone(), session.state));
+        let result = match (action, active) {
+            (HotkeyAction::StartStop, Some((session_id, _))) => {
+                stop_recording(app.clone(), state, session_id)
+                    .await
+                    .map(|_| ())
+            }
+            (HotkeyAction::StartStop, None) => {
+                let last_profile = state.last_recording_profile.lock().await.clone();
+                let profile = last_profile.unwrap_or_else(|| {
+                    load_app_settings(&state)
+                        .map(|settings| settings.default_recording_profile)
+                        .unwrap_or_default()
+                });
+                start_recording(app.clone(), state, profile)
+                    .await
+                    .map(|_| ())
+            }
+            (HotkeyAction::PauseResume, Some((session_id, RecordingState::Recording))) => {
+                pause_recording(app.clone(), state, session_id).await
+            }
+            (HotkeyAction::PauseResume, Some((session_id, _))) => {
+                resume_recording(app.clone(), state, session_id).await
+            }
+            (HotkeyAction::PauseResume, None) => Ok(()),
+        };
+        if let Err(error) = result {
+            tracing::warn!("hotkey {:?} failed: {}", action, error.message);
+        }
+    });
+}
+
+async fn stop_audio_meter(state: &RuntimeState, session_id: &str) {
+    let meter = state.audio_meters.lock().await.remove(session_id);
+    if let Some(mut meter) = meter {
+        let _ = meter.child.kill();
+        let _ = tauri::async_runtime::spawn_blocking(move || meter.child.wait()).await;
+    }
+}
+
+fn spawn_audio_level_reader(
+    app: AppHandle,
+    session_id: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    mut stdout: std::process::ChildStdout,
+) {
+    std::thread::spawn(move || {
+        use std::io::Read;
+        let mut buffer = vec![0u8; METER_WINDOW_BYTES];
+        while stdout.read_exact(&mut buffer).is_ok() {
+            let samples = buffer
+                .chunks_exact(2)
+                .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+                .collect::<Vec<_>>();
+            let level = compute_audio_level(&samples);
+            let event = AudioLevelEvent {
+                session_id: session_id.clone(),
+                t_ms: (Utc::now() - started_at).num_milliseconds().max(0) as u64,
+                rms_db: level.rms_db,
+                peak_db: level.peak_db,
+            };
+            if emit_event(&app, AppEvent::AudioLevel(event)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn spawn_preview_reader(
+    app: AppHandle,
+    session_id: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    mut stdout: std::process::ChildStdout,
+) {
+    std::thread::spawn(move || {
+        use base64::Engine;
+        use std::io::Read;
+        let mut splitter = JpegFrameSplitter::default();
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut emitting = true;
+        // 即使事件发送失败也要持续读取，否则管道写满会阻塞录制进程。
+        while let Ok(read) = stdout.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            for frame in splitter.push(&buffer[..read]) {
+                if !emitting {
+                    continue;
+                }
+                let event = PreviewFrameEvent {
+                    session_id: session_id.clone(),
+                    t_ms: (Utc::now() - started_at).num_milliseconds().max(0) as u64,
+                    data_url: format!(
+                        "data:image/jpeg;base64,{}",
+                        base64::engine::general_purpose::STANDARD.encode(&frame)
+                    ),
+                };
+                emitting = emit_event(&app, AppEvent::PreviewFrame(event)).is_ok();
+            }
+        }
+    });
+}
+
+async fn take_key_events(state: &RuntimeState, session_id: &str) -> Vec<KeyEvent> {
+    let tracker = state.key_tracks.lock().await.get(session_id).cloned();
+    let Some(tracker) = tracker else {
+        return Vec::new();
+    };
+    let items = tracker.lock().await.clone();
+    items
+}
+
+/// 每个样本按当时的采集区域换算，窗口录制中移动窗口后光标仍与画面对齐。
+fn normalize_cursor_samples(
+    samples: Vec<CursorTrackSample>,
+    bounds_track: &[WindowBoundsSample],
+    frame: Option<(u32, u32)>,
+) -> Vec<CursorTrackSample> {
+    samples
+        .into_iter()
+        .map(|mut sample| {
+            let Some(bounds) = bounds_at(bounds_track, sample.t_ms) else {
+                return sample;
+            };
+            let (raw_x, raw_y) = (sample.x as f64, sample.y as f64);
+            sample.offscreen = !bounds.contains(raw_x, raw_y);
+            let (clamped_x, clamped_y) = bounds.clamp(raw_x, raw_y);
+            let (x, y) = bounds.map_to_frame(clamped_x, clamped_y, frame);
+            sample.x = x as f32;
+            sample.y = y as f32;
+            sample
+        })
+        .collect()
+}
 
-    Ok(session.project_id)
+/// 写回录制开始前的勿扰设置；失败只记录日志，不影响停止录制。
+fn restore_session_do_not_disturb(session: &RecordingSession) {
+    let Some(previous) = session.do_not_disturb_restore.as_deref() else {
+        return;
+    };
+    if let Err(error) = restore_do_not_disturb(previous) {
+        tracing::warn!("failed to restore do not disturb: {}", error.message);
+    }
 }
 
-fn take_cursor_samples(state: &RuntimeState, session_id: &str) -> Vec<CursorTrackSample> {
+/// 窗口录制取采集期间记录的窗口位置；全屏录制只有固定的显示器范围。
+async fn take_bounds_track(
+    state: &RuntimeState,
+    session_id: &str,
+    initial: Option<CaptureBounds>,
+) -> Vec<WindowBoundsSample> {
     let tracker = state
-        .cursor_tracks
+        .window_bounds_tracks
         .lock()
-        .ok()
-        .and_then(|tracks| tracks.get(session_id).cloned());
+        .await
+        .get(session_id)
+        .cloned();
+    match tracker {
+        Some(tracker) => tracker.lock().await.clone(),
+        None => initial
+            .map(|bounds| vec![WindowBoundsSample { t_ms: 0, bounds }])
+            .unwrap_or_default(),
+    }
+}
+
+async fn take_app_segments(state: &RuntimeState, session_id: &str) -> Vec<AppFocusSegment> {
+    let tracker = state.app_tracks.lock().await.get(session_id).cloned();
     let Some(tracker) = tracker else {
         return Vec::new();
     };
-    tracker
-        .lock()
-        .map(|samples| samples.clone())
-        .unwrap_or_default()
+    let items = tracker.lock().await.clone();
+    items
 }
 
 fn write_cursor_track(
@@ -484,11 +875,22 @@ fn write_cursor_track(
         let mut normalized = samples
             .iter()
             .map(|sample| {
-                serde_json::json!({
+                let mut value = serde_json::json!({
                   "tMs": sample.t_ms.min(duration_ms),
                   "x": sample.x,
                   "y": sample.y
-                })
+                });
+                if sample.scroll_dx != 0.0 || sample.scroll_dy != 0.0 {
+                    value["scrollDx"] = serde_json::json!(sample.scroll_dx);
+                    value["scrollDy"] = serde_json::json!(sample.scroll_dy);
+                }
+                if sample.offscreen {
+                    value["offscreen"] = serde_json::json!(true);
+                }
+                if sample.click {
+                    value["click"] = serde_json::json!(true);
+                }
+                value
             })
             .collect::<Vec<_>>();
         if normalized
@@ -529,23 +931,25 @@ fn write_cursor_track(
 fn schedule_cursor_tracking_ticker(
     session_id: String,
     started_at: chrono::DateTime<chrono::Utc>,
+    mut sampler: CursorSampler,
     app: AppHandle,
 ) {
     tauri::async_runtime::spawn(async move {
+        let scroll_listener = ScrollListener::start();
         loop {
-            tokio::time::sleep(std::time::Duration::from_millis(120)).await;
+            tokio::time::sleep(std::time::Duration::from_millis(sampler.poll_interval_ms())).await;
             let runtime = app.state::<RuntimeState>();
             let session_state = {
-                let sessions = match runtime.recording_sessions.lock() {
-                    Ok(sessions) => sessions,
-                    Err(_) => break,
-                };
+                let sessions = runtime.recording_sessions.read().await;
                 sessions.get(&session_id).map(|session| session.state)
             };
             let Some(session_state) = session_state else {
                 break;
             };
             if session_state != RecordingState::Recording {
+                // 暂停期间的滚动与点击不属于成片内容，直接丢弃。
+                take_scroll_delta();
+                take_click();
                 continue;
             }
 
@@ -554,26 +958,303 @@ fn schedule_cursor_tracking_ticker(
                 continue;
             };
             let track = {
-                let tracks = match runtime.cursor_tracks.lock() {
-                    Ok(tracks) => tracks,
-                    Err(_) => break,
-                };
+                let tracks = runtime.cursor_tracks.lock().await;
                 tracks.get(&session_id).cloned()
             };
             let Some(track) = track else {
                 break;
             };
             let elapsed = (Utc::now() - started_at).num_milliseconds().max(0) as u64;
-            let mut samples = match track.lock() {
-                Ok(samples) => samples,
-                Err(_) => continue,
-            };
-            samples.push(CursorTrackSample {
+            let (scroll_dx, scroll_dy) = take_scroll_delta();
+            let has_scroll = scroll_dx != 0.0 || scroll_dy != 0.0;
+            let click = take_click();
+            if !sampler.should_record(elapsed, x, y, has_scroll || click) {
+                continue;
+            }
+            track.lock().await.push(CursorTrackSample {
                 t_ms: elapsed,
                 x,
                 y,
+                scroll_dx,
+                scroll_dy,
+                offscreen: false,
+                click,
             });
         }
+        if let Some(listener) = scroll_listener {
+            listener.stop();
+        }
+    });
+}
+
+fn schedule_key_tracking_ticker(
+    session_id: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    mode: KeystrokeCaptureMode,
+    app: AppHandle,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut tracker = KeyStateTracker::default();
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            let runtime = app.state::<RuntimeState>();
+            let session_state = {
+                let sessions = runtime.recording_sessions.read().await;
+                sessions.get(&session_id).map(|session| session.state)
+            };
+            let Some(session_state) = session_state else {
+                break;
+            };
+            if session_state != RecordingState::Recording {
+                continue;
+            }
+
+            let Some(pressed) = poll_pressed_keys() else {
+                break;
+            };
+            let elapsed = (Utc::now() - started_at).num_milliseconds().max(0) as u64;
+            let events = tracker.update(pressed, elapsed, mode);
+            if events.is_empty() {
+                continue;
+            }
+            let track = {
+                let tracks = runtime.key_tracks.lock().await;
+                tracks.get(&session_id).cloned()
+            };
+            let Some(track) = track else {
+                break;
+            };
+            track.lock().await.extend(events);
+        }
+    });
+}
+
+fn schedule_app_tracking_ticker(
+    session_id: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    app: AppHandle,
+) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            let runtime = app.state::<RuntimeState>();
+            let session_state = {
+                let sessions = runtime.recording_sessions.read().await;
+                sessions.get(&session_id).map(|session| session.state)
+            };
+            let Some(session_state) = session_state else {
+                break;
+            };
+            if session_state != RecordingState::Recording {
+                continue;
+            }
+
+            let Some(foreground) = poll_foreground_app() else {
+                break;
+            };
+            let elapsed = (Utc::now() - started_at).num_milliseconds().max(0) as u64;
+            let track = {
+                let tracks = runtime.app_tracks.lock().await;
+                tracks.get(&session_id).cloned()
+            };
+            let Some(track) = track else {
+                break;
+            };
+            record_app_sample(&mut *track.lock().await, foreground, elapsed);
+        }
+    });
+}
+
+const MICROPHONE_LOST_MESSAGE: &str = "麦克风已断开，已改为静音音轨继续录制";
+
+/// 录制期间定期枚举音频设备，增减时推送 devices/changed；
+/// 正在使用的麦克风被拔出时改为静音续录，而不是等 ffmpeg 因输入丢失退出。
+fn schedule_device_watch_ticker(session_id: String, app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut known: Option<Vec<RecordingDevice>> = None;
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+            let runtime = app.state::<RuntimeState>();
+            let session = {
+                let sessions = runtime.recording_sessions.read().await;
+                sessions
+                    .get(&session_id)
+                    .map(|session| (session.state, active_microphone(&session.profile)))
+            };
+            let Some((session_state, microphone)) = session else {
+                break;
+            };
+            // 枚举失败的一轮直接跳过，避免把“没读到列表”误判为设备全部移除。
+            let Ok(Some(devices)) = tauri::async_runtime::spawn_blocking(probe_audio_devices).await
+            else {
+                continue;
+            };
+            let Some(previous) = known.replace(devices.clone()) else {
+                continue;
+            };
+            let (added, removed) = diff_audio_devices(&previous, &devices);
+            if added.is_empty() && removed.is_empty() {
+                continue;
+            }
+            let microphone_lost =
+                microphone.is_some_and(|id| removed.iter().any(|device| device.id == id));
+            let _ = emit_event(
+                &app,
+                AppEvent::DevicesChanged(DevicesChangedEvent {
+                    added,
+                    removed,
+                    devices,
+                }),
+            );
+            if microphone_lost && session_state == RecordingState::Recording {
+                if let Err(error) = continue_without_microphone(&app, &session_id).await {
+                    tracing::warn!("failed to continue without microphone: {}", error.message);
+                }
+            }
+        }
+    });
+}
+
+/// 录制使用的麦克风已不在设备列表中；枚举失败时按未丢失处理。
+async fn microphone_missing(runtime: &RuntimeState, session_id: &str) -> bool {
+    let microphone = runtime
+        .recording_sessions
+        .read()
+        .await
+        .get(session_id)
+        .and_then(|session| active_microphone(&session.profile));
+    let Some(microphone) = microphone else {
+        return false;
+    };
+    match tauri::async_runtime::spawn_blocking(probe_audio_devices).await {
+        Ok(Some(devices)) => !devices.iter().any(|device| device.id == microphone),
+        _ => false,
+    }
+}
+
+/// 录制命令实际使用的麦克风；未选择或使用系统默认设备时无法判断是否被拔出。
+fn active_microphone(profile: &RecordingProfile) -> Option<String> {
+    profile
+        .microphone_device_id
+        .clone()
+        .filter(|id| !id.trim().is_empty() && id != "default")
+}
+
+/// 结束当前录制进程并以静音音轨续录到新分段，停止录制时再拼接。
+/// 重启进程的约 1 秒不会补帧，其后的光标轨迹会略微超前于画面。
+async fn continue_without_microphone(app: &AppHandle, session_id: &str) -> Result<(), AppError> {
+    let runtime = app.state::<RuntimeState>();
+    let (profile, project_id, started_at, part_index) = {
+        let mut sessions = runtime.recording_sessions.write().await;
+        let Some(session) = sessions.get_mut(session_id) else {
+            return Ok(());
+        };
+        // 设备监测与进程退出检测可能同时触发，先清掉麦克风的一方负责续录。
+        if active_microphone(&session.profile).is_none() {
+            return Ok(());
+        }
+        session.profile.microphone_device_id = None;
+        session.degrade_message = Some(MICROPHONE_LOST_MESSAGE.to_string());
+        (
+            session.profile.clone(),
+            session.project_id.clone(),
+            session.started_at,
+            session.capture_parts.len() + 1,
+        )
+    };
+    // 进程已被停止录制取走时不再续录。
+    let Some(process) = runtime.recording_processes.lock().await.remove(session_id) else {
+        return Ok(());
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut process = process;
+        stop_ffmpeg_process(&mut process.child)
+    })
+    .await
+    .map_err(|error| AppError::new("RECORDING_STOP_FAIL", error.to_string(), None))??;
+    stop_audio_meter(&runtime, session_id).await;
+
+    let part_path = recording_capture_part_path(&runtime.project_root, &project_id, part_index);
+    let source_label = match profile.capture_mode {
+        CaptureMode::Fullscreen => "全屏".to_string(),
+        CaptureMode::Window => "窗口".to_string(),
+    };
+    let mut spawn = tauri::async_runtime::spawn_blocking({
+        let part_path = part_path.clone();
+        move || {
+            let display_bounds = match profile.capture_mode {
+                CaptureMode::Fullscreen => {
+                    profile.display_id.as_deref().and_then(find_display_bounds)
+                }
+                CaptureMode::Window => None,
+            };
+            spawn_recording_process(&ffmpeg_bin(), &profile, display_bounds.as_ref(), &part_path)
+        }
+    })
+    .await
+    .map_err(|error| AppError::new("RECORDING_START_FAIL", error.to_string(), None))??;
+    let project_path = project_dir(&runtime.project_root, &project_id);
+    if let Err(error) = record_ffmpeg_pid(&project_path, spawn.child.id()) {
+        tracing::warn!("failed to record recording pid: {}", error.message);
+    }
+    if let Some(stdout) = spawn.child.stdout.take() {
+        spawn_preview_reader(app.clone(), session_id.to_string(), started_at, stdout);
+    }
+    runtime.recording_processes.lock().await.insert(
+        session_id.to_string(),
+        RecordingProcess { child: spawn.child },
+    );
+    if let Some(session) = runtime.recording_sessions.write().await.get_mut(session_id) {
+        session.capture_parts.push(part_path);
+    }
+    emit_event(
+        app,
+        AppEvent::RecordingStatus(RecordingStatusEvent {
+            session_id: session_id.to_string(),
+            status: "recording".to_string(),
+            duration_ms: (Utc::now() - started_at).num_milliseconds().max(0) as u64,
+            source_label,
+            detail: "麦克风已断开".to_string(),
+            degrade_message: Some(MICROPHONE_LOST_MESSAGE.to_string()),
+        }),
+    )
+}
+
+/// 窗口录制期间持续跟踪目标窗口的位置与尺寸；窗口暂时找不到（最小化、关闭）时保留上次位置。
+fn schedule_window_follow_ticker(
+    session_id: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    target: String,
+    app: AppHandle,
+) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            let runtime = app.state::<RuntimeState>();
+            let session_state = {
+                let sessions = runtime.recording_sessions.read().await;
+                sessions.get(&session_id).map(|session| session.state)
+            };
+            let Some(session_state) = session_state else {
+                break;
+            };
+            if session_state != RecordingState::Recording {
+                continue;
+            }
+
+            let Some(bounds) = find_window_bounds(&target) else {
+                continue;
+            };
+            let elapsed = (Utc::now() - started_at).num_milliseconds().max(0) as u64;
+            let track = {
+                let tracks = runtime.window_bounds_tracks.lock().await;
+                tracks.get(&session_id).cloned()
+            };
+            let Some(track) = track else {
+                break;
+            };
+            record_bounds_sample(&mut *track.lock().await, elapsed, bounds);
+        }
     });
 }
 
@@ -591,7 +1272,19 @@ fn current_cursor_position() -> Option<(f32, f32)> {
     }
 }
 
-#[cfg(not(target_os = "windows"))]
+/// 返回以主显示器左上角为原点的点坐标（非像素），由 capture bounds 换算到 Retina 帧。
+#[cfg(target_os = "macos")]
+fn current_cursor_position() -> Option<(f32, f32)> {
+    use core_graphics::event::CGEvent;
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let source = CGEventSource::new(CGEventSourceStateID::CombinedSessionState).ok()?;
+    let event = CGEvent::new(source).ok()?;
+    let point = event.location();
+    Some((point.x as f32, point.y as f32))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
 fn current_cursor_position() -> Option<(f32, f32)> {
     None
 }
@@ -607,10 +1300,7 @@ fn schedule_recording_status_ticker(app: AppHandle, session_id: String) {
                 crate::domain::models::CaptureMode,
                 Option<String>,
             )> = {
-                let sessions = match runtime.recording_sessions.lock() {
-                    Ok(sessions) => sessions,
-                    Err(_) => break,
-                };
+                let sessions = runtime.recording_sessions.read().await;
                 sessions.get(&session_id).map(|session| {
                     (
                         session.state,
@@ -624,10 +1314,7 @@ fn schedule_recording_status_ticker(app: AppHandle, session_id: String) {
                 break;
             };
             let process_exited = {
-                let mut processes = match runtime.recording_processes.lock() {
-                    Ok(processes) => processes,
-                    Err(_) => break,
-                };
+                let mut processes = runtime.recording_processes.lock().await;
                 let Some(process) = processes.get_mut(&session_id) else {
                     break;
                 };
@@ -637,35 +1324,49 @@ fn schedule_recording_status_ticker(app: AppHandle, session_id: String) {
                     Err(_) => true,
                 }
             };
+            // 麦克风被拔出导致 ffmpeg 退出时，改为静音续录而不是直接报错结束。
+            if process_exited
+                && state == RecordingState::Recording
+                && microphone_missing(&runtime, &session_id).await
+            {
+                match continue_without_microphone(&app, &session_id).await {
+                    Ok(()) => continue,
+                    Err(error) => {
+                        tracing::warn!("failed to continue without microphone: {}", error.message)
+                    }
+                }
+            }
             if process_exited {
-                let emitted_degrade_message =
-                    if let Ok(mut sessions) = runtime.recording_sessions.lock() {
-                        let message = sessions
-                            .get(&session_id)
-                            .and_then(|session| session.degrade_message.clone());
-                        sessions.remove(&session_id);
-                        message
-                    } else {
-                        degrade_message.clone()
-                    };
-                let _ = runtime
-                    .recording_processes
+                let emitted_degrade_message = runtime
+                    .recording_sessions
+                    .write()
+                    .await
+                    .remove(&session_id)
+                    .and_then(|session| {
+                        restore_session_do_not_disturb(&session);
+                        session.degrade_message
+                    })
+                    .or_else(|| degrade_message.clone());
+                runtime.recording_processes.lock().await.remove(&session_id);
+                runtime.cursor_tracks.lock().await.remove(&session_id);
+                runtime.key_tracks.lock().await.remove(&session_id);
+                runtime.app_tracks.lock().await.remove(&session_id);
+                runtime
+                    .window_bounds_tracks
                     .lock()
-                    .map(|mut processes| processes.remove(&session_id));
-                let _ = runtime
-                    .cursor_tracks
-                    .lock()
-                    .map(|mut tracks| tracks.remove(&session_id));
-                let _ = app.emit(
-                    "recording/status",
-                    RecordingStatusEvent {
+                    .await
+                    .remove(&session_id);
+                stop_audio_meter(&runtime, &session_id).await;
+                let _ = emit_event(
+                    &app,
+                    AppEvent::RecordingStatus(RecordingStatusEvent {
                         session_id: session_id.clone(),
                         status: "error".to_string(),
                         duration_ms: (Utc::now() - started_at).num_milliseconds().max(0) as u64,
                         source_label: "录制中断".to_string(),
                         detail: "录制进程异常退出，请检查权限或输入源".to_string(),
                         degrade_message: emitted_degrade_message,
-                    },
+                    }),
                 );
                 break;
             }
@@ -680,22 +1381,21 @@ fn schedule_recording_status_ticker(app: AppHandle, session_id: String) {
             .to_string();
 
             let duration_ms = (Utc::now() - started_at).num_milliseconds().max(0) as u64;
-            if app
-                .emit(
-                    "recording/status",
-                    RecordingStatusEvent {
-                        session_id: session_id.clone(),
-                        status: status.clone(),
-                        duration_ms,
-                        source_label: match capture_mode {
-                            crate::domain::models::CaptureMode::Fullscreen => "全屏".to_string(),
-                            crate::domain::models::CaptureMode::Window => "窗口".to_string(),
-                        },
-                        detail: "录制状态更新".to_string(),
-                        degrade_message: degrade_message.clone(),
+            if emit_event(
+                &app,
+                AppEvent::RecordingStatus(RecordingStatusEvent {
+                    session_id: session_id.clone(),
+                    status: status.clone(),
+                    duration_ms,
+                    source_label: match capture_mode {
+                        crate::domain::models::CaptureMode::Fullscreen => "全屏".to_string(),
+                        crate::domain::models::CaptureMode::Window => "窗口".to_string(),
                     },
-                )
-                .is_err()
+                    detail: "录制状态更新".to_string(),
+                    degrade_message: degrade_message.clone(),
+                }),
+            )
+            .is_err()
             {
                 break;
             }
@@ -1,14 +1,25 @@
-use crate::core::capture::service::{list_audio_devices, platform_capability};
-use crate::domain::models::{AppError, HotkeySettings, RecordingDevice};
+use crate::commands::export::drain_export_queue;
+use crate::commands::recording::handle_hotkey_action;
+use crate::commands::share::stop_share_server;
+use crate::core::capture::display::list_displays;
+use crate::core::capture::service::{list_audio_devices, platform_capability, validate_frame_rate};
+use crate::core::export::queue::MAX_CONCURRENT_EXPORTS_LIMIT;
+use crate::domain::events::AppEvent;
+use crate::domain::models::{
+    AppError, AppSettings, DisplayInfo, ExportPreset, FfmpegInfo, FfmpegInstallStatus,
+    HotkeySettings, RecordingDevice, S3Settings, MAX_S3_LINK_EXPIRY_HOURS,
+};
+use crate::infra::events::emit_event;
+use crate::infra::ffmpeg::info::ffmpeg_info;
+use crate::infra::ffmpeg::installer::{ffmpeg_install_status, install_managed_ffmpeg};
+use crate::infra::hotkeys::register_hotkeys;
+use crate::infra::storage::settings_store::{
+    load_settings_file, write_settings_file, SETTINGS_SCHEMA_VERSION,
+};
+use crate::infra::upload::token_store::{load_s3_secret, save_s3_secret};
 use crate::state::RuntimeState;
-use serde::{Deserialize, Serialize};
-use tauri::State;
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
-struct SettingsFile {
-    hotkeys: HotkeySettings,
-}
+use std::path::Path;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub async fn get_platform_capability() -> crate::core::capture::service::PlatformCapability {
@@ -20,67 +31,284 @@ pub async fn list_audio_input_devices() -> Vec<RecordingDevice> {
     list_audio_devices()
 }
 
+#[tauri::command]
+pub async fn list_video_input_devices() -> Vec<RecordingDevice> {
+    crate::core::capture::service::list_video_input_devices()
+}
+
+#[tauri::command]
+pub async fn list_capture_displays() -> Vec<DisplayInfo> {
+    list_displays()
+}
+
+#[tauri::command]
+pub async fn get_ffmpeg_install_status() -> FfmpegInstallStatus {
+    ffmpeg_install_status()
+}
+
+/// 录制前检查 ffmpeg 版本与组件，提前给出缺少 gdigrab、libx264 等的处理建议。
+#[tauri::command]
+pub async fn get_ffmpeg_info() -> Result<FfmpegInfo, AppError> {
+    tauri::async_runtime::spawn_blocking(ffmpeg_info)
+        .await
+        .map_err(|error| AppError::new("FFMPEG_EXEC_ERROR", error.to_string(), None))
+}
+
+/// 下载固定版本的 ffmpeg/ffprobe 到应用数据目录，进度经 `ffmpeg/install-progress` 推送。
+#[tauri::command]
+pub async fn install_ffmpeg(app: AppHandle) -> Result<FfmpegInstallStatus, AppError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        install_managed_ffmpeg(|event| {
+            let _ = emit_event(&app, AppEvent::FfmpegInstallProgress(event));
+        })
+    })
+    .await
+    .map_err(|error| AppError::new("FFMPEG_INSTALL_FAIL", error.to_string(), None))??;
+    Ok(ffmpeg_install_status())
+}
+
 #[tauri::command]
 pub async fn load_hotkeys(state: State<'_, RuntimeState>) -> Result<HotkeySettings, AppError> {
-    let settings = load_or_default_settings(&state)?;
-    Ok(settings.hotkeys)
+    Ok(load_or_default_settings(&state)?.hotkeys)
 }
 
 #[tauri::command]
 pub async fn save_hotkeys(
+    app: AppHandle,
     state: State<'_, RuntimeState>,
     hotkeys: HotkeySettings,
 ) -> Result<(), AppError> {
-    let settings = SettingsFile { hotkeys };
+    let _guard = state.settings_lock.lock().await;
+    let previous = load_or_default_settings(&state)?;
+    apply_hotkeys(&app, &previous.hotkeys, &hotkeys)?;
+    let settings = AppSettings {
+        hotkeys,
+        ..previous
+    };
     write_settings(&state, &settings)
 }
 
-fn load_or_default_settings(state: &RuntimeState) -> Result<SettingsFile, AppError> {
-    if !state.settings_path.exists() {
-        let settings = SettingsFile {
-            hotkeys: HotkeySettings::default(),
-        };
-        write_settings(state, &settings)?;
-        return Ok(settings);
+#[tauri::command]
+pub async fn load_settings(state: State<'_, RuntimeState>) -> Result<AppSettings, AppError> {
+    Ok(redact_secrets(load_or_default_settings(&state)?))
+}
+
+/// 整体替换设置：先校验，快捷键变化时重新注册，项目根目录在下次启动时生效。
+/// S3 密钥留空时沿用已保存的值。
+#[tauri::command]
+pub async fn update_settings(
+    app: AppHandle,
+    state: State<'_, RuntimeState>,
+    settings: AppSettings,
+) -> Result<AppSettings, AppError> {
+    let guard = state.settings_lock.lock().await;
+    let previous = load_or_default_settings(&state)?;
+    let mut settings = settings;
+    if let (Some(s3), Some(stored)) = (settings.s3.as_mut(), previous.s3.as_ref()) {
+        if s3.secret_access_key.is_empty() {
+            s3.secret_access_key = stored.secret_access_key.clone();
+        }
     }
-    let content = std::fs::read_to_string(&state.settings_path).map_err(|error| {
-        AppError::new(
-            "SETTINGS_READ_FAIL",
-            format!("failed to read settings: {error}"),
-            None,
-        )
-    })?;
-    serde_json::from_str::<SettingsFile>(&content).map_err(|error| {
-        AppError::new(
-            "SETTINGS_PARSE_FAIL",
-            format!("failed to parse settings: {error}"),
-            None,
-        )
-    })
+    validate_settings(&settings)?;
+    if previous.hotkeys.start_stop != settings.hotkeys.start_stop
+        || previous.hotkeys.pause_resume != settings.hotkeys.pause_resume
+    {
+        apply_hotkeys(&app, &previous.hotkeys, &settings.hotkeys)?;
+    }
+    let settings = AppSettings {
+        schema_version: SETTINGS_SCHEMA_VERSION,
+        ..settings
+    };
+    write_settings(&state, &settings)?;
+    drop(guard);
+    if !settings.share.enabled || previous.share.port != settings.share.port {
+        stop_share_server(&state).await;
+    }
+    if previous.max_concurrent_exports != settings.max_concurrent_exports {
+        state
+            .export_queue
+            .lock()
+            .await
+            .set_max_concurrent(settings.max_concurrent_exports);
+        drain_export_queue(&app).await;
+    }
+    Ok(redact_secrets(settings))
+}
+
+#[tauri::command]
+pub async fn list_export_presets(
+    state: State<'_, RuntimeState>,
+) -> Result<Vec<ExportPreset>, AppError> {
+    load_export_presets(&state)
 }
 
-fn write_settings(state: &RuntimeState, settings: &SettingsFile) -> Result<(), AppError> {
-    if let Some(parent) = state.settings_path.parent() {
-        std::fs::create_dir_all(parent).map_err(|error| {
-            AppError::new(
-                "SETTINGS_WRITE_FAIL",
-                format!("failed to create settings dir: {error}"),
-                None,
+pub fn load_export_presets(state: &RuntimeState) -> Result<Vec<ExportPreset>, AppError> {
+    Ok(load_or_default_settings(state)?.export_presets)
+}
+
+pub fn load_app_settings(state: &RuntimeState) -> Result<AppSettings, AppError> {
+    load_or_default_settings(state)
+}
+
+/// 新快捷键注册失败时恢复旧配置，避免全局快捷键全部失效。
+fn apply_hotkeys(
+    app: &AppHandle,
+    previous: &HotkeySettings,
+    hotkeys: &HotkeySettings,
+) -> Result<(), AppError> {
+    if let Err(error) = register_hotkeys(app, hotkeys, handle_hotkey_action) {
+        let _ = register_hotkeys(app, previous, handle_hotkey_action);
+        return Err(error);
+    }
+    Ok(())
+}
+
+// 超出常见编码器的线程上限后 ffmpeg 会报错或忽略，设置里提前拒绝。
+const MAX_ENCODER_THREADS: u16 = 64;
+
+fn validate_settings(settings: &AppSettings) -> Result<(), AppError> {
+    validate_frame_rate(settings.default_recording_profile.frame_rate)?;
+    if let Some(preset_id) = &settings.default_export_preset_id {
+        if !settings
+            .export_presets
+            .iter()
+            .any(|preset| &preset.id == preset_id)
+        {
+            return Err(AppError::new(
+                "EXPORT_PRESET_NOT_FOUND",
+                format!("export preset not found: {preset_id}"),
+                Some("请从预设列表中选择默认导出预设".to_string()),
             )
-        })?;
+            .with_param("presetId", preset_id));
+        }
     }
-    let raw = serde_json::to_string_pretty(settings).map_err(|error| {
-        AppError::new(
-            "SETTINGS_WRITE_FAIL",
-            format!("failed to serialize settings: {error}"),
-            None,
+    if let Some(project_root) = &settings.project_root {
+        if !Path::new(project_root).is_absolute() {
+            return Err(AppError::new(
+                "INVALID_PROJECT_ROOT",
+                format!("project root must be an absolute path: {project_root}"),
+                Some("请选择一个完整的文件夹路径".to_string()),
+            )
+            .with_param("path", project_root));
+        }
+    }
+    if settings.cleanup.auto_purge_trash && settings.cleanup.trash_retention_days == 0 {
+        return Err(AppError::new(
+            "INVALID_CLEANUP_POLICY",
+            "trash retention must be at least 1 day",
+            Some("保留天数至少为 1 天，或关闭自动清理".to_string()),
+        ));
+    }
+    if !(1..=MAX_CONCURRENT_EXPORTS_LIMIT).contains(&settings.max_concurrent_exports) {
+        return Err(AppError::new(
+            "INVALID_EXPORT_CONCURRENCY",
+            format!(
+                "max concurrent exports must be between 1 and {MAX_CONCURRENT_EXPORTS_LIMIT}: {}",
+                settings.max_concurrent_exports
+            ),
+            Some(format!(
+                "同时导出数量请设为 1~{MAX_CONCURRENT_EXPORTS_LIMIT}"
+            )),
         )
-    })?;
-    std::fs::write(&state.settings_path, raw).map_err(|error| {
-        AppError::new(
-            "SETTINGS_WRITE_FAIL",
-            format!("failed to write settings: {error}"),
-            None,
+        .with_param("max", MAX_CONCURRENT_EXPORTS_LIMIT));
+    }
+    if settings.encoder_threads > MAX_ENCODER_THREADS {
+        return Err(AppError::new(
+            "INVALID_ENCODER_THREADS",
+            format!(
+                "encoder threads must be at most {MAX_ENCODER_THREADS}: {}",
+                settings.encoder_threads
+            ),
+            Some(format!("编码线程数请设为 0（自动）~{MAX_ENCODER_THREADS}")),
         )
-    })
+        .with_param("max", MAX_ENCODER_THREADS));
+    }
+    if settings.share.link_expiry_hours == 0 {
+        return Err(AppError::new(
+            "INVALID_SHARE_POLICY",
+            "share link expiry must be at least 1 hour",
+            Some("分享链接有效期至少为 1 小时".to_string()),
+        ));
+    }
+    if let Some(s3) = &settings.s3 {
+        validate_s3_settings(s3)?;
+    }
+    Ok(())
+}
+
+fn validate_s3_settings(s3: &S3Settings) -> Result<(), AppError> {
+    let invalid = |field: &str, message: String, suggestion: &str| {
+        AppError::new("INVALID_S3_SETTINGS", message, Some(suggestion.to_string()))
+            .with_param("field", field)
+    };
+    let endpoint = s3.endpoint.trim();
+    if !(endpoint.starts_with("https://") || endpoint.starts_with("http://")) {
+        return Err(invalid(
+            "endpoint",
+            format!("s3 endpoint must be an http(s) url: {endpoint}"),
+            "服务地址需以 https:// 或 http:// 开头",
+        ));
+    }
+    for (field, value) in [
+        ("region", &s3.region),
+        ("bucket", &s3.bucket),
+        ("accessKeyId", &s3.access_key_id),
+        ("secretAccessKey", &s3.secret_access_key),
+    ] {
+        if value.trim().is_empty() {
+            return Err(invalid(
+                field,
+                format!("s3 {field} is required"),
+                "请填写完整的对象存储配置",
+            ));
+        }
+    }
+    if !(1..=MAX_S3_LINK_EXPIRY_HOURS).contains(&s3.link_expiry_hours) {
+        return Err(invalid(
+            "linkExpiryHours",
+            format!("s3 link expiry must be between 1 and {MAX_S3_LINK_EXPIRY_HOURS} hours"),
+            "预签名链接有效期为 1 小时到 7 天",
+        ));
+    }
+    Ok(())
+}
+
+/// 启动时按已保存的设置注册全局快捷键，失败只记录日志不阻塞启动。
+pub fn register_saved_hotkeys(app: &AppHandle, state: &RuntimeState) {
+    let result = load_or_default_settings(state)
+        .and_then(|settings| register_hotkeys(app, &settings.hotkeys, handle_hotkey_action));
+    if let Err(error) = result {
+        tracing::warn!("failed to register hotkeys: {}", error.message);
+    }
+}
+
+/// S3 密钥保存在 upload_tokens.json；旧版本写进 settings.json 的密钥在读取时迁移过去。
+fn load_or_default_settings(state: &RuntimeState) -> Result<AppSettings, AppError> {
+    let mut settings = load_settings_file(&state.settings_path)?;
+    match settings.s3.as_mut() {
+        Some(s3) if s3.secret_access_key.is_empty() => {
+            s3.secret_access_key = load_s3_secret(&state.upload_tokens_path).unwrap_or_default();
+        }
+        Some(_) => write_settings(state, &settings)?,
+        None => {}
+    }
+    Ok(settings)
+}
+
+fn write_settings(state: &RuntimeState, settings: &AppSettings) -> Result<(), AppError> {
+    let secret = settings
+        .s3
+        .as_ref()
+        .map(|s3| s3.secret_access_key.as_str())
+        .unwrap_or_default();
+    save_s3_secret(&state.upload_tokens_path, secret)?;
+    write_settings_file(&state.settings_path, &redact_secrets(settings.clone()))
+}
+
+/// 返回给前端或写入 settings.json 的设置不含 S3 密钥。
+fn redact_secrets(mut settings: AppSettings) -> AppSettings {
+    if let Some(s3) = settings.s3.as_mut() {
+        s3.secret_access_key.clear();
+    }
+    settings
 }
@@ -0,0 +1,91 @@
+use crate::commands::export::existing_export_path;
+use crate::commands::settings::load_app_settings;
+use crate::domain::models::{AppError, ShareLink};
+use crate::infra::share_server::{new_share_token, ShareServer, SharedFile};
+use crate::state::RuntimeState;
+use chrono::Utc;
+use tauri::State;
+
+/// 为最近一次导出的成片创建限时局域网链接，同一网络内的同事可直接在浏览器中观看。
+/// 需要先在设置中开启局域网分享。
+#[tauri::command]
+pub async fn create_share_link(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+) -> Result<ShareLink, AppError> {
+    let policy = load_app_settings(&state)?.share;
+    if !policy.enabled {
+        return Err(AppError::new(
+            "SHARE_DISABLED",
+            "lan sharing is disabled",
+            Some("请先在设置中开启局域网分享".to_string()),
+        ));
+    }
+    let path = existing_export_path(&state, &project_id)?;
+    let mut running = state.share_server.lock().await;
+    let server = match running.take() {
+        Some(server) => server,
+        None => ShareServer::start(policy.port)?,
+    };
+    let server = running.insert(server);
+    let now = Utc::now();
+    let shared = SharedFile {
+        project_id: project_id.clone(),
+        path,
+        created_at: now,
+        expires_at: now + chrono::Duration::hours(i64::from(policy.link_expiry_hours)),
+    };
+    let token = new_share_token();
+    let mut links = server.links().lock().await;
+    links.retain(|_, shared| shared.expires_at > now);
+    let link = share_link(server, &token, &shared);
+    links.insert(token, shared);
+    Ok(link)
+}
+
+/// 撤销后链接立即失效；令牌不存在（已过期或已撤销）时视为成功。
+#[tauri::command]
+pub async fn revoke_share_link(
+    state: State<'_, RuntimeState>,
+    token: String,
+) -> Result<(), AppError> {
+    if let Some(server) = state.share_server.lock().await.as_ref() {
+        server.links().lock().await.remove(&token);
+    }
+    Ok(())
+}
+
+/// 仍有效的分享链接，按创建时间倒序。
+#[tauri::command]
+pub async fn list_share_links(state: State<'_, RuntimeState>) -> Result<Vec<ShareLink>, AppError> {
+    let server = state.share_server.lock().await;
+    let Some(server) = server.as_ref() else {
+        return Ok(Vec::new());
+    };
+    let now = Utc::now();
+    let mut links = server
+        .links()
+        .lock()
+        .await
+        .iter()
+        .filter(|(_, shared)| shared.expires_at > now)
+        .map(|(token, shared)| share_link(server, token, shared))
+        .collect::<Vec<_>>();
+    links.sort_by_key(|link| std::cmp::Reverse(link.created_at));
+    Ok(links)
+}
+
+/// 关闭分享或更换端口时停止服务，已创建的链接随之失效。
+pub async fn stop_share_server(state: &RuntimeState) {
+    state.share_server.lock().await.take();
+}
+
+fn share_link(server: &ShareServer, token: &str, shared: &SharedFile) -> ShareLink {
+    ShareLink {
+        token: token.to_string(),
+        project_id: shared.project_id.clone(),
+        url: server.url(token),
+        created_at: shared.created_at,
+        expires_at: shared.expires_at,
+    }
+}
@@ -0,0 +1,269 @@
+use crate::commands::export::existing_export_path;
+use crate::commands::settings::load_app_settings;
+use crate::domain::events::AppEvent;
+use crate::domain::models::{
+    AppError, S3Settings, UploadProgressEvent, UploadRecord, UploadStatus, UploadTarget,
+};
+use crate::infra::desktop::open_url;
+use crate::infra::events::emit_event;
+use crate::infra::storage::project_store::{load_manifest, save_manifest};
+use crate::infra::upload::oauth::authorize;
+use crate::infra::upload::s3::{check_bucket, presign_get};
+use crate::infra::upload::token_store::{load_token, remove_token};
+use crate::infra::upload::{upload_file, UploadJob};
+use crate::state::RuntimeState;
+use chrono::Utc;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+/// 打开浏览器完成 OAuth 授权，令牌保存后即可上传到该目标。
+/// S3 的凭据来自设置，连接时只校验存储桶是否可访问。
+#[tauri::command]
+pub async fn connect_upload_account(
+    state: State<'_, RuntimeState>,
+    target: UploadTarget,
+) -> Result<(), AppError> {
+    if target == UploadTarget::S3 {
+        let s3 = s3_settings(&state)?;
+        return tauri::async_runtime::spawn_blocking(move || check_bucket(&s3))
+            .await
+            .map_err(|error| AppError::new("UPLOAD_FAIL", error.to_string(), None))?;
+    }
+    let tokens_path = state.upload_tokens_path.clone();
+    tauri::async_runtime::spawn_blocking(move || authorize(&tokens_path, target, open_url))
+        .await
+        .map_err(|error| AppError::new("UPLOAD_AUTH_FAIL", error.to_string(), None))?
+}
+
+#[tauri::command]
+pub async fn disconnect_upload_account(
+    state: State<'_, RuntimeState>,
+    target: UploadTarget,
+) -> Result<(), AppError> {
+    remove_token(&state.upload_tokens_path, target)
+}
+
+/// 已保存令牌的上传目标，设置中配置了对象存储时包含 S3。
+#[tauri::command]
+pub async fn list_upload_accounts(
+    state: State<'_, RuntimeState>,
+) -> Result<Vec<UploadTarget>, AppError> {
+    let mut targets = [UploadTarget::Youtube, UploadTarget::GoogleDrive]
+        .into_iter()
+        .filter(|target| load_token(&state.upload_tokens_path, *target).is_some())
+        .collect::<Vec<_>>();
+    if s3_settings(&state).is_ok() {
+        targets.push(UploadTarget::S3);
+    }
+    Ok(targets)
+}
+
+/// 为已上传到 S3 的成片重新生成预签名链接，其他目标的链接长期有效，原样返回。
+#[tauri::command]
+pub async fn refresh_upload_link(
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    target: UploadTarget,
+) -> Result<UploadRecord, AppError> {
+    let manifest = load_manifest(&state.project_root, &project_id)?;
+    let mut record = manifest
+        .uploads
+        .into_iter()
+        .find(|record| record.target == target && record.status == UploadStatus::Completed)
+        .ok_or_else(|| {
+            AppError::new(
+                "UPLOAD_NOT_FOUND",
+                format!("project {project_id} has no completed upload"),
+                Some("请先上传导出文件".to_string()),
+            )
+            .with_param("target", target.as_str())
+        })?;
+    if target != UploadTarget::S3 {
+        return Ok(record);
+    }
+    let s3 = s3_settings(&state)?;
+    let key = record.remote_id.clone().unwrap_or_default();
+    let expires_in = u64::from(s3.link_expiry_hours) * 3600;
+    let now = Utc::now();
+    record.url = Some(presign_get(&s3, &key, expires_in, now));
+    record.url_expires_at = Some(now + chrono::Duration::seconds(expires_in as i64));
+    update_upload_record(&state.project_root, &project_id, &record)?;
+    Ok(record)
+}
+
+/// 上传最近一次导出的成片，进度经 `upload/progress` 推送，结果记录到清单。
+/// 同一文件上次中断时从服务端记录的偏移续传。
+#[tauri::command]
+pub async fn upload_export(
+    app: AppHandle,
+    state: State<'_, RuntimeState>,
+    project_id: String,
+    target: UploadTarget,
+) -> Result<UploadRecord, AppError> {
+    let file = existing_export_path(&state, &project_id)?;
+    let key = format!("{project_id}:{}", target.as_str());
+    if !state.active_uploads.lock().await.insert(key.clone()) {
+        return Err(AppError::new(
+            "UPLOAD_ALREADY_ACTIVE",
+            "this export is already being uploaded",
+            Some("请等待当前上传完成".to_string()),
+        )
+        .with_param("target", target.as_str()));
+    }
+    let result = run_upload(app, &state, &project_id, target, &file).await;
+    state.active_uploads.lock().await.remove(&key);
+    result
+}
+
+async fn run_upload(
+    app: AppHandle,
+    state: &RuntimeState,
+    project_id: &str,
+    target: UploadTarget,
+    file: &Path,
+) -> Result<UploadRecord, AppError> {
+    let manifest = load_manifest(&state.project_root, project_id)?;
+    let file_path = file.to_string_lossy().to_string();
+    let total_bytes = std::fs::metadata(file)
+        .map(|meta| meta.len())
+        .unwrap_or_default();
+    // 只有同一文件、同样大小的未完成记录才续传，重新导出后从头上传。
+    let resume_session = manifest
+        .uploads
+        .iter()
+        .find(|record| {
+            record.target == target
+                && record.status != UploadStatus::Completed
+                && record.file_path == file_path
+                && record.total_bytes == total_bytes
+        })
+        .and_then(|record| record.session_uri.clone());
+    let title = manifest
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("FocusLens {}", manifest.created_at.format("%Y-%m-%d %H:%M")));
+    let mut record = UploadRecord {
+        target,
+        status: UploadStatus::Uploading,
+        file_path,
+        total_bytes,
+        session_uri: resume_session.clone(),
+        remote_id: None,
+        url: None,
+        url_expires_at: None,
+        started_at: Utc::now(),
+        completed_at: None,
+        last_error: None,
+    };
+    update_upload_record(&state.project_root, project_id, &record)?;
+
+    let s3 = match target {
+        UploadTarget::S3 => Some(s3_settings(state)?),
+        _ => None,
+    };
+    let project_root = state.project_root.clone();
+    let tokens_path = state.upload_tokens_path.clone();
+    let file = file.to_path_buf();
+    let id = project_id.to_string();
+    let session_record = record.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let mut last_progress = None;
+        upload_file(
+            UploadJob {
+                target,
+                project_id: &id,
+                tokens_path: &tokens_path,
+                s3: s3.as_ref(),
+                file: &file,
+                title: &title,
+                resume_session,
+            },
+            |session_uri| {
+                let record = UploadRecord {
+                    session_uri: Some(session_uri.to_string()),
+                    ..session_record.clone()
+                };
+                if let Err(error) = update_upload_record(&project_root, &id, &record) {
+                    tracing::warn!("failed to persist upload session: {}", error.message);
+                }
+            },
+            |uploaded_bytes, total_bytes| {
+                let progress = (uploaded_bytes * 100)
+                    .checked_div(total_bytes)
+                    .unwrap_or(100)
+                    .min(100) as u8;
+                if last_progress != Some(progress) {
+                    last_progress = Some(progress);
+                    let _ = emit_event(
+                        &app,
+                        AppEvent::UploadProgress(UploadProgressEvent {
+                            project_id: id.clone(),
+                            target,
+                            uploaded_bytes,
+                            total_bytes,
+                            progress,
+                        }),
+                    );
+                }
+            },
+        )
+    })
+    .await
+    .map_err(|error| AppError::new("UPLOAD_FAIL", error.to_string(), None))?;
+
+    // 会话地址可能已在上传中更新，以清单中的最新值为准。
+    record.session_uri = load_manifest(&state.project_root, project_id)
+        .ok()
+        .and_then(|manifest| {
+            manifest
+                .uploads
+                .into_iter()
+                .find(|item| item.target == target)
+        })
+        .and_then(|item| item.session_uri);
+    match result {
+        Ok(resource) => {
+            record.status = UploadStatus::Completed;
+            record.session_uri = None;
+            record.remote_id = Some(resource.remote_id);
+            record.url = Some(resource.url);
+            record.url_expires_at = resource.url_expires_at;
+            record.completed_at = Some(Utc::now());
+            update_upload_record(&state.project_root, project_id, &record)?;
+            Ok(record)
+        }
+        Err(error) => {
+            record.status = UploadStatus::Failed;
+            record.last_error = Some(error.clone());
+            update_upload_record(&state.project_root, project_id, &record)?;
+            Err(error)
+        }
+    }
+}
+
+fn s3_settings(state: &RuntimeState) -> Result<S3Settings, AppError> {
+    load_app_settings(state)?
+        .s3
+        .filter(|s3| !s3.endpoint.trim().is_empty() && !s3.bucket.trim().is_empty())
+        .ok_or_else(|| {
+            AppError::new(
+                "UPLOAD_NOT_CONFIGURED",
+                "s3 storage is not configured",
+                Some("请先在设置中填写对象存储地址与凭据".to_string()),
+            )
+            .with_param("target", UploadTarget::S3.as_str())
+        })
+}
+
+/// 每个目标只保留最近一条记录。
+fn update_upload_record(
+    project_root: &Path,
+    project_id: &str,
+    record: &UploadRecord,
+) -> Result<(), AppError> {
+    let mut manifest = load_manifest(project_root, project_id)?;
+    manifest.uploads.retain(|item| item.target != record.target);
+    manifest.uploads.push(record.clone());
+    manifest.updated_at = Utc::now();
+    save_manifest(project_root, project_id, &manifest)
+}
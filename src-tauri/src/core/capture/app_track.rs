@@ -0,0 +1,227 @@
+use crate::domain::models::{AppError, AppFocusSegment, CaptureBounds, WindowRect};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForegroundApp {
+    pub process_name: String,
+    pub window_title: String,
+    pub rect: Option<WindowRect>,
+}
+
+/// 同一应用/窗口（且窗口未移动）连续采样时延长当前片段，否则开启新片段。
+pub fn record_app_sample(segments: &mut Vec<AppFocusSegment>, app: ForegroundApp, t_ms: u64) {
+    if let Some(last) = segments.last_mut() {
+        last.end_ms = last.end_ms.max(t_ms);
+        if last.process_name == app.process_name
+            && last.window_title == app.window_title
+            && last.window_rect == app.rect
+        {
+            return;
+        }
+    }
+    segments.push(AppFocusSegment {
+        start_ms: t_ms,
+        end_ms: t_ms,
+        process_name: app.process_name,
+        window_title: app.window_title,
+        window_rect: app.rect,
+    });
+}
+
+/// 把屏幕坐标的窗口矩形裁到录制区域内并换算到帧坐标；完全不在录制区域内的矩形丢弃。
+pub fn normalize_app_segments(
+    segments: Vec<AppFocusSegment>,
+    bounds: Option<CaptureBounds>,
+    frame: Option<(u32, u32)>,
+) -> Vec<AppFocusSegment> {
+    let Some(bounds) = bounds else {
+        return segments;
+    };
+    segments
+        .into_iter()
+        .map(|mut segment| {
+            segment.window_rect = segment.window_rect.and_then(|rect| {
+                let left = rect.x.max(bounds.x);
+                let top = rect.y.max(bounds.y);
+                let right = (rect.x + rect.width).min(bounds.x + bounds.width);
+                let bottom = (rect.y + rect.height).min(bounds.y + bounds.height);
+                if right <= left || bottom <= top {
+                    return None;
+                }
+                let (x0, y0) = bounds.map_to_frame(left, top, frame);
+                let (x1, y1) = bounds.map_to_frame(right, bottom, frame);
+                Some(WindowRect {
+                    x: x0,
+                    y: y0,
+                    width: x1 - x0,
+                    height: y1 - y0,
+                })
+            });
+            segment
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+pub fn poll_foreground_app() -> Option<ForegroundApp> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::Foundation::RECT;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowRect, GetWindowTextW, GetWindowThreadProcessId,
+    };
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_null() {
+            return None;
+        }
+        let mut title = [0u16; 512];
+        let title_len = GetWindowTextW(hwnd, title.as_mut_ptr(), title.len() as i32).max(0);
+        let window_title = String::from_utf16_lossy(&title[..title_len as usize]);
+
+        let mut process_id = 0u32;
+        GetWindowThreadProcessId(hwnd, &mut process_id);
+        let mut process_name = String::new();
+        if process_id != 0 {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, process_id);
+            if !process.is_null() {
+                let mut image = [0u16; 1024];
+                let mut size = image.len() as u32;
+                if QueryFullProcessImageNameW(
+                    process,
+                    PROCESS_NAME_WIN32,
+                    image.as_mut_ptr(),
+                    &mut size,
+                ) != 0
+                {
+                    let full_path = String::from_utf16_lossy(&image[..size as usize]);
+                    process_name = full_path
+                        .rsplit(['\\', '/'])
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
+                }
+                CloseHandle(process);
+            }
+        }
+        let mut rect: RECT = std::mem::zeroed();
+        let rect =
+            (GetWindowRect(hwnd, &mut rect) != 0 && rect.right > rect.left).then(|| WindowRect {
+                x: rect.left as f64,
+                y: rect.top as f64,
+                width: (rect.right - rect.left) as f64,
+                height: (rect.bottom - rect.top) as f64,
+            });
+        Some(ForegroundApp {
+            process_name,
+            window_title,
+            rect,
+        })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn poll_foreground_app() -> Option<ForegroundApp> {
+    None
+}
+
+pub fn write_app_track(
+    path: &Path,
+    duration_ms: u64,
+    segments: &[AppFocusSegment],
+) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to create app track dir: {error}"),
+                None,
+            )
+        })?;
+    }
+    let payload = segments
+        .iter()
+        .filter(|segment| segment.start_ms < duration_ms)
+        .map(|segment| AppFocusSegment {
+            end_ms: segment.end_ms.min(duration_ms),
+            ..segment.clone()
+        })
+        .collect::<Vec<_>>();
+    let content = serde_json::to_string_pretty(&payload).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to serialize app track: {error}"),
+            None,
+        )
+    })?;
+    std::fs::write(path, content).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to write app track: {error}"),
+            None,
+        )
+    })
+}
+
+pub fn load_app_track(path: &Path) -> Vec<AppFocusSegment> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Vec<AppFocusSegment>>(&raw).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_app_segments, record_app_sample, ForegroundApp};
+    use crate::domain::models::{CaptureBounds, WindowRect};
+
+    fn app(process_name: &str, window_title: &str) -> ForegroundApp {
+        ForegroundApp {
+            process_name: process_name.to_string(),
+            window_title: window_title.to_string(),
+            rect: None,
+        }
+    }
+
+    #[test]
+    fn consecutive_samples_merge_into_segments() {
+        let mut segments = Vec::new();
+        record_app_sample(&mut segments, app("code.exe", "main.rs"), 0);
+        record_app_sample(&mut segments, app("code.exe", "main.rs"), 500);
+        record_app_sample(&mut segments, app("chrome.exe", "Docs"), 1000);
+        record_app_sample(&mut segments, app("chrome.exe", "Docs"), 1500);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[0].start_ms, segments[0].end_ms), (0, 1000));
+        assert_eq!(segments[1].process_name, "chrome.exe");
+        assert_eq!((segments[1].start_ms, segments[1].end_ms), (1000, 1500));
+    }
+
+    #[test]
+    fn window_rect_is_clipped_and_mapped_to_frame() {
+        let mut segments = Vec::new();
+        let mut sample = app("code.exe", "main.rs");
+        sample.rect = Some(WindowRect {
+            x: 1800.0,
+            y: -20.0,
+            width: 800.0,
+            height: 600.0,
+        });
+        record_app_sample(&mut segments, sample, 0);
+        let bounds = CaptureBounds {
+            x: 1920.0,
+            y: 0.0,
+            width: 3840.0,
+            height: 2160.0,
+            scale_factor: 2.0,
+        };
+        let normalized = normalize_app_segments(segments, Some(bounds), Some((1920, 1080)));
+        let rect = normalized[0].window_rect.expect("rect inside capture");
+        assert_eq!((rect.x, rect.y), (0.0, 0.0));
+        assert_eq!((rect.width, rect.height), (340.0, 290.0));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::domain::models::{CaptureBounds, DisplayInfo};
+
+#[cfg(target_os = "windows")]
+pub fn fullscreen_capture_bounds() -> Option<CaptureBounds> {
+    use windows_sys::Win32::Foundation::POINT;
+    use windows_sys::Win32::Graphics::Gdi::{MonitorFromPoint, MONITOR_DEFAULTTOPRIMARY};
+    use windows_sys::Win32::UI::HiDpi::{GetDpiForMonitor, GetDpiForSystem, MDT_EFFECTIVE_DPI};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+        SM_YVIRTUALSCREEN,
+    };
+
+    unsafe {
+        let width = GetSystemMetrics(SM_CXVIRTUALSCREEN);
+        let height = GetSystemMetrics(SM_CYVIRTUALSCREEN);
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        let monitor = MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY);
+        let mut dpi_x = 0u32;
+        let mut dpi_y = 0u32;
+        let dpi = if !monitor.is_null()
+            && GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) == 0
+            && dpi_x > 0
+        {
+            dpi_x
+        } else {
+            GetDpiForSystem()
+        };
+        Some(CaptureBounds {
+            x: GetSystemMetrics(SM_XVIRTUALSCREEN) as f64,
+            y: GetSystemMetrics(SM_YVIRTUALSCREEN) as f64,
+            width: width as f64,
+            height: height as f64,
+            scale_factor: dpi.max(96) as f64 / 96.0,
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn fullscreen_capture_bounds() -> Option<CaptureBounds> {
+    use core_graphics::display::CGDisplay;
+
+    let display = CGDisplay::main();
+    let bounds = display.bounds();
+    let pixels_wide = display.pixels_wide();
+    if bounds.size.width <= 0.0 || bounds.size.height <= 0.0 || pixels_wide == 0 {
+        return None;
+    }
+    Some(CaptureBounds {
+        x: bounds.origin.x,
+        y: bounds.origin.y,
+        width: bounds.size.width,
+        height: bounds.size.height,
+        scale_factor: bounds.size.width / pixels_wide as f64,
+    })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn fullscreen_capture_bounds() -> Option<CaptureBounds> {
+    None
+}
+
+#[cfg(target_os = "windows")]
+pub fn list_displays() -> Vec<DisplayInfo> {
+    use windows_sys::Win32::Foundation::{BOOL, LPARAM, RECT};
+    use windows_sys::Win32::Graphics::Gdi::{
+        EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO,
+    };
+    use windows_sys::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+    use windows_sys::Win32::UI::WindowsAndMessaging::MONITORINFOF_PRIMARY;
+
+    unsafe extern "system" fn collect_monitor(
+        monitor: HMONITOR,
+        _hdc: HDC,
+        _rect: *mut RECT,
+        data: LPARAM,
+    ) -> BOOL {
+        let monitors = &mut *(data as *mut Vec<HMONITOR>);
+        monitors.push(monitor);
+        1
+    }
+
+    let mut monitors: Vec<HMONITOR> = Vec::new();
+    let mut displays = Vec::new();
+    unsafe {
+        EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            Some(collect_monitor),
+            &mut monitors as *mut Vec<HMONITOR> as LPARAM,
+        );
+        for (index, monitor) in monitors.into_iter().enumerate() {
+            let mut info: MONITORINFO = std::mem::zeroed();
+            info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+            if GetMonitorInfoW(monitor, &mut info) == 0 {
+                continue;
+            }
+            let rect = info.rcMonitor;
+            let width = rect.right - rect.left;
+            let height = rect.bottom - rect.top;
+            let mut dpi_x = 0u32;
+            let mut dpi_y = 0u32;
+            let dpi = if GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) == 0 {
+                dpi_x.max(96)
+            } else {
+                96
+            };
+            let primary = info.dwFlags & MONITORINFOF_PRIMARY != 0;
+            displays.push(DisplayInfo {
+                id: format!("{}:{}", rect.left, rect.top),
+                label: format!(
+                    "显示器 {} ({width}x{height}){}",
+                    index + 1,
+                    if primary { " · 主屏" } else { "" }
+                ),
+                primary,
+                bounds: CaptureBounds {
+                    x: rect.left as f64,
+                    y: rect.top as f64,
+                    width: width as f64,
+                    height: height as f64,
+                    scale_factor: dpi as f64 / 96.0,
+                },
+            });
+        }
+    }
+    displays
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn list_displays() -> Vec<DisplayInfo> {
+    Vec::new()
+}
+
+pub fn find_display_bounds(display_id: &str) -> Option<CaptureBounds> {
+    list_displays()
+        .into_iter()
+        .find(|display| display.id == display_id)
+        .map(|display| display.bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::models::CaptureBounds;
+
+    #[test]
+    fn maps_physical_cursor_to_probed_frame() {
+        let bounds = CaptureBounds {
+            x: -1920.0,
+            y: 0.0,
+            width: 3840.0,
+            height: 1080.0,
+            scale_factor: 1.0,
+        };
+        let (x, y) = bounds.map_to_frame(0.0, 540.0, Some((1920, 540)));
+        assert_eq!((x, y), (960.0, 270.0));
+    }
+
+    #[test]
+    fn falls_back_to_dpi_scaled_frame_size() {
+        let bounds = CaptureBounds {
+            x: 0.0,
+            y: 0.0,
+            width: 2880.0,
+            height: 1800.0,
+            scale_factor: 1.5,
+        };
+        let (x, y) = bounds.map_to_frame(1440.0, 900.0, None);
+        assert_eq!((x, y), (960.0, 600.0));
+    }
+
+    #[test]
+    fn contains_flags_points_on_other_monitors() {
+        let bounds = CaptureBounds {
+            x: 0.0,
+            y: 0.0,
+            width: 1920.0,
+            height: 1080.0,
+            scale_factor: 1.0,
+        };
+        assert!(bounds.contains(1919.0, 0.0));
+        assert!(!bounds.contains(1920.0, 500.0));
+        assert!(!bounds.contains(-5.0, 500.0));
+        assert_eq!(bounds.clamp(2500.0, -20.0), (1919.0, 0.0));
+    }
+}
@@ -0,0 +1,371 @@
+use crate::domain::models::{AppError, KeystrokeCaptureMode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+/// 滚轮增量以 1/120 格为单位累积，由光标采样器在每次采样时取走。
+static SCROLL_ACC_X: AtomicI32 = AtomicI32::new(0);
+static SCROLL_ACC_Y: AtomicI32 = AtomicI32::new(0);
+const WHEEL_DELTA: f32 = 120.0;
+/// 左键按下标记，同样由光标采样器取走并写入对应样本。
+static CLICK_PENDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyEvent {
+    pub t_ms: u64,
+    pub label: String,
+}
+
+const VK_SHIFT: u16 = 0x10;
+const VK_CONTROL: u16 = 0x11;
+const VK_MENU: u16 = 0x12;
+const VK_LWIN: u16 = 0x5B;
+const VK_RWIN: u16 = 0x5C;
+const VK_LSHIFT: u16 = 0xA0;
+const VK_RMENU: u16 = 0xA5;
+
+const MASKED_LABEL: &str = "•";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl Modifiers {
+    fn from_pressed(pressed: &HashSet<u16>) -> Self {
+        Self {
+            ctrl: pressed.contains(&VK_CONTROL)
+                || pressed.contains(&0xA2)
+                || pressed.contains(&0xA3),
+            shift: pressed.contains(&VK_SHIFT)
+                || pressed.contains(&VK_LSHIFT)
+                || pressed.contains(&0xA1),
+            alt: pressed.contains(&VK_MENU)
+                || pressed.contains(&0xA4)
+                || pressed.contains(&VK_RMENU),
+            meta: pressed.contains(&VK_LWIN) || pressed.contains(&VK_RWIN),
+        }
+    }
+
+    fn has_command_modifier(&self) -> bool {
+        self.ctrl || self.alt || self.meta
+    }
+}
+
+fn is_modifier(vk: u16) -> bool {
+    matches!(vk, VK_SHIFT | VK_CONTROL | VK_MENU | VK_LWIN | VK_RWIN)
+        || (VK_LSHIFT..=VK_RMENU).contains(&vk)
+}
+
+/// 返回 (标签, 是否为可输入字符)；可输入字符在隐私模式下需要打码。
+pub fn vk_label(vk: u16) -> Option<(String, bool)> {
+    let (label, printable) = match vk {
+        0x30..=0x39 | 0x41..=0x5A => return Some(((vk as u8 as char).to_string(), true)),
+        0x60..=0x69 => return Some(((((vk - 0x60) as u8 + b'0') as char).to_string(), true)),
+        0x70..=0x7B => return Some((format!("F{}", vk - 0x6F), false)),
+        0x20 => ("Space", true),
+        0xBA => (";", true),
+        0xBB => ("=", true),
+        0xBC => (",", true),
+        0xBD => ("-", true),
+        0xBE => (".", true),
+        0xBF => ("/", true),
+        0xC0 => ("`", true),
+        0xDB => ("[", true),
+        0xDC => ("\\", true),
+        0xDD => ("]", true),
+        0xDE => ("'", true),
+        0x08 => ("Backspace", false),
+        0x09 => ("Tab", false),
+        0x0D => ("Enter", false),
+        0x1B => ("Esc", false),
+        0x21 => ("PageUp", false),
+        0x22 => ("PageDown", false),
+        0x23 => ("End", false),
+        0x24 => ("Home", false),
+        0x25 => ("←", false),
+        0x26 => ("↑", false),
+        0x27 => ("→", false),
+        0x28 => ("↓", false),
+        0x2D => ("Insert", false),
+        0x2E => ("Delete", false),
+        _ => return None,
+    };
+    Some((label.to_string(), printable))
+}
+
+pub fn format_key_label(
+    modifiers: Modifiers,
+    vk: u16,
+    mode: KeystrokeCaptureMode,
+) -> Option<String> {
+    if mode == KeystrokeCaptureMode::Off || is_modifier(vk) {
+        return None;
+    }
+    let (key, printable) = vk_label(vk)?;
+    if mode == KeystrokeCaptureMode::Masked && printable && !modifiers.has_command_modifier() {
+        return Some(MASKED_LABEL.to_string());
+    }
+    let mut parts = Vec::new();
+    if modifiers.ctrl {
+        parts.push("Ctrl");
+    }
+    if modifiers.alt {
+        parts.push("Alt");
+    }
+    if modifiers.shift {
+        parts.push("Shift");
+    }
+    if modifiers.meta {
+        parts.push("Win");
+    }
+    parts.push(key.as_str());
+    Some(parts.join("+"))
+}
+
+/// 轮询方式下的按键边沿检测：只对新按下的非修饰键产出事件。
+#[derive(Debug, Default)]
+pub struct KeyStateTracker {
+    previous: HashSet<u16>,
+}
+
+impl KeyStateTracker {
+    pub fn update(
+        &mut self,
+        pressed: HashSet<u16>,
+        t_ms: u64,
+        mode: KeystrokeCaptureMode,
+    ) -> Vec<KeyEvent> {
+        let modifiers = Modifiers::from_pressed(&pressed);
+        let mut fresh = pressed
+            .difference(&self.previous)
+            .copied()
+            .collect::<Vec<_>>();
+        fresh.sort_unstable();
+        let events = fresh
+            .into_iter()
+            .filter_map(|vk| format_key_label(modifiers, vk, mode))
+            .map(|label| KeyEvent { t_ms, label })
+            .collect();
+        self.previous = pressed;
+        events
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn poll_pressed_keys() -> Option<HashSet<u16>> {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
+
+    let mut pressed = HashSet::new();
+    for vk in 0x08u16..=0xDE {
+        let state = unsafe { GetAsyncKeyState(vk as i32) };
+        if (state as u16) & 0x8000 != 0 {
+            pressed.insert(vk);
+        }
+    }
+    Some(pressed)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn poll_pressed_keys() -> Option<HashSet<u16>> {
+    None
+}
+
+/// 取走自上次调用以来累积的滚动量（单位：格），dy 为正表示向下滚动。
+pub fn take_scroll_delta() -> (f32, f32) {
+    let dx = SCROLL_ACC_X.swap(0, Ordering::Relaxed) as f32 / WHEEL_DELTA;
+    let dy = SCROLL_ACC_Y.swap(0, Ordering::Relaxed) as f32 / WHEEL_DELTA;
+    (dx, dy)
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn record_scroll(horizontal: bool, wheel_delta: i16) {
+    if horizontal {
+        SCROLL_ACC_X.fetch_add(i32::from(wheel_delta), Ordering::Relaxed);
+    } else {
+        // Windows 滚轮向上为正，这里统一为“向下滚动为正”。
+        SCROLL_ACC_Y.fetch_sub(i32::from(wheel_delta), Ordering::Relaxed);
+    }
+}
+
+/// 取走自上次调用以来是否发生过左键点击。
+pub fn take_click() -> bool {
+    CLICK_PENDING.swap(false, Ordering::Relaxed)
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn record_click() {
+    CLICK_PENDING.store(true, Ordering::Relaxed);
+}
+
+/// 低级鼠标钩子：累积滚轮增量并记录左键点击。
+pub struct ScrollListener {
+    #[cfg(target_os = "windows")]
+    thread_id: u32,
+    #[cfg(target_os = "windows")]
+    handle: std::thread::JoinHandle<()>,
+}
+
+impl ScrollListener {
+    #[cfg(target_os = "windows")]
+    pub fn start() -> Option<Self> {
+        use windows_sys::Win32::System::Threading::GetCurrentThreadId;
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            CallNextHookEx, DispatchMessageW, GetMessageW, SetWindowsHookExW, TranslateMessage,
+            UnhookWindowsHookEx, MSG, MSLLHOOKSTRUCT, WH_MOUSE_LL, WM_LBUTTONDOWN, WM_MOUSEHWHEEL,
+            WM_MOUSEWHEEL,
+        };
+
+        unsafe extern "system" fn hook_proc(code: i32, wparam: usize, lparam: isize) -> isize {
+            if code >= 0 {
+                let message = wparam as u32;
+                if message == WM_MOUSEWHEEL || message == WM_MOUSEHWHEEL {
+                    let info = &*(lparam as *const MSLLHOOKSTRUCT);
+                    let delta = (info.mouseData >> 16) as u16 as i16;
+                    record_scroll(message == WM_MOUSEHWHEEL, delta);
+                } else if message == WM_LBUTTONDOWN {
+                    record_click();
+                }
+            }
+            CallNextHookEx(std::ptr::null_mut(), code, wparam, lparam)
+        }
+
+        take_scroll_delta();
+        take_click();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handle = std::thread::spawn(move || unsafe {
+            let hook = SetWindowsHookExW(WH_MOUSE_LL, Some(hook_proc), std::ptr::null_mut(), 0);
+            let _ = tx.send((!hook.is_null()).then(|| GetCurrentThreadId()));
+            if hook.is_null() {
+                return;
+            }
+            let mut message: MSG = std::mem::zeroed();
+            while GetMessageW(&mut message, std::ptr::null_mut(), 0, 0) > 0 {
+                TranslateMessage(&message);
+                DispatchMessageW(&message);
+            }
+            UnhookWindowsHookEx(hook);
+        });
+        let thread_id = rx.recv().ok().flatten()?;
+        Some(Self { thread_id, handle })
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn start() -> Option<Self> {
+        None
+    }
+
+    pub fn stop(self) {
+        #[cfg(target_os = "windows")]
+        {
+            use windows_sys::Win32::UI::WindowsAndMessaging::{PostThreadMessageW, WM_QUIT};
+            unsafe {
+                PostThreadMessageW(self.thread_id, WM_QUIT, 0, 0);
+            }
+            let _ = self.handle.join();
+        }
+    }
+}
+
+pub fn write_key_track(path: &Path, duration_ms: u64, events: &[KeyEvent]) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| {
+            AppError::new(
+                "IO_ERROR",
+                format!("failed to create key track dir: {error}"),
+                None,
+            )
+        })?;
+    }
+    let payload = events
+        .iter()
+        .map(|event| KeyEvent {
+            t_ms: event.t_ms.min(duration_ms),
+            label: event.label.clone(),
+        })
+        .collect::<Vec<_>>();
+    let content = serde_json::to_string_pretty(&payload).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to serialize key track: {error}"),
+            None,
+        )
+    })?;
+    std::fs::write(path, content).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to write key track: {error}"),
+            None,
+        )
+    })
+}
+
+pub fn load_key_track(path: &Path) -> Vec<KeyEvent> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Vec<KeyEvent>>(&raw).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_key_label, record_click, record_scroll, take_click, take_scroll_delta,
+        KeyStateTracker, Modifiers,
+    };
+    use crate::domain::models::KeystrokeCaptureMode;
+    use std::collections::HashSet;
+
+    #[test]
+    fn shortcut_label_includes_modifiers() {
+        let modifiers = Modifiers {
+            ctrl: true,
+            shift: true,
+            ..Modifiers::default()
+        };
+        let label = format_key_label(modifiers, 0x50, KeystrokeCaptureMode::Masked);
+        assert_eq!(label.as_deref(), Some("Ctrl+Shift+P"));
+    }
+
+    #[test]
+    fn masked_mode_hides_typed_characters() {
+        let label = format_key_label(Modifiers::default(), 0x41, KeystrokeCaptureMode::Masked);
+        assert_eq!(label.as_deref(), Some("•"));
+        let enter = format_key_label(Modifiers::default(), 0x0D, KeystrokeCaptureMode::Masked);
+        assert_eq!(enter.as_deref(), Some("Enter"));
+    }
+
+    #[test]
+    fn tracker_only_emits_new_presses() {
+        let mut tracker = KeyStateTracker::default();
+        let first = tracker.update(HashSet::from([0x11, 0x43]), 10, KeystrokeCaptureMode::Full);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].label, "Ctrl+C");
+        let held = tracker.update(HashSet::from([0x11, 0x43]), 30, KeystrokeCaptureMode::Full);
+        assert!(held.is_empty());
+    }
+
+    #[test]
+    fn scroll_delta_is_normalized_to_notches() {
+        take_scroll_delta();
+        record_scroll(false, -240);
+        record_scroll(true, 120);
+        let (dx, dy) = take_scroll_delta();
+        assert!((dx - 1.0).abs() < f32::EPSILON);
+        assert!((dy - 2.0).abs() < f32::EPSILON);
+        assert_eq!(take_scroll_delta(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn click_flag_is_consumed_once() {
+        take_click();
+        record_click();
+        assert!(take_click());
+        assert!(!take_click());
+    }
+}
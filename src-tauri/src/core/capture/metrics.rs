@@ -28,6 +28,43 @@ pub fn parse_drop_rates(ffmpeg_stderr: &str) -> (f32, f32) {
     (avg, peak)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioLevel {
+    pub rms_db: f32,
+    pub peak_db: f32,
+}
+
+pub const SILENCE_FLOOR_DB: f32 = -90.0;
+
+fn amplitude_to_db(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return SILENCE_FLOOR_DB;
+    }
+    (20.0 * amplitude.log10()).max(SILENCE_FLOOR_DB)
+}
+
+pub fn compute_audio_level(samples: &[i16]) -> AudioLevel {
+    if samples.is_empty() {
+        return AudioLevel {
+            rms_db: SILENCE_FLOOR_DB,
+            peak_db: SILENCE_FLOOR_DB,
+        };
+    }
+    let full_scale = 32768.0_f32;
+    let mut sum_sq = 0.0_f64;
+    let mut peak = 0.0_f32;
+    for sample in samples {
+        let normalized = *sample as f32 / full_scale;
+        sum_sq += (normalized as f64) * (normalized as f64);
+        peak = peak.max(normalized.abs());
+    }
+    let rms = (sum_sq / samples.len() as f64).sqrt() as f32;
+    AudioLevel {
+        rms_db: amplitude_to_db(rms),
+        peak_db: amplitude_to_db(peak),
+    }
+}
+
 fn extract_numeric(line: &str, key: &str) -> Option<f32> {
     let index = line.find(key)?;
     let segment = &line[index + key.len()..];
@@ -37,7 +74,7 @@ fn extract_numeric(line: &str, key: &str) -> Option<f32> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_drop_rates;
+    use super::{compute_audio_level, parse_drop_rates, SILENCE_FLOOR_DB};
 
     #[test]
     fn parse_drop_rate_from_frame_and_drop_count() {
@@ -54,4 +91,21 @@ mod tests {
         assert!((avg - 2.3).abs() < 0.01);
         assert!((peak - 3.4).abs() < 0.01);
     }
+
+    #[test]
+    fn audio_level_of_silence_hits_floor() {
+        let level = compute_audio_level(&[0; 1600]);
+        assert_eq!(level.rms_db, SILENCE_FLOOR_DB);
+        assert_eq!(level.peak_db, SILENCE_FLOOR_DB);
+    }
+
+    #[test]
+    fn audio_level_of_full_scale_square_is_zero_db() {
+        let samples = (0..1600)
+            .map(|idx| if idx % 2 == 0 { i16::MAX } else { i16::MIN })
+            .collect::<Vec<_>>();
+        let level = compute_audio_level(&samples);
+        assert!(level.rms_db.abs() < 0.01);
+        assert!(level.peak_db.abs() < 0.01);
+    }
 }
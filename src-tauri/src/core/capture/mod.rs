@@ -1,2 +1,7 @@
+pub mod app_track;
+pub mod display;
+pub mod input;
 pub mod metrics;
+pub mod sampling;
 pub mod service;
+pub mod window_follow;
@@ -0,0 +1,93 @@
+pub const MIN_CURSOR_SAMPLE_INTERVAL_MS: u16 = 8;
+pub const MAX_CURSOR_SAMPLE_INTERVAL_MS: u16 = 500;
+
+/// 超过该速度（像素/毫秒）视为快速移动，每次轮询都记录。
+const FAST_MOVE_PX_PER_MS: f32 = 1.0;
+/// 光标位移小于该值视为静止。
+const IDLE_DISTANCE_PX: f32 = 0.5;
+/// 静止时按基础间隔的倍数降低采样密度。
+const IDLE_INTERVAL_MULTIPLIER: u64 = 4;
+
+/// 决定光标轮询频率以及哪些轮询结果写入轨迹。
+///
+/// 固定模式下每次轮询都记录；自适应模式以基础间隔的 1/4 轮询，
+/// 快速移动时逐次记录，普通移动按基础间隔记录，静止时进一步稀疏。
+#[derive(Debug, Clone)]
+pub struct CursorSampler {
+    interval_ms: u64,
+    adaptive: bool,
+    last: Option<(u64, f32, f32)>,
+}
+
+impl CursorSampler {
+    pub fn new(interval_ms: u16, adaptive: bool) -> Self {
+        Self {
+            interval_ms: u64::from(
+                interval_ms.clamp(MIN_CURSOR_SAMPLE_INTERVAL_MS, MAX_CURSOR_SAMPLE_INTERVAL_MS),
+            ),
+            adaptive,
+            last: None,
+        }
+    }
+
+    pub fn poll_interval_ms(&self) -> u64 {
+        if self.adaptive {
+            (self.interval_ms / 4).max(u64::from(MIN_CURSOR_SAMPLE_INTERVAL_MS))
+        } else {
+            self.interval_ms
+        }
+    }
+
+    /// `force` 用于必须落盘的样本（例如携带滚动增量）。
+    pub fn should_record(&mut self, t_ms: u64, x: f32, y: f32, force: bool) -> bool {
+        let record = if !self.adaptive || force {
+            true
+        } else {
+            match self.last {
+                None => true,
+                Some((last_t, last_x, last_y)) => {
+                    let dt = t_ms.saturating_sub(last_t);
+                    let distance = ((x - last_x).powi(2) + (y - last_y).powi(2)).sqrt();
+                    if distance < IDLE_DISTANCE_PX {
+                        dt >= self.interval_ms * IDLE_INTERVAL_MULTIPLIER
+                    } else {
+                        distance / dt.max(1) as f32 >= FAST_MOVE_PX_PER_MS || dt >= self.interval_ms
+                    }
+                }
+            }
+        };
+        if record {
+            self.last = Some((t_ms, x, y));
+        }
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CursorSampler;
+
+    #[test]
+    fn fixed_sampler_records_every_poll() {
+        let mut sampler = CursorSampler::new(1000, false);
+        assert_eq!(sampler.poll_interval_ms(), 500);
+        assert!(sampler.should_record(0, 10.0, 10.0, false));
+        assert!(sampler.should_record(500, 10.0, 10.0, false));
+    }
+
+    #[test]
+    fn adaptive_sampler_densifies_fast_motion_and_thins_idle() {
+        let mut sampler = CursorSampler::new(120, true);
+        assert_eq!(sampler.poll_interval_ms(), 30);
+        assert!(sampler.should_record(0, 0.0, 0.0, false));
+        // 30ms 内移动 90px，属于快速移动。
+        assert!(sampler.should_record(30, 90.0, 0.0, false));
+        // 缓慢移动未到基础间隔时跳过。
+        assert!(!sampler.should_record(60, 95.0, 0.0, false));
+        assert!(sampler.should_record(150, 100.0, 0.0, false));
+        // 静止时直到 4 倍间隔才补一个样本。
+        assert!(!sampler.should_record(300, 100.0, 0.0, false));
+        assert!(sampler.should_record(630, 100.0, 0.0, false));
+        assert!(sampler.should_record(660, 100.0, 0.0, true));
+    }
+}
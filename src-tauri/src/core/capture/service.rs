@@ -1,5 +1,5 @@
-use crate::domain::models::RecordingDevice;
-use crate::infra::ffmpeg::command::ffmpeg_supports_input_format;
+use crate::domain::models::{AppError, RecordingDevice};
+use crate::infra::ffmpeg::command::{ffmpeg_bin, ffmpeg_supports_input_format};
 use serde::Serialize;
 use std::process::{Command, Stdio};
 
@@ -12,6 +12,27 @@ pub struct PlatformCapability {
     pub supports_microphone: bool,
     pub supports_system_audio: bool,
     pub system_audio_degrade_message: Option<String>,
+    /// 按键记录依赖 GetAsyncKeyState 轮询，目前仅 Windows 可用。
+    pub supports_keystroke_capture: bool,
+    pub frame_rate_presets: Vec<u8>,
+}
+
+pub const FRAME_RATE_PRESETS: [u8; 5] = [24, 25, 30, 48, 60];
+pub const MIN_FRAME_RATE: u8 = 10;
+pub const MAX_FRAME_RATE: u8 = 120;
+
+pub fn validate_frame_rate(frame_rate: u8) -> Result<u8, AppError> {
+    if !(MIN_FRAME_RATE..=MAX_FRAME_RATE).contains(&frame_rate) {
+        return Err(AppError::new(
+            "INVALID_FRAME_RATE",
+            format!("frame rate {frame_rate} is out of range {MIN_FRAME_RATE}-{MAX_FRAME_RATE}"),
+            Some("请选择 24/25/30/48/60 等常用帧率".to_string()),
+        )
+        .with_param("frameRate", frame_rate)
+        .with_param("min", MIN_FRAME_RATE)
+        .with_param("max", MAX_FRAME_RATE));
+    }
+    Ok(frame_rate)
 }
 
 pub fn platform_capability() -> PlatformCapability {
@@ -29,6 +50,8 @@ pub fn platform_capability() -> PlatformCapability {
             } else {
                 Some("当前 ffmpeg 不支持 WASAPI，系统音频将自动关闭".to_string())
             },
+            supports_keystroke_capture: true,
+            frame_rate_presets: FRAME_RATE_PRESETS.to_vec(),
         }
     }
     #[cfg(target_os = "macos")]
@@ -40,6 +63,8 @@ pub fn platform_capability() -> PlatformCapability {
             supports_microphone: true,
             supports_system_audio: false,
             system_audio_degrade_message: Some("当前环境不支持系统音频，仅录制麦克风".to_string()),
+            supports_keystroke_capture: false,
+            frame_rate_presets: FRAME_RATE_PRESETS.to_vec(),
         }
     }
     #[cfg(not(any(target_os = "windows", target_os = "macos")))]
@@ -51,100 +76,184 @@ pub fn platform_capability() -> PlatformCapability {
             supports_microphone: false,
             supports_system_audio: false,
             system_audio_degrade_message: Some("当前平台不在 MVP 支持范围".to_string()),
+            supports_keystroke_capture: false,
+            frame_rate_presets: FRAME_RATE_PRESETS.to_vec(),
         }
     }
 }
 
 pub fn list_audio_devices() -> Vec<RecordingDevice> {
-    #[cfg(target_os = "windows")]
-    {
-        let output = Command::new(
-            std::env::var("FOCUSLENS_FFMPEG_PATH").unwrap_or_else(|_| "ffmpeg".to_string()),
-        )
-        .arg("-hide_banner")
-        .arg("-list_devices")
-        .arg("true")
-        .arg("-f")
-        .arg("dshow")
-        .arg("-i")
-        .arg("dummy")
-        .stdout(Stdio::null())
-        .stderr(Stdio::piped())
-        .output();
-        if let Ok(output) = output {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let mut devices = Vec::new();
-            for line in stderr.lines() {
-                let trimmed = line.trim();
-                if !trimmed.contains('"') {
-                    continue;
-                }
-                if !trimmed.to_lowercase().contains("audio") {
-                    continue;
-                }
-                if let Some(label) = extract_quoted(trimmed) {
-                    devices.push(RecordingDevice {
-                        id: label.clone(),
-                        label,
-                        kind: "microphone".to_string(),
-                    });
-                }
-            }
-            if !devices.is_empty() {
-                return devices;
-            }
+    probe_audio_devices().unwrap_or_else(|| {
+        vec![RecordingDevice {
+            id: "default".to_string(),
+            label: "Default Microphone".to_string(),
+            kind: "microphone".to_string(),
+        }]
+    })
+}
+
+/// 录制与电平监测实际使用的麦克风。`default` 不是采集设备能识别的名称，
+/// 按枚举到的第一个音频输入处理；未选择或枚举不到设备时返回 None。
+pub fn resolve_microphone_device(device_id: Option<&str>) -> Option<String> {
+    let device_id = device_id.map(str::trim).filter(|id| !id.is_empty())?;
+    if device_id != "default" {
+        return Some(device_id.to_string());
+    }
+    probe_audio_devices()?
+        .into_iter()
+        .map(|device| device.id)
+        .find(|id| id != "default")
+}
+
+/// 通过 ffmpeg 枚举音频输入设备；枚举失败或没有设备时返回 None，
+/// 设备监测据此区分“设备被拔出”和“这一轮没能读到列表”。
+pub fn probe_audio_devices() -> Option<Vec<RecordingDevice>> {
+    list_ffmpeg_devices(DeviceKind::Audio)
+}
+
+/// 摄像头等视频输入设备，供摄像头叠加录制与设备选择使用；没有设备时返回空列表。
+/// macOS 的屏幕采集源（Capture screen N）不计入。
+pub fn list_video_input_devices() -> Vec<RecordingDevice> {
+    list_ffmpeg_devices(DeviceKind::Video).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeviceKind {
+    Audio,
+    Video,
+}
+
+impl DeviceKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Audio => "microphone",
+            Self::Video => "camera",
         }
     }
-    #[cfg(target_os = "macos")]
-    {
-        let output = Command::new(
-            std::env::var("FOCUSLENS_FFMPEG_PATH").unwrap_or_else(|_| "ffmpeg".to_string()),
-        )
-        .arg("-hide_banner")
-        .arg("-f")
-        .arg("avfoundation")
-        .arg("-list_devices")
-        .arg("true")
-        .arg("-i")
-        .arg("")
+}
+
+#[cfg(target_os = "windows")]
+const DEVICE_LISTING_FORMAT: Option<&str> = Some("dshow");
+#[cfg(target_os = "macos")]
+const DEVICE_LISTING_FORMAT: Option<&str> = Some("avfoundation");
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const DEVICE_LISTING_FORMAT: Option<&str> = None;
+
+fn list_ffmpeg_devices(kind: DeviceKind) -> Option<Vec<RecordingDevice>> {
+    let format = DEVICE_LISTING_FORMAT?;
+    let input = if format == "dshow" { "dummy" } else { "" };
+    let output = Command::new(ffmpeg_bin())
+        .args([
+            "-hide_banner",
+            "-f",
+            format,
+            "-list_devices",
+            "true",
+            "-i",
+            input,
+        ])
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
-        .output();
-        if let Ok(output) = output {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let mut devices = Vec::new();
-            let mut in_audio_section = false;
-            for line in stderr.lines() {
-                let trimmed = line.trim();
-                if trimmed.contains("AVFoundation audio devices") {
-                    in_audio_section = true;
-                    continue;
-                }
-                if trimmed.contains("AVFoundation video devices") {
-                    in_audio_section = false;
-                    continue;
-                }
-                if !in_audio_section {
-                    continue;
-                }
-                if let Some((id, label)) = extract_device_index_and_label(trimmed) {
-                    devices.push(RecordingDevice {
-                        id,
-                        label,
-                        kind: "microphone".to_string(),
-                    });
-                }
-            }
-            if !devices.is_empty() {
-                return devices;
-            }
+        .output()
+        .ok()?;
+    let listing = String::from_utf8_lossy(&output.stderr);
+    let devices = if format == "dshow" {
+        parse_dshow_devices(&listing, kind)
+    } else {
+        parse_avfoundation_devices(&listing, kind)
+    };
+    (!devices.is_empty()).then_some(devices)
+}
+
+/// 兼容两种 dshow 列表格式：旧版按 “DirectShow video/audio devices” 分段，
+/// 新版在每个设备名后标注 `(video)` / `(audio)`；跳过 “Alternative name” 行。
+fn parse_dshow_devices(listing: &str, kind: DeviceKind) -> Vec<RecordingDevice> {
+    let mut section = None;
+    let mut devices = Vec::new();
+    for line in listing.lines() {
+        let lower = line.to_lowercase();
+        if lower.contains("directshow video devices") {
+            section = Some(DeviceKind::Video);
+            continue;
+        }
+        if lower.contains("directshow audio devices") {
+            section = Some(DeviceKind::Audio);
+            continue;
+        }
+        if lower.contains("alternative name") {
+            continue;
+        }
+        let Some(label) = extract_quoted(line) else {
+            continue;
+        };
+        let tagged = if lower.trim_end().ends_with("(video)") {
+            Some(DeviceKind::Video)
+        } else if lower.trim_end().ends_with("(audio)") {
+            Some(DeviceKind::Audio)
+        } else {
+            section
+        };
+        if tagged == Some(kind) {
+            devices.push(RecordingDevice {
+                id: label.clone(),
+                label,
+                kind: kind.label().to_string(),
+            });
         }
     }
-    vec![RecordingDevice {
-        id: "default".to_string(),
-        label: "Default Microphone".to_string(),
-        kind: "microphone".to_string(),
-    }]
+    devices
+}
+
+/// avfoundation 以 `[索引] 名称` 列出设备，设备 id 为索引。
+fn parse_avfoundation_devices(listing: &str, kind: DeviceKind) -> Vec<RecordingDevice> {
+    let mut section = None;
+    let mut devices = Vec::new();
+    for line in listing.lines() {
+        if line.contains("AVFoundation video devices") {
+            section = Some(DeviceKind::Video);
+            continue;
+        }
+        if line.contains("AVFoundation audio devices") {
+            section = Some(DeviceKind::Audio);
+            continue;
+        }
+        if section != Some(kind) {
+            continue;
+        }
+        let Some((id, label)) = extract_device_index_and_label(line) else {
+            continue;
+        };
+        if kind == DeviceKind::Video && label.starts_with("Capture screen") {
+            continue;
+        }
+        devices.push(RecordingDevice {
+            id,
+            label,
+            kind: kind.label().to_string(),
+        });
+    }
+    devices
+}
+
+/// 按设备 id 比较两次枚举结果，返回（新增，移除）的设备。
+pub fn diff_audio_devices(
+    previous: &[RecordingDevice],
+    current: &[RecordingDevice],
+) -> (Vec<RecordingDevice>, Vec<RecordingDevice>) {
+    let missing_from = |list: &[RecordingDevice], device: &RecordingDevice| {
+        !list.iter().any(|item| item.id == device.id)
+    };
+    let added = current
+        .iter()
+        .filter(|device| missing_from(previous, device))
+        .cloned()
+        .collect();
+    let removed = previous
+        .iter()
+        .filter(|device| missing_from(current, device))
+        .cloned()
+        .collect();
+    (added, removed)
 }
 
 fn extract_quoted(line: &str) -> Option<String> {
@@ -153,11 +262,113 @@ fn extract_quoted(line: &str) -> Option<String> {
     Some(line[start + 1..start + 1 + end].to_string())
 }
 
-#[cfg(target_os = "macos")]
+/// 去掉 `[AVFoundation indev @ 0x…]` 日志前缀后解析 `[0] FaceTime HD Camera`。
 fn extract_device_index_and_label(line: &str) -> Option<(String, String)> {
-    let left = line.find('[')?;
-    let right = line[left + 1..].find(']')?;
-    let idx = line[left + 1..left + 1 + right].trim().to_string();
-    let label = extract_quoted(line)?;
-    Some((idx, label))
+    let body = line.split_once("] ")?.1.trim();
+    let rest = body.strip_prefix('[')?;
+    let (index, label) = rest.split_once(']')?;
+    let index = index.trim();
+    if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((index.to_string(), label.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        diff_audio_devices, parse_avfoundation_devices, parse_dshow_devices,
+        resolve_microphone_device, validate_frame_rate, DeviceKind, FRAME_RATE_PRESETS,
+    };
+    use crate::domain::models::RecordingDevice;
+
+    #[test]
+    fn named_microphones_resolve_without_probing() {
+        assert_eq!(
+            resolve_microphone_device(Some(" USB Mic ")).as_deref(),
+            Some("USB Mic")
+        );
+        assert!(resolve_microphone_device(Some("  ")).is_none());
+        assert!(resolve_microphone_device(None).is_none());
+    }
+
+    #[test]
+    fn frame_rate_presets_are_valid() {
+        for fps in FRAME_RATE_PRESETS {
+            assert_eq!(validate_frame_rate(fps).unwrap(), fps);
+        }
+    }
+
+    #[test]
+    fn frame_rate_out_of_range_is_rejected() {
+        assert_eq!(
+            validate_frame_rate(0).unwrap_err().code,
+            "INVALID_FRAME_RATE"
+        );
+        assert_eq!(
+            validate_frame_rate(240).unwrap_err().code,
+            "INVALID_FRAME_RATE"
+        );
+    }
+
+    #[test]
+    fn device_diff_reports_added_and_removed_by_id() {
+        let device = |id: &str| RecordingDevice {
+            id: id.to_string(),
+            label: format!("Mic {id}"),
+            kind: "microphone".to_string(),
+        };
+        let (added, removed) =
+            diff_audio_devices(&[device("a"), device("b")], &[device("b"), device("c")]);
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].id, "c");
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].id, "a");
+    }
+
+    #[test]
+    fn dshow_listing_is_split_by_device_kind() {
+        let tagged = r#"[dshow @ 000001] "Integrated Camera" (video)
+[dshow @ 000001]   Alternative name "@device_pnp_\\?\usb#vid_04f2"
+[dshow @ 000001] "Microphone (Realtek Audio)" (audio)
+[dshow @ 000001]   Alternative name "@device_cm_{33D9A762}\wave_{1}""#;
+        let cameras = parse_dshow_devices(tagged, DeviceKind::Video);
+        assert_eq!(cameras.len(), 1);
+        assert_eq!(cameras[0].id, "Integrated Camera");
+        assert_eq!(cameras[0].kind, "camera");
+        let microphones = parse_dshow_devices(tagged, DeviceKind::Audio);
+        assert_eq!(microphones.len(), 1);
+        assert_eq!(microphones[0].label, "Microphone (Realtek Audio)");
+
+        let sectioned = r#"[dshow @ 000001] DirectShow video devices (some may be both video and audio devices)
+[dshow @ 000001]  "OBS Virtual Camera"
+[dshow @ 000001] DirectShow audio devices
+[dshow @ 000001]  "Line In""#;
+        assert_eq!(
+            parse_dshow_devices(sectioned, DeviceKind::Video)[0].id,
+            "OBS Virtual Camera"
+        );
+        assert_eq!(
+            parse_dshow_devices(sectioned, DeviceKind::Audio)[0].id,
+            "Line In"
+        );
+    }
+
+    #[test]
+    fn avfoundation_listing_uses_indices_and_skips_screens() {
+        let listing = "[AVFoundation indev @ 0x7f8] AVFoundation video devices:
+[AVFoundation indev @ 0x7f8] [0] FaceTime HD Camera
+[AVFoundation indev @ 0x7f8] [1] Capture screen 0
+[AVFoundation indev @ 0x7f8] AVFoundation audio devices:
+[AVFoundation indev @ 0x7f8] [0] MacBook Pro Microphone";
+        let cameras = parse_avfoundation_devices(listing, DeviceKind::Video);
+        assert_eq!(cameras.len(), 1);
+        assert_eq!(
+            (cameras[0].id.as_str(), cameras[0].label.as_str()),
+            ("0", "FaceTime HD Camera")
+        );
+        let microphones = parse_avfoundation_devices(listing, DeviceKind::Audio);
+        assert_eq!(microphones.len(), 1);
+        assert_eq!(microphones[0].label, "MacBook Pro Microphone");
+    }
 }
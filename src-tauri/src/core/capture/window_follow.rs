@@ -0,0 +1,106 @@
+use crate::domain::models::CaptureBounds;
+
+/// 窗口录制期间目标窗口客户区的位置采样。gdigrab 按窗口句柄抓取，窗口移动时画面随之移动，
+/// 但光标采样是屏幕坐标，需要按当时的窗口位置换算才能与画面对齐。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowBoundsSample {
+    pub t_ms: u64,
+    pub bounds: CaptureBounds,
+}
+
+/// 只在窗口移动或缩放时追加采样，静止期间不增长。
+pub fn record_bounds_sample(track: &mut Vec<WindowBoundsSample>, t_ms: u64, bounds: CaptureBounds) {
+    if track.last().is_some_and(|last| last.bounds == bounds) {
+        return;
+    }
+    track.push(WindowBoundsSample { t_ms, bounds });
+}
+
+/// 取时刻 `t_ms` 生效的窗口位置；早于首个采样时使用首个采样。
+pub fn bounds_at(track: &[WindowBoundsSample], t_ms: u64) -> Option<CaptureBounds> {
+    let index = track.partition_point(|sample| sample.t_ms <= t_ms);
+    track
+        .get(index.saturating_sub(1))
+        .map(|sample| sample.bounds)
+}
+
+/// 按 gdigrab 的 `title=窗口标题` 目标查找窗口客户区的屏幕坐标；窗口不存在或已最小化时返回 None。
+#[cfg(target_os = "windows")]
+pub fn find_window_bounds(target: &str) -> Option<CaptureBounds> {
+    use windows_sys::Win32::Foundation::{POINT, RECT};
+    use windows_sys::Win32::Graphics::Gdi::ClientToScreen;
+    use windows_sys::Win32::UI::HiDpi::GetDpiForWindow;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{FindWindowW, GetClientRect};
+
+    let title = target.strip_prefix("title=")?;
+    let wide = title
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect::<Vec<u16>>();
+    unsafe {
+        let hwnd = FindWindowW(std::ptr::null(), wide.as_ptr());
+        if hwnd.is_null() {
+            return None;
+        }
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        };
+        if GetClientRect(hwnd, &mut rect) == 0 {
+            return None;
+        }
+        let mut origin = POINT { x: 0, y: 0 };
+        if ClientToScreen(hwnd, &mut origin) == 0 {
+            return None;
+        }
+        let width = rect.right - rect.left;
+        let height = rect.bottom - rect.top;
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        Some(CaptureBounds {
+            x: origin.x as f64,
+            y: origin.y as f64,
+            width: width as f64,
+            height: height as f64,
+            scale_factor: GetDpiForWindow(hwnd).max(96) as f64 / 96.0,
+        })
+    }
+}
+
+/// 其他平台的窗口录制会降级为整屏采集，沿用显示器坐标换算。
+#[cfg(not(target_os = "windows"))]
+pub fn find_window_bounds(_target: &str) -> Option<CaptureBounds> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bounds_at, record_bounds_sample};
+    use crate::domain::models::CaptureBounds;
+
+    fn bounds(x: f64) -> CaptureBounds {
+        CaptureBounds {
+            x,
+            y: 100.0,
+            width: 800.0,
+            height: 600.0,
+            scale_factor: 1.0,
+        }
+    }
+
+    #[test]
+    fn bounds_track_records_moves_and_resolves_by_time() {
+        let mut track = Vec::new();
+        record_bounds_sample(&mut track, 0, bounds(0.0));
+        record_bounds_sample(&mut track, 200, bounds(0.0));
+        record_bounds_sample(&mut track, 400, bounds(300.0));
+        assert_eq!(track.len(), 2);
+        assert_eq!(bounds_at(&track, 0), Some(bounds(0.0)));
+        assert_eq!(bounds_at(&track, 399), Some(bounds(0.0)));
+        assert_eq!(bounds_at(&track, 400), Some(bounds(300.0)));
+        assert_eq!(bounds_at(&[], 400), None);
+    }
+}
@@ -1,2 +1,4 @@
+pub mod presets;
 pub mod quality;
+pub mod queue;
 pub mod service;
@@ -0,0 +1,90 @@
+use crate::domain::models::{ExportPreset, ExportProfile, Resolution};
+
+// 预留给 AAC 音轨（128k）与封装开销的码率，按 Mbps 计。
+const AUDIO_OVERHEAD_MBPS: f64 = 0.2;
+// 目标体积留 10% 余量，避免编码器码率波动导致超限。
+const SIZE_SAFETY_RATIO: f64 = 0.9;
+const MIN_PRESET_BITRATE_MBPS: u8 = 1;
+
+/// 内置导出预设，首次生成 settings.json 时写入，用户可在文件中自行增改。
+pub fn builtin_export_presets() -> Vec<ExportPreset> {
+    let preset = |id: &str, name: &str, description: &str, resolution, bitrate_mbps, fps, max| {
+        ExportPreset {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            profile: ExportProfile {
+                resolution,
+                bitrate_mbps,
+                fps,
+                ..ExportProfile::default()
+            },
+            max_size_mb: max,
+        }
+    };
+    vec![
+        preset(
+            "youtube",
+            "YouTube",
+            "1080p 高码率，适合上传后由平台二次压缩",
+            Resolution::R1080p,
+            16,
+            60,
+            None,
+        ),
+        preset(
+            "x",
+            "X (Twitter)",
+            "1080p，控制在平台 512MB 上限以内",
+            Resolution::R1080p,
+            8,
+            30,
+            Some(512),
+        ),
+        preset(
+            "slack",
+            "Slack",
+            "1080p 中等码率，频道内可直接预览",
+            Resolution::R1080p,
+            6,
+            30,
+            Some(1024),
+        ),
+        preset(
+            "email",
+            "邮件附件",
+            "720p，按时长压缩到 25MB 以内",
+            Resolution::R720p,
+            4,
+            30,
+            Some(25),
+        ),
+    ]
+}
+
+/// 按体积上限换算码率：超出上限时按时长反推，最低 1 Mbps。
+pub fn bitrate_for_size_limit(bitrate_mbps: u8, max_size_mb: Option<u32>, duration_ms: u64) -> u8 {
+    let (Some(max_size_mb), true) = (max_size_mb, duration_ms > 0) else {
+        return bitrate_mbps;
+    };
+    let duration_sec = duration_ms as f64 / 1000.0;
+    let budget = max_size_mb as f64 * 8.0 * SIZE_SAFETY_RATIO / duration_sec - AUDIO_OVERHEAD_MBPS;
+    let capped = budget
+        .floor()
+        .clamp(MIN_PRESET_BITRATE_MBPS as f64, u8::MAX as f64) as u8;
+    bitrate_mbps.min(capped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bitrate_for_size_limit;
+
+    #[test]
+    fn size_limit_lowers_bitrate_for_long_recordings() {
+        // 25MB、60 秒：25*8*0.9/60-0.2 = 2.8 Mbps。
+        assert_eq!(bitrate_for_size_limit(4, Some(25), 60_000), 2);
+        assert_eq!(bitrate_for_size_limit(4, Some(25), 10_000), 4);
+        assert_eq!(bitrate_for_size_limit(4, Some(25), 3_600_000), 1);
+        assert_eq!(bitrate_for_size_limit(16, None, 3_600_000), 16);
+    }
+}
@@ -1,9 +1,33 @@
+use crate::domain::models::TimeRange;
+
+// 单段黑屏超过 2 秒基本可以判定为采集或编码异常。
+const MAX_BLACK_RANGE_MS: u64 = 2000;
+// 录屏允许长时间静止，只有冻结或静音覆盖绝大部分成片时才判定为异常。
+const MAX_FROZEN_COVERAGE: f64 = 0.9;
+const MAX_SILENT_COVERAGE: f64 = 0.95;
+
 #[derive(Debug, Clone)]
 pub struct QualityGateResult {
     pub passed: bool,
     pub reasons: Vec<String>,
 }
 
+/// ffmpeg 统计的掉帧率以录制帧为分母；导出帧率低于录制帧率时会按比例主动丢帧。
+/// 扣除这部分后换算为相对导出帧的掉帧率，再按统一阈值判断。缺失数据（负数）原样返回。
+pub fn export_drop_rate(measured: f32, recording_fps: u8, export_fps: u8) -> f32 {
+    if !measured.is_finite()
+        || measured < 0.0
+        || recording_fps == 0
+        || export_fps == 0
+        || export_fps >= recording_fps
+    {
+        return measured;
+    }
+    let kept = export_fps as f32 / recording_fps as f32;
+    let conversion = (1.0 - kept) * 100.0;
+    ((measured - conversion) / kept).max(0.0)
+}
+
 pub fn validate_mvp_quality(
     av_offset_ms: i64,
     avg_drop_rate: f32,
@@ -32,9 +56,69 @@ pub fn validate_mvp_quality(
     }
 }
 
+/// 按内容分析结果校验成片：长黑屏、几乎全程冻结、有麦克风却几乎全程静音均不达标。
+pub fn validate_content_ranges(
+    black_ranges: &[TimeRange],
+    frozen_ranges: &[TimeRange],
+    silent_ranges: &[TimeRange],
+    duration_ms: u64,
+    expect_audio: bool,
+) -> QualityGateResult {
+    let mut reasons = Vec::new();
+    if let Some(range) = black_ranges
+        .iter()
+        .find(|range| range_len_ms(range) > MAX_BLACK_RANGE_MS)
+    {
+        reasons.push(format!(
+            "检测到黑屏 {} (单段阈值 <={:.1}s)",
+            format_range(range),
+            MAX_BLACK_RANGE_MS as f64 / 1000.0
+        ));
+    }
+    let frozen = coverage(frozen_ranges, duration_ms);
+    if frozen > MAX_FROZEN_COVERAGE {
+        reasons.push(format!(
+            "画面冻结占比 {:.0}%，疑似导出画面未更新",
+            frozen * 100.0
+        ));
+    }
+    let silent = coverage(silent_ranges, duration_ms);
+    if expect_audio && silent > MAX_SILENT_COVERAGE {
+        reasons.push(format!(
+            "静音占比 {:.0}%，请确认麦克风是否正常录入",
+            silent * 100.0
+        ));
+    }
+    QualityGateResult {
+        passed: reasons.is_empty(),
+        reasons,
+    }
+}
+
+fn range_len_ms(range: &TimeRange) -> u64 {
+    range.end_ms.saturating_sub(range.start_ms)
+}
+
+fn coverage(ranges: &[TimeRange], duration_ms: u64) -> f64 {
+    if duration_ms == 0 {
+        return 0.0;
+    }
+    let total = ranges.iter().map(range_len_ms).sum::<u64>();
+    total.min(duration_ms) as f64 / duration_ms as f64
+}
+
+fn format_range(range: &TimeRange) -> String {
+    format!(
+        "{:.1}s-{:.1}s",
+        range.start_ms as f64 / 1000.0,
+        range.end_ms as f64 / 1000.0
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::validate_mvp_quality;
+    use super::{export_drop_rate, validate_content_ranges, validate_mvp_quality};
+    use crate::domain::models::TimeRange;
 
     #[test]
     fn quality_gate_passes_when_all_metrics_in_range() {
@@ -56,4 +140,34 @@ mod tests {
         assert!(!result.passed);
         assert_eq!(result.reasons.len(), 3);
     }
+
+    #[test]
+    fn drop_rate_excludes_frame_rate_conversion() {
+        assert_eq!(export_drop_rate(1.5, 30, 60), 1.5);
+        assert_eq!(export_drop_rate(-1.0, 60, 30), -1.0);
+        assert!((export_drop_rate(51.0, 60, 30) - 2.0).abs() < 0.01);
+        assert_eq!(export_drop_rate(40.0, 60, 30), 0.0);
+        assert!(validate_mvp_quality(20, export_drop_rate(51.0, 60, 30), 0.0).passed);
+        // 扣除转换丢帧后真实掉帧仍有 6%，不能被放宽的阈值吸收。
+        let result = validate_mvp_quality(
+            20,
+            export_drop_rate(53.0, 60, 30),
+            export_drop_rate(53.0, 60, 30),
+        );
+        assert_eq!(result.reasons.len(), 2);
+    }
+
+    #[test]
+    fn content_gate_flags_long_black_and_full_silence() {
+        let range = |start_ms, end_ms| TimeRange { start_ms, end_ms };
+        let black = [range(0, 800), range(5_000, 8_000)];
+        let silent = [range(0, 9_800)];
+        let result = validate_content_ranges(&black, &[], &silent, 10_000, true);
+        assert_eq!(result.reasons.len(), 2);
+        assert!(result.reasons[0].contains("5.0s-8.0s"));
+
+        let frozen = [range(0, 6_000)];
+        let result = validate_content_ranges(&[range(0, 800)], &frozen, &silent, 10_000, false);
+        assert!(result.passed);
+    }
 }
@@ -0,0 +1,161 @@
+use crate::domain::models::AppError;
+use std::collections::VecDeque;
+
+pub const DEFAULT_MAX_CONCURRENT_EXPORTS: usize = 1;
+pub const MAX_CONCURRENT_EXPORTS_LIMIT: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedExport {
+    pub task_id: String,
+    pub project_id: String,
+}
+
+/// 导出任务队列：等待中的任务按 FIFO 出队，运行数不超过并发上限。
+/// 同一项目同时只允许一个任务排队或运行，避免覆盖同一个 renders/output.mp4。
+#[derive(Debug)]
+pub struct ExportQueue {
+    pending: VecDeque<QueuedExport>,
+    running: Vec<QueuedExport>,
+    max_concurrent: usize,
+}
+
+impl Default for ExportQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_EXPORTS)
+    }
+}
+
+impl ExportQueue {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            pending: VecDeque::new(),
+            running: Vec::new(),
+            max_concurrent: max_concurrent.clamp(1, MAX_CONCURRENT_EXPORTS_LIMIT),
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    pub fn set_max_concurrent(&mut self, max_concurrent: usize) {
+        self.max_concurrent = max_concurrent.clamp(1, MAX_CONCURRENT_EXPORTS_LIMIT);
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &QueuedExport> {
+        self.pending.iter()
+    }
+
+    pub fn running(&self) -> impl Iterator<Item = &QueuedExport> {
+        self.running.iter()
+    }
+
+    pub fn contains_project(&self, project_id: &str) -> bool {
+        self.pending
+            .iter()
+            .chain(self.running.iter())
+            .any(|item| item.project_id == project_id)
+    }
+
+    pub fn enqueue(&mut self, task_id: &str, project_id: &str) -> Result<(), AppError> {
+        if self.contains_project(project_id) {
+            return Err(AppError::new(
+                "EXPORT_ALREADY_ACTIVE",
+                "当前项目已有导出任务进行中",
+                Some("请等待任务完成后再发起新导出".to_string()),
+            ));
+        }
+        self.pending.push_back(QueuedExport {
+            task_id: task_id.to_string(),
+            project_id: project_id.to_string(),
+        });
+        Ok(())
+    }
+
+    /// 取出下一个可运行的任务并标记为运行中；已达并发上限时返回 None。
+    pub fn start_next(&mut self) -> Option<QueuedExport> {
+        if self.running.len() >= self.max_concurrent {
+            return None;
+        }
+        let next = self.pending.pop_front()?;
+        self.running.push(next.clone());
+        Some(next)
+    }
+
+    pub fn finish(&mut self, task_id: &str) {
+        self.running.retain(|item| item.task_id != task_id);
+    }
+
+    /// 从等待队列移除任务，返回是否确实在排队（运行中的任务不受影响）。
+    pub fn remove_pending(&mut self, task_id: &str) -> bool {
+        let before = self.pending.len();
+        self.pending.retain(|item| item.task_id != task_id);
+        self.pending.len() != before
+    }
+
+    /// 按给定顺序重排等待中的任务，`task_ids` 必须恰好覆盖当前全部等待任务。
+    pub fn reorder(&mut self, task_ids: &[String]) -> Result<(), AppError> {
+        let mismatch = || {
+            AppError::new(
+                "EXPORT_QUEUE_MISMATCH",
+                "reorder list does not match pending export tasks",
+                Some("请刷新导出队列后重试".to_string()),
+            )
+        };
+        if task_ids.len() != self.pending.len() {
+            return Err(mismatch());
+        }
+        let mut remaining = self.pending.clone();
+        let mut reordered = VecDeque::with_capacity(task_ids.len());
+        for task_id in task_ids {
+            let index = remaining
+                .iter()
+                .position(|item| &item.task_id == task_id)
+                .ok_or_else(mismatch)?;
+            if let Some(item) = remaining.remove(index) {
+                reordered.push_back(item);
+            }
+        }
+        self.pending = reordered;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExportQueue;
+
+    #[test]
+    fn starts_tasks_in_fifo_order_within_concurrency_limit() {
+        let mut queue = ExportQueue::new(1);
+        queue.enqueue("t1", "p1").expect("enqueue t1");
+        queue.enqueue("t2", "p2").expect("enqueue t2");
+        assert!(queue.enqueue("t3", "p1").is_err());
+
+        assert_eq!(
+            queue.start_next().map(|item| item.task_id),
+            Some("t1".into())
+        );
+        assert!(queue.start_next().is_none());
+        queue.finish("t1");
+        assert_eq!(
+            queue.start_next().map(|item| item.task_id),
+            Some("t2".into())
+        );
+    }
+
+    #[test]
+    fn reorder_requires_exact_pending_set() {
+        let mut queue = ExportQueue::new(1);
+        queue.enqueue("t1", "p1").expect("enqueue t1");
+        queue.enqueue("t2", "p2").expect("enqueue t2");
+        queue.enqueue("t3", "p3").expect("enqueue t3");
+
+        assert!(queue.reorder(&["t3".into(), "t1".into()]).is_err());
+        queue
+            .reorder(&["t3".into(), "t1".into(), "t2".into()])
+            .expect("reorder");
+        let order: Vec<_> = queue.pending().map(|item| item.task_id.as_str()).collect();
+        assert_eq!(order, vec!["t3", "t1", "t2"]);
+    }
+}
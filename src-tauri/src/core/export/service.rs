@@ -1,53 +1,83 @@
-use crate::domain::models::ExportProgressEvent;
-use crate::infra::ffmpeg::capabilities::HardwareEncoderAvailability;
+use crate::domain::models::{ExportProgressEvent, ExportVariantProgress};
 
-pub fn planned_progress(
+pub fn queued_progress(task_id: &str) -> ExportProgressEvent {
+    ExportProgressEvent {
+        task_id: task_id.to_string(),
+        status: "queued".to_string(),
+        progress: 0,
+        detail: "导出任务排队中".to_string(),
+        variant: None,
+        usage: Vec::new(),
+    }
+}
+
+/// 导出结束（成功、失败或取消）时的终态事件。
+pub fn finished_progress(
     task_id: &str,
-    hw_encoder: HardwareEncoderAvailability,
-) -> Vec<ExportProgressEvent> {
-    let mut events = vec![
-        ExportProgressEvent {
-            task_id: task_id.to_string(),
-            status: "queued".to_string(),
-            progress: 0,
-            detail: "导出任务排队中".to_string(),
-        },
-        ExportProgressEvent {
-            task_id: task_id.to_string(),
-            status: "running".to_string(),
-            progress: 20,
-            detail: "正在解析项目配置".to_string(),
-        },
-        ExportProgressEvent {
-            task_id: task_id.to_string(),
-            status: "running".to_string(),
-            progress: 50,
-            detail: "正在编码视频流".to_string(),
+    status: &str,
+    progress: u8,
+    detail: impl Into<String>,
+) -> ExportProgressEvent {
+    ExportProgressEvent {
+        task_id: task_id.to_string(),
+        status: status.to_string(),
+        progress,
+        detail: detail.into(),
+        variant: None,
+        usage: Vec::new(),
+    }
+}
+
+/// 编码阶段的真实进度事件；回退到软件编码后状态标记为 fallback。
+pub fn encoding_progress(task_id: &str, fallback: bool, progress: u8) -> ExportProgressEvent {
+    ExportProgressEvent {
+        task_id: task_id.to_string(),
+        status: if fallback { "fallback" } else { "running" }.to_string(),
+        progress,
+        detail: if fallback {
+            format!("硬件编码失败，正在使用软件编码（{progress}%）")
+        } else {
+            format!("正在编码视频流（{progress}%）")
         },
-    ];
+        variant: None,
+        usage: Vec::new(),
+    }
+}
 
-    if !hw_encoder.available {
-        events.push(ExportProgressEvent {
-            task_id: task_id.to_string(),
-            status: "fallback".to_string(),
-            progress: 62,
-            detail: format!("硬件编码({})不可用，已自动回退到软件编码", hw_encoder.codec),
-        });
+/// 多版本导出：把单个版本的进度换算为汇总进度，并在描述前标注当前版本。
+pub fn variant_progress(
+    mut event: ExportProgressEvent,
+    index: usize,
+    count: usize,
+    label: &str,
+) -> ExportProgressEvent {
+    if count <= 1 {
+        return event;
     }
+    let own = event.progress;
+    event.progress = ((index * 100 + own as usize) / count) as u8;
+    event.detail = format!("[{}/{count} {label}] {}", index + 1, event.detail);
+    event.variant = Some(ExportVariantProgress {
+        index: index as u8,
+        count: count as u8,
+        progress: own,
+    });
+    event
+}
 
-    events.extend([
-        ExportProgressEvent {
-            task_id: task_id.to_string(),
-            status: "running".to_string(),
-            progress: 85,
-            detail: "正在封装 MP4".to_string(),
-        },
-        ExportProgressEvent {
-            task_id: task_id.to_string(),
-            status: "success".to_string(),
-            progress: 100,
-            detail: "导出完成".to_string(),
-        },
-    ]);
-    events
+#[cfg(test)]
+mod tests {
+    use super::{encoding_progress, variant_progress};
+
+    #[test]
+    fn variant_progress_aggregates_across_variants() {
+        let single = variant_progress(encoding_progress("t", false, 40), 0, 1, "1080p_16x9");
+        assert_eq!(single.progress, 40);
+        assert!(single.variant.is_none());
+
+        let second = variant_progress(encoding_progress("t", false, 50), 1, 2, "720p_9x16");
+        assert_eq!(second.progress, 75);
+        assert!(second.detail.starts_with("[2/2 720p_9x16] "));
+        assert_eq!(second.variant.map(|variant| variant.progress), Some(50));
+    }
 }
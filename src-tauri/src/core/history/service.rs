@@ -0,0 +1,85 @@
+use crate::domain::models::{EditHistory, EditHistoryStatus, EditSnapshot, ProjectManifest};
+use chrono::Utc;
+
+// 只保留最近的编辑步骤，避免历史文件无限增长。
+const MAX_HISTORY: usize = 50;
+
+/// 记录一次编辑：`before` 为修改前的快照，新的编辑会清空重做栈。
+pub fn record_edit(history: &mut EditHistory, before: EditSnapshot) {
+    history.undo.push(before);
+    if history.undo.len() > MAX_HISTORY {
+        let overflow = history.undo.len() - MAX_HISTORY;
+        history.undo.drain(..overflow);
+    }
+    history.redo.clear();
+}
+
+/// 撤销最近一次编辑，没有可撤销的记录时返回 false。
+pub fn apply_undo(history: &mut EditHistory, manifest: &mut ProjectManifest) -> bool {
+    let Some(snapshot) = history.undo.pop() else {
+        return false;
+    };
+    history.redo.push(swap_snapshot(manifest, snapshot));
+    true
+}
+
+/// 重做最近一次撤销，没有可重做的记录时返回 false。
+pub fn apply_redo(history: &mut EditHistory, manifest: &mut ProjectManifest) -> bool {
+    let Some(snapshot) = history.redo.pop() else {
+        return false;
+    };
+    history.undo.push(swap_snapshot(manifest, snapshot));
+    true
+}
+
+pub fn history_status(history: &EditHistory) -> EditHistoryStatus {
+    EditHistoryStatus {
+        can_undo: !history.undo.is_empty(),
+        can_redo: !history.redo.is_empty(),
+    }
+}
+
+/// 把快照写回清单，返回被替换下来的当前状态。
+fn swap_snapshot(manifest: &mut ProjectManifest, snapshot: EditSnapshot) -> EditSnapshot {
+    manifest.updated_at = Utc::now();
+    match snapshot {
+        EditSnapshot::Timeline(timeline) => {
+            EditSnapshot::Timeline(std::mem::replace(&mut manifest.timeline, timeline))
+        }
+        EditSnapshot::CameraMotion(camera_motion) => EditSnapshot::CameraMotion(std::mem::replace(
+            &mut manifest.camera_motion,
+            camera_motion,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_redo, apply_undo, history_status, record_edit};
+    use crate::domain::models::{EditHistory, EditSnapshot, ProjectManifest};
+
+    #[test]
+    fn undo_and_redo_swap_timeline_state() {
+        let mut manifest = ProjectManifest::default();
+        let mut history = EditHistory::default();
+        record_edit(
+            &mut history,
+            EditSnapshot::Timeline(manifest.timeline.clone()),
+        );
+        manifest.timeline.trim_start_ms = 2_000;
+
+        assert!(apply_undo(&mut history, &mut manifest));
+        assert_eq!(manifest.timeline.trim_start_ms, 0);
+        assert!(!history_status(&history).can_undo);
+        assert!(apply_redo(&mut history, &mut manifest));
+        assert_eq!(manifest.timeline.trim_start_ms, 2_000);
+        assert!(!apply_redo(&mut history, &mut manifest));
+
+        record_edit(
+            &mut history,
+            EditSnapshot::CameraMotion(manifest.camera_motion.clone()),
+        );
+        assert!(!history_status(&history).can_redo);
+        assert_eq!(history.undo.len(), 2);
+    }
+}
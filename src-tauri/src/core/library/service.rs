@@ -0,0 +1,238 @@
+use crate::domain::models::{
+    AppError, ProjectListItem, ProjectListPage, ProjectListQuery, ProjectManifest, ProjectSortKey,
+};
+use std::cmp::Reverse;
+
+pub const MAX_PAGE_SIZE: usize = 200;
+pub const MAX_PROJECT_TAGS: usize = 20;
+pub const MAX_TAG_CHARS: usize = 32;
+pub const MAX_NOTES_CHARS: usize = 4000;
+
+/// 规范化备注：去除首尾空白，空内容视为清除。
+pub fn normalize_notes(notes: &str) -> Result<Option<String>, AppError> {
+    let notes = notes.trim();
+    if notes.chars().count() > MAX_NOTES_CHARS {
+        return Err(AppError::new(
+            "INVALID_NOTES",
+            format!("notes exceed {MAX_NOTES_CHARS} characters"),
+            Some(format!("备注最多 {MAX_NOTES_CHARS} 个字符")),
+        )
+        .with_param("max", MAX_NOTES_CHARS));
+    }
+    Ok((!notes.is_empty()).then(|| notes.to_string()))
+}
+
+/// 规范化标签：去除首尾空白与空标签，按不区分大小写去重并保留首次出现的写法。
+pub fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if tag.is_empty()
+            || normalized
+                .iter()
+                .any(|existing| existing.to_lowercase() == tag.to_lowercase())
+        {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_CHARS {
+            return Err(AppError::new(
+                "INVALID_TAG",
+                format!("tag exceeds {MAX_TAG_CHARS} characters: {tag}"),
+                Some(format!("标签最多 {MAX_TAG_CHARS} 个字符")),
+            ));
+        }
+        normalized.push(tag.to_string());
+    }
+    if normalized.len() > MAX_PROJECT_TAGS {
+        return Err(AppError::new(
+            "INVALID_TAG",
+            format!("too many tags: {}", normalized.len()),
+            Some(format!("每个项目最多 {MAX_PROJECT_TAGS} 个标签")),
+        ));
+    }
+    Ok(normalized)
+}
+
+/// 项目列表条目，时长取裁剪后的区间。
+pub fn project_list_item(project_id: &str, manifest: &ProjectManifest) -> ProjectListItem {
+    let duration_ms = manifest
+        .timeline
+        .trim_end_ms
+        .saturating_sub(manifest.timeline.trim_start_ms);
+    ProjectListItem {
+        project_id: project_id.to_string(),
+        title: manifest.title.clone(),
+        tags: manifest.tags.clone(),
+        notes: manifest.notes.clone(),
+        created_at: manifest.created_at,
+        updated_at: manifest.updated_at,
+        status: manifest.status.clone(),
+        duration_ms,
+        has_export: manifest.artifacts.last_export_path.is_some(),
+        export_path: manifest.artifacts.last_export_path.clone(),
+        raw_path: manifest.artifacts.raw_recording_path.clone(),
+    }
+}
+
+/// 页码与每页条数：页码从 1 开始，未指定每页条数时一页返回全部。
+pub fn page_bounds(query: &ProjectListQuery, total: usize) -> (usize, usize) {
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query
+        .page_size
+        .map(|size| size.clamp(1, MAX_PAGE_SIZE))
+        .unwrap_or(total.max(1));
+    (page, page_size)
+}
+
+/// 对项目列表做过滤、排序与分页；页码超出范围时返回空页，`total` 仍为过滤后的总数。
+pub fn query_projects(
+    mut items: Vec<ProjectListItem>,
+    query: &ProjectListQuery,
+) -> ProjectListPage {
+    let search = query
+        .search
+        .as_deref()
+        .map(|text| text.trim().to_lowercase())
+        .filter(|text| !text.is_empty());
+    items.retain(|item| {
+        let status_ok = query
+            .status
+            .as_ref()
+            .is_none_or(|status| *status == item.status);
+        let search_ok = search.as_deref().is_none_or(|text| {
+            item.project_id.to_lowercase().contains(text)
+                || [item.title.as_deref(), item.notes.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .any(|field| field.to_lowercase().contains(text))
+        });
+        let tags_ok = query.tags.iter().all(|wanted| {
+            item.tags
+                .iter()
+                .any(|tag| tag.to_lowercase() == wanted.trim().to_lowercase())
+        });
+        status_ok && search_ok && tags_ok
+    });
+    match query.sort {
+        ProjectSortKey::UpdatedDesc => items.sort_by_key(|item| Reverse(item.updated_at)),
+        ProjectSortKey::CreatedDesc => items.sort_by_key(|item| Reverse(item.created_at)),
+        ProjectSortKey::TitleAsc => items.sort_by_cached_key(|item| {
+            item.title
+                .as_deref()
+                .unwrap_or(&item.project_id)
+                .to_lowercase()
+        }),
+        ProjectSortKey::DurationDesc => items.sort_by_key(|item| Reverse(item.duration_ms)),
+    }
+
+    let total = items.len();
+    let (page, page_size) = page_bounds(query, total);
+    let items = items
+        .into_iter()
+        .skip((page - 1).saturating_mul(page_size))
+        .take(page_size)
+        .collect();
+    ProjectListPage {
+        items,
+        total,
+        page,
+        page_size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_tags, query_projects};
+    use crate::domain::models::{ProjectListItem, ProjectListQuery, ProjectSortKey, ProjectStatus};
+    use chrono::{Duration, Utc};
+
+    fn item(id: &str, title: &str, status: ProjectStatus, duration_ms: u64) -> ProjectListItem {
+        let now = Utc::now();
+        ProjectListItem {
+            project_id: id.to_string(),
+            title: Some(title.to_string()),
+            tags: Vec::new(),
+            notes: None,
+            created_at: now,
+            updated_at: now - Duration::seconds(duration_ms as i64),
+            status,
+            duration_ms,
+            has_export: false,
+            export_path: None,
+            raw_path: None,
+        }
+    }
+
+    #[test]
+    fn filters_sorts_and_pages_projects() {
+        let items = vec![
+            item("a", "Onboarding demo", ProjectStatus::ReadyToEdit, 30),
+            item(
+                "b",
+                "Billing walkthrough",
+                ProjectStatus::ExportSucceeded,
+                10,
+            ),
+            item("c", "Demo take 2", ProjectStatus::ReadyToEdit, 20),
+        ];
+        let query = ProjectListQuery {
+            search: Some("DEMO".to_string()),
+            sort: ProjectSortKey::DurationDesc,
+            page_size: Some(1),
+            page: Some(2),
+            ..ProjectListQuery::default()
+        };
+        let page = query_projects(items.clone(), &query);
+        assert_eq!(page.total, 2);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].project_id, "c");
+
+        let mut noted = items.clone();
+        noted[1].notes = Some("take 3, audio clipped at 2:10".to_string());
+        let query = ProjectListQuery {
+            search: Some("clipped".to_string()),
+            ..ProjectListQuery::default()
+        };
+        assert_eq!(query_projects(noted, &query).items[0].project_id, "b");
+
+        let query = ProjectListQuery {
+            status: Some(ProjectStatus::ExportSucceeded),
+            ..ProjectListQuery::default()
+        };
+        let page = query_projects(items.clone(), &query);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].project_id, "b");
+
+        let page = query_projects(items, &ProjectListQuery::default());
+        let ids = page
+            .items
+            .iter()
+            .map(|item| item.project_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn tags_are_deduplicated_and_filterable() {
+        let tags = normalize_tags(vec![
+            " Client A ".to_string(),
+            "client a".to_string(),
+            String::new(),
+            "Course".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(tags, vec!["Client A", "Course"]);
+        assert!(normalize_tags(vec!["x".repeat(33)]).is_err());
+
+        let mut tagged = item("a", "Intro", ProjectStatus::ReadyToEdit, 10);
+        tagged.tags = tags;
+        let items = vec![tagged, item("b", "Outro", ProjectStatus::ReadyToEdit, 10)];
+        let query = ProjectListQuery {
+            tags: vec!["course".to_string()],
+            ..ProjectListQuery::default()
+        };
+        let page = query_projects(items, &query);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].project_id, "a");
+    }
+}
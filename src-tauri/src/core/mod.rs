@@ -1,5 +1,8 @@
 pub mod capture;
 pub mod export;
+pub mod history;
+pub mod library;
 pub mod motion;
 pub mod recovery;
 pub mod timeline;
+pub mod transcript;
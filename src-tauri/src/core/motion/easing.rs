@@ -0,0 +1,71 @@
+use crate::domain::models::CameraEasing;
+
+/// 把线性进度 t∈[0,1] 映射到缓动后的进度，评估与导出共用同一曲线。
+pub fn ease(easing: CameraEasing, t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    match easing {
+        CameraEasing::Linear => t,
+        CameraEasing::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+        CameraEasing::CubicBezier { x1, y1, x2, y2 } => cubic_bezier(
+            f64::from(x1).clamp(0.0, 1.0),
+            f64::from(y1),
+            f64::from(x2).clamp(0.0, 1.0),
+            f64::from(y2),
+            t,
+        ),
+    }
+}
+
+/// CSS 语义的三次贝塞尔：先按 x 反解参数 s，再取 y(s)。
+fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64, x: f64) -> f64 {
+    let component = |p1: f64, p2: f64, s: f64| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * s * p1 + 3.0 * inv * s * s * p2 + s * s * s
+    };
+    // x(s) 在 x1、x2 ∈ [0,1] 时单调，二分足够稳定。
+    let (mut low, mut high) = (0.0, 1.0);
+    let mut s = x;
+    for _ in 0..32 {
+        let value = component(x1, x2, s);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) * 0.5;
+    }
+    component(y1, y2, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ease;
+    use crate::domain::models::CameraEasing;
+
+    #[test]
+    fn ease_in_out_is_symmetric_and_anchored() {
+        assert_eq!(ease(CameraEasing::EaseInOut, 0.0), 0.0);
+        assert!((ease(CameraEasing::EaseInOut, 1.0) - 1.0).abs() < 1e-6);
+        assert!((ease(CameraEasing::EaseInOut, 0.5) - 0.5).abs() < 1e-4);
+        assert!(ease(CameraEasing::EaseInOut, 0.2) < 0.2);
+        assert!(ease(CameraEasing::EaseInOut, 0.8) > 0.8);
+    }
+
+    #[test]
+    fn linear_bezier_matches_identity() {
+        let linear = CameraEasing::CubicBezier {
+            x1: 0.0,
+            y1: 0.0,
+            x2: 1.0,
+            y2: 1.0,
+        };
+        for step in 0..=10 {
+            let t = step as f64 / 10.0;
+            assert!((ease(linear, t) - t).abs() < 1e-4);
+            assert_eq!(ease(CameraEasing::Linear, t), t);
+        }
+    }
+}
@@ -1,2 +1,5 @@
+pub mod easing;
+pub mod refine;
 pub mod smoothing;
+pub mod track_edit;
 pub mod tracker;
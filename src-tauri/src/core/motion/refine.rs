@@ -0,0 +1,161 @@
+use crate::domain::models::{CursorRefineOptions, CursorTrackPoint};
+
+/// 依次执行离群点剔除、缺口插值与滑动平均，输入轨迹需按时间排序。
+pub fn refine_track(
+    points: &[CursorTrackPoint],
+    options: &CursorRefineOptions,
+) -> Vec<CursorTrackPoint> {
+    let without_outliers = remove_outliers(points, options.outlier_jump_px);
+    let filled = fill_gaps(
+        &without_outliers,
+        options.max_gap_ms,
+        options.interpolation_step_ms,
+    );
+    smooth(&filled, options.smoothing_window)
+}
+
+fn distance(a: &CursorTrackPoint, b: &CursorTrackPoint) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+fn remove_outliers(points: &[CursorTrackPoint], jump_px: f32) -> Vec<CursorTrackPoint> {
+    if points.len() < 3 || jump_px <= 0.0 {
+        return points.to_vec();
+    }
+    let mut kept: Vec<CursorTrackPoint> = Vec::with_capacity(points.len());
+    kept.push(points[0]);
+    for index in 1..points.len() - 1 {
+        let current = &points[index];
+        let next = &points[index + 1];
+        let prev = kept.last().copied().unwrap_or(points[0]);
+        let has_scroll = current.scroll_dx != 0.0 || current.scroll_dy != 0.0;
+        // 只剔除“跳出去又跳回来”的尖峰，真实的大幅移动会在后续样本中延续。
+        let is_spike = distance(&prev, current) > jump_px
+            && distance(current, next) > jump_px
+            && distance(&prev, next) <= jump_px;
+        if is_spike && !has_scroll && !current.click {
+            continue;
+        }
+        kept.push(*current);
+    }
+    kept.push(points[points.len() - 1]);
+    kept
+}
+
+fn fill_gaps(points: &[CursorTrackPoint], max_gap_ms: u64, step_ms: u64) -> Vec<CursorTrackPoint> {
+    if points.len() < 2 || max_gap_ms == 0 || step_ms == 0 {
+        return points.to_vec();
+    }
+    let mut filled = Vec::with_capacity(points.len());
+    for pair in points.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        filled.push(start);
+        let gap = end.t_ms.saturating_sub(start.t_ms);
+        if gap <= max_gap_ms {
+            continue;
+        }
+        let mut t_ms = start.t_ms + step_ms;
+        while t_ms < end.t_ms {
+            let ratio = (t_ms - start.t_ms) as f32 / gap as f32;
+            filled.push(CursorTrackPoint {
+                t_ms,
+                x: start.x + (end.x - start.x) * ratio,
+                y: start.y + (end.y - start.y) * ratio,
+                scroll_dx: 0.0,
+                scroll_dy: 0.0,
+                offscreen: start.offscreen && end.offscreen,
+                click: false,
+            });
+            t_ms += step_ms;
+        }
+    }
+    if let Some(last) = points.last() {
+        filled.push(*last);
+    }
+    filled
+}
+
+fn smooth(points: &[CursorTrackPoint], window: usize) -> Vec<CursorTrackPoint> {
+    if window < 2 || points.len() < 3 {
+        return points.to_vec();
+    }
+    let radius = window / 2;
+    points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            if point.offscreen {
+                return *point;
+            }
+            let from = index.saturating_sub(radius);
+            let to = (index + radius).min(points.len() - 1);
+            let neighbours = points[from..=to]
+                .iter()
+                .filter(|neighbour| !neighbour.offscreen)
+                .collect::<Vec<_>>();
+            let count = neighbours.len() as f32;
+            CursorTrackPoint {
+                x: neighbours.iter().map(|item| item.x).sum::<f32>() / count,
+                y: neighbours.iter().map(|item| item.y).sum::<f32>() / count,
+                ..*point
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fill_gaps, refine_track, remove_outliers};
+    use crate::domain::models::{CursorRefineOptions, CursorTrackPoint};
+
+    fn point(t_ms: u64, x: f32, y: f32) -> CursorTrackPoint {
+        CursorTrackPoint {
+            t_ms,
+            x,
+            y,
+            scroll_dx: 0.0,
+            scroll_dy: 0.0,
+            offscreen: false,
+            click: false,
+        }
+    }
+
+    #[test]
+    fn outlier_spike_is_removed_but_real_move_is_kept() {
+        let spike = vec![
+            point(0, 100.0, 100.0),
+            point(120, 900.0, 100.0),
+            point(240, 110.0, 100.0),
+        ];
+        assert_eq!(remove_outliers(&spike, 240.0).len(), 2);
+
+        let real_move = vec![
+            point(0, 100.0, 100.0),
+            point(120, 900.0, 100.0),
+            point(240, 910.0, 100.0),
+        ];
+        assert_eq!(remove_outliers(&real_move, 240.0).len(), 3);
+    }
+
+    #[test]
+    fn long_gaps_are_linearly_interpolated() {
+        let filled = fill_gaps(&[point(0, 0.0, 0.0), point(400, 400.0, 0.0)], 250, 100);
+        let xs = filled.iter().map(|item| item.x).collect::<Vec<_>>();
+        assert_eq!(xs, vec![0.0, 100.0, 200.0, 300.0, 400.0]);
+    }
+
+    #[test]
+    fn refine_keeps_timestamps_and_smooths_jitter() {
+        let points = vec![
+            point(0, 100.0, 100.0),
+            point(120, 104.0, 100.0),
+            point(240, 96.0, 100.0),
+            point(360, 104.0, 100.0),
+            point(480, 100.0, 100.0),
+        ];
+        let refined = refine_track(&points, &CursorRefineOptions::default());
+        assert_eq!(refined.len(), points.len());
+        assert!((refined[2].x - 100.8).abs() < 0.01);
+        assert_eq!(refined[4].t_ms, 480);
+    }
+}
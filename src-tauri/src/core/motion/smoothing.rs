@@ -1,3 +1,6 @@
+use crate::core::motion::easing::ease;
+use crate::domain::models::{CameraEasing, CameraIntensity};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MotionPoint {
     pub x: f32,
@@ -10,6 +13,7 @@ pub struct MotionConfig {
     pub smoothing: f32,
     pub max_speed_px: f32,
     pub max_zoom_step: f32,
+    pub easing: CameraEasing,
 }
 
 impl Default for MotionConfig {
@@ -18,14 +22,33 @@ impl Default for MotionConfig {
             smoothing: 0.68,
             max_speed_px: 80.0,
             max_zoom_step: 0.08,
+            easing: CameraEasing::Linear,
+        }
+    }
+}
+
+/// 参数按 120ms 采样间隔调校，与导出镜头路径的参考间隔一致。
+const REFERENCE_SAMPLE_MS: f32 = 120.0;
+
+impl MotionConfig {
+    /// 按实际采样间隔折算单步参数：跟随比例按指数复合，速度与缩放上限按时长线性放大，
+    /// 保证同一段运动无论采样疏密，镜头跟随速度一致。
+    pub fn scaled_for_interval(self, dt_ms: f32) -> Self {
+        let steps = (dt_ms / REFERENCE_SAMPLE_MS).clamp(0.0, 8.0);
+        Self {
+            smoothing: self.smoothing.clamp(0.0, 1.0).powf(steps),
+            max_speed_px: self.max_speed_px * steps,
+            max_zoom_step: self.max_zoom_step * steps,
+            easing: self.easing,
         }
     }
 }
 
 pub fn smooth_motion(prev: MotionPoint, target: MotionPoint, cfg: MotionConfig) -> MotionPoint {
     let alpha = cfg.smoothing.clamp(0.0, 1.0);
-    let mut dx = (target.x - prev.x) * (1.0 - alpha);
-    let mut dy = (target.y - prev.y) * (1.0 - alpha);
+    let gain = ease(cfg.easing, f64::from(1.0 - alpha)) as f32;
+    let mut dx = (target.x - prev.x) * gain;
+    let mut dy = (target.y - prev.y) * gain;
     let distance = (dx * dx + dy * dy).sqrt();
     if distance > cfg.max_speed_px {
         let ratio = cfg.max_speed_px / distance;
@@ -33,7 +56,7 @@ pub fn smooth_motion(prev: MotionPoint, target: MotionPoint, cfg: MotionConfig)
         dy *= ratio;
     }
 
-    let mut dz = (target.zoom - prev.zoom) * (1.0 - alpha);
+    let mut dz = (target.zoom - prev.zoom) * gain;
     if dz.abs() > cfg.max_zoom_step {
         dz = cfg.max_zoom_step * dz.signum();
     }
@@ -45,9 +68,62 @@ pub fn smooth_motion(prev: MotionPoint, target: MotionPoint, cfg: MotionConfig)
     }
 }
 
+/// 弹簧参数；阻尼取临界值 2·sqrt(k·m)，保证缓入缓出且不过冲。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringConfig {
+    pub mass: f64,
+    pub stiffness: f64,
+    pub damping: f64,
+}
+
+impl SpringConfig {
+    pub fn for_intensity(intensity: CameraIntensity, smoothing: f32) -> Self {
+        let base_stiffness = match intensity {
+            CameraIntensity::Low => 60.0,
+            CameraIntensity::Medium => 110.0,
+            CameraIntensity::High => 170.0,
+        };
+        // smoothing 越高弹簧越软，镜头越“慢半拍”。
+        let softness = 1.4 - f64::from(smoothing.clamp(0.0, 1.0)) * 0.8;
+        let mass = 1.0;
+        let stiffness = base_stiffness * softness;
+        Self {
+            mass,
+            stiffness,
+            damping: 2.0 * (stiffness * mass).sqrt(),
+        }
+    }
+
+    fn angular_frequency(&self) -> f64 {
+        (self.stiffness / self.mass.max(f64::EPSILON)).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SpringState {
+    pub position: f64,
+    pub velocity: f64,
+}
+
+/// 临界阻尼弹簧的解析解，任意步长都稳定，无需子步积分。
+pub fn spring_step(state: SpringState, target: f64, dt_sec: f64, cfg: SpringConfig) -> SpringState {
+    if dt_sec <= 0.0 {
+        return state;
+    }
+    let omega = cfg.angular_frequency();
+    let offset = state.position - target;
+    let slope = state.velocity + omega * offset;
+    let decay = (-omega * dt_sec).exp();
+    SpringState {
+        position: target + (offset + slope * dt_sec) * decay,
+        velocity: (state.velocity - omega * slope * dt_sec) * decay,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{smooth_motion, MotionConfig, MotionPoint};
+    use super::{smooth_motion, spring_step, MotionConfig, MotionPoint, SpringConfig, SpringState};
+    use crate::domain::models::{CameraEasing, CameraIntensity};
 
     #[test]
     fn large_jump_is_clamped() {
@@ -65,6 +141,7 @@ mod tests {
             smoothing: 0.5,
             max_speed_px: 60.0,
             max_zoom_step: 0.1,
+            easing: CameraEasing::Linear,
         };
         let next = smooth_motion(prev, target, cfg);
         assert!(next.x <= 60.0);
@@ -84,4 +161,21 @@ mod tests {
         assert!((next.y - prev.y).abs() < f32::EPSILON);
         assert!((next.zoom - prev.zoom).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn spring_converges_without_overshoot() {
+        let cfg = SpringConfig::for_intensity(CameraIntensity::Medium, 0.5);
+        let mut state = SpringState::default();
+        let mut previous = 0.0;
+        for _ in 0..60 {
+            state = spring_step(state, 100.0, 1.0 / 30.0, cfg);
+            assert!(state.position <= 100.0 + 1e-9);
+            assert!(state.position >= previous);
+            previous = state.position;
+        }
+        assert!((state.position - 100.0).abs() < 0.5);
+        // 起步平缓：第一帧位移明显小于线性插值。
+        let first = spring_step(SpringState::default(), 100.0, 1.0 / 30.0, cfg);
+        assert!(first.position < 10.0);
+    }
 }
@@ -0,0 +1,80 @@
+use crate::domain::models::{CursorTrackEdit, CursorTrackPoint};
+
+/// 按顺序应用编辑；删除区间后保留首尾样本，避免导出时轨迹整段缺失。
+pub fn apply_track_edits(
+    points: &[CursorTrackPoint],
+    edits: &[CursorTrackEdit],
+) -> Vec<CursorTrackPoint> {
+    let mut result = points.to_vec();
+    for edit in edits {
+        match *edit {
+            CursorTrackEdit::DeleteSpan { start_ms, end_ms } => {
+                let (start_ms, end_ms) = (start_ms.min(end_ms), start_ms.max(end_ms));
+                let last_index = result.len().saturating_sub(1);
+                let mut index = 0usize;
+                result.retain(|point| {
+                    let keep = index == 0
+                        || index == last_index
+                        || point.t_ms < start_ms
+                        || point.t_ms > end_ms;
+                    index += 1;
+                    keep
+                });
+            }
+            CursorTrackEdit::Nudge { t_ms, dx, dy } => {
+                if let Some(point) = result.iter_mut().find(|point| point.t_ms == t_ms) {
+                    point.x += dx;
+                    point.y += dy;
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply_track_edits;
+    use crate::domain::models::{CursorTrackEdit, CursorTrackPoint};
+
+    fn point(t_ms: u64, x: f32) -> CursorTrackPoint {
+        CursorTrackPoint {
+            t_ms,
+            x,
+            y: 0.0,
+            scroll_dx: 0.0,
+            scroll_dy: 0.0,
+            offscreen: false,
+            click: false,
+        }
+    }
+
+    #[test]
+    fn delete_span_keeps_track_endpoints() {
+        let points = (0..5).map(|i| point(i * 100, i as f32)).collect::<Vec<_>>();
+        let edited = apply_track_edits(
+            &points,
+            &[CursorTrackEdit::DeleteSpan {
+                start_ms: 0,
+                end_ms: 250,
+            }],
+        );
+        let times = edited.iter().map(|item| item.t_ms).collect::<Vec<_>>();
+        assert_eq!(times, vec![0, 300, 400]);
+    }
+
+    #[test]
+    fn nudge_moves_matching_point_only() {
+        let points = vec![point(0, 10.0), point(120, 20.0)];
+        let edited = apply_track_edits(
+            &points,
+            &[CursorTrackEdit::Nudge {
+                t_ms: 120,
+                dx: -5.0,
+                dy: 3.0,
+            }],
+        );
+        assert_eq!(edited[0], points[0]);
+        assert_eq!((edited[1].x, edited[1].y), (15.0, 3.0));
+    }
+}
@@ -1,5 +1,7 @@
-use crate::core::motion::smoothing::{smooth_motion, MotionConfig, MotionPoint};
-use crate::domain::models::{CameraIntensity, CameraMotionProfile};
+use crate::core::motion::smoothing::{
+    smooth_motion, spring_step, MotionConfig, MotionPoint, SpringConfig, SpringState,
+};
+use crate::domain::models::{CameraIntensity, CameraMotionModel, CameraMotionProfile};
 
 #[derive(Debug, Clone, Copy)]
 pub struct CursorSample {
@@ -21,6 +23,9 @@ pub fn compute_motion_path(
     if samples.is_empty() {
         return Vec::new();
     }
+    if profile.model == CameraMotionModel::Spring {
+        return compute_spring_path(samples, profile);
+    }
     let config = profile_to_config(profile);
     let mut output = Vec::with_capacity(samples.len());
     let mut current = MotionPoint {
@@ -29,18 +34,78 @@ pub fn compute_motion_path(
         zoom: 1.0,
     };
     output.push(current);
+    let mut prev_t_ms = samples[0].t_ms;
     for sample in samples.iter().skip(1) {
         let target = MotionPoint {
             x: sample.x,
             y: sample.y,
             zoom: intensity_zoom(profile.intensity.clone()).min(profile.max_zoom.clamp(1.0, 2.0)),
         };
-        current = smooth_motion(current, target, config);
+        // 自适应采样的间隔不固定，按实际时间差折算，与导出路径保持一致。
+        let dt_ms = sample.t_ms.saturating_sub(prev_t_ms) as f32;
+        current = smooth_motion(current, target, config.scaled_for_interval(dt_ms));
+        prev_t_ms = sample.t_ms;
         output.push(current);
     }
     output
 }
 
+fn compute_spring_path(
+    samples: &[CursorSample],
+    profile: &CameraMotionProfile,
+) -> Vec<MotionPoint> {
+    let cfg = SpringConfig::for_intensity(profile.intensity.clone(), profile.smoothing);
+    let zoom = intensity_zoom(profile.intensity.clone()).min(profile.max_zoom.clamp(1.0, 2.0));
+    let mut x = SpringState {
+        position: f64::from(samples[0].x),
+        velocity: 0.0,
+    };
+    let mut y = SpringState {
+        position: f64::from(samples[0].y),
+        velocity: 0.0,
+    };
+    let mut prev_t_ms = samples[0].t_ms;
+    let mut output = Vec::with_capacity(samples.len());
+    output.push(MotionPoint {
+        x: samples[0].x,
+        y: samples[0].y,
+        zoom: 1.0,
+    });
+    for sample in samples.iter().skip(1) {
+        let dt_sec = sample.t_ms.saturating_sub(prev_t_ms) as f64 / 1000.0;
+        x = spring_step(x, f64::from(sample.x), dt_sec, cfg);
+        y = spring_step(y, f64::from(sample.y), dt_sec, cfg);
+        prev_t_ms = sample.t_ms;
+        output.push(MotionPoint {
+            x: x.position as f32,
+            y: y.position as f32,
+            zoom,
+        });
+    }
+    output
+}
+
+/// 把镜头路径均匀抽样到不超过 max_points 个点，始终保留末尾点，供前端绘制。
+pub fn downsample_path(
+    samples: &[CursorSample],
+    path: &[MotionPoint],
+    max_points: usize,
+) -> Vec<(u64, MotionPoint)> {
+    let len = samples.len().min(path.len());
+    if len == 0 || max_points == 0 {
+        return Vec::new();
+    }
+    let step = len.div_ceil(max_points).max(1);
+    let mut output = (0..len)
+        .step_by(step)
+        .map(|index| (samples[index].t_ms, path[index]))
+        .collect::<Vec<_>>();
+    if !(len - 1).is_multiple_of(step) {
+        output.push((samples[len - 1].t_ms, path[len - 1]));
+    }
+    output
+}
+
 pub fn evaluate_metrics(samples: &[CursorSample], path: &[MotionPoint]) -> MotionMetrics {
     if samples.len() < 2 || path.len() < 2 {
         return MotionMetrics {
@@ -107,16 +172,19 @@ fn profile_to_config(profile: &CameraMotionProfile) -> MotionConfig {
             smoothing: 0.72,
             max_speed_px: 120.0,
             max_zoom_step: 0.05,
+            easing: profile.easing,
         },
         CameraIntensity::Medium => MotionConfig {
             smoothing: 0.56,
             max_speed_px: 260.0,
             max_zoom_step: 0.1,
+            easing: profile.easing,
         },
         CameraIntensity::High => MotionConfig {
             smoothing: 0.42,
             max_speed_px: 360.0,
             max_zoom_step: 0.14,
+            easing: profile.easing,
         },
     };
     MotionConfig {
@@ -124,6 +192,7 @@ fn profile_to_config(profile: &CameraMotionProfile) -> MotionConfig {
         smoothing: ((base.smoothing + profile.smoothing) * 0.5).clamp(0.0, 1.0),
         max_speed_px: base.max_speed_px,
         max_zoom_step: base.max_zoom_step,
+        easing: base.easing,
     }
 }
 
@@ -137,8 +206,11 @@ fn intensity_zoom(intensity: CameraIntensity) -> f32 {
 
 #[cfg(test)]
 mod tests {
-    use super::{compute_motion_path, evaluate_metrics, CursorSample};
-    use crate::domain::models::{CameraIntensity, CameraMotionProfile};
+    use super::{compute_motion_path, downsample_path, evaluate_metrics, CursorSample};
+    use crate::domain::models::{
+        CameraEasing, CameraFocusMode, CameraIntensity, CameraMode, CameraMotionModel,
+        CameraMotionProfile,
+    };
 
     fn profile(intensity: CameraIntensity) -> CameraMotionProfile {
         CameraMotionProfile {
@@ -147,6 +219,12 @@ mod tests {
             smoothing: 0.56,
             max_zoom: 1.35,
             idle_threshold_ms: 500,
+            zoom_on_click: false,
+            click_hold_ms: 800,
+            model: CameraMotionModel::Hybrid,
+            easing: CameraEasing::Linear,
+            focus_mode: CameraFocusMode::Cursor,
+            mode: CameraMode::ZoomAndPan,
         }
     }
 
@@ -251,4 +329,81 @@ mod tests {
         let fast_dx = (fast_path[1].x - samples[0].x).abs();
         assert!(fast_dx > slow_dx);
     }
+
+    #[test]
+    fn path_is_independent_of_sample_interval() {
+        let sampled = |interval_ms: u64| {
+            (0..=1200 / interval_ms)
+                .map(|idx| CursorSample {
+                    t_ms: idx * interval_ms,
+                    x: if idx == 0 { 100.0 } else { 900.0 },
+                    y: if idx == 0 { 100.0 } else { 520.0 },
+                })
+                .collect::<Vec<_>>()
+        };
+        let config = profile(CameraIntensity::Medium);
+        let dense_samples = sampled(30);
+        let sparse_samples = sampled(120);
+        let dense = compute_motion_path(&dense_samples, &config);
+        let sparse = compute_motion_path(&sparse_samples, &config);
+        for (sample, point) in sparse_samples.iter().zip(&sparse) {
+            let index = dense_samples
+                .iter()
+                .position(|dense_sample| dense_sample.t_ms == sample.t_ms)
+                .unwrap();
+            assert!(
+                (dense[index].x - point.x).abs() < 16.0,
+                "x at {}ms",
+                sample.t_ms
+            );
+            assert!(
+                (dense[index].y - point.y).abs() < 16.0,
+                "y at {}ms",
+                sample.t_ms
+            );
+        }
+        assert_eq!(
+            evaluate_metrics(&dense_samples, &dense).transition_latency_ms,
+            evaluate_metrics(&sparse_samples, &sparse).transition_latency_ms
+        );
+    }
+
+    #[test]
+    fn spring_model_eases_into_target() {
+        let samples = (0..12)
+            .map(|idx| CursorSample {
+                t_ms: idx * 120,
+                x: if idx == 0 { 100.0 } else { 900.0 },
+                y: 300.0,
+            })
+            .collect::<Vec<_>>();
+        let mut spring = profile(CameraIntensity::Medium);
+        spring.model = CameraMotionModel::Spring;
+        let path = compute_motion_path(&samples, &spring);
+        let steps = path
+            .windows(2)
+            .map(|pair| pair[1].x - pair[0].x)
+            .collect::<Vec<_>>();
+        assert!(steps.iter().all(|step| *step >= 0.0));
+        assert!(path.iter().all(|point| point.x <= 900.0 + 1e-3));
+        assert!((path[path.len() - 1].x - 900.0).abs() < 5.0);
+        let metrics = evaluate_metrics(&samples, &path);
+        assert!(metrics.transition_latency_ms <= 600);
+    }
+
+    #[test]
+    fn downsampled_path_keeps_endpoints() {
+        let samples = (0..10)
+            .map(|idx| CursorSample {
+                t_ms: idx * 100,
+                x: idx as f32,
+                y: 0.0,
+            })
+            .collect::<Vec<_>>();
+        let path = compute_motion_path(&samples, &profile(CameraIntensity::Medium));
+        let points = downsample_path(&samples, &path, 4);
+        let times = points.iter().map(|(t_ms, _)| *t_ms).collect::<Vec<_>>();
+        assert_eq!(times, vec![0, 300, 600, 900]);
+        assert_eq!(downsample_path(&samples, &path, 20).len(), 10);
+    }
 }
@@ -1,5 +1,7 @@
-use crate::domain::models::RecoverableProject;
-use crate::infra::storage::project_store::{manifest_path, raw_recording_path};
+use crate::domain::models::{CursorTrackPoint, RecoverableProject};
+use crate::infra::storage::project_store::{
+    manifest_path, raw_recording_path, recording_capture_path,
+};
 use std::path::Path;
 
 pub fn scan_recoverable_projects(project_root: &Path) -> Vec<RecoverableProject> {
@@ -21,9 +23,10 @@ pub fn scan_recoverable_projects(project_root: &Path) -> Vec<RecoverableProject>
         };
 
         let marker = path.join("recovery.marker");
-        let raw_path = raw_recording_path(project_root, &project_id);
-        if marker.exists() && manifest_path(project_root, &project_id).exists() && raw_path.exists()
-        {
+        // 录制中断时只有 MKV，尚未重封装为 MP4。
+        let has_recording = raw_recording_path(project_root, &project_id).exists()
+            || recording_capture_path(project_root, &project_id).exists();
+        if marker.exists() && manifest_path(project_root, &project_id).exists() && has_recording {
             recovered.push(RecoverableProject {
                 project_id,
                 reason: "检测到未完成项目，支持恢复".to_string(),
@@ -34,3 +37,52 @@ pub fn scan_recoverable_projects(project_root: &Path) -> Vec<RecoverableProject>
 
     recovered
 }
+
+/// 补齐光标轨迹到录制结尾：中断的录制没有写出光标样本时，生成停在画面中心的静态轨迹；
+/// 已有轨迹则在结尾补一个停留点，保证镜头计算覆盖完整时长。
+pub fn extend_cursor_track(
+    points: &mut Vec<CursorTrackPoint>,
+    duration_ms: u64,
+    frame: (u32, u32),
+) {
+    let hold = |t_ms, x, y| CursorTrackPoint {
+        t_ms,
+        x,
+        y,
+        scroll_dx: 0.0,
+        scroll_dy: 0.0,
+        offscreen: false,
+        click: false,
+    };
+    points.retain(|point| point.t_ms <= duration_ms);
+    match points.last().copied() {
+        None => {
+            let (x, y) = (frame.0 as f32 / 2.0, frame.1 as f32 / 2.0);
+            points.extend([hold(0, x, y), hold(duration_ms, x, y)]);
+        }
+        Some(last) if last.t_ms < duration_ms => points.push(hold(duration_ms, last.x, last.y)),
+        Some(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extend_cursor_track;
+
+    #[test]
+    fn cursor_track_is_extended_to_recording_end() {
+        let mut points = Vec::new();
+        extend_cursor_track(&mut points, 5_000, (1920, 1080));
+        assert_eq!(points.len(), 2);
+        assert_eq!(
+            (points[1].t_ms, points[1].x, points[1].y),
+            (5_000, 960.0, 540.0)
+        );
+
+        points[1].t_ms = 3_000;
+        points[1].x = 100.0;
+        extend_cursor_track(&mut points, 4_000, (1920, 1080));
+        assert_eq!(points.len(), 3);
+        assert_eq!((points[2].t_ms, points[2].x), (4_000, 100.0));
+    }
+}
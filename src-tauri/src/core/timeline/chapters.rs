@@ -0,0 +1,222 @@
+use crate::core::transcript::service::is_cjk;
+use crate::domain::models::{AppFocusSegment, MarkerSource, TranscriptCue};
+use std::collections::HashSet;
+
+// 章节之间、章节与首尾之间的最小间隔，过密的章节在播放器进度条上无法点选。
+const MIN_CHAPTER_GAP_MS: u64 = 10_000;
+// 在某个应用停留不足 5 秒视为顺手切换（查看通知、复制粘贴），不算话题变化。
+const MIN_APP_DWELL_MS: u64 = 5_000;
+// 字幕间隔超过 2 秒视为一段话结束，话题只在段落之间切换。
+const PARAGRAPH_PAUSE_MS: u64 = 2_000;
+// 相邻段落的用词重合度低于该值时认为换了话题。
+const TOPIC_SIMILARITY: f32 = 0.1;
+// 用词太少的段落（寒暄、一句话）无法判断话题，不单独起章节。
+const MIN_TOPIC_TERMS: usize = 4;
+const MAX_TITLE_CHARS: usize = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChapterSuggestion {
+    pub t_ms: u64,
+    pub title: String,
+    pub source: MarkerSource,
+}
+
+/// 根据前台应用切换与文字稿话题变化建议章节，时间为源文件时间轴。
+/// 只保留 `[start_ms, end_ms]` 内、与首尾及 `existing_ms`（已确认的章节）相隔足够远的建议；
+/// 两条建议过近时保留较早的一条，同一时刻应用切换优先。
+pub fn suggest_chapters(
+    app_track: &[AppFocusSegment],
+    cues: &[TranscriptCue],
+    start_ms: u64,
+    end_ms: u64,
+    existing_ms: &[u64],
+) -> Vec<ChapterSuggestion> {
+    let mut candidates = app_switches(app_track);
+    candidates.extend(topic_shifts(cues));
+    candidates
+        .sort_by_key(|candidate| (candidate.t_ms, candidate.source != MarkerSource::AppSwitch));
+    let far_from = |t_ms: u64, other: u64| t_ms.abs_diff(other) >= MIN_CHAPTER_GAP_MS;
+    let mut chapters: Vec<ChapterSuggestion> = Vec::new();
+    for candidate in candidates {
+        let inside = candidate.t_ms >= start_ms.saturating_add(MIN_CHAPTER_GAP_MS)
+            && candidate.t_ms.saturating_add(MIN_CHAPTER_GAP_MS) <= end_ms;
+        if inside
+            && existing_ms
+                .iter()
+                .all(|existing| far_from(candidate.t_ms, *existing))
+            && chapters
+                .last()
+                .is_none_or(|last| far_from(candidate.t_ms, last.t_ms))
+        {
+            chapters.push(candidate);
+        }
+    }
+    chapters
+}
+
+/// 停留足够久的应用切换；短暂切走再切回不产生章节。
+fn app_switches(app_track: &[AppFocusSegment]) -> Vec<ChapterSuggestion> {
+    let mut current: Option<&str> = None;
+    let mut switches = Vec::new();
+    for segment in app_track {
+        if segment.end_ms.saturating_sub(segment.start_ms) < MIN_APP_DWELL_MS {
+            continue;
+        }
+        let app = segment.process_name.as_str();
+        if current.is_some_and(|current| current != app) {
+            let title = segment.window_title.trim();
+            let title = if title.is_empty() {
+                app.trim_end_matches(".exe")
+            } else {
+                title
+            };
+            switches.push(ChapterSuggestion {
+                t_ms: segment.start_ms,
+                title: truncate_title(title),
+                source: MarkerSource::AppSwitch,
+            });
+        }
+        current = Some(app);
+    }
+    switches
+}
+
+/// 按停顿把字幕分成段落，段落用词与当前话题的重合度过低时在段落开头起新章节。
+fn topic_shifts(cues: &[TranscriptCue]) -> Vec<ChapterSuggestion> {
+    let mut paragraphs: Vec<Vec<&TranscriptCue>> = Vec::new();
+    for cue in cues {
+        match paragraphs.last_mut() {
+            Some(paragraph)
+                if paragraph
+                    .last()
+                    .is_some_and(|last| cue.start_ms < last.end_ms + PARAGRAPH_PAUSE_MS) =>
+            {
+                paragraph.push(cue)
+            }
+            _ => paragraphs.push(vec![cue]),
+        }
+    }
+    let mut shifts = Vec::new();
+    let mut topic: HashSet<String> = HashSet::new();
+    for paragraph in paragraphs {
+        let terms = paragraph
+            .iter()
+            .flat_map(|cue| topic_terms(&cue.text))
+            .collect::<HashSet<_>>();
+        if terms.len() < MIN_TOPIC_TERMS {
+            continue;
+        }
+        if topic.len() >= MIN_TOPIC_TERMS && similarity(&topic, &terms) < TOPIC_SIMILARITY {
+            shifts.push(ChapterSuggestion {
+                t_ms: paragraph[0].start_ms,
+                title: truncate_title(&paragraph[0].text),
+                source: MarkerSource::Transcript,
+            });
+            topic.clear();
+        }
+        topic.extend(terms);
+    }
+    shifts
+}
+
+/// 话题用词：三个字母以上的字母数字词，中日韩文字按相邻两字切分。
+fn topic_terms(text: &str) -> Vec<String> {
+    let lower = text.to_lowercase();
+    let mut terms = Vec::new();
+    let mut word = String::new();
+    let mut previous_cjk: Option<char> = None;
+    for ch in lower.chars().chain(std::iter::once(' ')) {
+        let cjk = is_cjk(ch);
+        if ch.is_alphanumeric() && !cjk {
+            word.push(ch);
+        } else {
+            if word.chars().count() >= 3 {
+                terms.push(std::mem::take(&mut word));
+            }
+            word.clear();
+        }
+        if cjk {
+            if let Some(previous) = previous_cjk {
+                terms.push(format!("{previous}{ch}"));
+            }
+            previous_cjk = Some(ch);
+        } else {
+            previous_cjk = None;
+        }
+    }
+    terms
+}
+
+/// 新段落中有多少用词在当前话题里出现过。
+fn similarity(topic: &HashSet<String>, terms: &HashSet<String>) -> f32 {
+    terms.intersection(topic).count() as f32 / terms.len() as f32
+}
+
+fn truncate_title(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= MAX_TITLE_CHARS {
+        return text;
+    }
+    let mut title = text.chars().take(MAX_TITLE_CHARS - 1).collect::<String>();
+    title.push('…');
+    title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::suggest_chapters;
+    use crate::domain::models::{AppFocusSegment, MarkerSource, TranscriptCue};
+
+    fn app(start_ms: u64, end_ms: u64, process_name: &str, window_title: &str) -> AppFocusSegment {
+        AppFocusSegment {
+            start_ms,
+            end_ms,
+            process_name: process_name.to_string(),
+            window_title: window_title.to_string(),
+            window_rect: None,
+        }
+    }
+
+    fn cue(start_ms: u64, text: &str) -> TranscriptCue {
+        TranscriptCue {
+            start_ms,
+            end_ms: start_ms + 3_000,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn app_switches_and_topic_shifts_become_spaced_chapters() {
+        let app_track = [
+            app(0, 40_000, "code.exe", "main.rs"),
+            app(40_000, 42_000, "slack.exe", "General"),
+            app(42_000, 60_000, "code.exe", "lib.rs"),
+            app(60_000, 120_000, "chrome.exe", "Billing – Dashboard"),
+        ];
+        let cues = [
+            cue(1_000, "Today we refactor the export pipeline"),
+            cue(4_000, "the export pipeline encoder settings matter"),
+            cue(30_000, "export pipeline encoder fallback works"),
+            cue(90_000, "Next open billing page invoices"),
+            cue(93_000, "billing invoices show totals"),
+        ];
+        let chapters = suggest_chapters(&app_track, &cues, 0, 120_000, &[]);
+        let summary = chapters
+            .iter()
+            .map(|chapter| (chapter.t_ms, chapter.source))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (60_000, MarkerSource::AppSwitch),
+                (90_000, MarkerSource::Transcript)
+            ]
+        );
+        assert_eq!(chapters[0].title, "Billing – Dashboard");
+        assert_eq!(chapters[1].title, "Next open billing page invoices");
+
+        let near_existing = suggest_chapters(&app_track, &cues, 0, 120_000, &[85_000]);
+        assert_eq!(near_existing.len(), 1);
+        assert_eq!(suggest_chapters(&app_track, &cues, 0, 95_000, &[]).len(), 1);
+    }
+}
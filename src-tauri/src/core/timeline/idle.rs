@@ -0,0 +1,46 @@
+use crate::domain::models::TimeRange;
+
+// 空闲段两端各保留一小段原速画面，让操作结果和下一次操作的起手都能看清。
+const IDLE_EDGE_MS: u64 = 500;
+
+/// 根据操作时间点（光标移动、点击、滚动、按键）找出持续无操作的区间。
+/// 录制开头与结尾也视为边界，区间长度（扣除两端保留）不足 `min_idle_ms` 的忽略。
+pub fn detect_idle_ranges(
+    activity_ms: &[u64],
+    duration_ms: u64,
+    min_idle_ms: u64,
+) -> Vec<TimeRange> {
+    let mut marks = activity_ms
+        .iter()
+        .copied()
+        .filter(|t_ms| *t_ms < duration_ms)
+        .collect::<Vec<_>>();
+    marks.push(0);
+    marks.push(duration_ms);
+    marks.sort_unstable();
+    marks.dedup();
+    marks
+        .windows(2)
+        .filter_map(|pair| {
+            let start_ms = pair[0] + IDLE_EDGE_MS;
+            let end_ms = pair[1].saturating_sub(IDLE_EDGE_MS);
+            (end_ms > start_ms && end_ms - start_ms >= min_idle_ms)
+                .then_some(TimeRange { start_ms, end_ms })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_idle_ranges;
+
+    #[test]
+    fn idle_ranges_keep_edges_around_activity() {
+        let ranges = detect_idle_ranges(&[1_000, 1_200, 9_000], 12_000, 3_000);
+        let spans = ranges
+            .iter()
+            .map(|range| (range.start_ms, range.end_ms))
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![(1_700, 8_500)]);
+    }
+}
@@ -1 +1,4 @@
+pub mod chapters;
+pub mod idle;
+pub mod sensitive;
 pub mod service;
@@ -0,0 +1,359 @@
+use crate::domain::models::{BlurCandidate, NormalizedRect, SensitiveKind};
+use crate::infra::ocr::OcrWord;
+use regex::Regex;
+use std::sync::OnceLock;
+
+pub const DEFAULT_SCAN_INTERVAL_MS: u64 = 2_000;
+pub const MIN_SCAN_INTERVAL_MS: u64 = 500;
+pub const MAX_SCAN_INTERVAL_MS: u64 = 10_000;
+// 长录制自动放宽采样间隔，单次扫描的 OCR 次数不超过该值。
+const MAX_SCAN_FRAMES: u64 = 600;
+// 识别框向外扩几个像素，盖住字形边缘与 OCR 框的误差。
+const HIT_PADDING_PX: u32 = 6;
+// 相邻采样中同类命中的框重叠超过该比例视为同一处内容。
+const SAME_HIT_IOU: f64 = 0.5;
+
+/// 单帧中的一处敏感文本，矩形已归一化。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitiveHit {
+    pub kind: SensitiveKind,
+    pub rect: NormalizedRect,
+    pub text: String,
+}
+
+fn patterns() -> &'static [(SensitiveKind, Regex)] {
+    static PATTERNS: OnceLock<Vec<(SensitiveKind, Regex)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            (
+                SensitiveKind::Email,
+                r"(?i)\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b",
+            ),
+            // 常见平台的密钥前缀，以及 `api_key = xxx` 这类赋值写法。
+            (
+                SensitiveKind::ApiKey,
+                r"\b(?:sk-[A-Za-z0-9_-]{20,}|[sr]k_(?:live|test)_[A-Za-z0-9]{16,}|gh[pousr]_[A-Za-z0-9]{30,}|github_pat_[A-Za-z0-9_]{30,}|AKIA[0-9A-Z]{16}|AIza[0-9A-Za-z_-]{35}|xox[abprs]-[A-Za-z0-9-]{10,})|(?i:\b(?:api[_-]?key|secret|access[_-]?token|auth[_-]?token)\b\s*[:=]\s*\S{12,})",
+            ),
+            (SensitiveKind::CardNumber, r"\b(?:\d[ -]?){12,18}\d\b"),
+        ]
+        .into_iter()
+        .map(|(kind, pattern)| (kind, Regex::new(pattern).expect("valid sensitive pattern")))
+        .collect()
+    })
+}
+
+/// 均匀采样的扫描时间点（源文件时间轴），区间过长时自动放宽间隔。
+pub fn scan_times_ms(start_ms: u64, end_ms: u64, interval_ms: u64) -> Vec<u64> {
+    if end_ms <= start_ms {
+        return Vec::new();
+    }
+    let interval_ms = interval_ms
+        .clamp(MIN_SCAN_INTERVAL_MS, MAX_SCAN_INTERVAL_MS)
+        .max((end_ms - start_ms).div_ceil(MAX_SCAN_FRAMES));
+    (start_ms..end_ms).step_by(interval_ms as usize).collect()
+}
+
+/// 在单帧 OCR 结果中查找敏感文本。按行拼接后匹配，跨单词的卡号也能命中；
+/// 命中范围覆盖到的单词框合并为一个矩形。
+pub fn find_sensitive_text(
+    lines: &[Vec<OcrWord>],
+    frame_w: u32,
+    frame_h: u32,
+) -> Vec<SensitiveHit> {
+    if frame_w == 0 || frame_h == 0 {
+        return Vec::new();
+    }
+    let mut hits = Vec::new();
+    for words in lines {
+        let mut text = String::new();
+        let mut spans = Vec::with_capacity(words.len());
+        for word in words {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            spans.push((text.len(), text.len() + word.text.len()));
+            text.push_str(&word.text);
+        }
+        for (kind, pattern) in patterns() {
+            for found in pattern.find_iter(&text) {
+                if *kind == SensitiveKind::CardNumber && !luhn_valid(found.as_str()) {
+                    continue;
+                }
+                let covered = words
+                    .iter()
+                    .zip(&spans)
+                    .filter(|(_, (start, end))| *start < found.end() && *end > found.start())
+                    .map(|(word, _)| word);
+                if let Some(rect) = word_bounds(covered, frame_w, frame_h) {
+                    hits.push(SensitiveHit {
+                        kind: *kind,
+                        rect,
+                        text: found.as_str().to_string(),
+                    });
+                }
+            }
+        }
+    }
+    hits
+}
+
+/// 把逐帧命中合并为打码建议：相邻采样中同类且位置重叠的命中视为同一处，
+/// 时间范围向前后各扩一个采样间隔，宁可多遮一点也不漏掉出现与消失的瞬间。
+pub fn merge_hits(
+    samples: &[(u64, Vec<SensitiveHit>)],
+    interval_ms: u64,
+    range_start_ms: u64,
+    range_end_ms: u64,
+) -> Vec<BlurCandidate> {
+    struct Open {
+        hit: SensitiveHit,
+        first_ms: u64,
+        last_ms: u64,
+    }
+    let mut open: Vec<Open> = Vec::new();
+    let mut closed: Vec<Open> = Vec::new();
+    let mut previous_ms = None;
+    for (t_ms, hits) in samples {
+        let mut next: Vec<Open> = Vec::new();
+        for hit in hits {
+            let continued = open.iter().position(|item| {
+                item.hit.kind == hit.kind
+                    && Some(item.last_ms) == previous_ms
+                    && iou(&item.hit.rect, &hit.rect) >= SAME_HIT_IOU
+            });
+            match continued {
+                Some(index) => {
+                    let mut item = open.swap_remove(index);
+                    item.hit.rect = union(&item.hit.rect, &hit.rect);
+                    item.last_ms = *t_ms;
+                    next.push(item);
+                }
+                None => next.push(Open {
+                    hit: hit.clone(),
+                    first_ms: *t_ms,
+                    last_ms: *t_ms,
+                }),
+            }
+        }
+        closed.append(&mut open);
+        open = next;
+        previous_ms = Some(*t_ms);
+    }
+    closed.append(&mut open);
+    closed.sort_by_key(|item| item.first_ms);
+    closed
+        .into_iter()
+        .map(|item| BlurCandidate {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind: item.hit.kind,
+            rect: item.hit.rect,
+            start_ms: item
+                .first_ms
+                .saturating_sub(interval_ms)
+                .max(range_start_ms),
+            end_ms: (item.last_ms + interval_ms).min(range_end_ms),
+            preview: mask_preview(item.hit.kind, &item.hit.text),
+        })
+        .filter(|candidate| candidate.end_ms > candidate.start_ms)
+        .collect()
+}
+
+/// 只保留足以辨认位置的片段：邮箱留前两位与域名，密钥留前 4 位，卡号留后 4 位。
+pub fn mask_preview(kind: SensitiveKind, text: &str) -> String {
+    match kind {
+        SensitiveKind::Email => match text.split_once('@') {
+            Some((local, domain)) => {
+                format!("{}***@{domain}", local.chars().take(2).collect::<String>())
+            }
+            None => "***".to_string(),
+        },
+        SensitiveKind::ApiKey => format!("{}****", text.chars().take(4).collect::<String>()),
+        SensitiveKind::CardNumber => {
+            let digits = text
+                .chars()
+                .filter(char::is_ascii_digit)
+                .collect::<Vec<_>>();
+            let tail = digits[digits.len().saturating_sub(4)..]
+                .iter()
+                .collect::<String>();
+            format!("**** {tail}")
+        }
+    }
+}
+
+/// 卡号校验位，过滤掉订单号、时间戳之类的长数字。
+fn luhn_valid(text: &str) -> bool {
+    let digits = text
+        .chars()
+        .filter_map(|ch| ch.to_digit(10))
+        .collect::<Vec<_>>();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, digit)| {
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                *digit
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn word_bounds<'a>(
+    words: impl Iterator<Item = &'a OcrWord>,
+    frame_w: u32,
+    frame_h: u32,
+) -> Option<NormalizedRect> {
+    let (left, top, right, bottom) = words.fold(None::<(u32, u32, u32, u32)>, |bounds, word| {
+        let (left, top, right, bottom) = (
+            word.left,
+            word.top,
+            word.left + word.width,
+            word.top + word.height,
+        );
+        Some(match bounds {
+            None => (left, top, right, bottom),
+            Some((l, t, r, b)) => (l.min(left), t.min(top), r.max(right), b.max(bottom)),
+        })
+    })?;
+    let left = left.saturating_sub(HIT_PADDING_PX);
+    let top = top.saturating_sub(HIT_PADDING_PX);
+    let right = (right + HIT_PADDING_PX).min(frame_w);
+    let bottom = (bottom + HIT_PADDING_PX).min(frame_h);
+    (right > left && bottom > top).then(|| NormalizedRect {
+        x: f64::from(left) / f64::from(frame_w),
+        y: f64::from(top) / f64::from(frame_h),
+        width: f64::from(right - left) / f64::from(frame_w),
+        height: f64::from(bottom - top) / f64::from(frame_h),
+    })
+}
+
+fn union(a: &NormalizedRect, b: &NormalizedRect) -> NormalizedRect {
+    let x = a.x.min(b.x);
+    let y = a.y.min(b.y);
+    NormalizedRect {
+        x,
+        y,
+        width: (a.x + a.width).max(b.x + b.width) - x,
+        height: (a.y + a.height).max(b.y + b.height) - y,
+    }
+}
+
+fn iou(a: &NormalizedRect, b: &NormalizedRect) -> f64 {
+    let overlap_w = ((a.x + a.width).min(b.x + b.width) - a.x.max(b.x)).max(0.0);
+    let overlap_h = ((a.y + a.height).min(b.y + b.height) - a.y.max(b.y)).max(0.0);
+    let overlap = overlap_w * overlap_h;
+    let total = a.width * a.height + b.width * b.height - overlap;
+    if total <= 0.0 {
+        0.0
+    } else {
+        overlap / total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_sensitive_text, mask_preview, merge_hits, scan_times_ms, SensitiveHit};
+    use crate::domain::models::{NormalizedRect, SensitiveKind};
+    use crate::infra::ocr::OcrWord;
+
+    fn word(text: &str, left: u32, top: u32) -> OcrWord {
+        OcrWord {
+            text: text.to_string(),
+            left,
+            top,
+            width: text.len() as u32 * 10,
+            height: 20,
+        }
+    }
+
+    #[test]
+    fn detects_emails_keys_and_luhn_valid_cards() {
+        let lines = vec![
+            vec![
+                word("mail", 100, 100),
+                word("jane.doe@example.com", 150, 100),
+            ],
+            vec![word("OPENAI_KEY=sk-abcdefghijklmnopqrstuvwx", 100, 200)],
+            vec![
+                word("card", 100, 300),
+                word("4111", 150, 300),
+                word("1111", 200, 300),
+                word("1111", 250, 300),
+                word("1111", 300, 300),
+            ],
+            // 校验位不对的长数字不算卡号。
+            vec![word("order", 100, 400), word("1234567812345678", 160, 400)],
+        ];
+        let hits = find_sensitive_text(&lines, 1000, 1000);
+        let kinds = hits.iter().map(|hit| hit.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                SensitiveKind::Email,
+                SensitiveKind::ApiKey,
+                SensitiveKind::CardNumber
+            ]
+        );
+        // 卡号跨 4 个单词，矩形覆盖全部数字但不含前面的标签。
+        let card = &hits[2].rect;
+        assert!((card.x - 0.144).abs() < 1e-9);
+        assert!((card.width - 0.202).abs() < 1e-9);
+        assert_eq!(
+            mask_preview(hits[0].kind, &hits[0].text),
+            "ja***@example.com"
+        );
+        assert_eq!(mask_preview(hits[2].kind, &hits[2].text), "**** 1111");
+    }
+
+    #[test]
+    fn consecutive_hits_merge_into_one_candidate() {
+        let hit = |x: f64| SensitiveHit {
+            kind: SensitiveKind::Email,
+            rect: NormalizedRect {
+                x,
+                y: 0.1,
+                width: 0.2,
+                height: 0.05,
+            },
+            text: "jane@example.com".to_string(),
+        };
+        let samples = vec![
+            (0, vec![]),
+            (2_000, vec![hit(0.1)]),
+            (4_000, vec![hit(0.11)]),
+            (6_000, vec![]),
+            // 中断后再次出现视为新的建议。
+            (8_000, vec![hit(0.1)]),
+        ];
+        let candidates = merge_hits(&samples, 2_000, 0, 9_000);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!((candidates[0].start_ms, candidates[0].end_ms), (0, 6_000));
+        assert!((candidates[0].rect.width - 0.21).abs() < 1e-9);
+        assert_eq!(
+            (candidates[1].start_ms, candidates[1].end_ms),
+            (6_000, 9_000)
+        );
+    }
+
+    #[test]
+    fn long_ranges_cap_the_number_of_frames() {
+        assert_eq!(
+            scan_times_ms(1_000, 7_000, 2_000),
+            vec![1_000, 3_000, 5_000]
+        );
+        assert_eq!(scan_times_ms(0, 3_600_000, 2_000).len(), 600);
+        assert!(scan_times_ms(5_000, 5_000, 2_000).is_empty());
+    }
+}
@@ -1,4 +1,8 @@
-use crate::domain::models::{ProjectManifest, TimelinePatch};
+use crate::domain::models::{
+    AppError, BlurRegion, CursorSpotlight, NormalizedRect, ProjectManifest, ShapeAnnotation,
+    ShapeKind, TextAnnotation, TimeRange, TimelinePatch, TimelineSegment,
+};
+use crate::infra::ffmpeg::scene::parse_hex_color;
 use chrono::Utc;
 
 pub fn apply_timeline_patch(manifest: &mut ProjectManifest, patch: TimelinePatch) {
@@ -14,5 +18,570 @@ pub fn apply_timeline_patch(manifest: &mut ProjectManifest, patch: TimelinePatch
     if let Some(cursor_highlight_enabled) = patch.cursor_highlight_enabled {
         manifest.timeline.cursor_highlight_enabled = cursor_highlight_enabled;
     }
+    if let Some(scroll_indicator_enabled) = patch.scroll_indicator_enabled {
+        manifest.timeline.scroll_indicator_enabled = scroll_indicator_enabled;
+    }
+    if let Some(fit_mode) = patch.fit_mode {
+        manifest.timeline.fit_mode = fit_mode;
+    }
+    if let Some(cursor_style) = patch.cursor_style {
+        manifest.timeline.cursor_style = cursor_style;
+    }
+    if let Some(cursor_size) = patch.cursor_size {
+        manifest.timeline.cursor_size = cursor_size.clamp(12, 128);
+    }
+    if let Some(spotlight) = patch.spotlight {
+        manifest.timeline.spotlight = CursorSpotlight {
+            enabled: spotlight.enabled,
+            radius: spotlight.radius.clamp(40, 600),
+            dim: spotlight.dim.clamp(0.1, 0.9),
+            feather: spotlight.feather.min(300),
+        };
+    }
+    if let Some(auto_cut_silence) = patch.auto_cut_silence {
+        manifest.timeline.auto_cut_silence = auto_cut_silence;
+    }
+    if let Some(silence_cut_mode) = patch.silence_cut_mode {
+        manifest.timeline.silence_cut_mode = silence_cut_mode;
+    }
+    if let Some(idle_speedup_enabled) = patch.idle_speedup_enabled {
+        manifest.timeline.idle_speedup_enabled = idle_speedup_enabled;
+    }
+    if let Some(idle_speed) = patch.idle_speed {
+        manifest.timeline.idle_speed = idle_speed.clamp(1.5, 16.0);
+    }
+    if let Some(idle_min_ms) = patch.idle_min_ms {
+        manifest.timeline.idle_min_ms = idle_min_ms.clamp(1000, 60_000);
+    }
+    if let Some(segments) = patch.segments {
+        manifest.timeline.segments = segments;
+    }
+    if let Some(mut markers) = patch.markers {
+        markers.sort_by_key(|marker| marker.t_ms);
+        manifest.timeline.markers = markers;
+    }
     manifest.updated_at = Utc::now();
 }
+
+// 过短的保留片段在成片中一闪而过，视为误操作。
+const MIN_SEGMENT_MS: u64 = 200;
+// 低于 0.5x 时 atempo 需要串联且语音明显失真，高于 4x 则难以看清操作。
+pub const MIN_SEGMENT_SPEED: f32 = 0.5;
+pub const MAX_SEGMENT_SPEED: f32 = 4.0;
+
+/// 校验保留片段：每段不短于 200ms、互不重叠、倍速在 0.5–4 之间，返回按起点排序后的结果。
+pub fn validate_segments(segments: &[TimelineSegment]) -> Result<Vec<TimelineSegment>, AppError> {
+    let mut sorted = segments.to_vec();
+    sorted.sort_by_key(|segment| segment.start_ms);
+    if let Some(segment) = sorted
+        .iter()
+        .find(|segment| !(MIN_SEGMENT_SPEED..=MAX_SEGMENT_SPEED).contains(&segment.speed))
+    {
+        return Err(AppError::new(
+            "INVALID_TIMELINE",
+            format!(
+                "segment {}..{} speed {} is outside {MIN_SEGMENT_SPEED}..{MAX_SEGMENT_SPEED}",
+                segment.start_ms, segment.end_ms, segment.speed
+            ),
+            Some("片段倍速需在 0.5x 到 4x 之间".to_string()),
+        ));
+    }
+    if let Some(segment) = sorted
+        .iter()
+        .find(|segment| segment.end_ms < segment.start_ms + MIN_SEGMENT_MS)
+    {
+        return Err(AppError::new(
+            "INVALID_TIMELINE",
+            format!(
+                "segment {}..{} is shorter than {MIN_SEGMENT_MS}ms",
+                segment.start_ms, segment.end_ms
+            ),
+            Some("请调整片段起止时间".to_string()),
+        ));
+    }
+    if let Some(pair) = sorted
+        .windows(2)
+        .find(|pair| pair[1].start_ms < pair[0].end_ms)
+    {
+        return Err(AppError::new(
+            "INVALID_TIMELINE",
+            format!(
+                "segments overlap at {}..{}",
+                pair[1].start_ms, pair[0].end_ms
+            ),
+            Some("保留片段之间不能重叠".to_string()),
+        ));
+    }
+    Ok(sorted)
+}
+
+/// 保留片段在 `[start_ms, end_ms)` 内的空隙，即导出时需要剪掉的区间。
+pub fn segment_gaps(segments: &[TimelineSegment], start_ms: u64, end_ms: u64) -> Vec<TimeRange> {
+    let mut gaps = Vec::new();
+    let mut cursor = start_ms;
+    for segment in segments {
+        let keep_start = segment.start_ms.clamp(start_ms, end_ms);
+        if keep_start > cursor {
+            gaps.push(TimeRange {
+                start_ms: cursor,
+                end_ms: keep_start,
+            });
+        }
+        cursor = cursor.max(segment.end_ms.min(end_ms));
+    }
+    if end_ms > cursor {
+        gaps.push(TimeRange {
+            start_ms: cursor,
+            end_ms,
+        });
+    }
+    gaps
+}
+
+/// 从保留片段中剪掉 `cuts`（源文件时间轴，按起点排序）。没有保留片段时以整个裁剪区间为起点；
+/// 剪切后短于 200ms 的残片一并丢弃，片段倍速保持不变。
+pub fn cut_segments(
+    segments: &[TimelineSegment],
+    trim_start_ms: u64,
+    trim_end_ms: u64,
+    cuts: &[TimeRange],
+) -> Result<Vec<TimelineSegment>, AppError> {
+    let mut kept = if segments.is_empty() {
+        vec![TimelineSegment {
+            start_ms: trim_start_ms,
+            end_ms: trim_end_ms,
+            speed: 1.0,
+        }]
+    } else {
+        segments.to_vec()
+    };
+    for cut in cuts {
+        kept = kept
+            .into_iter()
+            .flat_map(|segment| {
+                let before = TimelineSegment {
+                    end_ms: segment.end_ms.min(cut.start_ms),
+                    ..segment
+                };
+                let after = TimelineSegment {
+                    start_ms: segment.start_ms.max(cut.end_ms),
+                    ..segment
+                };
+                [before, after]
+            })
+            .filter(|segment| segment.end_ms >= segment.start_ms + MIN_SEGMENT_MS)
+            .collect();
+    }
+    if kept.is_empty() {
+        return Err(AppError::new(
+            "INVALID_TIMELINE",
+            "cutting the selection would remove the whole recording",
+            Some("至少需要保留一段内容".to_string()),
+        ));
+    }
+    Ok(kept)
+}
+
+/// 把 `range` 并入静音区间：重叠或首尾相接的区间合并，结果按起点排序。
+pub fn merge_mute_range(
+    ranges: &[TimeRange],
+    range: TimeRange,
+) -> Result<Vec<TimeRange>, AppError> {
+    if range.end_ms <= range.start_ms {
+        return Err(AppError::new(
+            "INVALID_MUTE_RANGE",
+            format!("mute range {}..{} is empty", range.start_ms, range.end_ms),
+            Some("请确保静音区间的结束时间晚于开始时间".to_string()),
+        ));
+    }
+    let mut sorted = ranges.to_vec();
+    sorted.push(range);
+    sorted.sort_by_key(|item| item.start_ms);
+    let mut merged: Vec<TimeRange> = Vec::with_capacity(sorted.len());
+    for item in sorted {
+        match merged.last_mut() {
+            Some(last) if item.start_ms <= last.end_ms => {
+                last.end_ms = last.end_ms.max(item.end_ms)
+            }
+            _ => merged.push(item),
+        }
+    }
+    Ok(merged)
+}
+
+/// 从静音区间中扣除 `range`（恢复这段声音），被切开的区间拆成前后两段。
+pub fn subtract_mute_range(ranges: &[TimeRange], range: TimeRange) -> Vec<TimeRange> {
+    let mut remaining = Vec::with_capacity(ranges.len() + 1);
+    for item in ranges {
+        if item.end_ms <= range.start_ms || item.start_ms >= range.end_ms {
+            remaining.push(*item);
+            continue;
+        }
+        if item.start_ms < range.start_ms {
+            remaining.push(TimeRange {
+                start_ms: item.start_ms,
+                end_ms: range.start_ms,
+            });
+        }
+        if item.end_ms > range.end_ms {
+            remaining.push(TimeRange {
+                start_ms: range.end_ms,
+                end_ms: item.end_ms,
+            });
+        }
+    }
+    remaining
+}
+
+// 打码区域的最小边长（归一化），过小的框在预览中无法选中。
+const MIN_BLUR_SIDE: f64 = 0.005;
+
+/// 校验打码区域：矩形夹取到画面内且不能退化，时间区间不能为空。
+pub fn normalize_blur_region(region: BlurRegion) -> Result<BlurRegion, AppError> {
+    if region.end_ms <= region.start_ms {
+        return Err(AppError::new(
+            "INVALID_BLUR_REGION",
+            format!(
+                "blur region {}..{} is empty",
+                region.start_ms, region.end_ms
+            ),
+            Some("请调整打码区域的起止时间".to_string()),
+        ));
+    }
+    let rect = region.rect;
+    if ![rect.x, rect.y, rect.width, rect.height]
+        .iter()
+        .all(|value| value.is_finite())
+    {
+        return Err(invalid_blur_rect());
+    }
+    let x = rect.x.clamp(0.0, 1.0);
+    let y = rect.y.clamp(0.0, 1.0);
+    let rect = NormalizedRect {
+        x,
+        y,
+        width: (rect.x + rect.width).clamp(0.0, 1.0) - x,
+        height: (rect.y + rect.height).clamp(0.0, 1.0) - y,
+    };
+    if rect.width < MIN_BLUR_SIDE || rect.height < MIN_BLUR_SIDE {
+        return Err(invalid_blur_rect());
+    }
+    Ok(BlurRegion { rect, ..region })
+}
+
+fn invalid_blur_rect() -> AppError {
+    AppError::new(
+        "INVALID_BLUR_REGION",
+        "blur region must cover part of the frame",
+        Some("请在画面内框选需要打码的区域".to_string()),
+    )
+}
+
+// 标注是标题式短文本，过长的内容在画面上也放不下。
+const MAX_ANNOTATION_CHARS: usize = 200;
+
+/// 校验文字标注：文本去除首尾空白后不能为空，颜色须为 `#RRGGBB`，
+/// 位置与字号、不透明度夹取到可渲染范围。
+pub fn normalize_text_annotation(annotation: TextAnnotation) -> Result<TextAnnotation, AppError> {
+    let invalid = |field: &str, message: String, suggestion: &str| {
+        AppError::new(
+            "INVALID_TEXT_ANNOTATION",
+            message,
+            Some(suggestion.to_string()),
+        )
+        .with_param("field", field)
+    };
+    let text = annotation.text.trim().to_string();
+    if text.is_empty() || text.chars().count() > MAX_ANNOTATION_CHARS {
+        return Err(invalid(
+            "text",
+            format!("annotation text must be 1..={MAX_ANNOTATION_CHARS} chars"),
+            "标注文字不能为空，且不超过 200 个字符",
+        ));
+    }
+    if annotation.end_ms <= annotation.start_ms {
+        return Err(invalid(
+            "time",
+            format!(
+                "annotation {}..{} is empty",
+                annotation.start_ms, annotation.end_ms
+            ),
+            "请调整标注的起止时间",
+        ));
+    }
+    if !annotation.x.is_finite() || !annotation.y.is_finite() {
+        return Err(invalid(
+            "position",
+            "annotation position must be finite".to_string(),
+            "请重新拖动标注位置",
+        ));
+    }
+    let mut style = annotation.style;
+    for (field, color) in [
+        ("color", Some(&style.color)),
+        ("background", style.background.as_ref()),
+    ] {
+        if let Some(color) = color {
+            if parse_hex_color(color).is_none() {
+                return Err(invalid(
+                    field,
+                    format!("invalid annotation color: {color}"),
+                    "请使用 #RRGGBB 格式的颜色",
+                ));
+            }
+        }
+    }
+    style.font_size = style.font_size.clamp(12, 200);
+    style.background_opacity = style.background_opacity.clamp(0.0, 1.0);
+    Ok(TextAnnotation {
+        text,
+        x: annotation.x.clamp(0.0, 1.0),
+        y: annotation.y.clamp(0.0, 1.0),
+        style,
+        ..annotation
+    })
+}
+
+// 形状外接框的最小边长与箭头最短长度（归一化），过小的形状在画面上看不出来。
+const MIN_SHAPE_SIDE: f64 = 0.005;
+const MIN_ARROW_LENGTH: f64 = 0.01;
+
+/// 校验形状标注：坐标夹取到画面内，矩形与椭圆不能退化为线，箭头不能退化为点。
+pub fn normalize_shape_annotation(shape: ShapeAnnotation) -> Result<ShapeAnnotation, AppError> {
+    let invalid = |field: &str, message: String, suggestion: &str| {
+        AppError::new(
+            "INVALID_SHAPE_ANNOTATION",
+            message,
+            Some(suggestion.to_string()),
+        )
+        .with_param("field", field)
+    };
+    if shape.end_ms <= shape.start_ms {
+        return Err(invalid(
+            "time",
+            format!("shape {}..{} is empty", shape.start_ms, shape.end_ms),
+            "请调整形状的起止时间",
+        ));
+    }
+    let (mut from, mut to) = (shape.from, shape.to);
+    if ![from.x, from.y, to.x, to.y]
+        .iter()
+        .all(|value| value.is_finite())
+    {
+        return Err(invalid(
+            "position",
+            "shape position must be finite".to_string(),
+            "请重新绘制形状",
+        ));
+    }
+    for point in [&mut from, &mut to] {
+        point.x = point.x.clamp(0.0, 1.0);
+        point.y = point.y.clamp(0.0, 1.0);
+    }
+    let (width, height) = ((to.x - from.x).abs(), (to.y - from.y).abs());
+    let degenerate = match shape.kind {
+        ShapeKind::Arrow => width.hypot(height) < MIN_ARROW_LENGTH,
+        ShapeKind::Rectangle | ShapeKind::Ellipse => {
+            width < MIN_SHAPE_SIDE || height < MIN_SHAPE_SIDE
+        }
+    };
+    if degenerate {
+        return Err(invalid(
+            "position",
+            "shape is too small".to_string(),
+            "请在画面上拖出更大的形状",
+        ));
+    }
+    let mut style = shape.style;
+    for (field, color) in [
+        ("strokeColor", Some(&style.stroke_color)),
+        ("fill", style.fill.as_ref()),
+    ] {
+        if let Some(color) = color {
+            if parse_hex_color(color).is_none() {
+                return Err(invalid(
+                    field,
+                    format!("invalid shape color: {color}"),
+                    "请使用 #RRGGBB 格式的颜色",
+                ));
+            }
+        }
+    }
+    style.stroke_width = style.stroke_width.clamp(1, 40);
+    style.fill_opacity = style.fill_opacity.clamp(0.0, 1.0);
+    Ok(ShapeAnnotation {
+        from,
+        to,
+        style,
+        ..shape
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cut_segments, merge_mute_range, normalize_blur_region, normalize_shape_annotation,
+        normalize_text_annotation, segment_gaps, subtract_mute_range, validate_segments,
+    };
+    use crate::domain::models::{
+        AnnotationStyle, BlurMode, BlurRegion, NormalizedPoint, NormalizedRect, ShapeAnnotation,
+        ShapeKind, ShapeStyle, TextAnnotation, TimeRange, TimelineSegment,
+    };
+
+    fn range(start_ms: u64, end_ms: u64) -> TimeRange {
+        TimeRange { start_ms, end_ms }
+    }
+
+    fn segment(start_ms: u64, end_ms: u64) -> TimelineSegment {
+        TimelineSegment {
+            start_ms,
+            end_ms,
+            speed: 1.0,
+        }
+    }
+
+    #[test]
+    fn segments_are_sorted_and_overlaps_rejected() {
+        let sorted = validate_segments(&[segment(5_000, 8_000), segment(0, 2_000)]).unwrap();
+        assert_eq!(sorted[0].start_ms, 0);
+        assert!(validate_segments(&[segment(0, 3_000), segment(2_000, 4_000)]).is_err());
+        assert!(validate_segments(&[segment(1_000, 1_100)]).is_err());
+    }
+
+    #[test]
+    fn segment_speed_must_stay_within_supported_range() {
+        let with_speed = |speed: f32| TimelineSegment {
+            speed,
+            ..segment(0, 2_000)
+        };
+        assert!(validate_segments(&[with_speed(0.5), segment(3_000, 4_000)]).is_ok());
+        assert!(validate_segments(&[with_speed(4.0)]).is_ok());
+        assert!(validate_segments(&[with_speed(0.25)]).is_err());
+        assert!(validate_segments(&[with_speed(8.0)]).is_err());
+    }
+
+    #[test]
+    fn gaps_cover_everything_outside_kept_segments() {
+        let gaps = segment_gaps(
+            &[segment(1_000, 3_000), segment(5_000, 12_000)],
+            500,
+            10_000,
+        )
+        .iter()
+        .map(|gap| (gap.start_ms, gap.end_ms))
+        .collect::<Vec<_>>();
+        assert_eq!(gaps, vec![(500, 1_000), (3_000, 5_000)]);
+    }
+
+    #[test]
+    fn cuts_split_kept_segments_and_drop_slivers() {
+        let kept = cut_segments(&[], 0, 10_000, &[range(2_000, 3_000), range(9_900, 12_000)])
+            .unwrap()
+            .iter()
+            .map(|segment| (segment.start_ms, segment.end_ms))
+            .collect::<Vec<_>>();
+        assert_eq!(kept, vec![(0, 2_000), (3_000, 9_900)]);
+
+        let fast = TimelineSegment {
+            speed: 2.0,
+            ..segment(4_000, 8_000)
+        };
+        let kept =
+            cut_segments(&[segment(0, 1_000), fast], 0, 10_000, &[range(100, 5_000)]).unwrap();
+        assert_eq!(kept.len(), 1);
+        assert_eq!((kept[0].start_ms, kept[0].speed), (5_000, 2.0));
+        assert!(cut_segments(&[], 0, 1_000, &[range(0, 1_000)]).is_err());
+    }
+
+    #[test]
+    fn blur_regions_are_clamped_to_the_frame() {
+        let region = |x: f64, width: f64, start_ms: u64, end_ms: u64| BlurRegion {
+            id: "b1".to_string(),
+            rect: NormalizedRect {
+                x,
+                y: 0.5,
+                width,
+                height: 0.2,
+            },
+            start_ms,
+            end_ms,
+            mode: BlurMode::Blur,
+        };
+        let clamped = normalize_blur_region(region(0.9, 0.3, 0, 1_000)).unwrap();
+        assert!((clamped.rect.width - 0.1).abs() < 1e-9);
+        assert!(normalize_blur_region(region(1.2, 0.3, 0, 1_000)).is_err());
+        assert!(normalize_blur_region(region(0.1, 0.3, 1_000, 1_000)).is_err());
+        assert!(normalize_blur_region(region(f64::NAN, 0.3, 0, 1_000)).is_err());
+    }
+
+    #[test]
+    fn text_annotations_are_trimmed_and_clamped() {
+        let annotation = |text: &str, color: &str| TextAnnotation {
+            id: "t1".to_string(),
+            text: text.to_string(),
+            x: 1.4,
+            y: -0.2,
+            start_ms: 1_000,
+            end_ms: 4_000,
+            style: AnnotationStyle {
+                font_size: 400,
+                color: color.to_string(),
+                ..AnnotationStyle::default()
+            },
+        };
+        let normalized =
+            normalize_text_annotation(annotation("  Step 2: configure the webhook ", "#00FF88"))
+                .unwrap();
+        assert_eq!(normalized.text, "Step 2: configure the webhook");
+        assert_eq!((normalized.x, normalized.y), (1.0, 0.0));
+        assert_eq!(normalized.style.font_size, 200);
+        assert!(normalize_text_annotation(annotation("   ", "#00FF88")).is_err());
+        let error = normalize_text_annotation(annotation("ok", "green")).unwrap_err();
+        assert_eq!(error.code, "INVALID_TEXT_ANNOTATION");
+    }
+
+    #[test]
+    fn shapes_are_clamped_and_degenerate_ones_rejected() {
+        let shape = |kind: ShapeKind, to: (f64, f64)| ShapeAnnotation {
+            id: "s1".to_string(),
+            kind,
+            from: NormalizedPoint { x: 0.2, y: 0.2 },
+            to: NormalizedPoint { x: to.0, y: to.1 },
+            start_ms: 0,
+            end_ms: 2_000,
+            style: ShapeStyle {
+                stroke_width: 0,
+                ..ShapeStyle::default()
+            },
+        };
+        let clamped = normalize_shape_annotation(shape(ShapeKind::Rectangle, (1.5, 0.6))).unwrap();
+        assert_eq!(clamped.to.x, 1.0);
+        assert_eq!(clamped.style.stroke_width, 1);
+        // 水平箭头合法，高度为 0 的矩形与椭圆不合法。
+        assert!(normalize_shape_annotation(shape(ShapeKind::Arrow, (0.6, 0.2))).is_ok());
+        assert!(normalize_shape_annotation(shape(ShapeKind::Ellipse, (0.6, 0.2))).is_err());
+        assert!(normalize_shape_annotation(shape(ShapeKind::Arrow, (0.2, 0.2))).is_err());
+    }
+
+    #[test]
+    fn mute_ranges_merge_on_add_and_split_on_remove() {
+        let spans = |ranges: &[TimeRange]| {
+            ranges
+                .iter()
+                .map(|range| (range.start_ms, range.end_ms))
+                .collect::<Vec<_>>()
+        };
+        let ranges = merge_mute_range(&[range(1_000, 2_000)], range(5_000, 6_000)).unwrap();
+        let ranges = merge_mute_range(&ranges, range(1_500, 3_000)).unwrap();
+        assert_eq!(spans(&ranges), vec![(1_000, 3_000), (5_000, 6_000)]);
+        assert!(merge_mute_range(&ranges, range(4_000, 4_000)).is_err());
+
+        let ranges = subtract_mute_range(&ranges, range(2_000, 5_500));
+        assert_eq!(spans(&ranges), vec![(1_000, 2_000), (5_500, 6_000)]);
+        let ranges = subtract_mute_range(&ranges, range(1_200, 1_400));
+        assert_eq!(
+            spans(&ranges),
+            vec![(1_000, 1_200), (1_400, 2_000), (5_500, 6_000)]
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use crate::domain::models::{
+    AppError, ExportProfile, TimeRange, TranscriptCue, TranscriptWord, TranscriptWordRange,
+};
+use crate::infra::ffmpeg::export::subtitle_path;
+
+/// 读取项目挂载的字幕文件作为文字稿；未挂载、文件缺失或无法解析时返回空。
+/// 字幕时间轴对应原始录制，与时间轴区间使用同一套毫秒时间。
+pub fn load_transcript(profile: &ExportProfile) -> Vec<TranscriptCue> {
+    let Some(path) = subtitle_path(profile) else {
+        return Vec::new();
+    };
+    match std::fs::read(&path) {
+        Ok(bytes) => parse_transcript(&String::from_utf8_lossy(&bytes)),
+        Err(error) => {
+            tracing::warn!("failed to read transcript {}: {error}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+/// 解析 SRT、VTT 与 ASS 字幕为按时间排序的文字段，去掉样式标签，空白段跳过。
+pub fn parse_transcript(content: &str) -> Vec<TranscriptCue> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut cues = if content.contains("[Events]") {
+        parse_ass(&content)
+    } else {
+        parse_srt_or_vtt(&content)
+    };
+    cues.retain(|cue| !cue.text.is_empty() && cue.end_ms > cue.start_ms);
+    cues.sort_by_key(|cue| cue.start_ms);
+    cues
+}
+
+fn parse_srt_or_vtt(content: &str) -> Vec<TranscriptCue> {
+    content
+        .split("\n\n")
+        .filter_map(|block| {
+            let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+            let (start, end) = lines.next()?.split_once("-->")?;
+            // VTT 的时间行后面可能跟着 `align:start` 等位置设置。
+            let end = end.split_whitespace().next()?;
+            Some(TranscriptCue {
+                start_ms: parse_timestamp(start.trim())?,
+                end_ms: parse_timestamp(end)?,
+                text: clean_text(&lines.collect::<Vec<_>>().join(" ")),
+            })
+        })
+        .collect()
+}
+
+/// 按 `[Events]` 的 Format 行定位 Start、End、Text 列；Text 为最后一列，可以包含逗号。
+fn parse_ass(content: &str) -> Vec<TranscriptCue> {
+    let mut columns = None;
+    let mut cues = Vec::new();
+    for line in content.lines() {
+        if let Some(format) = line.strip_prefix("Format:") {
+            let names = format.split(',').map(str::trim).collect::<Vec<_>>();
+            let index = |name: &str| names.iter().position(|item| *item == name);
+            columns = match (index("Start"), index("End"), index("Text")) {
+                (Some(start), Some(end), Some(text)) => Some((start, end, text)),
+                _ => None,
+            };
+            continue;
+        }
+        let (Some(dialogue), Some((start, end, text))) = (line.strip_prefix("Dialogue:"), columns)
+        else {
+            continue;
+        };
+        let fields = dialogue.splitn(text + 1, ',').collect::<Vec<_>>();
+        let (Some(start_ms), Some(end_ms), Some(text)) = (
+            fields
+                .get(start)
+                .and_then(|value| parse_timestamp(value.trim())),
+            fields
+                .get(end)
+                .and_then(|value| parse_timestamp(value.trim())),
+            fields.get(text),
+        ) else {
+            continue;
+        };
+        cues.push(TranscriptCue {
+            start_ms,
+            end_ms,
+            text: clean_text(&text.replace("\\N", " ").replace("\\n", " ")),
+        });
+    }
+    cues
+}
+
+/// 支持 `01:02:03,450`（SRT）、`01:02:03.450` / `02:03.450`（VTT）与 `1:02:03.45`（ASS，百分之一秒）。
+fn parse_timestamp(value: &str) -> Option<u64> {
+    let (clock, fraction) = value.split_once([',', '.']).unwrap_or((value, "0"));
+    let mut seconds = 0u64;
+    for part in clock.split(':') {
+        seconds = seconds.checked_mul(60)?.checked_add(part.parse().ok()?)?;
+    }
+    if fraction.is_empty() || !fraction.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let millis = format!("{fraction:0<3}")[..3].parse::<u64>().ok()?;
+    seconds.checked_mul(1000)?.checked_add(millis)
+}
+
+/// 去掉 `<i>`、`<c.yellow>` 等 HTML 样式与 ASS 的 `{\b1}` 覆盖标签，合并空白。
+fn clean_text(text: &str) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut closing = None;
+    for ch in text.chars() {
+        match (closing, ch) {
+            (None, '<') => closing = Some('>'),
+            (None, '{') => closing = Some('}'),
+            (Some(end), ch) if ch == end => closing = None,
+            (None, ch) => cleaned.push(ch),
+            _ => {}
+        }
+    }
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 把字幕段拆成词：空格分隔的词各占一个，中日韩文字每个字单独成词。
+/// 字幕只有段级时间，段内按字符数线性分配，删词时的剪切点因此是近似的。
+pub fn split_words(cues: &[TranscriptCue]) -> Vec<TranscriptWord> {
+    let mut words = Vec::new();
+    for cue in cues {
+        let tokens = cue
+            .text
+            .split_whitespace()
+            .flat_map(split_cjk)
+            .collect::<Vec<_>>();
+        let total_chars = tokens
+            .iter()
+            .map(|token| token.chars().count() as u64)
+            .sum::<u64>()
+            .max(1);
+        let span = cue.end_ms - cue.start_ms;
+        let mut offset = 0u64;
+        for token in tokens {
+            let start_ms = cue.start_ms + span * offset / total_chars;
+            offset += token.chars().count() as u64;
+            words.push(TranscriptWord {
+                index: words.len(),
+                start_ms,
+                end_ms: cue.start_ms + span * offset / total_chars,
+                text: token.to_string(),
+            });
+        }
+    }
+    words
+}
+
+fn split_cjk(token: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut run_start = 0;
+    for (index, ch) in token.char_indices() {
+        if is_cjk(ch) {
+            if run_start < index {
+                parts.push(&token[run_start..index]);
+            }
+            parts.push(&token[index..index + ch.len_utf8()]);
+            run_start = index + ch.len_utf8();
+        }
+    }
+    if run_start < token.len() {
+        parts.push(&token[run_start..]);
+    }
+    parts
+}
+
+pub fn is_cjk(ch: char) -> bool {
+    matches!(ch as u32, 0x3040..=0x30ff | 0x3400..=0x4dbf | 0x4e00..=0x9fff | 0xac00..=0xd7af | 0xf900..=0xfaff)
+}
+
+/// 选中的词换算为要剪掉的时间区间（源文件时间轴），重叠或相接的区间合并，按起点排序。
+pub fn word_cut_ranges(
+    words: &[TranscriptWord],
+    selections: &[TranscriptWordRange],
+) -> Result<Vec<TimeRange>, AppError> {
+    let mut ranges = Vec::with_capacity(selections.len());
+    for selection in selections {
+        let (Some(first), Some(last)) = (
+            words.get(selection.start_index),
+            words.get(selection.end_index),
+        ) else {
+            return Err(AppError::new(
+                "TRANSCRIPT_RANGE_INVALID",
+                format!(
+                    "word range {}..={} is outside the transcript ({} words)",
+                    selection.start_index,
+                    selection.end_index,
+                    words.len()
+                ),
+                Some("文字稿已变化，请刷新后重新选择".to_string()),
+            ));
+        };
+        if selection.end_index < selection.start_index {
+            return Err(AppError::new(
+                "TRANSCRIPT_RANGE_INVALID",
+                format!(
+                    "word range {}..={} is reversed",
+                    selection.start_index, selection.end_index
+                ),
+                Some("文字稿已变化，请刷新后重新选择".to_string()),
+            ));
+        }
+        ranges.push(TimeRange {
+            start_ms: first.start_ms,
+            end_ms: last.end_ms,
+        });
+    }
+    ranges.sort_by_key(|range| range.start_ms);
+    let mut merged: Vec<TimeRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start_ms <= last.end_ms => {
+                last.end_ms = last.end_ms.max(range.end_ms);
+            }
+            _ => merged.push(range),
+        }
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_transcript, split_words, word_cut_ranges};
+    use crate::domain::models::{TranscriptCue, TranscriptWordRange};
+
+    fn cue(start_ms: u64, end_ms: u64, text: &str) -> TranscriptCue {
+        TranscriptCue {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn srt_and_vtt_cues_are_parsed_without_markup() {
+        let srt = "\u{feff}1\r\n00:00:01,000 --> 00:00:03,500\r\n打开 <i>账单</i>\r\n页面\r\n\r\n2\r\n00:00:04,000 --> 00:00:04,000\r\n空段\r\n";
+        assert_eq!(parse_transcript(srt), [cue(1_000, 3_500, "打开 账单 页面")]);
+
+        let vtt = "WEBVTT\n\nNOTE 注释\n\nintro\n01:05.250 --> 01:07.000 align:start\n<c.yellow>Billing</c> page\n";
+        assert_eq!(parse_transcript(vtt), [cue(65_250, 67_000, "Billing page")]);
+    }
+
+    #[test]
+    fn ass_dialogue_uses_format_columns() {
+        let ass = "[Script Info]\nTitle: demo\n\n[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\nDialogue: 0,0:00:02.50,0:00:04.00,Default,,0,0,0,,{\\b1}Hello{\\b0}, world\\Nagain\nComment: 0,0:00:05.00,0:00:06.00,Default,,0,0,0,,skip\n";
+        assert_eq!(
+            parse_transcript(ass),
+            [cue(2_500, 4_000, "Hello, world again")]
+        );
+    }
+
+    #[test]
+    fn words_share_cue_time_by_length_and_map_to_cut_ranges() {
+        let cues = [
+            cue(1_000, 2_000, "open billing"),
+            cue(3_000, 3_300, "账单页"),
+        ];
+        let words = split_words(&cues);
+        let texts = words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["open", "billing", "账", "单", "页"]);
+        assert_eq!((words[0].start_ms, words[0].end_ms), (1_000, 1_363));
+        assert_eq!((words[1].start_ms, words[1].end_ms), (1_363, 2_000));
+        assert_eq!((words[3].start_ms, words[3].end_ms), (3_100, 3_200));
+
+        let ranges = word_cut_ranges(
+            &words,
+            &[
+                TranscriptWordRange {
+                    start_index: 3,
+                    end_index: 4,
+                },
+                TranscriptWordRange {
+                    start_index: 1,
+                    end_index: 2,
+                },
+                TranscriptWordRange {
+                    start_index: 0,
+                    end_index: 0,
+                },
+            ],
+        )
+        .unwrap();
+        let ranges = ranges
+            .iter()
+            .map(|range| (range.start_ms, range.end_ms))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(1_000, 3_300)]);
+        let out_of_range = TranscriptWordRange {
+            start_index: 4,
+            end_index: 5,
+        };
+        assert_eq!(
+            word_cut_ranges(&words, &[out_of_range]).unwrap_err().code,
+            "TRANSCRIPT_RANGE_INVALID"
+        );
+    }
+}
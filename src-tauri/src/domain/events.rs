@@ -0,0 +1,84 @@
+use crate::domain::models::{
+    AudioLevelEvent, DevicesChangedEvent, ExportProgressEvent, FfmpegInstallProgressEvent,
+    MotionPreviewProgressEvent, PreviewFrameEvent, ProjectArchiveProgressEvent,
+    RecordingStatusEvent, RecoveryEvent, UploadProgressEvent,
+};
+use serde::Serialize;
+
+/// 事件负载结构变化时递增，前端据此判断是否兼容。
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// 后端推送给前端的全部事件；事件名与负载一一对应，统一经 `emit_event` 发出。
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AppEvent {
+    RecordingStatus(RecordingStatusEvent),
+    AudioLevel(AudioLevelEvent),
+    PreviewFrame(PreviewFrameEvent),
+    ExportProgress(ExportProgressEvent),
+    MotionPreviewProgress(MotionPreviewProgressEvent),
+    ArchiveProgress(ProjectArchiveProgressEvent),
+    Recovery(RecoveryEvent),
+    FfmpegInstallProgress(FfmpegInstallProgressEvent),
+    UploadProgress(UploadProgressEvent),
+    DevicesChanged(DevicesChangedEvent),
+}
+
+impl AppEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RecordingStatus(_) => "recording/status",
+            Self::AudioLevel(_) => "recording/audio-level",
+            Self::PreviewFrame(_) => "recording/preview",
+            Self::ExportProgress(_) => "export/progress",
+            Self::MotionPreviewProgress(_) => "preview/progress",
+            Self::ArchiveProgress(_) => "project/archive-progress",
+            Self::Recovery(_) => "recovery/status",
+            Self::FfmpegInstallProgress(_) => "ffmpeg/install-progress",
+            Self::UploadProgress(_) => "upload/progress",
+            Self::DevicesChanged(_) => "devices/changed",
+        }
+    }
+}
+
+/// 实际发出的负载：在事件字段旁附带 `schemaVersion`。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventEnvelope {
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub event: AppEvent,
+}
+
+impl From<AppEvent> for EventEnvelope {
+    fn from(event: AppEvent) -> Self {
+        Self {
+            schema_version: EVENT_SCHEMA_VERSION,
+            event,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppEvent, EventEnvelope};
+    use crate::domain::models::RecoveryEvent;
+
+    #[test]
+    fn envelope_flattens_payload_next_to_schema_version() {
+        let event = AppEvent::Recovery(RecoveryEvent {
+            project_id: "p1".to_string(),
+            status: "recovered".to_string(),
+        });
+        assert_eq!(event.name(), "recovery/status");
+        let value = serde_json::to_value(EventEnvelope::from(event)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "schemaVersion": 1,
+                "projectId": "p1",
+                "status": "recovered"
+            })
+        );
+    }
+}
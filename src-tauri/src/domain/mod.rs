@@ -1,2 +1,3 @@
+pub mod events;
 pub mod models;
 pub mod state_machine;
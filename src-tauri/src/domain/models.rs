@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,6 +12,30 @@ pub struct RecordingProfile {
     pub microphone_device_id: Option<String>,
     pub system_audio_enabled: bool,
     pub hotkeys: Hotkeys,
+    /// 目标码率，设置后使用 CBR 风格码控，未设置时使用 CRF。
+    #[serde(default)]
+    pub recording_bitrate_mbps: Option<u8>,
+    #[serde(default = "default_recording_crf")]
+    pub crf: u8,
+    #[serde(default)]
+    pub preset: EncoderPreset,
+    /// 关闭后原始录制不绘制系统光标，导出时可改用光标轨迹合成光标，避免出现双光标。
+    #[serde(default = "default_true")]
+    pub capture_cursor: bool,
+    #[serde(default)]
+    pub keystroke_capture: KeystrokeCaptureMode,
+    /// 全屏模式下只录制指定显示器，为空时录制整个虚拟桌面。
+    #[serde(default)]
+    pub display_id: Option<String>,
+    /// 光标采样基础间隔，范围 8–500ms。
+    #[serde(default = "default_cursor_sample_interval_ms")]
+    pub cursor_sample_interval_ms: u16,
+    /// 快速移动时加密采样、静止时稀疏采样。
+    #[serde(default)]
+    pub adaptive_cursor_sampling: bool,
+    /// 录制期间开启系统勿扰，停止后恢复原设置，避免通知弹窗录进画面。
+    #[serde(default)]
+    pub do_not_disturb: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +53,114 @@ pub struct CameraMotionProfile {
     pub smoothing: f32,
     pub max_zoom: f32,
     pub idle_threshold_ms: u64,
+    /// 点击时镜头推近点击位置，保持 click_hold_ms 后缓出。
+    #[serde(default)]
+    pub zoom_on_click: bool,
+    #[serde(default = "default_click_hold_ms")]
+    pub click_hold_ms: u64,
+    #[serde(default)]
+    pub model: CameraMotionModel,
+    #[serde(default)]
+    pub easing: CameraEasing,
+    #[serde(default)]
+    pub focus_mode: CameraFocusMode,
+    #[serde(default)]
+    pub mode: CameraMode,
+}
+
+fn default_click_hold_ms() -> u64 {
+    800
+}
+
+/// 画面外观：录屏内容缩进到背景之上，可加圆角与投影。
+/// 圆角与留白以 1080p 短边为基准，导出时按实际分辨率等比换算。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneStyle {
+    pub enabled: bool,
+    pub background: SceneBackground,
+    pub corner_radius: u32,
+    pub padding: u32,
+    /// 投影不透明度，0 表示不加投影。
+    pub shadow: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SceneBackground {
+    Color { color: String },
+    Gradient { from: String, to: String },
+    Wallpaper { path: String },
+}
+
+/// 按键提示：导出时把按键轨迹渲染成画面底部（或顶部）居中的胶囊标签。
+/// 字号与边距以 1080p 短边为基准，导出时按实际分辨率等比换算。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystrokeOverlay {
+    pub enabled: bool,
+    pub position: OverlayPosition,
+    pub font_size: u32,
+    /// 胶囊底色不透明度。
+    pub opacity: f32,
+    /// 每次按键的显示时长，下一次按键会提前替换。
+    pub hold_ms: u64,
+}
+
+/// 导出混音：各音轨增益与总音量，1.0 为原始音量。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioMix {
+    pub master_volume: f32,
+    pub mic_volume: f32,
+    pub system_volume: f32,
+    pub music_volume: f32,
+    /// 背景音乐文件，导出时循环铺满整段成片。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music_path: Option<String>,
+}
+
+/// 导出调色：亮度、对比度、饱和度与 gamma，可选套用 .cube LUT；默认值不改变画面。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorGrade {
+    /// 亮度偏移，0 为原始亮度。
+    pub brightness: f32,
+    pub contrast: f32,
+    pub saturation: f32,
+    pub gamma: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lut_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlayPosition {
+    #[default]
+    Bottom,
+    Top,
+}
+
+/// 烧录进画面的时间码。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimecodeMode {
+    #[default]
+    Off,
+    /// 录制内的相对时间 `HH:MM:SS.mmm`，与原始录制时间轴一致。
+    Elapsed,
+    /// 录制时的本地时钟 `YYYY-MM-DD HH:MM:SS`。
+    WallClock,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayCorner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +172,66 @@ pub struct ExportProfile {
     pub fps: u8,
     pub video_codec: VideoCodec,
     pub audio_codec: AudioCodec,
+    #[serde(default)]
+    pub audio_denoise: AudioDenoise,
+    #[serde(default)]
+    pub video_denoise: VideoDenoise,
+    /// 录制帧率低于导出帧率时用运动补偿补帧（如 30→60fps），光标移动更顺滑，导出明显变慢。
+    #[serde(default)]
+    pub frame_interpolation: bool,
+    /// 在每次点击时混入一声短促的点击音，音量取 0..1。
+    #[serde(default)]
+    pub click_sound_enabled: bool,
+    #[serde(default = "default_click_sound_volume")]
+    pub click_sound_volume: f32,
+    /// 片头/片尾素材，导出时与正片拼接成一个 MP4。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intro_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outro_path: Option<String>,
+    /// 外挂字幕（SRT/VTT/ASS），时间轴与原始录制一致，导出时烧录进画面。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle_path: Option<String>,
+    /// 字号以 1080p 短边为基准；ASS 字幕保留自带样式，不受这两项影响。
+    #[serde(default = "default_subtitle_font_size")]
+    pub subtitle_font_size: u32,
+    #[serde(default)]
+    pub subtitle_position: OverlayPosition,
+    /// 多版本导出时覆盖时间线画幅，未设置时沿用 `timeline.aspect_ratio`。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aspect_ratio: Option<AspectRatio>,
+    /// 后台导出：降低 ffmpeg 进程优先级并限制编码线程数，导出期间仍可流畅使用电脑。
+    #[serde(default)]
+    pub low_priority: bool,
+    /// 时间码叠加，便于测试人员按时间点提交问题。
+    #[serde(default)]
+    pub timecode: TimecodeMode,
+    #[serde(default)]
+    pub timecode_corner: OverlayCorner,
+    /// 来自预设的成片体积上限，码率已按此下调；设置后必须重新编码，不能流复制。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u32>,
+}
+
+fn default_subtitle_font_size() -> u32 {
+    42
+}
+
+fn default_click_sound_volume() -> f32 {
+    0.6
+}
+
+/// 面向平台的导出预设：非技术用户只需选择发布渠道，码率等参数由预设决定。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPreset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub profile: ExportProfile,
+    /// 成片体积上限，导出时按时长下调码率。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +241,257 @@ pub struct TimelineConfig {
     pub trim_end_ms: u64,
     pub aspect_ratio: AspectRatio,
     pub cursor_highlight_enabled: bool,
+    #[serde(default)]
+    pub scroll_indicator_enabled: bool,
+    #[serde(default)]
+    pub fit_mode: FitMode,
+    /// 合成光标样式与尺寸（以 1080p 短边为基准），开启光标高亮时生效。
+    #[serde(default)]
+    pub cursor_style: CursorStyle,
+    #[serde(default = "default_cursor_size")]
+    pub cursor_size: u32,
+    /// 光标聚光灯：压暗光标周围半径以外的画面，独立于合成光标开关。
+    #[serde(default)]
+    pub spotlight: CursorSpotlight,
+    /// 自动剪除静音：`silent_ranges` 由 silencedetect 分析得出（源文件时间轴）。
+    #[serde(default)]
+    pub auto_cut_silence: bool,
+    #[serde(default)]
+    pub silence_cut_mode: SilenceCutMode,
+    #[serde(default)]
+    pub silent_ranges: Vec<TimeRange>,
+    /// 无光标移动与按键的长时间空闲段按 `idle_speed` 倍速快进。
+    #[serde(default)]
+    pub idle_speedup_enabled: bool,
+    #[serde(default = "default_idle_speed")]
+    pub idle_speed: f32,
+    #[serde(default = "default_idle_min_ms")]
+    pub idle_min_ms: u64,
+    /// 保留片段（源文件时间轴，按起点排序且互不重叠）；为空时保留整个裁剪区间。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<TimelineSegment>,
+    /// 隐私打码区域（源文件时间轴），导出时在镜头裁剪之前作用于原始画面。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blur_regions: Vec<BlurRegion>,
+    /// 静音区间（源文件时间轴，按起点排序且互不重叠），导出时该段音轨音量置零。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mute_ranges: Vec<TimeRange>,
+    /// 文字标注（源文件时间轴），导出时叠加在最终画面上。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<TextAnnotation>,
+    /// 箭头与形状标注（源文件时间轴），导出时叠加在最终画面上。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shapes: Vec<ShapeAnnotation>,
+    /// 章节标记（源文件时间轴，按时间排序）。自动生成的建议需确认后才算正式章节。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub markers: Vec<TimelineMarker>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineMarker {
+    pub id: String,
+    pub t_ms: u64,
+    pub title: String,
+    #[serde(default)]
+    pub source: MarkerSource,
+    /// 自动建议的标记为 false，确认后置为 true；重新生成建议时只替换未确认的标记。
+    #[serde(default = "default_marker_accepted")]
+    pub accepted: bool,
+}
+
+fn default_marker_accepted() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkerSource {
+    #[default]
+    Manual,
+    /// 前台应用切换。
+    AppSwitch,
+    /// 文字稿话题变化。
+    Transcript,
+}
+
+/// 相对源画面的归一化矩形（0..1），与录制分辨率无关。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BlurMode {
+    /// 方框模糊，保留大致色块。
+    #[default]
+    Blur,
+    /// delogo 用周边像素插值填充，适合去除整块文字。
+    Delogo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlurRegion {
+    pub id: String,
+    pub rect: NormalizedRect,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    #[serde(default)]
+    pub mode: BlurMode,
+}
+
+/// 画面上的文字标注，如“第 2 步：配置 Webhook”。
+/// `x`/`y` 是文字框在画面剩余空间中的相对位置：0 贴左（上）边，1 贴右（下）边，0.5 居中。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TextAnnotation {
+    pub id: String,
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    #[serde(default)]
+    pub style: AnnotationStyle,
+}
+
+/// 字号以 1080p 短边为基准，导出时按实际分辨率等比换算。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationStyle {
+    pub font_size: u32,
+    /// `#RRGGBB`。
+    pub color: String,
+    /// 底框颜色（`#RRGGBB`），为空时不画底框。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    pub background_opacity: f32,
+}
+
+impl Default for AnnotationStyle {
+    fn default() -> Self {
+        Self {
+            font_size: 48,
+            color: "#FFFFFF".to_string(),
+            background: Some("#000000".to_string()),
+            background_opacity: 0.6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShapeKind {
+    /// 由 `from` 指向 `to`，箭头画在 `to` 一端。
+    Arrow,
+    Rectangle,
+    Ellipse,
+}
+
+/// 相对最终画面的归一化坐标（0..1）。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// 形状标注：矩形与椭圆以 `from`/`to` 为外接框的两个对角。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShapeAnnotation {
+    pub id: String,
+    pub kind: ShapeKind,
+    pub from: NormalizedPoint,
+    pub to: NormalizedPoint,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    #[serde(default)]
+    pub style: ShapeStyle,
+}
+
+/// 线宽以 1080p 短边为基准，导出时按实际分辨率等比换算。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShapeStyle {
+    /// `#RRGGBB`。
+    pub stroke_color: String,
+    pub stroke_width: u32,
+    /// 填充颜色（`#RRGGBB`），为空时只画轮廓；箭头总是以描边颜色实心绘制。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fill: Option<String>,
+    pub fill_opacity: f32,
+}
+
+impl Default for ShapeStyle {
+    fn default() -> Self {
+        Self {
+            stroke_color: "#FF3B30".to_string(),
+            stroke_width: 6,
+            fill: None,
+            fill_opacity: 0.25,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SensitiveKind {
+    Email,
+    ApiKey,
+    CardNumber,
+}
+
+/// 敏感内容扫描提出的打码建议，用户确认后转为打码区域。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlurCandidate {
+    pub id: String,
+    pub kind: SensitiveKind,
+    pub rect: NormalizedRect,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// 脱敏后的识别文本（如 `jo***@example.com`），便于用户核对，原文不落盘。
+    pub preview: String,
+}
+
+fn default_idle_speed() -> f32 {
+    4.0
+}
+
+fn default_idle_min_ms() -> u64 {
+    3000
+}
+
+fn default_cursor_size() -> u32 {
+    36
+}
+
+/// 聚光灯参数：半径与羽化以 1080p 短边为基准的像素计，`dim` 为圈外压暗程度（0..1）。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorSpotlight {
+    pub enabled: bool,
+    pub radius: u32,
+    pub dim: f32,
+    pub feather: u32,
+}
+
+impl Default for CursorSpotlight {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            radius: 160,
+            dim: 0.55,
+            feather: 60,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,16 +500,105 @@ pub struct ProjectManifest {
     pub schema_version: u8,
     pub app_version: String,
     pub title: Option<String>,
+    /// 用户自定义标签，用于按产品、客户或课程分组。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 自由备注，如“第 3 条，2:10 处音频削波”。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// 录制开始的时刻，用于叠加录制时的时钟；早期项目没有记录。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recording_started_at: Option<DateTime<Utc>>,
     pub recording: RecordingProfile,
     pub camera_motion: CameraMotionProfile,
     pub export: ExportProfile,
     pub timeline: TimelineConfig,
+    #[serde(default)]
+    pub scene: SceneStyle,
+    #[serde(default)]
+    pub keystroke_overlay: KeystrokeOverlay,
+    #[serde(default)]
+    pub audio_mix: AudioMix,
+    #[serde(default)]
+    pub color_grade: ColorGrade,
     pub artifacts: ProjectArtifacts,
     pub quality: QualityMetrics,
     pub status: ProjectStatus,
     pub last_error: Option<AppError>,
+    /// 成片上传记录，每个上传目标保留最近一次。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uploads: Vec<UploadRecord>,
+    /// 待确认的打码建议，由 `detect_sensitive_content` 整体替换。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blur_candidates: Vec<BlurCandidate>,
+    /// 最近几次成功导出的资源消耗，新记录在后。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub export_history: Vec<ExportHistoryEntry>,
+}
+
+/// `load_project` 的返回值：清单之外附带从 app_track.json 读取的前台应用切换，
+/// 只用于展示，不写回清单。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedProject {
+    #[serde(flatten)]
+    pub manifest: ProjectManifest,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub app_track: Vec<AppFocusSegment>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum UploadTarget {
+    Youtube,
+    GoogleDrive,
+    /// 设置中配置的 S3 兼容对象存储（AWS S3、MinIO 等），不走 OAuth。
+    S3,
+}
+
+impl UploadTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UploadTarget::Youtube => "youtube",
+            UploadTarget::GoogleDrive => "googleDrive",
+            UploadTarget::S3 => "s3",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadStatus {
+    Uploading,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadRecord {
+    pub target: UploadTarget,
+    pub status: UploadStatus,
+    /// 上传的本地文件，重新导出后与之不同则不再续传。
+    pub file_path: String,
+    pub total_bytes: u64,
+    /// 断点续传会话地址，上传完成后清空。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// 预签名链接的失效时间，永久链接为空。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_expires_at: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<AppError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -75,6 +608,169 @@ pub struct ProjectArtifacts {
     pub cursor_track_path: Option<String>,
     pub last_export_path: Option<String>,
     pub export_log_path: Option<String>,
+    pub key_track_path: Option<String>,
+    pub app_track_path: Option<String>,
+    #[serde(default)]
+    pub refined_cursor_track_path: Option<String>,
+    /// 手工编辑后的最新轨迹修订，重新执行后处理时清空。
+    #[serde(default)]
+    pub edited_cursor_track_path: Option<String>,
+    #[serde(default)]
+    pub cursor_track_revision: u32,
+    /// 多版本导出的附加成片，主版本仍记录在 `last_export_path`。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variant_export_paths: Vec<String>,
+    /// 成片旁的 SRT/VTT 字幕，项目有文字稿时随导出写出。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub caption_paths: Vec<String>,
+    /// 音轨波形缓存，随原始录制变化自动重建。
+    #[serde(default)]
+    pub waveform_path: Option<String>,
+}
+
+impl ProjectArtifacts {
+    /// 导出与镜头评估使用的光标轨迹：手工编辑 > 后处理 > 原始轨迹。
+    pub fn effective_cursor_track_path(&self) -> Option<&str> {
+        self.edited_cursor_track_path
+            .as_deref()
+            .or(self.refined_cursor_track_path.as_deref())
+            .or(self.cursor_track_path.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorTrackPoint {
+    pub t_ms: u64,
+    pub x: f32,
+    pub y: f32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub scroll_dx: f32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub scroll_dy: f32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub offscreen: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub click: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeRange {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// 多片段时间线中的保留片段，`speed` 为该段的播放倍速（0.5–4）。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    #[serde(default = "default_segment_speed")]
+    pub speed: f32,
+}
+
+fn default_segment_speed() -> f32 {
+    1.0
+}
+
+/// 录制音轨的峰值包络，`peaks[i]` 覆盖 `[i, i+1) / samples_per_second` 秒。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioWaveform {
+    pub samples_per_second: u32,
+    pub peaks: Vec<f32>,
+}
+
+/// 可撤销编辑前的状态快照，撤销时与清单中的对应部分互换。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "state", rename_all = "snake_case")]
+pub enum EditSnapshot {
+    Timeline(TimelineConfig),
+    CameraMotion(CameraMotionProfile),
+}
+
+/// 编辑历史，保存在项目目录的 edit_history.json 中。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditHistory {
+    #[serde(default)]
+    pub undo: Vec<EditSnapshot>,
+    #[serde(default)]
+    pub redo: Vec<EditSnapshot>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditHistoryStatus {
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+/// 时间线胶片条中的一帧缩略图。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineThumbnail {
+    pub t_ms: u64,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum CursorTrackEdit {
+    DeleteSpan { start_ms: u64, end_ms: u64 },
+    Nudge { t_ms: u64, dx: f32, dy: f32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorTrackSnapshot {
+    pub revision: u32,
+    pub points: Vec<CursorTrackPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CursorRefineOptions {
+    /// 样本与前一样本、后一样本的距离都超过该值（像素），而前后样本彼此接近时视为离群点。
+    pub outlier_jump_px: f32,
+    /// 相邻样本间隔超过该值时做线性插值补点。
+    pub max_gap_ms: u64,
+    pub interpolation_step_ms: u64,
+    /// 滑动平均窗口大小，小于 2 时不平滑。
+    pub smoothing_window: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AppFocusSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub process_name: String,
+    pub window_title: String,
+    /// 前台窗口矩形；采集期间为屏幕坐标，停止录制后换算到录制帧坐标。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_rect: Option<WindowRect>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl WindowRect {
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +779,16 @@ pub struct QualityMetrics {
     pub av_offset_ms: i64,
     pub avg_drop_rate: f32,
     pub peak_drop_rate: f32,
+    /// 成片内容分析检出的黑屏、冻结与静音区间（成片时间轴）。
+    #[serde(default)]
+    pub black_ranges: Vec<TimeRange>,
+    #[serde(default)]
+    pub frozen_ranges: Vec<TimeRange>,
+    #[serde(default)]
+    pub silent_ranges: Vec<TimeRange>,
+    /// 参与内容分析的成片时长，用于计算冻结与静音占比。
+    #[serde(default)]
+    pub analyzed_duration_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -92,6 +798,18 @@ pub struct TimelinePatch {
     pub trim_end_ms: Option<u64>,
     pub aspect_ratio: Option<AspectRatio>,
     pub cursor_highlight_enabled: Option<bool>,
+    pub scroll_indicator_enabled: Option<bool>,
+    pub fit_mode: Option<FitMode>,
+    pub cursor_style: Option<CursorStyle>,
+    pub cursor_size: Option<u32>,
+    pub spotlight: Option<CursorSpotlight>,
+    pub auto_cut_silence: Option<bool>,
+    pub silence_cut_mode: Option<SilenceCutMode>,
+    pub idle_speedup_enabled: Option<bool>,
+    pub idle_speed: Option<f32>,
+    pub idle_min_ms: Option<u64>,
+    pub segments: Option<Vec<TimelineSegment>>,
+    pub markers: Option<Vec<TimelineMarker>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -102,6 +820,163 @@ pub struct CameraMotionPatch {
     pub smoothing: Option<f32>,
     pub max_zoom: Option<f32>,
     pub idle_threshold_ms: Option<u64>,
+    pub zoom_on_click: Option<bool>,
+    pub click_hold_ms: Option<u64>,
+    pub model: Option<CameraMotionModel>,
+    pub easing: Option<CameraEasing>,
+    pub focus_mode: Option<CameraFocusMode>,
+    pub mode: Option<CameraMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneStylePatch {
+    pub enabled: Option<bool>,
+    pub background: Option<SceneBackground>,
+    pub corner_radius: Option<u32>,
+    pub padding: Option<u32>,
+    pub shadow: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystrokeOverlayPatch {
+    pub enabled: Option<bool>,
+    pub position: Option<OverlayPosition>,
+    pub font_size: Option<u32>,
+    pub opacity: Option<f32>,
+    pub hold_ms: Option<u64>,
+}
+
+/// `music_path` 传空字符串表示移除背景音乐。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioMixPatch {
+    pub master_volume: Option<f32>,
+    pub mic_volume: Option<f32>,
+    pub system_volume: Option<f32>,
+    pub music_volume: Option<f32>,
+    pub music_path: Option<String>,
+}
+
+/// `lut_path` 传空字符串表示移除 LUT。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorGradePatch {
+    pub brightness: Option<f32>,
+    pub contrast: Option<f32>,
+    pub saturation: Option<f32>,
+    pub gamma: Option<f32>,
+    pub lut_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectListItem {
+    pub project_id: String,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub status: ProjectStatus,
+    pub duration_ms: u64,
+    pub has_export: bool,
+    pub export_path: Option<String>,
+    pub raw_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectSortKey {
+    #[default]
+    UpdatedDesc,
+    CreatedDesc,
+    TitleAsc,
+    DurationDesc,
+}
+
+/// 项目列表查询条件，全部为空时按最近修改返回全部项目。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectListQuery {
+    /// 页码从 1 开始。
+    pub page: Option<usize>,
+    /// 为空时不分页。
+    pub page_size: Option<usize>,
+    pub status: Option<ProjectStatus>,
+    /// 按标题、备注或项目 id 模糊匹配，不区分大小写。
+    pub search: Option<String>,
+    /// 只返回同时带有这些标签的项目（不区分大小写）。
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub sort: ProjectSortKey,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectListPage {
+    pub items: Vec<ProjectListItem>,
+    /// 过滤后（分页前）的项目数。
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+/// 文字稿中的一段，时间对应原始录制。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptCue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// 文字稿拆出的词，时间按所在字幕段内的字符位置估算。`index` 为全稿中的序号。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptWord {
+    pub index: usize,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// 选中的连续词，首尾序号都包含在内。
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptWordRange {
+    pub start_index: usize,
+    pub end_index: usize,
+}
+
+/// 全文搜索命中的项目；标题或备注命中时 `transcript_hits` 可能为空。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSearchResult {
+    pub item: ProjectListItem,
+    pub transcript_hits: Vec<TranscriptCue>,
+}
+
+/// 清单修订摘要，用于在列表中辨认要回滚到的版本。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestRevision {
+    pub revision_id: String,
+    pub saved_at: DateTime<Utc>,
+    pub title: Option<String>,
+    pub trim_start_ms: u64,
+    pub trim_end_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedProject {
+    pub project_id: String,
+    pub title: Option<String>,
+    pub deleted_at: DateTime<Utc>,
+    pub duration_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,6 +987,56 @@ pub struct RecoverableProject {
     pub path: String,
 }
 
+/// 采集区域在光标坐标系下的位置，`scale_factor` 为每个帧像素对应的光标坐标单位
+/// （Windows 高 DPI 下大于 1，macOS Retina 下小于 1）。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureBounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub scale_factor: f64,
+}
+
+impl CaptureBounds {
+    /// 帧尺寸未知时按缩放推算，例如未声明 DPI 感知的 gdigrab 采集到的是逻辑像素。
+    pub fn expected_frame_size(&self) -> (f64, f64) {
+        let scale = self.scale_factor.max(0.1);
+        (self.width / scale, self.height / scale)
+    }
+
+    /// 把物理像素光标坐标换算到录制帧坐标，保证导出取景与画面一致。
+    pub fn map_to_frame(&self, x: f64, y: f64, frame: Option<(u32, u32)>) -> (f64, f64) {
+        let (frame_w, frame_h) = frame
+            .map(|(width, height)| (width as f64, height as f64))
+            .unwrap_or_else(|| self.expected_frame_size());
+        let scale_x = frame_w / self.width.max(1.0);
+        let scale_y = frame_h / self.height.max(1.0);
+        ((x - self.x) * scale_x, (y - self.y) * scale_y)
+    }
+
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    pub fn clamp(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            x.clamp(self.x, self.x + (self.width - 1.0).max(0.0)),
+            y.clamp(self.y, self.y + (self.height - 1.0).max(0.0)),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    pub id: String,
+    pub label: String,
+    pub primary: bool,
+    pub bounds: CaptureBounds,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordingDevice {
@@ -138,13 +1063,186 @@ pub struct RecordingStatusEvent {
     pub degrade_message: Option<String>,
 }
 
+/// 录制期间音频输入设备增减；`devices` 为最新的完整列表。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevicesChangedEvent {
+    pub added: Vec<RecordingDevice>,
+    pub removed: Vec<RecordingDevice>,
+    pub devices: Vec<RecordingDevice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioLevelEvent {
+    pub session_id: String,
+    pub t_ms: u64,
+    pub rms_db: f32,
+    pub peak_db: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewFrameEvent {
+    pub session_id: String,
+    pub t_ms: u64,
+    /// `data:image/jpeg;base64,...`，前端可直接作为 img src。
+    pub data_url: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportProgressEvent {
     pub task_id: String,
     pub status: String,
+    /// 多版本导出时为全部版本的汇总进度。
     pub progress: u8,
     pub detail: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<ExportVariantProgress>,
+    /// 仅在成功的终态事件中携带，每个导出版本一项。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub usage: Vec<ExportUsage>,
+}
+
+/// 单个导出版本的资源消耗，用于比较硬件与软件编码的开销。
+/// `cpu_time_ms` 为 ffmpeg 各次调用（含回退重试与片头片尾拼接）的用户态与内核态时间之和。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportUsage {
+    pub label: String,
+    pub encoder: String,
+    pub cpu_time_ms: u64,
+    pub wall_time_ms: u64,
+    pub output_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportHistoryEntry {
+    pub task_id: String,
+    pub finished_at: DateTime<Utc>,
+    pub variants: Vec<ExportUsage>,
+}
+
+/// 多版本导出中当前版本的序号（从 0 开始）、总数与自身进度。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportVariantProgress {
+    pub index: u8,
+    pub count: u8,
+    pub progress: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MotionPreviewProgressEvent {
+    pub project_id: String,
+    pub status: String,
+    pub progress: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectArchiveProgressEvent {
+    pub project_id: String,
+    pub progress: u8,
+}
+
+/// 托管 ffmpeg 安装进度，`stage` 依次为 downloading / verifying / extracting / completed。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FfmpegInstallProgressEvent {
+    pub stage: String,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+    pub progress: u8,
+}
+
+/// `stop_recording` 的结果。开启录制后自动导出时附带导出任务 id；
+/// 自动导出未能发起不影响录制结果，原因放在 `export_error`。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopRecordingResult {
+    pub project_id: String,
+    pub export_task_id: Option<String>,
+    pub export_error: Option<AppError>,
+}
+
+/// 试录检查结果：`errors` 会让正式录制失败或缺少声画，`warnings` 只影响质量。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureTestReport {
+    pub ready: bool,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fps: Option<f32>,
+    pub has_audio: bool,
+    pub mean_volume_db: Option<f32>,
+    pub max_volume_db: Option<f32>,
+    pub errors: Vec<AppError>,
+    pub warnings: Vec<AppError>,
+}
+
+/// 当前生效的 ffmpeg 来源。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FfmpegSource {
+    Env,
+    Managed,
+    Sidecar,
+    System,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FfmpegInstallStatus {
+    pub source: FfmpegSource,
+    pub ffmpeg_path: String,
+    pub ffprobe_path: String,
+    pub managed_installed: bool,
+    /// 已配置固定版本的下载地址，可调用 `install_ffmpeg`。
+    pub download_available: bool,
+    pub pinned_version: Option<String>,
+}
+
+/// `get_ffmpeg_info` 的结果：版本、编译配置与可用组件清单，以及兼容性检查。
+/// `errors`/`warnings` 沿用错误码，前端按错误目录本地化展示。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FfmpegInfo {
+    pub path: String,
+    pub source: FfmpegSource,
+    pub available: bool,
+    pub version: Option<String>,
+    pub major_version: Option<u32>,
+    pub configuration: Vec<String>,
+    pub encoders: Vec<String>,
+    pub devices: Vec<String>,
+    pub filters: Vec<String>,
+    /// `errors` 为空，录制与导出所需组件齐全。
+    pub compatible: bool,
+    pub errors: Vec<AppError>,
+    pub warnings: Vec<AppError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProgressEvent {
+    pub project_id: String,
+    pub target: UploadTarget,
+    pub uploaded_bytes: u64,
+    pub total_bytes: u64,
+    pub progress: u8,
+}
+
+/// 中断录制被修复或恢复后通知前端刷新项目列表。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryEvent {
+    pub project_id: String,
+    /// `repaired` 或 `recovered`。
+    pub status: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -152,6 +1250,10 @@ pub struct AppError {
     pub code: String,
     pub message: String,
     pub suggestion: Option<String>,
+    /// 文案插值参数，前端按 `code` 查本地化文案后替换 `{key}` 占位符；
+    /// `message` 保留为日志与未收录错误码时的兜底文案。
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
 }
 
 impl AppError {
@@ -164,10 +1266,148 @@ impl AppError {
             code: code.into(),
             message: message.into(),
             suggestion,
+            params: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_param(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.params.insert(key.into(), value.to_string());
+        self
+    }
+}
+
+/// 应用设置（settings.json）。缺失字段取 `AppSettings::default()`，
+/// 结构不兼容的变更递增 `schema_version` 并在读取时迁移。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    pub schema_version: u32,
+    pub hotkeys: HotkeySettings,
+    pub export_presets: Vec<ExportPreset>,
+    pub locale: Locale,
+    /// 新建录制与快捷键首次开录使用的配置。
+    pub default_recording_profile: RecordingProfile,
+    pub default_export_preset_id: Option<String>,
+    /// 自定义项目根目录，为空时使用应用数据目录下的 projects/；修改后重启生效。
+    pub project_root: Option<String>,
+    pub telemetry_opt_in: bool,
+    pub cleanup: CleanupPolicy,
+    /// 停止录制后立即按默认导出预设排队导出，未设置默认预设时使用第一个预设。
+    pub auto_export_after_recording: bool,
+    /// 自建对象存储上传目标，未配置时不可上传到 S3。
+    pub s3: Option<S3Settings>,
+    pub share: SharePolicy,
+    /// 同时运行的导出任务数（1~4），笔记本可设为 1，工作站可调大并行导出。
+    pub max_concurrent_exports: usize,
+    /// 单个导出的编码线程上限，0 表示由 ffmpeg 按核心数决定。
+    pub encoder_threads: u16,
+}
+
+pub const DEFAULT_SHARE_LINK_EXPIRY_HOURS: u32 = 24;
+
+/// 局域网分享链接。默认关闭，开启后首次创建链接时启动本地服务。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SharePolicy {
+    pub enabled: bool,
+    /// 监听端口，0 表示由系统分配。
+    pub port: u16,
+    pub link_expiry_hours: u32,
+}
+
+impl Default for SharePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 0,
+            link_expiry_hours: DEFAULT_SHARE_LINK_EXPIRY_HOURS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareLink {
+    pub token: String,
+    pub project_id: String,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub const DEFAULT_S3_LINK_EXPIRY_HOURS: u32 = 24;
+/// SigV4 预签名链接的最长有效期为 7 天。
+pub const MAX_S3_LINK_EXPIRY_HOURS: u32 = 7 * 24;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct S3Settings {
+    /// 服务地址，如 `https://s3.us-east-1.amazonaws.com` 或 `http://nas.local:9000`。
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    /// 保存在 upload_tokens.json，settings.json 与返回给前端的设置中为空。
+    pub secret_access_key: String,
+    /// 路径风格（`endpoint/bucket/key`），MinIO 等自建服务通常需要开启。
+    pub path_style: bool,
+    /// 对象键前缀，如 `recordings/`。
+    pub key_prefix: String,
+    pub link_expiry_hours: u32,
+}
+
+impl Default for S3Settings {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            region: "us-east-1".to_string(),
+            bucket: String::new(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            path_style: true,
+            key_prefix: String::new(),
+            link_expiry_hours: DEFAULT_S3_LINK_EXPIRY_HOURS,
         }
     }
 }
 
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CleanupPolicy {
+    /// 后台定时清理回收站中超过保留期的项目。
+    pub auto_purge_trash: bool,
+    pub trash_retention_days: u32,
+}
+
+impl Default for CleanupPolicy {
+    fn default() -> Self {
+        Self {
+            auto_purge_trash: true,
+            trash_retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+        }
+    }
+}
+
+impl CleanupPolicy {
+    /// 自动清理关闭时返回 None。
+    pub fn trash_retention(&self) -> Option<chrono::Duration> {
+        self.auto_purge_trash
+            .then(|| chrono::Duration::days(i64::from(self.trash_retention_days)))
+    }
+}
+
+/// 界面语言，决定前端按错误码选用的文案目录。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CaptureMode {
@@ -184,6 +1424,40 @@ pub enum Resolution {
     R720p,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EncoderPreset {
+    #[default]
+    Ultrafast,
+    Superfast,
+    Veryfast,
+    Faster,
+    Fast,
+    Medium,
+}
+
+impl EncoderPreset {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EncoderPreset::Ultrafast => "ultrafast",
+            EncoderPreset::Superfast => "superfast",
+            EncoderPreset::Veryfast => "veryfast",
+            EncoderPreset::Faster => "faster",
+            EncoderPreset::Fast => "fast",
+            EncoderPreset::Medium => "medium",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeystrokeCaptureMode {
+    #[default]
+    Off,
+    Full,
+    Masked,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CameraIntensity {
@@ -192,6 +1466,78 @@ pub enum CameraIntensity {
     High,
 }
 
+/// 镜头跟随模型：hybrid 为指数跟随+死区，spring 为临界阻尼弹簧。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CameraMotionModel {
+    #[default]
+    Hybrid,
+    Spring,
+}
+
+/// 镜头取景焦点：cursor 跟随光标，window 框住当前前台窗口（未知时回退光标）。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CameraFocusMode {
+    #[default]
+    Cursor,
+    Window,
+}
+
+/// 镜头运动方式：zoomAndPan 推近并跟随，panOnly 保持 1.0x 只平移取景，
+/// zoomOnly 推近但取景固定在画面中心。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CameraMode {
+    #[default]
+    ZoomAndPan,
+    PanOnly,
+    ZoomOnly,
+}
+
+/// 镜头单步跟随比例的缓动曲线；cubicBezier 与 CSS 同义，x1/x2 需落在 [0,1]。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum CameraEasing {
+    #[default]
+    Linear,
+    EaseInOut,
+    CubicBezier {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+    },
+}
+
+/// 画幅转换方式：crop 裁掉多余画面，pad 加黑边，blur_background 用模糊放大的画面填充两侧。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FitMode {
+    #[default]
+    Crop,
+    Pad,
+    BlurBackground,
+}
+
+/// 静音区间的处理方式：drop 直接删除，speed_up 快进播放。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SilenceCutMode {
+    #[default]
+    Drop,
+    SpeedUp,
+}
+
+/// 合成光标：halo 在录制到的系统光标下叠加光晕，arrow 绘制箭头（适合未录入系统光标的素材）。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CursorStyle {
+    Arrow,
+    #[default]
+    Halo,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AspectRatio {
     #[serde(rename = "16:9")]
@@ -208,6 +1554,46 @@ pub enum ExportFormat {
     Mp4,
 }
 
+/// 短循环动图格式，体积远小于 GIF，适合把界面操作嵌入文档。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationFormat {
+    Webp,
+    Apng,
+}
+
+impl AnimationFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Webp => "webp",
+            Self::Apng => "apng",
+        }
+    }
+}
+
+/// 动图导出参数；区间为源文件时间轴，`loop_count` 为 0 时无限循环。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationExportOptions {
+    pub format: AnimationFormat,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    #[serde(default)]
+    pub loop_count: u32,
+    #[serde(default = "default_animation_width")]
+    pub width: u32,
+    #[serde(default = "default_animation_fps")]
+    pub fps: u8,
+}
+
+fn default_animation_width() -> u32 {
+    800
+}
+
+fn default_animation_fps() -> u8 {
+    15
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum VideoCodec {
@@ -220,7 +1606,29 @@ pub enum AudioCodec {
     Aac,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 导出音频降噪：light 用 afftdn 轻度压制底噪，strong 优先 arnndn（需模型文件）。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioDenoise {
+    #[default]
+    Off,
+    Light,
+    Strong,
+}
+
+/// 导出画面降噪：light、medium 用 hqdn3d 时空降噪，strong 用更慢但保留细节的 nlmeans，
+/// 适合对着屏幕翻拍等噪点明显的素材。
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoDenoise {
+    #[default]
+    Off,
+    Light,
+    Medium,
+    Strong,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ProjectStatus {
     Recording,
@@ -243,6 +1651,42 @@ impl Default for RecordingProfile {
                 start_stop: "Ctrl+Shift+R".to_string(),
                 pause_resume: "Ctrl+Shift+P".to_string(),
             },
+            recording_bitrate_mbps: None,
+            crf: default_recording_crf(),
+            preset: EncoderPreset::default(),
+            capture_cursor: true,
+            keystroke_capture: KeystrokeCaptureMode::default(),
+            display_id: None,
+            cursor_sample_interval_ms: default_cursor_sample_interval_ms(),
+            adaptive_cursor_sampling: false,
+            do_not_disturb: false,
+        }
+    }
+}
+
+fn default_recording_crf() -> u8 {
+    23
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_cursor_sample_interval_ms() -> u16 {
+    120
+}
+
+fn is_zero(value: &f32) -> bool {
+    *value == 0.0
+}
+
+impl Default for CursorRefineOptions {
+    fn default() -> Self {
+        Self {
+            outlier_jump_px: 240.0,
+            max_gap_ms: 250,
+            interpolation_step_ms: 120,
+            smoothing_window: 5,
         }
     }
 }
@@ -255,6 +1699,36 @@ impl Default for CameraMotionProfile {
             smoothing: 0.68,
             max_zoom: 1.35,
             idle_threshold_ms: 500,
+            zoom_on_click: false,
+            click_hold_ms: default_click_hold_ms(),
+            model: CameraMotionModel::Hybrid,
+            easing: CameraEasing::Linear,
+            focus_mode: CameraFocusMode::Cursor,
+            mode: CameraMode::ZoomAndPan,
+        }
+    }
+}
+
+impl Default for AudioMix {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            mic_volume: 1.0,
+            system_volume: 1.0,
+            music_volume: 0.3,
+            music_path: None,
+        }
+    }
+}
+
+impl Default for ColorGrade {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            gamma: 1.0,
+            lut_path: None,
         }
     }
 }
@@ -268,6 +1742,21 @@ impl Default for ExportProfile {
             fps: 30,
             video_codec: VideoCodec::H264,
             audio_codec: AudioCodec::Aac,
+            audio_denoise: AudioDenoise::Off,
+            video_denoise: VideoDenoise::Off,
+            frame_interpolation: false,
+            click_sound_enabled: false,
+            click_sound_volume: default_click_sound_volume(),
+            intro_path: None,
+            outro_path: None,
+            subtitle_path: None,
+            subtitle_font_size: default_subtitle_font_size(),
+            subtitle_position: OverlayPosition::Bottom,
+            aspect_ratio: None,
+            low_priority: false,
+            timecode: TimecodeMode::Off,
+            timecode_corner: OverlayCorner::TopLeft,
+            max_size_mb: None,
         }
     }
 }
@@ -279,6 +1768,23 @@ impl Default for TimelineConfig {
             trim_end_ms: 0,
             aspect_ratio: AspectRatio::Widescreen,
             cursor_highlight_enabled: true,
+            scroll_indicator_enabled: false,
+            fit_mode: FitMode::Crop,
+            cursor_style: CursorStyle::Halo,
+            cursor_size: default_cursor_size(),
+            spotlight: CursorSpotlight::default(),
+            auto_cut_silence: false,
+            silence_cut_mode: SilenceCutMode::Drop,
+            silent_ranges: Vec::new(),
+            idle_speedup_enabled: false,
+            idle_speed: default_idle_speed(),
+            idle_min_ms: default_idle_min_ms(),
+            segments: Vec::new(),
+            blur_regions: Vec::new(),
+            mute_ranges: Vec::new(),
+            annotations: Vec::new(),
+            shapes: Vec::new(),
+            markers: Vec::new(),
         }
     }
 }
@@ -290,16 +1796,53 @@ impl Default for ProjectManifest {
             schema_version: 1,
             app_version: "0.1.0".to_string(),
             title: None,
+            tags: Vec::new(),
+            notes: None,
             created_at: now,
             updated_at: now,
+            recording_started_at: None,
             recording: RecordingProfile::default(),
             camera_motion: CameraMotionProfile::default(),
             export: ExportProfile::default(),
             timeline: TimelineConfig::default(),
+            scene: SceneStyle::default(),
+            keystroke_overlay: KeystrokeOverlay::default(),
+            audio_mix: AudioMix::default(),
+            color_grade: ColorGrade::default(),
             artifacts: ProjectArtifacts::default(),
             quality: QualityMetrics::default(),
             status: ProjectStatus::Recording,
             last_error: None,
+            uploads: Vec::new(),
+            blur_candidates: Vec::new(),
+            export_history: Vec::new(),
+        }
+    }
+}
+
+impl Default for SceneStyle {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            background: SceneBackground::Gradient {
+                from: "#4f46e5".to_string(),
+                to: "#db2777".to_string(),
+            },
+            corner_radius: 16,
+            padding: 64,
+            shadow: 0.45,
+        }
+    }
+}
+
+impl Default for KeystrokeOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            position: OverlayPosition::Bottom,
+            font_size: 40,
+            opacity: 0.7,
+            hold_ms: 1200,
         }
     }
 }
@@ -310,6 +1853,10 @@ impl Default for QualityMetrics {
             av_offset_ms: 0,
             avg_drop_rate: 0.0,
             peak_drop_rate: 0.0,
+            black_ranges: Vec::new(),
+            frozen_ranges: Vec::new(),
+            silent_ranges: Vec::new(),
+            analyzed_duration_ms: 0,
         }
     }
 }
@@ -322,3 +1869,66 @@ impl Default for HotkeySettings {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+    use std::collections::BTreeSet;
+    use std::path::Path;
+
+    /// 形似错误码、实际是系统常量或环境变量名的字符串；`FOCUSLENS_` 前缀的环境变量另行排除。
+    const NON_ERROR_CONSTANTS: [&str; 3] = [
+        "CARGO_MANIFEST_DIR",
+        "NOC_GLOBAL_SETTING_TOASTS_ENABLED",
+        "REG_DWORD",
+    ];
+
+    fn collect_backend_codes(dir: &Path, pattern: &Regex, codes: &mut BTreeSet<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect_backend_codes(&path, pattern, codes);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                let source = std::fs::read_to_string(&path).unwrap();
+                codes.extend(
+                    pattern
+                        .captures_iter(&source)
+                        .map(|captures| captures[1].to_string())
+                        .filter(|code| {
+                            !code.starts_with("FOCUSLENS_")
+                                && !NON_ERROR_CONSTANTS.contains(&code.as_str())
+                        }),
+                );
+            }
+        }
+    }
+
+    /// 错误码经由辅助函数传递时不一定紧跟 `AppError::new`，这里按字面量收集全部错误码，
+    /// 要求前端中英文案与后端一一对应，新增或删除错误码时同步维护 errorCatalog.ts。
+    #[test]
+    fn error_codes_match_frontend_catalog() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let code_pattern = Regex::new(r#""([A-Z][A-Z0-9]*(?:_[A-Z0-9]+)+)""#).unwrap();
+        let mut backend_codes = BTreeSet::new();
+        collect_backend_codes(&root.join("src"), &code_pattern, &mut backend_codes);
+
+        let catalog = std::fs::read_to_string(root.join("../src/utils/errorCatalog.ts")).unwrap();
+        let zh_start = catalog.find("const zhCN").unwrap();
+        let en_start = catalog.find("const enUS").unwrap();
+        let end = catalog.find("const catalogs").unwrap();
+        let key_pattern = Regex::new(r"(?m)^  ([A-Z][A-Z0-9_]+): \{").unwrap();
+        for (locale, section) in [
+            ("zhCN", &catalog[zh_start..en_start]),
+            ("enUS", &catalog[en_start..end]),
+        ] {
+            let keys = key_pattern
+                .captures_iter(section)
+                .map(|captures| captures[1].to_string())
+                .collect::<BTreeSet<_>>();
+            let missing = backend_codes.difference(&keys).collect::<Vec<_>>();
+            assert!(missing.is_empty(), "{locale} 缺少错误码文案：{missing:?}");
+            let stale = keys.difference(&backend_codes).collect::<Vec<_>>();
+            assert!(stale.is_empty(), "{locale} 含后端未使用的错误码：{stale:?}");
+        }
+    }
+}
@@ -19,6 +19,7 @@ pub enum ExportState {
     Fallback,
     Success,
     Failed,
+    Cancelled,
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,84 @@
+use crate::domain::models::AppError;
+use std::path::Path;
+use std::process::Command;
+
+/// 在系统文件管理器中定位文件：Windows 用 Explorer 选中，macOS 用 Finder 显示，
+/// 其他平台用 xdg-open 打开所在目录。
+pub fn reveal_in_file_manager(path: &Path) -> Result<(), AppError> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("explorer");
+        command.arg(format!("/select,{}", path.display()));
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg("-R").arg(path);
+        command
+    };
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(path.parent().unwrap_or(path));
+        command
+    };
+    spawn_detached(&mut command)
+}
+
+/// 用系统默认程序打开文件。
+pub fn open_with_default_app(path: &Path) -> Result<(), AppError> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("explorer");
+        command.arg(path);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg(path);
+        command
+    };
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(path);
+        command
+    };
+    spawn_detached(&mut command)
+}
+
+/// 用系统默认浏览器打开网址。
+pub fn open_url(url: &str) -> Result<(), AppError> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler").arg(url);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    };
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    };
+    spawn_detached(&mut command)
+}
+
+// Explorer 成功时也可能返回非零退出码，只以能否启动进程判断失败。
+fn spawn_detached(command: &mut Command) -> Result<(), AppError> {
+    command.spawn().map(|_| ()).map_err(|error| {
+        AppError::new(
+            "SHELL_OPEN_FAILED",
+            format!("failed to launch system file manager: {error}"),
+            Some("请手动打开导出目录".to_string()),
+        )
+    })
+}
@@ -0,0 +1,181 @@
+use crate::domain::models::AppError;
+use crate::infra::ffmpeg::command::output_with_timeout;
+use std::process::Command;
+use std::time::Duration;
+
+// 读写系统设置都是本地命令，卡住多半是系统服务无响应，不能拖住开始录制。
+const SETTING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Windows 没有公开的专注助手接口，改为关闭通知中心的横幅（toast）开关。
+#[cfg(target_os = "windows")]
+mod platform {
+    pub const QUIET_VALUE: &str = "0";
+    pub const NORMAL_VALUE: &str = "1";
+    const KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Notifications\Settings";
+    const VALUE: &str = "NOC_GLOBAL_SETTING_TOASTS_ENABLED";
+
+    pub fn read_command() -> (&'static str, Vec<String>) {
+        (
+            "reg",
+            vec!["query".into(), KEY.into(), "/v".into(), VALUE.into()],
+        )
+    }
+
+    pub fn write_commands(value: &str) -> Vec<(&'static str, Vec<String>)> {
+        vec![(
+            "reg",
+            [
+                "add",
+                KEY,
+                "/v",
+                VALUE,
+                "/t",
+                "REG_DWORD",
+                "/d",
+                value,
+                "/f",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+        )]
+    }
+}
+
+/// macOS 写入通知中心的勿扰开关并重启 NotificationCenter 使其生效；
+/// macOS 12 起由“专注模式”接管，该开关可能不再起作用。
+#[cfg(target_os = "macos")]
+mod platform {
+    pub const QUIET_VALUE: &str = "1";
+    pub const NORMAL_VALUE: &str = "0";
+    const DOMAIN: &str = "com.apple.notificationcenterui";
+
+    pub fn read_command() -> (&'static str, Vec<String>) {
+        (
+            "defaults",
+            ["-currentHost", "read", DOMAIN, "doNotDisturb"]
+                .map(str::to_string)
+                .to_vec(),
+        )
+    }
+
+    pub fn write_commands(value: &str) -> Vec<(&'static str, Vec<String>)> {
+        vec![
+            (
+                "defaults",
+                [
+                    "-currentHost",
+                    "write",
+                    DOMAIN,
+                    "doNotDisturb",
+                    "-boolean",
+                    value,
+                ]
+                .map(str::to_string)
+                .to_vec(),
+            ),
+            ("killall", vec!["NotificationCenter".into()]),
+        ]
+    }
+}
+
+/// 其他平台按 GNOME 的通知横幅开关处理，没有 gsettings 的桌面环境会开启失败。
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    pub const QUIET_VALUE: &str = "false";
+    pub const NORMAL_VALUE: &str = "true";
+    const SCHEMA: &str = "org.gnome.desktop.notifications";
+
+    pub fn read_command() -> (&'static str, Vec<String>) {
+        (
+            "gsettings",
+            ["get", SCHEMA, "show-banners"].map(str::to_string).to_vec(),
+        )
+    }
+
+    pub fn write_commands(value: &str) -> Vec<(&'static str, Vec<String>)> {
+        vec![(
+            "gsettings",
+            ["set", SCHEMA, "show-banners", value]
+                .map(str::to_string)
+                .to_vec(),
+        )]
+    }
+}
+
+/// 开启勿扰，返回开启前的设置值供停止录制时恢复；原本已处于勿扰时返回 None，停止后也不改动。
+pub fn enable_do_not_disturb() -> Result<Option<String>, AppError> {
+    let (program, args) = platform::read_command();
+    // 从未改过通知设置时系统里没有该值，按默认的“允许通知”处理。
+    let previous = run_setting_command(program, &args)
+        .ok()
+        .and_then(|output| parse_setting_value(&output))
+        .unwrap_or_else(|| platform::NORMAL_VALUE.to_string());
+    if previous == platform::QUIET_VALUE {
+        return Ok(None);
+    }
+    write_setting(platform::QUIET_VALUE)?;
+    Ok(Some(previous))
+}
+
+pub fn restore_do_not_disturb(previous: &str) -> Result<(), AppError> {
+    write_setting(previous)
+}
+
+fn write_setting(value: &str) -> Result<(), AppError> {
+    for (program, args) in platform::write_commands(value) {
+        run_setting_command(program, &args)?;
+    }
+    Ok(())
+}
+
+fn run_setting_command(program: &str, args: &[String]) -> Result<String, AppError> {
+    let output = output_with_timeout(Command::new(program).args(args), SETTING_TIMEOUT)
+        .map_err(|error| dnd_error(format!("failed to run {program}: {error}")))?;
+    if !output.status.success() {
+        return Err(dnd_error(format!(
+            "{program} exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn dnd_error(message: String) -> AppError {
+    AppError::new(
+        "DO_NOT_DISTURB_FAILED",
+        message,
+        Some("请手动开启系统的勿扰/专注模式".to_string()),
+    )
+}
+
+/// 取输出最后一个非空行的最后一个字段：`reg query` 的 `0x1` 换算为十进制，
+/// `defaults read` 与 `gsettings get` 的输出原样返回。
+fn parse_setting_value(output: &str) -> Option<String> {
+    let token = output
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())?
+        .split_whitespace()
+        .last()?;
+    match token.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16)
+            .ok()
+            .map(|value| value.to_string()),
+        None => Some(token.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_setting_value;
+
+    #[test]
+    fn setting_values_are_parsed_from_platform_output() {
+        let reg = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Notifications\\Settings\r\n    NOC_GLOBAL_SETTING_TOASTS_ENABLED    REG_DWORD    0x1\r\n\r\n";
+        assert_eq!(parse_setting_value(reg).as_deref(), Some("1"));
+        assert_eq!(parse_setting_value("0\n").as_deref(), Some("0"));
+        assert_eq!(parse_setting_value("true\n").as_deref(), Some("true"));
+        assert_eq!(parse_setting_value("\n"), None);
+    }
+}
@@ -0,0 +1,9 @@
+use crate::domain::events::{AppEvent, EventEnvelope};
+use crate::domain::models::AppError;
+use tauri::{AppHandle, Emitter};
+
+/// 所有后端事件的唯一出口，保证事件名与负载结构一致。
+pub fn emit_event(app: &AppHandle, event: AppEvent) -> Result<(), AppError> {
+    app.emit(event.name(), EventEnvelope::from(event))
+        .map_err(|error| AppError::new("EVENT_ERROR", error.to_string(), None))
+}
@@ -0,0 +1,133 @@
+use crate::domain::models::{AppError, TimeRange};
+use crate::infra::ffmpeg::command::run_ffmpeg;
+use crate::infra::ffmpeg::silence::{SILENCE_MIN_DURATION_MS, SILENCE_NOISE_DB};
+use std::path::Path;
+
+// 持续 0.5 秒以上、亮度低于 10% 的画面视为黑屏。
+const BLACK_MIN_DURATION_SEC: f64 = 0.5;
+const BLACK_PIXEL_THRESHOLD: f64 = 0.10;
+// 录屏本身常有静止画面，只记录 2 秒以上几乎无像素变化的区间。
+const FREEZE_NOISE_DB: f64 = -60.0;
+const FREEZE_MIN_DURATION_SEC: f64 = 2.0;
+
+/// 成片内容分析结果，区间均为成片时间轴。
+#[derive(Debug, Clone, Default)]
+pub struct ContentAnalysis {
+    pub black_ranges: Vec<TimeRange>,
+    pub frozen_ranges: Vec<TimeRange>,
+    pub silent_ranges: Vec<TimeRange>,
+}
+
+/// 单次解码成片，同时跑 blackdetect、freezedetect 与 silencedetect。
+pub fn analyze_content(
+    input_path: &Path,
+    duration_ms: u64,
+    has_audio: bool,
+) -> Result<ContentAnalysis, AppError> {
+    let mut args = vec![
+        "-hide_banner".to_string(),
+        "-nostats".to_string(),
+        "-i".to_string(),
+        input_path.to_string_lossy().to_string(),
+        "-vf".to_string(),
+        format!(
+            "blackdetect=d={BLACK_MIN_DURATION_SEC:.2}:pix_th={BLACK_PIXEL_THRESHOLD:.2},freezedetect=n={FREEZE_NOISE_DB:.0}dB:d={FREEZE_MIN_DURATION_SEC:.2}"
+        ),
+    ];
+    if has_audio {
+        args.push("-af".to_string());
+        args.push(format!(
+            "silencedetect=noise={SILENCE_NOISE_DB:.1}dB:d={:.3}",
+            SILENCE_MIN_DURATION_MS as f64 / 1000.0
+        ));
+    } else {
+        args.push("-an".to_string());
+    }
+    args.extend(["-f".to_string(), "null".to_string(), "-".to_string()]);
+    let output = run_ffmpeg(args)?;
+    if !output.status.success() {
+        return Err(AppError::new(
+            "CONTENT_ANALYSIS_FAILED",
+            output.stderr.lines().last().unwrap_or_default().to_string(),
+            Some("请确认导出文件完整可播放".to_string()),
+        ));
+    }
+    Ok(parse_content_analysis(&output.stderr, duration_ms))
+}
+
+fn parse_content_analysis(stderr: &str, duration_ms: u64) -> ContentAnalysis {
+    ContentAnalysis {
+        black_ranges: parse_detect_ranges(stderr, "black_start:", "black_end:", duration_ms),
+        frozen_ranges: parse_detect_ranges(stderr, "freeze_start:", "freeze_end:", duration_ms),
+        silent_ranges: parse_detect_ranges(stderr, "silence_start:", "silence_end:", duration_ms),
+    }
+}
+
+/// 解析 *detect 滤镜日志中的起止时间；起止可能同行（blackdetect）也可能分行，
+/// 文件末尾未闭合的区间以 `duration_ms` 收尾。
+pub fn parse_detect_ranges(
+    stderr: &str,
+    start_key: &str,
+    end_key: &str,
+    duration_ms: u64,
+) -> Vec<TimeRange> {
+    let value_after = |line: &str, key: &str| {
+        let rest = &line[line.find(key)? + key.len()..];
+        rest.split_whitespace().next()?.parse::<f64>().ok()
+    };
+    let to_ms = |sec: f64| (sec.max(0.0) * 1000.0).round() as u64;
+    let mut ranges = Vec::new();
+    let mut open: Option<u64> = None;
+    for line in stderr.lines() {
+        if let Some(start) = value_after(line, start_key) {
+            open = Some(to_ms(start));
+        }
+        if let Some(end) = value_after(line, end_key) {
+            if let Some(start_ms) = open.take() {
+                ranges.push(TimeRange {
+                    start_ms,
+                    end_ms: to_ms(end),
+                });
+            }
+        }
+    }
+    if let Some(start_ms) = open {
+        if duration_ms > start_ms {
+            ranges.push(TimeRange {
+                start_ms,
+                end_ms: duration_ms,
+            });
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_content_analysis;
+
+    #[test]
+    fn parses_black_frozen_and_silent_ranges_from_one_pass() {
+        let stderr = "\
+[blackdetect @ 0x1] black_start:0 black_end:1.5 black_duration:1.5
+[freezedetect @ 0x2] lavfi.freezedetect.freeze_start: 4.2
+[freezedetect @ 0x2] lavfi.freezedetect.freeze_duration: 3.1
+[freezedetect @ 0x2] lavfi.freezedetect.freeze_end: 7.3
+[silencedetect @ 0x3] silence_start: 2
+[silencedetect @ 0x3] silence_end: 3.5 | silence_duration: 1.5
+[freezedetect @ 0x2] lavfi.freezedetect.freeze_start: 9";
+        let analysis = parse_content_analysis(stderr, 12_000);
+        let spans = |ranges: &[crate::domain::models::TimeRange]| {
+            ranges
+                .iter()
+                .map(|range| (range.start_ms, range.end_ms))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(spans(&analysis.black_ranges), vec![(0, 1500)]);
+        assert_eq!(
+            spans(&analysis.frozen_ranges),
+            vec![(4200, 7300), (9000, 12_000)]
+        );
+        assert_eq!(spans(&analysis.silent_ranges), vec![(2000, 3500)]);
+    }
+}
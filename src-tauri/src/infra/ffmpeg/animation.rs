@@ -0,0 +1,126 @@
+use crate::domain::models::{AnimationExportOptions, AnimationFormat, AppError};
+
+// 动图面向文档里的短操作演示，过长时体积失控，应改用视频。
+const MAX_ANIMATION_DURATION_MS: u64 = 15_000;
+// WebP 与 APNG 的循环次数都以 16 位整数存储。
+const MAX_ANIMATION_LOOPS: u32 = u16::MAX as u32;
+const ANIMATION_WIDTH_RANGE: (u32, u32) = (160, 1920);
+const ANIMATION_FPS_RANGE: (u8, u8) = (5, 30);
+
+/// 校验区间与循环次数，宽度与帧率收敛到支持范围内后返回。
+pub fn normalize_animation_options(
+    options: &AnimationExportOptions,
+) -> Result<AnimationExportOptions, AppError> {
+    if options.end_ms <= options.start_ms {
+        return Err(AppError::new(
+            "ANIMATION_RANGE_INVALID",
+            "animation range end must be after start",
+            Some("请重新选择动图区间".to_string()),
+        ));
+    }
+    let duration_ms = options.end_ms - options.start_ms;
+    if duration_ms > MAX_ANIMATION_DURATION_MS {
+        return Err(AppError::new(
+            "ANIMATION_TOO_LONG",
+            format!("animation lasts {duration_ms}ms, limit is {MAX_ANIMATION_DURATION_MS}ms"),
+            Some("动图最长 15 秒，更长的片段请导出为视频".to_string()),
+        )
+        .with_param("seconds", MAX_ANIMATION_DURATION_MS / 1000));
+    }
+    if options.loop_count > MAX_ANIMATION_LOOPS {
+        return Err(AppError::new(
+            "ANIMATION_LOOP_INVALID",
+            format!(
+                "loop count {} exceeds {MAX_ANIMATION_LOOPS}",
+                options.loop_count
+            ),
+            Some("循环次数填 0 表示无限循环".to_string()),
+        )
+        .with_param("max", MAX_ANIMATION_LOOPS));
+    }
+    Ok(AnimationExportOptions {
+        width: options
+            .width
+            .clamp(ANIMATION_WIDTH_RANGE.0, ANIMATION_WIDTH_RANGE.1),
+        fps: options
+            .fps
+            .clamp(ANIMATION_FPS_RANGE.0, ANIMATION_FPS_RANGE.1),
+        ..options.clone()
+    })
+}
+
+/// 接在时间线滤镜之后：降帧、按宽度等比缩放（高度取偶数）。
+pub fn build_animation_filter(options: &AnimationExportOptions) -> String {
+    format!(
+        "fps={},scale={}:-2:flags=lanczos",
+        options.fps, options.width
+    )
+}
+
+/// 编码参数：WebP 用有损压缩，APNG 为无损，转 rgb24 去掉不需要的透明通道。
+pub fn animation_codec_args(options: &AnimationExportOptions) -> Vec<String> {
+    let loops = options.loop_count.to_string();
+    let args: Vec<&str> = match options.format {
+        AnimationFormat::Webp => vec![
+            "-c:v", "libwebp", "-quality", "75", "-loop", &loops, "-f", "webp",
+        ],
+        AnimationFormat::Apng => vec![
+            "-c:v", "apng", "-pred", "mixed", "-pix_fmt", "rgb24", "-plays", &loops, "-f", "apng",
+        ],
+    };
+    args.into_iter().map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{animation_codec_args, build_animation_filter, normalize_animation_options};
+    use crate::domain::models::{AnimationExportOptions, AnimationFormat};
+
+    fn options(format: AnimationFormat, end_ms: u64, loop_count: u32) -> AnimationExportOptions {
+        AnimationExportOptions {
+            format,
+            start_ms: 2_000,
+            end_ms,
+            loop_count,
+            width: 4_000,
+            fps: 60,
+        }
+    }
+
+    #[test]
+    fn options_are_validated_and_clamped() {
+        let normalized =
+            normalize_animation_options(&options(AnimationFormat::Webp, 8_000, 0)).unwrap();
+        assert_eq!((normalized.width, normalized.fps), (1920, 30));
+        assert_eq!(
+            build_animation_filter(&normalized),
+            "fps=30,scale=1920:-2:flags=lanczos"
+        );
+
+        let code = |options| normalize_animation_options(&options).unwrap_err().code;
+        assert_eq!(
+            code(options(AnimationFormat::Webp, 2_000, 0)),
+            "ANIMATION_RANGE_INVALID"
+        );
+        assert_eq!(
+            code(options(AnimationFormat::Apng, 17_001, 0)),
+            "ANIMATION_TOO_LONG"
+        );
+        assert_eq!(
+            code(options(AnimationFormat::Apng, 8_000, 70_000)),
+            "ANIMATION_LOOP_INVALID"
+        );
+    }
+
+    #[test]
+    fn codec_args_carry_loop_count_per_format() {
+        let webp = animation_codec_args(&options(AnimationFormat::Webp, 8_000, 0));
+        assert_eq!(
+            webp,
+            ["-c:v", "libwebp", "-quality", "75", "-loop", "0", "-f", "webp"]
+        );
+        let apng = animation_codec_args(&options(AnimationFormat::Apng, 8_000, 3));
+        assert!(apng.windows(2).any(|pair| pair == ["-plays", "3"]));
+        assert_eq!(apng.last().map(String::as_str), Some("apng"));
+    }
+}
@@ -0,0 +1,377 @@
+use crate::domain::models::{ShapeAnnotation, ShapeKind, TextAnnotation};
+use crate::infra::ffmpeg::keystroke_overlay::{escape_drawtext, overlay_font_path};
+use crate::infra::ffmpeg::scene::parse_hex_color;
+use std::path::PathBuf;
+
+// 字号、边距以 1080p 短边为基准。
+const ANNOTATION_REFERENCE_SIDE: f64 = 1080.0;
+const ANNOTATION_MARGIN: f64 = 48.0;
+// 三次贝塞尔逼近四分之一椭圆弧的控制点系数。
+const ELLIPSE_KAPPA: f64 = 0.552_284_75;
+
+/// 标注文字常含中文，优先选带 CJK 字形的系统字体，都不存在时退回按键提示字体。
+pub fn annotation_font_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("FOCUSLENS_ANNOTATION_FONT") {
+        return Some(PathBuf::from(path)).filter(|path| path.is_file());
+    }
+    let candidates: &[&str] = if cfg!(target_os = "windows") {
+        &["C:/Windows/Fonts/msyh.ttc", "C:/Windows/Fonts/simhei.ttf"]
+    } else if cfg!(target_os = "macos") {
+        &[
+            "/System/Library/Fonts/PingFang.ttc",
+            "/System/Library/Fonts/STHeiti Medium.ttc",
+        ]
+    } else {
+        &[
+            "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+            "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+        ]
+    };
+    candidates
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_file())
+        .or_else(overlay_font_path)
+}
+
+/// 每条标注一个 drawtext，作用于最终画面。时间从裁剪起点算起，
+/// 完全落在裁剪区间外的标注被跳过，跨过裁剪终点的截断到终点。
+pub fn build_annotation_filters(
+    annotations: &[TextAnnotation],
+    font: Option<&str>,
+    target_w: u32,
+    target_h: u32,
+    trim_start_ms: u64,
+    trim_end_ms: u64,
+) -> Vec<String> {
+    let scale = target_w.min(target_h) as f64 / ANNOTATION_REFERENCE_SIDE;
+    let margin = (ANNOTATION_MARGIN * scale).round() as u32;
+    let font = font
+        .map(|font| format!("fontfile='{font}':"))
+        .unwrap_or_default();
+    annotations
+        .iter()
+        .filter_map(|annotation| {
+            let end_ms = if trim_end_ms > trim_start_ms {
+                annotation.end_ms.min(trim_end_ms)
+            } else {
+                annotation.end_ms
+            };
+            if end_ms <= trim_start_ms || end_ms <= annotation.start_ms {
+                return None;
+            }
+            let start = annotation.start_ms.saturating_sub(trim_start_ms) as f64 / 1000.0;
+            let end = (end_ms - trim_start_ms) as f64 / 1000.0;
+            let style = &annotation.style;
+            let font_size = ((style.font_size as f64 * scale).round() as u32).max(8);
+            let padding = (font_size as f64 * 0.35).round() as u32;
+            // 底框向外扩 padding，位置按扩展后的框计算，贴边时也不会被裁掉。
+            let inset = margin + padding;
+            let box_filter = match style.background.as_deref().and_then(parse_hex_color) {
+                Some(background) => format!(
+                    "box=1:boxcolor={}@{:.2}:boxborderw={padding}:",
+                    ffmpeg_color(background),
+                    style.background_opacity.clamp(0.0, 1.0)
+                ),
+                None => String::new(),
+            };
+            let color = parse_hex_color(&style.color).unwrap_or((255, 255, 255));
+            Some(format!(
+                "drawtext={font}text={}:expansion=none:fontsize={font_size}:fontcolor={}:{box_filter}x={inset}+(w-text_w-{})*{:.4}:y={inset}+(h-text_h-{})*{:.4}:enable='between(t,{start:.3},{end:.3})'",
+                escape_drawtext(&annotation.text),
+                ffmpeg_color(color),
+                inset * 2,
+                annotation.x.clamp(0.0, 1.0),
+                inset * 2,
+                annotation.y.clamp(0.0, 1.0),
+            ))
+        })
+        .collect()
+}
+
+/// 把形状标注写成 ASS 矢量绘图脚本，交给 libass 在最终画面上渲染。
+/// 脚本分辨率即输出分辨率，坐标直接为像素；时间从裁剪起点算起。
+/// 裁剪区间内没有任何形状时返回 None。
+pub fn build_shape_script(
+    shapes: &[ShapeAnnotation],
+    target_w: u32,
+    target_h: u32,
+    trim_start_ms: u64,
+    trim_end_ms: u64,
+) -> Option<String> {
+    let scale = target_w.min(target_h) as f64 / ANNOTATION_REFERENCE_SIDE;
+    let events = shapes
+        .iter()
+        .filter_map(|shape| {
+            let end_ms = if trim_end_ms > trim_start_ms {
+                shape.end_ms.min(trim_end_ms)
+            } else {
+                shape.end_ms
+            };
+            if end_ms <= trim_start_ms || end_ms <= shape.start_ms {
+                return None;
+            }
+            let from = (shape.from.x * target_w as f64, shape.from.y * target_h as f64);
+            let to = (shape.to.x * target_w as f64, shape.to.y * target_h as f64);
+            let stroke_width = (shape.style.stroke_width as f64 * scale).max(1.0);
+            let stroke = parse_hex_color(&shape.style.stroke_color).unwrap_or((255, 59, 48));
+            let (points, tags) = match shape.kind {
+                ShapeKind::Arrow => (
+                    arrow_outline(from, to, stroke_width)?,
+                    format!("\\bord0\\1c{}\\1a&H00&", ass_color(stroke)),
+                ),
+                ShapeKind::Rectangle | ShapeKind::Ellipse => {
+                    let fill = shape.style.fill.as_deref().and_then(parse_hex_color);
+                    let fill_alpha = match fill {
+                        Some(_) => ((1.0 - shape.style.fill_opacity.clamp(0.0, 1.0)) * 255.0)
+                            .round() as u8,
+                        None => 255,
+                    };
+                    let outline = if shape.kind == ShapeKind::Rectangle {
+                        rectangle_outline(from, to)
+                    } else {
+                        ellipse_outline(from, to)
+                    };
+                    (
+                        outline,
+                        format!(
+                            "\\bord{stroke_width:.1}\\3c{}\\3a&H00&\\1c{}\\1a&H{fill_alpha:02X}&",
+                            ass_color(stroke),
+                            ass_color(fill.unwrap_or(stroke)),
+                        ),
+                    )
+                }
+            };
+            Some(format!(
+                "Dialogue: 0,{},{},Shape,,0,0,0,,{{\\an7\\pos(0,0)\\shad0{tags}\\p1}}{points}{{\\p0}}",
+                ass_time(shape.start_ms.saturating_sub(trim_start_ms)),
+                ass_time(end_ms - trim_start_ms),
+            ))
+        })
+        .collect::<Vec<_>>();
+    if events.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "[Script Info]\nScriptType: v4.00+\nPlayResX: {target_w}\nPlayResY: {target_h}\nScaledBorderAndShadow: yes\n\n\
+[V4+ Styles]\n\
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+Style: Shape,Arial,20,&H00FFFFFF,&H00FFFFFF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,0,0,7,0,0,0,1\n\n\
+[Events]\n\
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n{}\n",
+        events.join("\n")
+    ))
+}
+
+fn rectangle_outline(from: (f64, f64), to: (f64, f64)) -> String {
+    let (left, top, right, bottom) = (
+        from.0.min(to.0),
+        from.1.min(to.1),
+        from.0.max(to.0),
+        from.1.max(to.1),
+    );
+    format!(
+        "m {} {} l {} {} {} {} {} {}",
+        px(left),
+        px(top),
+        px(right),
+        px(top),
+        px(right),
+        px(bottom),
+        px(left),
+        px(bottom)
+    )
+}
+
+/// 四段三次贝塞尔拼成的椭圆，从顶点顺时针绘制。
+fn ellipse_outline(from: (f64, f64), to: (f64, f64)) -> String {
+    let (cx, cy) = ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0);
+    let (rx, ry) = ((to.0 - from.0).abs() / 2.0, (to.1 - from.1).abs() / 2.0);
+    let (kx, ky) = (rx * ELLIPSE_KAPPA, ry * ELLIPSE_KAPPA);
+    let point = |x: f64, y: f64| format!("{} {}", px(x), px(y));
+    format!(
+        "m {} b {} {} {} b {} {} {} b {} {} {} b {} {} {}",
+        point(cx, cy - ry),
+        point(cx + kx, cy - ry),
+        point(cx + rx, cy - ky),
+        point(cx + rx, cy),
+        point(cx + rx, cy + ky),
+        point(cx + kx, cy + ry),
+        point(cx, cy + ry),
+        point(cx - kx, cy + ry),
+        point(cx - rx, cy + ky),
+        point(cx - rx, cy),
+        point(cx - rx, cy - ky),
+        point(cx - kx, cy - ry),
+        point(cx, cy - ry),
+    )
+}
+
+/// 箭杆宽度等于线宽，箭头长约 4 倍线宽；箭头过短时按长度等比缩小箭头。
+fn arrow_outline(from: (f64, f64), to: (f64, f64), stroke_width: f64) -> Option<String> {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx.hypot(dy);
+    if length < 1.0 {
+        return None;
+    }
+    let (ux, uy) = (dx / length, dy / length);
+    let (nx, ny) = (-uy, ux);
+    let head_length = (stroke_width * 4.0).min(length * 0.5);
+    let head_half = head_length * 0.6;
+    let shaft_half = (stroke_width / 2.0).min(head_half);
+    let base = (to.0 - ux * head_length, to.1 - uy * head_length);
+    let offset = |point: (f64, f64), distance: f64| {
+        format!(
+            "{} {}",
+            px(point.0 + nx * distance),
+            px(point.1 + ny * distance)
+        )
+    };
+    Some(format!(
+        "m {} l {} {} {} {} {} {}",
+        offset(from, shaft_half),
+        offset(base, shaft_half),
+        offset(base, head_half),
+        offset(to, 0.0),
+        offset(base, -head_half),
+        offset(base, -shaft_half),
+        offset(from, -shaft_half),
+    ))
+}
+
+fn px(value: f64) -> i64 {
+    value.round() as i64
+}
+
+/// ASS 颜色为 `&HBBGGRR&`。
+fn ass_color((r, g, b): (u8, u8, u8)) -> String {
+    format!("&H{b:02X}{g:02X}{r:02X}&")
+}
+
+/// ASS 时间精确到百分之一秒：`H:MM:SS.cc`。
+fn ass_time(ms: u64) -> String {
+    let centis = ms / 10;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        centis / 360_000,
+        centis / 6_000 % 60,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
+fn ffmpeg_color((r, g, b): (u8, u8, u8)) -> String {
+    format!("0x{r:02X}{g:02X}{b:02X}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_annotation_filters, build_shape_script};
+    use crate::domain::models::{
+        AnnotationStyle, NormalizedPoint, ShapeAnnotation, ShapeKind, ShapeStyle, TextAnnotation,
+    };
+
+    fn annotation(
+        text: &str,
+        start_ms: u64,
+        end_ms: u64,
+        style: AnnotationStyle,
+    ) -> TextAnnotation {
+        TextAnnotation {
+            id: "a".to_string(),
+            text: text.to_string(),
+            x: 0.5,
+            y: 1.0,
+            start_ms,
+            end_ms,
+            style,
+        }
+    }
+
+    #[test]
+    fn annotations_follow_trim_and_stay_inside_the_frame() {
+        let filters = build_annotation_filters(
+            &[
+                annotation(
+                    "Step 2: configure the webhook",
+                    3_000,
+                    9_000,
+                    AnnotationStyle::default(),
+                ),
+                annotation(
+                    "plain",
+                    0,
+                    2_500,
+                    AnnotationStyle {
+                        color: "#ffcc00".to_string(),
+                        background: None,
+                        ..AnnotationStyle::default()
+                    },
+                ),
+                // 完全在裁剪起点之前。
+                annotation("gone", 0, 1_000, AnnotationStyle::default()),
+            ],
+            None,
+            1920,
+            1080,
+            2_000,
+            8_000,
+        );
+        assert_eq!(filters.len(), 2);
+        assert_eq!(
+            filters[0],
+            "drawtext=text=Step 2\\\\: configure the webhook:expansion=none:fontsize=48:fontcolor=0xFFFFFF:box=1:boxcolor=0x000000@0.60:boxborderw=17:x=65+(w-text_w-130)*0.5000:y=65+(h-text_h-130)*1.0000:enable='between(t,1.000,6.000)'"
+        );
+        assert!(filters[1].contains("fontcolor=0xFFCC00:x="));
+        assert!(filters[1].ends_with("enable='between(t,0.000,0.500)'"));
+    }
+
+    fn shape(kind: ShapeKind, from: (f64, f64), to: (f64, f64), start_ms: u64) -> ShapeAnnotation {
+        ShapeAnnotation {
+            id: "s".to_string(),
+            kind,
+            from: NormalizedPoint {
+                x: from.0,
+                y: from.1,
+            },
+            to: NormalizedPoint { x: to.0, y: to.1 },
+            start_ms,
+            end_ms: start_ms + 1_500,
+            style: ShapeStyle::default(),
+        }
+    }
+
+    #[test]
+    fn shapes_become_ass_drawings_in_output_pixels() {
+        let script = build_shape_script(
+            &[
+                shape(ShapeKind::Arrow, (0.1, 0.5), (0.3, 0.5), 2_000),
+                shape(ShapeKind::Rectangle, (0.5, 0.5), (0.25, 0.25), 2_000),
+                shape(ShapeKind::Ellipse, (0.0, 0.0), (0.5, 0.5), 2_000),
+            ],
+            1920,
+            1080,
+            1_000,
+            0,
+        )
+        .expect("shape script");
+        assert!(script.contains("PlayResX: 1920\nPlayResY: 1080"));
+        let events = script
+            .lines()
+            .filter(|line| line.starts_with("Dialogue:"))
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0],
+            "Dialogue: 0,0:00:01.00,0:00:02.50,Shape,,0,0,0,,{\\an7\\pos(0,0)\\shad0\\bord0\\1c&H303BFF&\\1a&H00&\\p1}m 192 543 l 552 543 552 554 576 540 552 526 552 537 192 537{\\p0}"
+        );
+        // 未设置填充时填充完全透明，只留描边。
+        assert!(events[1].contains("\\bord6.0\\3c&H303BFF&\\3a&H00&\\1c&H303BFF&\\1a&HFF&"));
+        assert!(events[1].contains("m 480 270 l 960 270 960 540 480 540"));
+        assert!(events[2].contains("\\p1}m 480 0 b "));
+
+        // 完全在裁剪起点之前的形状不生成脚本。
+        let outside = [shape(ShapeKind::Ellipse, (0.0, 0.0), (0.5, 0.5), 0)];
+        assert!(build_shape_script(&outside, 1920, 1080, 5_000, 0).is_none());
+    }
+}
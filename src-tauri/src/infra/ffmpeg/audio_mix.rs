@@ -0,0 +1,99 @@
+use crate::domain::models::{AudioMix, TimeRange};
+use crate::infra::ffmpeg::export::escape_filter_path;
+use std::path::Path;
+
+/// 录制音轨的增益。分轨录制之前麦克风与系统声音已混在同一音轨里，
+/// 两路都录制时只能取两者的平均值。
+pub fn recording_track_gain(mix: &AudioMix, mic_recorded: bool, system_recorded: bool) -> f32 {
+    match (mic_recorded, system_recorded) {
+        (true, false) => mix.mic_volume,
+        (false, true) => mix.system_volume,
+        (true, true) => (mix.mic_volume + mix.system_volume) / 2.0,
+        (false, false) => 1.0,
+    }
+}
+
+/// 增益为 1 时不插入滤镜，避免无意义的重新采样。
+pub fn build_volume_filter(gain: f32) -> Option<String> {
+    ((gain - 1.0).abs() > f32::EPSILON).then(|| format!("volume={gain:.3}"))
+}
+
+/// 静音区间换算到裁剪后的时间轴，合并为一个按时间启用的 `volume=0`。
+pub fn build_mute_filter(
+    ranges: &[TimeRange],
+    trim_start_ms: u64,
+    trim_end_ms: Option<u64>,
+) -> Option<String> {
+    let trim_end_ms = trim_end_ms.unwrap_or(u64::MAX);
+    let windows = ranges
+        .iter()
+        .filter(|range| range.end_ms > trim_start_ms && range.start_ms < trim_end_ms)
+        .map(|range| {
+            let start = range.start_ms.saturating_sub(trim_start_ms) as f64 / 1000.0;
+            let end = (range.end_ms.min(trim_end_ms) - trim_start_ms) as f64 / 1000.0;
+            format!("between(t,{start:.3},{end:.3})")
+        })
+        .collect::<Vec<_>>();
+    (!windows.is_empty()).then(|| format!("volume=0:enable='{}'", windows.join("+")))
+}
+
+/// 背景音乐循环铺满成片，按成片时长截断；滤镜段以主音轨为输入、混音结果为输出。
+pub fn build_music_filter(music_path: &Path, volume: f32) -> String {
+    let file = escape_filter_path(music_path);
+    format!(
+        "anull[mix_main];amovie=filename='{file}':loop=0,asetpts=N/SR/TB,volume={volume:.3}[mix_music];\
+[mix_main][mix_music]amix=inputs=2:duration=first:dropout_transition=0:normalize=0"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_music_filter, build_mute_filter, build_volume_filter, recording_track_gain};
+    use crate::domain::models::{AudioMix, TimeRange};
+    use std::path::Path;
+
+    #[test]
+    fn recording_gain_follows_recorded_sources() {
+        let mix = AudioMix {
+            mic_volume: 1.5,
+            system_volume: 0.5,
+            ..AudioMix::default()
+        };
+        assert_eq!(recording_track_gain(&mix, true, false), 1.5);
+        assert_eq!(recording_track_gain(&mix, false, true), 0.5);
+        assert_eq!(recording_track_gain(&mix, true, true), 1.0);
+        assert_eq!(build_volume_filter(1.0), None);
+        assert_eq!(build_volume_filter(0.5).as_deref(), Some("volume=0.500"));
+    }
+
+    #[test]
+    fn music_is_looped_and_mixed_under_main_track() {
+        let filter = build_music_filter(Path::new("/music/bgm.mp3"), 0.3);
+        assert!(filter.starts_with("anull[mix_main];amovie=filename='/music/bgm.mp3':loop=0"));
+        assert!(filter.contains("volume=0.300[mix_music]"));
+        assert!(filter.ends_with("amix=inputs=2:duration=first:dropout_transition=0:normalize=0"));
+    }
+
+    #[test]
+    fn mute_ranges_are_shifted_into_trimmed_time() {
+        let ranges = [
+            TimeRange {
+                start_ms: 500,
+                end_ms: 1_500,
+            },
+            TimeRange {
+                start_ms: 3_000,
+                end_ms: 9_000,
+            },
+            TimeRange {
+                start_ms: 12_000,
+                end_ms: 13_000,
+            },
+        ];
+        assert_eq!(
+            build_mute_filter(&ranges, 1_000, Some(10_000)).as_deref(),
+            Some("volume=0:enable='between(t,0.000,0.500)+between(t,2.000,8.000)'")
+        );
+        assert_eq!(build_mute_filter(&ranges[..1], 2_000, None), None);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::domain::models::{BlurMode, BlurRegion};
+
+// 模糊半径上限（源画面像素），再大也看不出区别，只会拖慢编码。
+const MAX_BLUR_RADIUS: u32 = 24;
+// 打码区域的最小边长，过小的区域 boxblur 无法工作。
+const MIN_REGION_SIDE: u32 = 8;
+
+/// 源画面像素坐标下的打码矩形（偶数对齐，完全落在画面内）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PixelRect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+/// 把打码区域展开为作用于原始画面的滤镜，放在镜头裁剪之前。
+/// 滤镜时间从裁剪起点算起，与 `-ss` 之后的帧时间戳一致；完全落在裁剪区间外的区域被跳过。
+pub fn build_blur_filters(
+    regions: &[BlurRegion],
+    source_w: u32,
+    source_h: u32,
+    trim_start_ms: u64,
+    trim_end_ms: u64,
+) -> Vec<String> {
+    regions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, region)| {
+            if region.end_ms <= trim_start_ms
+                || (trim_end_ms > trim_start_ms && region.start_ms >= trim_end_ms)
+            {
+                return None;
+            }
+            let start = region.start_ms.saturating_sub(trim_start_ms) as f64 / 1000.0;
+            let end = (region.end_ms - trim_start_ms) as f64 / 1000.0;
+            let enable = format!("enable='between(t,{start:.3},{end:.3})'");
+            let rect = pixel_rect(region, source_w, source_h, region.mode == BlurMode::Delogo)?;
+            Some(match region.mode {
+                BlurMode::Blur => {
+                    let radius = (rect.width.min(rect.height) / 4).clamp(1, MAX_BLUR_RADIUS);
+                    format!(
+                        "split=2[blur_base{index}][blur_src{index}];\
+[blur_src{index}]crop={w}:{h}:{x}:{y},boxblur=luma_radius={radius}:luma_power=2[blur_patch{index}];\
+[blur_base{index}][blur_patch{index}]overlay={x}:{y}:{enable}",
+                        w = rect.width,
+                        h = rect.height,
+                        x = rect.x,
+                        y = rect.y,
+                    )
+                }
+                BlurMode::Delogo => format!(
+                    "delogo=x={}:y={}:w={}:h={}:{enable}",
+                    rect.x, rect.y, rect.width, rect.height
+                ),
+            })
+        })
+        .collect()
+}
+
+/// 归一化矩形换算到像素并夹取到画面内。delogo 要求区域与画面边缘至少留 1 像素，
+/// 用于插值的周边像素才存在。
+fn pixel_rect(
+    region: &BlurRegion,
+    source_w: u32,
+    source_h: u32,
+    keep_margin: bool,
+) -> Option<PixelRect> {
+    let margin = u32::from(keep_margin);
+    let even_floor = |value: f64| (value.max(0.0) as u32) / 2 * 2;
+    let max_w = source_w.checked_sub(margin * 2)?;
+    let max_h = source_h.checked_sub(margin * 2)?;
+    let x = even_floor(region.rect.x * source_w as f64).clamp(margin, source_w - margin);
+    let y = even_floor(region.rect.y * source_h as f64).clamp(margin, source_h - margin);
+    // 宽高向上取偶数，保证打码完全覆盖所选区域。
+    let width = even_floor((region.rect.width * source_w as f64).ceil() + 1.0)
+        .max(MIN_REGION_SIDE)
+        .min(max_w);
+    let height = even_floor((region.rect.height * source_h as f64).ceil() + 1.0)
+        .max(MIN_REGION_SIDE)
+        .min(max_h);
+    if width < MIN_REGION_SIDE || height < MIN_REGION_SIDE {
+        return None;
+    }
+    // 区域超出右、下边缘时整体内移，保持尺寸不变。
+    Some(PixelRect {
+        x: x.min(source_w - margin - width),
+        y: y.min(source_h - margin - height),
+        width,
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_blur_filters;
+    use crate::domain::models::{BlurMode, BlurRegion, NormalizedRect};
+
+    fn region(
+        mode: BlurMode,
+        rect: (f64, f64, f64, f64),
+        start_ms: u64,
+        end_ms: u64,
+    ) -> BlurRegion {
+        BlurRegion {
+            id: "r".to_string(),
+            rect: NormalizedRect {
+                x: rect.0,
+                y: rect.1,
+                width: rect.2,
+                height: rect.3,
+            },
+            start_ms,
+            end_ms,
+            mode,
+        }
+    }
+
+    #[test]
+    fn blur_regions_map_to_trimmed_time_and_source_pixels() {
+        let filters = build_blur_filters(
+            &[
+                region(BlurMode::Blur, (0.25, 0.5, 0.25, 0.1), 3000, 6000),
+                region(BlurMode::Delogo, (0.9, 0.9, 0.2, 0.2), 0, 2500),
+                // 完全在裁剪起点之前，不生成滤镜。
+                region(BlurMode::Blur, (0.0, 0.0, 0.5, 0.5), 0, 1000),
+            ],
+            1920,
+            1080,
+            2000,
+            0,
+        );
+        assert_eq!(filters.len(), 2);
+        assert_eq!(
+            filters[0],
+            "split=2[blur_base0][blur_src0];\
+[blur_src0]crop=480:108:480:540,boxblur=luma_radius=24:luma_power=2[blur_patch0];\
+[blur_base0][blur_patch0]overlay=480:540:enable='between(t,1.000,4.000)'"
+        );
+        // 超出右下角的区域内移到画面内，并与边缘保留 1 像素。
+        assert_eq!(
+            filters[1],
+            "delogo=x=1535:y=863:w=384:h=216:enable='between(t,0.000,0.500)'"
+        );
+    }
+
+    #[test]
+    fn regions_after_trim_end_are_skipped() {
+        let filters = build_blur_filters(
+            &[region(BlurMode::Blur, (0.1, 0.1, 0.1, 0.1), 9000, 9500)],
+            1280,
+            720,
+            0,
+            8000,
+        );
+        assert!(filters.is_empty());
+    }
+}
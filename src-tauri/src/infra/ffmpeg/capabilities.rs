@@ -1,3 +1,9 @@
+use crate::infra::ffmpeg::command::{ffmpeg_bin, output_with_timeout, probe_timeout};
+use std::process::Command;
+use std::sync::OnceLock;
+
+pub const SOFTWARE_CODEC: &str = "libx264";
+
 #[derive(Debug, Clone)]
 pub struct HardwareEncoderAvailability {
     pub available: bool,
@@ -5,64 +11,141 @@ pub struct HardwareEncoderAvailability {
     pub codec: String,
 }
 
+/// 检测本机可用的硬件 H.264 编码器，按平台优先级逐个试编码，首个成功者胜出；
+/// 都不可用时返回软件编码。结果在进程内缓存，避免每次导出重复探测。
 pub fn detect_hardware_encoder() -> HardwareEncoderAvailability {
-    let codec = preferred_codec().to_string();
-    let output = std::process::Command::new(
-        std::env::var("FOCUSLENS_FFMPEG_PATH").unwrap_or_else(|_| "ffmpeg".to_string()),
-    )
-    .arg("-hide_banner")
-    .arg("-encoders")
-    .output();
+    static DETECTED: OnceLock<HardwareEncoderAvailability> = OnceLock::new();
+    DETECTED.get_or_init(probe_hardware_encoder).clone()
+}
 
-    if let Ok(output) = output {
-        let stdout = String::from_utf8_lossy(&output.stdout).to_lowercase();
-        let available = output.status.success() && stdout.contains(&codec.to_lowercase());
-        if available {
-            return HardwareEncoderAvailability {
-                available: true,
-                detail: format!("detected hardware encoder: {codec}"),
-                codec,
-            };
-        }
+fn probe_hardware_encoder() -> HardwareEncoderAvailability {
+    let listed = output_with_timeout(
+        Command::new(ffmpeg_bin())
+            .arg("-hide_banner")
+            .arg("-encoders"),
+        probe_timeout(),
+    )
+    .ok()
+    .filter(|output| output.status.success())
+    .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+    .unwrap_or_default();
+    let compiled = rank_compiled_encoders(&listed, hardware_candidates());
+    // ffmpeg 编译了编码器不代表有对应显卡，需实际编码一帧确认驱动可用。
+    if let Some(codec) = compiled.iter().find(|codec| encoder_works(codec)) {
+        return HardwareEncoderAvailability {
+            available: true,
+            detail: format!("detected hardware encoder: {codec}"),
+            codec: codec.to_string(),
+        };
     }
+    let tried = if compiled.is_empty() {
+        "none".to_string()
+    } else {
+        compiled.join(", ")
+    };
 
     #[cfg(target_os = "windows")]
     {
         HardwareEncoderAvailability {
             available: false,
-            detail: "windows: hardware encoder unavailable, fallback to software".to_string(),
-            codec,
+            detail: format!(
+                "windows: hardware encoder unavailable (tried: {tried}), fallback to software"
+            ),
+            codec: SOFTWARE_CODEC.to_string(),
         }
     }
     #[cfg(target_os = "macos")]
     {
         HardwareEncoderAvailability {
             available: false,
-            detail: "macos: hardware encoder unavailable, fallback to software".to_string(),
-            codec,
+            detail: format!(
+                "macos: hardware encoder unavailable (tried: {tried}), fallback to software"
+            ),
+            codec: SOFTWARE_CODEC.to_string(),
         }
     }
     #[cfg(not(any(target_os = "windows", target_os = "macos")))]
     {
+        let _ = tried;
         HardwareEncoderAvailability {
             available: false,
             detail: "当前平台不在 MVP 支持范围，使用软件编码".to_string(),
-            codec,
+            codec: SOFTWARE_CODEC.to_string(),
         }
     }
 }
 
-fn preferred_codec() -> &'static str {
+/// 各平台硬件编码器，按优先级排列。
+fn hardware_candidates() -> &'static [&'static str] {
     #[cfg(target_os = "windows")]
     {
-        "h264_nvenc"
+        &["h264_nvenc", "h264_amf", "h264_qsv"]
     }
     #[cfg(target_os = "macos")]
     {
-        "h264_videotoolbox"
+        &["h264_videotoolbox"]
     }
     #[cfg(not(any(target_os = "windows", target_os = "macos")))]
     {
-        "libx264"
+        &[]
+    }
+}
+
+/// 从 `ffmpeg -encoders` 输出中筛出已编译的候选编码器，保持候选顺序。
+fn rank_compiled_encoders(listed: &str, candidates: &[&'static str]) -> Vec<&'static str> {
+    let names = listed
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .collect::<Vec<_>>();
+    candidates
+        .iter()
+        .copied()
+        .filter(|candidate| names.contains(candidate))
+        .collect()
+}
+
+fn encoder_works(codec: &str) -> bool {
+    // 个别显卡驱动初始化会卡住，试编码同样受探测超时约束。
+    output_with_timeout(
+        Command::new(ffmpeg_bin()).args([
+            "-hide_banner",
+            "-loglevel",
+            "error",
+            "-f",
+            "lavfi",
+            "-i",
+            "color=c=black:s=256x256:r=30:d=0.1",
+            "-frames:v",
+            "1",
+            "-c:v",
+            codec,
+            "-f",
+            "null",
+            "-",
+        ]),
+        probe_timeout(),
+    )
+    .map(|output| output.status.success())
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rank_compiled_encoders;
+
+    #[test]
+    fn compiled_encoders_keep_candidate_priority() {
+        let listed = "\
+Encoders:
+ V..... = Video
+ ------
+ V....D h264_amf             AMD AMF H.264 Encoder (codec h264)
+ V....D h264_qsv             H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10 (Intel Quick Sync Video acceleration) (codec h264)
+ V....D libx264              libx264 H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10 (codec h264)";
+        assert_eq!(
+            rank_compiled_encoders(listed, &["h264_nvenc", "h264_qsv", "h264_amf"]),
+            vec!["h264_qsv", "h264_amf"]
+        );
+        assert!(rank_compiled_encoders("", &["h264_nvenc"]).is_empty());
     }
 }
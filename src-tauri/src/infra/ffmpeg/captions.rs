@@ -0,0 +1,201 @@
+use crate::core::transcript::service::load_transcript;
+use crate::domain::models::{AppError, ExportProfile, ProjectManifest, TranscriptCue};
+use crate::infra::ffmpeg::export::export_remap_plan;
+use crate::infra::ffmpeg::probe::probe_media;
+use crate::infra::ffmpeg::time_remap::{remap_range_sec, RemapSegment};
+use std::path::{Path, PathBuf};
+
+const CAPTION_EXTENSIONS: [&str; 2] = ["srt", "vtt"];
+
+/// 成片旁的字幕文件路径（`output.srt`、`output.vtt`）。
+pub fn sidecar_caption_paths(output_path: &Path) -> Vec<PathBuf> {
+    CAPTION_EXTENSIONS
+        .iter()
+        .map(|extension| output_path.with_extension(extension))
+        .collect()
+}
+
+/// 项目有文字稿时在成片旁写出 SRT 与 VTT 字幕，时间对齐到成片（裁剪、删除段、变速、片头）；
+/// 没有文字稿时删除上次导出遗留的字幕文件。返回写出的文件路径。
+pub fn write_sidecar_captions(
+    manifest: &ProjectManifest,
+    profile: &ExportProfile,
+    input_path: &Path,
+    output_path: &Path,
+) -> Result<Vec<PathBuf>, AppError> {
+    let paths = sidecar_caption_paths(output_path);
+    let cues = load_transcript(profile);
+    if cues.is_empty() {
+        for path in &paths {
+            let _ = std::fs::remove_file(path);
+        }
+        return Ok(Vec::new());
+    }
+    let (duration_ms, remap) = export_remap_plan(manifest, input_path);
+    let intro_ms = profile
+        .intro_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .and_then(|path| probe_media(Path::new(path)).ok())
+        .map_or(0, |summary| summary.container_duration_ms);
+    let cues = map_cues_to_output(
+        &cues,
+        manifest.timeline.trim_start_ms,
+        duration_ms,
+        &remap,
+        intro_ms,
+    );
+    for (path, content) in paths.iter().zip([format_srt(&cues), format_vtt(&cues)]) {
+        std::fs::write(path, content).map_err(|error| {
+            AppError::new(
+                "EXPORT_CAPTION_WRITE_FAIL",
+                format!("failed to write {}: {error}", path.display()),
+                None,
+            )
+        })?;
+    }
+    Ok(paths)
+}
+
+/// 字幕时间从源文件时间轴换算到成片时间轴；落在裁剪区间外或被整段删除的字幕丢弃。
+fn map_cues_to_output(
+    cues: &[TranscriptCue],
+    trim_start_ms: u64,
+    duration_ms: u64,
+    remap: &[RemapSegment],
+    offset_ms: u64,
+) -> Vec<TranscriptCue> {
+    let duration_sec = duration_ms as f64 / 1000.0;
+    cues.iter()
+        .filter_map(|cue| {
+            let start_sec = (cue.start_ms as f64 - trim_start_ms as f64) / 1000.0;
+            let end_sec = (cue.end_ms as f64 - trim_start_ms as f64) / 1000.0;
+            let (start_sec, end_sec) =
+                remap_range_sec(remap, start_sec.max(0.0), end_sec.min(duration_sec))?;
+            let to_ms = |sec: f64| offset_ms + (sec * 1000.0).round() as u64;
+            let (start_ms, end_ms) = (to_ms(start_sec), to_ms(end_sec));
+            (end_ms > start_ms).then(|| TranscriptCue {
+                start_ms,
+                end_ms,
+                text: cue.text.clone(),
+            })
+        })
+        .collect()
+}
+
+fn format_srt(cues: &[TranscriptCue]) -> String {
+    cues.iter()
+        .enumerate()
+        .map(|(index, cue)| {
+            format!(
+                "{}\n{} --> {}\n{}\n\n",
+                index + 1,
+                format_timestamp(cue.start_ms, ','),
+                format_timestamp(cue.end_ms, ','),
+                cue.text
+            )
+        })
+        .collect()
+}
+
+fn format_vtt(cues: &[TranscriptCue]) -> String {
+    let mut content = String::from("WEBVTT\n\n");
+    for cue in cues {
+        content.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(cue.start_ms, '.'),
+            format_timestamp(cue.end_ms, '.'),
+            cue.text
+        ));
+    }
+    content
+}
+
+/// `HH:MM:SS,mmm`（SRT）或 `HH:MM:SS.mmm`（VTT）。
+fn format_timestamp(ms: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_srt, format_vtt, map_cues_to_output};
+    use crate::core::transcript::service::parse_transcript;
+    use crate::domain::models::TranscriptCue;
+    use crate::infra::ffmpeg::time_remap::RemapSegment;
+
+    fn cue(start_ms: u64, end_ms: u64, text: &str) -> TranscriptCue {
+        TranscriptCue {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn cues_follow_trim_cuts_speed_and_intro() {
+        let cues = [
+            cue(500, 1_500, "before trim"),
+            cue(2_000, 3_000, "opening"),
+            cue(4_200, 4_800, "deleted"),
+            cue(5_500, 7_000, "fast part"),
+            cue(20_000, 21_000, "after trim"),
+        ];
+        // 裁剪起点 1s、时长 10s；导出时间轴 3..4s 删除，6..10s 两倍速。
+        let remap = [
+            RemapSegment {
+                start_sec: 0.0,
+                end_sec: 3.0,
+                speed: 1.0,
+            },
+            RemapSegment {
+                start_sec: 4.0,
+                end_sec: 5.0,
+                speed: 1.0,
+            },
+            RemapSegment {
+                start_sec: 5.0,
+                end_sec: 10.0,
+                speed: 2.0,
+            },
+        ];
+        let mapped = map_cues_to_output(&cues, 1_000, 10_000, &remap, 2_000);
+        let summary = mapped
+            .iter()
+            .map(|cue| (cue.start_ms, cue.end_ms, cue.text.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (2_000, 2_500, "before trim"),
+                (3_000, 4_000, "opening"),
+                (5_500, 6_500, "fast part"),
+            ]
+        );
+
+        let untouched = map_cues_to_output(&cues[1..2], 0, 10_000, &[], 0);
+        assert_eq!(untouched, [cue(2_000, 3_000, "opening")]);
+    }
+
+    #[test]
+    fn srt_and_vtt_round_trip_through_parser() {
+        let cues = [
+            cue(1_250, 3_000, "打开账单页面"),
+            cue(3_723_004, 3_724_000, "done"),
+        ];
+        let srt = format_srt(&cues);
+        assert!(
+            srt.starts_with("1\n00:00:01,250 --> 00:00:03,000\n打开账单页面\n\n2\n01:02:03,004")
+        );
+        let vtt = format_vtt(&cues);
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:01.250 --> 00:00:03.000\n"));
+        assert_eq!(parse_transcript(&srt), cues);
+        assert_eq!(parse_transcript(&vtt), cues);
+    }
+}
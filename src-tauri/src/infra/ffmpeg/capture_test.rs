@@ -0,0 +1,247 @@
+use crate::core::capture::service::resolve_microphone_device;
+use crate::domain::models::{AppError, CaptureBounds, CaptureTestReport, RecordingProfile};
+use crate::infra::ffmpeg::command::run_ffmpeg;
+use crate::infra::ffmpeg::probe::{probe_streams, StreamSummary};
+use crate::infra::ffmpeg::recording::{spawn_recording_process, stop_ffmpeg_process};
+use std::path::Path;
+use std::time::Duration;
+
+const CAPTURE_TEST_DURATION: Duration = Duration::from_secs(3);
+// 实际帧率低于目标的八成时，多半是编码器跟不上或采集源限速，正式录制会明显掉帧。
+const LOW_FPS_RATIO: f32 = 0.8;
+// anullsrc 静音轨的 max_volume 为 -91dB，真实麦克风即使安静也会有底噪。
+const SILENT_MAX_VOLUME_DB: f32 = -80.0;
+
+/// 音轨的平均与峰值音量（dB），来自 volumedetect。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeLevels {
+    pub mean_db: f32,
+    pub max_db: f32,
+}
+
+/// 用与正式录制相同的参数试录几秒并检查产物。试录文件由调用方删除；
+/// 启动或探测失败也归入报告的 `errors`，不作为函数错误返回。
+pub fn record_capture_test(
+    ffmpeg_bin: &str,
+    profile: &RecordingProfile,
+    capture_region: Option<&CaptureBounds>,
+    output_path: &Path,
+) -> CaptureTestReport {
+    let spawn = match spawn_recording_process(ffmpeg_bin, profile, capture_region, output_path) {
+        Ok(spawn) => spawn,
+        Err(error) => return failed_report(error),
+    };
+    let mut child = spawn.child;
+    // 预览帧写在 stdout，不读走的话 ffmpeg 会在管道写满后卡住。
+    let drain = child.stdout.take().map(|mut stdout| {
+        std::thread::spawn(move || {
+            let _ = std::io::copy(&mut stdout, &mut std::io::sink());
+        })
+    });
+    std::thread::sleep(CAPTURE_TEST_DURATION);
+    let stopped = stop_ffmpeg_process(&mut child);
+    let _ = child.wait();
+    if let Some(drain) = drain {
+        let _ = drain.join();
+    }
+    if let Err(error) = stopped {
+        return failed_report(error);
+    }
+
+    let streams = match probe_streams(output_path) {
+        Ok(streams) => streams,
+        Err(error) => return failed_report(error),
+    };
+    let levels = if streams.has_audio {
+        measure_volume(output_path)
+            .map_err(|error| tracing::warn!("capture test volumedetect failed: {}", error.message))
+            .ok()
+            .flatten()
+    } else {
+        None
+    };
+    build_capture_test_report(profile, spawn.degrade_message, &streams, levels)
+}
+
+fn measure_volume(path: &Path) -> Result<Option<VolumeLevels>, AppError> {
+    let output = run_ffmpeg([
+        "-hide_banner".to_string(),
+        "-nostats".to_string(),
+        "-i".to_string(),
+        path.to_string_lossy().to_string(),
+        "-vn".to_string(),
+        "-af".to_string(),
+        "volumedetect".to_string(),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ])?;
+    if !output.status.success() {
+        return Err(AppError::new(
+            "FFMPEG_EXEC_ERROR",
+            output.stderr.lines().last().unwrap_or_default().to_string(),
+            None,
+        ));
+    }
+    Ok(parse_volumedetect(&output.stderr))
+}
+
+/// 解析 volumedetect 日志里的 `mean_volume: -27.3 dB` 与 `max_volume: -8.1 dB`。
+fn parse_volumedetect(stderr: &str) -> Option<VolumeLevels> {
+    let value = |key: &str| {
+        stderr.lines().find_map(|line| {
+            line.split_once(key)?
+                .1
+                .trim()
+                .trim_end_matches("dB")
+                .trim()
+                .parse::<f32>()
+                .ok()
+        })
+    };
+    Some(VolumeLevels {
+        mean_db: value("mean_volume:")?,
+        max_db: value("max_volume:")?,
+    })
+}
+
+fn failed_report(error: AppError) -> CaptureTestReport {
+    CaptureTestReport {
+        ready: false,
+        width: None,
+        height: None,
+        fps: None,
+        has_audio: false,
+        mean_volume_db: None,
+        max_volume_db: None,
+        errors: vec![error],
+        warnings: Vec::new(),
+    }
+}
+
+/// 正式录制总会写一条音轨（无音源时为静音轨），所以缺少音轨说明音频采集或编码出错。
+fn build_capture_test_report(
+    profile: &RecordingProfile,
+    degrade_message: Option<String>,
+    streams: &StreamSummary,
+    levels: Option<VolumeLevels>,
+) -> CaptureTestReport {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    if streams.width.is_none() || streams.frame_rate.is_none() {
+        errors.push(AppError::new(
+            "CAPTURE_TEST_NO_VIDEO",
+            "试录文件中没有可用的视频画面",
+            Some("请检查屏幕录制权限与所选显示器或窗口".to_string()),
+        ));
+    }
+    if !streams.has_audio {
+        errors.push(AppError::new(
+            "CAPTURE_TEST_NO_AUDIO",
+            "试录文件中没有音轨",
+            Some("请检查麦克风权限与音频设备".to_string()),
+        ));
+    }
+    if let Some(fps) = streams.frame_rate {
+        let target = f32::from(profile.frame_rate);
+        if fps < target * LOW_FPS_RATIO {
+            warnings.push(
+                AppError::new(
+                    "CAPTURE_TEST_LOW_FPS",
+                    format!("试录帧率 {fps:.1} 低于目标 {target}"),
+                    Some("请降低帧率或分辨率，或改用硬件编码".to_string()),
+                )
+                .with_param("fps", format!("{fps:.1}"))
+                .with_param("target", target),
+            );
+        }
+    }
+    let expects_sound = profile.system_audio_enabled
+        || resolve_microphone_device(profile.microphone_device_id.as_deref()).is_some();
+    if expects_sound && levels.is_some_and(|levels| levels.max_db <= SILENT_MAX_VOLUME_DB) {
+        warnings.push(AppError::new(
+            "CAPTURE_TEST_SILENT_AUDIO",
+            "试录音轨没有声音",
+            Some("请确认麦克风未静音，并检查系统的麦克风权限".to_string()),
+        ));
+    }
+    if let Some(message) = degrade_message {
+        warnings.push(
+            AppError::new("CAPTURE_TEST_DEGRADED", message.clone(), None)
+                .with_param("detail", message),
+        );
+    }
+    CaptureTestReport {
+        ready: errors.is_empty(),
+        width: streams.width,
+        height: streams.height,
+        fps: streams.frame_rate,
+        has_audio: streams.has_audio,
+        mean_volume_db: levels.map(|levels| levels.mean_db),
+        max_volume_db: levels.map(|levels| levels.max_db),
+        errors,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_capture_test_report, parse_volumedetect, VolumeLevels};
+    use crate::domain::models::RecordingProfile;
+    use crate::infra::ffmpeg::probe::StreamSummary;
+
+    #[test]
+    fn volumedetect_levels_are_parsed() {
+        let stderr = "\
+[Parsed_volumedetect_0 @ 0x1] n_samples: 288000
+[Parsed_volumedetect_0 @ 0x1] mean_volume: -27.3 dB
+[Parsed_volumedetect_0 @ 0x1] max_volume: -8.1 dB";
+        assert_eq!(
+            parse_volumedetect(stderr),
+            Some(VolumeLevels {
+                mean_db: -27.3,
+                max_db: -8.1
+            })
+        );
+        assert_eq!(parse_volumedetect("size=N/A"), None);
+    }
+
+    #[test]
+    fn report_flags_missing_streams_and_quality_problems() {
+        let mut profile = RecordingProfile {
+            frame_rate: 30,
+            microphone_device_id: Some("USB Mic".to_string()),
+            ..RecordingProfile::default()
+        };
+        let streams = StreamSummary {
+            width: Some(1920),
+            height: Some(1080),
+            frame_rate: Some(20.0),
+            has_audio: true,
+        };
+        let silent = Some(VolumeLevels {
+            mean_db: -91.0,
+            max_db: -91.0,
+        });
+        let report = build_capture_test_report(&profile, None, &streams, silent);
+        assert!(report.ready);
+        let codes = report
+            .warnings
+            .iter()
+            .map(|warning| warning.code.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(codes, ["CAPTURE_TEST_LOW_FPS", "CAPTURE_TEST_SILENT_AUDIO"]);
+
+        profile.microphone_device_id = None;
+        let broken = StreamSummary {
+            width: None,
+            height: None,
+            frame_rate: None,
+            has_audio: false,
+        };
+        let report = build_capture_test_report(&profile, None, &broken, None);
+        assert!(!report.ready);
+        assert_eq!(report.errors.len(), 2);
+        assert!(report.warnings.is_empty());
+    }
+}
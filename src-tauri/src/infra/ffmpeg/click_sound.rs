@@ -0,0 +1,97 @@
+// 间隔过近的点击（双击、连点）只发一声，避免叠成一团噪声。
+const MIN_CLICK_GAP_SEC: f64 = 0.08;
+// 点击过密时只保留前面的部分，避免 amix 输入过多导致 ffmpeg 初始化缓慢。
+const MAX_CLICK_SOUNDS: usize = 400;
+const CLICK_SAMPLE_RATE: u32 = 48_000;
+const CLICK_DURATION_SEC: f64 = 0.05;
+
+/// 把点击时间（源文件时间轴，秒）换算为裁剪后时间轴上的发声时刻（毫秒）。
+pub fn click_sound_offsets_ms(
+    click_times_sec: &[f64],
+    trim_start_ms: u64,
+    trim_end_ms: Option<u64>,
+) -> Vec<u64> {
+    let trim_start_sec = trim_start_ms as f64 / 1000.0;
+    let trim_end_sec = trim_end_ms.map(|end| end as f64 / 1000.0);
+    let mut offsets: Vec<u64> = Vec::new();
+    let mut last_sec: Option<f64> = None;
+    for &t_sec in click_times_sec {
+        if t_sec < trim_start_sec || trim_end_sec.is_some_and(|end| t_sec >= end) {
+            continue;
+        }
+        if last_sec.is_some_and(|last| t_sec - last < MIN_CLICK_GAP_SEC) {
+            continue;
+        }
+        if offsets.len() >= MAX_CLICK_SOUNDS {
+            break;
+        }
+        last_sec = Some(t_sec);
+        offsets.push(((t_sec - trim_start_sec) * 1000.0).round() as u64);
+    }
+    offsets
+}
+
+/// 生成一段衰减正弦作为点击音，按各点击时刻 adelay 后与原音轨 amix。
+/// 滤镜段以主音轨为输入、混音结果为输出，可直接串在 `-af` 链中间。
+pub fn build_click_sound_filter(offsets_ms: &[u64], volume: f32) -> Option<String> {
+    if offsets_ms.is_empty() {
+        return None;
+    }
+    let count = offsets_ms.len();
+    let amplitude = volume.clamp(0.0, 1.0);
+    let sample = format!("{amplitude:.2}*sin(2*PI*2600*t)*exp(-90*t)");
+    let mut chains = vec![
+        "anull[click_main]".to_string(),
+        format!(
+            "aevalsrc='{sample}|{sample}':s={CLICK_SAMPLE_RATE}:d={CLICK_DURATION_SEC},asplit={count}{}",
+            labels("click", count)
+        ),
+    ];
+    for (index, offset) in offsets_ms.iter().enumerate() {
+        chains.push(format!(
+            "[click{index}]adelay={offset}:all=1[click_d{index}]"
+        ));
+    }
+    chains.push(format!(
+        "[click_main]{}amix=inputs={}:duration=first:dropout_transition=0:normalize=0",
+        labels("click_d", count),
+        count + 1
+    ));
+    Some(chains.join(";"))
+}
+
+fn labels(prefix: &str, count: usize) -> String {
+    (0..count)
+        .map(|index| format!("[{prefix}{index}]"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_click_sound_filter, click_sound_offsets_ms};
+
+    #[test]
+    fn click_offsets_follow_trim_and_skip_double_clicks() {
+        let clicks = [0.5, 1.2, 1.25, 2.0, 4.5];
+        assert_eq!(
+            click_sound_offsets_ms(&clicks, 1000, Some(4000)),
+            vec![200, 1000]
+        );
+        assert_eq!(
+            click_sound_offsets_ms(&clicks, 0, None),
+            vec![500, 1200, 2000, 4500]
+        );
+    }
+
+    #[test]
+    fn click_filter_mixes_delayed_samples_into_main_track() {
+        assert_eq!(build_click_sound_filter(&[], 0.6), None);
+        let filter = build_click_sound_filter(&[200, 1000], 0.6).unwrap();
+        assert!(filter.starts_with("anull[click_main];aevalsrc='0.60*sin"));
+        assert!(filter.contains("asplit=2[click0][click1]"));
+        assert!(filter.contains("[click1]adelay=1000:all=1[click_d1]"));
+        assert!(filter.ends_with(
+            "[click_main][click_d0][click_d1]amix=inputs=3:duration=first:dropout_transition=0:normalize=0"
+        ));
+    }
+}
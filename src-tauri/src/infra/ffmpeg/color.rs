@@ -0,0 +1,80 @@
+use crate::domain::models::ColorGrade;
+use std::path::PathBuf;
+
+/// 调色参数与默认值的差异小于该值时视为未调整，不加 eq 滤镜。
+const ADJUST_EPSILON: f32 = 0.005;
+
+/// 已设置且文件存在的 LUT；文件丢失时跳过，不阻断导出。
+pub fn color_lut_path(grade: &ColorGrade) -> Option<PathBuf> {
+    grade
+        .lut_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+}
+
+fn eq_adjusted(grade: &ColorGrade) -> bool {
+    grade.brightness.abs() > ADJUST_EPSILON
+        || (grade.contrast - 1.0).abs() > ADJUST_EPSILON
+        || (grade.saturation - 1.0).abs() > ADJUST_EPSILON
+        || (grade.gamma - 1.0).abs() > ADJUST_EPSILON
+}
+
+/// 调色是否会改变画面；未生效时仍可走流复制。
+pub fn color_grade_active(grade: &ColorGrade) -> bool {
+    eq_adjusted(grade) || color_lut_path(grade).is_some()
+}
+
+/// 先用 eq 校正亮度、对比度等基础参数，再套 LUT 做风格化；`lut` 为已转义的滤镜路径。
+pub fn build_color_filters(grade: &ColorGrade, lut: Option<&str>) -> Vec<String> {
+    let mut filters = Vec::new();
+    if eq_adjusted(grade) {
+        filters.push(format!(
+            "eq=brightness={:.3}:contrast={:.3}:saturation={:.3}:gamma={:.3}",
+            grade.brightness, grade.contrast, grade.saturation, grade.gamma
+        ));
+    }
+    if let Some(lut) = lut {
+        filters.push(format!("lut3d=file='{lut}'"));
+    }
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_color_filters, color_grade_active};
+    use crate::domain::models::ColorGrade;
+
+    #[test]
+    fn default_grade_adds_no_filters() {
+        let grade = ColorGrade::default();
+        assert!(!color_grade_active(&grade));
+        assert!(build_color_filters(&grade, None).is_empty());
+
+        let missing_lut = ColorGrade {
+            lut_path: Some("/missing/film.cube".to_string()),
+            ..ColorGrade::default()
+        };
+        assert!(!color_grade_active(&missing_lut));
+    }
+
+    #[test]
+    fn eq_runs_before_lut() {
+        let grade = ColorGrade {
+            brightness: 0.05,
+            contrast: 1.15,
+            saturation: 1.2,
+            ..ColorGrade::default()
+        };
+        assert!(color_grade_active(&grade));
+        assert_eq!(
+            build_color_filters(&grade, Some("C\\:/luts/film.cube")),
+            [
+                "eq=brightness=0.050:contrast=1.150:saturation=1.200:gamma=1.000",
+                "lut3d=file='C\\:/luts/film.cube'",
+            ]
+        );
+    }
+}
@@ -1,6 +1,22 @@
 use crate::domain::models::AppError;
+use crate::infra::ffmpeg::installer::resolve_binary;
+use serde::Deserialize;
 use std::ffi::OsStr;
-use std::process::{Command, ExitStatus, Stdio};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+// ffprobe、`-formats` 等探测类命令通常秒级返回。
+const PROBE_TIMEOUT_SEC: u64 = 30;
+// 静音/内容分析、缩略图等整段处理命令的总时长上限。
+const FFMPEG_TIMEOUT_SEC: u64 = 2 * 60 * 60;
+// 带进度的编码命令按“进度停滞”判定卡死，长视频导出不受总时长限制。
+const FFMPEG_STALL_TIMEOUT_SEC: u64 = 120;
+const WATCHDOG_POLL: Duration = Duration::from_millis(20);
 
 pub struct CommandOutput {
     pub status: ExitStatus,
@@ -8,21 +24,132 @@ pub struct CommandOutput {
     pub stdout: String,
 }
 
+/// 优先使用 `FOCUSLENS_FFMPEG_PATH`，其次是托管下载或内置 sidecar，最后回退到 PATH。
 pub fn ffmpeg_bin() -> String {
-    std::env::var("FOCUSLENS_FFMPEG_PATH").unwrap_or_else(|_| "ffmpeg".to_string())
+    resolve_binary("ffmpeg", "FOCUSLENS_FFMPEG_PATH").0
+}
+
+/// arnndn 使用的 RNNoise 模型文件，未配置或文件不存在时返回 None。
+pub fn rnnoise_model_path() -> Option<PathBuf> {
+    std::env::var("FOCUSLENS_RNNOISE_MODEL")
+        .ok()
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
 }
 
 pub fn ffprobe_bin() -> String {
-    std::env::var("FOCUSLENS_FFPROBE_PATH").unwrap_or_else(|_| "ffprobe".to_string())
+    resolve_binary("ffprobe", "FOCUSLENS_FFPROBE_PATH").0
 }
 
-pub fn ffmpeg_supports_input_format(format_name: &str) -> bool {
-    let output = Command::new(ffmpeg_bin())
-        .arg("-hide_banner")
-        .arg("-formats")
+/// 探测类命令（ffprobe、`-version`、`-formats`）超时，可用 `FOCUSLENS_PROBE_TIMEOUT_SEC` 覆盖。
+pub fn probe_timeout() -> Duration {
+    timeout_from_env("FOCUSLENS_PROBE_TIMEOUT_SEC", PROBE_TIMEOUT_SEC)
+}
+
+/// 整段处理命令超时，可用 `FOCUSLENS_FFMPEG_TIMEOUT_SEC` 覆盖。
+pub fn ffmpeg_timeout() -> Duration {
+    timeout_from_env("FOCUSLENS_FFMPEG_TIMEOUT_SEC", FFMPEG_TIMEOUT_SEC)
+}
+
+/// 编码进度停滞超时，可用 `FOCUSLENS_FFMPEG_STALL_TIMEOUT_SEC` 覆盖。
+pub fn ffmpeg_stall_timeout() -> Duration {
+    timeout_from_env(
+        "FOCUSLENS_FFMPEG_STALL_TIMEOUT_SEC",
+        FFMPEG_STALL_TIMEOUT_SEC,
+    )
+}
+
+fn timeout_from_env(key: &str, default_sec: u64) -> Duration {
+    parse_timeout_sec(std::env::var(key).ok().as_deref(), default_sec)
+}
+
+/// 非法或为 0 的配置回退到默认值，避免误配成“立即超时”。
+fn parse_timeout_sec(value: Option<&str>, default_sec: u64) -> Duration {
+    let sec = value
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|sec| *sec > 0)
+        .unwrap_or(default_sec);
+    Duration::from_secs(sec)
+}
+
+/// 与 `Command::output` 相同，但超过 `timeout` 仍未退出时终止子进程，
+/// 返回 `ErrorKind::TimedOut`。stdin 置空，stdout/stderr 在独立线程读取以免管道写满阻塞。
+pub fn output_with_timeout(command: &mut Command, timeout: Duration) -> std::io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .output();
+        .spawn()?;
+    let stdout_reader = child.stdout.take().map(spawn_pipe_reader);
+    let stderr_reader = child.stderr.take().map(spawn_pipe_reader);
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("timed out after {}s", timeout.as_secs()),
+            ));
+        }
+        std::thread::sleep(WATCHDOG_POLL);
+    };
+    Ok(Output {
+        status,
+        stdout: join_pipe_reader(stdout_reader),
+        stderr: join_pipe_reader(stderr_reader),
+    })
+}
+
+fn spawn_pipe_reader<R: Read + Send + 'static>(mut pipe: R) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+fn join_pipe_reader(reader: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    reader
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default()
+}
+
+/// 子进程执行失败转为 AppError：超时统一为 `FFMPEG_TIMEOUT`，其余沿用调用方的错误码。
+pub fn command_error(
+    error: std::io::Error,
+    code: &str,
+    context: &str,
+    suggestion: Option<&str>,
+) -> AppError {
+    if error.kind() == std::io::ErrorKind::TimedOut {
+        return timeout_error(format!("{context}: {error}"));
+    }
+    AppError::new(
+        code,
+        format!("{context}: {error}"),
+        suggestion.map(str::to_string),
+    )
+}
+
+fn timeout_error(message: String) -> AppError {
+    AppError::new(
+        "FFMPEG_TIMEOUT",
+        message,
+        Some("ffmpeg 长时间无响应，已终止进程；请检查输入文件，或调大超时配置后重试".to_string()),
+    )
+}
+
+pub fn ffmpeg_supports_input_format(format_name: &str) -> bool {
+    let output = output_with_timeout(
+        Command::new(ffmpeg_bin())
+            .arg("-hide_banner")
+            .arg("-formats"),
+        probe_timeout(),
+    );
     let Ok(output) = output else {
         return false;
     };
@@ -43,18 +170,15 @@ pub fn ffmpeg_supports_input_format(format_name: &str) -> bool {
 }
 
 pub fn ensure_ffmpeg_available() -> Result<(), AppError> {
-    let output = Command::new(ffmpeg_bin())
-        .arg("-version")
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .output()
+    let output = output_with_timeout(Command::new(ffmpeg_bin()).arg("-version"), probe_timeout())
         .map_err(|error| {
-            AppError::new(
-                "FFMPEG_NOT_FOUND",
-                format!("failed to execute ffmpeg: {error}"),
-                Some("请安装 ffmpeg 并加入 PATH，或设置 FOCUSLENS_FFMPEG_PATH".to_string()),
-            )
-        })?;
+        command_error(
+            error,
+            "FFMPEG_NOT_FOUND",
+            "failed to execute ffmpeg",
+            Some("可在设置中一键安装 ffmpeg，或设置 FOCUSLENS_FFMPEG_PATH"),
+        )
+    })?;
     if !output.status.success() {
         return Err(AppError::new(
             "FFMPEG_NOT_FOUND",
@@ -70,16 +194,13 @@ where
     I: IntoIterator<Item = S>,
     S: AsRef<OsStr>,
 {
-    let output = Command::new(ffmpeg_bin())
-        .args(args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
+    let output = output_with_timeout(Command::new(ffmpeg_bin()).args(args), ffmpeg_timeout())
         .map_err(|error| {
-            AppError::new(
+            command_error(
+                error,
                 "FFMPEG_EXEC_ERROR",
-                format!("failed to run ffmpeg: {error}"),
-                Some("确认 ffmpeg 安装状态并检查导出参数".to_string()),
+                "failed to run ffmpeg",
+                Some("确认 ffmpeg 安装状态并检查导出参数"),
             )
         })?;
     Ok(CommandOutput {
@@ -88,3 +209,213 @@ where
         stdout: String::from_utf8_lossy(&output.stdout).to_string(),
     })
 }
+
+/// 以 `-progress pipe:1` 运行 ffmpeg，逐条回调已编码时长（毫秒）。
+/// 调用方需自行在参数中加入 `-progress pipe:1 -nostats`。
+/// `cancel` 被置位后终止 ffmpeg 子进程，返回的退出状态为失败；
+/// 编码进度超过 [`ffmpeg_stall_timeout`] 没有推进时同样终止，并返回 `FFMPEG_TIMEOUT`。
+/// `low_priority` 时以低于正常的调度优先级运行，长时间导出不拖慢前台应用。
+pub fn run_ffmpeg_with_progress<I, S, F>(
+    args: I,
+    cancel: Option<&AtomicBool>,
+    low_priority: bool,
+    mut on_progress: F,
+) -> Result<CommandOutput, AppError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+    F: FnMut(u64),
+{
+    use std::io::{BufRead, BufReader};
+
+    let mut command = Command::new(ffmpeg_bin());
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if low_priority {
+        set_below_normal_priority(&mut command);
+    }
+    let mut child = command.spawn().map_err(|error| {
+        AppError::new(
+            "FFMPEG_EXEC_ERROR",
+            format!("failed to run ffmpeg: {error}"),
+            Some("确认 ffmpeg 安装状态并检查导出参数".to_string()),
+        )
+    })?;
+    if low_priority {
+        renice_spawned(child.id());
+    }
+    // stderr 单独线程读取，避免管道写满后 ffmpeg 阻塞。
+    let stderr_reader = child.stderr.take().map(spawn_pipe_reader);
+    // stdout 按行转发到本线程，读取阻塞时仍能定期检查取消与停滞。
+    let (sender, receiver) = mpsc::channel();
+    if let Some(pipe) = child.stdout.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    let stall_timeout = ffmpeg_stall_timeout();
+    let mut last_advance = Instant::now();
+    let mut last_out_time_ms = None;
+    let mut stalled = false;
+    let mut stdout = String::new();
+    loop {
+        if cancel.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+            let _ = child.kill();
+            break;
+        }
+        match receiver.recv_timeout(WATCHDOG_POLL) {
+            Ok(line) => {
+                if let Some(out_time_ms) = parse_progress_out_time_ms(&line) {
+                    if last_out_time_ms != Some(out_time_ms) {
+                        last_out_time_ms = Some(out_time_ms);
+                        last_advance = Instant::now();
+                    }
+                    on_progress(out_time_ms);
+                }
+                stdout.push_str(&line);
+                stdout.push('\n');
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if last_advance.elapsed() >= stall_timeout {
+            stalled = true;
+            let _ = child.kill();
+            break;
+        }
+    }
+    let status = child.wait().map_err(|error| {
+        AppError::new(
+            "FFMPEG_EXEC_ERROR",
+            format!("failed to wait ffmpeg: {error}"),
+            None,
+        )
+    })?;
+    if stalled {
+        return Err(timeout_error(format!(
+            "ffmpeg stalled: no progress for {}s",
+            stall_timeout.as_secs()
+        )));
+    }
+    let stderr = String::from_utf8_lossy(&join_pipe_reader(stderr_reader)).to_string();
+    Ok(CommandOutput {
+        status,
+        stderr,
+        stdout,
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn set_below_normal_priority(command: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    use windows_sys::Win32::System::Threading::BELOW_NORMAL_PRIORITY_CLASS;
+    command.creation_flags(BELOW_NORMAL_PRIORITY_CLASS);
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_below_normal_priority(_command: &mut Command) {}
+
+#[cfg(target_os = "windows")]
+fn renice_spawned(_pid: u32) {}
+
+/// 没有 libc 依赖，借助系统自带的 renice 调低已启动进程的 nice 值；失败时按正常优先级继续导出。
+#[cfg(not(target_os = "windows"))]
+fn renice_spawned(pid: u32) {
+    let result = output_with_timeout(
+        Command::new("renice")
+            .args(["-n", "10", "-p"])
+            .arg(pid.to_string()),
+        probe_timeout(),
+    );
+    if !result.is_ok_and(|output| output.status.success()) {
+        tracing::warn!("failed to lower ffmpeg priority for pid {pid}");
+    }
+}
+
+/// ffmpeg 的 `out_time_ms` 实际单位是微秒（与 `out_time_us` 相同），这里统一换算为毫秒。
+pub fn parse_progress_out_time_ms(line: &str) -> Option<u64> {
+    let (key, value) = line.trim().split_once('=')?;
+    if key != "out_time_us" && key != "out_time_ms" {
+        return None;
+    }
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .map(|us| us.max(0) as u64 / 1000)
+}
+
+#[derive(Debug, Deserialize)]
+struct InputSizeStream {
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InputSizeProbe {
+    streams: Vec<InputSizeStream>,
+}
+
+pub fn probe_input_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let output = output_with_timeout(
+        Command::new(ffprobe_bin())
+            .arg("-v")
+            .arg("error")
+            .arg("-select_streams")
+            .arg("v:0")
+            .arg("-show_entries")
+            .arg("stream=width,height")
+            .arg("-of")
+            .arg("json")
+            .arg(path),
+        probe_timeout(),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let parsed: InputSizeProbe = serde_json::from_slice(&output.stdout).ok()?;
+    let stream = parsed.streams.first()?;
+    let (Some(width), Some(height)) = (stream.width, stream.height) else {
+        return None;
+    };
+    if width == 0 || height == 0 {
+        None
+    } else {
+        Some((width, height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_progress_out_time_ms, parse_timeout_sec};
+    use std::time::Duration;
+
+    #[test]
+    fn progress_lines_are_converted_to_ms() {
+        assert_eq!(
+            parse_progress_out_time_ms("out_time_us=1500000"),
+            Some(1500)
+        );
+        assert_eq!(parse_progress_out_time_ms("out_time_ms=250000"), Some(250));
+        assert_eq!(parse_progress_out_time_ms("out_time_us=N/A"), None);
+        assert_eq!(parse_progress_out_time_ms("out_time_us=-20"), Some(0));
+        assert_eq!(parse_progress_out_time_ms("frame=12"), None);
+    }
+
+    #[test]
+    fn invalid_or_zero_timeouts_fall_back_to_default() {
+        assert_eq!(parse_timeout_sec(Some("45"), 30), Duration::from_secs(45));
+        assert_eq!(parse_timeout_sec(Some(" 5 "), 30), Duration::from_secs(5));
+        assert_eq!(parse_timeout_sec(Some("0"), 30), Duration::from_secs(30));
+        assert_eq!(parse_timeout_sec(Some("abc"), 30), Duration::from_secs(30));
+        assert_eq!(parse_timeout_sec(None, 30), Duration::from_secs(30));
+    }
+}
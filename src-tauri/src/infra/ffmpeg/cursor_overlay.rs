@@ -0,0 +1,275 @@
+use crate::domain::models::CursorStyle;
+use crate::infra::ffmpeg::export::{escape_filter_path, sample_camera_path};
+use std::path::Path;
+
+pub const CURSOR_OVERLAY_NAME: &str = "cursor";
+pub const SPOTLIGHT_OVERLAY_NAME: &str = "spotlight";
+// 光标不可见（移到未录制的显示器）时移出画面，而不是删除 overlay。
+const CURSOR_HIDDEN_OFFSET: i64 = -10_000;
+
+/// 裁剪窗口占源画面的比例，与 crop 滤镜的宽高表达式一致。
+#[derive(Debug, Clone, Copy)]
+pub struct CropWindow {
+    pub width_ratio: f64,
+    pub height_ratio: f64,
+}
+
+impl CropWindow {
+    pub fn new(source_ar: f64, content_ar: f64, zoom: f64) -> Self {
+        let zoom = zoom.max(1.0);
+        if source_ar > content_ar {
+            Self {
+                width_ratio: (content_ar / source_ar) / zoom,
+                height_ratio: 1.0 / zoom,
+            }
+        } else {
+            Self {
+                width_ratio: 1.0 / zoom,
+                height_ratio: (source_ar / content_ar) / zoom,
+            }
+        }
+    }
+
+    /// 源画面归一化坐标换算到裁剪窗口归一化坐标；crop 会把越界窗口夹回画面内。
+    fn project(&self, center: (f64, f64), point: (f64, f64)) -> (f64, f64) {
+        let left = (center.0 - self.width_ratio / 2.0).clamp(0.0, 1.0 - self.width_ratio);
+        let top = (center.1 - self.height_ratio / 2.0).clamp(0.0, 1.0 - self.height_ratio);
+        (
+            (point.0 - left) / self.width_ratio,
+            (point.1 - top) / self.height_ratio,
+        )
+    }
+}
+
+pub struct CursorSample {
+    pub t_sec: f64,
+    pub x: f64,
+    pub y: f64,
+    pub visible: bool,
+}
+
+pub struct CursorKeyframes {
+    pub first: Option<(i64, i64)>,
+    pub commands: String,
+}
+
+/// 光标从源画面到内容画面（裁剪并缩放后）的映射。
+pub struct CursorProjection {
+    pub window: CropWindow,
+    pub content: (u32, u32),
+    pub hotspot: (f64, f64),
+    /// 接收位置命令的 overlay 实例名。
+    pub overlay: &'static str,
+}
+
+/// 逐帧计算跟随光标的图层在内容画面中的左上角位置并生成 sendcmd 脚本。
+/// 样本坐标为源画面归一化坐标；`camera` 为镜头中心路径，None 表示居中静止。
+pub fn build_cursor_keyframes(
+    samples: &[CursorSample],
+    camera: Option<&[(f64, f64, f64)]>,
+    projection: &CursorProjection,
+    trim_start_sec: f64,
+    trim_end_sec: Option<f64>,
+    fps: u8,
+) -> CursorKeyframes {
+    let CursorProjection {
+        window,
+        content,
+        hotspot,
+        overlay,
+    } = projection;
+    let mut keyframes = CursorKeyframes {
+        first: None,
+        commands: String::new(),
+    };
+    let Some(last) = samples.last() else {
+        return keyframes;
+    };
+    let path = samples
+        .iter()
+        .map(|sample| (sample.t_sec, sample.x, sample.y))
+        .collect::<Vec<_>>();
+    let end_sec = trim_end_sec.unwrap_or(last.t_sec).min(last.t_sec);
+    let frame_sec = 1.0 / f64::from(fps.max(1));
+    let mut previous: Option<(i64, i64)> = None;
+    let mut frame = 0u64;
+    loop {
+        let t_out = frame as f64 * frame_sec;
+        let t_src = trim_start_sec + t_out;
+        if t_src > end_sec && frame > 0 {
+            break;
+        }
+        let index = samples.partition_point(|sample| sample.t_sec <= t_src);
+        let visible = samples[index.saturating_sub(1)].visible;
+        let position = if visible {
+            let center = camera
+                .map(|camera| sample_camera_path(camera, t_src))
+                .unwrap_or((0.5, 0.5));
+            let (u, v) = window.project(center, sample_camera_path(&path, t_src));
+            (
+                (u * content.0 as f64 - hotspot.0).round() as i64,
+                (v * content.1 as f64 - hotspot.1).round() as i64,
+            )
+        } else {
+            (CURSOR_HIDDEN_OFFSET, CURSOR_HIDDEN_OFFSET)
+        };
+        if previous != Some(position) {
+            keyframes.commands.push_str(&format!(
+                "{t_out:.4} overlay@{overlay} x {}, overlay@{overlay} y {};\n",
+                position.0, position.1
+            ));
+            previous = Some(position);
+            keyframes.first.get_or_insert(position);
+        }
+        frame += 1;
+    }
+    keyframes
+}
+
+/// 光标热点相对图层左上角的位置：箭头在尖端，光晕在圆心。
+pub fn cursor_hotspot(style: CursorStyle, size: u32) -> (f64, f64) {
+    match style {
+        CursorStyle::Arrow => (1.0, 1.0),
+        CursorStyle::Halo => (size as f64 / 2.0, size as f64 / 2.0),
+    }
+}
+
+/// 在内容画面上叠加合成光标：光标图层用 geq 生成一帧，overlay 默认重复最后一帧。
+pub fn build_cursor_overlay_filter(
+    style: CursorStyle,
+    size: u32,
+    keyframes_path: &Path,
+    first: (i64, i64),
+) -> String {
+    let file = escape_filter_path(keyframes_path);
+    format!(
+        "sendcmd=f='{file}'[cursor_base];{}[cursor_img];[cursor_base][cursor_img]overlay@{CURSOR_OVERLAY_NAME}=x={}:y={}",
+        cursor_image_source(style, size),
+        first.0,
+        first.1
+    )
+}
+
+fn cursor_image_source(style: CursorStyle, size: u32) -> String {
+    let base = format!("color=c=black@0:s={size}x{size}:r=1:d=1,format=rgba");
+    match style {
+        CursorStyle::Arrow => {
+            let border = (size as f64 / 16.0).max(1.0);
+            let outer = arrow_region(size, 0.0);
+            let inner = arrow_region(size, border);
+            format!("{base},geq=r='255*{inner}':g='255*{inner}':b='255*{inner}':a='255*{outer}'")
+        }
+        CursorStyle::Halo => {
+            let center = size as f64 / 2.0;
+            format!(
+                "{base},geq=r='255':g='212':b='0':a='110*lte(hypot(X+0.5-{center:.1},Y+0.5-{center:.1}),{center:.1})'"
+            )
+        }
+    }
+}
+
+/// 聚光灯遮罩为内容画面两倍大小，圆心位于遮罩中心，任意光标位置都能盖满整个画面。
+pub fn spotlight_hotspot(content: (u32, u32)) -> (f64, f64) {
+    (content.0 as f64, content.1 as f64)
+}
+
+/// 压暗圆外画面：遮罩用 geq 生成一帧，圆内透明，羽化带内线性过渡到 `dim` 不透明度。
+pub fn build_spotlight_filter(
+    content: (u32, u32),
+    radius: f64,
+    feather: f64,
+    dim: f64,
+    keyframes_path: &Path,
+    first: (i64, i64),
+) -> String {
+    let file = escape_filter_path(keyframes_path);
+    let (w, h) = (content.0 * 2, content.1 * 2);
+    let (cx, cy) = spotlight_hotspot(content);
+    let alpha = (dim.clamp(0.0, 1.0) * 255.0).round();
+    let feather = feather.max(1.0);
+    format!(
+        "sendcmd=f='{file}'[spot_base];color=c=black@0:s={w}x{h}:r=1:d=1,format=rgba,\
+geq=r='0':g='0':b='0':a='{alpha}*clip((hypot(X+0.5-{cx:.1},Y+0.5-{cy:.1})-{radius:.1})/{feather:.1},0,1)'[spot_mask];\
+[spot_base][spot_mask]overlay@{SPOTLIGHT_OVERLAY_NAME}=x={}:y={}",
+        first.0,
+        first.1
+    )
+}
+
+/// 箭头三角形（尖端在左上角）向内收缩 `margin` 像素后的区域表达式。
+fn arrow_region(size: u32, margin: f64) -> String {
+    let bottom = size as f64 - 1.0 - margin * 1.09;
+    let diagonal = margin * 1.414;
+    format!("gte(X,{margin:.2})*gte(Y-X,{diagonal:.2})*lte(Y+0.4286*X,{bottom:.2})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_cursor_keyframes, build_spotlight_filter, spotlight_hotspot, CropWindow,
+        CursorProjection, CursorSample, CURSOR_OVERLAY_NAME, SPOTLIGHT_OVERLAY_NAME,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn cursor_keyframes_follow_crop_window_and_hide_offscreen() {
+        let samples = vec![
+            CursorSample {
+                t_sec: 0.0,
+                x: 0.5,
+                y: 0.5,
+                visible: true,
+            },
+            CursorSample {
+                t_sec: 0.1,
+                x: 0.5,
+                y: 0.5,
+                visible: false,
+            },
+        ];
+        // 2 倍放大、镜头居中：源画面中心对应内容画面中心。
+        let projection = CursorProjection {
+            window: CropWindow::new(16.0 / 9.0, 16.0 / 9.0, 2.0),
+            content: (1920, 1080),
+            hotspot: (18.0, 18.0),
+            overlay: CURSOR_OVERLAY_NAME,
+        };
+        let keyframes = build_cursor_keyframes(&samples, None, &projection, 0.0, None, 10);
+        assert_eq!(keyframes.first, Some((942, 522)));
+        assert!(keyframes
+            .commands
+            .contains("0.1000 overlay@cursor x -10000, overlay@cursor y -10000;"));
+    }
+
+    #[test]
+    fn spotlight_mask_is_centered_on_cursor() {
+        let samples = vec![CursorSample {
+            t_sec: 0.0,
+            x: 0.25,
+            y: 0.5,
+            visible: true,
+        }];
+        let projection = CursorProjection {
+            window: CropWindow::new(16.0 / 9.0, 16.0 / 9.0, 1.0),
+            content: (1280, 720),
+            hotspot: spotlight_hotspot((1280, 720)),
+            overlay: SPOTLIGHT_OVERLAY_NAME,
+        };
+        let keyframes = build_cursor_keyframes(&samples, None, &projection, 0.0, None, 10);
+        // 遮罩中心 (1280, 720) 对齐光标 (320, 360)。
+        assert_eq!(keyframes.first, Some((-960, -360)));
+        assert!(keyframes.commands.contains("overlay@spotlight x -960"));
+
+        let filter = build_spotlight_filter(
+            (1280, 720),
+            120.0,
+            40.0,
+            0.5,
+            Path::new("/tmp/spot.cmd"),
+            (-960, -360),
+        );
+        assert!(filter.contains("s=2560x1440"));
+        assert!(filter.contains("a='128*clip((hypot(X+0.5-1280.0,Y+0.5-720.0)-120.0)/40.0,0,1)'"));
+        assert!(filter.ends_with("overlay@spotlight=x=-960:y=-360"));
+    }
+}
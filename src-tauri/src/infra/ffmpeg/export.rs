@@ -1,10 +1,45 @@
+use crate::core::capture::input::KeyEvent;
+use crate::core::motion::easing::ease;
+use crate::core::motion::smoothing::{spring_step, SpringConfig, SpringState};
+use crate::core::timeline::idle::detect_idle_ranges;
+use crate::core::timeline::service::segment_gaps;
 use crate::domain::models::{
-    AppError, AspectRatio, CameraIntensity, ExportProfile, ProjectManifest, Resolution,
+    AnimationExportOptions, AppError, AppFocusSegment, AspectRatio, AudioDenoise, CameraEasing,
+    CameraFocusMode, CameraIntensity, CameraMode, CameraMotionModel, CameraMotionProfile,
+    ExportProfile, FitMode, OverlayPosition, ProjectManifest, Resolution, SilenceCutMode,
+    TimecodeMode, VideoDenoise,
 };
-use crate::infra::ffmpeg::command::{ffprobe_bin, run_ffmpeg, CommandOutput};
-use serde::Deserialize;
-use std::path::Path;
-use std::process::{Command, Stdio};
+use crate::infra::ffmpeg::animation::{animation_codec_args, build_animation_filter};
+use crate::infra::ffmpeg::annotation::{
+    annotation_font_path, build_annotation_filters, build_shape_script,
+};
+use crate::infra::ffmpeg::audio_mix::{
+    build_music_filter, build_mute_filter, build_volume_filter, recording_track_gain,
+};
+use crate::infra::ffmpeg::blur::build_blur_filters;
+use crate::infra::ffmpeg::capabilities::{detect_hardware_encoder, SOFTWARE_CODEC};
+use crate::infra::ffmpeg::click_sound::{build_click_sound_filter, click_sound_offsets_ms};
+use crate::infra::ffmpeg::color::{build_color_filters, color_grade_active, color_lut_path};
+use crate::infra::ffmpeg::command::{
+    probe_input_dimensions, rnnoise_model_path, run_ffmpeg, run_ffmpeg_with_progress, CommandOutput,
+};
+use crate::infra::ffmpeg::cursor_overlay::{
+    build_cursor_keyframes, build_cursor_overlay_filter, build_spotlight_filter, cursor_hotspot,
+    spotlight_hotspot, CropWindow, CursorProjection, CursorSample, CURSOR_OVERLAY_NAME,
+    SPOTLIGHT_OVERLAY_NAME,
+};
+use crate::infra::ffmpeg::keystroke_overlay::{
+    build_keystroke_filters, keystroke_spans, overlay_font_path,
+};
+use crate::infra::ffmpeg::probe::{probe_media, probe_streams, StreamSummary};
+use crate::infra::ffmpeg::scene::{build_scene_filter, scene_padding};
+use crate::infra::ffmpeg::time_remap::{
+    build_audio_remap, build_speed_indicator_filters, build_video_remap, plan_remap,
+    remapped_duration_sec, RemapCut, RemapSegment,
+};
+use crate::infra::ffmpeg::timecode::build_timecode_filter;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub struct ExportAttemptResult {
     pub success: bool,
@@ -13,25 +48,88 @@ pub struct ExportAttemptResult {
     pub output_path: String,
 }
 
-pub fn export_with_fallback(
+/// 导出编码进度回调：参数为当前尝试的编码器与按裁剪后时长折算的百分比。
+/// `cancel` 置位后终止编码并返回 `EXPORT_CANCELLED`，不再尝试软件编码回退。
+/// `threads` 为编码线程上限，None 时由 ffmpeg 按核心数决定。
+pub fn export_with_fallback<F>(
     manifest: &ProjectManifest,
     input_path: &Path,
     output_path: &Path,
     profile: &ExportProfile,
-) -> Result<ExportAttemptResult, AppError> {
-    let primary_codec = hardware_codec();
-    let mut first = run_export_once(manifest, input_path, output_path, profile, primary_codec)?;
+    threads: Option<usize>,
+    cancel: &AtomicBool,
+    mut on_progress: F,
+) -> Result<ExportAttemptResult, AppError>
+where
+    F: FnMut(&str, u8),
+{
+    ensure_not_cancelled(cancel)?;
+    let primary_codec = detect_hardware_encoder().codec;
+    let duration_ms = export_duration_ms(manifest, input_path);
+    let remap = export_remap_segments(manifest, duration_ms);
+    let mut tracker = EncodeTracker {
+        duration_ms: if remap.is_empty() {
+            duration_ms
+        } else {
+            (remapped_duration_sec(&remap) * 1000.0).round() as u64
+        },
+        remap,
+        cancel,
+        low_priority: profile.low_priority,
+        threads,
+        on_progress: &mut on_progress,
+    };
+    if tracker.remap.is_empty()
+        && lossless_trim_eligible(manifest, profile, probe_streams(input_path).ok().as_ref())
+    {
+        let copied = run_stream_copy(manifest, input_path, output_path, &mut tracker)?;
+        if copied.status.success() {
+            return Ok(ExportAttemptResult {
+                success: true,
+                used_codec: STREAM_COPY_CODEC.to_string(),
+                stderr: copied.stderr,
+                output_path: output_path.to_string_lossy().to_string(),
+            });
+        }
+        // 流复制失败（如源文件封装异常）时不报错，继续走常规编码。
+        ensure_not_cancelled(cancel)?;
+    }
+    let mut first = run_export_once(
+        manifest,
+        input_path,
+        output_path,
+        profile,
+        &primary_codec,
+        &mut tracker,
+    )?;
     if first.status.success() {
         return Ok(ExportAttemptResult {
             success: true,
-            used_codec: primary_codec.to_string(),
+            used_codec: primary_codec,
+            stderr: first.stderr,
+            output_path: output_path.to_string_lossy().to_string(),
+        });
+    }
+    ensure_not_cancelled(cancel)?;
+    // 未检测到硬件编码器时首次尝试已是软件编码，无需重复。
+    if primary_codec == SOFTWARE_CODEC {
+        return Ok(ExportAttemptResult {
+            success: false,
+            used_codec: primary_codec,
             stderr: first.stderr,
             output_path: output_path.to_string_lossy().to_string(),
         });
     }
 
-    let fallback_codec = "libx264";
-    let second = run_export_once(manifest, input_path, output_path, profile, fallback_codec)?;
+    let fallback_codec = SOFTWARE_CODEC;
+    let second = run_export_once(
+        manifest,
+        input_path,
+        output_path,
+        profile,
+        fallback_codec,
+        &mut tracker,
+    )?;
     if second.status.success() {
         let mut stderr = first.stderr;
         if !stderr.is_empty() {
@@ -46,6 +144,7 @@ pub fn export_with_fallback(
         });
     }
 
+    ensure_not_cancelled(cancel)?;
     first.stderr.push_str("\n---- fallback ----\n");
     first.stderr.push_str(&second.stderr);
     Ok(ExportAttemptResult {
@@ -56,13 +155,140 @@ pub fn export_with_fallback(
     })
 }
 
-fn run_export_once(
+fn ensure_not_cancelled(cancel: &AtomicBool) -> Result<(), AppError> {
+    if cancel.load(Ordering::SeqCst) {
+        return Err(AppError::new(
+            "EXPORT_CANCELLED",
+            "export cancelled by user",
+            None,
+        ));
+    }
+    Ok(())
+}
+
+/// 单次编码尝试共享的上下文：成片时长、时间重映射片段与进度回调。
+struct EncodeTracker<'a, F> {
+    duration_ms: u64,
+    remap: Vec<RemapSegment>,
+    cancel: &'a AtomicBool,
+    low_priority: bool,
+    threads: Option<usize>,
+    on_progress: &'a mut F,
+}
+
+// 静音段两端各保留一小段，避免剪掉语句的起音和尾音。
+const SILENCE_PADDING_SEC: f64 = 0.2;
+const SILENCE_SPEEDUP: f64 = 4.0;
+
+// 光标位移小于该值（源画面像素）视为手抖，不算操作。
+const IDLE_MOVE_EPSILON_PX: f64 = 3.0;
+
+/// 汇总需要删除或加速的区间并展开为导出时间轴上的片段；未启用时为空。
+fn export_remap_segments(manifest: &ProjectManifest, duration_ms: u64) -> Vec<RemapSegment> {
+    let timeline = &manifest.timeline;
+    let has_segments = !timeline.segments.is_empty();
+    if duration_ms == 0
+        || !(timeline.auto_cut_silence || timeline.idle_speedup_enabled || has_segments)
+    {
+        return Vec::new();
+    }
+    let trim_start_sec = timeline.trim_start_ms as f64 / 1000.0;
+    let mut cuts = Vec::new();
+    if has_segments {
+        let gaps = segment_gaps(
+            &timeline.segments,
+            timeline.trim_start_ms,
+            timeline.trim_start_ms + duration_ms,
+        );
+        cuts.extend(gaps.iter().map(|gap| RemapCut {
+            start_sec: gap.start_ms as f64 / 1000.0 - trim_start_sec,
+            end_sec: gap.end_ms as f64 / 1000.0 - trim_start_sec,
+            speed: None,
+        }));
+        // 片段倍速排在静音、空闲加速之前，起点相同时以片段设置为准。
+        cuts.extend(
+            timeline
+                .segments
+                .iter()
+                .filter(|segment| (segment.speed - 1.0).abs() > f32::EPSILON)
+                .map(|segment| RemapCut {
+                    start_sec: segment.start_ms as f64 / 1000.0 - trim_start_sec,
+                    end_sec: segment.end_ms as f64 / 1000.0 - trim_start_sec,
+                    speed: Some(f64::from(segment.speed)),
+                }),
+        );
+    }
+    if timeline.auto_cut_silence {
+        let speed = match timeline.silence_cut_mode {
+            SilenceCutMode::Drop => None,
+            SilenceCutMode::SpeedUp => Some(SILENCE_SPEEDUP),
+        };
+        cuts.extend(timeline.silent_ranges.iter().map(|range| RemapCut {
+            start_sec: range.start_ms as f64 / 1000.0 - trim_start_sec + SILENCE_PADDING_SEC,
+            end_sec: range.end_ms as f64 / 1000.0 - trim_start_sec - SILENCE_PADDING_SEC,
+            speed,
+        }));
+    }
+    if timeline.idle_speedup_enabled {
+        let idle = detect_idle_ranges(
+            &activity_marks(manifest),
+            timeline.trim_start_ms + duration_ms,
+            timeline.idle_min_ms,
+        );
+        let speed = f64::from(timeline.idle_speed.max(1.0));
+        cuts.extend(idle.iter().map(|range| RemapCut {
+            start_sec: range.start_ms as f64 / 1000.0 - trim_start_sec,
+            end_sec: range.end_ms as f64 / 1000.0 - trim_start_sec,
+            speed: Some(speed),
+        }));
+    }
+    plan_remap(duration_ms as f64 / 1000.0, &cuts)
+}
+
+/// 操作时间点（源文件时间轴，毫秒）：光标移动、点击、滚动与按键。
+fn activity_marks(manifest: &ProjectManifest) -> Vec<u64> {
+    let points = load_cursor_track(manifest);
+    let cursor_marks = points.windows(2).filter_map(|pair| {
+        let moved = (pair[1].x - pair[0].x).hypot(pair[1].y - pair[0].y) > IDLE_MOVE_EPSILON_PX;
+        let active = moved || pair[1].click || pair[1].scroll_dy.abs() > f64::EPSILON;
+        active.then(|| (pair[1].t_sec * 1000.0).round() as u64)
+    });
+    cursor_marks
+        .chain(load_key_events(manifest).iter().map(|event| event.t_ms))
+        .collect()
+}
+
+/// 成片时长（毫秒，重映射前）与重映射片段，与编码时使用的计划一致。
+pub fn export_remap_plan(
+    manifest: &ProjectManifest,
+    input_path: &Path,
+) -> (u64, Vec<RemapSegment>) {
+    let duration_ms = export_duration_ms(manifest, input_path);
+    (duration_ms, export_remap_segments(manifest, duration_ms))
+}
+
+/// 成片时长：优先使用裁剪区间，未设置裁剪终点时探测源文件时长。
+fn export_duration_ms(manifest: &ProjectManifest, input_path: &Path) -> u64 {
+    let trim_start_ms = manifest.timeline.trim_start_ms;
+    if manifest.timeline.trim_end_ms > trim_start_ms {
+        return manifest.timeline.trim_end_ms - trim_start_ms;
+    }
+    probe_media(input_path)
+        .map(|summary| summary.container_duration_ms.saturating_sub(trim_start_ms))
+        .unwrap_or(0)
+}
+
+fn run_export_once<F>(
     manifest: &ProjectManifest,
     input_path: &Path,
     output_path: &Path,
     profile: &ExportProfile,
     codec: &str,
-) -> Result<CommandOutput, AppError> {
+    tracker: &mut EncodeTracker<'_, F>,
+) -> Result<CommandOutput, AppError>
+where
+    F: FnMut(&str, u8),
+{
     let (target_w, target_h) = output_resolution(
         profile.resolution.clone(),
         manifest.timeline.aspect_ratio.clone(),
@@ -70,9 +296,12 @@ fn run_export_once(
     let mut args: Vec<String> = vec![
         "-y".to_string(),
         "-hide_banner".to_string(),
+        "-benchmark".to_string(),
         "-loglevel".to_string(),
         "info".to_string(),
         "-stats".to_string(),
+        "-progress".to_string(),
+        "pipe:1".to_string(),
     ];
 
     if manifest.timeline.trim_start_ms > 0 {
@@ -92,15 +321,69 @@ fn run_export_once(
 
     args.push("-i".to_string());
     args.push(input_path.to_string_lossy().to_string());
+    let click_sound = build_click_sound(manifest, profile);
+    let music_path = background_music_path(manifest);
+    // 无音轨的录制补一条静音轨，点击音与背景音乐才有可混入的主音轨。
+    if (click_sound.is_some() || music_path.is_some())
+        && probe_media(input_path).is_ok_and(|summary| summary.audio_duration_ms.is_none())
+    {
+        args.push("-f".to_string());
+        args.push("lavfi".to_string());
+        args.push("-i".to_string());
+        args.push("anullsrc=r=48000:cl=stereo".to_string());
+        args.push("-shortest".to_string());
+    }
 
-    let vf = build_video_filters(manifest, profile, input_path);
+    let mut vf = build_video_filters(
+        manifest,
+        profile,
+        input_path,
+        &camera_keyframes_path(input_path),
+    );
+    let mut audio_filters = Vec::new();
+    audio_filters.extend(build_audio_filters(
+        profile.audio_denoise,
+        rnnoise_model_path().as_deref(),
+    ));
+    let mix = &manifest.audio_mix;
+    audio_filters.extend(build_volume_filter(recording_gain(manifest)));
+    let timeline = &manifest.timeline;
+    audio_filters.extend(build_mute_filter(
+        &timeline.mute_ranges,
+        timeline.trim_start_ms,
+        (timeline.trim_end_ms > timeline.trim_start_ms).then_some(timeline.trim_end_ms),
+    ));
+    audio_filters.extend(click_sound);
+    // 重映射放在滤镜链末尾，前面按时间生效的叠加层仍使用连续的裁剪后时间轴。
+    if !tracker.remap.is_empty() {
+        let font = overlay_font_path().map(|path| escape_filter_path(&path));
+        for indicator in
+            build_speed_indicator_filters(&tracker.remap, font.as_deref(), target_w, target_h)
+        {
+            vf.push(',');
+            vf.push_str(&indicator);
+        }
+        vf.push(',');
+        vf.push_str(&build_video_remap(&tracker.remap));
+        audio_filters.push(build_audio_remap(&tracker.remap));
+    }
+    // 背景音乐与总音量作用于重映射之后的成片时间轴，音乐不会被剪断或变速。
+    if let Some(music_path) = &music_path {
+        audio_filters.push(build_music_filter(music_path, mix.music_volume));
+    }
+    audio_filters.extend(build_volume_filter(mix.master_volume));
     args.push("-vf".to_string());
     args.push(vf);
+    if !audio_filters.is_empty() {
+        args.push("-af".to_string());
+        args.push(audio_filters.join(","));
+    }
 
     args.push("-r".to_string());
     args.push(profile.fps.to_string());
     args.push("-c:v".to_string());
     args.push(codec.to_string());
+    args.extend(thread_args(tracker.threads));
     args.push("-b:v".to_string());
     args.push(format!("{}M", profile.bitrate_mbps));
     args.push("-pix_fmt".to_string());
@@ -116,8 +399,459 @@ fn run_export_once(
     args.push("-aspect".to_string());
     args.push(format!("{target_w}:{target_h}"));
     args.push(output_path.to_string_lossy().to_string());
+    run_tracked(args, codec, tracker)
+}
+
+/// 单个导出的编码线程上限：设置中的 `encoder_threads`（0 为不限）与后台导出的一半核心取较小者。
+pub fn resolve_encoder_threads(configured: u16, low_priority: bool) -> Option<usize> {
+    let cores = std::thread::available_parallelism().map_or(2, |cores| cores.get());
+    encoder_thread_limit(configured, low_priority, cores)
+}
+
+fn encoder_thread_limit(configured: u16, low_priority: bool, cores: usize) -> Option<usize> {
+    let configured = (configured > 0).then_some(usize::from(configured));
+    // 后台导出把编码线程限制在一半核心，给前台应用留出算力。
+    let background = low_priority.then_some((cores / 2).max(1));
+    match (configured, background) {
+        (Some(configured), Some(background)) => Some(configured.min(background)),
+        (configured, background) => configured.or(background),
+    }
+}
+
+fn thread_args(threads: Option<usize>) -> Vec<String> {
+    threads
+        .map(|threads| vec!["-threads".to_string(), threads.to_string()])
+        .unwrap_or_default()
+}
+
+const STREAM_COPY_CODEC: &str = "copy";
+
+/// 只裁剪首尾、不需要任何画面或音频处理时可直接流复制，跳过重新编码。
+/// 流复制保留源的分辨率、帧率与码率，因此源须与目标分辨率、帧率完全一致，
+/// 且未设置体积上限；起点对齐到最近的关键帧。
+fn lossless_trim_eligible(
+    manifest: &ProjectManifest,
+    profile: &ExportProfile,
+    source: Option<&StreamSummary>,
+) -> bool {
+    let timeline = &manifest.timeline;
+    let needs_processing = manifest.camera_motion.enabled
+        || timeline.cursor_highlight_enabled
+        || timeline.spotlight.enabled
+        || timeline.scroll_indicator_enabled
+        || timeline.auto_cut_silence
+        || timeline.idle_speedup_enabled
+        || !timeline.blur_regions.is_empty()
+        || !timeline.mute_ranges.is_empty()
+        || !timeline.annotations.is_empty()
+        || !timeline.shapes.is_empty()
+        || manifest.scene.enabled
+        || manifest.keystroke_overlay.enabled
+        || color_grade_active(&manifest.color_grade)
+        || profile.audio_denoise != AudioDenoise::Off
+        || profile.video_denoise != VideoDenoise::Off
+        || profile.frame_interpolation
+        || profile.click_sound_enabled
+        || audio_mix_active(manifest)
+        || subtitle_path(profile).is_some()
+        || profile.timecode != TimecodeMode::Off
+        || !stitch_clip_paths(profile).is_empty()
+        || profile.max_size_mb.is_some();
+    if needs_processing {
+        return false;
+    }
+    let Some(StreamSummary {
+        width: Some(source_w),
+        height: Some(source_h),
+        frame_rate: Some(source_fps),
+        ..
+    }) = source
+    else {
+        return false;
+    };
+    let (target_w, target_h) =
+        output_resolution(profile.resolution.clone(), timeline.aspect_ratio.clone());
+    (*source_w, *source_h) == (target_w, target_h)
+        && (source_fps - f32::from(profile.fps)).abs() < 0.01
+}
+
+fn run_stream_copy<F>(
+    manifest: &ProjectManifest,
+    input_path: &Path,
+    output_path: &Path,
+    tracker: &mut EncodeTracker<'_, F>,
+) -> Result<CommandOutput, AppError>
+where
+    F: FnMut(&str, u8),
+{
+    let timeline = &manifest.timeline;
+    let mut args: Vec<String> = vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-benchmark".to_string(),
+        "-loglevel".to_string(),
+        "info".to_string(),
+        "-stats".to_string(),
+        "-progress".to_string(),
+        "pipe:1".to_string(),
+    ];
+    if timeline.trim_start_ms > 0 {
+        args.push("-ss".to_string());
+        args.push(format!("{:.3}", timeline.trim_start_ms as f64 / 1000.0));
+    }
+    if timeline.trim_end_ms > timeline.trim_start_ms {
+        args.push("-to".to_string());
+        args.push(format!("{:.3}", timeline.trim_end_ms as f64 / 1000.0));
+    }
+    args.push("-i".to_string());
+    args.push(input_path.to_string_lossy().to_string());
+    args.push("-map".to_string());
+    args.push("0".to_string());
+    args.push("-c".to_string());
+    args.push(STREAM_COPY_CODEC.to_string());
+    args.push("-avoid_negative_ts".to_string());
+    args.push("make_zero".to_string());
+    args.push("-movflags".to_string());
+    args.push("+faststart".to_string());
+    args.push(output_path.to_string_lossy().to_string());
+    run_tracked(args, STREAM_COPY_CODEC, tracker)
+}
+
+fn run_tracked<F>(
+    args: Vec<String>,
+    codec: &str,
+    tracker: &mut EncodeTracker<'_, F>,
+) -> Result<CommandOutput, AppError>
+where
+    F: FnMut(&str, u8),
+{
+    let mut last = 0u8;
+    let duration_ms = tracker.duration_ms;
+    let on_progress = &mut tracker.on_progress;
+    run_ffmpeg_with_progress(
+        args,
+        Some(tracker.cancel),
+        tracker.low_priority,
+        |out_time_ms| {
+            if duration_ms == 0 {
+                return;
+            }
+            // 100% 留给封装完成后的成功事件。
+            let progress = progress_percent(out_time_ms, duration_ms).min(99);
+            if progress > last {
+                last = progress;
+                on_progress(codec, progress);
+            }
+        },
+    )
+}
+
+pub struct StitchClip {
+    pub path: PathBuf,
+    pub duration_ms: u64,
+    pub has_audio: bool,
+}
+
+/// 片头/片尾路径（按 intro、outro 顺序），未配置时为空。
+pub fn stitch_clip_paths(profile: &ExportProfile) -> Vec<PathBuf> {
+    [
+        stitch_clip_path(&profile.intro_path),
+        stitch_clip_path(&profile.outro_path),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn stitch_clip_path(path: &Option<String>) -> Option<PathBuf> {
+    path.as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 把片头、正片、片尾统一到输出分辨率与帧率后拼接为最终 MP4。
+/// 素材缺少音轨时补静音，保证 concat 的音视频流数量一致。
+pub fn stitch_intro_outro(
+    manifest: &ProjectManifest,
+    profile: &ExportProfile,
+    main_path: &Path,
+    output_path: &Path,
+    codec: &str,
+    threads: Option<usize>,
+    cancel: &AtomicBool,
+) -> Result<CommandOutput, AppError> {
+    let mut paths = Vec::new();
+    paths.extend(stitch_clip_path(&profile.intro_path));
+    paths.push(main_path.to_path_buf());
+    paths.extend(stitch_clip_path(&profile.outro_path));
+    let mut clips = Vec::with_capacity(paths.len());
+    for path in paths {
+        let summary = probe_media(&path).map_err(|error| {
+            AppError::new(
+                "EXPORT_CLIP_INVALID",
+                format!("failed to probe {}: {}", path.display(), error.message),
+                Some("请确认片头/片尾文件是可播放的视频".to_string()),
+            )
+        })?;
+        clips.push(StitchClip {
+            path,
+            duration_ms: summary.container_duration_ms,
+            has_audio: summary.audio_duration_ms.is_some(),
+        });
+    }
+
+    let (target_w, target_h) = output_resolution(
+        profile.resolution.clone(),
+        manifest.timeline.aspect_ratio.clone(),
+    );
+    let mut args: Vec<String> = vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-benchmark".to_string(),
+        "-loglevel".to_string(),
+        "info".to_string(),
+        "-nostats".to_string(),
+        "-progress".to_string(),
+        "pipe:1".to_string(),
+    ];
+    for clip in &clips {
+        args.push("-i".to_string());
+        args.push(clip.path.to_string_lossy().to_string());
+    }
+    args.push("-filter_complex".to_string());
+    args.push(build_concat_filter(&clips, target_w, target_h, profile.fps));
+    args.extend(
+        [
+            "-map",
+            "[vout]",
+            "-map",
+            "[aout]",
+            "-c:v",
+            codec,
+            "-pix_fmt",
+            "yuv420p",
+            "-c:a",
+            "aac",
+            "-b:a",
+            "128k",
+            "-movflags",
+            "+faststart",
+        ]
+        .map(str::to_string),
+    );
+    args.push("-b:v".to_string());
+    args.push(format!("{}M", profile.bitrate_mbps));
+    args.extend(thread_args(threads));
+    args.push(output_path.to_string_lossy().to_string());
+    run_ffmpeg_with_progress(args, Some(cancel), profile.low_priority, |_| {})
+}
+
+fn build_concat_filter(clips: &[StitchClip], width: u32, height: u32, fps: u8) -> String {
+    let mut chains = Vec::with_capacity(clips.len() * 2 + 1);
+    let mut concat_inputs = String::new();
+    for (index, clip) in clips.iter().enumerate() {
+        chains.push(format!(
+            "[{index}:v]scale={width}:{height}:force_original_aspect_ratio=decrease,\
+pad={width}:{height}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={fps},format=yuv420p[v{index}]"
+        ));
+        if clip.has_audio {
+            chains.push(format!(
+                "[{index}:a]aresample=48000,aformat=sample_fmts=fltp:channel_layouts=stereo[a{index}]"
+            ));
+        } else {
+            chains.push(format!(
+                "anullsrc=r=48000:cl=stereo,atrim=duration={:.3}[a{index}]",
+                clip.duration_ms as f64 / 1000.0
+            ));
+        }
+        concat_inputs.push_str(&format!("[v{index}][a{index}]"));
+    }
+    chains.push(format!(
+        "{concat_inputs}concat=n={}:v=1:a=1[vout][aout]",
+        clips.len()
+    ));
+    chains.join(";")
+}
+
+pub const MOTION_PREVIEW_HEIGHT: u32 = 480;
+const MOTION_PREVIEW_FPS: u8 = 30;
+
+/// 渲染镜头运动预览：只编码选定区间，480p + ultrafast，不带音频。
+/// 区间通过裁剪时间轴传入，镜头关键帧与点击放大会按区间起点对齐。
+pub fn render_motion_preview<F>(
+    manifest: &ProjectManifest,
+    input_path: &Path,
+    output_path: &Path,
+    start_ms: u64,
+    end_ms: u64,
+    mut on_progress: F,
+) -> Result<CommandOutput, AppError>
+where
+    F: FnMut(u8),
+{
+    let mut preview_manifest = manifest.clone();
+    preview_manifest.timeline.trim_start_ms = start_ms;
+    preview_manifest.timeline.trim_end_ms = end_ms;
+    let profile = ExportProfile {
+        resolution: Resolution::R720p,
+        fps: MOTION_PREVIEW_FPS,
+        ..manifest.export.clone()
+    };
+    let mut vf = build_video_filters(
+        &preview_manifest,
+        &profile,
+        input_path,
+        &input_path.with_file_name("camera_keyframes_preview.cmd"),
+    );
+    vf.push_str(&format!(",scale=-2:{MOTION_PREVIEW_HEIGHT}"));
+    let args: Vec<String> = vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-nostats".to_string(),
+        "-progress".to_string(),
+        "pipe:1".to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", start_ms as f64 / 1000.0),
+        "-to".to_string(),
+        format!("{:.3}", end_ms as f64 / 1000.0),
+        "-i".to_string(),
+        input_path.to_string_lossy().to_string(),
+        "-vf".to_string(),
+        vf,
+        "-an".to_string(),
+        "-c:v".to_string(),
+        "libx264".to_string(),
+        "-preset".to_string(),
+        "ultrafast".to_string(),
+        "-crf".to_string(),
+        "30".to_string(),
+        "-pix_fmt".to_string(),
+        "yuv420p".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ];
+    let duration_ms = end_ms.saturating_sub(start_ms).max(1);
+    run_ffmpeg_with_progress(args, None, false, |out_time_ms| {
+        on_progress(progress_percent(out_time_ms, duration_ms));
+    })
+}
+
+/// 把选定区间渲染为循环动图，画面处理与正式导出一致，不带音频，也不做静音剪除与变速。
+/// `options` 需先经过 `normalize_animation_options` 校验。
+pub fn render_animation(
+    manifest: &ProjectManifest,
+    input_path: &Path,
+    output_path: &Path,
+    options: &AnimationExportOptions,
+) -> Result<CommandOutput, AppError> {
+    let mut clip_manifest = manifest.clone();
+    clip_manifest.timeline.trim_start_ms = options.start_ms;
+    clip_manifest.timeline.trim_end_ms = options.end_ms;
+    let profile = ExportProfile {
+        fps: options.fps,
+        frame_interpolation: false,
+        ..manifest.export.clone()
+    };
+    let mut vf = build_video_filters(
+        &clip_manifest,
+        &profile,
+        input_path,
+        &input_path.with_file_name("camera_keyframes_animation.cmd"),
+    );
+    vf.push(',');
+    vf.push_str(&build_animation_filter(options));
+    let mut args: Vec<String> = vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-nostats".to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", options.start_ms as f64 / 1000.0),
+        "-to".to_string(),
+        format!("{:.3}", options.end_ms as f64 / 1000.0),
+        "-i".to_string(),
+        input_path.to_string_lossy().to_string(),
+        "-vf".to_string(),
+        vf,
+        "-an".to_string(),
+    ];
+    args.extend(animation_codec_args(options));
+    args.push(output_path.to_string_lossy().to_string());
+    run_ffmpeg_with_progress(args, None, false, |_| {})
+}
+
+// 封面只取首帧，但滤镜链按一小段区间计算，镜头关键帧与叠加层的时间才有参照。
+const POSTER_WINDOW_MS: u64 = 1_000;
+
+/// 渲染 `t_ms` 处的封面：裁剪、镜头与场景样式与成片一致，高质量 JPEG。
+/// 时间码是给测试看的辅助信息，封面上不叠加。
+pub fn render_poster(
+    manifest: &ProjectManifest,
+    input_path: &Path,
+    output_path: &Path,
+    t_ms: u64,
+    size: Resolution,
+) -> Result<(), AppError> {
+    let mut poster_manifest = manifest.clone();
+    poster_manifest.timeline.trim_start_ms = t_ms;
+    poster_manifest.timeline.trim_end_ms = t_ms + POSTER_WINDOW_MS;
+    let profile = ExportProfile {
+        resolution: size,
+        frame_interpolation: false,
+        timecode: TimecodeMode::Off,
+        ..manifest.export.clone()
+    };
+    let vf = build_video_filters(
+        &poster_manifest,
+        &profile,
+        input_path,
+        &input_path.with_file_name("camera_keyframes_poster.cmd"),
+    );
+    let output = run_ffmpeg([
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", t_ms as f64 / 1000.0),
+        "-t".to_string(),
+        format!("{:.3}", POSTER_WINDOW_MS as f64 / 1000.0),
+        "-i".to_string(),
+        input_path.to_string_lossy().to_string(),
+        "-vf".to_string(),
+        vf,
+        "-frames:v".to_string(),
+        "1".to_string(),
+        "-q:v".to_string(),
+        "2".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ])?;
+    if !output.status.success() || !output_path.exists() {
+        return Err(AppError::new(
+            "POSTER_EXPORT_FAILED",
+            output.stderr.lines().last().unwrap_or_default().to_string(),
+            Some("请确认录制文件完整，且时间点在录制时长以内".to_string()),
+        ));
+    }
+    Ok(())
+}
 
-    run_ffmpeg(args)
+pub fn progress_percent(out_time_ms: u64, duration_ms: u64) -> u8 {
+    ((out_time_ms.min(duration_ms) * 100) / duration_ms.max(1)) as u8
+}
+
+/// 累加 `-benchmark` 输出的 `bench: utime=1.234s stime=0.056s rtime=2.000s`，
+/// 日志里有多次 ffmpeg 调用（回退重试、拼接）时逐行相加。
+pub fn parse_benchmark_cpu_ms(stderr: &str) -> u64 {
+    let seconds = |line: &str, key: &str| {
+        line.split_whitespace()
+            .find_map(|field| field.strip_prefix(key))
+            .and_then(|value| value.trim_end_matches('s').parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+    stderr
+        .lines()
+        .filter(|line| line.contains("bench: utime="))
+        .map(|line| seconds(line, "utime=") + seconds(line, "stime="))
+        .map(|total| (total * 1000.0).round() as u64)
+        .sum()
 }
 
 pub fn classify_export_error(stderr: &str) -> AppError {
@@ -153,87 +887,653 @@ pub fn classify_export_error(stderr: &str) -> AppError {
     )
 }
 
-fn hardware_codec() -> &'static str {
-    #[cfg(target_os = "windows")]
-    {
-        "h264_nvenc"
-    }
-    #[cfg(target_os = "macos")]
-    {
-        "h264_videotoolbox"
-    }
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    {
-        "libx264"
-    }
+fn build_video_filters(
+    manifest: &ProjectManifest,
+    profile: &ExportProfile,
+    input_path: &Path,
+    keyframes_path: &Path,
+) -> String {
+    let (target_w, target_h) = output_resolution(
+        profile.resolution.clone(),
+        manifest.timeline.aspect_ratio.clone(),
+    );
+    let (source_w, source_h) = probe_input_dimensions(input_path).unwrap_or((target_w, target_h));
+    let fit_mode = manifest.timeline.fit_mode;
+    let scene = &manifest.scene;
+    // 启用场景外观时内容缩进到留白以内，留白区域由场景背景填充。
+    let (box_w, box_h) = if scene.enabled {
+        let padding = scene_padding(scene, target_w, target_h);
+        (target_w - padding * 2, target_h - padding * 2)
+    } else {
+        (target_w, target_h)
+    };
+    // 非裁剪模式下镜头只在原始画幅内推拉，再整体缩放进目标画幅。
+    let content_ar = if fit_mode == FitMode::Crop {
+        box_w as f64 / box_h as f64
+    } else {
+        source_w as f64 / source_h.max(1) as f64
+    };
+    let (content_w, content_h) = fit_dimensions(fit_mode, content_ar, box_w, box_h);
+    let mut filters: Vec<String> = Vec::new();
+
+    // 降噪放在裁剪缩放之前，避免镜头推近时把噪点一起放大。
+    filters.extend(build_video_denoise_filter(profile.video_denoise));
+    // 补帧同样作用于原始画面，之后的镜头推拉与叠加层都按导出帧率逐帧计算。
+    if profile.frame_interpolation {
+        let source_fps = probe_streams(input_path)
+            .ok()
+            .and_then(|streams| streams.frame_rate)
+            .unwrap_or_else(|| f32::from(manifest.recording.frame_rate));
+        filters.extend(build_interpolation_filter(source_fps, profile.fps));
+    }
+    // 打码作用于原始画面，镜头推拉与缩放时随内容一起移动。
+    filters.extend(build_blur_filters(
+        &manifest.timeline.blur_regions,
+        source_w,
+        source_h,
+        manifest.timeline.trim_start_ms,
+        manifest.timeline.trim_end_ms,
+    ));
+    filters.push(build_crop_filter(
+        manifest,
+        content_ar,
+        source_w as f64,
+        source_h as f64,
+        profile.fps,
+        keyframes_path,
+    ));
+
+    filters.push(format!("scale={content_w}:{content_h}"));
+    // 调色只作用于录制内容，光标、标注与场景背景保持原色。
+    let lut = color_lut_path(&manifest.color_grade).map(|path| escape_filter_path(&path));
+    filters.extend(build_color_filters(&manifest.color_grade, lut.as_deref()));
+    // 聚光灯压暗在合成光标之下，光标本身保持原亮度。
+    if manifest.timeline.spotlight.enabled {
+        if let Some(filter) = build_cursor_spotlight(
+            manifest,
+            (source_w, source_h),
+            content_ar,
+            (content_w, content_h),
+            profile.fps,
+            &keyframes_path.with_extension("spotlight.cmd"),
+        ) {
+            filters.push(filter);
+        }
+    }
+    // 光标在点击放大与画幅适配之前叠加，随内容一起缩放。
+    if manifest.timeline.cursor_highlight_enabled {
+        if let Some(filter) = build_synthetic_cursor(
+            manifest,
+            (source_w, source_h),
+            content_ar,
+            (content_w, content_h),
+            profile.fps,
+            &keyframes_path.with_extension("cursor.cmd"),
+        ) {
+            filters.push(filter);
+        }
+    }
+    if manifest.camera_motion.enabled
+        && manifest.camera_motion.zoom_on_click
+        && manifest.camera_motion.mode != CameraMode::PanOnly
+    {
+        let spans = click_zoom_spans(
+            manifest,
+            &load_cursor_track(manifest),
+            content_ar,
+            source_w as f64,
+            source_h as f64,
+        );
+        if let Some(filter) = build_click_zoom_filter(&spans, content_w, content_h, profile.fps) {
+            filters.push(filter);
+        }
+    }
+    if scene.enabled {
+        filters.push(build_scene_filter(
+            scene,
+            content_w,
+            content_h,
+            target_w,
+            target_h,
+            profile.fps,
+        ));
+    } else if let Some(filter) = build_fit_filter(fit_mode, target_w, target_h) {
+        filters.push(filter);
+    }
+    if manifest.timeline.scroll_indicator_enabled {
+        let trim_start_sec = manifest.timeline.trim_start_ms as f64 / 1000.0;
+        let trim_end_sec = (manifest.timeline.trim_end_ms > manifest.timeline.trim_start_ms)
+            .then(|| manifest.timeline.trim_end_ms as f64 / 1000.0);
+        filters.extend(build_scroll_indicator_filters(
+            &load_cursor_track(manifest),
+            trim_start_sec,
+            trim_end_sec,
+        ));
+    }
+    if manifest.keystroke_overlay.enabled {
+        filters.extend(build_keystroke_overlay(manifest, target_w, target_h));
+    }
+    if let Some(filter) = build_shape_overlay(
+        manifest,
+        target_w,
+        target_h,
+        &keyframes_path.with_extension("shapes.ass"),
+    ) {
+        filters.push(filter);
+    }
+    if !manifest.timeline.annotations.is_empty() {
+        let font = annotation_font_path().map(|path| escape_filter_path(&path));
+        filters.extend(build_annotation_filters(
+            &manifest.timeline.annotations,
+            font.as_deref(),
+            target_w,
+            target_h,
+            manifest.timeline.trim_start_ms,
+            manifest.timeline.trim_end_ms,
+        ));
+    }
+    if let Some(path) = subtitle_path(profile) {
+        filters.push(build_subtitle_filter(
+            &path,
+            profile,
+            manifest.timeline.trim_start_ms,
+            target_w,
+            target_h,
+        ));
+    }
+    if profile.timecode != TimecodeMode::Off {
+        let font = overlay_font_path().map(|path| escape_filter_path(&path));
+        filters.extend(build_timecode_filter(
+            profile.timecode,
+            profile.timecode_corner,
+            font.as_deref(),
+            target_w,
+            target_h,
+            manifest.timeline.trim_start_ms,
+            manifest.recording_started_at,
+        ));
+    }
+    filters.push("setsar=1".to_string());
+    filters.push(format!("setdar={target_w}/{target_h}"));
+    filters.join(",")
+}
+
+fn build_crop_filter(
+    manifest: &ProjectManifest,
+    target_ar: f64,
+    source_w: f64,
+    source_h: f64,
+    fps: u8,
+    keyframes_path: &Path,
+) -> String {
+    let zoom = camera_zoom(manifest);
+    let crop_w = format!(
+        "if(gt(iw/ih,{target_ar:.6}),trunc((ih*{target_ar:.6})/{zoom:.6}/2)*2,trunc(iw/{zoom:.6}/2)*2)"
+    );
+    let crop_h = format!(
+        "if(gt(iw/ih,{target_ar:.6}),trunc(ih/{zoom:.6}/2)*2,trunc((iw/{target_ar:.6})/{zoom:.6}/2)*2)"
+    );
+
+    if camera_follows_target(manifest, source_w / source_h.max(1.0), target_ar) {
+        let cursor_track = camera_target_track(manifest);
+        if let Some(path) =
+            smooth_camera_path(&cursor_track, source_w, source_h, &manifest.camera_motion)
+        {
+            let trim_start_sec = manifest.timeline.trim_start_ms as f64 / 1000.0;
+            let trim_end_sec = (manifest.timeline.trim_end_ms > manifest.timeline.trim_start_ms)
+                .then(|| manifest.timeline.trim_end_ms as f64 / 1000.0);
+            let keyframes = build_camera_keyframes(&path, trim_start_sec, trim_end_sec, fps);
+            if let Some((x0, y0)) = keyframes.first {
+                if std::fs::write(keyframes_path, &keyframes.commands).is_ok() {
+                    let file = escape_filter_path(keyframes_path);
+                    return format!(
+                        "sendcmd=f='{file}',crop@{CAMERA_CROP_NAME}=w='{crop_w}':h='{crop_h}':x='iw*{x0:.6}-ow/2':y='ih*{y0:.6}-oh/2'"
+                    );
+                }
+            }
+        }
+        // 关键帧文件写入失败时退回分段表达式，长录屏精度会下降但仍可导出。
+        if let Some((nx_expr, ny_expr)) =
+            build_cursor_position_expr(&cursor_track, source_w, source_h, &manifest.camera_motion)
+        {
+            let x = format!("max(0,min(iw-ow,iw*({nx_expr})-ow/2))");
+            let y = format!("max(0,min(ih-oh,ih*({ny_expr})-oh/2))");
+            return format!("crop=w='{crop_w}':h='{crop_h}':x='{x}':y='{y}'");
+        }
+    }
+
+    format!("crop=w='{crop_w}':h='{crop_h}':x='(iw-ow)/2':y='(ih-oh)/2'")
+}
+
+/// 内容缩放后的尺寸：crop 模式铺满目标画幅，其余模式按内容比例完整放入（宽高取偶数）。
+fn fit_dimensions(fit_mode: FitMode, content_ar: f64, target_w: u32, target_h: u32) -> (u32, u32) {
+    let target_ar = target_w as f64 / target_h as f64;
+    if fit_mode == FitMode::Crop || (content_ar - target_ar).abs() < 0.001 {
+        return (target_w, target_h);
+    }
+    let even = |value: f64| ((value / 2.0).round() as u32 * 2).max(2);
+    if content_ar > target_ar {
+        (target_w, even(target_w as f64 / content_ar).min(target_h))
+    } else {
+        (even(target_h as f64 * content_ar).min(target_w), target_h)
+    }
+}
+
+/// 把缩放后的内容放进目标画幅：pad 居中补黑边，blur_background 以放大模糊的同一画面作底。
+fn build_fit_filter(fit_mode: FitMode, target_w: u32, target_h: u32) -> Option<String> {
+    match fit_mode {
+        FitMode::Crop => None,
+        FitMode::Pad => Some(format!(
+            "pad={target_w}:{target_h}:(ow-iw)/2:(oh-ih)/2:color=black"
+        )),
+        FitMode::BlurBackground => Some(format!(
+            "split=2[fit_fg][fit_bg];[fit_bg]scale={target_w}:{target_h}:force_original_aspect_ratio=increase,\
+crop={target_w}:{target_h},boxblur=luma_radius=24:luma_power=2,eq=brightness=-0.06[fit_blur];\
+[fit_blur][fit_fg]overlay=(W-w)/2:(H-h)/2"
+        )),
+    }
+}
+
+/// 镜头跟随目标：光标轨迹，窗口取景模式下替换为前台窗口中心。
+fn camera_target_track(manifest: &ProjectManifest) -> Vec<CursorPoint> {
+    let cursor_track = load_cursor_track(manifest);
+    if manifest.camera_motion.focus_mode == CameraFocusMode::Window {
+        return apply_window_focus(&cursor_track, &load_window_focus_segments(manifest));
+    }
+    cursor_track
+}
+
+/// 按光标轨迹逐帧定位合成光标；轨迹为空或关键帧文件写入失败时不叠加。
+fn build_synthetic_cursor(
+    manifest: &ProjectManifest,
+    source: (u32, u32),
+    content_ar: f64,
+    (content_w, content_h): (u32, u32),
+    fps: u8,
+    keyframes_path: &Path,
+) -> Option<String> {
+    let style = manifest.timeline.cursor_style;
+    let scale = content_w.min(content_h) as f64 / 1080.0;
+    let size = ((manifest.timeline.cursor_size as f64 * scale).round() as u32).max(8);
+    let projection = CursorProjection {
+        window: source_crop_window(manifest, source, content_ar),
+        content: (content_w, content_h),
+        hotspot: cursor_hotspot(style, size),
+        overlay: CURSOR_OVERLAY_NAME,
+    };
+    let first = write_cursor_keyframes(
+        manifest,
+        source,
+        content_ar,
+        &projection,
+        fps,
+        keyframes_path,
+    )?;
+    Some(build_cursor_overlay_filter(
+        style,
+        size,
+        keyframes_path,
+        first,
+    ))
+}
+
+/// 聚光灯遮罩跟随光标移动，光标离开录制画面时整个遮罩移出、不再压暗。
+fn build_cursor_spotlight(
+    manifest: &ProjectManifest,
+    source: (u32, u32),
+    content_ar: f64,
+    content: (u32, u32),
+    fps: u8,
+    keyframes_path: &Path,
+) -> Option<String> {
+    let spotlight = manifest.timeline.spotlight;
+    let scale = content.0.min(content.1) as f64 / 1080.0;
+    let projection = CursorProjection {
+        window: source_crop_window(manifest, source, content_ar),
+        content,
+        hotspot: spotlight_hotspot(content),
+        overlay: SPOTLIGHT_OVERLAY_NAME,
+    };
+    let first = write_cursor_keyframes(
+        manifest,
+        source,
+        content_ar,
+        &projection,
+        fps,
+        keyframes_path,
+    )?;
+    Some(build_spotlight_filter(
+        content,
+        spotlight.radius as f64 * scale,
+        spotlight.feather as f64 * scale,
+        spotlight.dim as f64,
+        keyframes_path,
+        first,
+    ))
+}
+
+fn source_crop_window(
+    manifest: &ProjectManifest,
+    (source_w, source_h): (u32, u32),
+    content_ar: f64,
+) -> CropWindow {
+    let source_ar = source_w.max(1) as f64 / source_h.max(1) as f64;
+    CropWindow::new(source_ar, content_ar, camera_zoom(manifest))
+}
+
+/// 生成跟随光标图层的关键帧文件，返回首帧位置；轨迹为空或写入失败时返回 None。
+fn write_cursor_keyframes(
+    manifest: &ProjectManifest,
+    (source_w, source_h): (u32, u32),
+    content_ar: f64,
+    projection: &CursorProjection,
+    fps: u8,
+    keyframes_path: &Path,
+) -> Option<(i64, i64)> {
+    let safe_w = source_w.max(1) as f64;
+    let safe_h = source_h.max(1) as f64;
+    let samples = load_cursor_track(manifest)
+        .iter()
+        .map(|point| CursorSample {
+            t_sec: point.t_sec,
+            x: point.x / safe_w,
+            y: point.y / safe_h,
+            visible: !point.offscreen,
+        })
+        .collect::<Vec<_>>();
+    let camera = camera_follows_target(manifest, safe_w / safe_h, content_ar)
+        .then(|| {
+            smooth_camera_path(
+                &camera_target_track(manifest),
+                safe_w,
+                safe_h,
+                &manifest.camera_motion,
+            )
+        })
+        .flatten();
+    let trim_start_sec = manifest.timeline.trim_start_ms as f64 / 1000.0;
+    let trim_end_sec = (manifest.timeline.trim_end_ms > manifest.timeline.trim_start_ms)
+        .then(|| manifest.timeline.trim_end_ms as f64 / 1000.0);
+    let keyframes = build_cursor_keyframes(
+        &samples,
+        camera.as_deref(),
+        projection,
+        trim_start_sec,
+        trim_end_sec,
+        fps,
+    );
+    let first = keyframes.first?;
+    std::fs::write(keyframes_path, &keyframes.commands).ok()?;
+    Some(first)
+}
+
+/// 箭头与形状写成 ASS 脚本交给 libass 渲染，位于文字标注之下。
+fn build_shape_overlay(
+    manifest: &ProjectManifest,
+    target_w: u32,
+    target_h: u32,
+    script_path: &Path,
+) -> Option<String> {
+    let script = build_shape_script(
+        &manifest.timeline.shapes,
+        target_w,
+        target_h,
+        manifest.timeline.trim_start_ms,
+        manifest.timeline.trim_end_ms,
+    )?;
+    std::fs::write(script_path, script).ok()?;
+    Some(format!(
+        "ass=filename='{}'",
+        escape_filter_path(script_path)
+    ))
+}
+
+/// 按键提示叠加在最终画面上，不随镜头缩放或场景留白移动。
+fn build_keystroke_overlay(
+    manifest: &ProjectManifest,
+    target_w: u32,
+    target_h: u32,
+) -> Vec<String> {
+    let events = load_key_events(manifest);
+    let timeline = &manifest.timeline;
+    let trim_end_ms =
+        (timeline.trim_end_ms > timeline.trim_start_ms).then_some(timeline.trim_end_ms);
+    let spans = keystroke_spans(
+        &events,
+        manifest.keystroke_overlay.hold_ms,
+        timeline.trim_start_ms,
+        trim_end_ms,
+    );
+    let font = overlay_font_path().map(|path| escape_filter_path(&path));
+    build_keystroke_filters(
+        &spans,
+        &manifest.keystroke_overlay,
+        font.as_deref(),
+        target_w,
+        target_h,
+    )
+}
+
+const CAMERA_CROP_NAME: &str = "camera";
+// 裁剪后保留的宽度不足源画面的该比例时（如 16:9 裁 9:16、1:1）自动跟随取景。
+const AUTO_REFRAME_MAX_WIDTH_RATIO: f64 = 0.8;
+// 相邻帧镜头位移小于该值（归一化）时不再下发命令，压缩关键帧文件体积。
+const KEYFRAME_EPSILON: f64 = 0.0002;
+
+fn camera_keyframes_path(input_path: &Path) -> PathBuf {
+    input_path.with_file_name("camera_keyframes.cmd")
+}
+
+struct CameraKeyframes {
+    first: Option<(f64, f64)>,
+    commands: String,
+}
+
+/// 按输出帧率对镜头路径逐帧采样，生成 sendcmd 脚本；时间已换算为裁剪后的时间轴。
+/// crop 会把越界的 x/y 自动夹取到画面内，因此命令里只需给出中心对齐的偏移。
+fn build_camera_keyframes(
+    path: &[(f64, f64, f64)],
+    trim_start_sec: f64,
+    trim_end_sec: Option<f64>,
+    fps: u8,
+) -> CameraKeyframes {
+    let mut keyframes = CameraKeyframes {
+        first: None,
+        commands: String::new(),
+    };
+    let Some(last) = path.last() else {
+        return keyframes;
+    };
+    let end_sec = trim_end_sec.unwrap_or(last.0).min(last.0);
+    let frame_sec = 1.0 / f64::from(fps.max(1));
+    let mut previous: Option<(f64, f64)> = None;
+    let mut frame = 0u64;
+    loop {
+        let t_out = frame as f64 * frame_sec;
+        let t_src = trim_start_sec + t_out;
+        if t_src > end_sec && frame > 0 {
+            break;
+        }
+        let (x, y) = sample_camera_path(path, t_src);
+        let changed = previous
+            .map(|(px, py)| (x - px).abs() > KEYFRAME_EPSILON || (y - py).abs() > KEYFRAME_EPSILON)
+            .unwrap_or(true);
+        if changed {
+            keyframes.commands.push_str(&format!(
+                "{t_out:.4} crop@{CAMERA_CROP_NAME} x iw*{x:.6}-ow/2, crop@{CAMERA_CROP_NAME} y ih*{y:.6}-oh/2;\n"
+            ));
+            previous = Some((x, y));
+            keyframes.first.get_or_insert((x, y));
+        }
+        frame += 1;
+    }
+    keyframes
+}
+
+pub(crate) fn sample_camera_path(path: &[(f64, f64, f64)], t_sec: f64) -> (f64, f64) {
+    let index = path.partition_point(|item| item.0 <= t_sec);
+    match (
+        index.checked_sub(1).and_then(|i| path.get(i)),
+        path.get(index),
+    ) {
+        (Some(a), Some(b)) => {
+            let ratio = ((t_sec - a.0) / (b.0 - a.0).max(0.001)).clamp(0.0, 1.0);
+            (a.1 + (b.1 - a.1) * ratio, a.2 + (b.2 - a.2) * ratio)
+        }
+        (Some(a), None) => (a.1, a.2),
+        (None, Some(b)) => (b.1, b.2),
+        (None, None) => (0.5, 0.5),
+    }
+}
+
+/// 画面降噪滤镜；hqdn3d 参数依次为空间亮度、空间色度、时间亮度、时间色度强度。
+fn build_video_denoise_filter(denoise: VideoDenoise) -> Option<String> {
+    match denoise {
+        VideoDenoise::Off => None,
+        VideoDenoise::Light => Some("hqdn3d=2:1.5:3:2.25".to_string()),
+        VideoDenoise::Medium => Some("hqdn3d=4:3:6:4.5".to_string()),
+        VideoDenoise::Strong => Some("nlmeans=s=3.5:p=7:r=15".to_string()),
+    }
+}
+
+/// 源帧率明显低于导出帧率时才补帧；双向运动估计配合重叠块补偿，减少光标拖影。
+fn build_interpolation_filter(source_fps: f32, target_fps: u8) -> Option<String> {
+    (source_fps + 1.0 < f32::from(target_fps)).then(|| {
+        format!("minterpolate=fps={target_fps}:mi_mode=mci:mc_mode=aobmc:me_mode=bidir:vsbmc=1")
+    })
+}
+
+/// 导出音频滤镜链；strong 档缺少 RNNoise 模型时退回更激进的 afftdn 参数。
+fn build_audio_filters(denoise: AudioDenoise, rnnoise_model: Option<&Path>) -> Option<String> {
+    match denoise {
+        AudioDenoise::Off => None,
+        AudioDenoise::Light => Some("afftdn=nr=12:nf=-40".to_string()),
+        AudioDenoise::Strong => Some(match rnnoise_model {
+            Some(model) => format!("highpass=f=80,arnndn=m='{}'", escape_filter_path(model)),
+            None => "highpass=f=80,afftdn=nr=24:nf=-35".to_string(),
+        }),
+    }
+}
+
+fn recording_gain(manifest: &ProjectManifest) -> f32 {
+    recording_track_gain(
+        &manifest.audio_mix,
+        manifest.recording.microphone_device_id.is_some(),
+        manifest.recording.system_audio_enabled,
+    )
+}
+
+/// 混音设置是否会改变成片音轨；未生效时仍可走流复制。
+fn audio_mix_active(manifest: &ProjectManifest) -> bool {
+    build_volume_filter(recording_gain(manifest)).is_some()
+        || build_volume_filter(manifest.audio_mix.master_volume).is_some()
+        || background_music_path(manifest).is_some()
+}
+
+/// 背景音乐文件不存在时跳过混音，不阻断导出。
+fn background_music_path(manifest: &ProjectManifest) -> Option<PathBuf> {
+    manifest
+        .audio_mix
+        .music_path
+        .as_deref()
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
 }
 
-fn build_video_filters(
-    manifest: &ProjectManifest,
-    profile: &ExportProfile,
-    input_path: &Path,
-) -> String {
-    let (target_w, target_h) = output_resolution(
-        profile.resolution.clone(),
-        manifest.timeline.aspect_ratio.clone(),
+/// 点击音混在降噪之后，避免被当作噪声削弱；时间轴为裁剪后、重映射前。
+fn build_click_sound(manifest: &ProjectManifest, profile: &ExportProfile) -> Option<String> {
+    if !profile.click_sound_enabled {
+        return None;
+    }
+    let clicks = load_cursor_track(manifest)
+        .iter()
+        .filter(|point| point.click)
+        .map(|point| point.t_sec)
+        .collect::<Vec<_>>();
+    let timeline = &manifest.timeline;
+    let offsets = click_sound_offsets_ms(
+        &clicks,
+        timeline.trim_start_ms,
+        (timeline.trim_end_ms > timeline.trim_start_ms).then_some(timeline.trim_end_ms),
     );
-    let (source_w, source_h) = probe_input_dimensions(input_path).unwrap_or((target_w, target_h));
-    let target_ar = target_w as f64 / target_h as f64;
-    let mut filters: Vec<String> = Vec::new();
+    build_click_sound_filter(&offsets, profile.click_sound_volume)
+}
 
-    filters.push(build_crop_filter(
-        manifest,
-        target_ar,
-        source_w as f64,
-        source_h as f64,
-    ));
+// libass 渲染 SRT/VTT 时的默认脚本高度，force_style 中的尺寸以此为单位。
+const SUBTITLE_PLAY_RES_Y: f64 = 288.0;
+const SUBTITLE_MARGIN: f64 = 60.0;
 
-    if manifest.timeline.cursor_highlight_enabled {
-        // MVP 使用轻量视觉增强替代复杂光标合成，避免引入轨道级渲染依赖。
-        filters.push("eq=contrast=1.03:saturation=1.06".to_string());
-    }
+pub fn subtitle_path(profile: &ExportProfile) -> Option<PathBuf> {
+    profile
+        .subtitle_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
 
-    filters.push(format!("scale={target_w}:{target_h}"));
-    filters.push("setsar=1".to_string());
-    filters.push(format!("setdar={target_w}/{target_h}"));
-    filters.join(",")
+fn is_ass_subtitle(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ass") || ext.eq_ignore_ascii_case("ssa"))
 }
 
-fn build_crop_filter(
-    manifest: &ProjectManifest,
-    target_ar: f64,
-    source_w: f64,
-    source_h: f64,
+/// 烧录字幕：字幕时间轴对应原始录制，裁剪后先把时间戳平移回去再渲染。
+fn build_subtitle_filter(
+    path: &Path,
+    profile: &ExportProfile,
+    trim_start_ms: u64,
+    target_w: u32,
+    target_h: u32,
 ) -> String {
-    let zoom = camera_zoom(manifest);
-    let crop_w = format!(
-        "if(gt(iw/ih,{target_ar:.6}),trunc((ih*{target_ar:.6})/{zoom:.6}/2)*2,trunc(iw/{zoom:.6}/2)*2)"
-    );
-    let crop_h = format!(
-        "if(gt(iw/ih,{target_ar:.6}),trunc(ih/{zoom:.6}/2)*2,trunc((iw/{target_ar:.6})/{zoom:.6}/2)*2)"
-    );
+    let file = escape_filter_path(path);
+    let subtitles = if is_ass_subtitle(path) {
+        format!("ass=filename='{file}'")
+    } else {
+        // force_style 按脚本高度换算，竖屏时仍以短边为基准保持观感一致。
+        let to_script = |px: f64| {
+            (px * target_w.min(target_h) as f64 / 1080.0 * SUBTITLE_PLAY_RES_Y / target_h as f64)
+                .round()
+                .max(1.0)
+        };
+        let alignment = match profile.subtitle_position {
+            OverlayPosition::Bottom => 2,
+            OverlayPosition::Top => 8,
+        };
+        format!(
+            "subtitles=filename='{file}':force_style='FontSize={},Alignment={alignment},MarginV={},Outline=1,Shadow=0'",
+            to_script(profile.subtitle_font_size as f64),
+            to_script(SUBTITLE_MARGIN)
+        )
+    };
+    if trim_start_ms == 0 {
+        return subtitles;
+    }
+    let offset = trim_start_ms as f64 / 1000.0;
+    format!("setpts=PTS+{offset:.3}/TB,{subtitles},setpts=PTS-{offset:.3}/TB")
+}
+
+/// 滤镜参数中的路径需转义冒号（Windows 盘符）并统一为正斜杠。
+pub(crate) fn escape_filter_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .replace('\'', "'\\''")
+        .replace(':', "\\:")
+}
 
+/// zoomOnly 模式下取景固定在画面中心，不跟随光标或窗口。
+/// 未开启镜头运动时，横屏素材裁成竖屏等窄画幅会丢掉大半画面，
+/// 此时仍按光标路径水平取景，避免操作区域被裁出画外。
+fn camera_follows_target(manifest: &ProjectManifest, source_ar: f64, target_ar: f64) -> bool {
     if manifest.camera_motion.enabled {
-        let cursor_track = load_cursor_track(manifest);
-        if let Some((nx_expr, ny_expr)) = build_cursor_position_expr(
-            &cursor_track,
-            source_w,
-            source_h,
-            manifest.camera_motion.smoothing as f64,
-            manifest.camera_motion.idle_threshold_ms as f64,
-            manifest.camera_motion.intensity.clone(),
-        ) {
-            let x = format!("max(0,min(iw-ow,iw*({nx_expr})-ow/2))");
-            let y = format!("max(0,min(ih-oh,ih*({ny_expr})-oh/2))");
-            return format!("crop=w='{crop_w}':h='{crop_h}':x='{x}':y='{y}'");
-        }
+        return manifest.camera_motion.mode != CameraMode::ZoomOnly;
     }
-
-    format!("crop=w='{crop_w}':h='{crop_h}':x='(iw-ow)/2':y='(ih-oh)/2'")
+    target_ar < source_ar * AUTO_REFRAME_MAX_WIDTH_RATIO
 }
 
+/// panOnly 模式固定 1.0x，仅在内容与目标画幅比例不同时有余量可平移。
 fn camera_zoom(manifest: &ProjectManifest) -> f64 {
-    if !manifest.camera_motion.enabled {
+    if !manifest.camera_motion.enabled || manifest.camera_motion.mode == CameraMode::PanOnly {
         return 1.0;
     }
     let base = match manifest.camera_motion.intensity {
@@ -253,10 +1553,15 @@ struct CursorPoint {
     t_sec: f64,
     x: f64,
     y: f64,
+    scroll_dy: f64,
+    offscreen: bool,
+    click: bool,
+    /// 目标已替换为前台窗口中心，不参与空闲回中判断。
+    pinned: bool,
 }
 
 fn load_cursor_track(manifest: &ProjectManifest) -> Vec<CursorPoint> {
-    let Some(path) = manifest.artifacts.cursor_track_path.as_ref() else {
+    let Some(path) = manifest.artifacts.effective_cursor_track_path() else {
         return Vec::new();
     };
     let Ok(raw) = std::fs::read_to_string(path) else {
@@ -272,50 +1577,112 @@ fn load_cursor_track(manifest: &ProjectManifest) -> Vec<CursorPoint> {
                 t_sec: value.get("tMs")?.as_u64()? as f64 / 1000.0,
                 x: value.get("x")?.as_f64()?,
                 y: value.get("y")?.as_f64()?,
+                scroll_dy: value
+                    .get("scrollDy")
+                    .and_then(|item| item.as_f64())
+                    .unwrap_or(0.0),
+                offscreen: value
+                    .get("offscreen")
+                    .and_then(|item| item.as_bool())
+                    .unwrap_or(false),
+                click: value
+                    .get("click")
+                    .and_then(|item| item.as_bool())
+                    .unwrap_or(false),
+                pinned: false,
             })
         })
         .collect::<Vec<_>>()
 }
 
-#[derive(Debug, Deserialize)]
-struct InputSizeStream {
-    width: Option<u32>,
-    height: Option<u32>,
-}
-
-#[derive(Debug, Deserialize)]
-struct InputSizeProbe {
-    streams: Vec<InputSizeStream>,
-}
-
-fn probe_input_dimensions(path: &Path) -> Option<(u32, u32)> {
-    let output = Command::new(ffprobe_bin())
-        .arg("-v")
-        .arg("error")
-        .arg("-select_streams")
-        .arg("v:0")
-        .arg("-show_entries")
-        .arg("stream=width,height")
-        .arg("-of")
-        .arg("json")
-        .arg(path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .output()
-        .ok()?;
-    if !output.status.success() {
-        return None;
-    }
-    let parsed: InputSizeProbe = serde_json::from_slice(&output.stdout).ok()?;
-    let stream = parsed.streams.first()?;
-    let (Some(width), Some(height)) = (stream.width, stream.height) else {
-        return None;
-    };
-    if width == 0 || height == 0 {
-        None
-    } else {
-        Some((width, height))
+fn load_key_events(manifest: &ProjectManifest) -> Vec<KeyEvent> {
+    manifest
+        .artifacts
+        .key_track_path
+        .as_deref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str::<Vec<KeyEvent>>(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn load_window_focus_segments(manifest: &ProjectManifest) -> Vec<AppFocusSegment> {
+    manifest
+        .artifacts
+        .app_track_path
+        .as_deref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str::<Vec<AppFocusSegment>>(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// 窗口焦点模式：落在已知窗口矩形片段内的样本改为以窗口中心为目标，其余保持光标跟随。
+fn apply_window_focus(points: &[CursorPoint], segments: &[AppFocusSegment]) -> Vec<CursorPoint> {
+    points
+        .iter()
+        .map(|point| {
+            let t_ms = (point.t_sec * 1000.0).round() as u64;
+            let rect = segments
+                .iter()
+                .find(|segment| segment.start_ms <= t_ms && t_ms <= segment.end_ms)
+                .and_then(|segment| segment.window_rect);
+            match rect {
+                Some(rect) => {
+                    let (x, y) = rect.center();
+                    CursorPoint {
+                        x,
+                        y,
+                        offscreen: false,
+                        pinned: true,
+                        ..*point
+                    }
+                }
+                None => *point,
+            }
+        })
+        .collect()
+}
+
+const SCROLL_INDICATOR_HOLD_SEC: f64 = 0.4;
+
+/// 把连续同方向的滚动样本合并为区间，在画面右侧用半透明条提示滚动方向。
+/// 上下两个方向各只生成一个 drawbox，区间合并进同一个 enable 表达式；裁剪区间外的样本跳过。
+fn build_scroll_indicator_filters(
+    points: &[CursorPoint],
+    trim_start_sec: f64,
+    trim_end_sec: Option<f64>,
+) -> Vec<String> {
+    let duration = trim_end_sec.map(|end| end - trim_start_sec);
+    let mut spans: Vec<(f64, f64, bool)> = Vec::new();
+    for point in points.iter().filter(|point| {
+        point.scroll_dy.abs() > f64::EPSILON
+            && point.t_sec >= trim_start_sec
+            && trim_end_sec.is_none_or(|end| point.t_sec < end)
+    }) {
+        let downward = point.scroll_dy > 0.0;
+        let start = point.t_sec - trim_start_sec;
+        let end = start + SCROLL_INDICATOR_HOLD_SEC;
+        let end = duration.map_or(end, |duration| end.min(duration));
+        match spans.last_mut() {
+            Some(last) if last.2 == downward && start <= last.1 => last.1 = end,
+            _ => spans.push((start, end, downward)),
+        }
     }
+    [(false, "ih*0.33"), (true, "ih*0.52")]
+        .into_iter()
+        .filter_map(|(downward, y)| {
+            let enable = spans
+                .iter()
+                .filter(|span| span.2 == downward)
+                .map(|(start, end, _)| format!("between(t,{start:.3},{end:.3})"))
+                .collect::<Vec<_>>();
+            (!enable.is_empty()).then(|| {
+                format!(
+                    "drawbox=x=iw-28:y={y}:w=10:h=ih*0.15:color=white@0.65:t=fill:enable='{}'",
+                    enable.join("+")
+                )
+            })
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -353,6 +1720,32 @@ fn hybrid_settings(intensity: CameraIntensity, smoothing: f64) -> HybridSettings
     }
 }
 
+/// 增益按 120ms 采样间隔调校；变间隔轨迹按实际时间差折算，保证跟随速度与采样密度无关。
+const REFERENCE_SAMPLE_MS: f64 = 120.0;
+
+impl HybridSettings {
+    fn scaled_for_interval(self, dt_ms: f64) -> Self {
+        let steps = (dt_ms / REFERENCE_SAMPLE_MS).clamp(0.0, 8.0);
+        let scale = |gain: f64| 1.0 - (1.0 - gain.clamp(0.0, 1.0)).powf(steps);
+        Self {
+            follow_gain: scale(self.follow_gain),
+            recenter_gain: scale(self.recenter_gain),
+            micro_follow_gain: scale(self.micro_follow_gain),
+            ..self
+        }
+    }
+
+    /// 单步跟随比例经缓动曲线映射，与 compute_motion_path 的处理一致。
+    fn eased(self, easing: CameraEasing) -> Self {
+        Self {
+            follow_gain: ease(easing, self.follow_gain),
+            recenter_gain: ease(easing, self.recenter_gain),
+            micro_follow_gain: ease(easing, self.micro_follow_gain),
+            ..self
+        }
+    }
+}
+
 fn follow_with_dead_zone(center: f64, target: f64, settings: HybridSettings) -> f64 {
     let delta = target - center;
     if delta.abs() <= settings.dead_zone {
@@ -367,28 +1760,74 @@ fn build_cursor_position_expr(
     points: &[CursorPoint],
     source_w: f64,
     source_h: f64,
-    smoothing: f64,
-    idle_threshold_ms: f64,
-    intensity: CameraIntensity,
+    motion: &CameraMotionProfile,
 ) -> Option<(String, String)> {
+    // FFmpeg 表达式嵌套层数有限，分段过多会导致 crop 表达式解析失败。
+    const MAX_SEGMENTS: usize = 64;
+    let full_smooth_points = smooth_camera_path(points, source_w, source_h, motion)?;
+
+    let step = full_smooth_points.len().div_ceil(MAX_SEGMENTS).max(1);
+    let mut smooth_points = full_smooth_points
+        .iter()
+        .step_by(step)
+        .copied()
+        .collect::<Vec<_>>();
+    if let Some(last) = full_smooth_points.last().copied() {
+        if smooth_points
+            .last()
+            .map(|item| (item.0 - last.0).abs() > 0.001)
+            .unwrap_or(true)
+        {
+            smooth_points.push(last);
+        }
+    }
+
+    let x_points = smooth_points
+        .iter()
+        .map(|(t, x, _)| (*t, *x))
+        .collect::<Vec<_>>();
+    let y_points = smooth_points
+        .iter()
+        .map(|(t, _, y)| (*t, *y))
+        .collect::<Vec<_>>();
+    Some((piecewise_expr(&x_points), piecewise_expr(&y_points)))
+}
+
+/// 在完整光标轨迹上计算镜头中心（归一化坐标）路径，返回 (t_sec, cx, cy)。
+fn smooth_camera_path(
+    points: &[CursorPoint],
+    source_w: f64,
+    source_h: f64,
+    motion: &CameraMotionProfile,
+) -> Option<Vec<(f64, f64, f64)>> {
     if points.is_empty() {
         return None;
     }
+    let smoothing = motion.smoothing as f64;
+    let idle_threshold_ms = motion.idle_threshold_ms as f64;
+    let intensity = motion.intensity.clone();
+    let model = motion.model;
     let safe_w = source_w.max(1.0);
     let safe_h = source_h.max(1.0);
-    let settings = hybrid_settings(intensity, smoothing);
+    let settings = hybrid_settings(intensity.clone(), smoothing);
     let effective_idle_threshold_ms = (idle_threshold_ms.clamp(120.0, 900.0)
         * (0.65 + smoothing.clamp(0.0, 1.0) * 0.20))
         .clamp(120.0, 900.0);
 
-    // FFmpeg 表达式嵌套层数有限，分段过多会导致 crop 表达式解析失败。
-    const MAX_SEGMENTS: usize = 64;
+    // 光标移到未录制的显示器时样本无意义，跳过这些片段，取景保持在最近的屏内位置。
     let normalized = points
         .iter()
+        .filter(|point| !point.offscreen)
         .map(|point| {
             let nx = (point.x / safe_w).clamp(0.02, 0.98);
             let ny = (point.y / safe_h).clamp(0.02, 0.98);
-            (point.t_sec, nx, ny)
+            (
+                point.t_sec,
+                nx,
+                ny,
+                point.scroll_dy.abs() > f64::EPSILON,
+                point.pinned,
+            )
         })
         .collect::<Vec<_>>();
     if normalized.is_empty() {
@@ -404,53 +1843,162 @@ fn build_cursor_position_expr(
     let mut prev_cursor_x = normalized[0].1;
     let mut prev_cursor_y = normalized[0].2;
     let mut idle_acc_ms = 0.0;
-    for (t_sec, nx, ny) in normalized.into_iter().skip(1) {
+    let spring = SpringConfig::for_intensity(intensity, smoothing as f32);
+    let mut spring_x = SpringState {
+        position: center_x,
+        velocity: 0.0,
+    };
+    let mut spring_y = SpringState {
+        position: center_y,
+        velocity: 0.0,
+    };
+    for (t_sec, nx, ny, scrolling, pinned) in normalized.into_iter().skip(1) {
         let dt_ms = ((t_sec - prev_time).max(0.0)) * 1000.0;
         let movement = ((nx - prev_cursor_x).powi(2) + (ny - prev_cursor_y).powi(2)).sqrt();
-        if movement <= settings.movement_epsilon {
+        if movement <= settings.movement_epsilon && !scrolling && !pinned {
             idle_acc_ms += dt_ms;
         } else {
             idle_acc_ms = 0.0;
         }
 
-        if idle_acc_ms >= effective_idle_threshold_ms {
-            center_x += (0.5 - center_x) * settings.recenter_gain;
-            center_y += (0.5 - center_y) * settings.recenter_gain;
+        let step_settings = settings.scaled_for_interval(dt_ms).eased(motion.easing);
+        if model == CameraMotionModel::Spring {
+            let (target_x, target_y) = if scrolling && movement <= settings.movement_epsilon {
+                (spring_x.position, spring_y.position)
+            } else if idle_acc_ms >= effective_idle_threshold_ms {
+                (0.5, 0.5)
+            } else {
+                (nx, ny)
+            };
+            let dt_sec = dt_ms / 1000.0;
+            spring_x = spring_step(spring_x, target_x, dt_sec, spring);
+            spring_y = spring_step(spring_y, target_y, dt_sec, spring);
+            center_x = spring_x.position.clamp(0.03, 0.97);
+            center_y = spring_y.position.clamp(0.03, 0.97);
+        } else if scrolling && movement <= settings.movement_epsilon {
+            // 滚动阅读时光标基本静止，保持当前取景，避免被当作空闲而回中。
+        } else if idle_acc_ms >= effective_idle_threshold_ms {
+            center_x += (0.5 - center_x) * step_settings.recenter_gain;
+            center_y += (0.5 - center_y) * step_settings.recenter_gain;
         } else {
-            center_x = follow_with_dead_zone(center_x, nx, settings);
-            center_y = follow_with_dead_zone(center_y, ny, settings);
+            center_x = follow_with_dead_zone(center_x, nx, step_settings);
+            center_y = follow_with_dead_zone(center_y, ny, step_settings);
         }
         full_smooth_points.push((t_sec, center_x, center_y));
         prev_time = t_sec;
         prev_cursor_x = nx;
         prev_cursor_y = ny;
     }
+    Some(full_smooth_points)
+}
 
-    let step = full_smooth_points.len().div_ceil(MAX_SEGMENTS).max(1);
-    let mut smooth_points = full_smooth_points
+const CLICK_ZOOM_FACTOR: f64 = 1.5;
+const CLICK_ZOOM_RAMP_SEC: f64 = 0.3;
+// zoompan 表达式同样受嵌套层数限制，超出部分的点击不再放大。
+const MAX_CLICK_SPANS: usize = 24;
+
+/// 点击放大区间，时间已扣除裁剪起点；锚点是点击位置在取景窗口内的归一化坐标。
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ClickZoomSpan {
+    start: f64,
+    end: f64,
+    anchor_x: f64,
+    anchor_y: f64,
+}
+
+fn click_zoom_spans(
+    manifest: &ProjectManifest,
+    points: &[CursorPoint],
+    target_ar: f64,
+    source_w: f64,
+    source_h: f64,
+) -> Vec<ClickZoomSpan> {
+    let safe_w = source_w.max(1.0);
+    let safe_h = source_h.max(1.0);
+    let camera_path = if camera_follows_target(manifest, safe_w / safe_h, target_ar) {
+        smooth_camera_path(points, safe_w, safe_h, &manifest.camera_motion).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    // 与 crop 滤镜一致的取景窗口尺寸（归一化到源画面）。
+    let zoom = camera_zoom(manifest);
+    let (window_w, window_h) = if safe_w / safe_h > target_ar {
+        (target_ar * safe_h / zoom / safe_w, 1.0 / zoom)
+    } else {
+        (1.0 / zoom, safe_w / target_ar / zoom / safe_h)
+    };
+    let trim_start_sec = manifest.timeline.trim_start_ms as f64 / 1000.0;
+    let hold_sec = manifest.camera_motion.click_hold_ms as f64 / 1000.0;
+    let mut spans: Vec<ClickZoomSpan> = Vec::new();
+    for point in points
         .iter()
-        .step_by(step)
-        .copied()
-        .collect::<Vec<_>>();
-    if let Some(last) = full_smooth_points.last().copied() {
-        if smooth_points
-            .last()
-            .map(|item| (item.0 - last.0).abs() > 0.001)
-            .unwrap_or(true)
-        {
-            smooth_points.push(last);
+        .filter(|point| point.click && !point.offscreen)
+    {
+        let start = point.t_sec - trim_start_sec;
+        if start < 0.0 {
+            continue;
         }
+        let end = start + hold_sec + CLICK_ZOOM_RAMP_SEC * 2.0;
+        if let Some(last) = spans.last_mut() {
+            if start <= last.end {
+                // 放大期间的连续点击只延长保持时间，锚点不跳动。
+                last.end = end;
+                continue;
+            }
+        }
+        if spans.len() >= MAX_CLICK_SPANS {
+            break;
+        }
+        let index = camera_path
+            .partition_point(|item| item.0 <= point.t_sec)
+            .saturating_sub(1);
+        let (center_x, center_y) = camera_path
+            .get(index)
+            .map(|item| (item.1, item.2))
+            .unwrap_or((0.5, 0.5));
+        let left = (center_x - window_w / 2.0).clamp(0.0, (1.0 - window_w).max(0.0));
+        let top = (center_y - window_h / 2.0).clamp(0.0, (1.0 - window_h).max(0.0));
+        spans.push(ClickZoomSpan {
+            start,
+            end,
+            anchor_x: ((point.x / safe_w - left) / window_w).clamp(0.0, 1.0),
+            anchor_y: ((point.y / safe_h - top) / window_h).clamp(0.0, 1.0),
+        });
     }
+    spans
+}
 
-    let x_points = smooth_points
-        .iter()
-        .map(|(t, x, _)| (*t, *x))
-        .collect::<Vec<_>>();
-    let y_points = smooth_points
-        .iter()
-        .map(|(t, _, y)| (*t, *y))
-        .collect::<Vec<_>>();
-    Some((piecewise_expr(&x_points), piecewise_expr(&y_points)))
+/// 以点击位置为锚点缩放：锚点在画面中的位置保持不变，缓入、保持后缓出。
+fn build_click_zoom_filter(
+    spans: &[ClickZoomSpan],
+    target_w: u32,
+    target_h: u32,
+    fps: u8,
+) -> Option<String> {
+    if spans.is_empty() {
+        return None;
+    }
+    let mut zoom_expr = "1".to_string();
+    let mut x_expr = "0".to_string();
+    let mut y_expr = "0".to_string();
+    for span in spans.iter().rev() {
+        let ClickZoomSpan {
+            start,
+            end,
+            anchor_x,
+            anchor_y,
+        } = *span;
+        let window = format!("between(it,{start:.3},{end:.3})");
+        zoom_expr = format!(
+            "if({window},1+{:.3}*min(1,min((it-{start:.3})/{CLICK_ZOOM_RAMP_SEC:.3},({end:.3}-it)/{CLICK_ZOOM_RAMP_SEC:.3})),{zoom_expr})",
+            CLICK_ZOOM_FACTOR - 1.0
+        );
+        x_expr = format!("if({window},(iw-iw/zoom)*{anchor_x:.4},{x_expr})");
+        y_expr = format!("if({window},(ih-ih/zoom)*{anchor_y:.4},{y_expr})");
+    }
+    Some(format!(
+        "zoompan=z='{zoom_expr}':x='{x_expr}':y='{y_expr}':d=1:s={target_w}x{target_h}:fps={fps}"
+    ))
 }
 
 fn piecewise_expr(points: &[(f64, f64)]) -> String {
@@ -488,8 +2036,156 @@ fn output_resolution(resolution: Resolution, aspect_ratio: AspectRatio) -> (u32,
 
 #[cfg(test)]
 mod tests {
-    use super::{camera_zoom, classify_export_error};
-    use crate::domain::models::{CameraIntensity, ProjectManifest};
+    use super::{
+        apply_window_focus, build_audio_filters, build_camera_keyframes, build_click_zoom_filter,
+        build_concat_filter, build_crop_filter, build_cursor_position_expr, build_fit_filter,
+        build_interpolation_filter, build_scroll_indicator_filters, build_subtitle_filter,
+        build_video_denoise_filter, camera_zoom, classify_export_error, click_zoom_spans,
+        encoder_thread_limit, escape_filter_path, export_remap_segments, fit_dimensions,
+        hybrid_settings, lossless_trim_eligible, parse_benchmark_cpu_ms, CursorPoint, StitchClip,
+        StreamSummary,
+    };
+    use crate::domain::models::{
+        AppFocusSegment, AudioDenoise, CameraIntensity, CameraMode, CameraMotionProfile,
+        ExportProfile, FitMode, OverlayPosition, ProjectManifest, TimelineSegment, VideoDenoise,
+        WindowRect,
+    };
+
+    #[test]
+    fn video_denoise_levels_map_to_filters() {
+        assert_eq!(build_video_denoise_filter(VideoDenoise::Off), None);
+        assert_eq!(
+            build_video_denoise_filter(VideoDenoise::Medium).as_deref(),
+            Some("hqdn3d=4:3:6:4.5")
+        );
+        assert!(build_video_denoise_filter(VideoDenoise::Strong)
+            .is_some_and(|filter| filter.starts_with("nlmeans=")));
+    }
+
+    #[test]
+    fn interpolation_only_raises_frame_rate() {
+        assert_eq!(
+            build_interpolation_filter(29.97, 60).as_deref(),
+            Some("minterpolate=fps=60:mi_mode=mci:mc_mode=aobmc:me_mode=bidir:vsbmc=1")
+        );
+        assert_eq!(build_interpolation_filter(59.94, 60), None);
+        assert_eq!(build_interpolation_filter(60.0, 30), None);
+    }
+
+    #[test]
+    fn audio_denoise_prefers_rnnoise_model_when_available() {
+        assert_eq!(build_audio_filters(AudioDenoise::Off, None), None);
+        assert_eq!(
+            build_audio_filters(AudioDenoise::Strong, None).as_deref(),
+            Some("highpass=f=80,afftdn=nr=24:nf=-35")
+        );
+        let model = std::path::Path::new("C:\\models\\std.rnnn");
+        assert_eq!(
+            build_audio_filters(AudioDenoise::Strong, Some(model)).as_deref(),
+            Some("highpass=f=80,arnndn=m='C\\:/models/std.rnnn'")
+        );
+    }
+
+    #[test]
+    fn lossless_trim_requires_plain_timeline_and_matching_output() {
+        let mut manifest = ProjectManifest::default();
+        manifest.camera_motion.enabled = false;
+        manifest.timeline.cursor_highlight_enabled = false;
+        manifest.timeline.scroll_indicator_enabled = false;
+        manifest.scene.enabled = false;
+        let source = |width, height, frame_rate| StreamSummary {
+            width: Some(width),
+            height: Some(height),
+            frame_rate: Some(frame_rate),
+            has_audio: true,
+        };
+        let matching = source(1920, 1080, 30.0);
+        let profile = ExportProfile::default();
+        assert!(lossless_trim_eligible(&manifest, &profile, Some(&matching)));
+        for mismatched in [
+            source(2560, 1440, 30.0),
+            source(1440, 900, 30.0),
+            source(1920, 1080, 60.0),
+        ] {
+            assert!(!lossless_trim_eligible(
+                &manifest,
+                &profile,
+                Some(&mismatched)
+            ));
+        }
+        assert!(!lossless_trim_eligible(&manifest, &profile, None));
+
+        let size_capped = ExportProfile {
+            max_size_mb: Some(25),
+            ..ExportProfile::default()
+        };
+        assert!(!lossless_trim_eligible(
+            &manifest,
+            &size_capped,
+            Some(&matching)
+        ));
+        let denoised = ExportProfile {
+            audio_denoise: AudioDenoise::Light,
+            ..ExportProfile::default()
+        };
+        assert!(!lossless_trim_eligible(
+            &manifest,
+            &denoised,
+            Some(&matching)
+        ));
+        let video_denoised = ExportProfile {
+            video_denoise: VideoDenoise::Light,
+            ..ExportProfile::default()
+        };
+        assert!(!lossless_trim_eligible(
+            &manifest,
+            &video_denoised,
+            Some(&matching)
+        ));
+        manifest.camera_motion.enabled = true;
+        assert!(!lossless_trim_eligible(
+            &manifest,
+            &profile,
+            Some(&matching)
+        ));
+    }
+
+    #[test]
+    fn concat_filter_fills_silence_for_clips_without_audio() {
+        let clips = vec![
+            StitchClip {
+                path: "intro.mp4".into(),
+                duration_ms: 2500,
+                has_audio: false,
+            },
+            StitchClip {
+                path: "output_main.mp4".into(),
+                duration_ms: 60_000,
+                has_audio: true,
+            },
+        ];
+        let graph = build_concat_filter(&clips, 1920, 1080, 30);
+        assert!(graph.contains("anullsrc=r=48000:cl=stereo,atrim=duration=2.500[a0]"));
+        assert!(graph.contains("[1:a]aresample=48000"));
+        assert!(graph.contains("pad=1920:1080:(ow-iw)/2:(oh-ih)/2,setsar=1,fps=30"));
+        assert!(graph.ends_with("[v0][a0][v1][a1]concat=n=2:v=1:a=1[vout][aout]"));
+    }
+
+    #[test]
+    fn blur_background_fits_widescreen_into_vertical_frame() {
+        assert_eq!(
+            fit_dimensions(FitMode::BlurBackground, 16.0 / 9.0, 1080, 1920),
+            (1080, 608)
+        );
+        assert_eq!(
+            fit_dimensions(FitMode::Crop, 16.0 / 9.0, 1080, 1920),
+            (1080, 1920)
+        );
+        assert_eq!(build_fit_filter(FitMode::Crop, 1080, 1920), None);
+        let graph = build_fit_filter(FitMode::BlurBackground, 1080, 1920).expect("blur graph");
+        assert!(graph.starts_with("split=2[fit_fg][fit_bg];"));
+        assert!(graph.ends_with("[fit_blur][fit_fg]overlay=(W-w)/2:(H-h)/2"));
+    }
 
     #[test]
     fn classify_permission_error() {
@@ -503,6 +2199,28 @@ mod tests {
         assert_eq!(err.code, "NO_SPACE");
     }
 
+    #[test]
+    fn encoder_threads_combine_setting_and_background_mode() {
+        assert_eq!(encoder_thread_limit(0, false, 8), None);
+        assert_eq!(encoder_thread_limit(0, true, 8), Some(4));
+        assert_eq!(encoder_thread_limit(0, true, 1), Some(1));
+        assert_eq!(encoder_thread_limit(6, false, 8), Some(6));
+        assert_eq!(encoder_thread_limit(6, true, 8), Some(4));
+        assert_eq!(encoder_thread_limit(2, true, 8), Some(2));
+    }
+
+    #[test]
+    fn benchmark_cpu_time_sums_every_invocation() {
+        let stderr = "\
+frame=  300 fps=120 q=-1.0 Lsize=    2048kB time=00:00:10.00
+bench: utime=3.250s stime=0.250s rtime=2.100s
+bench: maxrss=204800KiB
+---- stitch ----
+bench: utime=0.400s stime=0.100s rtime=0.600s";
+        assert_eq!(parse_benchmark_cpu_ms(stderr), 4_000);
+        assert_eq!(parse_benchmark_cpu_ms("no stderr output"), 0);
+    }
+
     #[test]
     fn camera_zoom_should_respect_user_cap() {
         let mut manifest = ProjectManifest::default();
@@ -524,4 +2242,270 @@ mod tests {
         let zoom = camera_zoom(&manifest);
         assert!(zoom > 1.35);
     }
+
+    #[test]
+    fn camera_mode_controls_zoom_and_follow() {
+        let dir =
+            std::env::temp_dir().join(format!("focuslens-camera-mode-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let track = dir.join("cursor.json");
+        std::fs::write(
+            &track,
+            r#"[{"tMs":0,"x":200,"y":200},{"tMs":1000,"x":1700,"y":900},{"tMs":2000,"x":1700,"y":900}]"#,
+        )
+        .unwrap();
+        let keyframes = dir.join("camera.cmd");
+        let mut manifest = ProjectManifest::default();
+        manifest.camera_motion.enabled = true;
+        manifest.artifacts.cursor_track_path = Some(track.to_string_lossy().to_string());
+
+        let follow = build_crop_filter(&manifest, 16.0 / 9.0, 1920.0, 1080.0, 30, &keyframes);
+        assert!(follow.starts_with("sendcmd="));
+
+        manifest.camera_motion.mode = CameraMode::PanOnly;
+        assert_eq!(camera_zoom(&manifest), 1.0);
+        let pan = build_crop_filter(&manifest, 9.0 / 16.0, 1920.0, 1080.0, 30, &keyframes);
+        assert!(pan.starts_with("sendcmd="));
+
+        manifest.camera_motion.mode = CameraMode::ZoomOnly;
+        assert!(camera_zoom(&manifest) > 1.0);
+        let centered = build_crop_filter(&manifest, 16.0 / 9.0, 1920.0, 1080.0, 30, &keyframes);
+        assert!(centered.ends_with(":x='(iw-ow)/2':y='(ih-oh)/2'"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn vertical_export_follows_cursor_without_camera_motion() {
+        let dir =
+            std::env::temp_dir().join(format!("focuslens-vertical-reframe-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let track = dir.join("cursor.json");
+        std::fs::write(
+            &track,
+            r#"[{"tMs":0,"x":200,"y":500},{"tMs":1000,"x":1700,"y":500},{"tMs":2000,"x":1700,"y":500}]"#,
+        )
+        .unwrap();
+        let keyframes = dir.join("camera.cmd");
+        let mut manifest = ProjectManifest::default();
+        manifest.camera_motion.enabled = false;
+        manifest.artifacts.cursor_track_path = Some(track.to_string_lossy().to_string());
+
+        let vertical = build_crop_filter(&manifest, 9.0 / 16.0, 1920.0, 1080.0, 30, &keyframes);
+        assert!(vertical.starts_with("sendcmd="));
+        let landscape = build_crop_filter(&manifest, 16.0 / 9.0, 1920.0, 1080.0, 30, &keyframes);
+        assert!(landscape.ends_with(":x='(iw-ow)/2':y='(ih-oh)/2'"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn scroll_indicator_merges_consecutive_scroll_samples() {
+        let point = |t_sec: f64, scroll_dy: f64| CursorPoint {
+            t_sec,
+            x: 0.0,
+            y: 0.0,
+            scroll_dy,
+            offscreen: false,
+            click: false,
+            pinned: false,
+        };
+        let points = vec![
+            point(0.2, 1.0),
+            point(1.0, 1.0),
+            point(1.12, 2.0),
+            point(1.24, 0.0),
+            point(3.0, -1.0),
+            point(4.0, 1.0),
+            point(5.9, 1.0),
+            point(7.0, 1.0),
+        ];
+        let filters = build_scroll_indicator_filters(&points, 0.5, Some(6.0));
+        assert_eq!(filters.len(), 2);
+        assert!(filters[0].contains("ih*0.33"));
+        assert!(filters[0].contains("enable='between(t,2.500,2.900)'"));
+        assert!(filters[1].contains("ih*0.52"));
+        assert!(filters[1].contains(
+            "enable='between(t,0.500,1.020)+between(t,3.500,3.900)+between(t,5.400,5.500)'"
+        ));
+    }
+
+    #[test]
+    fn cursor_expr_ignores_offscreen_samples() {
+        let point = |t_sec: f64, x: f64, offscreen: bool| CursorPoint {
+            t_sec,
+            x,
+            y: 540.0,
+            scroll_dy: 0.0,
+            offscreen,
+            click: false,
+            pinned: false,
+        };
+        let with_offscreen = vec![
+            point(0.0, 400.0, false),
+            point(0.5, 1919.0, true),
+            point(1.0, 800.0, false),
+        ];
+        let onscreen_only = vec![point(0.0, 400.0, false), point(1.0, 800.0, false)];
+        let motion = CameraMotionProfile {
+            smoothing: 0.5,
+            idle_threshold_ms: 400,
+            ..CameraMotionProfile::default()
+        };
+        let expr =
+            |points: &[CursorPoint]| build_cursor_position_expr(points, 1920.0, 1080.0, &motion);
+        assert_eq!(expr(&with_offscreen), expr(&onscreen_only));
+        assert!(expr(&[point(0.0, 1919.0, true)]).is_none());
+    }
+
+    #[test]
+    fn hybrid_gains_scale_with_sample_interval() {
+        let settings = hybrid_settings(CameraIntensity::Medium, 0.5);
+        let reference = settings.scaled_for_interval(120.0);
+        assert!((reference.follow_gain - settings.follow_gain).abs() < 1e-9);
+        let half = settings.scaled_for_interval(60.0);
+        let two_half_steps = 1.0 - (1.0 - half.follow_gain).powi(2);
+        assert!((two_half_steps - settings.follow_gain).abs() < 1e-9);
+    }
+
+    #[test]
+    fn click_zoom_merges_overlapping_clicks() {
+        let mut manifest = ProjectManifest::default();
+        manifest.camera_motion.zoom_on_click = true;
+        manifest.camera_motion.click_hold_ms = 500;
+        let point = |t_sec: f64, click: bool| CursorPoint {
+            t_sec,
+            x: 960.0,
+            y: 540.0,
+            scroll_dy: 0.0,
+            offscreen: false,
+            click,
+            pinned: false,
+        };
+        let points = vec![
+            point(0.0, false),
+            point(1.0, true),
+            point(1.5, true),
+            point(4.0, true),
+        ];
+        let spans = click_zoom_spans(&manifest, &points, 16.0 / 9.0, 1920.0, 1080.0);
+        assert_eq!(spans.len(), 2);
+        assert!((spans[0].start - 1.0).abs() < 1e-6);
+        assert!((spans[0].end - 2.6).abs() < 1e-6);
+        assert!((spans[0].anchor_x - 0.5).abs() < 1e-6);
+        let filter = build_click_zoom_filter(&spans, 1920, 1080, 30).unwrap_or_default();
+        assert!(filter.starts_with("zoompan=z='if(between(it,1.000,2.600)"));
+        assert!(filter.ends_with(":d=1:s=1920x1080:fps=30"));
+        assert!(build_click_zoom_filter(&[], 1920, 1080, 30).is_none());
+    }
+
+    #[test]
+    fn camera_keyframes_follow_trimmed_timeline() {
+        let path = vec![(0.0, 0.5, 0.5), (2.0, 0.7, 0.5), (4.0, 0.7, 0.5)];
+        let keyframes = build_camera_keyframes(&path, 1.0, None, 10);
+        let lines = keyframes.commands.lines().collect::<Vec<_>>();
+        assert_eq!(keyframes.first, Some((0.6, 0.5)));
+        assert!(lines[0].starts_with("0.0000 crop@camera x iw*0.600000-ow/2,"));
+        // 2s 之后镜头静止，不再重复下发命令。
+        assert_eq!(lines.len(), 11);
+        assert!(lines[10].starts_with("1.0000 "));
+        assert!(build_camera_keyframes(&[], 0.0, None, 30).first.is_none());
+    }
+
+    #[test]
+    fn subtitle_filter_shifts_trimmed_timeline_and_scales_font() {
+        let profile = ExportProfile {
+            subtitle_font_size: 42,
+            subtitle_position: OverlayPosition::Top,
+            ..ExportProfile::default()
+        };
+        let filter = build_subtitle_filter(
+            std::path::Path::new("C:\\captions\\demo.srt"),
+            &profile,
+            1500,
+            1920,
+            1080,
+        );
+        assert_eq!(
+            filter,
+            "setpts=PTS+1.500/TB,subtitles=filename='C\\:/captions/demo.srt':force_style='FontSize=11,Alignment=8,MarginV=16,Outline=1,Shadow=0',setpts=PTS-1.500/TB"
+        );
+        let ass = build_subtitle_filter(
+            std::path::Path::new("/tmp/demo.ASS"),
+            &profile,
+            0,
+            1080,
+            1920,
+        );
+        assert_eq!(ass, "ass=filename='/tmp/demo.ASS'");
+    }
+
+    #[test]
+    fn filter_path_escapes_drive_colon() {
+        let path = std::path::Path::new("C:\\Users\\demo\\camera_keyframes.cmd");
+        assert_eq!(
+            escape_filter_path(path),
+            "C\\:/Users/demo/camera_keyframes.cmd"
+        );
+    }
+
+    #[test]
+    fn window_focus_targets_active_window_center() {
+        let point = |t_sec: f64| CursorPoint {
+            t_sec,
+            x: 100.0,
+            y: 100.0,
+            scroll_dy: 0.0,
+            offscreen: false,
+            click: false,
+            pinned: false,
+        };
+        let segments = vec![
+            AppFocusSegment {
+                start_ms: 0,
+                end_ms: 1000,
+                process_name: "code.exe".to_string(),
+                window_title: "main.rs".to_string(),
+                window_rect: Some(WindowRect {
+                    x: 800.0,
+                    y: 200.0,
+                    width: 400.0,
+                    height: 300.0,
+                }),
+            },
+            AppFocusSegment {
+                start_ms: 1000,
+                end_ms: 2000,
+                process_name: "explorer.exe".to_string(),
+                window_title: String::new(),
+                window_rect: None,
+            },
+        ];
+        let focused = apply_window_focus(&[point(0.5), point(1.5)], &segments);
+        assert_eq!((focused[0].x, focused[0].y), (1000.0, 350.0));
+        assert!(focused[0].pinned);
+        assert_eq!((focused[1].x, focused[1].y), (100.0, 100.0));
+        assert!(!focused[1].pinned);
+    }
+
+    #[test]
+    fn segment_speed_becomes_remap_speed() {
+        let mut manifest = ProjectManifest::default();
+        manifest.timeline.trim_start_ms = 1_000;
+        manifest.timeline.segments = vec![
+            TimelineSegment {
+                start_ms: 1_000,
+                end_ms: 3_000,
+                speed: 2.0,
+            },
+            TimelineSegment {
+                start_ms: 4_000,
+                end_ms: 6_000,
+                speed: 0.5,
+            },
+        ];
+        let remap = export_remap_segments(&manifest, 5_000)
+            .iter()
+            .map(|segment| (segment.start_sec, segment.end_sec, segment.speed))
+            .collect::<Vec<_>>();
+        assert_eq!(remap, vec![(0.0, 2.0, 2.0), (3.0, 5.0, 0.5)]);
+    }
 }
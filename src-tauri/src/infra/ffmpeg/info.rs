@@ -0,0 +1,367 @@
+use crate::domain::models::{AppError, FfmpegInfo};
+use crate::infra::ffmpeg::command::{output_with_timeout, probe_timeout};
+use crate::infra::ffmpeg::installer::resolve_binary;
+use std::process::Command;
+
+/// `-progress pipe:1` 的 `out_time_us`、`freezedetect` 等依赖 4.x 之后的行为。
+const MIN_MAJOR_VERSION: u32 = 4;
+const REQUIRED_ENCODERS: [&str; 2] = ["libx264", "aac"];
+/// 导出与镜头运动必需的滤镜。
+const REQUIRED_FILTERS: [&str; 9] = [
+    "scale", "crop", "pad", "overlay", "setpts", "concat", "format", "zoompan", "geq",
+];
+/// 缺失时只影响单项功能：按键与时间码叠加、调色、音视频降噪、补帧与质量门检测。
+const OPTIONAL_FILTERS: [(&str, &str); 11] = [
+    ("drawtext", "keystroke and timecode overlay"),
+    ("eq", "color adjustment"),
+    ("lut3d", "color LUT"),
+    ("hqdn3d", "video denoise"),
+    ("nlmeans", "video denoise"),
+    ("minterpolate", "frame interpolation"),
+    ("arnndn", "noise reduction"),
+    ("afftdn", "noise reduction"),
+    ("silencedetect", "silence detection"),
+    ("blackdetect", "quality gate"),
+    ("freezedetect", "quality gate"),
+];
+
+/// 录制所需的采集设备，按平台区分。
+fn required_devices() -> &'static [&'static str] {
+    #[cfg(target_os = "windows")]
+    {
+        &["gdigrab", "dshow"]
+    }
+    #[cfg(target_os = "macos")]
+    {
+        &["avfoundation"]
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        &[]
+    }
+}
+
+fn optional_devices() -> &'static [&'static str] {
+    #[cfg(target_os = "windows")]
+    {
+        &["wasapi"]
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        &[]
+    }
+}
+
+/// 汇总当前 ffmpeg 的版本、编译配置与编码器/设备/滤镜清单，并给出兼容性问题。
+/// `errors` 非空时录制或导出必然失败，`warnings` 只影响部分功能。
+pub fn ffmpeg_info() -> FfmpegInfo {
+    let (path, source) = resolve_binary("ffmpeg", "FOCUSLENS_FFMPEG_PATH");
+    let Some(version_output) = run_listing(&path, "-version") else {
+        return FfmpegInfo {
+            path,
+            source,
+            available: false,
+            version: None,
+            major_version: None,
+            configuration: Vec::new(),
+            encoders: Vec::new(),
+            devices: Vec::new(),
+            filters: Vec::new(),
+            compatible: false,
+            errors: vec![AppError::new(
+                "FFMPEG_NOT_FOUND",
+                "failed to execute ffmpeg -version",
+                Some("可在设置中一键安装 ffmpeg，或设置 FOCUSLENS_FFMPEG_PATH".to_string()),
+            )],
+            warnings: Vec::new(),
+        };
+    };
+    let version = parse_version(&version_output);
+    let major_version = version.as_deref().and_then(parse_major_version);
+    let encoders = parse_listing(&run_listing(&path, "-encoders").unwrap_or_default());
+    let devices = parse_listing(&run_listing(&path, "-devices").unwrap_or_default());
+    let filters = parse_listing(&run_listing(&path, "-filters").unwrap_or_default());
+    let (errors, warnings) = check_compatibility(
+        version.as_deref(),
+        major_version,
+        &encoders,
+        &devices,
+        &filters,
+        required_devices(),
+        optional_devices(),
+    );
+    FfmpegInfo {
+        path,
+        source,
+        available: true,
+        version,
+        major_version,
+        configuration: parse_configuration(&version_output),
+        encoders,
+        devices,
+        filters,
+        compatible: errors.is_empty(),
+        errors,
+        warnings,
+    }
+}
+
+fn run_listing(path: &str, flag: &str) -> Option<String> {
+    let output = output_with_timeout(
+        Command::new(path).arg("-hide_banner").arg(flag),
+        probe_timeout(),
+    )
+    .ok()
+    .filter(|output| output.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 首行形如 `ffmpeg version 6.1.1-full_build-www.gyan.dev Copyright ...`。
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("ffmpeg version "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .map(str::to_string)
+}
+
+/// 发行版版本号取主版本；`N-113000-g...` 之类的 git 构建无法判断，返回 None。
+fn parse_major_version(version: &str) -> Option<u32> {
+    let version = version.strip_prefix('n').unwrap_or(version);
+    let digits = version
+        .chars()
+        .take_while(|ch| ch.is_ascii_digit())
+        .collect::<String>();
+    digits.parse().ok()
+}
+
+fn parse_configuration(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("configuration:"))
+        .map(|flags| flags.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// 解析 `-encoders`/`-devices`/`-filters` 的表格输出：跳过 `------` 分隔线之前的图例，
+/// 每行第一列是标志位、第二列是名称。`-filters` 没有分隔线，以标志列形如 `T.C` 识别。
+fn parse_listing(output: &str) -> Vec<String> {
+    let has_separator = output.lines().any(|line| line.trim().starts_with("---"));
+    let mut in_body = !has_separator;
+    let mut names = Vec::new();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("---") {
+            in_body = true;
+            continue;
+        }
+        if !in_body {
+            continue;
+        }
+        let mut columns = trimmed.split_whitespace();
+        let (Some(flags), Some(name)) = (columns.next(), columns.next()) else {
+            continue;
+        };
+        if name == "="
+            || !flags
+                .chars()
+                .all(|ch| ch.is_ascii_uppercase() || ch == '.' || ch == '|')
+        {
+            continue;
+        }
+        names.push(name.to_string());
+    }
+    names
+}
+
+fn check_compatibility(
+    version: Option<&str>,
+    major_version: Option<u32>,
+    encoders: &[String],
+    devices: &[String],
+    filters: &[String],
+    required_devices: &[&str],
+    optional_devices: &[&str],
+) -> (Vec<AppError>, Vec<AppError>) {
+    let has = |list: &[String], name: &str| list.iter().any(|item| item == name);
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    if let Some(major) = major_version.filter(|major| *major < MIN_MAJOR_VERSION) {
+        errors.push(
+            AppError::new(
+                "FFMPEG_VERSION_UNSUPPORTED",
+                format!(
+                    "ffmpeg {} is older than the minimum supported {MIN_MAJOR_VERSION}.0",
+                    version.unwrap_or_default()
+                ),
+                Some("请升级 ffmpeg，或在设置中安装推荐版本".to_string()),
+            )
+            .with_param("version", major)
+            .with_param("min", MIN_MAJOR_VERSION),
+        );
+    }
+    for encoder in REQUIRED_ENCODERS {
+        if !has(encoders, encoder) {
+            errors.push(
+                AppError::new(
+                    "FFMPEG_MISSING_ENCODER",
+                    format!("ffmpeg is built without encoder {encoder}"),
+                    Some("请改用包含 libx264 的完整构建（如 full/gpl 版本）".to_string()),
+                )
+                .with_param("name", encoder),
+            );
+        }
+    }
+    for device in required_devices {
+        if !has(devices, device) {
+            errors.push(
+                AppError::new(
+                    "FFMPEG_MISSING_DEVICE",
+                    format!("ffmpeg is built without capture device {device}"),
+                    Some("当前 ffmpeg 无法录屏，请改用官方完整构建".to_string()),
+                )
+                .with_param("name", *device),
+            );
+        }
+    }
+    for device in optional_devices {
+        if !has(devices, device) {
+            warnings.push(
+                AppError::new(
+                    "FFMPEG_MISSING_DEVICE",
+                    format!("ffmpeg is built without capture device {device}"),
+                    Some("系统音频录制将不可用".to_string()),
+                )
+                .with_param("name", *device),
+            );
+        }
+    }
+    for filter in REQUIRED_FILTERS {
+        if !has(filters, filter) {
+            errors.push(
+                AppError::new(
+                    "FFMPEG_MISSING_FILTER",
+                    format!("ffmpeg is built without filter {filter}"),
+                    Some("导出将失败，请改用官方完整构建".to_string()),
+                )
+                .with_param("name", filter),
+            );
+        }
+    }
+    for (filter, feature) in OPTIONAL_FILTERS {
+        if !has(filters, filter) {
+            warnings.push(
+                AppError::new(
+                    "FFMPEG_MISSING_FILTER",
+                    format!("ffmpeg is built without filter {filter}, {feature} is unavailable"),
+                    None,
+                )
+                .with_param("name", filter)
+                .with_param("feature", feature),
+            );
+        }
+    }
+    (errors, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_compatibility, parse_configuration, parse_listing, parse_major_version,
+        parse_version, OPTIONAL_FILTERS, REQUIRED_ENCODERS, REQUIRED_FILTERS,
+    };
+
+    const VERSION_OUTPUT: &str = "\
+ffmpeg version 6.1.1-full_build-www.gyan.dev Copyright (c) 2000-2023 the FFmpeg developers
+built with gcc 12.2.0 (Rev10, Built by MSYS2 project)
+configuration: --enable-gpl --enable-version3 --enable-libx264
+libavutil      58. 29.100 / 58. 29.100";
+
+    #[test]
+    fn version_and_configuration_are_parsed() {
+        assert_eq!(
+            parse_version(VERSION_OUTPUT).as_deref(),
+            Some("6.1.1-full_build-www.gyan.dev")
+        );
+        assert_eq!(
+            parse_configuration(VERSION_OUTPUT),
+            vec!["--enable-gpl", "--enable-version3", "--enable-libx264"]
+        );
+        assert_eq!(parse_major_version("6.1.1-full_build"), Some(6));
+        assert_eq!(parse_major_version("n7.0"), Some(7));
+        assert_eq!(parse_major_version("N-113000-gabcdef"), None);
+    }
+
+    #[test]
+    fn listings_skip_legend_and_keep_names() {
+        let encoders = "\
+Encoders:
+ V..... = Video
+ A..... = Audio
+ ------
+ V....D libx264              libx264 H.264 / AVC
+ A....D aac                  AAC (Advanced Audio Coding)";
+        assert_eq!(parse_listing(encoders), vec!["libx264", "aac"]);
+
+        let devices = "\
+Devices:
+ D. = Demuxing supported
+ E. = Muxing supported
+ ---
+ D  dshow           DirectShow capture
+ D  gdigrab         GDI API Windows frame grabber";
+        assert_eq!(parse_listing(devices), vec!["dshow", "gdigrab"]);
+
+        let filters = "\
+ ... scale             V->V       Scale the input video size.
+ T.C overlay           VV->V      Overlay a video source on top of the input.
+ ... silencedetect     A->A       Detect silence.";
+        assert_eq!(
+            parse_listing(filters),
+            vec!["scale", "overlay", "silencedetect"]
+        );
+    }
+
+    #[test]
+    fn missing_requirements_are_errors_and_optional_ones_warnings() {
+        let names = |list: &[&str]| list.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        let encoders = names(&REQUIRED_ENCODERS);
+        let mut filters = names(&REQUIRED_FILTERS);
+        filters.extend(OPTIONAL_FILTERS.iter().map(|(name, _)| name.to_string()));
+        let devices = names(&["gdigrab", "dshow"]);
+
+        let (errors, warnings) = check_compatibility(
+            Some("6.1"),
+            Some(6),
+            &encoders,
+            &devices,
+            &filters,
+            &["gdigrab", "dshow"],
+            &[],
+        );
+        assert!(errors.is_empty() && warnings.is_empty());
+
+        let (errors, warnings) = check_compatibility(
+            Some("3.4.2"),
+            Some(3),
+            &names(&["aac"]),
+            &names(&["dshow"]),
+            &names(&REQUIRED_FILTERS),
+            &["gdigrab", "dshow"],
+            &["wasapi"],
+        );
+        let codes = errors
+            .iter()
+            .map(|error| (error.code.as_str(), error.params.get("name").cloned()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                ("FFMPEG_VERSION_UNSUPPORTED", None),
+                ("FFMPEG_MISSING_ENCODER", Some("libx264".to_string())),
+                ("FFMPEG_MISSING_DEVICE", Some("gdigrab".to_string())),
+            ]
+        );
+        assert_eq!(warnings.len(), 1 + OPTIONAL_FILTERS.len());
+    }
+}
@@ -0,0 +1,401 @@
+use crate::domain::models::{
+    AppError, FfmpegInstallProgressEvent, FfmpegInstallStatus, FfmpegSource,
+};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// 托管副本所在目录（应用数据目录下的 `ffmpeg/`），启动时设置一次。
+static MANAGED_DIR: OnceLock<PathBuf> = OnceLock::new();
+static INSTALLING: AtomicBool = AtomicBool::new(false);
+
+const MANAGED_BINARIES: [&str; 2] = ["ffmpeg", "ffprobe"];
+const DOWNLOAD_FILE: &str = "download.zip.part";
+const DOWNLOAD_CHUNK: usize = 64 * 1024;
+const CONNECT_TIMEOUT_SEC: u64 = 15;
+const READ_TIMEOUT_SEC: u64 = 60;
+
+/// 固定版本的静态构建：zip 压缩包，内含 ffmpeg 与 ffprobe。
+#[derive(Debug, Clone)]
+pub struct PinnedBuild {
+    pub version: Option<String>,
+    pub url: String,
+    pub sha256: String,
+}
+
+pub fn set_managed_dir(dir: PathBuf) {
+    let _ = MANAGED_DIR.set(dir);
+}
+
+pub fn executable_name(binary: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("{binary}.exe")
+    } else {
+        binary.to_string()
+    }
+}
+
+/// 已下载到应用数据目录的副本，未安装时返回 None。
+pub fn managed_binary(binary: &str) -> Option<PathBuf> {
+    MANAGED_DIR
+        .get()
+        .map(|dir| dir.join(executable_name(binary)))
+        .filter(|path| path.is_file())
+}
+
+/// 安装包内置的 Tauri sidecar，打包后与主程序位于同一目录。
+pub fn sidecar_binary(binary: &str) -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    Some(exe.parent()?.join(executable_name(binary))).filter(|path| path.is_file())
+}
+
+/// 按 环境变量 > 托管下载 > sidecar > PATH 的顺序解析可执行文件。
+pub fn resolve_binary(binary: &str, env_key: &str) -> (String, FfmpegSource) {
+    if let Ok(path) = std::env::var(env_key) {
+        return (path, FfmpegSource::Env);
+    }
+    if let Some(path) = managed_binary(binary) {
+        return (path.to_string_lossy().to_string(), FfmpegSource::Managed);
+    }
+    if let Some(path) = sidecar_binary(binary) {
+        return (path.to_string_lossy().to_string(), FfmpegSource::Sidecar);
+    }
+    (binary.to_string(), FfmpegSource::System)
+}
+
+/// 下载地址与校验和由发布流水线在编译期注入，运行时可用同名环境变量覆盖（如内网镜像）。
+pub fn pinned_build() -> Option<PinnedBuild> {
+    let url = config_value(
+        "FOCUSLENS_FFMPEG_DOWNLOAD_URL",
+        option_env!("FOCUSLENS_FFMPEG_DOWNLOAD_URL"),
+    )?;
+    let sha256 = config_value(
+        "FOCUSLENS_FFMPEG_DOWNLOAD_SHA256",
+        option_env!("FOCUSLENS_FFMPEG_DOWNLOAD_SHA256"),
+    )?;
+    Some(PinnedBuild {
+        version: config_value(
+            "FOCUSLENS_FFMPEG_DOWNLOAD_VERSION",
+            option_env!("FOCUSLENS_FFMPEG_DOWNLOAD_VERSION"),
+        ),
+        url,
+        sha256: sha256.to_ascii_lowercase(),
+    })
+}
+
+fn config_value(key: &str, compiled: Option<&'static str>) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .or_else(|| compiled.map(str::to_string))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn ffmpeg_install_status() -> FfmpegInstallStatus {
+    let (ffmpeg_path, source) = resolve_binary("ffmpeg", "FOCUSLENS_FFMPEG_PATH");
+    let (ffprobe_path, _) = resolve_binary("ffprobe", "FOCUSLENS_FFPROBE_PATH");
+    let build = pinned_build();
+    FfmpegInstallStatus {
+        source,
+        ffmpeg_path,
+        ffprobe_path,
+        managed_installed: MANAGED_BINARIES
+            .iter()
+            .all(|binary| managed_binary(binary).is_some()),
+        download_available: MANAGED_DIR.get().is_some() && build.is_some(),
+        pinned_version: build.and_then(|build| build.version),
+    }
+}
+
+/// 下载固定版本的静态构建到托管目录：边下载边计算 SHA-256，校验通过后解出 ffmpeg/ffprobe。
+/// 同一时间只允许一次安装；失败时清理临时文件，已有的托管副本保持不变。
+pub fn install_managed_ffmpeg<F>(mut on_progress: F) -> Result<(), AppError>
+where
+    F: FnMut(FfmpegInstallProgressEvent),
+{
+    let dir = MANAGED_DIR.get().ok_or_else(|| {
+        AppError::new(
+            "FFMPEG_INSTALL_UNAVAILABLE",
+            "managed ffmpeg dir is not initialized",
+            None,
+        )
+    })?;
+    let build = pinned_build().ok_or_else(|| {
+        AppError::new(
+            "FFMPEG_INSTALL_UNAVAILABLE",
+            "no pinned ffmpeg build is configured",
+            Some("请使用内置 ffmpeg 的安装包，或设置 FOCUSLENS_FFMPEG_PATH".to_string()),
+        )
+    })?;
+    if INSTALLING.swap(true, Ordering::SeqCst) {
+        return Err(AppError::new(
+            "FFMPEG_INSTALL_BUSY",
+            "ffmpeg installation is already running",
+            Some("请等待当前安装完成".to_string()),
+        ));
+    }
+    let archive_path = dir.join(DOWNLOAD_FILE);
+    let result = download_and_extract(dir, &archive_path, &build, &mut on_progress);
+    let _ = std::fs::remove_file(&archive_path);
+    INSTALLING.store(false, Ordering::SeqCst);
+    result
+}
+
+fn download_and_extract<F>(
+    dir: &Path,
+    archive_path: &Path,
+    build: &PinnedBuild,
+    on_progress: &mut F,
+) -> Result<(), AppError>
+where
+    F: FnMut(FfmpegInstallProgressEvent),
+{
+    std::fs::create_dir_all(dir).map_err(|error| install_io_error("create managed dir", error))?;
+    let (digest, downloaded_bytes) = download(&build.url, archive_path, on_progress)?;
+    on_progress(progress_event("verifying", downloaded_bytes, None));
+    if digest != build.sha256 {
+        return Err(AppError::new(
+            "FFMPEG_CHECKSUM_MISMATCH",
+            format!(
+                "ffmpeg download checksum mismatch: expected {}, got {digest}",
+                build.sha256
+            ),
+            Some("下载内容可能被篡改或不完整，请重试".to_string()),
+        ));
+    }
+    on_progress(progress_event("extracting", downloaded_bytes, None));
+    extract_binaries(archive_path, dir)?;
+    on_progress(progress_event("completed", downloaded_bytes, None));
+    tracing::info!("installed managed ffmpeg into {}", dir.display());
+    Ok(())
+}
+
+/// 下载到 `target`，返回小写十六进制 SHA-256 与字节数。
+fn download<F>(url: &str, target: &Path, on_progress: &mut F) -> Result<(String, u64), AppError>
+where
+    F: FnMut(FfmpegInstallProgressEvent),
+{
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(CONNECT_TIMEOUT_SEC))
+        .timeout_read(Duration::from_secs(READ_TIMEOUT_SEC))
+        .build();
+    let response = agent.get(url).call().map_err(|error| {
+        AppError::new(
+            "FFMPEG_DOWNLOAD_FAIL",
+            format!("failed to download ffmpeg: {error}"),
+            Some("检查网络连接后重试".to_string()),
+        )
+    })?;
+    let total_bytes = response
+        .header("Content-Length")
+        .and_then(|value| value.parse::<u64>().ok());
+    let mut reader = response.into_reader();
+    let mut file =
+        File::create(target).map_err(|error| install_io_error("create archive", error))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; DOWNLOAD_CHUNK];
+    let mut downloaded_bytes = 0u64;
+    let mut last_step = None;
+    on_progress(progress_event("downloading", 0, total_bytes));
+    loop {
+        let read = reader.read(&mut buffer).map_err(|error| {
+            AppError::new(
+                "FFMPEG_DOWNLOAD_FAIL",
+                format!("failed to download ffmpeg: {error}"),
+                Some("检查网络连接后重试".to_string()),
+            )
+        })?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        file.write_all(&buffer[..read])
+            .map_err(|error| install_io_error("write archive", error))?;
+        downloaded_bytes += read as u64;
+        let event = progress_event("downloading", downloaded_bytes, total_bytes);
+        // 已知总大小时按百分比、未知时按每 MiB 通知一次，避免事件过密。
+        let step = match total_bytes {
+            Some(_) => u64::from(event.progress),
+            None => downloaded_bytes >> 20,
+        };
+        if last_step != Some(step) {
+            last_step = Some(step);
+            on_progress(event);
+        }
+    }
+    file.flush()
+        .map_err(|error| install_io_error("write archive", error))?;
+    Ok((format!("{:x}", hasher.finalize()), downloaded_bytes))
+}
+
+fn progress_event(
+    stage: &str,
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+) -> FfmpegInstallProgressEvent {
+    let progress = match stage {
+        "downloading" => total_bytes
+            .and_then(|total| (downloaded_bytes * 100).checked_div(total))
+            .unwrap_or(0)
+            .min(100) as u8,
+        _ => 100,
+    };
+    FfmpegInstallProgressEvent {
+        stage: stage.to_string(),
+        downloaded_bytes,
+        total_bytes,
+        progress,
+    }
+}
+
+/// 压缩包内 ffmpeg/ffprobe 可能位于任意子目录（如 `bin/`），按文件名匹配。
+fn archive_binary_name(entry_name: &str) -> Option<&'static str> {
+    let file_name = entry_name.rsplit(['/', '\\']).next()?;
+    MANAGED_BINARIES
+        .iter()
+        .copied()
+        .find(|binary| file_name == executable_name(binary))
+}
+
+/// 先解到 `.part` 再改名，两个文件都齐全才算成功。
+fn extract_binaries(archive_path: &Path, dir: &Path) -> Result<(), AppError> {
+    let file = File::open(archive_path).map_err(|error| install_io_error("open archive", error))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|error| {
+        AppError::new(
+            "FFMPEG_INSTALL_FAIL",
+            format!("invalid ffmpeg archive: {error}"),
+            None,
+        )
+    })?;
+    let mut extracted = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(|error| {
+            AppError::new(
+                "FFMPEG_INSTALL_FAIL",
+                format!("invalid ffmpeg archive: {error}"),
+                None,
+            )
+        })?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(binary) = archive_binary_name(entry.name()) else {
+            continue;
+        };
+        if extracted.iter().any(|(name, _)| *name == binary) {
+            continue;
+        }
+        let staging = dir.join(format!("{}.part", executable_name(binary)));
+        let mut output =
+            File::create(&staging).map_err(|error| install_io_error("extract", error))?;
+        std::io::copy(&mut entry, &mut output)
+            .map_err(|error| install_io_error("extract", error))?;
+        mark_executable(&staging)?;
+        extracted.push((binary, staging));
+    }
+    if let Some(missing) = MANAGED_BINARIES
+        .iter()
+        .find(|binary| !extracted.iter().any(|(name, _)| name == *binary))
+    {
+        for (_, staging) in &extracted {
+            let _ = std::fs::remove_file(staging);
+        }
+        return Err(AppError::new(
+            "FFMPEG_INSTALL_FAIL",
+            format!("ffmpeg archive does not contain {missing}"),
+            None,
+        ));
+    }
+    for (binary, staging) in extracted {
+        std::fs::rename(&staging, dir.join(executable_name(binary)))
+            .map_err(|error| install_io_error("replace binary", error))?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn mark_executable(path: &Path) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .map_err(|error| install_io_error("set permissions", error))
+}
+
+#[cfg(not(unix))]
+fn mark_executable(_path: &Path) -> Result<(), AppError> {
+    Ok(())
+}
+
+fn install_io_error(action: &str, error: std::io::Error) -> AppError {
+    AppError::new(
+        "FFMPEG_INSTALL_FAIL",
+        format!("failed to {action}: {error}"),
+        Some("确认磁盘空间和应用数据目录权限".to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{archive_binary_name, executable_name, extract_binaries, progress_event};
+    use std::io::Write;
+    use tempfile::tempdir;
+    use zip::write::SimpleFileOptions;
+
+    fn write_archive(path: &std::path::Path, entries: &[&str]) {
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        for name in entries {
+            writer
+                .start_file(name.to_string(), SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(name.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn binaries_are_found_in_nested_archive_dirs() {
+        let ffmpeg = format!("ffmpeg-7.1/bin/{}", executable_name("ffmpeg"));
+        assert_eq!(archive_binary_name(&ffmpeg), Some("ffmpeg"));
+        assert_eq!(
+            archive_binary_name(&executable_name("ffprobe")),
+            Some("ffprobe")
+        );
+        assert_eq!(archive_binary_name("ffmpeg-7.1/doc/ffmpeg.html"), None);
+        assert_eq!(archive_binary_name("bin/ffplay"), None);
+    }
+
+    #[test]
+    fn extraction_requires_both_binaries() {
+        let temp = tempdir().unwrap();
+        let archive = temp.path().join("build.zip");
+        let ffmpeg = format!("bin/{}", executable_name("ffmpeg"));
+        let ffprobe = format!("bin/{}", executable_name("ffprobe"));
+
+        write_archive(&archive, &[&ffmpeg, "README.txt"]);
+        assert_eq!(
+            extract_binaries(&archive, temp.path()).unwrap_err().code,
+            "FFMPEG_INSTALL_FAIL"
+        );
+        assert!(!temp.path().join(executable_name("ffmpeg")).exists());
+
+        write_archive(&archive, &[&ffmpeg, &ffprobe, "README.txt"]);
+        extract_binaries(&archive, temp.path()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join(executable_name("ffprobe"))).unwrap(),
+            ffprobe
+        );
+        assert!(!temp.path().join("README.txt").exists());
+    }
+
+    #[test]
+    fn download_progress_is_a_clamped_percentage() {
+        assert_eq!(progress_event("downloading", 50, Some(200)).progress, 25);
+        assert_eq!(progress_event("downloading", 300, Some(200)).progress, 100);
+        assert_eq!(progress_event("downloading", 50, None).progress, 0);
+        assert_eq!(progress_event("downloading", 0, Some(0)).progress, 0);
+        assert_eq!(progress_event("verifying", 50, None).progress, 100);
+    }
+}
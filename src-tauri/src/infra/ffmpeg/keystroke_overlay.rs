@@ -0,0 +1,174 @@
+use crate::core::capture::input::KeyEvent;
+use crate::domain::models::{KeystrokeOverlay, OverlayPosition};
+use std::path::PathBuf;
+
+// 字号、边距以 1080p 短边为基准。
+const KEYSTROKE_REFERENCE_SIDE: f64 = 1080.0;
+const KEYSTROKE_MARGIN: f64 = 72.0;
+// 按键过密时只保留前面的区间，避免滤镜图过长导致 ffmpeg 初始化缓慢。
+const MAX_KEYSTROKE_SPANS: usize = 600;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeystrokeSpan {
+    pub start_sec: f64,
+    pub end_sec: f64,
+    pub label: String,
+}
+
+/// 叠加文字（按键提示、倍速标注）使用的 drawtext 字体。
+/// 未配置时按平台选常见字体，都不存在则交给 fontconfig。
+pub fn overlay_font_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("FOCUSLENS_OVERLAY_FONT") {
+        return Some(PathBuf::from(path)).filter(|path| path.is_file());
+    }
+    let candidates: &[&str] = if cfg!(target_os = "windows") {
+        &["C:/Windows/Fonts/segoeui.ttf", "C:/Windows/Fonts/arial.ttf"]
+    } else if cfg!(target_os = "macos") {
+        &["/System/Library/Fonts/Helvetica.ttc"]
+    } else {
+        &["/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf"]
+    };
+    candidates
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_file())
+}
+
+/// 把按键事件换算为导出时间轴上的显示区间：每个按键保持 `hold_ms`，
+/// 下一次按键到来时提前结束；短时间内重复同一按键合并为 “×N”。
+pub fn keystroke_spans(
+    events: &[KeyEvent],
+    hold_ms: u64,
+    trim_start_ms: u64,
+    trim_end_ms: Option<u64>,
+) -> Vec<KeystrokeSpan> {
+    let hold_sec = hold_ms as f64 / 1000.0;
+    let mut spans: Vec<(KeystrokeSpan, String, u32)> = Vec::new();
+    for event in events.iter().filter(|event| {
+        event.t_ms >= trim_start_ms && trim_end_ms.is_none_or(|end| event.t_ms < end)
+    }) {
+        let start = (event.t_ms - trim_start_ms) as f64 / 1000.0;
+        if let Some((span, label, count)) = spans.last_mut() {
+            if *label == event.label && start <= span.end_sec {
+                *count += 1;
+                span.end_sec = start + hold_sec;
+                span.label = format!("{label} ×{count}");
+                continue;
+            }
+            span.end_sec = span.end_sec.min(start);
+        }
+        spans.push((
+            KeystrokeSpan {
+                start_sec: start,
+                end_sec: start + hold_sec,
+                label: event.label.clone(),
+            },
+            event.label.clone(),
+            1,
+        ));
+    }
+    if let (Some(end), Some((span, _, _))) = (trim_end_ms, spans.last_mut()) {
+        span.end_sec = span
+            .end_sec
+            .min(end.saturating_sub(trim_start_ms) as f64 / 1000.0);
+    }
+    spans
+        .into_iter()
+        .map(|(span, _, _)| span)
+        .filter(|span| span.end_sec > span.start_sec)
+        .take(MAX_KEYSTROKE_SPANS)
+        .collect()
+}
+
+/// 每个区间一个 drawtext：文字居中，box 作为胶囊底色，按时间 enable。
+pub fn build_keystroke_filters(
+    spans: &[KeystrokeSpan],
+    style: &KeystrokeOverlay,
+    font: Option<&str>,
+    target_w: u32,
+    target_h: u32,
+) -> Vec<String> {
+    let scale = target_w.min(target_h) as f64 / KEYSTROKE_REFERENCE_SIDE;
+    let font_size = ((style.font_size as f64 * scale).round() as u32).max(8);
+    let padding = (font_size as f64 * 0.45).round() as u32;
+    let margin = (KEYSTROKE_MARGIN * scale).round() as u32 + padding;
+    let y = match style.position {
+        OverlayPosition::Bottom => format!("h-text_h-{margin}"),
+        OverlayPosition::Top => margin.to_string(),
+    };
+    let opacity = style.opacity.clamp(0.0, 1.0);
+    let font = font
+        .map(|font| format!("fontfile='{font}':"))
+        .unwrap_or_default();
+    spans
+        .iter()
+        .map(|span| {
+            format!(
+                "drawtext={font}text={}:expansion=none:fontsize={font_size}:fontcolor=white:box=1:boxcolor=black@{opacity:.2}:boxborderw={padding}:x=(w-text_w)/2:y={y}:enable='between(t,{:.3},{:.3})'",
+                escape_drawtext(&span.label),
+                span.start_sec,
+                span.end_sec
+            )
+        })
+        .collect()
+}
+
+/// drawtext 文本不加引号，按滤镜选项层、滤镜图层依次做反斜杠转义。
+pub(crate) fn escape_drawtext(text: &str) -> String {
+    let escape = |value: &str, special: &[char]| {
+        value.chars().fold(String::new(), |mut escaped, ch| {
+            if special.contains(&ch) {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+            escaped
+        })
+    };
+    let option_level = escape(text, &['\\', '\'', ':']);
+    escape(&option_level, &['\\', '\'', '[', ']', ',', ';'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_keystroke_filters, escape_drawtext, keystroke_spans};
+    use crate::core::capture::input::KeyEvent;
+    use crate::domain::models::KeystrokeOverlay;
+
+    fn key(t_ms: u64, label: &str) -> KeyEvent {
+        KeyEvent {
+            t_ms,
+            label: label.to_string(),
+        }
+    }
+
+    #[test]
+    fn keystroke_spans_follow_trim_and_merge_repeats() {
+        let events = vec![
+            key(500, "A"),
+            key(2000, "Ctrl+Z"),
+            key(2300, "Ctrl+Z"),
+            key(2600, "Ctrl+Shift+P"),
+            key(9000, "Enter"),
+        ];
+        let spans = keystroke_spans(&events, 1200, 1000, Some(5000));
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].label, "Ctrl+Z ×2");
+        assert!((spans[0].start_sec - 1.0).abs() < 1e-9);
+        assert!((spans[0].end_sec - 1.6).abs() < 1e-9);
+        assert_eq!(spans[1].label, "Ctrl+Shift+P");
+        assert!((spans[1].end_sec - 2.8).abs() < 1e-9);
+
+        let filters =
+            build_keystroke_filters(&spans, &KeystrokeOverlay::default(), None, 1920, 1080);
+        assert!(filters[1].starts_with("drawtext=text=Ctrl+Shift+P:expansion=none:fontsize=40"));
+        assert!(filters[1].contains("y=h-text_h-90:enable='between(t,1.600,2.800)'"));
+    }
+
+    #[test]
+    fn drawtext_escapes_filtergraph_specials() {
+        assert_eq!(escape_drawtext("Ctrl+,"), "Ctrl+\\,");
+        assert_eq!(escape_drawtext("Shift+;"), "Shift+\\;");
+        assert_eq!(escape_drawtext("\\"), "\\\\\\\\");
+        assert_eq!(escape_drawtext("'"), "\\\\\\'");
+    }
+}
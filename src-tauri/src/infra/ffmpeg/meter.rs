@@ -0,0 +1,75 @@
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use crate::core::capture::service::resolve_microphone_device;
+use crate::domain::models::{AppError, RecordingProfile};
+use std::process::{Child, Command, Stdio};
+
+pub const METER_SAMPLE_RATE: u32 = 16_000;
+pub const METER_WINDOW_MS: u64 = 100;
+/// 单声道 s16le，每个窗口的字节数。
+pub const METER_WINDOW_BYTES: usize =
+    (METER_SAMPLE_RATE as u64 * METER_WINDOW_MS / 1000) as usize * 2;
+
+/// 实际录制所用麦克风的输入参数，`default` 解析为具体设备；没有真实麦克风输入的平台返回 None，
+/// 与未选择麦克风同样处理，不用合成信号冒充电平。
+#[cfg(target_os = "windows")]
+fn meter_input_args(profile: &RecordingProfile) -> Option<Vec<String>> {
+    let mic = resolve_microphone_device(profile.microphone_device_id.as_deref())?;
+    Some(vec![
+        "-f".to_string(),
+        "dshow".to_string(),
+        "-i".to_string(),
+        format!("audio={mic}"),
+    ])
+}
+
+#[cfg(target_os = "macos")]
+fn meter_input_args(profile: &RecordingProfile) -> Option<Vec<String>> {
+    let mic = resolve_microphone_device(profile.microphone_device_id.as_deref())?;
+    Some(vec![
+        "-f".to_string(),
+        "avfoundation".to_string(),
+        "-i".to_string(),
+        format!(":{mic}"),
+    ])
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn meter_input_args(_profile: &RecordingProfile) -> Option<Vec<String>> {
+    None
+}
+
+fn build_audio_meter_command(ffmpeg_bin: &str, profile: &RecordingProfile) -> Option<Command> {
+    let input = meter_input_args(profile)?;
+    let mut command = Command::new(ffmpeg_bin);
+    command.arg("-hide_banner");
+    command.arg("-loglevel");
+    command.arg("error");
+    command.stdin(Stdio::null());
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::null());
+    command.args(input);
+
+    command.arg("-vn");
+    command.arg("-ac").arg("1");
+    command.arg("-ar").arg(METER_SAMPLE_RATE.to_string());
+    command.arg("-f").arg("s16le");
+    command.arg("pipe:1");
+    Some(command)
+}
+
+/// 未选择麦克风或平台没有真实麦克风输入时返回 None，此时录制只有静音轨，无需电平监测。
+pub fn spawn_audio_meter_process(
+    ffmpeg_bin: &str,
+    profile: &RecordingProfile,
+) -> Result<Option<Child>, AppError> {
+    let Some(mut command) = build_audio_meter_command(ffmpeg_bin, profile) else {
+        return Ok(None);
+    };
+    command.spawn().map(Some).map_err(|error| {
+        AppError::new(
+            "AUDIO_METER_START_FAIL",
+            format!("failed to start audio meter process: {error}"),
+            None,
+        )
+    })
+}
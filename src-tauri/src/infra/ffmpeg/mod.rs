@@ -1,5 +1,28 @@
+pub mod analysis;
+pub mod animation;
+pub mod annotation;
+pub mod audio_mix;
+pub mod blur;
 pub mod capabilities;
+pub mod captions;
+pub mod capture_test;
+pub mod click_sound;
+pub mod color;
 pub mod command;
+pub mod cursor_overlay;
 pub mod export;
+pub mod info;
+pub mod installer;
+pub mod keystroke_overlay;
+pub mod meter;
+pub mod orphans;
+pub mod preview;
 pub mod probe;
 pub mod recording;
+pub mod repair;
+pub mod scene;
+pub mod silence;
+pub mod thumbnails;
+pub mod time_remap;
+pub mod timecode;
+pub mod waveform;
@@ -0,0 +1,235 @@
+use crate::domain::models::AppError;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// 录制期间写在项目目录下的 ffmpeg 进程登记，正常停止录制时删除。
+/// 应用崩溃后文件残留，下次启动据此终止仍在写盘的孤儿进程。
+pub const FFMPEG_SESSION_FILE: &str = "ffmpeg.session.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FfmpegSession {
+    pids: Vec<u32>,
+}
+
+pub fn record_ffmpeg_pid(project_dir: &Path, pid: u32) -> Result<(), AppError> {
+    let path = project_dir.join(FFMPEG_SESSION_FILE);
+    let mut session = read_session(&path);
+    if !session.pids.contains(&pid) {
+        session.pids.push(pid);
+    }
+    let content = serde_json::to_string(&session)
+        .map_err(|error| AppError::new("SERDE_ERROR", error.to_string(), None))?;
+    std::fs::write(&path, content).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to record ffmpeg pid: {error}"),
+            None,
+        )
+    })
+}
+
+pub fn clear_ffmpeg_pids(project_dir: &Path) {
+    let _ = std::fs::remove_file(project_dir.join(FFMPEG_SESSION_FILE));
+}
+
+// 先请求 ffmpeg 正常退出，让它写完文件尾，超时后再强制结束。
+const GRACEFUL_STOP_TIMEOUT: Duration = Duration::from_secs(2);
+const GRACEFUL_STOP_POLL: Duration = Duration::from_millis(100);
+
+/// 启动时扫描所有项目，终止登记过且仍在运行的 ffmpeg 进程，返回终止的数量。
+/// 只处理进程名为 ffmpeg、命令行包含该项目目录的 pid，避免误杀复用了同一 pid 的其他程序。
+pub fn terminate_orphan_ffmpeg(project_root: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(project_root) else {
+        return 0;
+    };
+    let mut terminated = 0;
+    for entry in entries.flatten() {
+        let path = entry.path().join(FFMPEG_SESSION_FILE);
+        if !path.exists() {
+            continue;
+        }
+        let project_dir = entry.path();
+        for pid in read_session(&path).pids {
+            let owned = process_image_name(pid).is_some_and(|name| is_ffmpeg_image(&name))
+                && process_command_line(pid)
+                    .is_some_and(|command_line| writes_into(&command_line, &project_dir));
+            if owned && stop_process(pid) {
+                tracing::warn!("terminated orphan ffmpeg process {pid}");
+                terminated += 1;
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+    terminated
+}
+
+fn read_session(path: &Path) -> FfmpegSession {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn is_ffmpeg_image(name: &str) -> bool {
+    let name = name.trim().trim_matches('"').to_ascii_lowercase();
+    let file_name = name.rsplit(['/', '\\']).next().unwrap_or(&name);
+    file_name == "ffmpeg" || file_name == "ffmpeg.exe"
+}
+
+/// 录制输出都在项目目录下，命令行里必然出现该目录；比较时忽略大小写与路径分隔符差异。
+fn writes_into(command_line: &str, project_dir: &Path) -> bool {
+    let normalize = |text: &str| text.replace('\\', "/").to_lowercase();
+    let dir = normalize(&project_dir.to_string_lossy());
+    !dir.is_empty() && normalize(command_line).contains(dir.trim_end_matches('/'))
+}
+
+/// 先发送正常退出请求，超时仍在运行再强制结束。
+fn stop_process(pid: u32) -> bool {
+    if request_stop(pid) {
+        let deadline = Instant::now() + GRACEFUL_STOP_TIMEOUT;
+        while Instant::now() < deadline {
+            if process_image_name(pid).is_none() {
+                return true;
+            }
+            std::thread::sleep(GRACEFUL_STOP_POLL);
+        }
+    }
+    kill_process(pid)
+}
+
+#[cfg(target_os = "windows")]
+fn process_image_name(pid: u32) -> Option<String> {
+    let output = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    // 形如 "ffmpeg.exe","1234","Console","1","10,000 K"；无匹配时输出提示文字。
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .find(|line| line.contains(&format!("\"{pid}\"")))?;
+    line.split(',').next().map(str::to_string)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn process_image_name(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+#[cfg(target_os = "windows")]
+fn process_command_line(pid: u32) -> Option<String> {
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            &format!("(Get-CimInstance Win32_Process -Filter 'ProcessId={pid}').CommandLine"),
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let command_line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !command_line.is_empty()).then_some(command_line)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn process_command_line(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "args="])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let command_line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !command_line.is_empty()).then_some(command_line)
+}
+
+#[cfg(target_os = "windows")]
+fn request_stop(pid: u32) -> bool {
+    Command::new("taskkill")
+        .args(["/PID", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn request_stop(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(target_os = "windows")]
+fn kill_process(pid: u32) -> bool {
+    Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn kill_process(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-9", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clear_ffmpeg_pids, is_ffmpeg_image, read_session, record_ffmpeg_pid, writes_into};
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn session_file_tracks_pids_and_matches_ffmpeg_images() {
+        let temp = tempdir().unwrap();
+        record_ffmpeg_pid(temp.path(), 42).unwrap();
+        record_ffmpeg_pid(temp.path(), 43).unwrap();
+        record_ffmpeg_pid(temp.path(), 42).unwrap();
+        let path = temp.path().join(super::FFMPEG_SESSION_FILE);
+        assert_eq!(read_session(&path).pids, vec![42, 43]);
+        clear_ffmpeg_pids(temp.path());
+        assert!(!path.exists());
+
+        assert!(is_ffmpeg_image("\"ffmpeg.exe\""));
+        assert!(is_ffmpeg_image("/usr/local/bin/ffmpeg"));
+        assert!(!is_ffmpeg_image("ffprobe"));
+        assert!(!is_ffmpeg_image("notepad.exe"));
+    }
+
+    #[test]
+    fn ownership_requires_the_project_dir_in_the_command_line() {
+        let command_line =
+            r#"ffmpeg -y -f gdigrab -i desktop C:\Users\me\FocusLens\p1\assets\recording_raw.mp4"#;
+        assert!(writes_into(
+            command_line,
+            Path::new("c:/users/me/FocusLens/p1")
+        ));
+        assert!(!writes_into(
+            command_line,
+            Path::new("C:/Users/me/FocusLens/p2")
+        ));
+        assert!(writes_into(
+            "/usr/bin/ffmpeg -i x /home/me/FocusLens/p1/assets/recording_raw.mp4",
+            Path::new("/home/me/FocusLens/p1/")
+        ));
+    }
+}
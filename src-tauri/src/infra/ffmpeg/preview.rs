@@ -0,0 +1,61 @@
+pub const PREVIEW_FPS: u8 = 2;
+pub const PREVIEW_WIDTH: u32 = 480;
+
+const JPEG_SOI: [u8; 2] = [0xFF, 0xD8];
+const JPEG_EOI: [u8; 2] = [0xFF, 0xD9];
+
+/// 把 ffmpeg 输出的 MJPEG 字节流按 SOI/EOI 标记切分为完整 JPEG 帧。
+#[derive(Debug, Default)]
+pub struct JpegFrameSplitter {
+    buffer: Vec<u8>,
+}
+
+impl JpegFrameSplitter {
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut frames = Vec::new();
+        loop {
+            let Some(start) = find_marker(&self.buffer, 0, JPEG_SOI) else {
+                // 保留最后一个字节，防止 SOI 标记被拆在两次读取之间。
+                let keep_from = self.buffer.len().saturating_sub(1);
+                self.buffer.drain(..keep_from);
+                break;
+            };
+            let Some(end) = find_marker(&self.buffer, start + 2, JPEG_EOI) else {
+                self.buffer.drain(..start);
+                break;
+            };
+            frames.push(self.buffer[start..end + 2].to_vec());
+            self.buffer.drain(..end + 2);
+        }
+        frames
+    }
+}
+
+fn find_marker(buffer: &[u8], from: usize, marker: [u8; 2]) -> Option<usize> {
+    buffer
+        .get(from..)?
+        .windows(2)
+        .position(|window| window == marker)
+        .map(|offset| from + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JpegFrameSplitter;
+
+    #[test]
+    fn splitter_emits_frames_across_chunk_boundaries() {
+        let mut splitter = JpegFrameSplitter::default();
+        assert!(splitter.push(&[0x00, 0xFF]).is_empty());
+        assert!(splitter.push(&[0xD8, 0x01, 0x02, 0xFF]).is_empty());
+        let frames = splitter.push(&[0xD9, 0xFF, 0xD8, 0x03, 0xFF, 0xD9]);
+        assert_eq!(
+            frames,
+            vec![
+                vec![0xFF, 0xD8, 0x01, 0x02, 0xFF, 0xD9],
+                vec![0xFF, 0xD8, 0x03, 0xFF, 0xD9],
+            ]
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use crate::domain::models::AppError;
-use crate::infra::ffmpeg::command::ffprobe_bin;
+use crate::infra::ffmpeg::command::{
+    command_error, ffprobe_bin, output_with_timeout, probe_timeout,
+};
 use serde::Deserialize;
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::Command;
 
 #[derive(Debug, Deserialize)]
 struct ProbeFormat {
@@ -13,6 +15,9 @@ struct ProbeFormat {
 struct ProbeStream {
     codec_type: Option<String>,
     duration: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    avg_frame_rate: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -27,40 +32,16 @@ pub struct ProbeSummary {
     pub audio_duration_ms: Option<u64>,
 }
 
-pub fn probe_media(path: &Path) -> Result<ProbeSummary, AppError> {
-    let output = Command::new(ffprobe_bin())
-        .arg("-v")
-        .arg("error")
-        .arg("-show_entries")
-        .arg("stream=codec_type,duration:format=duration")
-        .arg("-of")
-        .arg("json")
-        .arg(path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .map_err(|error| {
-            AppError::new(
-                "FFPROBE_EXEC_ERROR",
-                format!("failed to run ffprobe: {error}"),
-                Some("请安装 ffprobe 并加入 PATH".to_string()),
-            )
-        })?;
-    if !output.status.success() {
-        return Err(AppError::new(
-            "FFPROBE_EXEC_ERROR",
-            String::from_utf8_lossy(&output.stderr).to_string(),
-            Some("检查输入媒体文件是否完整".to_string()),
-        ));
-    }
+/// 首个视频流的画面参数与是否带音轨，用于检查试录结果。
+pub struct StreamSummary {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub frame_rate: Option<f32>,
+    pub has_audio: bool,
+}
 
-    let parsed: ProbeOutput = serde_json::from_slice(&output.stdout).map_err(|error| {
-        AppError::new(
-            "FFPROBE_PARSE_ERROR",
-            format!("failed to parse ffprobe output: {error}"),
-            None,
-        )
-    })?;
+pub fn probe_media(path: &Path) -> Result<ProbeSummary, AppError> {
+    let parsed = run_ffprobe(path, "stream=codec_type,duration:format=duration")?;
 
     let video_duration_ms = parsed
         .streams
@@ -81,11 +62,78 @@ pub fn probe_media(path: &Path) -> Result<ProbeSummary, AppError> {
     })
 }
 
+pub fn probe_streams(path: &Path) -> Result<StreamSummary, AppError> {
+    let parsed = run_ffprobe(
+        path,
+        "stream=codec_type,width,height,avg_frame_rate:format=duration",
+    )?;
+    let video = parsed
+        .streams
+        .iter()
+        .find(|stream| stream.codec_type.as_deref() == Some("video"));
+    Ok(StreamSummary {
+        width: video.and_then(|stream| stream.width),
+        height: video.and_then(|stream| stream.height),
+        frame_rate: video
+            .and_then(|stream| stream.avg_frame_rate.as_deref())
+            .and_then(parse_frame_rate),
+        has_audio: parsed
+            .streams
+            .iter()
+            .any(|stream| stream.codec_type.as_deref() == Some("audio")),
+    })
+}
+
+fn run_ffprobe(path: &Path, entries: &str) -> Result<ProbeOutput, AppError> {
+    let output = output_with_timeout(
+        Command::new(ffprobe_bin())
+            .arg("-v")
+            .arg("error")
+            .arg("-show_entries")
+            .arg(entries)
+            .arg("-of")
+            .arg("json")
+            .arg(path),
+        probe_timeout(),
+    )
+    .map_err(|error| {
+        command_error(
+            error,
+            "FFPROBE_EXEC_ERROR",
+            "failed to run ffprobe",
+            Some("请安装 ffprobe 并加入 PATH"),
+        )
+    })?;
+    if !output.status.success() {
+        return Err(AppError::new(
+            "FFPROBE_EXEC_ERROR",
+            String::from_utf8_lossy(&output.stderr).to_string(),
+            Some("检查输入媒体文件是否完整".to_string()),
+        ));
+    }
+
+    serde_json::from_slice(&output.stdout).map_err(|error| {
+        AppError::new(
+            "FFPROBE_PARSE_ERROR",
+            format!("failed to parse ffprobe output: {error}"),
+            None,
+        )
+    })
+}
+
 fn parse_duration_ms(raw: Option<&str>) -> Option<u64> {
     raw.and_then(|value| value.parse::<f64>().ok())
         .map(|seconds| (seconds * 1000.0).max(0.0) as u64)
 }
 
+/// 解析 ffprobe 的 `30/1`、`30000/1001` 形式帧率；没有帧的流报告 `0/0`，返回 None。
+fn parse_frame_rate(raw: &str) -> Option<f32> {
+    let (num, den) = raw.split_once('/').unwrap_or((raw, "1"));
+    let num = num.trim().parse::<f32>().ok()?;
+    let den = den.trim().parse::<f32>().ok()?;
+    (num > 0.0 && den > 0.0).then(|| num / den)
+}
+
 pub fn calc_av_offset_ms(video_duration_ms: Option<u64>, audio_duration_ms: Option<u64>) -> i64 {
     match (video_duration_ms, audio_duration_ms) {
         (Some(video), Some(audio)) => video as i64 - audio as i64,
@@ -95,7 +143,7 @@ pub fn calc_av_offset_ms(video_duration_ms: Option<u64>, audio_duration_ms: Opti
 
 #[cfg(test)]
 mod tests {
-    use super::calc_av_offset_ms;
+    use super::{calc_av_offset_ms, parse_frame_rate};
 
     #[test]
     fn av_offset_positive() {
@@ -106,4 +154,12 @@ mod tests {
     fn av_offset_zero_when_missing() {
         assert_eq!(calc_av_offset_ms(Some(30_000), None), 0);
     }
+
+    #[test]
+    fn frame_rate_parses_ratios_and_rejects_empty_streams() {
+        assert_eq!(parse_frame_rate("30/1"), Some(30.0));
+        assert!((parse_frame_rate("30000/1001").unwrap() - 29.97).abs() < 0.01);
+        assert_eq!(parse_frame_rate("0/0"), None);
+        assert_eq!(parse_frame_rate("25"), Some(25.0));
+    }
 }
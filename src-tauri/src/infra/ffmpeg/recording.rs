@@ -1,8 +1,11 @@
+#[cfg(target_os = "windows")]
+use crate::core::capture::service::resolve_microphone_device;
 #[cfg(not(target_os = "windows"))]
 use crate::domain::models::Resolution;
-use crate::domain::models::{AppError, CaptureMode, RecordingProfile};
+use crate::domain::models::{AppError, CaptureBounds, CaptureMode, RecordingProfile};
 #[cfg(target_os = "windows")]
 use crate::infra::ffmpeg::command::ffmpeg_supports_input_format;
+use crate::infra::ffmpeg::preview::{PREVIEW_FPS, PREVIEW_WIDTH};
 use std::ffi::OsString;
 use std::path::Path;
 use std::process::{Child, Command, Stdio};
@@ -24,6 +27,7 @@ pub struct RecordingSpawn {
 fn build_recording_command(
     ffmpeg_bin: &str,
     profile: &RecordingProfile,
+    capture_region: Option<&CaptureBounds>,
     output_path: &Path,
 ) -> (Command, Option<String>) {
     let mut command = Command::new(ffmpeg_bin);
@@ -32,11 +36,14 @@ fn build_recording_command(
     command.arg("-loglevel");
     command.arg("warning");
     command.stdin(Stdio::piped());
-    command.stdout(Stdio::null());
+    command.stdout(Stdio::piped());
     command.stderr(Stdio::null());
 
     #[cfg(target_os = "windows")]
-    let degrade_message = configure_windows_capture(&mut command, profile);
+    let degrade_message = configure_windows_capture(&mut command, profile, capture_region);
+
+    #[cfg(not(target_os = "windows"))]
+    let _ = capture_region;
 
     #[cfg(target_os = "macos")]
     let degrade_message = configure_macos_capture(&mut command, profile);
@@ -49,16 +56,52 @@ fn build_recording_command(
     command.arg("-c:v");
     command.arg("libx264");
     command.arg("-preset");
-    command.arg("ultrafast");
-    command.arg("-movflags");
-    command.arg("+faststart");
+    command.arg(profile.preset.as_str());
+    append_rate_control_args(&mut command, profile);
     command.arg("-r");
     command.arg(profile.frame_rate.to_string());
     command.arg(output_path.as_os_str());
+    append_preview_output_args(&mut command);
 
     (command, degrade_message)
 }
 
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn cursor_flag(capture_cursor: bool) -> &'static str {
+    if capture_cursor {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+fn append_rate_control_args(command: &mut Command, profile: &RecordingProfile) {
+    match profile.recording_bitrate_mbps.filter(|value| *value > 0) {
+        Some(bitrate) => {
+            command.arg("-b:v").arg(format!("{bitrate}M"));
+            command.arg("-maxrate").arg(format!("{bitrate}M"));
+            command
+                .arg("-bufsize")
+                .arg(format!("{}M", u32::from(bitrate) * 2));
+        }
+        None => {
+            command.arg("-crf").arg(profile.crf.min(51).to_string());
+        }
+    }
+}
+
+/// 第二路输出：低帧率小尺寸 MJPEG 写到 stdout，供录制中实时预览。
+fn append_preview_output_args(command: &mut Command) {
+    command.arg("-map").arg("0:v:0");
+    command.arg("-an");
+    command
+        .arg("-vf")
+        .arg(format!("fps={PREVIEW_FPS},scale={PREVIEW_WIDTH}:-2"));
+    command.arg("-q:v").arg("8");
+    command.arg("-f").arg("mjpeg");
+    command.arg("pipe:1");
+}
+
 fn exited_too_early(child: &mut Child) -> Result<bool, AppError> {
     std::thread::sleep(Duration::from_millis(400));
     let status = child.try_wait().map_err(|error| {
@@ -74,9 +117,11 @@ fn exited_too_early(child: &mut Child) -> Result<bool, AppError> {
 pub fn spawn_recording_process(
     ffmpeg_bin: &str,
     profile: &RecordingProfile,
+    capture_region: Option<&CaptureBounds>,
     output_path: &Path,
 ) -> Result<RecordingSpawn, AppError> {
-    let (mut command, degrade_message) = build_recording_command(ffmpeg_bin, profile, output_path);
+    let (mut command, degrade_message) =
+        build_recording_command(ffmpeg_bin, profile, capture_region, output_path);
     let mut child = command.spawn().map_err(|error| {
         AppError::new(
             "RECORDING_START_FAIL",
@@ -92,7 +137,7 @@ pub fn spawn_recording_process(
             fallback_profile.microphone_device_id = None;
 
             let (mut fallback_command, _) =
-                build_recording_command(ffmpeg_bin, &fallback_profile, output_path);
+                build_recording_command(ffmpeg_bin, &fallback_profile, capture_region, output_path);
             let mut fallback_child = fallback_command.spawn().map_err(|error| {
                 AppError::new(
                     "RECORDING_START_FAIL",
@@ -129,13 +174,36 @@ pub fn spawn_recording_process(
 }
 
 #[cfg(target_os = "windows")]
-fn configure_windows_capture(command: &mut Command, profile: &RecordingProfile) -> Option<String> {
+fn configure_windows_capture(
+    command: &mut Command,
+    profile: &RecordingProfile,
+    capture_region: Option<&CaptureBounds>,
+) -> Option<String> {
     command.arg("-f").arg("gdigrab");
     command
         .arg("-framerate")
         .arg(profile.frame_rate.to_string());
+    command
+        .arg("-draw_mouse")
+        .arg(cursor_flag(profile.capture_cursor));
     match profile.capture_mode {
         CaptureMode::Fullscreen => {
+            if let Some(region) = capture_region {
+                // gdigrab 未声明 DPI 感知，偏移与尺寸需使用逻辑像素。
+                let scale = region.scale_factor.max(0.1);
+                let (width, height) = region.expected_frame_size();
+                command
+                    .arg("-offset_x")
+                    .arg(((region.x / scale).round() as i64).to_string());
+                command
+                    .arg("-offset_y")
+                    .arg(((region.y / scale).round() as i64).to_string());
+                command.arg("-video_size").arg(format!(
+                    "{}x{}",
+                    (width as i64 / 2) * 2,
+                    (height as i64 / 2) * 2
+                ));
+            }
             command.arg("-i").arg("desktop");
         }
         CaptureMode::Window => {
@@ -147,11 +215,7 @@ fn configure_windows_capture(command: &mut Command, profile: &RecordingProfile)
 
     let mut audio_inputs = 0usize;
     let mut degrade_message = None;
-    if let Some(mic) = profile
-        .microphone_device_id
-        .as_deref()
-        .filter(|value| !value.trim().is_empty() && *value != "default")
-    {
+    if let Some(mic) = resolve_microphone_device(profile.microphone_device_id.as_deref()) {
         command.arg("-f").arg("dshow");
         command.arg("-i").arg(format!("audio={mic}"));
         audio_inputs += 1;
@@ -203,6 +267,9 @@ fn configure_macos_capture(command: &mut Command, profile: &RecordingProfile) ->
     command
         .arg("-video_size")
         .arg(resolution_size(&profile.resolution));
+    command
+        .arg("-capture_cursor")
+        .arg(cursor_flag(profile.capture_cursor));
     command.arg("-i").arg("1:none");
     command
         .arg("-f")
@@ -296,7 +363,7 @@ pub fn build_ffmpeg_recording_debug_command(
     profile: &RecordingProfile,
     output_path: &Path,
 ) -> Vec<OsString> {
-    let (command, _) = build_recording_command("ffmpeg", profile, output_path);
+    let (command, _) = build_recording_command("ffmpeg", profile, None, output_path);
     command
         .get_args()
         .map(|arg| arg.to_os_string())
@@ -306,12 +373,12 @@ pub fn build_ffmpeg_recording_debug_command(
 #[cfg(test)]
 mod tests {
     use super::build_ffmpeg_recording_debug_command;
-    use crate::domain::models::RecordingProfile;
+    use crate::domain::models::{EncoderPreset, RecordingProfile};
 
     #[test]
     fn build_recording_command_includes_fps_and_output() {
         let profile = RecordingProfile::default();
-        let output = std::path::Path::new("recording.mp4");
+        let output = std::path::Path::new("recording.mkv");
         let args = build_ffmpeg_recording_debug_command(&profile, output);
         let joined = args
             .iter()
@@ -319,6 +386,41 @@ mod tests {
             .collect::<Vec<_>>()
             .join(" ");
         assert!(joined.contains("30"));
-        assert!(joined.contains("recording.mp4"));
+        assert!(joined.contains("recording.mkv"));
+        assert!(!joined.contains("-movflags"));
+        assert!(joined.ends_with("-f mjpeg pipe:1"));
+    }
+
+    #[test]
+    fn build_recording_command_uses_crf_and_preset_by_default() {
+        let profile = RecordingProfile {
+            preset: EncoderPreset::Veryfast,
+            crf: 60,
+            ..RecordingProfile::default()
+        };
+        let args = joined_args(&profile);
+        assert!(args.contains("-preset veryfast"));
+        assert!(args.contains("-crf 51"));
+        assert!(!args.contains("-b:v"));
+    }
+
+    #[test]
+    fn build_recording_command_prefers_bitrate_when_set() {
+        let profile = RecordingProfile {
+            recording_bitrate_mbps: Some(12),
+            ..RecordingProfile::default()
+        };
+        let args = joined_args(&profile);
+        assert!(args.contains("-b:v 12M"));
+        assert!(args.contains("-bufsize 24M"));
+        assert!(!args.contains("-crf"));
+    }
+
+    fn joined_args(profile: &RecordingProfile) -> String {
+        build_ffmpeg_recording_debug_command(profile, std::path::Path::new("recording.mkv"))
+            .iter()
+            .map(|item| item.to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 }
@@ -0,0 +1,156 @@
+use crate::domain::models::AppError;
+use crate::infra::ffmpeg::command::run_ffmpeg;
+use std::path::{Path, PathBuf};
+
+/// 流复制重封装：忽略损坏包，重新写出 moov，使中断的录制可以被播放和剪辑。
+/// 原始文件完全缺少索引时 ffmpeg 无法读取，返回 RECORDING_UNRECOVERABLE。
+pub fn remux_recording(input_path: &Path, output_path: &Path) -> Result<(), AppError> {
+    let output = run_ffmpeg(remux_args(input_path, output_path))?;
+    if !output.status.success() || !output_path.exists() {
+        let _ = std::fs::remove_file(output_path);
+        return Err(AppError::new(
+            "RECORDING_UNRECOVERABLE",
+            output
+                .stderr
+                .lines()
+                .last()
+                .unwrap_or("remux failed")
+                .to_string(),
+            Some("录制文件缺少索引或已损坏，无法修复".to_string()),
+        ));
+    }
+    Ok(())
+}
+
+/// 录制以 MKV 写入，停止或恢复时重封装为 MP4；成功后删除 MKV，失败时保留以便再次修复。
+pub fn finalize_capture(capture_path: &Path, output_path: &Path) -> Result<(), AppError> {
+    remux_recording(capture_path, output_path)?;
+    let _ = std::fs::remove_file(capture_path);
+    Ok(())
+}
+
+/// 把续录分段按顺序拼到首段之后。视频参数一致可直接复制；音频在分段之间可能从麦克风
+/// 换成静音轨，采样率不同，因此重新编码。成功后删除分段，失败时保留首段与分段。
+pub fn join_capture_parts(capture_path: &Path, parts: &[PathBuf]) -> Result<(), AppError> {
+    let list_path = capture_path.with_extension("parts.txt");
+    let joined_path = capture_path.with_extension("joined.mkv");
+    let mut files = vec![capture_path.to_path_buf()];
+    files.extend(parts.iter().filter(|part| part.exists()).cloned());
+    std::fs::write(&list_path, concat_list(&files)).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to write concat list: {error}"),
+            None,
+        )
+    })?;
+    let output = run_ffmpeg(join_args(&list_path, &joined_path));
+    let _ = std::fs::remove_file(&list_path);
+    let output = output?;
+    if !output.status.success() || !joined_path.exists() {
+        let _ = std::fs::remove_file(&joined_path);
+        return Err(AppError::new(
+            "RECORDING_JOIN_FAILED",
+            output
+                .stderr
+                .lines()
+                .last()
+                .unwrap_or("concat failed")
+                .to_string(),
+            None,
+        ));
+    }
+    std::fs::rename(&joined_path, capture_path).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to replace recording: {error}"),
+            None,
+        )
+    })?;
+    for part in parts {
+        let _ = std::fs::remove_file(part);
+    }
+    Ok(())
+}
+
+fn concat_list(files: &[PathBuf]) -> String {
+    files
+        .iter()
+        .map(|file| format!("file '{}'\n", file.to_string_lossy().replace('\'', "'\\''")))
+        .collect()
+}
+
+fn join_args(list_path: &Path, output_path: &Path) -> Vec<String> {
+    [
+        "-y",
+        "-hide_banner",
+        "-loglevel",
+        "error",
+        "-f",
+        "concat",
+        "-safe",
+        "0",
+        "-i",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .chain([list_path.to_string_lossy().to_string()])
+    .chain(
+        ["-map", "0", "-c:v", "copy", "-c:a", "aac", "-b:a", "128k"]
+            .into_iter()
+            .map(str::to_string),
+    )
+    .chain([output_path.to_string_lossy().to_string()])
+    .collect()
+}
+
+fn remux_args(input_path: &Path, output_path: &Path) -> Vec<String> {
+    [
+        "-y",
+        "-hide_banner",
+        "-loglevel",
+        "error",
+        "-err_detect",
+        "ignore_err",
+        "-fflags",
+        "+genpts+discardcorrupt",
+        "-i",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .chain([input_path.to_string_lossy().to_string()])
+    .chain(
+        ["-map", "0", "-c", "copy", "-movflags", "+faststart"]
+            .into_iter()
+            .map(str::to_string),
+    )
+    .chain([output_path.to_string_lossy().to_string()])
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{concat_list, join_args, remux_args};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn remux_copies_all_streams_without_reencoding() {
+        let args = remux_args(Path::new("raw.mp4"), Path::new("fixed.mp4"));
+        let joined = args.join(" ");
+        assert!(joined.contains("-i raw.mp4 -map 0 -c copy"));
+        assert_eq!(args.last().map(String::as_str), Some("fixed.mp4"));
+    }
+
+    #[test]
+    fn capture_parts_are_listed_in_order_and_audio_reencoded() {
+        let list = concat_list(&[
+            PathBuf::from("/p/recording_raw.mkv"),
+            PathBuf::from("/p/it's.part1.mkv"),
+        ]);
+        assert_eq!(
+            list,
+            "file '/p/recording_raw.mkv'\nfile '/p/it'\\''s.part1.mkv'\n"
+        );
+        let joined = join_args(Path::new("parts.txt"), Path::new("joined.mkv")).join(" ");
+        assert!(joined.contains("-f concat -safe 0 -i parts.txt -map 0 -c:v copy -c:a aac"));
+    }
+}
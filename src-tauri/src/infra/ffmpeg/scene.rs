@@ -0,0 +1,172 @@
+use crate::domain::models::{SceneBackground, SceneStyle};
+use crate::infra::ffmpeg::export::escape_filter_path;
+use std::path::Path;
+
+// 圆角、留白等参数以 1080p 短边为基准。
+const SCENE_REFERENCE_SIDE: f64 = 1080.0;
+const SCENE_SHADOW_BLUR: f64 = 24.0;
+const SCENE_SHADOW_OFFSET: f64 = 12.0;
+const SCENE_FALLBACK_COLOR: (u8, u8, u8) = (0x11, 0x18, 0x27);
+
+/// 场景留白换算到输出像素（取偶数），保证内容区至少保留一半画面。
+pub fn scene_padding(scene: &SceneStyle, target_w: u32, target_h: u32) -> u32 {
+    let scale = scene_scale(target_w, target_h);
+    let max_padding = target_w.min(target_h) / 4;
+    let padding = (scene.padding as f64 * scale / 2.0).round() as u32 * 2;
+    padding.min(max_padding / 2 * 2)
+}
+
+/// 把已缩放到 `content_w x content_h` 的内容合成到场景背景上。
+/// 背景与投影都是静态画面，只生成一帧再循环，避免逐帧执行 geq/boxblur。
+pub fn build_scene_filter(
+    scene: &SceneStyle,
+    content_w: u32,
+    content_h: u32,
+    target_w: u32,
+    target_h: u32,
+    fps: u8,
+) -> String {
+    let scale = scene_scale(target_w, target_h);
+    let radius =
+        ((scene.corner_radius as f64 * scale).round() as u32).min(content_w.min(content_h) / 2);
+    let shadow = scene.shadow.clamp(0.0, 1.0);
+    let mut chains = Vec::new();
+
+    let mut base = format!(
+        "{},format=rgba",
+        background_source(&scene.background, target_w, target_h)
+    );
+    if shadow > 0.0 {
+        let blur = (SCENE_SHADOW_BLUR * scale).round().max(2.0) as u32;
+        let offset = (SCENE_SHADOW_OFFSET * scale).round() as u32;
+        let shadow_w = content_w + blur * 2;
+        let shadow_h = content_h + blur * 2;
+        chains.push(format!("{base}[scene_base]"));
+        chains.push(format!(
+            "color=c=black:s={shadow_w}x{shadow_h}:r=1:d=1,format=gray,geq=lum='{}',boxblur={blur}:1[scene_shadow_mask]",
+            rounded_rect_expr(blur, blur, content_w, content_h, radius)
+        ));
+        chains.push(format!(
+            "color=c=black:s={shadow_w}x{shadow_h}:r=1:d=1,format=rgba[scene_shadow_rgb]"
+        ));
+        chains.push(format!(
+            "[scene_shadow_rgb][scene_shadow_mask]alphamerge,colorchannelmixer=aa={shadow:.3}[scene_shadow]"
+        ));
+        base = format!("[scene_base][scene_shadow]overlay=(W-w)/2:(H-h)/2+{offset}");
+    }
+    chains.push(format!(
+        "{base},loop=loop=-1:size=1,setpts=N/({fps}*TB)[scene_bg]"
+    ));
+
+    // 首段承接前面的滤镜链（未标注输入即为录屏画面）。
+    let content = if radius > 0 {
+        chains.push(format!(
+            "color=c=black:s={content_w}x{content_h}:r=1:d=1,format=gray,geq=lum='{}'[scene_mask]",
+            rounded_rect_expr(0, 0, content_w, content_h, radius)
+        ));
+        chains.push("[scene_fg][scene_mask]alphamerge[scene_content]".to_string());
+        "scene_content"
+    } else {
+        "scene_fg"
+    };
+    chains.insert(0, "format=rgba[scene_fg]".to_string());
+    chains.push(format!(
+        "[scene_bg][{content}]overlay=(W-w)/2:(H-h)/2:shortest=1,format=yuv420p"
+    ));
+    chains.join(";")
+}
+
+fn scene_scale(target_w: u32, target_h: u32) -> f64 {
+    target_w.min(target_h) as f64 / SCENE_REFERENCE_SIDE
+}
+
+/// 圆角矩形遮罩表达式：矩形左上角 (x0, y0)，内部 255、外部 0。
+fn rounded_rect_expr(x0: u32, y0: u32, width: u32, height: u32, radius: u32) -> String {
+    let cx = x0 as f64 + width as f64 / 2.0;
+    let cy = y0 as f64 + height as f64 / 2.0;
+    let inner_x = width as f64 / 2.0 - radius as f64;
+    let inner_y = height as f64 / 2.0 - radius as f64;
+    format!(
+        "255*lte(hypot(max(abs(X+0.5-{cx:.1})-{inner_x:.1},0),max(abs(Y+0.5-{cy:.1})-{inner_y:.1},0)),{radius})"
+    )
+}
+
+/// 单帧背景源；壁纸缺失或颜色无法解析时退回深色纯色背景。
+fn background_source(background: &SceneBackground, width: u32, height: u32) -> String {
+    let solid = |(r, g, b): (u8, u8, u8)| {
+        format!("color=c=0x{r:02x}{g:02x}{b:02x}:s={width}x{height}:r=1:d=1")
+    };
+    match background {
+        SceneBackground::Color { color } => {
+            solid(parse_hex_color(color).unwrap_or(SCENE_FALLBACK_COLOR))
+        }
+        SceneBackground::Gradient { from, to } => {
+            let from = parse_hex_color(from).unwrap_or(SCENE_FALLBACK_COLOR);
+            let to = parse_hex_color(to).unwrap_or(SCENE_FALLBACK_COLOR);
+            let channel =
+                |start: u8, end: u8| format!("{start}+({})*(X/W+Y/H)/2", end as i32 - start as i32);
+            format!(
+                "color=c=black:s={width}x{height}:r=1:d=1,format=rgb24,geq=r='{}':g='{}':b='{}'",
+                channel(from.0, to.0),
+                channel(from.1, to.1),
+                channel(from.2, to.2)
+            )
+        }
+        SceneBackground::Wallpaper { path } => {
+            let path = Path::new(path);
+            if !path.is_file() {
+                return solid(SCENE_FALLBACK_COLOR);
+            }
+            format!(
+                "movie='{}',scale={width}:{height}:force_original_aspect_ratio=increase,crop={width}:{height},trim=end_frame=1",
+                escape_filter_path(path)
+            )
+        }
+    }
+}
+
+/// 解析 `#rrggbb` / `rrggbb` 颜色。
+pub fn parse_hex_color(value: &str) -> Option<(u8, u8, u8)> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_scene_filter, parse_hex_color, scene_padding};
+    use crate::domain::models::{SceneBackground, SceneStyle};
+
+    #[test]
+    fn scene_filter_composites_rounded_content_over_static_background() {
+        let scene = SceneStyle {
+            enabled: true,
+            background: SceneBackground::Color {
+                color: "#1e1e2e".to_string(),
+            },
+            corner_radius: 16,
+            padding: 64,
+            shadow: 0.0,
+        };
+        assert_eq!(scene_padding(&scene, 1920, 1080), 64);
+        assert_eq!(scene_padding(&scene, 1280, 720), 42);
+
+        let graph = build_scene_filter(&scene, 1792, 952, 1920, 1080, 30);
+        assert!(graph.starts_with("format=rgba[scene_fg];color=c=0x1e1e2e:s=1920x1080"));
+        assert!(graph.contains("loop=loop=-1:size=1,setpts=N/(30*TB)[scene_bg]"));
+        assert!(graph.contains("[scene_fg][scene_mask]alphamerge[scene_content]"));
+        assert!(graph.ends_with(
+            "[scene_bg][scene_content]overlay=(W-w)/2:(H-h)/2:shortest=1,format=yuv420p"
+        ));
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_hex_color("#4f46e5"), Some((0x4f, 0x46, 0xe5)));
+        assert_eq!(parse_hex_color("db2777"), Some((0xdb, 0x27, 0x77)));
+        assert_eq!(parse_hex_color("red"), None);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::domain::models::{AppError, TimeRange};
+use crate::infra::ffmpeg::analysis::parse_detect_ranges;
+use crate::infra::ffmpeg::command::run_ffmpeg;
+use std::path::Path;
+
+pub const SILENCE_NOISE_DB: f64 = -35.0;
+pub const SILENCE_MIN_DURATION_MS: u64 = 1200;
+
+/// 用 silencedetect 分析音轨，返回静音区间（源文件时间轴）。
+pub fn detect_silence(
+    input_path: &Path,
+    noise_db: f64,
+    min_duration_ms: u64,
+    duration_ms: u64,
+) -> Result<Vec<TimeRange>, AppError> {
+    let output = run_ffmpeg([
+        "-hide_banner".to_string(),
+        "-nostats".to_string(),
+        "-i".to_string(),
+        input_path.to_string_lossy().to_string(),
+        "-vn".to_string(),
+        "-af".to_string(),
+        format!(
+            "silencedetect=noise={noise_db:.1}dB:d={:.3}",
+            min_duration_ms as f64 / 1000.0
+        ),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ])?;
+    if !output.status.success() {
+        return Err(AppError::new(
+            "SILENCE_DETECT_FAILED",
+            output.stderr.lines().last().unwrap_or_default().to_string(),
+            Some("请确认录制文件包含音轨".to_string()),
+        ));
+    }
+    Ok(parse_silencedetect(&output.stderr, duration_ms))
+}
+
+/// 解析 silencedetect 日志；文件末尾未闭合的静音段以 `duration_ms` 收尾。
+fn parse_silencedetect(stderr: &str, duration_ms: u64) -> Vec<TimeRange> {
+    parse_detect_ranges(stderr, "silence_start:", "silence_end:", duration_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_silencedetect;
+
+    #[test]
+    fn parses_silence_ranges_and_closes_trailing_silence() {
+        let stderr = "\
+[silencedetect @ 0x1] silence_start: 1.504
+[silencedetect @ 0x1] silence_end: 3.25 | silence_duration: 1.746
+size=N/A time=00:00:09.00 bitrate=N/A
+[silencedetect @ 0x1] silence_start: 8.1";
+        let ranges = parse_silencedetect(stderr, 9500);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!((ranges[0].start_ms, ranges[0].end_ms), (1504, 3250));
+        assert_eq!((ranges[1].start_ms, ranges[1].end_ms), (8100, 9500));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::domain::models::AppError;
+use crate::infra::ffmpeg::command::run_ffmpeg;
+use std::path::{Path, PathBuf};
+
+pub const THUMBNAIL_WIDTH: u32 = 160;
+pub const MAX_THUMBNAILS: u32 = 120;
+
+/// 均匀分布的取帧时间点，取每段的中点，避开首帧黑屏与末帧截断。
+pub fn thumbnail_times_ms(duration_ms: u64, count: u32) -> Vec<u64> {
+    if duration_ms == 0 || count == 0 {
+        return Vec::new();
+    }
+    let step = duration_ms as f64 / count as f64;
+    (0..count)
+        .map(|index| ((index as f64 + 0.5) * step).round() as u64)
+        .collect()
+}
+
+/// 逐帧 seek 截取缩略图到 `out_dir`，返回与 `times_ms` 一一对应的文件路径。
+pub fn extract_thumbnails(
+    input_path: &Path,
+    out_dir: &Path,
+    times_ms: &[u64],
+    width: u32,
+) -> Result<Vec<PathBuf>, AppError> {
+    times_ms
+        .iter()
+        .enumerate()
+        .map(|(index, t_ms)| {
+            let path = out_dir.join(format!("thumb_{index:03}.jpg"));
+            let output = run_ffmpeg([
+                "-hide_banner".to_string(),
+                "-y".to_string(),
+                "-ss".to_string(),
+                format!("{:.3}", *t_ms as f64 / 1000.0),
+                "-i".to_string(),
+                input_path.to_string_lossy().to_string(),
+                "-frames:v".to_string(),
+                "1".to_string(),
+                "-vf".to_string(),
+                format!("scale={width}:-2"),
+                "-q:v".to_string(),
+                "5".to_string(),
+                path.to_string_lossy().to_string(),
+            ])?;
+            if !output.status.success() || !path.exists() {
+                return Err(AppError::new(
+                    "THUMBNAIL_FAILED",
+                    output.stderr.lines().last().unwrap_or_default().to_string(),
+                    Some("请确认录制文件完整可播放".to_string()),
+                ));
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+/// 截取单帧原尺寸 PNG。输入端 seek 在转码时是帧精确的，长录制也能快速定位。
+pub fn extract_frame_png(input_path: &Path, t_ms: u64, output_path: &Path) -> Result<(), AppError> {
+    let output = run_ffmpeg([
+        "-hide_banner".to_string(),
+        "-y".to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", t_ms as f64 / 1000.0),
+        "-i".to_string(),
+        input_path.to_string_lossy().to_string(),
+        "-frames:v".to_string(),
+        "1".to_string(),
+        "-c:v".to_string(),
+        "png".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ])?;
+    if !output.status.success() || !output_path.exists() {
+        return Err(AppError::new(
+            "FRAME_EXTRACT_FAILED",
+            output.stderr.lines().last().unwrap_or_default().to_string(),
+            Some("请确认录制文件完整，且时间点在录制时长以内".to_string()),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::thumbnail_times_ms;
+
+    #[test]
+    fn thumbnail_times_sit_at_segment_midpoints() {
+        assert_eq!(
+            thumbnail_times_ms(10_000, 4),
+            vec![1_250, 3_750, 6_250, 8_750]
+        );
+        assert!(thumbnail_times_ms(0, 4).is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+// 过短的保留片段（秒）并入相邻剪切区间，避免产生一闪而过的画面。
+const MIN_SEGMENT_SEC: f64 = 0.1;
+
+/// 导出时间轴（裁剪起点为 0）上需要删除或加速的区间。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RemapCut {
+    pub start_sec: f64,
+    pub end_sec: f64,
+    /// None 表示删除该区间，否则按倍速播放。
+    pub speed: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RemapSegment {
+    pub start_sec: f64,
+    pub end_sec: f64,
+    pub speed: f64,
+}
+
+/// 把剪切区间展开为首尾相接的播放片段：区间外原速保留，删除区间直接跳过。
+/// 删除区间优先于加速区间；加速区间互相重叠时先开始的优先。
+/// 没有任何剪切时返回空列表，调用方据此跳过整段重映射。
+pub fn plan_remap(duration_sec: f64, cuts: &[RemapCut]) -> Vec<RemapSegment> {
+    let clamped = cuts.iter().map(|cut| RemapCut {
+        start_sec: cut.start_sec.max(0.0),
+        end_sec: cut.end_sec.min(duration_sec),
+        speed: cut.speed,
+    });
+    let (drops, speeds): (Vec<_>, Vec<_>) = clamped.partition(|cut| cut.speed.is_none());
+    let mut cuts = speeds
+        .iter()
+        .flat_map(|cut| subtract_drops(*cut, &drops))
+        .chain(drops.iter().copied())
+        .filter(|cut| cut.end_sec - cut.start_sec >= MIN_SEGMENT_SEC)
+        .collect::<Vec<_>>();
+    if cuts.is_empty() {
+        return Vec::new();
+    }
+    cuts.sort_by(|a, b| a.start_sec.total_cmp(&b.start_sec));
+
+    let mut segments = Vec::new();
+    let mut cursor = 0.0;
+    for cut in cuts {
+        let start = cut.start_sec.max(cursor);
+        if cut.end_sec <= start {
+            continue;
+        }
+        if start - cursor >= MIN_SEGMENT_SEC {
+            segments.push(RemapSegment {
+                start_sec: cursor,
+                end_sec: start,
+                speed: 1.0,
+            });
+        }
+        if let Some(speed) = cut.speed {
+            segments.push(RemapSegment {
+                start_sec: start,
+                end_sec: cut.end_sec,
+                speed,
+            });
+        }
+        cursor = cut.end_sec;
+    }
+    if duration_sec - cursor >= MIN_SEGMENT_SEC {
+        segments.push(RemapSegment {
+            start_sec: cursor,
+            end_sec: duration_sec,
+            speed: 1.0,
+        });
+    }
+    segments
+}
+
+/// 从加速区间中挖掉删除区间，可能拆成多段。
+fn subtract_drops(cut: RemapCut, drops: &[RemapCut]) -> Vec<RemapCut> {
+    drops.iter().fold(vec![cut], |pieces, drop| {
+        pieces
+            .into_iter()
+            .flat_map(|piece| {
+                if drop.end_sec <= piece.start_sec || drop.start_sec >= piece.end_sec {
+                    return vec![piece];
+                }
+                [
+                    RemapCut {
+                        end_sec: drop.start_sec,
+                        ..piece
+                    },
+                    RemapCut {
+                        start_sec: drop.end_sec,
+                        ..piece
+                    },
+                ]
+                .into_iter()
+                .filter(|part| part.end_sec > part.start_sec)
+                .collect()
+            })
+            .collect()
+    })
+}
+
+/// 在加速片段（重映射前的时间轴）右上角标注倍速，如 “4×”。
+pub fn build_speed_indicator_filters(
+    segments: &[RemapSegment],
+    font: Option<&str>,
+    target_w: u32,
+    target_h: u32,
+) -> Vec<String> {
+    let scale = target_w.min(target_h) as f64 / 1080.0;
+    let font_size = ((36.0 * scale).round() as u32).max(8);
+    let margin = (40.0 * scale).round() as u32;
+    let padding = (font_size as f64 * 0.4).round() as u32;
+    let font = font
+        .map(|font| format!("fontfile='{font}':"))
+        .unwrap_or_default();
+    segments
+        .iter()
+        .filter(|segment| segment.speed > 1.0)
+        .map(|segment| {
+            let speed = if segment.speed.fract().abs() < 1e-6 {
+                format!("{}", segment.speed as u32)
+            } else {
+                format!("{:.1}", segment.speed)
+            };
+            format!(
+                "drawtext={font}text={speed}×:expansion=none:fontsize={font_size}:fontcolor=white:box=1:boxcolor=black@0.55:boxborderw={padding}:x=w-text_w-{}:y={}:enable='between(t,{:.3},{:.3})'",
+                margin + padding,
+                margin + padding,
+                segment.start_sec,
+                segment.end_sec
+            )
+        })
+        .collect()
+}
+
+pub fn remapped_duration_sec(segments: &[RemapSegment]) -> f64 {
+    segments
+        .iter()
+        .map(|segment| (segment.end_sec - segment.start_sec) / segment.speed)
+        .sum()
+}
+
+/// 把导出时间轴上的区间映射到成片时间轴；区间横跨删除段时首尾收缩到保留部分，
+/// 完全落在删除段内时返回 None。没有重映射片段时原样返回。
+pub fn remap_range_sec(
+    segments: &[RemapSegment],
+    start_sec: f64,
+    end_sec: f64,
+) -> Option<(f64, f64)> {
+    if segments.is_empty() {
+        return (end_sec > start_sec).then_some((start_sec, end_sec));
+    }
+    let mut offset = 0.0;
+    let mut mapped: Option<(f64, f64)> = None;
+    for segment in segments {
+        let start = start_sec.max(segment.start_sec);
+        let end = end_sec.min(segment.end_sec);
+        if end > start {
+            let to_output = |t: f64| offset + (t - segment.start_sec) / segment.speed;
+            mapped = Some((
+                mapped.map_or(to_output(start), |(first, _)| first),
+                to_output(end),
+            ));
+        }
+        offset += (segment.end_sec - segment.start_sec) / segment.speed;
+    }
+    mapped
+}
+
+/// 视频按片段 trim 后拼接，加速片段用 setpts 压缩时间戳。
+pub fn build_video_remap(segments: &[RemapSegment]) -> String {
+    let count = segments.len();
+    let mut chains = vec![format!("split={count}{}", labels("remap_vin", count))];
+    for (index, segment) in segments.iter().enumerate() {
+        chains.push(format!(
+            "[remap_vin{index}]trim=start={:.3}:end={:.3},setpts=(PTS-STARTPTS)/{:.3}[remap_v{index}]",
+            segment.start_sec, segment.end_sec, segment.speed
+        ));
+    }
+    chains.push(format!(
+        "{}concat=n={count}:v=1:a=0",
+        labels("remap_v", count)
+    ));
+    chains.join(";")
+}
+
+/// 音频同样分段拼接，加速片段用 atempo 变速不变调。
+pub fn build_audio_remap(segments: &[RemapSegment]) -> String {
+    let count = segments.len();
+    let mut chains = vec![format!("asplit={count}{}", labels("remap_ain", count))];
+    for (index, segment) in segments.iter().enumerate() {
+        let tempo = if (segment.speed - 1.0).abs() > f64::EPSILON {
+            format!(",atempo={:.3}", segment.speed)
+        } else {
+            String::new()
+        };
+        chains.push(format!(
+            "[remap_ain{index}]atrim=start={:.3}:end={:.3},asetpts=PTS-STARTPTS{tempo}[remap_a{index}]",
+            segment.start_sec, segment.end_sec
+        ));
+    }
+    chains.push(format!(
+        "{}concat=n={count}:v=0:a=1",
+        labels("remap_a", count)
+    ));
+    chains.join(";")
+}
+
+fn labels(prefix: &str, count: usize) -> String {
+    (0..count)
+        .map(|index| format!("[{prefix}{index}]"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_audio_remap, build_speed_indicator_filters, plan_remap, remap_range_sec,
+        remapped_duration_sec, RemapCut, RemapSegment,
+    };
+
+    #[test]
+    fn plan_remap_skips_dropped_and_keeps_sped_up_ranges() {
+        let cuts = [
+            RemapCut {
+                start_sec: 6.0,
+                end_sec: 8.0,
+                speed: Some(4.0),
+            },
+            RemapCut {
+                start_sec: 2.0,
+                end_sec: 3.0,
+                speed: None,
+            },
+        ];
+        let segments = plan_remap(10.0, &cuts);
+        assert_eq!(
+            segments,
+            vec![
+                RemapSegment {
+                    start_sec: 0.0,
+                    end_sec: 2.0,
+                    speed: 1.0
+                },
+                RemapSegment {
+                    start_sec: 3.0,
+                    end_sec: 6.0,
+                    speed: 1.0
+                },
+                RemapSegment {
+                    start_sec: 6.0,
+                    end_sec: 8.0,
+                    speed: 4.0
+                },
+                RemapSegment {
+                    start_sec: 8.0,
+                    end_sec: 10.0,
+                    speed: 1.0
+                },
+            ]
+        );
+        assert!((remapped_duration_sec(&segments) - 7.5).abs() < 1e-9);
+        assert_eq!(remap_range_sec(&segments, 1.0, 2.5), Some((1.0, 2.0)));
+        assert_eq!(remap_range_sec(&segments, 2.2, 2.8), None);
+        assert_eq!(remap_range_sec(&segments, 7.0, 9.0), Some((5.25, 6.5)));
+        assert_eq!(remap_range_sec(&[], 1.0, 2.0), Some((1.0, 2.0)));
+        assert!(build_audio_remap(&segments).contains(
+            "[remap_ain2]atrim=start=6.000:end=8.000,asetpts=PTS-STARTPTS,atempo=4.000[remap_a2]"
+        ));
+        assert!(plan_remap(10.0, &[]).is_empty());
+        let indicators = build_speed_indicator_filters(&segments, None, 1920, 1080);
+        assert_eq!(indicators.len(), 1);
+        assert!(indicators[0].starts_with("drawtext=text=4×:"));
+    }
+
+    #[test]
+    fn dropped_ranges_take_precedence_over_speed_ups() {
+        let cuts = [
+            RemapCut {
+                start_sec: 2.0,
+                end_sec: 8.0,
+                speed: Some(4.0),
+            },
+            RemapCut {
+                start_sec: 4.0,
+                end_sec: 5.0,
+                speed: None,
+            },
+        ];
+        let spans = plan_remap(10.0, &cuts)
+            .iter()
+            .map(|segment| (segment.start_sec, segment.end_sec, segment.speed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            vec![
+                (0.0, 2.0, 1.0),
+                (2.0, 4.0, 4.0),
+                (5.0, 8.0, 4.0),
+                (8.0, 10.0, 1.0)
+            ]
+        );
+    }
+}
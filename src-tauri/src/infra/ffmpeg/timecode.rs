@@ -0,0 +1,112 @@
+use crate::domain::models::{OverlayCorner, TimecodeMode};
+use crate::infra::ffmpeg::keystroke_overlay::escape_drawtext;
+use chrono::{DateTime, Utc};
+
+// 字号、边距以 1080p 短边为基准，与倍速标注一致。
+const TIMECODE_FONT_SIZE: f64 = 32.0;
+const TIMECODE_MARGIN: f64 = 40.0;
+
+/// 时间码 drawtext 滤镜，放在重映射之前，删除或加速的片段处时间码随之跳变，
+/// 画面上的时间始终对应原始录制。`WallClock` 缺少录制开始时刻时退回相对时间码；
+/// 录制中暂停过的项目，暂停之后的时钟会比实际偏早。
+pub fn build_timecode_filter(
+    mode: TimecodeMode,
+    corner: OverlayCorner,
+    font: Option<&str>,
+    target_w: u32,
+    target_h: u32,
+    trim_start_ms: u64,
+    recording_started_at: Option<DateTime<Utc>>,
+) -> Option<String> {
+    let trim_start_sec = trim_start_ms as f64 / 1000.0;
+    let text = match (mode, recording_started_at) {
+        (TimecodeMode::Off, _) => return None,
+        (TimecodeMode::WallClock, Some(started_at)) => format!(
+            "%{{pts:localtime:{:.3}}}",
+            started_at.timestamp_millis() as f64 / 1000.0 + trim_start_sec
+        ),
+        (TimecodeMode::Elapsed | TimecodeMode::WallClock, _) => {
+            format!("%{{pts:hms:{trim_start_sec:.3}}}")
+        }
+    };
+    let scale = target_w.min(target_h) as f64 / 1080.0;
+    let font_size = ((TIMECODE_FONT_SIZE * scale).round() as u32).max(8);
+    let padding = (font_size as f64 * 0.4).round() as u32;
+    let inset = (TIMECODE_MARGIN * scale).round() as u32 + padding;
+    let (x, y) = match corner {
+        OverlayCorner::TopLeft => (format!("{inset}"), format!("{inset}")),
+        OverlayCorner::TopRight => (format!("w-text_w-{inset}"), format!("{inset}")),
+        OverlayCorner::BottomLeft => (format!("{inset}"), format!("h-text_h-{inset}")),
+        OverlayCorner::BottomRight => (format!("w-text_w-{inset}"), format!("h-text_h-{inset}")),
+    };
+    let font = font
+        .map(|font| format!("fontfile='{font}':"))
+        .unwrap_or_default();
+    Some(format!(
+        "drawtext={font}text={}:fontsize={font_size}:fontcolor=white:box=1:boxcolor=black@0.55:boxborderw={padding}:x={x}:y={y}",
+        escape_drawtext(&text)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_timecode_filter;
+    use crate::domain::models::{OverlayCorner, TimecodeMode};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn timecode_offsets_by_trim_and_recording_start() {
+        assert_eq!(
+            build_timecode_filter(
+                TimecodeMode::Off,
+                OverlayCorner::TopLeft,
+                None,
+                1920,
+                1080,
+                0,
+                None
+            ),
+            None
+        );
+        let elapsed = build_timecode_filter(
+            TimecodeMode::Elapsed,
+            OverlayCorner::BottomRight,
+            None,
+            1920,
+            1080,
+            2_500,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            elapsed,
+            "drawtext=text=%{pts\\\\:hms\\\\:2.500}:fontsize=32:fontcolor=white:box=1:boxcolor=black@0.55:boxborderw=13:x=w-text_w-53:y=h-text_h-53"
+        );
+
+        let started_at = Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap();
+        let wall_clock = build_timecode_filter(
+            TimecodeMode::WallClock,
+            OverlayCorner::TopLeft,
+            Some("/fonts/a.ttf"),
+            1280,
+            720,
+            1_000,
+            Some(started_at),
+        )
+        .unwrap();
+        assert!(wall_clock.starts_with(
+            "drawtext=fontfile='/fonts/a.ttf':text=%{pts\\\\:localtime\\\\:1772357401.000}:fontsize=21:"
+        ));
+        let fallback = build_timecode_filter(
+            TimecodeMode::WallClock,
+            OverlayCorner::TopLeft,
+            None,
+            1280,
+            720,
+            0,
+            None,
+        )
+        .unwrap();
+        assert!(fallback.contains(":hms\\\\:0.000}"));
+    }
+}
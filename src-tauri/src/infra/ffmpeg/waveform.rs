@@ -0,0 +1,81 @@
+use crate::domain::models::AppError;
+use crate::infra::ffmpeg::command::{
+    command_error, ffmpeg_bin, ffmpeg_timeout, output_with_timeout,
+};
+use std::path::Path;
+use std::process::Command;
+
+// 波形只需要包络，单声道 8kHz 足够且解码数据量小。
+const WAVEFORM_SAMPLE_RATE: u32 = 8_000;
+pub const MAX_WAVEFORM_SAMPLES_PER_SECOND: u32 = 200;
+
+/// 解码音轨为 s16le 单声道 PCM，按 `samples_per_second` 分桶取峰值（0~1）。
+pub fn extract_waveform_peaks(
+    input_path: &Path,
+    samples_per_second: u32,
+) -> Result<Vec<f32>, AppError> {
+    let output = output_with_timeout(
+        Command::new(ffmpeg_bin())
+            .args(["-hide_banner", "-nostats", "-i"])
+            .arg(input_path)
+            .args(["-vn", "-ac", "1", "-ar"])
+            .arg(WAVEFORM_SAMPLE_RATE.to_string())
+            .args(["-f", "s16le", "-"]),
+        ffmpeg_timeout(),
+    )
+    .map_err(|error| {
+        command_error(
+            error,
+            "FFMPEG_EXEC_ERROR",
+            "failed to run ffmpeg",
+            Some("确认 ffmpeg 安装状态"),
+        )
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::new(
+            "WAVEFORM_FAILED",
+            stderr.lines().last().unwrap_or_default().to_string(),
+            Some("请确认录制文件包含音轨".to_string()),
+        ));
+    }
+    let bucket_len = (WAVEFORM_SAMPLE_RATE / samples_per_second.max(1)).max(1) as usize;
+    Ok(peak_buckets(&output.stdout, bucket_len))
+}
+
+/// 每 `bucket_len` 个采样取绝对值峰值，末尾不足一桶的采样单独成桶。
+fn peak_buckets(pcm: &[u8], bucket_len: usize) -> Vec<f32> {
+    let samples = pcm
+        .chunks_exact(2)
+        .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect::<Vec<_>>();
+    samples
+        .chunks(bucket_len)
+        .map(|bucket| {
+            let peak = bucket
+                .iter()
+                .map(|sample| sample.unsigned_abs())
+                .max()
+                .unwrap_or(0);
+            (peak as f32 / i16::MAX as f32).min(1.0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::peak_buckets;
+
+    #[test]
+    fn peak_buckets_take_absolute_maximum() {
+        let pcm = [0i16, -16384, 8192, 32767, -32768]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect::<Vec<_>>();
+        let peaks = peak_buckets(&pcm, 2);
+        assert_eq!(peaks.len(), 3);
+        assert!((peaks[0] - 0.5).abs() < 1e-3);
+        assert!((peaks[1] - 1.0).abs() < 1e-6);
+        assert!((peaks[2] - 1.0).abs() < 1e-6);
+    }
+}
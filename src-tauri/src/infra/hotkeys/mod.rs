@@ -0,0 +1,65 @@
+use crate::domain::models::{AppError, HotkeySettings};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    StartStop,
+    PauseResume,
+}
+
+/// 注销已有快捷键后按设置重新注册，窗口失焦时同样生效。
+pub fn register_hotkeys<R, F>(
+    app: &AppHandle<R>,
+    settings: &HotkeySettings,
+    on_action: F,
+) -> Result<(), AppError>
+where
+    R: Runtime,
+    F: Fn(&AppHandle<R>, HotkeyAction) + Clone + Send + Sync + 'static,
+{
+    let bindings = [
+        (settings.start_stop.trim(), HotkeyAction::StartStop),
+        (settings.pause_resume.trim(), HotkeyAction::PauseResume),
+    ];
+    // 清空的快捷键不注册，两个都清空不算冲突。
+    let (start_stop, pause_resume) = (bindings[0].0, bindings[1].0);
+    if !start_stop.is_empty() && start_stop.eq_ignore_ascii_case(pause_resume) {
+        return Err(AppError::new(
+            "HOTKEY_CONFLICT",
+            "开始/停止与暂停/继续不能使用相同的快捷键",
+            None,
+        ));
+    }
+
+    let shortcuts = app.global_shortcut();
+    shortcuts.unregister_all().map_err(register_error)?;
+    for (accelerator, action) in bindings {
+        if accelerator.is_empty() {
+            continue;
+        }
+        let on_action = on_action.clone();
+        shortcuts
+            .on_shortcut(accelerator, move |app, _shortcut, event| {
+                if event.state() == ShortcutState::Pressed {
+                    on_action(app, action);
+                }
+            })
+            .map_err(|error| {
+                AppError::new(
+                    "HOTKEY_REGISTER_FAIL",
+                    format!("failed to register hotkey {accelerator}: {error}"),
+                    Some("请检查快捷键格式，或是否已被其他应用占用".to_string()),
+                )
+            })?;
+    }
+    Ok(())
+}
+
+fn register_error(error: tauri_plugin_global_shortcut::Error) -> AppError {
+    AppError::new(
+        "HOTKEY_REGISTER_FAIL",
+        format!("failed to reset hotkeys: {error}"),
+        None,
+    )
+}
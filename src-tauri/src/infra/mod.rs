@@ -1,3 +1,10 @@
+pub mod desktop;
+pub mod do_not_disturb;
+pub mod events;
 pub mod ffmpeg;
+pub mod hotkeys;
 pub mod logging;
+pub mod ocr;
+pub mod share_server;
 pub mod storage;
+pub mod upload;
@@ -0,0 +1,147 @@
+use crate::domain::models::AppError;
+use crate::infra::ffmpeg::command::{command_error, output_with_timeout};
+use crate::infra::ffmpeg::installer::resolve_binary;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+// 单帧识别通常在数秒内完成，超时多为引擎卡死或画面异常巨大。
+const OCR_TIMEOUT: Duration = Duration::from_secs(60);
+// 置信度过低的词多为图标或噪点，参与匹配只会制造误报。
+const MIN_WORD_CONFIDENCE: f64 = 30.0;
+
+/// tesseract 的 `(page, block, par, line)` 编号，相同即为同一行。
+type LineKey = (u32, u32, u32, u32);
+
+/// 识别出的单词及其在帧内的像素框。
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrWord {
+    pub text: String,
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 优先使用 `FOCUSLENS_TESSERACT_PATH`，其次是托管目录或 sidecar，最后回退到 PATH。
+pub fn tesseract_bin() -> String {
+    resolve_binary("tesseract", "FOCUSLENS_TESSERACT_PATH").0
+}
+
+/// 扫描前确认 OCR 引擎可用，避免抽帧完成后才发现无法识别。
+pub fn ensure_ocr_available() -> Result<(), AppError> {
+    let output = output_with_timeout(Command::new(tesseract_bin()).arg("--version"), OCR_TIMEOUT)
+        .map_err(|error| {
+        command_error(
+            error,
+            "OCR_ENGINE_NOT_FOUND",
+            "failed to execute tesseract",
+            Some("请安装 Tesseract OCR，或设置 FOCUSLENS_TESSERACT_PATH"),
+        )
+    })?;
+    if !output.status.success() {
+        return Err(AppError::new(
+            "OCR_ENGINE_NOT_FOUND",
+            "tesseract command exists but returns non-zero on --version",
+            Some("确认 Tesseract OCR 可正常运行".to_string()),
+        ));
+    }
+    Ok(())
+}
+
+/// 识别单帧图片，按行返回单词（同一行内按从左到右的顺序）。
+pub fn recognize_lines(image_path: &Path) -> Result<Vec<Vec<OcrWord>>, AppError> {
+    let output = output_with_timeout(
+        Command::new(tesseract_bin())
+            .arg(image_path)
+            .arg("stdout")
+            .arg("tsv"),
+        OCR_TIMEOUT,
+    )
+    .map_err(|error| {
+        command_error(
+            error,
+            "SENSITIVE_SCAN_FAILED",
+            "failed to run tesseract",
+            Some("确认 Tesseract OCR 安装状态后重试"),
+        )
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::new(
+            "SENSITIVE_SCAN_FAILED",
+            stderr.lines().last().unwrap_or_default().to_string(),
+            Some("确认 Tesseract OCR 安装状态后重试".to_string()),
+        ));
+    }
+    Ok(parse_tsv(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 解析 tesseract 的 TSV 输出：只取 level 5（单词）行，
+/// 按 `block/par/line` 编号分组为文本行。
+fn parse_tsv(tsv: &str) -> Vec<Vec<OcrWord>> {
+    let mut lines: Vec<(LineKey, Vec<OcrWord>)> = Vec::new();
+    for row in tsv.lines().skip(1) {
+        let columns = row.split('\t').collect::<Vec<_>>();
+        if columns.len() < 12 || columns[0] != "5" {
+            continue;
+        }
+        let number = |index: usize| columns[index].trim().parse::<u32>().ok();
+        let confidence = columns[10].trim().parse::<f64>().unwrap_or(-1.0);
+        let text = columns[11].trim();
+        if text.is_empty() || confidence < MIN_WORD_CONFIDENCE {
+            continue;
+        }
+        let (Some(page), Some(block), Some(par), Some(line)) =
+            (number(1), number(2), number(3), number(4))
+        else {
+            continue;
+        };
+        let (Some(left), Some(top), Some(width), Some(height)) =
+            (number(6), number(7), number(8), number(9))
+        else {
+            continue;
+        };
+        let word = OcrWord {
+            text: text.to_string(),
+            left,
+            top,
+            width,
+            height,
+        };
+        let key = (page, block, par, line);
+        match lines.last_mut() {
+            Some((last_key, words)) if *last_key == key => words.push(word),
+            _ => lines.push((key, vec![word])),
+        }
+    }
+    lines.into_iter().map(|(_, words)| words).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_tsv;
+
+    #[test]
+    fn tsv_words_are_grouped_by_line() {
+        let tsv = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+1\t1\t0\t0\t0\t0\t0\t0\t1920\t1080\t-1\t\n\
+4\t1\t1\t1\t1\t0\t100\t40\t300\t20\t-1\t\n\
+5\t1\t1\t1\t1\t1\t100\t40\t80\t20\t96.1\tcontact:\n\
+5\t1\t1\t1\t1\t2\t190\t40\t210\t20\t91.4\tjane@example.com\n\
+5\t1\t1\t1\t2\t1\t100\t80\t20\t20\t12.0\t~\n\
+5\t1\t1\t1\t3\t1\t100\t120\t60\t20\t88.0\tsk-live\n";
+        let lines = parse_tsv(tsv);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0]
+                .iter()
+                .map(|word| word.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["contact:", "jane@example.com"]
+        );
+        assert_eq!((lines[0][1].left, lines[0][1].width), (190, 210));
+        // 低置信度的噪点被过滤，整行随之消失。
+        assert_eq!(lines[1][0].text, "sk-live");
+    }
+}
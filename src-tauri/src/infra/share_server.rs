@@ -0,0 +1,348 @@
+use crate::domain::models::AppError;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const ACCEPT_POLL: Duration = Duration::from_millis(200);
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// 请求头上限，超出直接断开，避免被慢速或畸形请求占住线程。
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+const SHARE_PATH_PREFIX: &str = "/s/";
+
+#[derive(Debug, Clone)]
+pub struct SharedFile {
+    pub project_id: String,
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 令牌 → 分享文件。服务线程用 `blocking_lock` 读取，命令侧 `.await` 取锁。
+pub type ShareLinks = Arc<Mutex<HashMap<String, SharedFile>>>;
+
+/// 局域网分享服务：监听所有网卡，只响应 `GET/HEAD /s/<令牌>`，
+/// 令牌过期或撤销后返回 404。实例释放时停止监听。
+pub struct ShareServer {
+    host: String,
+    port: u16,
+    links: ShareLinks,
+    stop: Arc<AtomicBool>,
+}
+
+impl ShareServer {
+    /// `port` 为 0 时由系统分配空闲端口。
+    pub fn start(port: u16) -> Result<Self, AppError> {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                Ok(listener)
+            })
+            .map_err(|error| {
+                AppError::new(
+                    "SHARE_SERVER_START_FAIL",
+                    format!("failed to listen on port {port}: {error}"),
+                    Some("端口可能已被占用，请在设置中更换分享端口".to_string()),
+                )
+                .with_param("port", port)
+            })?;
+        let port = listener
+            .local_addr()
+            .map(|address| address.port())
+            .unwrap_or(port);
+        let links: ShareLinks = Arc::new(Mutex::new(HashMap::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let server_links = links.clone();
+        let server_stop = stop.clone();
+        std::thread::spawn(move || serve(listener, server_links, server_stop));
+        tracing::info!("share server listening on port {port}");
+        Ok(Self {
+            host: lan_address(),
+            port,
+            links,
+            stop,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn links(&self) -> &ShareLinks {
+        &self.links
+    }
+
+    pub fn url(&self, token: &str) -> String {
+        format!(
+            "http://{}:{}{SHARE_PATH_PREFIX}{token}",
+            self.host, self.port
+        )
+    }
+}
+
+impl Drop for ShareServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+/// 256 位随机令牌，链接本身即访问凭据。
+pub fn new_share_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// 本机在局域网中的地址：向外部地址“连接”UDP 套接字只选路由，不发送数据。
+fn lan_address() -> String {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect(("8.8.8.8", 80))?;
+            socket.local_addr()
+        })
+        .map(|address| address.ip().to_string())
+        .unwrap_or_else(|_| Ipv4Addr::LOCALHOST.to_string())
+}
+
+fn serve(listener: TcpListener, links: ShareLinks, stop: Arc<AtomicBool>) {
+    while !stop.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let links = links.clone();
+                std::thread::spawn(move || {
+                    if let Err(error) = handle_connection(stream, &links) {
+                        tracing::debug!("share request failed: {error}");
+                    }
+                });
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL);
+            }
+            Err(error) => {
+                tracing::warn!("share server accept failed: {error}");
+                std::thread::sleep(ACCEPT_POLL);
+            }
+        }
+    }
+    tracing::info!("share server stopped");
+}
+
+struct Request {
+    method: String,
+    path: String,
+    range: Option<String>,
+}
+
+fn handle_connection(mut stream: TcpStream, links: &ShareLinks) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let Some(request) = read_request(&stream)? else {
+        return respond_status(&mut stream, 400, "Bad Request");
+    };
+    if request.method != "GET" && request.method != "HEAD" {
+        return respond_status(&mut stream, 405, "Method Not Allowed");
+    }
+    let shared = request
+        .path
+        .strip_prefix(SHARE_PATH_PREFIX)
+        .and_then(|token| links.blocking_lock().get(token).cloned())
+        .filter(|shared| shared.expires_at > Utc::now());
+    let Some(shared) = shared else {
+        return respond_status(&mut stream, 404, "Not Found");
+    };
+    let Ok(mut file) = File::open(&shared.path) else {
+        return respond_status(&mut stream, 404, "Not Found");
+    };
+    let length = file.metadata()?.len();
+    let range = match request.range.as_deref() {
+        Some(header) => match parse_range(header, length) {
+            Some(range) => Some(range),
+            None => {
+                write!(
+                    stream,
+                    "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{length}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )?;
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+    let (start, end) = range.unwrap_or((0, length.saturating_sub(1)));
+    let body_length = if length == 0 { 0 } else { end - start + 1 };
+    let status = if range.is_some() {
+        "206 Partial Content"
+    } else {
+        "200 OK"
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {}\r\nContent-Length: {body_length}\r\nAccept-Ranges: bytes\r\nCache-Control: no-store\r\nConnection: close\r\n",
+        content_type(&shared.path)
+    )?;
+    if range.is_some() {
+        write!(stream, "Content-Range: bytes {start}-{end}/{length}\r\n")?;
+    }
+    write!(stream, "\r\n")?;
+    if request.method == "GET" && body_length > 0 {
+        file.seek(SeekFrom::Start(start))?;
+        std::io::copy(&mut file.take(body_length), &mut stream)?;
+    }
+    stream.flush()
+}
+
+fn read_request(stream: &TcpStream) -> std::io::Result<Option<Request>> {
+    let mut reader = BufReader::new(stream).take(MAX_HEADER_BYTES as u64);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Ok(None);
+    };
+    let mut request = Request {
+        method: method.to_string(),
+        // 查询串不参与匹配。
+        path: path.split('?').next().unwrap_or_default().to_string(),
+        range: None,
+    };
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            return Ok(Some(request));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("range") {
+                request.range = Some(value.trim().to_string());
+            }
+        }
+    }
+}
+
+/// 解析单段 `Range` 头（`bytes=0-99`、`bytes=100-`、`bytes=-100`），返回闭区间。
+fn parse_range(header: &str, length: u64) -> Option<(u64, u64)> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') || length == 0 {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix = suffix.parse::<u64>().ok()?.min(length);
+            if suffix == 0 {
+                return None;
+            }
+            (length - suffix, length - 1)
+        }
+        (start, "") => (start.parse().ok()?, length - 1),
+        (start, end) => (
+            start.parse().ok()?,
+            end.parse::<u64>().ok()?.min(length - 1),
+        ),
+    };
+    (start <= end && start < length).then_some((start, end))
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("gif") => "image/gif",
+        Some("webm") => "video/webm",
+        Some("mov") => "video/quicktime",
+        _ => "video/mp4",
+    }
+}
+
+fn respond_status(stream: &mut TcpStream, code: u16, reason: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {code} {reason}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{reason}",
+        reason.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new_share_token, parse_range, ShareServer, SharedFile};
+    use chrono::{Duration, Utc};
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use tempfile::tempdir;
+
+    #[test]
+    fn range_headers_are_clamped_to_the_file() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 99)));
+        assert_eq!(parse_range("bytes=900-", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=0-5000", 1000), Some((0, 999)));
+        assert_eq!(parse_range("bytes=1000-", 1000), None);
+        assert_eq!(parse_range("bytes=0-1,5-6", 1000), None);
+        assert_eq!(parse_range("items=0-1", 1000), None);
+    }
+
+    fn get(port: u16, path: &str, range: Option<&str>) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let range = range
+            .map(|range| format!("Range: {range}\r\n"))
+            .unwrap_or_default();
+        write!(stream, "GET {path} HTTP/1.1\r\nHost: test\r\n{range}\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_only_live_tokens() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("final.mp4");
+        std::fs::write(&path, b"0123456789").unwrap();
+        let server = ShareServer::start(0).unwrap();
+        let live = new_share_token();
+        let expired = new_share_token();
+        {
+            let mut links = server.links().blocking_lock();
+            for (token, expires_at) in [
+                (&live, Utc::now() + Duration::hours(1)),
+                (&expired, Utc::now() - Duration::seconds(1)),
+            ] {
+                links.insert(
+                    token.clone(),
+                    SharedFile {
+                        project_id: "p1".to_string(),
+                        path: path.clone(),
+                        created_at: Utc::now(),
+                        expires_at,
+                    },
+                );
+            }
+        }
+
+        let full = get(server.port(), &format!("/s/{live}"), None);
+        assert!(full.starts_with("HTTP/1.1 200 OK"));
+        assert!(full.contains("Content-Type: video/mp4"));
+        assert!(full.ends_with("\r\n\r\n0123456789"));
+
+        let partial = get(server.port(), &format!("/s/{live}"), Some("bytes=2-4"));
+        assert!(partial.starts_with("HTTP/1.1 206 Partial Content"));
+        assert!(partial.contains("Content-Range: bytes 2-4/10"));
+        assert!(partial.ends_with("\r\n\r\n234"));
+
+        assert!(get(server.port(), &format!("/s/{expired}"), None).starts_with("HTTP/1.1 404"));
+        assert!(get(server.port(), "/s/unknown", None).starts_with("HTTP/1.1 404"));
+        assert!(get(server.port(), "/", None).starts_with("HTTP/1.1 404"));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::domain::models::AppError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// 派生产物所依据的源文件指纹；重新录制会改变大小或修改时间，缓存随之失效。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceFingerprint {
+    pub len: u64,
+    pub modified_ms: u64,
+}
+
+pub fn source_fingerprint(path: &Path) -> Result<SourceFingerprint, AppError> {
+    let metadata = std::fs::metadata(path).map_err(|error| {
+        AppError::new(
+            "PROJECT_ASSET_MISSING",
+            format!("failed to read {}: {error}", path.display()),
+            Some("请重新录制后再试".to_string()),
+        )
+    })?;
+    let modified_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+    Ok(SourceFingerprint {
+        len: metadata.len(),
+        modified_ms,
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry<K, T> {
+    source: SourceFingerprint,
+    key: K,
+    data: T,
+}
+
+/// 读取缓存：源文件指纹与生成参数 `key` 都一致时才命中，文件缺失或损坏视为未命中。
+pub fn load_cached<K, T>(cache_path: &Path, source: SourceFingerprint, key: &K) -> Option<T>
+where
+    K: DeserializeOwned + PartialEq,
+    T: DeserializeOwned,
+{
+    let raw = std::fs::read_to_string(cache_path).ok()?;
+    let entry = serde_json::from_str::<CacheEntry<K, T>>(&raw).ok()?;
+    (entry.source == source && &entry.key == key).then_some(entry.data)
+}
+
+pub fn save_cached<K, T>(
+    cache_path: &Path,
+    source: SourceFingerprint,
+    key: &K,
+    data: &T,
+) -> Result<(), AppError>
+where
+    K: Serialize,
+    T: Serialize,
+{
+    let content = serde_json::to_string(&CacheEntry { source, key, data }).map_err(|error| {
+        AppError::new(
+            "SERDE_ERROR",
+            format!("failed to serialize artifact cache: {error}"),
+            None,
+        )
+    })?;
+    std::fs::write(cache_path, content).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to write artifact cache: {error}"),
+            Some("确认磁盘空间和路径权限".to_string()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_cached, save_cached, SourceFingerprint};
+    use tempfile::tempdir;
+
+    #[test]
+    fn cache_misses_when_source_or_key_changes() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("cache.json");
+        let source = SourceFingerprint {
+            len: 1024,
+            modified_ms: 1,
+        };
+        save_cached(&path, source, &12u32, &vec![1u64, 2, 3]).unwrap();
+        assert_eq!(
+            load_cached::<u32, Vec<u64>>(&path, source, &12),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(load_cached::<u32, Vec<u64>>(&path, source, &24), None);
+        let rerecorded = SourceFingerprint {
+            len: 2048,
+            ..source
+        };
+        assert_eq!(load_cached::<u32, Vec<u64>>(&path, rerecorded, &12), None);
+    }
+}
@@ -0,0 +1,652 @@
+use crate::core::library::service::{page_bounds, project_list_item};
+use crate::core::transcript::service::load_transcript;
+use crate::domain::models::{
+    AppError, ProjectListItem, ProjectListPage, ProjectListQuery, ProjectManifest,
+    ProjectSearchResult, ProjectSortKey, ProjectStatus, TranscriptCue,
+};
+use crate::infra::ffmpeg::export::subtitle_path;
+use crate::infra::storage::project_store::load_manifest;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, TransactionBehavior};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// 索引放在项目根目录下，不是目录，列表扫描时自然跳过。
+pub const INDEX_FILE_NAME: &str = "library.sqlite3";
+const SCHEMA_VERSION: i64 = 3;
+const MAX_SEARCH_RESULTS: usize = 50;
+// 单个项目最多返回的文字稿命中段数，常见词不至于把整份字幕都带回前端。
+const MAX_TRANSCRIPT_HITS: usize = 20;
+// 导出与列表可能同时写读索引，等待而不是立即报 SQLITE_BUSY。
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn index_path(project_root: &Path) -> PathBuf {
+    project_root.join(INDEX_FILE_NAME)
+}
+
+/// 写入或覆盖一个项目的索引行。清单仍是唯一的数据源，索引只服务于列表查询。
+pub fn index_project(
+    project_root: &Path,
+    project_id: &str,
+    manifest: &ProjectManifest,
+) -> Result<(), AppError> {
+    let mut connection = open_index(project_root)?;
+    let transaction = connection.transaction().map_err(index_error)?;
+    upsert_project(&transaction, project_id, manifest)?;
+    transaction.commit().map_err(index_error)
+}
+
+pub fn unindex_project(project_root: &Path, project_id: &str) -> Result<(), AppError> {
+    let connection = open_index(project_root)?;
+    delete_project(&connection, project_id)
+}
+
+/// 单个项目写索引失败后调用：把版本号清零，下次打开索引时按磁盘上的清单整体重建，
+/// 不让列表一直停留在过期的数据上。版本号也写不进去时删除索引文件，效果相同。
+pub fn mark_index_stale(project_root: &Path) {
+    let reset = Connection::open(index_path(project_root)).and_then(|connection| {
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        connection.pragma_update(None, "user_version", 0)
+    });
+    if let Err(error) = reset {
+        tracing::warn!("failed to mark project index stale: {error}");
+        let _ = std::fs::remove_file(index_path(project_root));
+    }
+}
+
+/// 清空索引并按磁盘上的清单重建，返回收录的项目数；清单损坏的项目跳过。
+pub fn rebuild_index(project_root: &Path) -> Result<usize, AppError> {
+    let mut connection = open_index(project_root)?;
+    let transaction = connection.transaction().map_err(index_error)?;
+    let indexed = fill_from_disk(&transaction, project_root)?;
+    transaction.commit().map_err(index_error)?;
+    Ok(indexed)
+}
+
+/// 与 `query_projects` 相同的过滤、排序与分页语义，在索引上完成。
+pub fn query_index(
+    project_root: &Path,
+    query: &ProjectListQuery,
+) -> Result<ProjectListPage, AppError> {
+    let connection = open_index(project_root)?;
+    let mut clauses = Vec::new();
+    let mut values = Vec::new();
+    if let Some(status) = &query.status {
+        clauses.push("status = ?".to_string());
+        values.push(Value::Text(status_key(status)?));
+    }
+    if let Some(search) = query
+        .search
+        .as_deref()
+        .map(|text| text.trim().to_lowercase())
+        .filter(|text| !text.is_empty())
+    {
+        clauses.push("instr(search_text, ?) > 0".to_string());
+        values.push(Value::Text(search));
+    }
+    for tag in &query.tags {
+        clauses.push(
+            "EXISTS (SELECT 1 FROM project_tags t WHERE t.project_id = p.project_id AND t.tag = ?)"
+                .to_string(),
+        );
+        values.push(Value::Text(tag.trim().to_lowercase()));
+    }
+    let filter = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", clauses.join(" AND "))
+    };
+
+    let total: i64 = connection
+        .query_row(
+            &format!("SELECT COUNT(*) FROM projects p{filter}"),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )
+        .map_err(index_error)?;
+    let total = total as usize;
+    let (page, page_size) = page_bounds(query, total);
+    let order = match query.sort {
+        ProjectSortKey::UpdatedDesc => "updated_at DESC",
+        ProjectSortKey::CreatedDesc => "created_at DESC",
+        ProjectSortKey::TitleAsc => "sort_title ASC",
+        ProjectSortKey::DurationDesc => "duration_ms DESC",
+    };
+    values.push(Value::Integer(page_size as i64));
+    values.push(Value::Integer(
+        (page - 1).saturating_mul(page_size).min(i64::MAX as usize) as i64,
+    ));
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT item FROM projects p{filter} ORDER BY {order}, project_id LIMIT ? OFFSET ?"
+        ))
+        .map_err(index_error)?;
+    let items = statement
+        .query_map(params_from_iter(values.iter()), |row| {
+            row.get::<_, String>(0)
+        })
+        .map_err(index_error)?
+        .map(|row| parse_item(&row.map_err(index_error)?))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ProjectListPage {
+        items,
+        total,
+        page,
+        page_size,
+    })
+}
+
+/// 按标题、备注、项目 id 与文字稿全文搜索，空格分隔的每个词都要在项目中出现（不区分大小写），
+/// 最近修改的在前。文字稿命中段按时间排序，只要包含任一搜索词即返回。
+pub fn search_index(project_root: &Path, text: &str) -> Result<Vec<ProjectSearchResult>, AppError> {
+    let mut terms = text
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    terms.sort();
+    terms.dedup();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let connection = open_index(project_root)?;
+    let project_filter = vec![
+        "(instr(p.search_text, ?) > 0 OR EXISTS (SELECT 1 FROM transcript_cues c \
+         WHERE c.project_id = p.project_id AND instr(c.search_text, ?) > 0))";
+        terms.len()
+    ]
+    .join(" AND ");
+    let mut values = terms
+        .iter()
+        .flat_map(|term| [Value::Text(term.clone()), Value::Text(term.clone())])
+        .collect::<Vec<_>>();
+    values.push(Value::Integer(MAX_SEARCH_RESULTS as i64));
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT project_id, item FROM projects p WHERE {project_filter} \
+             ORDER BY updated_at DESC, project_id LIMIT ?"
+        ))
+        .map_err(index_error)?;
+    let projects = statement
+        .query_map(params_from_iter(values.iter()), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(index_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(index_error)?;
+
+    let cue_filter = vec!["instr(search_text, ?) > 0"; terms.len()].join(" OR ");
+    let mut cue_statement = connection
+        .prepare(&format!(
+            "SELECT start_ms, end_ms, text FROM transcript_cues \
+             WHERE project_id = ? AND ({cue_filter}) ORDER BY start_ms LIMIT ?"
+        ))
+        .map_err(index_error)?;
+    projects
+        .into_iter()
+        .map(|(project_id, json)| {
+            let mut values = vec![Value::Text(project_id)];
+            values.extend(terms.iter().cloned().map(Value::Text));
+            values.push(Value::Integer(MAX_TRANSCRIPT_HITS as i64));
+            let transcript_hits = cue_statement
+                .query_map(params_from_iter(values.iter()), |row| {
+                    Ok(TranscriptCue {
+                        start_ms: row.get::<_, i64>(0)?.max(0) as u64,
+                        end_ms: row.get::<_, i64>(1)?.max(0) as u64,
+                        text: row.get(2)?,
+                    })
+                })
+                .map_err(index_error)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(index_error)?;
+            Ok(ProjectSearchResult {
+                item: parse_item(&json)?,
+                transcript_hits,
+            })
+        })
+        .collect()
+}
+
+fn parse_item(json: &str) -> Result<ProjectListItem, AppError> {
+    serde_json::from_str(json).map_err(|error| {
+        AppError::new(
+            "PROJECT_INDEX_ERROR",
+            format!("corrupted index row: {error}"),
+            None,
+        )
+    })
+}
+
+/// 打开索引；新建或版本不符时建表并从磁盘重建，旧版本的表直接丢弃。
+/// 建表在写事务内完成，多个窗口同时打开时只有一个会执行重建。
+fn open_index(project_root: &Path) -> Result<Connection, AppError> {
+    let mut connection = Connection::open(index_path(project_root)).map_err(index_error)?;
+    connection.busy_timeout(BUSY_TIMEOUT).map_err(index_error)?;
+    if schema_version(&connection)? != SCHEMA_VERSION {
+        let transaction = connection
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(index_error)?;
+        if schema_version(&transaction)? != SCHEMA_VERSION {
+            transaction
+                .execute_batch(
+                    "DROP TABLE IF EXISTS transcript_sources;
+                     DROP TABLE IF EXISTS transcript_cues;
+                     DROP TABLE IF EXISTS project_tags;
+                     DROP TABLE IF EXISTS projects;
+                     CREATE TABLE projects (
+                         project_id TEXT PRIMARY KEY,
+                         status TEXT NOT NULL,
+                         search_text TEXT NOT NULL,
+                         sort_title TEXT NOT NULL,
+                         created_at INTEGER NOT NULL,
+                         updated_at INTEGER NOT NULL,
+                         duration_ms INTEGER NOT NULL,
+                         item TEXT NOT NULL
+                     );
+                     CREATE TABLE project_tags (
+                         project_id TEXT NOT NULL,
+                         tag TEXT NOT NULL,
+                         PRIMARY KEY (project_id, tag)
+                     );
+                     CREATE INDEX project_tags_tag ON project_tags (tag);
+                     CREATE TABLE transcript_cues (
+                         project_id TEXT NOT NULL,
+                         start_ms INTEGER NOT NULL,
+                         end_ms INTEGER NOT NULL,
+                         text TEXT NOT NULL,
+                         search_text TEXT NOT NULL
+                     );
+                     CREATE INDEX transcript_cues_project ON transcript_cues (project_id);
+                     CREATE TABLE transcript_sources (
+                         project_id TEXT PRIMARY KEY,
+                         path TEXT NOT NULL,
+                         modified_at INTEGER
+                     );",
+                )
+                .map_err(index_error)?;
+            fill_from_disk(&transaction, project_root)?;
+            transaction
+                .pragma_update(None, "user_version", SCHEMA_VERSION)
+                .map_err(index_error)?;
+        }
+        transaction.commit().map_err(index_error)?;
+    }
+    Ok(connection)
+}
+
+fn schema_version(connection: &Connection) -> Result<i64, AppError> {
+    connection
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(index_error)
+}
+
+fn fill_from_disk(connection: &Connection, project_root: &Path) -> Result<usize, AppError> {
+    connection
+        .execute_batch(
+            "DELETE FROM transcript_sources; DELETE FROM transcript_cues;
+             DELETE FROM project_tags; DELETE FROM projects;",
+        )
+        .map_err(index_error)?;
+    let mut indexed = 0;
+    if let Ok(entries) = std::fs::read_dir(project_root) {
+        for entry in entries.flatten() {
+            if !entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                continue;
+            }
+            let project_id = entry.file_name().to_string_lossy().to_string();
+            let Ok(manifest) = load_manifest(project_root, &project_id) else {
+                continue;
+            };
+            upsert_project(connection, &project_id, &manifest)?;
+            indexed += 1;
+        }
+    }
+    Ok(indexed)
+}
+
+fn upsert_project(
+    connection: &Connection,
+    project_id: &str,
+    manifest: &ProjectManifest,
+) -> Result<(), AppError> {
+    let item = project_list_item(project_id, manifest);
+    let search_text = [
+        Some(project_id),
+        item.title.as_deref(),
+        item.notes.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(str::to_lowercase)
+    .collect::<Vec<_>>()
+    .join("\n");
+    let sort_title = item.title.as_deref().unwrap_or(project_id).to_lowercase();
+    let json = serde_json::to_string(&item)
+        .map_err(|error| AppError::new("SERDE_ERROR", error.to_string(), None))?;
+    for table in ["project_tags", "projects"] {
+        connection
+            .execute(
+                &format!("DELETE FROM {table} WHERE project_id = ?1"),
+                [project_id],
+            )
+            .map_err(index_error)?;
+    }
+    connection
+        .execute(
+            "INSERT INTO projects
+                 (project_id, status, search_text, sort_title, created_at, updated_at, duration_ms, item)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                project_id,
+                status_key(&item.status)?,
+                search_text,
+                sort_title,
+                item.created_at.timestamp_millis(),
+                item.updated_at.timestamp_millis(),
+                item.duration_ms.min(i64::MAX as u64) as i64,
+                json,
+            ],
+        )
+        .map_err(index_error)?;
+    for tag in &item.tags {
+        connection
+            .execute(
+                "INSERT OR IGNORE INTO project_tags (project_id, tag) VALUES (?1, ?2)",
+                params![project_id, tag.to_lowercase()],
+            )
+            .map_err(index_error)?;
+    }
+    // 文字稿来自挂载的字幕文件，路径与修改时间都没变时沿用已索引的字幕段，
+    // 避免每次保存清单都重新解析整份字幕。
+    let source = transcript_source(manifest);
+    let indexed_source = connection
+        .query_row(
+            "SELECT path, modified_at FROM transcript_sources WHERE project_id = ?1",
+            [project_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?)),
+        )
+        .optional()
+        .map_err(index_error)?;
+    if source == indexed_source {
+        return Ok(());
+    }
+    for table in ["transcript_cues", "transcript_sources"] {
+        connection
+            .execute(
+                &format!("DELETE FROM {table} WHERE project_id = ?1"),
+                [project_id],
+            )
+            .map_err(index_error)?;
+    }
+    let Some((path, modified_at)) = source else {
+        return Ok(());
+    };
+    for cue in load_transcript(&manifest.export) {
+        connection
+            .execute(
+                "INSERT INTO transcript_cues (project_id, start_ms, end_ms, text, search_text)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    project_id,
+                    cue.start_ms.min(i64::MAX as u64) as i64,
+                    cue.end_ms.min(i64::MAX as u64) as i64,
+                    cue.text,
+                    cue.text.to_lowercase(),
+                ],
+            )
+            .map_err(index_error)?;
+    }
+    connection
+        .execute(
+            "INSERT INTO transcript_sources (project_id, path, modified_at) VALUES (?1, ?2, ?3)",
+            params![project_id, path, modified_at],
+        )
+        .map_err(index_error)?;
+    Ok(())
+}
+
+/// 挂载字幕的路径与修改时间（毫秒），文件不存在时修改时间为空。
+fn transcript_source(manifest: &ProjectManifest) -> Option<(String, Option<i64>)> {
+    let path = subtitle_path(&manifest.export)?;
+    let modified_at = std::fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis().min(i64::MAX as u128) as i64);
+    Some((path.to_string_lossy().to_string(), modified_at))
+}
+
+fn delete_project(connection: &Connection, project_id: &str) -> Result<(), AppError> {
+    for table in [
+        "transcript_sources",
+        "transcript_cues",
+        "project_tags",
+        "projects",
+    ] {
+        connection
+            .execute(
+                &format!("DELETE FROM {table} WHERE project_id = ?1"),
+                [project_id],
+            )
+            .map_err(index_error)?;
+    }
+    Ok(())
+}
+
+/// 状态按清单中的序列化形式存储，与 serde 的重命名规则保持一致。
+fn status_key(status: &ProjectStatus) -> Result<String, AppError> {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .ok_or_else(|| AppError::new("SERDE_ERROR", "failed to serialize project status", None))
+}
+
+fn index_error(error: rusqlite::Error) -> AppError {
+    AppError::new(
+        "PROJECT_INDEX_ERROR",
+        format!("project index error: {error}"),
+        Some("请重建项目索引后重试".to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        index_path, index_project, mark_index_stale, query_index, rebuild_index, search_index,
+        unindex_project,
+    };
+    use crate::domain::models::{ProjectListQuery, ProjectManifest, ProjectSortKey, ProjectStatus};
+    use crate::infra::storage::project_store::save_manifest;
+    use tempfile::tempdir;
+
+    fn manifest(
+        title: &str,
+        tags: &[&str],
+        status: ProjectStatus,
+        duration_ms: u64,
+    ) -> ProjectManifest {
+        let mut manifest = ProjectManifest {
+            title: Some(title.to_string()),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            status,
+            ..ProjectManifest::default()
+        };
+        manifest.timeline.trim_start_ms = 0;
+        manifest.timeline.trim_end_ms = duration_ms;
+        manifest
+    }
+
+    fn ids(root: &std::path::Path, query: &ProjectListQuery) -> Vec<String> {
+        query_index(root, query)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item.project_id)
+            .collect()
+    }
+
+    #[test]
+    fn index_filters_sorts_and_pages_like_the_manifest_scan() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        save_manifest(
+            root,
+            "a",
+            &manifest("Beta demo", &["Work"], ProjectStatus::ReadyToEdit, 3_000),
+        )
+        .unwrap();
+        save_manifest(
+            root,
+            "b",
+            &manifest(
+                "alpha",
+                &["work", "draft"],
+                ProjectStatus::ExportSucceeded,
+                9_000,
+            ),
+        )
+        .unwrap();
+        save_manifest(
+            root,
+            "c",
+            &manifest("Gamma Demo", &[], ProjectStatus::ReadyToEdit, 1_000),
+        )
+        .unwrap();
+
+        let by_title = ProjectListQuery {
+            sort: ProjectSortKey::TitleAsc,
+            ..ProjectListQuery::default()
+        };
+        assert_eq!(ids(root, &by_title), ["b", "a", "c"]);
+        let search = ProjectListQuery {
+            search: Some(" DEMO ".to_string()),
+            status: Some(ProjectStatus::ReadyToEdit),
+            sort: ProjectSortKey::DurationDesc,
+            ..ProjectListQuery::default()
+        };
+        assert_eq!(ids(root, &search), ["a", "c"]);
+        let tagged = ProjectListQuery {
+            tags: vec!["WORK".to_string(), "draft".to_string()],
+            ..ProjectListQuery::default()
+        };
+        assert_eq!(ids(root, &tagged), ["b"]);
+        let paged = query_index(
+            root,
+            &ProjectListQuery {
+                page: Some(2),
+                page_size: Some(2),
+                sort: ProjectSortKey::TitleAsc,
+                ..ProjectListQuery::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(paged.total, 3);
+        assert_eq!(paged.items.len(), 1);
+        assert_eq!(paged.items[0].project_id, "c");
+
+        unindex_project(root, "b").unwrap();
+        assert_eq!(ids(root, &tagged), Vec::<String>::new());
+        index_project(
+            root,
+            "b",
+            &manifest("alpha", &["draft"], ProjectStatus::ReadyToEdit, 0),
+        )
+        .unwrap();
+        assert_eq!(ids(root, &tagged), Vec::<String>::new());
+    }
+
+    #[test]
+    fn missing_index_is_rebuilt_from_manifests() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        save_manifest(root, "a", &ProjectManifest::default()).unwrap();
+        save_manifest(root, "b", &ProjectManifest::default()).unwrap();
+        std::fs::create_dir_all(root.join(".trash")).unwrap();
+        std::fs::remove_file(index_path(root)).unwrap();
+
+        assert_eq!(
+            query_index(root, &ProjectListQuery::default())
+                .unwrap()
+                .total,
+            2
+        );
+        std::fs::remove_dir_all(root.join("b")).unwrap();
+        assert_eq!(rebuild_index(root).unwrap(), 1);
+        assert_eq!(ids(root, &ProjectListQuery::default()), ["a"]);
+    }
+
+    #[test]
+    fn stale_index_is_rebuilt_on_next_query() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        save_manifest(root, "a", &ProjectManifest::default()).unwrap();
+        save_manifest(root, "b", &ProjectManifest::default()).unwrap();
+        unindex_project(root, "b").unwrap();
+        assert_eq!(ids(root, &ProjectListQuery::default()), ["a"]);
+
+        mark_index_stale(root);
+        assert_eq!(
+            query_index(root, &ProjectListQuery::default())
+                .unwrap()
+                .total,
+            2
+        );
+    }
+
+    #[test]
+    fn search_matches_titles_and_transcript_cues() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let subtitles = root.join("demo.srt");
+        std::fs::write(
+            &subtitles,
+            "1\n00:00:01,000 --> 00:00:02,000\nWelcome\n\n2\n00:01:10,000 --> 00:01:12,500\nNow the Billing page\n",
+        )
+        .unwrap();
+        let mut demo = manifest("Release demo", &[], ProjectStatus::ReadyToEdit, 0);
+        demo.export.subtitle_path = Some(subtitles.to_string_lossy().to_string());
+        save_manifest(root, "demo", &demo).unwrap();
+        let demo_manifest = demo;
+        save_manifest(
+            root,
+            "notes",
+            &ProjectManifest {
+                notes: Some("billing page mockups".to_string()),
+                ..ProjectManifest::default()
+            },
+        )
+        .unwrap();
+
+        let results = search_index(root, "billing PAGE").unwrap();
+        assert_eq!(results.len(), 2);
+        let demo = results
+            .iter()
+            .find(|result| result.item.project_id == "demo")
+            .unwrap();
+        assert_eq!(demo.transcript_hits.len(), 1);
+        assert_eq!(demo.transcript_hits[0].start_ms, 70_000);
+        assert_eq!(demo.transcript_hits[0].text, "Now the Billing page");
+        assert_eq!(search_index(root, "release welcome").unwrap().len(), 1);
+        assert!(search_index(root, "billing invoices").unwrap().is_empty());
+        assert!(search_index(root, "   ").unwrap().is_empty());
+
+        // 修改时间不变时沿用已索引的字幕段，变化后重新解析。
+        let modified = std::fs::metadata(&subtitles).unwrap().modified().unwrap();
+        std::fs::write(
+            &subtitles,
+            "1\n00:00:01,000 --> 00:00:02,000\nInvoices overview\n",
+        )
+        .unwrap();
+        let file = std::fs::File::options()
+            .write(true)
+            .open(&subtitles)
+            .unwrap();
+        file.set_modified(modified).unwrap();
+        save_manifest(root, "demo", &demo_manifest).unwrap();
+        assert!(search_index(root, "invoices").unwrap().is_empty());
+        file.set_modified(modified + std::time::Duration::from_secs(5))
+            .unwrap();
+        save_manifest(root, "demo", &demo_manifest).unwrap();
+        assert_eq!(search_index(root, "invoices").unwrap().len(), 1);
+        assert!(search_index(root, "welcome").unwrap().is_empty());
+    }
+}
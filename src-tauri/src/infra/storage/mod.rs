@@ -1 +1,6 @@
+pub mod artifact_cache;
+pub mod index;
+pub mod project_archive;
 pub mod project_store;
+pub mod settings_store;
+pub mod trash;
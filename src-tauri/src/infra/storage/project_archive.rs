@@ -0,0 +1,320 @@
+use crate::domain::models::AppError;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub const ARCHIVE_EXTENSION: &str = "focuslens";
+pub const BUNDLE_INFO_NAME: &str = "bundle.json";
+const BUNDLE_FORMAT_VERSION: u8 = 1;
+const COPY_CHUNK_BYTES: usize = 1 << 20;
+
+/// 归档包描述：记录来源项目，导入时据此识别并改写清单中的绝对路径。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleInfo {
+    pub format_version: u8,
+    pub project_id: String,
+    pub include_renders: bool,
+}
+
+/// 把项目目录打包为 `.focuslens`（zip）：project.json、assets，可选 renders。
+/// 视频与图片已是压缩格式，直接存储；清单与轨迹 JSON 使用 deflate。
+/// 缩略图与波形缓存可由导入方重建，不打包。`on_progress` 参数为已写入/总字节数。
+pub fn write_project_archive<F>(
+    project_dir: &Path,
+    project_id: &str,
+    dest: &Path,
+    include_renders: bool,
+    mut on_progress: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(u64, u64),
+{
+    let mut entries = vec![(project_dir.join("project.json"), "project.json".to_string())];
+    collect_files(&project_dir.join("assets"), "assets", &mut entries)?;
+    if include_renders {
+        collect_files(&project_dir.join("renders"), "renders", &mut entries)?;
+    }
+    let total = entries
+        .iter()
+        .filter_map(|(path, _)| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum::<u64>();
+
+    let file = File::create(dest).map_err(|error| archive_io_error("create archive", error))?;
+    let mut writer = ZipWriter::new(file);
+    let info = BundleInfo {
+        format_version: BUNDLE_FORMAT_VERSION,
+        project_id: project_id.to_string(),
+        include_renders,
+    };
+    writer
+        .start_file(BUNDLE_INFO_NAME, options_for(Path::new(BUNDLE_INFO_NAME)))
+        .map_err(archive_zip_error)?;
+    let info = serde_json::to_vec_pretty(&info).map_err(|error| {
+        AppError::new(
+            "SERDE_ERROR",
+            format!("failed to serialize bundle info: {error}"),
+            None,
+        )
+    })?;
+    writer
+        .write_all(&info)
+        .map_err(|error| archive_io_error("write archive", error))?;
+
+    let mut done = 0u64;
+    let mut buffer = vec![0u8; COPY_CHUNK_BYTES];
+    for (path, name) in &entries {
+        writer
+            .start_file(name.as_str(), options_for(path))
+            .map_err(archive_zip_error)?;
+        let mut source = File::open(path).map_err(|error| archive_io_error("read asset", error))?;
+        loop {
+            let read = source
+                .read(&mut buffer)
+                .map_err(|error| archive_io_error("read asset", error))?;
+            if read == 0 {
+                break;
+            }
+            writer
+                .write_all(&buffer[..read])
+                .map_err(|error| archive_io_error("write archive", error))?;
+            done += read as u64;
+            on_progress(done, total);
+        }
+    }
+    writer.finish().map_err(archive_zip_error)?;
+    Ok(())
+}
+
+/// 解压 `.focuslens` 到 `target_dir`，只接受 project.json、assets/ 与 renders/ 下的条目。
+/// 条目名经过 `enclosed_name` 校验，拒绝 `..` 等越界路径。
+pub fn extract_project_archive(
+    archive_path: &Path,
+    target_dir: &Path,
+) -> Result<BundleInfo, AppError> {
+    let file = File::open(archive_path).map_err(|error| {
+        AppError::new(
+            "ARCHIVE_NOT_FOUND",
+            format!("failed to open archive: {error}"),
+            Some("确认归档文件路径是否正确".to_string()),
+        )
+    })?;
+    let mut archive = ZipArchive::new(file).map_err(invalid_archive)?;
+    let info: BundleInfo = {
+        let entry = archive.by_name(BUNDLE_INFO_NAME).map_err(invalid_archive)?;
+        serde_json::from_reader(entry)
+            .map_err(|error| invalid_archive(std::io::Error::from(error).into()))?
+    };
+    if info.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(AppError::new(
+            "UNSUPPORTED_ARCHIVE",
+            format!(
+                "archive format {} is newer than supported {}",
+                info.format_version, BUNDLE_FORMAT_VERSION
+            ),
+            Some("请升级应用后重试".to_string()),
+        ));
+    }
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(invalid_archive)?;
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+        let accepted = relative == Path::new("project.json")
+            || relative.starts_with("assets")
+            || relative.starts_with("renders");
+        if !accepted || entry.is_dir() {
+            continue;
+        }
+        let output = target_dir.join(relative);
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|error| archive_io_error("create project dir", error))?;
+        }
+        let mut target =
+            File::create(&output).map_err(|error| archive_io_error("write asset", error))?;
+        std::io::copy(&mut entry, &mut target)
+            .map_err(|error| archive_io_error("write asset", error))?;
+    }
+    if !target_dir.join("project.json").exists() {
+        return Err(AppError::new(
+            "ARCHIVE_INVALID",
+            "archive does not contain project.json",
+            Some("请选择由 FocusLens 打包的 .focuslens 文件".to_string()),
+        ));
+    }
+    Ok(info)
+}
+
+/// 把清单中来自其他机器的绝对路径改写到 `target_dir` 下：
+/// 取 `/assets/` 或 `/renders/` 之后的部分（兼容 Windows 反斜杠），文件不存在时返回 None。
+/// 清单来自外部归档，含 `..`、根路径等非普通段或拼接后越出 `target_dir` 时同样返回 None。
+pub fn rebase_artifact_path(path: &str, target_dir: &Path) -> Option<String> {
+    let normalized = path.replace('\\', "/");
+    let start = ["/assets/", "/renders/"]
+        .iter()
+        .filter_map(|marker| normalized.rfind(marker))
+        .max()?;
+    let relative = Path::new(&normalized[start + 1..]);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    let rebased = target_dir.join(relative);
+    (rebased.starts_with(target_dir) && rebased.exists())
+        .then(|| rebased.to_string_lossy().to_string())
+}
+
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    entries: &mut Vec<(PathBuf, String)>,
+) -> Result<(), AppError> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    let items = std::fs::read_dir(dir).map_err(|error| archive_io_error("read dir", error))?;
+    for item in items.flatten() {
+        let path = item.path();
+        let name = format!("{prefix}/{}", item.file_name().to_string_lossy());
+        if name == "assets/thumbs" || name == "assets/waveform.json" {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, &name, entries)?;
+        } else if path.is_file() {
+            entries.push((path, name));
+        }
+    }
+    Ok(())
+}
+
+fn options_for(path: &Path) -> SimpleFileOptions {
+    let compressed = matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("mp4" | "mov" | "mkv" | "png" | "jpg" | "jpeg")
+    );
+    SimpleFileOptions::default()
+        .compression_method(if compressed {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        })
+        .large_file(true)
+}
+
+fn archive_io_error(action: &str, error: std::io::Error) -> AppError {
+    AppError::new(
+        "ARCHIVE_IO_ERROR",
+        format!("failed to {action}: {error}"),
+        Some("确认磁盘空间和路径权限".to_string()),
+    )
+}
+
+fn invalid_archive(error: zip::result::ZipError) -> AppError {
+    AppError::new(
+        "ARCHIVE_INVALID",
+        format!("failed to read archive: {error}"),
+        Some("请选择由 FocusLens 打包的 .focuslens 文件".to_string()),
+    )
+}
+
+fn archive_zip_error(error: zip::result::ZipError) -> AppError {
+    AppError::new(
+        "ARCHIVE_IO_ERROR",
+        format!("failed to write archive: {error}"),
+        Some("确认磁盘空间和路径权限".to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        extract_project_archive, rebase_artifact_path, write_project_archive, BundleInfo,
+        BUNDLE_INFO_NAME,
+    };
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    fn archive_contains_manifest_and_assets_without_caches() {
+        let temp = tempdir().unwrap();
+        let project = temp.path().join("p1");
+        std::fs::create_dir_all(project.join("assets").join("thumbs")).unwrap();
+        std::fs::create_dir_all(project.join("renders")).unwrap();
+        std::fs::write(project.join("project.json"), b"{}").unwrap();
+        std::fs::write(project.join("assets").join("recording_raw.mp4"), b"video").unwrap();
+        std::fs::write(project.join("assets").join("waveform.json"), b"[]").unwrap();
+        std::fs::write(project.join("renders").join("export.mp4"), b"out").unwrap();
+
+        let dest = temp.path().join("p1.focuslens");
+        let mut last = (0, 0);
+        write_project_archive(&project, "p1", &dest, false, |done, total| {
+            last = (done, total)
+        })
+        .unwrap();
+        assert_eq!(last, (7, 7));
+
+        let mut archive = zip::ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+        let mut names = archive.file_names().map(str::to_string).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["assets/recording_raw.mp4", BUNDLE_INFO_NAME, "project.json"]
+        );
+        let info: BundleInfo =
+            serde_json::from_reader(archive.by_name(BUNDLE_INFO_NAME).unwrap()).unwrap();
+        assert_eq!(info.project_id, "p1");
+        assert!(!info.include_renders);
+    }
+
+    #[test]
+    fn extracted_archive_rebases_foreign_artifact_paths() {
+        let temp = tempdir().unwrap();
+        let project = temp.path().join("p1");
+        std::fs::create_dir_all(project.join("assets")).unwrap();
+        std::fs::write(project.join("project.json"), b"{}").unwrap();
+        std::fs::write(project.join("assets").join("cursor_track.json"), b"[]").unwrap();
+        let dest = temp.path().join("p1.focuslens");
+        write_project_archive(&project, "p1", &dest, true, |_, _| {}).unwrap();
+
+        let imported = temp.path().join("p2");
+        let info = extract_project_archive(&dest, &imported).unwrap();
+        assert!(info.include_renders);
+        assert!(imported.join("project.json").exists());
+        assert_eq!(
+            rebase_artifact_path(
+                r"C:\Users\a\FocusLens\p1\assets\cursor_track.json",
+                &imported
+            ),
+            Some(
+                imported
+                    .join("assets")
+                    .join("cursor_track.json")
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            rebase_artifact_path("/home/a/p1/renders/output.mp4", &imported),
+            None
+        );
+        assert_eq!(
+            rebase_artifact_path(
+                &format!(
+                    "/x/renders/../../{}/project.json",
+                    imported.file_name().unwrap().to_string_lossy()
+                ),
+                &imported
+            ),
+            None
+        );
+    }
+}
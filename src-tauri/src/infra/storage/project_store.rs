@@ -1,12 +1,17 @@
 use crate::domain::models::{
-    AppError, CameraMotionProfile, ExportProfile, ProjectArtifacts, ProjectManifest, ProjectStatus,
-    QualityMetrics, RecordingProfile, TimelineConfig,
+    AppError, AudioMix, CameraMotionProfile, ColorGrade, CursorTrackPoint, EditHistory,
+    ExportProfile, KeystrokeOverlay, ProjectArtifacts, ProjectManifest, ProjectStatus,
+    QualityMetrics, RecordingProfile, SceneStyle, TimelineConfig,
 };
+use crate::infra::storage::index::{index_project, mark_index_stale};
 use chrono::Utc;
 use serde_json::{json, Value};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 pub const CURRENT_SCHEMA_VERSION: u8 = 1;
+// 清单修订只保留最近若干份，避免频繁保存撑大项目目录。
+const MAX_MANIFEST_REVISIONS: usize = 30;
 
 pub fn project_dir(project_root: &Path, project_id: &str) -> PathBuf {
     project_root.join(project_id)
@@ -41,16 +46,26 @@ pub fn create_project_manifest(recording: RecordingProfile) -> ProjectManifest {
         schema_version: CURRENT_SCHEMA_VERSION,
         app_version: "0.1.0".to_string(),
         title: None,
+        tags: Vec::new(),
+        notes: None,
         created_at: now,
         updated_at: now,
+        recording_started_at: None,
         recording,
         camera_motion: CameraMotionProfile::default(),
         export: ExportProfile::default(),
         timeline: TimelineConfig::default(),
+        scene: SceneStyle::default(),
+        keystroke_overlay: KeystrokeOverlay::default(),
+        audio_mix: AudioMix::default(),
+        color_grade: ColorGrade::default(),
         artifacts: ProjectArtifacts::default(),
         quality: QualityMetrics::default(),
         status: ProjectStatus::ReadyToEdit,
         last_error: None,
+        uploads: Vec::new(),
+        blur_candidates: Vec::new(),
+        export_history: Vec::new(),
     }
 }
 
@@ -60,24 +75,178 @@ pub fn raw_recording_path(project_root: &Path, project_id: &str) -> PathBuf {
         .join("recording_raw.mp4")
 }
 
+/// 录制过程中写入的 MKV：进程被强制结束时仍可读取，停止后重封装为 recording_raw.mp4。
+pub fn recording_capture_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join("recording_raw.mkv")
+}
+
+/// 录制中途切换输入（如麦克风被拔出）后续录的分段，停止时拼回 recording_raw.mkv。
+pub fn recording_capture_part_path(project_root: &Path, project_id: &str, index: usize) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join(format!("recording_raw.part{index}.mkv"))
+}
+
+/// 修复前的中断录制原样保留，修复结果不理想时可手工找回。
+pub fn partial_recording_backup_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join("recording_raw.partial.mp4")
+}
+
 pub fn cursor_track_path(project_root: &Path, project_id: &str) -> PathBuf {
     project_dir(project_root, project_id)
         .join("assets")
         .join("cursor_track.json")
 }
 
+pub fn refined_cursor_track_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join("cursor_track_refined.json")
+}
+
+pub fn cursor_track_revision_path(project_root: &Path, project_id: &str, revision: u32) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join(format!("cursor_track_r{revision}.json"))
+}
+
+pub fn load_cursor_track_points(path: &Path) -> Result<Vec<CursorTrackPoint>, AppError> {
+    let raw = std::fs::read_to_string(path).map_err(|error| {
+        AppError::new(
+            "CURSOR_TRACK_READ_FAIL",
+            format!("failed to read cursor track: {error}"),
+            None,
+        )
+    })?;
+    serde_json::from_str(&raw).map_err(|error| {
+        AppError::new(
+            "CURSOR_TRACK_PARSE_FAIL",
+            format!("failed to parse cursor track: {error}"),
+            None,
+        )
+    })
+}
+
+pub fn write_cursor_track_points(path: &Path, points: &[CursorTrackPoint]) -> Result<(), AppError> {
+    let content = serde_json::to_string_pretty(points).map_err(|error| {
+        AppError::new(
+            "SERDE_ERROR",
+            format!("failed to serialize cursor track: {error}"),
+            None,
+        )
+    })?;
+    std::fs::write(path, content).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to write cursor track: {error}"),
+            Some("确认磁盘空间和路径权限".to_string()),
+        )
+    })
+}
+
+pub fn key_track_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join("key_track.json")
+}
+
+pub fn app_track_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join("app_track.json")
+}
+
 pub fn export_output_path(project_root: &Path, project_id: &str) -> PathBuf {
     project_dir(project_root, project_id)
         .join("renders")
         .join("output.mp4")
 }
 
+/// 主版本先编码到这里，成功后再改名为 output.mp4，取消或失败不会破坏上一次的成片。
+pub fn export_partial_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("renders")
+        .join("output.partial.mp4")
+}
+
+/// 需要拼接片头片尾时，正片先编码到这里再与素材合成主版本成片。
+pub fn export_main_segment_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("renders")
+        .join("output_main.mp4")
+}
+
+/// 多版本导出的附加成片，`label` 描述分辨率与画幅，如 `720p_9x16`。
+pub fn export_variant_output_path(
+    project_root: &Path,
+    project_id: &str,
+    index: usize,
+    label: &str,
+) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("renders")
+        .join(format!("output_{index}_{label}.mp4"))
+}
+
+/// 时间线缩略图目录，内含 `index.json` 缓存索引。
+pub fn timeline_thumbnails_dir(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join("thumbs")
+}
+
+pub fn audio_waveform_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("assets")
+        .join("waveform.json")
+}
+
+pub fn frame_still_path(project_root: &Path, project_id: &str, t_ms: u64) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("renders")
+        .join("stills")
+        .join(format!("frame_{t_ms}.png"))
+}
+
+/// 敏感内容扫描的临时抽帧目录，扫描结束后删除。
+pub fn sensitive_scan_dir(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("renders")
+        .join("scan")
+}
+
+/// 封面固定写入 renders/poster.jpg，重复导出时覆盖。
+pub fn poster_output_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("renders")
+        .join("poster.jpg")
+}
+
+/// 动图导出固定写入 renders/loop.<ext>，重复导出时覆盖。
+pub fn animation_output_path(project_root: &Path, project_id: &str, extension: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("renders")
+        .join(format!("loop.{extension}"))
+}
+
+pub fn motion_preview_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id)
+        .join("renders")
+        .join("motion_preview.mp4")
+}
+
 pub fn export_log_path(project_root: &Path, project_id: &str, task_id: &str) -> PathBuf {
     project_dir(project_root, project_id)
         .join("renders")
         .join(format!("export-{task_id}.log"))
 }
 
+/// 原子写入清单：先写临时文件并落盘，再替换 project.json。
+/// 替换前把当前可解析的清单保留为 project.json.bak，供损坏时回退。
 pub fn save_manifest(
     project_root: &Path,
     project_id: &str,
@@ -92,26 +261,137 @@ pub fn save_manifest(
             None,
         )
     })?;
-    std::fs::write(path, content).map_err(|error| {
+    let write_error = |error: std::io::Error| {
         AppError::new(
             "IO_ERROR",
             format!("failed to write manifest: {error}"),
             Some("确认磁盘空间和路径权限".to_string()),
         )
-    })?;
+    };
+    let temp_path = path.with_extension("json.tmp");
+    {
+        let mut file = std::fs::File::create(&temp_path).map_err(write_error)?;
+        file.write_all(content.as_bytes()).map_err(write_error)?;
+        file.sync_all().map_err(write_error)?;
+    }
+    let current_is_valid = std::fs::read_to_string(&path)
+        .ok()
+        .is_some_and(|raw| serde_json::from_str::<Value>(&raw).is_ok());
+    if current_is_valid {
+        std::fs::copy(&path, manifest_backup_path(&path)).map_err(write_error)?;
+    }
+    std::fs::rename(&temp_path, &path).map_err(write_error)?;
+    // 索引写失败不影响保存，标记索引过期，下次查询时从清单重建。
+    if let Err(error) = index_project(project_root, project_id, manifest) {
+        tracing::warn!("failed to update project index: {}", error.message);
+        mark_index_stale(project_root);
+    }
+    Ok(())
+}
+
+pub fn manifest_revisions_dir(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id).join("history")
+}
+
+/// 修订 id 形如 `20261016T101500123Z`，对应 `history/project-<id>.json`。
+pub fn manifest_revision_path(
+    project_root: &Path,
+    project_id: &str,
+    revision_id: &str,
+) -> Result<PathBuf, AppError> {
+    if revision_id.is_empty() || !revision_id.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+        return Err(AppError::new(
+            "INVALID_REVISION_ID",
+            format!("invalid manifest revision id: {revision_id}"),
+            Some("请使用修订列表中的记录".to_string()),
+        ));
+    }
+    Ok(
+        manifest_revisions_dir(project_root, project_id)
+            .join(format!("project-{revision_id}.json")),
+    )
+}
+
+/// 列出清单修订 id，最新的在前。
+pub fn list_manifest_revision_ids(project_root: &Path, project_id: &str) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(manifest_revisions_dir(project_root, project_id)) else {
+        return Vec::new();
+    };
+    let mut ids = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            Some(
+                name.strip_prefix("project-")?
+                    .strip_suffix(".json")?
+                    .to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids
+}
+
+/// 把当前的 project.json 存为一份修订，只保留最近 `MAX_MANIFEST_REVISIONS` 份。
+/// 只在用户编辑后调用，状态、上传进度等自动保存不产生修订。
+pub fn write_manifest_revision(project_root: &Path, project_id: &str) -> Result<(), AppError> {
+    let dir = manifest_revisions_dir(project_root, project_id);
+    let io_error = |error: std::io::Error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to write manifest revision: {error}"),
+            None,
+        )
+    };
+    let content =
+        std::fs::read_to_string(manifest_path(project_root, project_id)).map_err(io_error)?;
+    std::fs::create_dir_all(&dir).map_err(io_error)?;
+    let revision_id = Utc::now().format("%Y%m%dT%H%M%S%3fZ").to_string();
+    std::fs::write(
+        manifest_revision_path(project_root, project_id, &revision_id)?,
+        content,
+    )
+    .map_err(io_error)?;
+    for stale in list_manifest_revision_ids(project_root, project_id)
+        .iter()
+        .skip(MAX_MANIFEST_REVISIONS)
+    {
+        let _ = std::fs::remove_file(manifest_revision_path(project_root, project_id, stale)?);
+    }
     Ok(())
 }
 
+fn manifest_backup_path(path: &Path) -> PathBuf {
+    path.with_extension("json.bak")
+}
+
+/// 读取清单；project.json 缺失或无法解析时回退到 project.json.bak 并修复主文件。
+/// 版本过新等语义错误不回退。
 pub fn load_manifest(project_root: &Path, project_id: &str) -> Result<ProjectManifest, AppError> {
     let path = manifest_path(project_root, project_id);
-    if !path.exists() {
+    let backup = manifest_backup_path(&path);
+    if !path.exists() && !backup.exists() {
         return Err(AppError::new(
             "PROJECT_NOT_FOUND",
             format!("project manifest not found: {project_id}"),
             Some("先完成一次录制生成项目".to_string()),
-        ));
+        )
+        .with_param("projectId", project_id));
+    }
+    match load_manifest_from_file(&path) {
+        Err(error)
+            if matches!(error.code.as_str(), "IO_ERROR" | "SERDE_ERROR") && backup.exists() =>
+        {
+            let manifest = load_manifest_from_file(&backup).map_err(|_| error.clone())?;
+            tracing::warn!(
+                "project {project_id} manifest unreadable ({}), restored from backup",
+                error.message
+            );
+            let _ = std::fs::copy(&backup, &path);
+            Ok(manifest)
+        }
+        result => result,
     }
-    load_manifest_from_file(&path)
 }
 
 pub fn load_manifest_from_file(path: &Path) -> Result<ProjectManifest, AppError> {
@@ -160,6 +440,81 @@ pub fn load_manifest_from_file(path: &Path) -> Result<ProjectManifest, AppError>
     })
 }
 
+/// 复制项目素材到新项目：优先硬链接，跨盘等失败时退回复制。
+/// 缩略图与波形缓存记录的是原项目路径，不复制，由新项目按需重建。
+pub fn duplicate_project_assets(
+    project_root: &Path,
+    source_id: &str,
+    target_id: &str,
+) -> Result<(), AppError> {
+    let source_dir = project_dir(project_root, source_id).join("assets");
+    let target_dir = project_dir(project_root, target_id).join("assets");
+    let entries = std::fs::read_dir(&source_dir).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to read assets dir: {error}"),
+            Some("检查原项目素材是否完整".to_string()),
+        )
+    })?;
+    for entry in entries.flatten() {
+        let source = entry.path();
+        let name = entry.file_name();
+        if !source.is_file() || name == "waveform.json" {
+            continue;
+        }
+        link_or_copy(&source, &target_dir.join(&name))?;
+    }
+    Ok(())
+}
+
+/// 优先硬链接，跨盘等失败时退回复制。
+pub fn link_or_copy(source: &Path, target: &Path) -> Result<(), AppError> {
+    if std::fs::hard_link(source, target).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(source, target).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to copy {}: {error}", source.display()),
+            Some("确认磁盘空间和路径权限".to_string()),
+        )
+    })?;
+    Ok(())
+}
+
+pub fn edit_history_path(project_root: &Path, project_id: &str) -> PathBuf {
+    project_dir(project_root, project_id).join("edit_history.json")
+}
+
+/// 读取编辑历史；文件不存在或无法解析时从空历史开始，不阻塞编辑。
+pub fn load_edit_history(project_root: &Path, project_id: &str) -> EditHistory {
+    std::fs::read_to_string(edit_history_path(project_root, project_id))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_edit_history(
+    project_root: &Path,
+    project_id: &str,
+    history: &EditHistory,
+) -> Result<(), AppError> {
+    let content = serde_json::to_string(history).map_err(|error| {
+        AppError::new(
+            "SERDE_ERROR",
+            format!("failed to serialize edit history: {error}"),
+            None,
+        )
+    })?;
+    std::fs::write(edit_history_path(project_root, project_id), content).map_err(|error| {
+        AppError::new(
+            "IO_ERROR",
+            format!("failed to write edit history: {error}"),
+            Some("确认磁盘空间和路径权限".to_string()),
+        )
+    })
+}
+
 pub fn mark_recovery_marker(project_root: &Path, project_id: &str) -> Result<(), AppError> {
     let marker_path = project_dir(project_root, project_id).join("recovery.marker");
     std::fs::write(marker_path, "recoverable").map_err(|error| {
@@ -230,10 +585,86 @@ fn migrate_to_v1(mut value: Value) -> Result<Value, AppError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{load_manifest_from_file, CURRENT_SCHEMA_VERSION};
+    use super::{
+        duplicate_project_assets, ensure_project_dirs, list_manifest_revision_ids, load_manifest,
+        load_manifest_from_file, manifest_path, manifest_revision_path, manifest_revisions_dir,
+        save_manifest, write_manifest_revision, CURRENT_SCHEMA_VERSION,
+    };
+    use crate::domain::models::ProjectManifest;
     use serde_json::json;
     use tempfile::tempdir;
 
+    #[test]
+    fn duplicate_assets_skip_derived_caches() {
+        let temp = tempdir().unwrap();
+        ensure_project_dirs(temp.path(), "source").unwrap();
+        ensure_project_dirs(temp.path(), "copy").unwrap();
+        let assets = temp.path().join("source").join("assets");
+        std::fs::write(assets.join("recording_raw.mp4"), b"video").unwrap();
+        std::fs::write(assets.join("waveform.json"), b"{}").unwrap();
+        std::fs::create_dir_all(assets.join("thumbs")).unwrap();
+
+        duplicate_project_assets(temp.path(), "source", "copy").unwrap();
+        let copied = temp.path().join("copy").join("assets");
+        assert_eq!(
+            std::fs::read(copied.join("recording_raw.mp4")).unwrap(),
+            b"video"
+        );
+        assert!(!copied.join("waveform.json").exists());
+        assert!(!copied.join("thumbs").exists());
+    }
+
+    #[test]
+    fn corrupted_manifest_falls_back_to_backup() {
+        let temp = tempdir().unwrap();
+        let mut manifest = ProjectManifest {
+            title: Some("first".to_string()),
+            ..ProjectManifest::default()
+        };
+        save_manifest(temp.path(), "p1", &manifest).unwrap();
+        manifest.title = Some("second".to_string());
+        save_manifest(temp.path(), "p1", &manifest).unwrap();
+        let path = manifest_path(temp.path(), "p1");
+        assert!(!path.with_extension("json.tmp").exists());
+
+        std::fs::write(&path, "{\"schemaVersion\": 1, \"tit").unwrap();
+        let recovered = load_manifest(temp.path(), "p1").unwrap();
+        assert_eq!(recovered.title.as_deref(), Some("first"));
+        assert!(load_manifest_from_file(&path).is_ok());
+    }
+
+    #[test]
+    fn manifest_revisions_are_bounded() {
+        let temp = tempdir().unwrap();
+        let manifest = ProjectManifest::default();
+        save_manifest(temp.path(), "p1", &manifest).unwrap();
+        assert!(list_manifest_revision_ids(temp.path(), "p1").is_empty());
+        for _ in 0..3 {
+            save_manifest(temp.path(), "p1", &manifest).unwrap();
+            write_manifest_revision(temp.path(), "p1").unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let ids = list_manifest_revision_ids(temp.path(), "p1");
+        assert_eq!(ids.len(), 3);
+        assert!(ids[0] > ids[2]);
+        let path = manifest_revision_path(temp.path(), "p1", &ids[0]).unwrap();
+        assert!(load_manifest_from_file(&path).is_ok());
+        assert!(manifest_revision_path(temp.path(), "p1", "../project").is_err());
+
+        let dir = manifest_revisions_dir(temp.path(), "p1");
+        for index in 0..40 {
+            std::fs::write(
+                dir.join(format!("project-19700101T0000{index:05}Z.json")),
+                "{}",
+            )
+            .unwrap();
+        }
+        write_manifest_revision(temp.path(), "p1").unwrap();
+        let ids = list_manifest_revision_ids(temp.path(), "p1");
+        assert_eq!(ids.len(), super::MAX_MANIFEST_REVISIONS);
+        assert!(ids[0].starts_with("20"));
+    }
+
     #[test]
     fn reject_future_schema() {
         let temp = tempdir().unwrap();
@@ -0,0 +1,170 @@
+use crate::core::export::presets::builtin_export_presets;
+use crate::core::export::queue::DEFAULT_MAX_CONCURRENT_EXPORTS;
+use crate::domain::models::{
+    AppError, AppSettings, CleanupPolicy, HotkeySettings, Locale, RecordingProfile, SharePolicy,
+};
+use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
+            hotkeys: HotkeySettings::default(),
+            export_presets: builtin_export_presets(),
+            locale: Locale::default(),
+            default_recording_profile: RecordingProfile::default(),
+            default_export_preset_id: None,
+            project_root: None,
+            telemetry_opt_in: false,
+            cleanup: CleanupPolicy::default(),
+            auto_export_after_recording: false,
+            s3: None,
+            share: SharePolicy::default(),
+            max_concurrent_exports: DEFAULT_MAX_CONCURRENT_EXPORTS,
+            encoder_threads: 0,
+        }
+    }
+}
+
+/// 读取 settings.json；文件不存在时写入默认设置。旧版本文件迁移后立即回写。
+pub fn load_settings_file(path: &Path) -> Result<AppSettings, AppError> {
+    if !path.exists() {
+        let settings = AppSettings::default();
+        write_settings_file(path, &settings)?;
+        return Ok(settings);
+    }
+    let raw = std::fs::read_to_string(path).map_err(|error| {
+        AppError::new(
+            "SETTINGS_READ_FAIL",
+            format!("failed to read settings: {error}"),
+            None,
+        )
+    })?;
+    let value: Value = serde_json::from_str(&raw).map_err(settings_parse_error)?;
+    let schema_version = value
+        .get("schemaVersion")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32;
+    if schema_version > SETTINGS_SCHEMA_VERSION {
+        return Err(AppError::new(
+            "UNSUPPORTED_SCHEMA",
+            format!(
+                "settings schemaVersion {schema_version} is newer than supported {SETTINGS_SCHEMA_VERSION}"
+            ),
+            Some("请升级应用后重试".to_string()),
+        ));
+    }
+    if schema_version == SETTINGS_SCHEMA_VERSION {
+        return serde_json::from_value(value).map_err(settings_parse_error);
+    }
+    let settings: AppSettings =
+        serde_json::from_value(migrate_settings_to_v1(value)?).map_err(settings_parse_error)?;
+    write_settings_file(path, &settings)?;
+    tracing::info!("migrated settings from schema {schema_version} to {SETTINGS_SCHEMA_VERSION}");
+    Ok(settings)
+}
+
+/// 与清单相同，先写临时文件再替换，避免写到一半崩溃留下损坏的设置。
+pub fn write_settings_file(path: &Path, settings: &AppSettings) -> Result<(), AppError> {
+    let write_error = |error: std::io::Error| {
+        AppError::new(
+            "SETTINGS_WRITE_FAIL",
+            format!("failed to write settings: {error}"),
+            None,
+        )
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let raw = serde_json::to_string_pretty(settings).map_err(|error| {
+        AppError::new(
+            "SETTINGS_WRITE_FAIL",
+            format!("failed to serialize settings: {error}"),
+            None,
+        )
+    })?;
+    let temp_path = path.with_extension("json.tmp");
+    {
+        let mut file = std::fs::File::create(&temp_path).map_err(write_error)?;
+        file.write_all(raw.as_bytes()).map_err(write_error)?;
+        file.sync_all().map_err(write_error)?;
+    }
+    std::fs::rename(&temp_path, path).map_err(write_error)
+}
+
+/// 无版本号的旧设置只有 hotkeys/exportPresets/locale，字段名未变，
+/// 补上版本号后其余分区由默认值填充。
+fn migrate_settings_to_v1(mut value: Value) -> Result<Value, AppError> {
+    let Some(object) = value.as_object_mut() else {
+        return Err(AppError::new(
+            "MIGRATION_ERROR",
+            "legacy settings should be a JSON object",
+            None,
+        ));
+    };
+    object.insert(
+        "schemaVersion".to_string(),
+        Value::from(SETTINGS_SCHEMA_VERSION),
+    );
+    Ok(value)
+}
+
+fn settings_parse_error(error: serde_json::Error) -> AppError {
+    AppError::new(
+        "SETTINGS_PARSE_FAIL",
+        format!("failed to parse settings: {error}"),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_settings_file, SETTINGS_SCHEMA_VERSION};
+    use crate::domain::models::Locale;
+    use tempfile::tempdir;
+
+    #[test]
+    fn legacy_settings_are_migrated_and_written_back() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("settings.json");
+        std::fs::write(
+            &path,
+            r#"{"hotkeys":{"startStop":"Ctrl+Alt+R","pauseResume":"Ctrl+Alt+P"},"locale":"en-US"}"#,
+        )
+        .unwrap();
+
+        let settings = load_settings_file(&path).unwrap();
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(settings.hotkeys.start_stop, "Ctrl+Alt+R");
+        assert_eq!(settings.locale, Locale::EnUs);
+        assert!(!settings.export_presets.is_empty());
+        assert!(!settings.telemetry_opt_in);
+        assert_eq!(settings.cleanup.trash_retention_days, 30);
+        assert_eq!(settings.max_concurrent_exports, 1);
+        assert_eq!(settings.encoder_threads, 0);
+
+        let raw: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(raw["schemaVersion"], SETTINGS_SCHEMA_VERSION);
+        assert!(raw.get("cleanup").is_some());
+    }
+
+    #[test]
+    fn missing_file_gets_defaults_and_newer_schema_is_rejected() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join("settings.json");
+        let settings = load_settings_file(&path).unwrap();
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert!(path.exists());
+
+        std::fs::write(&path, r#"{"schemaVersion":99}"#).unwrap();
+        assert_eq!(
+            load_settings_file(&path).unwrap_err().code,
+            "UNSUPPORTED_SCHEMA"
+        );
+    }
+}
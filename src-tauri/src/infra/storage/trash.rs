@@ -0,0 +1,202 @@
+use crate::domain::models::{AppError, TrashedProject};
+use crate::infra::storage::index::{index_project, mark_index_stale, unindex_project};
+use crate::infra::storage::project_store::{load_manifest, load_manifest_from_file, project_dir};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const TRASH_DIR_NAME: &str = ".trash";
+const TRASH_INFO_NAME: &str = "trash.json";
+const AUTO_PURGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrashInfo {
+    deleted_at: DateTime<Utc>,
+}
+
+pub fn trash_dir(project_root: &Path) -> PathBuf {
+    project_root.join(TRASH_DIR_NAME)
+}
+
+/// 把项目目录移入 `projects/.trash/`，并记录删除时间。
+/// 同盘重命名，不复制素材；清单中的绝对路径在恢复后依然有效。
+pub fn move_to_trash(project_root: &Path, project_id: &str) -> Result<(), AppError> {
+    let source = project_dir(project_root, project_id);
+    let target = trash_dir(project_root).join(project_id);
+    std::fs::create_dir_all(trash_dir(project_root)).map_err(trash_io_error)?;
+    if target.exists() {
+        std::fs::remove_dir_all(&target).map_err(trash_io_error)?;
+    }
+    let info = serde_json::to_vec(&TrashInfo {
+        deleted_at: Utc::now(),
+    })
+    .map_err(|error| AppError::new("SERDE_ERROR", error.to_string(), None))?;
+    std::fs::write(source.join(TRASH_INFO_NAME), info).map_err(trash_io_error)?;
+    std::fs::rename(&source, &target).map_err(|error| {
+        let _ = std::fs::remove_file(source.join(TRASH_INFO_NAME));
+        AppError::new(
+            "PROJECT_DELETE_FAIL",
+            format!("failed to move project {project_id} to trash: {error}"),
+            Some("请关闭占用该项目文件的程序后重试".to_string()),
+        )
+    })?;
+    if let Err(error) = unindex_project(project_root, project_id) {
+        tracing::warn!("failed to remove project from index: {}", error.message);
+        mark_index_stale(project_root);
+    }
+    Ok(())
+}
+
+/// 列出回收站中的项目，最近删除的在前；清单损坏的条目跳过。
+pub fn list_trash(project_root: &Path) -> Vec<TrashedProject> {
+    let Ok(entries) = std::fs::read_dir(trash_dir(project_root)) else {
+        return Vec::new();
+    };
+    let mut projects = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let manifest = load_manifest_from_file(&dir.join("project.json")).ok()?;
+            Some(TrashedProject {
+                project_id: entry.file_name().to_string_lossy().to_string(),
+                title: manifest.title,
+                deleted_at: deleted_at(&dir),
+                duration_ms: manifest
+                    .timeline
+                    .trim_end_ms
+                    .saturating_sub(manifest.timeline.trim_start_ms),
+            })
+        })
+        .collect::<Vec<_>>();
+    projects.sort_by_key(|project| std::cmp::Reverse(project.deleted_at));
+    projects
+}
+
+pub fn restore_from_trash(project_root: &Path, project_id: &str) -> Result<(), AppError> {
+    let source = trash_dir(project_root).join(project_id);
+    let target = project_dir(project_root, project_id);
+    if !source.is_dir() {
+        return Err(AppError::new(
+            "TRASH_ITEM_NOT_FOUND",
+            format!("project {project_id} is not in trash"),
+            Some("请刷新回收站列表后重试".to_string()),
+        )
+        .with_param("projectId", project_id));
+    }
+    if target.exists() {
+        return Err(AppError::new(
+            "PROJECT_ALREADY_EXISTS",
+            format!("project {project_id} already exists"),
+            None,
+        ));
+    }
+    std::fs::rename(&source, &target).map_err(trash_io_error)?;
+    let _ = std::fs::remove_file(target.join(TRASH_INFO_NAME));
+    let indexed = load_manifest(project_root, project_id)
+        .and_then(|manifest| index_project(project_root, project_id, &manifest));
+    if let Err(error) = indexed {
+        tracing::warn!("failed to restore project into index: {}", error.message);
+        mark_index_stale(project_root);
+    }
+    Ok(())
+}
+
+/// 清理删除时间早于 `older_than` 的项目，None 表示清空回收站。返回清理的项目数。
+pub fn purge_trash(project_root: &Path, older_than: Option<Duration>) -> Result<usize, AppError> {
+    let Ok(entries) = std::fs::read_dir(trash_dir(project_root)) else {
+        return Ok(0);
+    };
+    let cutoff = older_than.map(|age| Utc::now() - age);
+    let mut purged = 0;
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        if cutoff.is_some_and(|cutoff| deleted_at(&dir) > cutoff) {
+            continue;
+        }
+        let removed = if dir.is_dir() {
+            std::fs::remove_dir_all(&dir)
+        } else {
+            std::fs::remove_file(&dir)
+        };
+        removed.map_err(trash_io_error)?;
+        purged += 1;
+    }
+    Ok(purged)
+}
+
+/// 后台定时清理超过保留期的回收站项目。保留期每轮重新读取，
+/// 返回 None 表示自动清理已关闭，本轮跳过。
+pub fn spawn_trash_auto_purge<F>(project_root: PathBuf, retention: F)
+where
+    F: Fn() -> Option<Duration> + Send + 'static,
+{
+    std::thread::spawn(move || loop {
+        let Some(retention) = retention() else {
+            std::thread::sleep(AUTO_PURGE_INTERVAL);
+            continue;
+        };
+        match purge_trash(&project_root, Some(retention)) {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("purged {count} expired projects from trash"),
+            Err(error) => tracing::warn!("trash auto purge failed: {}", error.message),
+        }
+        std::thread::sleep(AUTO_PURGE_INTERVAL);
+    });
+}
+
+/// 读取删除时间；缺少记录时退回目录修改时间，仍取不到则视为最早删除。
+fn deleted_at(dir: &Path) -> DateTime<Utc> {
+    std::fs::read_to_string(dir.join(TRASH_INFO_NAME))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<TrashInfo>(&raw).ok())
+        .map(|info| info.deleted_at)
+        .or_else(|| {
+            let modified = std::fs::metadata(dir)
+                .and_then(|meta| meta.modified())
+                .ok()?;
+            Some(modified.into())
+        })
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+fn trash_io_error(error: std::io::Error) -> AppError {
+    AppError::new(
+        "TRASH_IO_ERROR",
+        format!("trash operation failed: {error}"),
+        Some("确认磁盘空间和路径权限".to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{list_trash, move_to_trash, purge_trash, restore_from_trash, trash_dir};
+    use crate::domain::models::ProjectManifest;
+    use crate::infra::storage::project_store::{ensure_project_dirs, save_manifest};
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn trashed_projects_can_be_restored_or_purged() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        for id in ["keep", "drop"] {
+            ensure_project_dirs(root, id).unwrap();
+            save_manifest(root, id, &ProjectManifest::default()).unwrap();
+            move_to_trash(root, id).unwrap();
+            assert!(!root.join(id).exists());
+        }
+        assert_eq!(list_trash(root).len(), 2);
+
+        restore_from_trash(root, "keep").unwrap();
+        assert!(root.join("keep").join("project.json").exists());
+        assert!(!root.join("keep").join("trash.json").exists());
+        assert!(restore_from_trash(root, "keep").is_err());
+
+        assert_eq!(purge_trash(root, Some(Duration::days(30))).unwrap(), 0);
+        assert_eq!(purge_trash(root, None).unwrap(), 1);
+        assert!(list_trash(root).is_empty());
+        assert!(trash_dir(root).exists());
+    }
+}
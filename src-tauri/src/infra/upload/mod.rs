@@ -0,0 +1,157 @@
+pub mod oauth;
+pub mod resumable;
+pub mod s3;
+pub mod token_store;
+
+use crate::domain::models::{AppError, S3Settings, UploadTarget};
+use chrono::{DateTime, Utc};
+use oauth::access_token;
+use resumable::{
+    query_session, start_session, upload_chunk, uploaded_resource, RequestError, SessionState,
+};
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+
+/// 网络抖动时每个分片的重试次数，重试前先向服务端查询已接收的偏移。
+const MAX_CHUNK_RETRIES: u32 = 3;
+const RETRY_BACKOFF: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedResource {
+    pub remote_id: String,
+    pub url: String,
+    /// 预签名链接的失效时间。
+    pub url_expires_at: Option<DateTime<Utc>>,
+}
+
+pub struct UploadJob<'a> {
+    pub target: UploadTarget,
+    pub project_id: &'a str,
+    pub tokens_path: &'a Path,
+    pub s3: Option<&'a S3Settings>,
+    pub file: &'a Path,
+    pub title: &'a str,
+    /// 上次中断时保存的会话地址，仍有效时从服务端记录的偏移继续。
+    pub resume_session: Option<String>,
+}
+
+/// 断点续传上传文件。新建会话后立即通过 `on_session` 交给调用方持久化，
+/// 应用重启后可凭此续传；`on_progress` 回调已确认的字节数与总字节数。
+pub fn upload_file<S, P>(
+    job: UploadJob<'_>,
+    mut on_session: S,
+    mut on_progress: P,
+) -> Result<UploadedResource, AppError>
+where
+    S: FnMut(&str),
+    P: FnMut(u64, u64),
+{
+    let file_name = job
+        .file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "focuslens.mp4".to_string());
+    if job.target == UploadTarget::S3 {
+        let settings = job.s3.ok_or_else(|| {
+            AppError::new(
+                "UPLOAD_NOT_CONFIGURED",
+                "s3 storage is not configured",
+                Some("请先在设置中填写对象存储地址与凭据".to_string()),
+            )
+            .with_param("target", job.target.as_str())
+        })?;
+        let key = s3::object_key(settings, job.project_id, &file_name);
+        return s3::upload_object(
+            settings,
+            job.file,
+            &key,
+            job.resume_session,
+            on_session,
+            on_progress,
+        );
+    }
+
+    let mut file = File::open(job.file).map_err(|error| {
+        AppError::new(
+            "UPLOAD_READ_FAIL",
+            format!("failed to open export file: {error}"),
+            Some("请先完成导出".to_string()),
+        )
+    })?;
+    let total_bytes = file
+        .metadata()
+        .map_err(|error| AppError::new("UPLOAD_READ_FAIL", error.to_string(), None))?
+        .len();
+    let mut token = access_token(job.tokens_path, job.target, false)?;
+    let mut refreshed = false;
+    let mut session = job.resume_session;
+    let mut offset = None;
+    let mut failures = 0;
+
+    loop {
+        let result = match (&session, offset) {
+            (None, _) => {
+                match start_session(job.target, &token, job.title, &file_name, total_bytes) {
+                    Ok(uri) => {
+                        on_session(&uri);
+                        session = Some(uri);
+                        offset = Some(0);
+                        continue;
+                    }
+                    Err(error) => Err(error),
+                }
+            }
+            (Some(uri), None) => query_session(uri, &token, total_bytes),
+            (Some(uri), Some(start)) => upload_chunk(uri, &token, &mut file, start, total_bytes),
+        };
+        match result {
+            Ok(SessionState::Incomplete(next)) => {
+                failures = 0;
+                refreshed = false;
+                offset = Some(next);
+                on_progress(next, total_bytes);
+            }
+            Ok(SessionState::Completed(body)) => {
+                on_progress(total_bytes, total_bytes);
+                return uploaded_resource(job.target, &body).ok_or_else(|| {
+                    AppError::new(
+                        "UPLOAD_FAIL",
+                        format!("upload finished without a resource id: {body}"),
+                        None,
+                    )
+                });
+            }
+            // 令牌在长时间上传中过期：刷新一次后重试当前请求。
+            Err(RequestError::Unauthorized) if !refreshed => {
+                token = access_token(job.tokens_path, job.target, true)?;
+                refreshed = true;
+            }
+            Err(RequestError::Unauthorized) => {
+                return Err(AppError::new(
+                    "UPLOAD_AUTH_EXPIRED",
+                    "upload authorization was rejected",
+                    Some("请重新连接账号".to_string()),
+                )
+                .with_param("target", job.target.as_str()));
+            }
+            // 会话过期（约一周）或已失效：从头开始新会话。
+            Err(RequestError::SessionExpired) => {
+                session = None;
+                offset = None;
+            }
+            Err(RequestError::Failed(error)) => {
+                failures += 1;
+                if failures > MAX_CHUNK_RETRIES || session.is_none() {
+                    return Err(error);
+                }
+                tracing::warn!(
+                    "upload chunk failed ({failures}/{MAX_CHUNK_RETRIES}): {}",
+                    error.message
+                );
+                std::thread::sleep(RETRY_BACKOFF * failures);
+                offset = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use crate::domain::models::{AppError, UploadTarget};
+use crate::infra::upload::token_store::{load_token, save_token, StoredToken};
+use base64::Engine;
+use chrono::Utc;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const AUTH_ENDPOINT: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const TOKEN_ENDPOINT: &str = "https://oauth2.googleapis.com/token";
+/// 等待用户在浏览器中完成授权的上限。
+const AUTHORIZE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+
+/// 桌面应用的 OAuth 客户端，由发布流水线在编译期注入，运行时可用同名环境变量覆盖。
+/// Google 的桌面客户端密钥不具保密性，安全性由 PKCE 保证。
+struct ClientConfig {
+    client_id: String,
+    client_secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: i64,
+    refresh_token: Option<String>,
+}
+
+fn client_config() -> Result<ClientConfig, AppError> {
+    let value = |key: &str, compiled: Option<&'static str>| {
+        std::env::var(key)
+            .ok()
+            .or_else(|| compiled.map(str::to_string))
+            .filter(|value| !value.trim().is_empty())
+    };
+    let client_id = value(
+        "FOCUSLENS_GOOGLE_CLIENT_ID",
+        option_env!("FOCUSLENS_GOOGLE_CLIENT_ID"),
+    )
+    .ok_or_else(|| {
+        AppError::new(
+            "UPLOAD_NOT_CONFIGURED",
+            "google oauth client id is not configured",
+            Some("当前版本未启用上传，请设置 FOCUSLENS_GOOGLE_CLIENT_ID".to_string()),
+        )
+    })?;
+    Ok(ClientConfig {
+        client_id,
+        client_secret: value(
+            "FOCUSLENS_GOOGLE_CLIENT_SECRET",
+            option_env!("FOCUSLENS_GOOGLE_CLIENT_SECRET"),
+        ),
+    })
+}
+
+/// S3 使用设置中的访问密钥，没有 OAuth 授权范围。
+fn scope(target: UploadTarget) -> Option<&'static str> {
+    match target {
+        UploadTarget::Youtube => Some("https://www.googleapis.com/auth/youtube.upload"),
+        // 只能访问本应用创建的文件，不读取用户网盘中的其他内容。
+        UploadTarget::GoogleDrive => Some("https://www.googleapis.com/auth/drive.file"),
+        UploadTarget::S3 => None,
+    }
+}
+
+/// 回环地址 + PKCE 的桌面授权流程：在本机随机端口监听回调，
+/// 用系统浏览器打开授权页，拿到授权码后换取令牌并保存。
+pub fn authorize<F>(
+    tokens_path: &Path,
+    target: UploadTarget,
+    open_browser: F,
+) -> Result<(), AppError>
+where
+    F: FnOnce(&str) -> Result<(), AppError>,
+{
+    let scope = scope(target).ok_or_else(|| {
+        AppError::new(
+            "UPLOAD_AUTH_FAIL",
+            format!("{} does not use oauth", target.as_str()),
+            None,
+        )
+    })?;
+    let config = client_config()?;
+    let listener = TcpListener::bind(("127.0.0.1", 0)).map_err(oauth_io_error)?;
+    let port = listener.local_addr().map_err(oauth_io_error)?.port();
+    let redirect_uri = format!("http://127.0.0.1:{port}");
+    let verifier = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    let state = uuid::Uuid::new_v4().simple().to_string();
+    let url = authorization_url(
+        &config.client_id,
+        &redirect_uri,
+        scope,
+        &pkce_challenge(&verifier),
+        &state,
+    );
+    open_browser(&url)?;
+    let code = wait_for_code(&listener, &state)?;
+
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code.as_str()),
+        ("client_id", config.client_id.as_str()),
+        ("redirect_uri", redirect_uri.as_str()),
+        ("code_verifier", verifier.as_str()),
+    ];
+    if let Some(secret) = config.client_secret.as_deref() {
+        form.push(("client_secret", secret));
+    }
+    let response = request_token(&form)?;
+    save_token(
+        tokens_path,
+        target,
+        StoredToken {
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            expires_at: Utc::now() + chrono::Duration::seconds(response.expires_in),
+        },
+    )
+}
+
+/// 返回可用的访问令牌，过期或 `force_refresh` 时先用刷新令牌换新。
+pub fn access_token(
+    tokens_path: &Path,
+    target: UploadTarget,
+    force_refresh: bool,
+) -> Result<String, AppError> {
+    let token = load_token(tokens_path, target).ok_or_else(|| {
+        AppError::new(
+            "UPLOAD_NOT_CONNECTED",
+            format!("{} account is not connected", target.as_str()),
+            Some("请先连接账号再上传".to_string()),
+        )
+        .with_param("target", target.as_str())
+    })?;
+    if !force_refresh && !token.is_expired() {
+        return Ok(token.access_token);
+    }
+    let Some(refresh_token) = token.refresh_token.clone() else {
+        return Err(AppError::new(
+            "UPLOAD_AUTH_EXPIRED",
+            "upload authorization expired",
+            Some("请重新连接账号".to_string()),
+        )
+        .with_param("target", target.as_str()));
+    };
+    let config = client_config()?;
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token.as_str()),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if let Some(secret) = config.client_secret.as_deref() {
+        form.push(("client_secret", secret));
+    }
+    let response = request_token(&form)?;
+    let access_token = response.access_token.clone();
+    save_token(
+        tokens_path,
+        target,
+        StoredToken {
+            access_token: response.access_token,
+            // 刷新响应通常不再返回刷新令牌，沿用原值。
+            refresh_token: response.refresh_token.or(Some(refresh_token)),
+            expires_at: Utc::now() + chrono::Duration::seconds(response.expires_in),
+        },
+    )?;
+    Ok(access_token)
+}
+
+fn request_token(form: &[(&str, &str)]) -> Result<TokenResponse, AppError> {
+    let response = ureq::post(TOKEN_ENDPOINT)
+        .timeout(Duration::from_secs(30))
+        .send_form(form)
+        .map_err(|error| match error {
+            ureq::Error::Status(400 | 401, _) => AppError::new(
+                "UPLOAD_AUTH_EXPIRED",
+                format!("token request rejected: {error}"),
+                Some("请重新连接账号".to_string()),
+            ),
+            _ => AppError::new(
+                "UPLOAD_NETWORK_ERROR",
+                format!("token request failed: {error}"),
+                Some("检查网络连接后重试".to_string()),
+            ),
+        })?;
+    response.into_json::<TokenResponse>().map_err(|error| {
+        AppError::new(
+            "UPLOAD_AUTH_FAIL",
+            format!("invalid token response: {error}"),
+            None,
+        )
+    })
+}
+
+fn authorization_url(
+    client_id: &str,
+    redirect_uri: &str,
+    scope: &str,
+    challenge: &str,
+    state: &str,
+) -> String {
+    let params = [
+        ("client_id", client_id),
+        ("redirect_uri", redirect_uri),
+        ("response_type", "code"),
+        ("scope", scope),
+        ("code_challenge", challenge),
+        ("code_challenge_method", "S256"),
+        ("state", state),
+        // 要求返回刷新令牌，长视频上传超过一小时也能续期。
+        ("access_type", "offline"),
+        ("prompt", "consent"),
+    ];
+    let query = params
+        .iter()
+        .map(|(key, value)| format!("{key}={}", percent_encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{AUTH_ENDPOINT}?{query}")
+}
+
+/// RFC 7636 S256：BASE64URL(SHA256(verifier))，不带填充。
+fn pkce_challenge(verifier: &str) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// 轮询接受回调连接，忽略与本次授权无关的请求（如 favicon）。
+fn wait_for_code(listener: &TcpListener, state: &str) -> Result<String, AppError> {
+    listener.set_nonblocking(true).map_err(oauth_io_error)?;
+    let deadline = Instant::now() + AUTHORIZE_TIMEOUT;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Some(result) = handle_callback(stream, state) {
+                    return result;
+                }
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
+                if Instant::now() >= deadline {
+                    return Err(AppError::new(
+                        "UPLOAD_AUTH_TIMEOUT",
+                        "timed out waiting for oauth callback",
+                        Some("请在浏览器中完成授权后重试".to_string()),
+                    ));
+                }
+                std::thread::sleep(ACCEPT_POLL);
+            }
+            Err(error) => return Err(oauth_io_error(error)),
+        }
+    }
+}
+
+fn handle_callback(mut stream: TcpStream, state: &str) -> Option<Result<String, AppError>> {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line).ok()?;
+    let params = parse_callback_query(&request_line)?;
+    let get = |key: &str| {
+        params
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.clone())
+    };
+    if get("state").as_deref() != Some(state) {
+        respond(&mut stream, "授权请求已失效，请回到 FocusLens 重试。");
+        return None;
+    }
+    if let Some(error) = get("error") {
+        respond(&mut stream, "已取消授权，可以关闭此页面。");
+        return Some(Err(AppError::new(
+            "UPLOAD_AUTH_DENIED",
+            format!("oauth authorization denied: {error}"),
+            Some("如需上传请重新连接并允许访问".to_string()),
+        )));
+    }
+    let code = get("code")?;
+    respond(&mut stream, "授权完成，可以关闭此页面并回到 FocusLens。");
+    Some(Ok(code))
+}
+
+fn respond(stream: &mut TcpStream, message: &str) {
+    let body = format!("<!doctype html><meta charset=\"utf-8\"><p>{message}</p>");
+    let _ = write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+}
+
+/// 解析回调请求行 `GET /?code=...&state=... HTTP/1.1` 中的查询参数。
+fn parse_callback_query(request_line: &str) -> Option<Vec<(String, String)>> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let (_, query) = target.split_once('?')?;
+    Some(
+        query
+            .split('&')
+            .filter_map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                Some((percent_decode(key)?, percent_decode(value)?))
+            })
+            .collect(),
+    )
+}
+
+pub fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let hex = value.get(index + 1..index + 3)?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                index += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+fn oauth_io_error(error: std::io::Error) -> AppError {
+    AppError::new(
+        "UPLOAD_AUTH_FAIL",
+        format!("oauth callback listener failed: {error}"),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_callback_query, percent_decode, percent_encode, pkce_challenge};
+
+    #[test]
+    fn pkce_challenge_matches_rfc_example() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn callback_query_is_percent_decoded() {
+        assert_eq!(
+            percent_encode("https://a.b/c d"),
+            "https%3A%2F%2Fa.b%2Fc%20d"
+        );
+        assert_eq!(percent_decode("4%2F0Ab+c").as_deref(), Some("4/0Ab c"));
+        assert_eq!(percent_decode("%zz"), None);
+        let params =
+            parse_callback_query("GET /?state=s1&code=4%2F0AbC&scope=x HTTP/1.1\r\n").unwrap();
+        assert_eq!(
+            params,
+            vec![
+                ("state".to_string(), "s1".to_string()),
+                ("code".to_string(), "4/0AbC".to_string()),
+                ("scope".to_string(), "x".to_string()),
+            ]
+        );
+        assert!(parse_callback_query("GET /favicon.ico HTTP/1.1").is_none());
+    }
+}
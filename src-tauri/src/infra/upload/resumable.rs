@@ -0,0 +1,266 @@
+use crate::domain::models::{AppError, UploadTarget};
+use crate::infra::upload::UploadedResource;
+use serde_json::{json, Value};
+use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
+
+/// Google 要求分片大小为 256 KiB 的整数倍（最后一片除外）。
+pub const CHUNK_SIZE: u64 = 32 * 256 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 单次请求的结果：访问令牌失效与会话过期需要调用方分别处理。
+#[derive(Debug)]
+pub enum RequestError {
+    Unauthorized,
+    SessionExpired,
+    Failed(AppError),
+}
+
+#[derive(Debug)]
+pub enum SessionState {
+    /// 服务端已确认接收的字节数，从此处继续上传。
+    Incomplete(u64),
+    /// 上传完成，附带服务端返回的资源描述。
+    Completed(Value),
+}
+
+fn agent() -> ureq::Agent {
+    // 308 Resume Incomplete 不是重定向，必须原样返回。
+    ureq::AgentBuilder::new()
+        .redirects(0)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+}
+
+/// 创建会话的地址与元数据。YouTube 默认以“不公开列出”发布，分享链接后才能观看。
+/// S3 使用分片上传，没有 Google 续传会话。
+fn session_request(
+    target: UploadTarget,
+    title: &str,
+    file_name: &str,
+) -> Option<(&'static str, Value)> {
+    match target {
+        UploadTarget::Youtube => Some((
+            "https://www.googleapis.com/upload/youtube/v3/videos?uploadType=resumable&part=snippet,status",
+            json!({
+                "snippet": { "title": title, "description": "Recorded with FocusLens" },
+                "status": { "privacyStatus": "unlisted" }
+            }),
+        )),
+        UploadTarget::GoogleDrive => Some((
+            "https://www.googleapis.com/upload/drive/v3/files?uploadType=resumable&fields=id,webViewLink",
+            json!({
+                "name": file_name,
+                "mimeType": "video/mp4"
+            }),
+        )),
+        UploadTarget::S3 => None,
+    }
+}
+
+/// 创建续传会话，返回后续分片上传使用的会话地址。
+pub fn start_session(
+    target: UploadTarget,
+    access_token: &str,
+    title: &str,
+    file_name: &str,
+    total_bytes: u64,
+) -> Result<String, RequestError> {
+    let (url, metadata) = session_request(target, title, file_name).ok_or_else(|| {
+        RequestError::Failed(AppError::new(
+            "UPLOAD_FAIL",
+            format!("{} does not support resumable sessions", target.as_str()),
+            None,
+        ))
+    })?;
+    let response = agent()
+        .post(url)
+        .set("Authorization", &format!("Bearer {access_token}"))
+        .set("X-Upload-Content-Type", "video/mp4")
+        .set("X-Upload-Content-Length", &total_bytes.to_string())
+        .send_json(metadata)
+        .map_err(request_error)?;
+    response
+        .header("Location")
+        .map(str::to_string)
+        .ok_or_else(|| {
+            RequestError::Failed(AppError::new(
+                "UPLOAD_FAIL",
+                "upload session response has no Location header",
+                None,
+            ))
+        })
+}
+
+/// 查询会话已接收的字节数，用于中断后续传。
+pub fn query_session(
+    session_uri: &str,
+    access_token: &str,
+    total_bytes: u64,
+) -> Result<SessionState, RequestError> {
+    let response = agent()
+        .put(session_uri)
+        .set("Authorization", &format!("Bearer {access_token}"))
+        .set("Content-Range", &format!("bytes */{total_bytes}"))
+        .send_bytes(&[])
+        .map_err(request_error)?;
+    session_state(response)
+}
+
+/// 从 `offset` 处上传一个分片。
+pub fn upload_chunk<R: Read + Seek>(
+    session_uri: &str,
+    access_token: &str,
+    file: &mut R,
+    offset: u64,
+    total_bytes: u64,
+) -> Result<SessionState, RequestError> {
+    let length = CHUNK_SIZE.min(total_bytes - offset);
+    let mut buffer = vec![0u8; length as usize];
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(&mut buffer))
+        .map_err(|error| {
+            RequestError::Failed(AppError::new(
+                "UPLOAD_READ_FAIL",
+                format!("failed to read export file: {error}"),
+                Some("确认导出文件仍然存在".to_string()),
+            ))
+        })?;
+    let response = agent()
+        .put(session_uri)
+        .set("Authorization", &format!("Bearer {access_token}"))
+        .set(
+            "Content-Range",
+            &format!("bytes {offset}-{}/{total_bytes}", offset + length - 1),
+        )
+        .send_bytes(&buffer)
+        .map_err(request_error)?;
+    session_state(response)
+}
+
+fn session_state(response: ureq::Response) -> Result<SessionState, RequestError> {
+    if response.status() == 308 {
+        return Ok(SessionState::Incomplete(next_offset(
+            response.header("Range"),
+        )));
+    }
+    let body = response.into_json::<Value>().map_err(|error| {
+        RequestError::Failed(AppError::new(
+            "UPLOAD_FAIL",
+            format!("invalid upload response: {error}"),
+            None,
+        ))
+    })?;
+    Ok(SessionState::Completed(body))
+}
+
+/// `Range: bytes=0-524287` 表示已接收到第 524287 字节；没有该头表示尚未接收任何数据。
+fn next_offset(range: Option<&str>) -> u64 {
+    range
+        .and_then(|range| range.trim().strip_prefix("bytes="))
+        .and_then(|range| range.split_once('-'))
+        .and_then(|(_, end)| end.trim().parse::<u64>().ok())
+        .map(|end| end + 1)
+        .unwrap_or(0)
+}
+
+/// 从完成响应中取出资源 id 与可分享链接。
+pub fn uploaded_resource(target: UploadTarget, body: &Value) -> Option<UploadedResource> {
+    let remote_id = body.get("id")?.as_str()?.to_string();
+    let url = match target {
+        UploadTarget::Youtube => format!("https://youtu.be/{remote_id}"),
+        UploadTarget::GoogleDrive => body
+            .get("webViewLink")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("https://drive.google.com/file/d/{remote_id}/view")),
+        UploadTarget::S3 => return None,
+    };
+    Some(UploadedResource {
+        remote_id,
+        url,
+        url_expires_at: None,
+    })
+}
+
+fn request_error(error: ureq::Error) -> RequestError {
+    match error {
+        ureq::Error::Status(401, _) => RequestError::Unauthorized,
+        ureq::Error::Status(404 | 410, _) => RequestError::SessionExpired,
+        ureq::Error::Status(403, response) => {
+            let detail = response.into_string().unwrap_or_default();
+            let quota = detail.contains("quotaExceeded") || detail.contains("uploadLimitExceeded");
+            RequestError::Failed(AppError::new(
+                if quota {
+                    "UPLOAD_QUOTA_EXCEEDED"
+                } else {
+                    "UPLOAD_FORBIDDEN"
+                },
+                format!("upload rejected: {detail}"),
+                Some(if quota {
+                    "已达到当日上传配额，请稍后再试".to_string()
+                } else {
+                    "请确认账号有上传权限，或重新连接账号".to_string()
+                }),
+            ))
+        }
+        ureq::Error::Status(code, response) => RequestError::Failed(AppError::new(
+            "UPLOAD_FAIL",
+            format!(
+                "upload request failed with status {code}: {}",
+                response.into_string().unwrap_or_default()
+            ),
+            Some("稍后重试，已上传部分会继续续传".to_string()),
+        )),
+        ureq::Error::Transport(transport) => RequestError::Failed(AppError::new(
+            "UPLOAD_NETWORK_ERROR",
+            format!("upload request failed: {transport}"),
+            Some("检查网络连接后重试，已上传部分会继续续传".to_string()),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_offset, uploaded_resource, CHUNK_SIZE};
+    use crate::domain::models::UploadTarget;
+    use crate::infra::upload::UploadedResource;
+    use serde_json::json;
+
+    #[test]
+    fn range_header_gives_next_offset() {
+        assert_eq!(next_offset(Some("bytes=0-524287")), 524_288);
+        assert_eq!(next_offset(Some(" bytes=0-0 ")), 1);
+        assert_eq!(next_offset(None), 0);
+        assert_eq!(next_offset(Some("garbage")), 0);
+        assert_eq!(CHUNK_SIZE % (256 * 1024), 0);
+    }
+
+    #[test]
+    fn completed_responses_map_to_share_urls() {
+        assert_eq!(
+            uploaded_resource(UploadTarget::Youtube, &json!({ "id": "abc123" })),
+            Some(UploadedResource {
+                remote_id: "abc123".to_string(),
+                url: "https://youtu.be/abc123".to_string(),
+                url_expires_at: None,
+            })
+        );
+        assert_eq!(
+            uploaded_resource(UploadTarget::GoogleDrive, &json!({ "id": "f1" }))
+                .unwrap()
+                .url,
+            "https://drive.google.com/file/d/f1/view"
+        );
+        assert_eq!(
+            uploaded_resource(
+                UploadTarget::GoogleDrive,
+                &json!({ "id": "f1", "webViewLink": "https://drive.google.com/x" })
+            )
+            .unwrap()
+            .url,
+            "https://drive.google.com/x"
+        );
+        assert!(uploaded_resource(UploadTarget::Youtube, &json!({})).is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::domain::models::{AppError, UploadTarget};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+pub const UPLOAD_TOKENS_FILE: &str = "upload_tokens.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl StoredToken {
+    /// 预留一分钟余量，避免请求途中令牌过期。
+    pub fn is_expired(&self) -> bool {
+        self.expires_at - chrono::Duration::seconds(60) <= Utc::now()
+    }
+}
+
+/// upload_tokens.json 的内容：按上传目标保存的 OAuth 令牌，以及 S3 的访问密钥。
+/// 密钥与令牌放在同一个受限文件里，不写入 settings.json。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadCredentials {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    s3_secret_access_key: Option<String>,
+    #[serde(flatten)]
+    tokens: BTreeMap<String, StoredToken>,
+}
+
+/// 文件不存在或损坏时视为未连接。
+fn load_credentials(path: &Path) -> UploadCredentials {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn load_token(path: &Path, target: UploadTarget) -> Option<StoredToken> {
+    load_credentials(path).tokens.remove(target.as_str())
+}
+
+pub fn save_token(path: &Path, target: UploadTarget, token: StoredToken) -> Result<(), AppError> {
+    let mut credentials = load_credentials(path);
+    credentials
+        .tokens
+        .insert(target.as_str().to_string(), token);
+    write_credentials(path, &credentials)
+}
+
+pub fn remove_token(path: &Path, target: UploadTarget) -> Result<(), AppError> {
+    let mut credentials = load_credentials(path);
+    if credentials.tokens.remove(target.as_str()).is_none() {
+        return Ok(());
+    }
+    write_credentials(path, &credentials)
+}
+
+pub fn load_s3_secret(path: &Path) -> Option<String> {
+    load_credentials(path)
+        .s3_secret_access_key
+        .filter(|secret| !secret.is_empty())
+}
+
+/// 传入空字符串时删除已保存的密钥。
+pub fn save_s3_secret(path: &Path, secret: &str) -> Result<(), AppError> {
+    let mut credentials = load_credentials(path);
+    let secret = (!secret.is_empty()).then(|| secret.to_string());
+    if credentials.s3_secret_access_key == secret {
+        return Ok(());
+    }
+    credentials.s3_secret_access_key = secret;
+    write_credentials(path, &credentials)
+}
+
+/// 令牌以明文写入应用数据目录，Unix 下仅本人可读写。
+fn write_credentials(path: &Path, credentials: &UploadCredentials) -> Result<(), AppError> {
+    let write_error = |error: std::io::Error| {
+        AppError::new(
+            "UPLOAD_TOKEN_WRITE_FAIL",
+            format!("failed to write upload tokens: {error}"),
+            Some("确认应用数据目录可写".to_string()),
+        )
+    };
+    let raw = serde_json::to_string_pretty(credentials)
+        .map_err(|error| AppError::new("SERDE_ERROR", error.to_string(), None))?;
+    let temp_path = path.with_extension("json.tmp");
+    {
+        let mut file = std::fs::File::create(&temp_path).map_err(write_error)?;
+        restrict_permissions(&file).map_err(write_error)?;
+        file.write_all(raw.as_bytes()).map_err(write_error)?;
+        file.sync_all().map_err(write_error)?;
+    }
+    std::fs::rename(&temp_path, path).map_err(write_error)
+}
+
+#[cfg(unix)]
+fn restrict_permissions(file: &std::fs::File) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_file: &std::fs::File) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        load_s3_secret, load_token, remove_token, save_s3_secret, save_token, StoredToken,
+        UPLOAD_TOKENS_FILE,
+    };
+    use crate::domain::models::UploadTarget;
+    use chrono::{Duration, Utc};
+    use tempfile::tempdir;
+
+    #[test]
+    fn tokens_are_stored_per_target() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join(UPLOAD_TOKENS_FILE);
+        assert!(load_token(&path, UploadTarget::Youtube).is_none());
+
+        let token = StoredToken {
+            access_token: "a1".to_string(),
+            refresh_token: Some("r1".to_string()),
+            expires_at: Utc::now() + Duration::hours(1),
+        };
+        save_token(&path, UploadTarget::Youtube, token).unwrap();
+        let stored = load_token(&path, UploadTarget::Youtube).unwrap();
+        assert_eq!(stored.access_token, "a1");
+        assert!(!stored.is_expired());
+        assert!(load_token(&path, UploadTarget::GoogleDrive).is_none());
+
+        remove_token(&path, UploadTarget::Youtube).unwrap();
+        assert!(load_token(&path, UploadTarget::Youtube).is_none());
+    }
+
+    #[test]
+    fn s3_secret_is_kept_alongside_tokens() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join(UPLOAD_TOKENS_FILE);
+        let token = StoredToken {
+            access_token: "a1".to_string(),
+            refresh_token: None,
+            expires_at: Utc::now() + Duration::hours(1),
+        };
+        save_token(&path, UploadTarget::GoogleDrive, token).unwrap();
+        save_s3_secret(&path, "secret").unwrap();
+
+        assert_eq!(load_s3_secret(&path).as_deref(), Some("secret"));
+        assert_eq!(
+            load_token(&path, UploadTarget::GoogleDrive)
+                .unwrap()
+                .access_token,
+            "a1"
+        );
+
+        save_s3_secret(&path, "").unwrap();
+        assert!(load_s3_secret(&path).is_none());
+        assert!(load_token(&path, UploadTarget::GoogleDrive).is_some());
+    }
+}
@@ -4,55 +4,198 @@ pub mod domain;
 pub mod infra;
 pub mod state;
 
-use commands::export::{get_export_task_status, retry_export, start_export};
+use crate::core::export::queue::DEFAULT_MAX_CONCURRENT_EXPORTS;
+use commands::export::{
+    cancel_export, export_animation, export_poster, get_export_task_status, list_export_queue,
+    open_export, render_motion_preview, reorder_export_queue, retry_export,
+    reveal_export_in_folder, start_export, start_export_with_preset,
+};
 use commands::project::{
-    delete_project, evaluate_camera_motion, list_projects, load_project, recover_projects,
-    update_camera_motion, update_project_title, update_timeline, validate_quality_gate,
+    accept_blur_candidate, add_blur_region, add_mute_range, add_shape_annotation,
+    add_text_annotation, archive_project, cut_transcript_words, delete_project,
+    detect_sensitive_content, detect_silent_ranges, dismiss_blur_candidate, duplicate_project,
+    evaluate_camera_motion, extract_frame, generate_timeline_thumbnails, get_audio_waveform,
+    get_cursor_track, get_edit_history_status, get_transcript_words, import_project_archive,
+    import_video, list_manifest_revisions, list_projects, list_trashed_projects, load_project,
+    patch_cursor_track, purge_trash, rebuild_project_index, recover_project, recover_projects,
+    redo_edit, refine_cursor_track, remove_blur_region, remove_mute_range, remove_shape_annotation,
+    remove_text_annotation, repair_recording, restore_manifest_revision, restore_project,
+    search_projects, suggest_chapters, undo_edit, update_audio_mix, update_blur_region,
+    update_camera_motion, update_color_grade, update_keystroke_overlay, update_project_notes,
+    update_project_tags, update_project_title, update_scene_style, update_shape_annotation,
+    update_text_annotation, update_timeline, validate_quality_gate,
+};
+use commands::recording::{
+    pause_recording, resume_recording, run_capture_test, start_recording, stop_recording,
 };
-use commands::recording::{pause_recording, resume_recording, start_recording, stop_recording};
 use commands::settings::{
-    get_platform_capability, list_audio_input_devices, load_hotkeys, save_hotkeys,
+    get_ffmpeg_info, get_ffmpeg_install_status, get_platform_capability, install_ffmpeg,
+    list_audio_input_devices, list_capture_displays, list_export_presets, list_video_input_devices,
+    load_hotkeys, load_settings, register_saved_hotkeys, save_hotkeys, update_settings,
+};
+use commands::share::{create_share_link, list_share_links, revoke_share_link};
+use commands::upload::{
+    connect_upload_account, disconnect_upload_account, list_upload_accounts, refresh_upload_link,
+    upload_export,
 };
+use infra::ffmpeg::installer::set_managed_dir;
+use infra::ffmpeg::orphans::terminate_orphan_ffmpeg;
 use infra::logging::init_tracing;
+use infra::storage::settings_store::load_settings_file;
+use infra::storage::trash::spawn_trash_auto_purge;
 use state::RuntimeState;
+use std::path::{Path, PathBuf};
 use tauri::Manager;
 
 pub fn run() {
     init_tracing();
 
     tauri::Builder::default()
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .setup(|app| {
             let app_data_dir = app
                 .path()
                 .app_data_dir()
                 .map_err(|error| error.to_string())?;
-            std::fs::create_dir_all(app_data_dir.join("projects"))
-                .map_err(|error| error.to_string())?;
-            app.manage(RuntimeState::new(app_data_dir.join("projects")));
+            let settings_path = app_data_dir.join("settings.json");
+            let project_root = resolve_project_root(&app_data_dir, &settings_path)?;
+            set_managed_dir(app_data_dir.join("ffmpeg"));
+            // 上次崩溃遗留的录制 ffmpeg 仍在写盘时先终止，再提供恢复入口。
+            terminate_orphan_ffmpeg(&project_root);
+            let max_concurrent_exports = load_settings_file(&settings_path)
+                .map(|settings| settings.max_concurrent_exports)
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_EXPORTS);
+            app.manage(RuntimeState::new(
+                project_root.clone(),
+                settings_path.clone(),
+                max_concurrent_exports,
+            ));
+            spawn_trash_auto_purge(project_root, move || {
+                load_settings_file(&settings_path)
+                    .map(|settings| settings.cleanup)
+                    .unwrap_or_default()
+                    .trash_retention()
+            });
+            register_saved_hotkeys(app.handle(), &app.state::<RuntimeState>());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             start_recording,
+            run_capture_test,
             pause_recording,
             resume_recording,
             stop_recording,
             list_projects,
+            rebuild_project_index,
+            search_projects,
+            get_transcript_words,
+            cut_transcript_words,
+            suggest_chapters,
             load_project,
+            update_project_notes,
+            update_project_tags,
             update_project_title,
+            list_manifest_revisions,
+            restore_manifest_revision,
+            duplicate_project,
+            archive_project,
+            import_project_archive,
+            import_video,
             delete_project,
+            list_trashed_projects,
+            restore_project,
+            purge_trash,
             update_timeline,
+            add_blur_region,
+            update_blur_region,
+            remove_blur_region,
+            add_mute_range,
+            remove_mute_range,
+            detect_sensitive_content,
+            accept_blur_candidate,
+            dismiss_blur_candidate,
+            add_text_annotation,
+            update_text_annotation,
+            remove_text_annotation,
+            add_shape_annotation,
+            update_shape_annotation,
+            remove_shape_annotation,
             update_camera_motion,
+            update_keystroke_overlay,
+            update_audio_mix,
+            update_color_grade,
+            detect_silent_ranges,
+            generate_timeline_thumbnails,
+            get_audio_waveform,
+            extract_frame,
+            undo_edit,
+            redo_edit,
+            get_edit_history_status,
+            update_scene_style,
             evaluate_camera_motion,
+            refine_cursor_track,
+            get_cursor_track,
+            patch_cursor_track,
             validate_quality_gate,
             start_export,
+            start_export_with_preset,
             retry_export,
+            cancel_export,
+            list_export_queue,
+            reorder_export_queue,
             get_export_task_status,
+            render_motion_preview,
+            export_animation,
+            export_poster,
+            reveal_export_in_folder,
+            open_export,
+            create_share_link,
+            revoke_share_link,
+            list_share_links,
+            connect_upload_account,
+            disconnect_upload_account,
+            list_upload_accounts,
+            refresh_upload_link,
+            upload_export,
             recover_projects,
+            recover_project,
+            repair_recording,
             get_platform_capability,
+            get_ffmpeg_info,
+            get_ffmpeg_install_status,
+            install_ffmpeg,
             list_audio_input_devices,
+            list_video_input_devices,
+            list_capture_displays,
             load_hotkeys,
-            save_hotkeys
+            list_export_presets,
+            save_hotkeys,
+            load_settings,
+            update_settings
         ])
         .run(tauri::generate_context!())
         .expect("failed to run FocusLens");
 }
+
+/// 设置中的自定义项目根目录不可用时记录日志并退回应用数据目录下的 projects/。
+fn resolve_project_root(app_data_dir: &Path, settings_path: &Path) -> Result<PathBuf, String> {
+    let default_root = app_data_dir.join("projects");
+    let custom_root = match load_settings_file(settings_path) {
+        Ok(settings) => settings.project_root.map(PathBuf::from),
+        Err(error) => {
+            tracing::warn!("failed to load settings: {}", error.message);
+            None
+        }
+    };
+    if let Some(root) = custom_root {
+        match std::fs::create_dir_all(&root) {
+            Ok(()) => return Ok(root),
+            Err(error) => tracing::warn!(
+                "custom project root {} is unavailable: {error}",
+                root.display()
+            ),
+        }
+    }
+    std::fs::create_dir_all(&default_root).map_err(|error| error.to_string())?;
+    Ok(default_root)
+}
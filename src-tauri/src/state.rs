@@ -1,10 +1,20 @@
-use crate::domain::models::{AppError, ExportProfile, RecordingProfile};
+use crate::core::capture::input::KeyEvent;
+use crate::core::capture::window_follow::WindowBoundsSample;
+use crate::core::export::queue::ExportQueue;
+use crate::domain::models::{
+    AppError, AppFocusSegment, CaptureBounds, ExportProfile, ProjectListItem, RecordingProfile,
+};
 use crate::domain::state_machine::{ExportState, RecordingState};
+use crate::infra::share_server::ShareServer;
+use crate::infra::upload::token_store::UPLOAD_TOKENS_FILE;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::process::Child;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::{Mutex, RwLock};
 
 #[derive(Debug, Clone)]
 pub struct RecordingSession {
@@ -14,6 +24,13 @@ pub struct RecordingSession {
     pub state: RecordingState,
     pub started_at: DateTime<Utc>,
     pub degrade_message: Option<String>,
+    /// 录制开始时采集区域的物理坐标范围（全屏为显示器，窗口录制为目标窗口），
+    /// 用于把光标样本换算到帧坐标。
+    pub capture_bounds: Option<CaptureBounds>,
+    /// 开启勿扰前的系统设置值，停止录制时写回；未由本次录制开启时为空。
+    pub do_not_disturb_restore: Option<String>,
+    /// 麦克风断开后以静音续录的分段，停止录制时按顺序拼接到首段之后。
+    pub capture_parts: Vec<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -26,9 +43,13 @@ pub struct ExportTask {
     pub task_id: String,
     pub project_id: String,
     pub profile: ExportProfile,
+    /// 同一任务中依次导出的附加版本（如竖屏 720p），输出到 renders/ 下的独立文件。
+    pub variants: Vec<ExportProfile>,
     pub state: ExportState,
     pub retries: u8,
     pub last_error: Option<AppError>,
+    /// 取消标记，导出管线在编码进度回调间隙检查并终止 ffmpeg。
+    pub cancel_flag: Arc<AtomicBool>,
 }
 
 #[derive(Debug, Clone)]
@@ -36,30 +57,72 @@ pub struct CursorTrackSample {
     pub t_ms: u64,
     pub x: f32,
     pub y: f32,
+    pub scroll_dx: f32,
+    pub scroll_dy: f32,
+    /// 样本落在所录显示器之外，坐标已被夹取到边界。
+    pub offscreen: bool,
+    /// 该采样周期内发生过左键点击。
+    pub click: bool,
 }
 
+/// 项目列表缓存条目，清单修改时间变化后重新读取。
+#[derive(Debug, Clone)]
+pub struct CachedProjectListItem {
+    pub manifest_modified: SystemTime,
+    pub item: ProjectListItem,
+}
+
+/// 运行时共享状态。锁均为 tokio 异步锁：命令与后台任务以 `.await` 取锁，
+/// 不阻塞异步运行时，也不存在锁中毒导致的连锁失败；持锁期间不做阻塞 IO。
 pub struct RuntimeState {
     pub project_root: PathBuf,
-    pub recording_sessions: Mutex<HashMap<String, RecordingSession>>,
+    pub recording_sessions: RwLock<HashMap<String, RecordingSession>>,
     pub recording_processes: Mutex<HashMap<String, RecordingProcess>>,
     pub cursor_tracks: Mutex<HashMap<String, Arc<Mutex<Vec<CursorTrackSample>>>>>,
-    pub export_tasks: Mutex<HashMap<String, ExportTask>>,
+    pub key_tracks: Mutex<HashMap<String, Arc<Mutex<Vec<KeyEvent>>>>>,
+    pub app_tracks: Mutex<HashMap<String, Arc<Mutex<Vec<AppFocusSegment>>>>>,
+    /// 窗口录制时目标窗口的位置变化，停止录制后按时间换算光标坐标。
+    pub window_bounds_tracks: Mutex<HashMap<String, Arc<Mutex<Vec<WindowBoundsSample>>>>>,
+    pub audio_meters: Mutex<HashMap<String, RecordingProcess>>,
+    pub last_recording_profile: Mutex<Option<RecordingProfile>>,
+    pub export_tasks: RwLock<HashMap<String, ExportTask>>,
+    pub export_queue: Mutex<ExportQueue>,
     pub settings_path: PathBuf,
+    /// 串行化设置的读改写，避免并发保存互相覆盖；持锁期间只读写 settings.json 这类小文件。
+    pub settings_lock: Mutex<()>,
+    /// 上传目标的 OAuth 令牌，与 settings.json 同目录。
+    pub upload_tokens_path: PathBuf,
+    /// 进行中的上传，键为 `项目 id:上传目标`，防止同一目标重复上传。
+    pub active_uploads: Mutex<HashSet<String>>,
+    /// 局域网分享服务，首次创建分享链接时启动，关闭分享设置后释放。
+    pub share_server: Mutex<Option<ShareServer>>,
+    pub project_list_cache: Mutex<HashMap<String, CachedProjectListItem>>,
 }
 
 impl RuntimeState {
-    pub fn new(project_root: PathBuf) -> Self {
-        let settings_path = project_root
-            .parent()
-            .unwrap_or(project_root.as_path())
-            .join("settings.json");
+    pub fn new(
+        project_root: PathBuf,
+        settings_path: PathBuf,
+        max_concurrent_exports: usize,
+    ) -> Self {
         Self {
             project_root,
-            recording_sessions: Mutex::new(HashMap::new()),
+            recording_sessions: RwLock::new(HashMap::new()),
             recording_processes: Mutex::new(HashMap::new()),
             cursor_tracks: Mutex::new(HashMap::new()),
-            export_tasks: Mutex::new(HashMap::new()),
+            key_tracks: Mutex::new(HashMap::new()),
+            app_tracks: Mutex::new(HashMap::new()),
+            window_bounds_tracks: Mutex::new(HashMap::new()),
+            audio_meters: Mutex::new(HashMap::new()),
+            last_recording_profile: Mutex::new(None),
+            export_tasks: RwLock::new(HashMap::new()),
+            export_queue: Mutex::new(ExportQueue::new(max_concurrent_exports)),
+            upload_tokens_path: settings_path.with_file_name(UPLOAD_TOKENS_FILE),
             settings_path,
+            settings_lock: Mutex::new(()),
+            active_uploads: Mutex::new(HashSet::new()),
+            share_server: Mutex::new(None),
+            project_list_cache: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -6,23 +6,29 @@ import { useExportStore } from "./stores/exportStore";
 import { useRecordingStore } from "./stores/recordingStore";
 import { useSettingsStore } from "./stores/settingsStore";
 import { useTauriEvent } from "./hooks/useTauriEvents";
-import type { ExportStatus, ProjectListItem, RecordingStatusEvent } from "./types/project";
+import { formatExportUsage } from "./utils/format";
+import type {
+  ProjectListItem,
+  ProjectListPage,
+  ProjectListQuery
+} from "./types/project";
 
 type AppView = "dashboard" | "studio";
-type StudioMode = "recording" | "review";
 
-type ExportProgressEvent = {
-  taskId: string;
-  status: ExportStatus;
-  progress: number;
-  detail: string;
-};
+const PROJECT_PAGE_SIZE = 24;
+type StudioMode = "recording" | "review";
 
 function App() {
   const [view, setView] = useState<AppView>("dashboard");
   const [studioMode, setStudioMode] = useState<StudioMode>("recording");
   const [activeProjectId, setActiveProjectId] = useState<string | null>(null);
   const [projects, setProjects] = useState<ProjectListItem[]>([]);
+  const [projectTotal, setProjectTotal] = useState(0);
+  const [projectQuery, setProjectQuery] = useState<ProjectListQuery>({
+    page: 1,
+    pageSize: PROJECT_PAGE_SIZE,
+    sort: "updated_desc"
+  });
   const [loadingProjects, setLoadingProjects] = useState(false);
   const [projectListError, setProjectListError] = useState<string | null>(null);
 
@@ -32,38 +38,51 @@ function App() {
   const recordingStatus = useRecordingStore((state) => state.status);
   const recordingProjectId = useRecordingStore((state) => state.projectId);
   const loadSettings = useSettingsStore((state) => state.loadSettings);
+  const setAudioDevices = useSettingsStore((state) => state.setAudioDevices);
 
   const refreshProjects = useCallback(async () => {
     setLoadingProjects(true);
     setProjectListError(null);
     try {
-      const rows = await invoke<ProjectListItem[]>("list_projects");
-      setProjects(rows);
+      const page = await invoke<ProjectListPage>("list_projects", { query: projectQuery });
+      setProjects(page.items);
+      setProjectTotal(page.total);
     } catch (error) {
       setProjectListError(`读取项目列表失败：${String(error)}`);
     } finally {
       setLoadingProjects(false);
     }
-  }, []);
+  }, [projectQuery]);
 
   useEffect(() => {
     void loadSettings().catch(() => undefined);
+  }, [loadSettings]);
+
+  useEffect(() => {
     void refreshProjects();
-  }, [loadSettings, refreshProjects]);
-
-  useTauriEvent<ExportProgressEvent>("export/progress", (event) => {
-    setProgress(
-      event.payload.taskId,
-      event.payload.status,
-      event.payload.progress,
-      event.payload.detail
-    );
+  }, [refreshProjects]);
+
+  useTauriEvent("export/progress", (event) => {
+    const usage = event.payload.usage ?? [];
+    const detail =
+      usage.length > 0
+        ? `${event.payload.detail}：${usage
+            .map((item) =>
+              usage.length > 1 ? `${item.label} ${formatExportUsage(item)}` : formatExportUsage(item)
+            )
+            .join("；")}`
+        : event.payload.detail;
+    setProgress(event.payload.taskId, event.payload.status, event.payload.progress, detail);
   }, [setProgress]);
 
-  useTauriEvent<RecordingStatusEvent>("recording/status", (event) => {
+  useTauriEvent("recording/status", (event) => {
     syncRecording(event.payload);
   }, [syncRecording]);
 
+  useTauriEvent("devices/changed", (event) => {
+    setAudioDevices(event.payload.devices);
+  }, [setAudioDevices]);
+
   useEffect(() => {
     if (recordingStatus === "stopped" && recordingProjectId) {
       setActiveProjectId(recordingProjectId);
@@ -105,6 +124,37 @@ function App() {
     await refreshProjects();
   };
 
+  const duplicateProject = async (projectId: string, copyAssets: boolean) => {
+    await invoke<string>("duplicate_project", { projectId, copyAssets });
+    await refreshProjects();
+  };
+
+  const rebuildProjectIndex = async () => {
+    await invoke<number>("rebuild_project_index");
+    await refreshProjects();
+  };
+
+  const importProjectArchive = async (path: string) => {
+    await invoke<string>("import_project_archive", { path });
+    await refreshProjects();
+  };
+
+  const importVideo = async (path: string) => {
+    const projectId = await invoke<string>("import_video", { path });
+    await refreshProjects();
+    openProjectInStudio(projectId);
+  };
+
+  const updateProjectTags = async (projectId: string, tags: string[]) => {
+    await invoke<string[]>("update_project_tags", { projectId, tags });
+    await refreshProjects();
+  };
+
+  const updateProjectNotes = async (projectId: string, notes: string) => {
+    await invoke("update_project_notes", { projectId, notes });
+    await refreshProjects();
+  };
+
   const renameProject = async (projectId: string, title: string) => {
     await invoke("update_project_title", { projectId, title });
     await refreshProjects();
@@ -115,13 +165,24 @@ function App() {
       {view === "dashboard" ? (
         <DashboardPage
           projects={projects}
+          total={projectTotal}
+          query={projectQuery}
+          onQueryChange={(patch) =>
+            setProjectQuery((current) => ({ ...current, page: 1, ...patch }))
+          }
           loading={loadingProjects}
           error={projectListError}
           onRefresh={refreshProjects}
+          onRebuildIndex={rebuildProjectIndex}
           onNewRecording={startNewRecording}
           onOpenProject={openProjectInStudio}
           onDeleteProject={deleteProject}
           onRenameProject={renameProject}
+          onDuplicateProject={duplicateProject}
+          onUpdateProjectTags={updateProjectTags}
+          onUpdateProjectNotes={updateProjectNotes}
+          onImportArchive={importProjectArchive}
+          onImportVideo={importVideo}
         />
       ) : (
         <StudioPage
@@ -0,0 +1,121 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  AnimationExportOptions,
+  AnimationFormat,
+  TimelineConfig
+} from "../types/project";
+import { Button } from "./ui/Button";
+import { normalizeInvokeError } from "../utils/tauriError";
+
+type AnimationExportPanelProps = {
+  projectId: string;
+  timeline: TimelineConfig;
+};
+
+// 与后端上限一致，超出时后端同样会拒绝。
+const MAX_ANIMATION_SECONDS = 15;
+
+export function AnimationExportPanel({ projectId, timeline }: AnimationExportPanelProps) {
+  const [format, setFormat] = useState<AnimationFormat>("webp");
+  const [startSec, setStartSec] = useState(timeline.trimStartMs / 1000);
+  const [lengthSec, setLengthSec] = useState(5);
+  const [loopCount, setLoopCount] = useState(0);
+  const [width, setWidth] = useState(800);
+  const [pending, setPending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [outputPath, setOutputPath] = useState<string | null>(null);
+
+  const exportAnimation = async () => {
+    setPending(true);
+    setError(null);
+    setOutputPath(null);
+    try {
+      const startMs = Math.round(startSec * 1000);
+      const options: AnimationExportOptions = {
+        format,
+        startMs,
+        endMs: startMs + Math.round(lengthSec * 1000),
+        loopCount,
+        width
+      };
+      const path = await invoke<string>("export_animation", { projectId, options });
+      setOutputPath(path);
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "ANIMATION_RANGE_INVALID", "导出动图失败");
+      setError(parsed.suggestion ? `${parsed.message}：${parsed.suggestion}` : parsed.message);
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <details className="panel stack">
+      <summary>
+        <strong>导出动图</strong>
+      </summary>
+      <p className="note">
+        把一小段操作导出为循环 WebP 或 APNG，体积远小于 GIF，适合嵌入文档；最长{" "}
+        {MAX_ANIMATION_SECONDS} 秒，不含声音。
+      </p>
+      {error && <p className="error">{error}</p>}
+      <div className="stack form-grid-two">
+        <label>
+          格式
+          <select
+            value={format}
+            onChange={(event) => setFormat(event.target.value as AnimationFormat)}
+          >
+            <option value="webp">WebP（有损，体积最小）</option>
+            <option value="apng">APNG（无损）</option>
+          </select>
+        </label>
+        <label>
+          宽度
+          <select value={width} onChange={(event) => setWidth(Number(event.target.value))}>
+            <option value={480}>480px</option>
+            <option value={800}>800px</option>
+            <option value={1280}>1280px</option>
+          </select>
+        </label>
+        <label>
+          起点（秒）
+          <input
+            type="number"
+            min={0}
+            step={0.1}
+            value={startSec}
+            onChange={(event) => setStartSec(Number(event.target.value))}
+          />
+        </label>
+        <label>
+          时长（秒）
+          <input
+            type="number"
+            min={0.5}
+            max={MAX_ANIMATION_SECONDS}
+            step={0.5}
+            value={lengthSec}
+            onChange={(event) => setLengthSec(Number(event.target.value))}
+          />
+        </label>
+        <label>
+          循环次数（0 为无限）
+          <input
+            type="number"
+            min={0}
+            step={1}
+            value={loopCount}
+            onChange={(event) => setLoopCount(Math.max(0, Math.round(Number(event.target.value))))}
+          />
+        </label>
+      </div>
+      <div className="row gap">
+        <Button variant="outline" onClick={() => void exportAnimation()} loading={pending}>
+          导出动图
+        </Button>
+      </div>
+      {outputPath && <p className="muted">已导出：{outputPath}</p>}
+    </details>
+  );
+}
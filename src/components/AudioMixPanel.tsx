@@ -0,0 +1,64 @@
+import { useEffect, useState } from "react";
+import type { AudioMix } from "../types/project";
+
+type AudioMixPanelProps = {
+  mix: AudioMix;
+  onChange: (patch: Partial<AudioMix>) => Promise<void>;
+};
+
+const volumeKeys = [
+  ["masterVolume", "总音量"],
+  ["micVolume", "麦克风"],
+  ["systemVolume", "系统声音"],
+  ["musicVolume", "背景音乐"]
+] as const;
+
+export function AudioMixPanel({ mix, onChange }: AudioMixPanelProps) {
+  const [musicDraft, setMusicDraft] = useState(mix.musicPath ?? "");
+  const [musicError, setMusicError] = useState<string | null>(null);
+  useEffect(() => setMusicDraft(mix.musicPath ?? ""), [mix.musicPath]);
+  const applyPatch = (patch: Partial<AudioMix>) => {
+    void onChange(patch).catch(() => undefined);
+  };
+  const saveMusicPath = () => {
+    setMusicError(null);
+    void onChange({ musicPath: musicDraft.trim() }).catch((error) =>
+      setMusicError(`背景音乐无效：${String(error)}`)
+    );
+  };
+
+  return (
+    <div className="panel stack">
+      <strong>混音</strong>
+      <p className="note">未分轨的录制中麦克风与系统声音共用一条音轨，两者同时录制时取平均音量。</p>
+      <div className="stack form-grid-two">
+        {volumeKeys.map(([key, label]) => (
+          <label key={key}>
+            {label} {Math.round(mix[key] * 100)}%
+            <input
+              type="range"
+              min={0}
+              max={2}
+              step={0.05}
+              value={mix[key]}
+              onChange={(event) =>
+                applyPatch({ [key]: Number(event.target.value) } as Partial<AudioMix>)
+              }
+            />
+          </label>
+        ))}
+        <label>
+          背景音乐文件
+          <input
+            type="text"
+            placeholder="可选，本地音频路径"
+            value={musicDraft}
+            onChange={(event) => setMusicDraft(event.target.value)}
+            onBlur={saveMusicPath}
+          />
+        </label>
+        {musicError && <p className="note">{musicError}</p>}
+      </div>
+    </div>
+  );
+}
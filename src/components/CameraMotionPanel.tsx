@@ -1,4 +1,11 @@
-import type { CameraIntensity, CameraMotionProfile } from "../types/project";
+import type {
+  CameraEasing,
+  CameraFocusMode,
+  CameraIntensity,
+  CameraMode,
+  CameraMotionModel,
+  CameraMotionProfile
+} from "../types/project";
 
 type CameraMotionPanelProps = {
   profile: CameraMotionProfile;
@@ -6,9 +13,13 @@ type CameraMotionPanelProps = {
 };
 
 const intensities: CameraIntensity[] = ["low", "medium", "high"];
+const defaultBezier: CameraEasing = { type: "cubicBezier", x1: 0.25, y1: 0.1, x2: 0.25, y2: 1 };
+const bezierKeys = ["x1", "y1", "x2", "y2"] as const;
 
 export function CameraMotionPanel({ profile, onChange }: CameraMotionPanelProps) {
   const zoomValue = Math.min(Math.max(profile.maxZoom, 1), 1.5);
+  const clickHoldMs = profile.clickHoldMs ?? 800;
+  const easing: CameraEasing = profile.easing ?? { type: "linear" };
   const applyPatch = (patch: Partial<CameraMotionProfile>) => {
     void onChange(patch).catch(() => undefined);
   };
@@ -44,6 +55,70 @@ export function CameraMotionPanel({ profile, onChange }: CameraMotionPanelProps)
             ))}
           </select>
         </label>
+        <label>
+          镜头方式
+          <select
+            value={profile.mode ?? "zoomAndPan"}
+            onChange={(event) => applyPatch({ mode: event.target.value as CameraMode })}
+          >
+            <option value="zoomAndPan">推近并跟随</option>
+            <option value="panOnly">仅平移（1.0x）</option>
+            <option value="zoomOnly">仅推近（居中）</option>
+          </select>
+        </label>
+        <label>
+          取景焦点
+          <select
+            value={profile.focusMode ?? "cursor"}
+            onChange={(event) => applyPatch({ focusMode: event.target.value as CameraFocusMode })}
+          >
+            <option value="cursor">跟随光标</option>
+            <option value="window">框住前台窗口</option>
+          </select>
+        </label>
+        <label>
+          跟随模型
+          <select
+            value={profile.model ?? "hybrid"}
+            onChange={(event) => applyPatch({ model: event.target.value as CameraMotionModel })}
+          >
+            <option value="hybrid">hybrid（死区跟随）</option>
+            <option value="spring">spring（弹簧缓动）</option>
+          </select>
+        </label>
+        <label>
+          缓动曲线
+          <select
+            value={easing.type}
+            onChange={(event) => {
+              const type = event.target.value as CameraEasing["type"];
+              applyPatch({ easing: type === "cubicBezier" ? defaultBezier : { type } });
+            }}
+          >
+            <option value="linear">linear</option>
+            <option value="easeInOut">ease-in-out</option>
+            <option value="cubicBezier">cubic-bezier（自定义）</option>
+          </select>
+        </label>
+        {easing.type === "cubicBezier" && (
+          <div className="form-grid-two">
+            {bezierKeys.map((key) => (
+              <label key={key}>
+                {key}
+                <input
+                  type="number"
+                  step={0.05}
+                  min={key.startsWith("x") ? 0 : undefined}
+                  max={key.startsWith("x") ? 1 : undefined}
+                  value={easing[key]}
+                  onChange={(event) =>
+                    applyPatch({ easing: { ...easing, [key]: Number(event.target.value) } })
+                  }
+                />
+              </label>
+            ))}
+          </div>
+        )}
         <label>
           平滑系数 ({profile.smoothing.toFixed(2)})
           <input
@@ -63,6 +138,7 @@ export function CameraMotionPanel({ profile, onChange }: CameraMotionPanelProps)
             max={1.5}
             step={0.05}
             value={zoomValue}
+            disabled={profile.mode === "panOnly"}
             onChange={(event) => applyPatch({ maxZoom: Number(event.target.value) })}
           />
         </label>
@@ -77,6 +153,31 @@ export function CameraMotionPanel({ profile, onChange }: CameraMotionPanelProps)
             onChange={(event) => applyPatch({ idleThresholdMs: Number(event.target.value) })}
           />
         </label>
+        <label className="toggle-field toggle-field--full">
+          <span className="toggle-field-main">
+            <input
+              type="checkbox"
+              checked={profile.zoomOnClick ?? false}
+              disabled={!profile.enabled}
+              onChange={(event) => applyPatch({ zoomOnClick: event.target.checked })}
+            />
+            <span className="toggle-field-title">点击时推近</span>
+          </span>
+          <span className="toggle-field-hint">鼠标点击处短暂放大，保持后自动缓出。</span>
+        </label>
+        {profile.zoomOnClick && (
+          <label>
+            点击放大保持 ({clickHoldMs}ms)
+            <input
+              type="range"
+              min={200}
+              max={3000}
+              step={100}
+              value={clickHoldMs}
+              onChange={(event) => applyPatch({ clickHoldMs: Number(event.target.value) })}
+            />
+          </label>
+        )}
       </div>
     </div>
   );
@@ -0,0 +1,66 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { AppError, CaptureTestReport, RecordingProfile } from "../types/project";
+import { Button } from "./ui/Button";
+import { normalizeInvokeError } from "../utils/tauriError";
+
+type CaptureTestPanelProps = {
+  profile: RecordingProfile;
+  disabled: boolean;
+};
+
+function describeIssue(issue: AppError) {
+  const parsed = normalizeInvokeError(issue, issue.code, issue.message);
+  return parsed.suggestion ? `${parsed.message}：${parsed.suggestion}` : parsed.message;
+}
+
+export function CaptureTestPanel({ profile, disabled }: CaptureTestPanelProps) {
+  const [report, setReport] = useState<CaptureTestReport | null>(null);
+  const [pending, setPending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const runTest = async () => {
+    setPending(true);
+    setError(null);
+    try {
+      setReport(await invoke<CaptureTestReport>("run_capture_test", { profile }));
+    } catch (e) {
+      setReport(null);
+      setError(describeIssue(normalizeInvokeError(e, "RECORDING_START_FAIL", "试录失败")));
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <div className="panel stack">
+      <strong>试录检查</strong>
+      <p className="note">按当前设置录制 3 秒并检查画面、帧率与音轨，试录文件不会保留。</p>
+      <Button variant="outline" onClick={() => void runTest()} loading={pending} disabled={disabled}>
+        {pending ? "正在试录..." : "开始试录"}
+      </Button>
+      {error && <p className="warn">{error}</p>}
+      {report && (
+        <div className="stack">
+          <p className={report.ready ? "muted" : "warn"}>
+            {report.ready ? "可以开始录制" : "发现会导致录制失败的问题"}
+          </p>
+          {report.width != null && report.height != null && (
+            <p className="muted mono">
+              {report.width}×{report.height}
+              {report.fps != null && ` · ${report.fps.toFixed(1)} fps`}
+              {report.hasAudio
+                ? report.maxVolumeDb != null && ` · 峰值 ${report.maxVolumeDb.toFixed(1)} dB`
+                : " · 无音轨"}
+            </p>
+          )}
+          {[...report.errors, ...report.warnings].map((issue, index) => (
+            <p key={`${issue.code}-${index}`} className={index < report.errors.length ? "warn" : "note"}>
+              {describeIssue(issue)}
+            </p>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,83 @@
+import { useState } from "react";
+import type { TimelineMarker } from "../types/project";
+import { Button } from "./ui/Button";
+import { formatDuration } from "../utils/format";
+import { normalizeInvokeError } from "../utils/tauriError";
+
+type ChaptersPanelProps = {
+  markers: TimelineMarker[];
+  onSuggest: () => Promise<void>;
+  onChange: (markers: TimelineMarker[]) => Promise<void>;
+};
+
+const sourceLabels: Record<TimelineMarker["source"], string> = {
+  manual: "手动",
+  app_switch: "应用切换",
+  transcript: "话题变化"
+};
+
+export function ChaptersPanel({ markers, onSuggest, onChange }: ChaptersPanelProps) {
+  const [pending, setPending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async (action: () => Promise<void>) => {
+    setPending(true);
+    setError(null);
+    try {
+      await action();
+    } catch (e) {
+      setError(normalizeInvokeError(e, "INVALID_TIMELINE", "更新章节失败").message);
+    } finally {
+      setPending(false);
+    }
+  };
+
+  const accept = (markerId: string) =>
+    run(() =>
+      onChange(
+        markers.map((marker) => (marker.id === markerId ? { ...marker, accepted: true } : marker))
+      )
+    );
+  const remove = (markerId: string) =>
+    run(() => onChange(markers.filter((marker) => marker.id !== markerId)));
+
+  return (
+    <div className="panel stack">
+      <div className="row gap">
+        <strong>章节</strong>
+        <Button variant="outline" onClick={() => void run(onSuggest)} loading={pending} className="push-end">
+          自动建议章节
+        </Button>
+      </div>
+      {error && <p className="error">{error}</p>}
+      {markers.length === 0 ? (
+        <p className="note">根据前台应用切换与字幕话题变化生成章节建议，确认后保留。</p>
+      ) : (
+        <ul className="trash-list">
+          {markers.map((marker) => (
+            <li key={marker.id}>
+              <span>
+                {formatDuration(marker.tMs)} {marker.title}
+                <small className="muted">
+                  {" "}
+                  · {sourceLabels[marker.source]}
+                  {marker.accepted ? "" : " · 待确认"}
+                </small>
+              </span>
+              <span className="row gap">
+                {!marker.accepted && (
+                  <Button variant="primary" onClick={() => void accept(marker.id)} disabled={pending}>
+                    确认
+                  </Button>
+                )}
+                <Button variant="outline" onClick={() => void remove(marker.id)} disabled={pending}>
+                  删除
+                </Button>
+              </span>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,79 @@
+import { useEffect, useState } from "react";
+import type { ColorGrade } from "../types/project";
+import { Button } from "./ui/Button";
+
+type ColorGradePanelProps = {
+  grade: ColorGrade;
+  onChange: (patch: Partial<ColorGrade>) => Promise<void>;
+};
+
+const adjustKeys = [
+  ["brightness", "亮度", -0.5, 0.5, 0],
+  ["contrast", "对比度", 0.5, 2, 1],
+  ["saturation", "饱和度", 0, 2, 1],
+  ["gamma", "Gamma", 0.5, 2, 1]
+] as const;
+
+export function ColorGradePanel({ grade, onChange }: ColorGradePanelProps) {
+  const [lutDraft, setLutDraft] = useState(grade.lutPath ?? "");
+  const [lutError, setLutError] = useState<string | null>(null);
+  useEffect(() => setLutDraft(grade.lutPath ?? ""), [grade.lutPath]);
+  const applyPatch = (patch: Partial<ColorGrade>) => {
+    void onChange(patch).catch(() => undefined);
+  };
+  const saveLutPath = () => {
+    setLutError(null);
+    void onChange({ lutPath: lutDraft.trim() }).catch((error) =>
+      setLutError(`LUT 无效：${String(error)}`)
+    );
+  };
+
+  return (
+    <div className="panel stack">
+      <strong>调色</strong>
+      <p className="note">笔记本屏幕录制偏灰时可略微提高对比度与饱和度，LUT 在基础调整之后套用。</p>
+      <div className="stack form-grid-two">
+        {adjustKeys.map(([key, label, min, max]) => (
+          <label key={key}>
+            {label} {grade[key].toFixed(2)}
+            <input
+              type="range"
+              min={min}
+              max={max}
+              step={0.05}
+              value={grade[key]}
+              onChange={(event) =>
+                applyPatch({ [key]: Number(event.target.value) } as Partial<ColorGrade>)
+              }
+            />
+          </label>
+        ))}
+        <label>
+          LUT 文件
+          <input
+            type="text"
+            placeholder="可选，本地 .cube 路径"
+            value={lutDraft}
+            onChange={(event) => setLutDraft(event.target.value)}
+            onBlur={saveLutPath}
+          />
+        </label>
+        {lutError && <p className="note">{lutError}</p>}
+      </div>
+      <div className="row gap">
+        <Button
+          variant="ghost"
+          onClick={() =>
+            applyPatch(
+              Object.fromEntries(
+                adjustKeys.map(([key, , , , neutral]) => [key, neutral])
+              ) as Partial<ColorGrade>
+            )
+          }
+        >
+          重置调整
+        </Button>
+      </div>
+    </div>
+  );
+}
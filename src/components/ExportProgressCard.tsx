@@ -5,20 +5,23 @@ type ExportProgressCardProps = {
   progress: number;
   detail: string;
   onRetry: () => Promise<void>;
+  onCancel?: () => Promise<void>;
 };
 
 export function ExportProgressCard({
   status,
   progress,
   detail,
-  onRetry
+  onRetry,
+  onCancel
 }: ExportProgressCardProps) {
   const statusText = {
     queued: "排队中",
     running: "导出中",
     fallback: "软编回退",
     success: "已完成",
-    failed: "失败"
+    failed: "失败",
+    cancelled: "已取消"
   } as const;
 
   const percent = Math.max(0, Math.min(100, Math.round(progress)));
@@ -40,6 +43,11 @@ export function ExportProgressCard({
           重试导出
         </button>
       )}
+      {onCancel && (status === "queued" || status === "running" || status === "fallback") && (
+        <button onClick={() => void onCancel()}>
+          取消导出
+        </button>
+      )}
     </div>
   );
 }
@@ -0,0 +1,87 @@
+import type { KeystrokeOverlay, OverlayPosition } from "../types/project";
+
+type KeystrokeOverlayPanelProps = {
+  overlay: KeystrokeOverlay;
+  hasKeyTrack: boolean;
+  onChange: (patch: Partial<KeystrokeOverlay>) => Promise<void>;
+};
+
+export function KeystrokeOverlayPanel({
+  overlay,
+  hasKeyTrack,
+  onChange
+}: KeystrokeOverlayPanelProps) {
+  const applyPatch = (patch: Partial<KeystrokeOverlay>) => {
+    void onChange(patch).catch(() => undefined);
+  };
+
+  return (
+    <div className="panel stack">
+      <strong>按键提示</strong>
+      <p className="note">
+        {hasKeyTrack
+          ? "导出时在画面中显示快捷键，如 Ctrl+Shift+P。"
+          : "本次录制未记录按键，请在录制设置中开启按键记录。"}
+      </p>
+      <div className="stack form-grid-two">
+        <label className="toggle-field toggle-field--full">
+          <span className="toggle-field-main">
+            <input
+              type="checkbox"
+              checked={overlay.enabled}
+              disabled={!hasKeyTrack}
+              onChange={(event) => applyPatch({ enabled: event.target.checked })}
+            />
+            <span className="toggle-field-title">显示按键提示</span>
+          </span>
+          <span className="toggle-field-hint">适合以键盘操作为主的教程。</span>
+        </label>
+        <label>
+          位置
+          <select
+            value={overlay.position}
+            onChange={(event) =>
+              applyPatch({ position: event.target.value as OverlayPosition })
+            }
+          >
+            <option value="bottom">底部居中</option>
+            <option value="top">顶部居中</option>
+          </select>
+        </label>
+        <label>
+          字号 {overlay.fontSize}px
+          <input
+            type="range"
+            min={16}
+            max={96}
+            step={2}
+            value={overlay.fontSize}
+            onChange={(event) => applyPatch({ fontSize: Number(event.target.value) })}
+          />
+        </label>
+        <label>
+          底色不透明度 {Math.round(overlay.opacity * 100)}%
+          <input
+            type="range"
+            min={0}
+            max={1}
+            step={0.05}
+            value={overlay.opacity}
+            onChange={(event) => applyPatch({ opacity: Number(event.target.value) })}
+          />
+        </label>
+        <label>
+          停留 {(overlay.holdMs / 1000).toFixed(1)} 秒
+          <input
+            type="range"
+            min={300}
+            max={5000}
+            step={100}
+            value={overlay.holdMs}
+            onChange={(event) => applyPatch({ holdMs: Number(event.target.value) })}
+          />
+        </label>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,76 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { ManifestRevision } from "../types/project";
+import { Button } from "./ui/Button";
+import { formatDateTime, formatDuration } from "../utils/format";
+import { normalizeInvokeError } from "../utils/tauriError";
+
+type ManifestRevisionsPanelProps = {
+  projectId: string;
+  onRestore: (revisionId: string) => Promise<void>;
+};
+
+export function ManifestRevisionsPanel({ projectId, onRestore }: ManifestRevisionsPanelProps) {
+  const [revisions, setRevisions] = useState<ManifestRevision[]>([]);
+  const [pending, setPending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = async () => {
+    try {
+      setRevisions(await invoke<ManifestRevision[]>("list_manifest_revisions", { projectId }));
+    } catch (e) {
+      setError(normalizeInvokeError(e, "IO_ERROR", "读取历史版本失败").message);
+    }
+  };
+
+  const restore = async (revision: ManifestRevision) => {
+    if (!window.confirm(`回滚到 ${formatDateTime(revision.savedAt)} 保存的版本？当前配置会另存为新版本。`)) {
+      return;
+    }
+    setPending(true);
+    setError(null);
+    try {
+      await onRestore(revision.revisionId);
+      await refresh();
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "REVISION_NOT_FOUND", "回滚失败");
+      setError(parsed.suggestion ?? parsed.message);
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <details
+      className="panel stack"
+      onToggle={(event) => {
+        if ((event.target as HTMLDetailsElement).open) void refresh();
+      }}
+    >
+      <summary>
+        <strong>历史版本</strong>
+      </summary>
+      {error && <p className="error">{error}</p>}
+      {revisions.length === 0 ? (
+        <p className="note">暂无保存记录。</p>
+      ) : (
+        <ul className="trash-list">
+          {revisions.map((revision) => (
+            <li key={revision.revisionId}>
+              <span>
+                {formatDateTime(revision.savedAt)}
+                <small className="muted">
+                  {" "}
+                  · 裁剪 {formatDuration(revision.trimStartMs)}–{formatDuration(revision.trimEndMs)}
+                </small>
+              </span>
+              <Button variant="outline" onClick={() => void restore(revision)} disabled={pending}>
+                回滚
+              </Button>
+            </li>
+          ))}
+        </ul>
+      )}
+    </details>
+  );
+}
@@ -1,16 +1,21 @@
 import { useState, useRef } from "react";
-import { convertFileSrc } from "@tauri-apps/api/core";
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
 import { ProjectListItem, ProjectStatus } from "../types/project";
 import { Button } from "./ui/Button";
 import { Icons } from "./ui/Icons";
 import { StatusChip } from "./ui/StatusChip";
 import { formatDuration, formatDateTime } from "../utils/format";
+import { normalizeInvokeError } from "../utils/tauriError";
 
 type ProjectCardProps = {
   project: ProjectListItem;
   onOpen: (projectId: string) => void;
   onDelete: (projectId: string) => Promise<void>;
   onRename: (projectId: string, title: string) => Promise<void>;
+  onDuplicate: (projectId: string, copyAssets: boolean) => Promise<void>;
+  onTagsChange: (projectId: string, tags: string[]) => Promise<void>;
+  onNotesChange: (projectId: string, notes: string) => Promise<void>;
+  onTagClick: (tag: string) => void;
 };
 
 const statusMap: Record<ProjectStatus, string> = {
@@ -33,7 +38,11 @@ export function ProjectCard({
   project,
   onOpen,
   onDelete,
-  onRename
+  onRename,
+  onDuplicate,
+  onTagsChange,
+  onNotesChange,
+  onTagClick
 }: ProjectCardProps) {
   const [isEditing, setIsEditing] = useState(false);
   const [titleDraft, setTitleDraft] = useState("");
@@ -65,7 +74,7 @@ export function ProjectCard({
   };
 
   const handleDelete = async () => {
-    if (!window.confirm("确认删除该项目及其录制文件吗？此操作不可恢复。")) return;
+    if (!window.confirm("确认删除该项目吗？项目会移入回收站，30 天内可恢复。")) return;
     setPending(true);
     try {
       await onDelete(project.projectId);
@@ -76,6 +85,73 @@ export function ProjectCard({
     }
   };
 
+  const handleDuplicate = async () => {
+    const copyAssets = window.confirm(
+      "是否同时复制录制素材？\n确定：复制素材，副本独立于原项目。\n取消：共享原项目素材，节省磁盘空间。"
+    );
+    setPending(true);
+    try {
+      await onDuplicate(project.projectId, copyAssets);
+      setError("项目复制成功");
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setPending(false);
+    }
+  };
+
+  const handleArchive = async () => {
+    const dest = window.prompt("打包保存路径（.focuslens）", `${title}.focuslens`);
+    if (!dest?.trim()) return;
+    const includeRenders = project.hasExport && window.confirm("是否一并打包已导出的成片？");
+    setPending(true);
+    setError(null);
+    try {
+      const path = await invoke<string>("archive_project", {
+        projectId: project.projectId,
+        dest: dest.trim(),
+        includeRenders
+      });
+      setError(`项目打包成功：${path}`);
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "ARCHIVE_IO_ERROR", "项目打包失败");
+      setError(parsed.suggestion ?? parsed.message);
+    } finally {
+      setPending(false);
+    }
+  };
+
+  const handleEditTags = async () => {
+    const draft = window.prompt("项目标签（用逗号分隔）", project.tags.join(", "));
+    if (draft === null) return;
+    const tags = draft.split(/[,，]/).map((tag) => tag.trim()).filter(Boolean);
+    setPending(true);
+    setError(null);
+    try {
+      await onTagsChange(project.projectId, tags);
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "INVALID_TAG", "标签保存失败");
+      setError(parsed.suggestion ?? parsed.message);
+    } finally {
+      setPending(false);
+    }
+  };
+
+  const handleEditNotes = async () => {
+    const draft = window.prompt("项目备注", project.notes ?? "");
+    if (draft === null) return;
+    setPending(true);
+    setError(null);
+    try {
+      await onNotesChange(project.projectId, draft);
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "INVALID_NOTES", "备注保存失败");
+      setError(parsed.suggestion ?? parsed.message);
+    } finally {
+      setPending(false);
+    }
+  };
+
   const copyExportPath = async (path: string) => {
     try {
       await navigator.clipboard.writeText(path);
@@ -86,6 +162,15 @@ export function ProjectCard({
     }
   };
 
+  const runExportShellCommand = async (command: "reveal_export_in_folder" | "open_export") => {
+    try {
+      await invoke(command, { projectId: project.projectId });
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "SHELL_OPEN_FAILED", "无法打开导出文件");
+      setError(parsed.suggestion ?? parsed.message);
+    }
+  };
+
   const downloadExport = (path: string) => {
     try {
       const href = convertFileSrc(path);
@@ -170,6 +255,32 @@ export function ProjectCard({
         </div>
       )}
 
+      <div className="project-tags">
+        {project.tags.map((tag) => (
+          <button key={tag} type="button" className="project-tag" onClick={() => onTagClick(tag)}>
+            #{tag}
+          </button>
+        ))}
+        <button
+          type="button"
+          className="project-tag project-tag--edit"
+          onClick={() => void handleEditTags()}
+          disabled={pending}
+        >
+          {project.tags.length ? "编辑标签" : "+ 添加标签"}
+        </button>
+        <button
+          type="button"
+          className="project-tag project-tag--edit"
+          onClick={() => void handleEditNotes()}
+          disabled={pending}
+        >
+          {project.notes ? "编辑备注" : "+ 添加备注"}
+        </button>
+      </div>
+
+      {project.notes && <p className="note project-notes">{project.notes}</p>}
+
       {error && <p className={`note project-feedback ${isFeedbackPositive ? "project-feedback-success" : "project-feedback-error"}`}>{error}</p>}
 
       <div className="project-meta">
@@ -206,8 +317,34 @@ export function ProjectCard({
                 onClick={() => void copyExportPath(project.exportPath!)}
                 title="复制路径"
               />
+              <Button
+                variant="outline"
+                icon={<Icons.Folder size={16} />}
+                onClick={() => void runExportShellCommand("reveal_export_in_folder")}
+                title="在文件夹中显示"
+              />
+              <Button
+                variant="outline"
+                icon={<Icons.Resume size={16} />}
+                onClick={() => void runExportShellCommand("open_export")}
+                title="播放成片"
+              />
             </>
           )}
+          <Button
+            variant="outline"
+            icon={<Icons.Copy size={16} />}
+            onClick={() => void handleDuplicate()}
+            disabled={pending}
+            title="复制项目"
+          />
+          <Button
+            variant="outline"
+            icon={<Icons.Export size={16} />}
+            onClick={() => void handleArchive()}
+            disabled={pending}
+            title="打包项目"
+          />
           <Button
             variant="danger"
             icon={<Icons.Delete size={16} />}
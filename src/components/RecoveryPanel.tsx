@@ -0,0 +1,81 @@
+import { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { RecoverableProject } from "../types/project";
+import { Button } from "./ui/Button";
+import { normalizeInvokeError } from "../utils/tauriError";
+
+type RecoveryPanelProps = {
+  onRecovered: () => Promise<void>;
+};
+
+export function RecoveryPanel({ onRecovered }: RecoveryPanelProps) {
+  const [items, setItems] = useState<RecoverableProject[]>([]);
+  const [pendingId, setPendingId] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setItems(await invoke<RecoverableProject[]>("recover_projects"));
+    } catch {
+      setItems([]);
+    }
+  }, []);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  const runRecovery = async (command: "recover_project" | "repair_recording", projectId: string) => {
+    setPendingId(projectId);
+    setError(null);
+    try {
+      await invoke(command, { projectId });
+      await refresh();
+      await onRecovered();
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "RECOVERY_FAILED", "恢复录制失败");
+      setError(parsed.suggestion ? `${parsed.message}：${parsed.suggestion}` : parsed.message);
+    } finally {
+      setPendingId(null);
+    }
+  };
+
+  if (items.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="panel stack">
+      <strong>检测到 {items.length} 个未正常结束的录制</strong>
+      {error && <p className="error">{error}</p>}
+      <ul className="trash-list">
+        {items.map((item) => (
+          <li key={item.projectId}>
+            <span>
+              <span className="mono">{item.projectId.slice(0, 8)}</span>
+              <small className="muted"> · {item.reason}</small>
+            </span>
+            <span className="row gap">
+              <Button
+                variant="primary"
+                onClick={() => void runRecovery("recover_project", item.projectId)}
+                loading={pendingId === item.projectId}
+                disabled={pendingId !== null}
+              >
+                恢复
+              </Button>
+              <Button
+                variant="outline"
+                onClick={() => void runRecovery("repair_recording", item.projectId)}
+                disabled={pendingId !== null}
+                title="文件无法打开时，重新封装录制文件"
+              >
+                修复录制
+              </Button>
+            </span>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
@@ -0,0 +1,139 @@
+import type { SceneBackground, SceneStyle } from "../types/project";
+
+type ScenePanelProps = {
+  scene: SceneStyle;
+  onChange: (patch: Partial<SceneStyle>) => Promise<void>;
+};
+
+const defaultBackgrounds: Record<SceneBackground["type"], SceneBackground> = {
+  color: { type: "color", color: "#111827" },
+  gradient: { type: "gradient", from: "#4f46e5", to: "#db2777" },
+  wallpaper: { type: "wallpaper", path: "" }
+};
+
+export function ScenePanel({ scene, onChange }: ScenePanelProps) {
+  const background = scene.background;
+  const applyPatch = (patch: Partial<SceneStyle>) => {
+    void onChange(patch).catch(() => undefined);
+  };
+
+  return (
+    <div className="panel stack">
+      <strong>画面外观</strong>
+      <p className="note">为录屏加背景、圆角和投影，导出时合成。</p>
+      <div className="stack form-grid-two">
+        <label className="toggle-field toggle-field--full">
+          <span className="toggle-field-main">
+            <input
+              type="checkbox"
+              checked={scene.enabled}
+              onChange={(event) => applyPatch({ enabled: event.target.checked })}
+            />
+            <span className="toggle-field-title">启用画面外观</span>
+          </span>
+          <span className="toggle-field-hint">
+            内容缩进到背景之上，适合产品演示与社交媒体发布。
+          </span>
+        </label>
+        <label>
+          背景
+          <select
+            value={background.type}
+            onChange={(event) => {
+              const type = event.target.value as SceneBackground["type"];
+              if (type !== "wallpaper") {
+                applyPatch({ background: defaultBackgrounds[type] });
+              }
+            }}
+          >
+            <option value="color">纯色</option>
+            <option value="gradient">渐变</option>
+            <option value="wallpaper" disabled={background.type !== "wallpaper"}>
+              壁纸
+            </option>
+          </select>
+        </label>
+        {background.type === "color" && (
+          <label>
+            颜色
+            <input
+              type="color"
+              value={background.color}
+              onChange={(event) =>
+                applyPatch({ background: { type: "color", color: event.target.value } })
+              }
+            />
+          </label>
+        )}
+        {background.type === "gradient" && (
+          <label>
+            渐变色
+            <span className="row gap">
+              <input
+                type="color"
+                value={background.from}
+                onChange={(event) =>
+                  applyPatch({ background: { ...background, from: event.target.value } })
+                }
+              />
+              <input
+                type="color"
+                value={background.to}
+                onChange={(event) =>
+                  applyPatch({ background: { ...background, to: event.target.value } })
+                }
+              />
+            </span>
+          </label>
+        )}
+        <label>
+          壁纸路径
+          <input
+            type="text"
+            placeholder="本地图片路径，回车应用"
+            defaultValue={background.type === "wallpaper" ? background.path : ""}
+            onKeyDown={(event) => {
+              const path = event.currentTarget.value.trim();
+              if (event.key === "Enter" && path) {
+                applyPatch({ background: { type: "wallpaper", path } });
+              }
+            }}
+          />
+        </label>
+        <label>
+          圆角 {scene.cornerRadius}px
+          <input
+            type="range"
+            min={0}
+            max={96}
+            step={2}
+            value={scene.cornerRadius}
+            onChange={(event) => applyPatch({ cornerRadius: Number(event.target.value) })}
+          />
+        </label>
+        <label>
+          留白 {scene.padding}px
+          <input
+            type="range"
+            min={0}
+            max={240}
+            step={8}
+            value={scene.padding}
+            onChange={(event) => applyPatch({ padding: Number(event.target.value) })}
+          />
+        </label>
+        <label>
+          投影 {Math.round(scene.shadow * 100)}%
+          <input
+            type="range"
+            min={0}
+            max={1}
+            step={0.05}
+            value={scene.shadow}
+            onChange={(event) => applyPatch({ shadow: Number(event.target.value) })}
+          />
+        </label>
+      </div>
+    </div>
+  );
+}
@@ -1,16 +1,66 @@
-import type { AspectRatio, TimelineConfig } from "../types/project";
+import { useEffect, useState } from "react";
+import type {
+  AspectRatio,
+  CursorSpotlight,
+  CursorStyle,
+  FitMode,
+  SilenceCutMode,
+  TimelineConfig,
+  TimelineSegment
+} from "../types/project";
 
 type TimelineEditorProps = {
   timeline: TimelineConfig;
   onChange: (patch: Partial<TimelineConfig>) => Promise<void>;
+  onDetectSilence?: () => Promise<void>;
 };
 
 const aspectOptions: AspectRatio[] = ["16:9", "9:16", "1:1"];
+const segmentSpeeds = [0.5, 0.75, 1, 1.5, 2, 3, 4];
+const defaultSpotlight: CursorSpotlight = { enabled: false, radius: 160, dim: 0.55, feather: 60 };
 
-export function TimelineEditor({ timeline, onChange }: TimelineEditorProps) {
+export function TimelineEditor({ timeline, onChange, onDetectSilence }: TimelineEditorProps) {
+  const [detectingSilence, setDetectingSilence] = useState(false);
+  const [segmentDraft, setSegmentDraft] = useState<TimelineSegment[]>(timeline.segments ?? []);
+  const [segmentError, setSegmentError] = useState<string | null>(null);
+  useEffect(() => {
+    setSegmentDraft(timeline.segments ?? []);
+  }, [timeline.segments]);
+  const updateSegment = (index: number, patch: Partial<TimelineSegment>) => {
+    setSegmentDraft((prev) =>
+      prev.map((segment, current) => (current === index ? { ...segment, ...patch } : segment))
+    );
+  };
+  const addSegment = () => {
+    const last = segmentDraft[segmentDraft.length - 1];
+    const startMs = last ? last.endMs + 1000 : timeline.trimStartMs;
+    setSegmentDraft((prev) => [...prev, { startMs, endMs: startMs + 5000 }]);
+  };
+  const saveSegments = () => {
+    const sorted = [...segmentDraft].sort((a, b) => a.startMs - b.startMs);
+    setSegmentError(null);
+    void onChange({ segments: sorted }).catch((error) =>
+      setSegmentError(`片段无效：${String(error)}`)
+    );
+  };
+  const silentRanges = timeline.silentRanges ?? [];
+  const spotlight = timeline.spotlight ?? defaultSpotlight;
+  const silentSeconds = silentRanges.reduce(
+    (total, range) => total + (range.endMs - range.startMs) / 1000,
+    0
+  );
   const applyPatch = (patch: Partial<TimelineConfig>) => {
     void onChange(patch).catch(() => undefined);
   };
+  const detectSilence = () => {
+    if (!onDetectSilence) {
+      return;
+    }
+    setDetectingSilence(true);
+    void onDetectSilence()
+      .catch(() => undefined)
+      .finally(() => setDetectingSilence(false));
+  };
 
   return (
     <div className="panel stack">
@@ -60,6 +110,17 @@ export function TimelineEditor({ timeline, onChange }: TimelineEditorProps) {
             ))}
           </select>
         </label>
+        <label>
+          画幅适配
+          <select
+            value={timeline.fitMode ?? "crop"}
+            onChange={(event) => applyPatch({ fitMode: event.target.value as FitMode })}
+          >
+            <option value="crop">裁剪铺满</option>
+            <option value="pad">黑边留白</option>
+            <option value="blur_background">模糊背景</option>
+          </select>
+        </label>
         <label className="toggle-field toggle-field--full">
           <span className="toggle-field-main">
             <input
@@ -70,9 +131,215 @@ export function TimelineEditor({ timeline, onChange }: TimelineEditorProps) {
             <span className="toggle-field-title">光标高亮</span>
           </span>
           <span className="toggle-field-hint">
-            导出时突出鼠标位置，适合教程和演示类内容。
+            导出时按光标轨迹合成光标，适合教程和演示类内容。
           </span>
         </label>
+        {timeline.cursorHighlightEnabled && (
+          <>
+            <label>
+              光标样式
+              <select
+                value={timeline.cursorStyle ?? "halo"}
+                onChange={(event) => applyPatch({ cursorStyle: event.target.value as CursorStyle })}
+              >
+                <option value="halo">光晕</option>
+                <option value="arrow">箭头</option>
+              </select>
+            </label>
+            <label>
+              光标尺寸 {timeline.cursorSize ?? 36}px
+              <input
+                type="range"
+                min={12}
+                max={128}
+                step={4}
+                value={timeline.cursorSize ?? 36}
+                onChange={(event) => applyPatch({ cursorSize: Number(event.target.value) })}
+              />
+            </label>
+          </>
+        )}
+        <label className="toggle-field toggle-field--full">
+          <span className="toggle-field-main">
+            <input
+              type="checkbox"
+              checked={spotlight.enabled}
+              onChange={(event) => applyPatch({ spotlight: { ...spotlight, enabled: event.target.checked } })}
+            />
+            <span className="toggle-field-title">光标聚光灯</span>
+          </span>
+          <span className="toggle-field-hint">压暗光标周围以外的画面，引导观众视线。</span>
+        </label>
+        {spotlight.enabled && (
+          <>
+            <label>
+              聚光半径 {spotlight.radius}px
+              <input
+                type="range"
+                min={40}
+                max={600}
+                step={10}
+                value={spotlight.radius}
+                onChange={(event) => applyPatch({ spotlight: { ...spotlight, radius: Number(event.target.value) } })}
+              />
+            </label>
+            <label>
+              压暗程度 {Math.round(spotlight.dim * 100)}%
+              <input
+                type="range"
+                min={0.1}
+                max={0.9}
+                step={0.05}
+                value={spotlight.dim}
+                onChange={(event) => applyPatch({ spotlight: { ...spotlight, dim: Number(event.target.value) } })}
+              />
+            </label>
+            <label>
+              边缘羽化 {spotlight.feather}px
+              <input
+                type="range"
+                min={0}
+                max={300}
+                step={10}
+                value={spotlight.feather}
+                onChange={(event) => applyPatch({ spotlight: { ...spotlight, feather: Number(event.target.value) } })}
+              />
+            </label>
+          </>
+        )}
+        <label className="toggle-field toggle-field--full">
+          <span className="toggle-field-main">
+            <input
+              type="checkbox"
+              checked={timeline.autoCutSilence ?? false}
+              onChange={(event) => {
+                applyPatch({ autoCutSilence: event.target.checked });
+                if (event.target.checked && silentRanges.length === 0) {
+                  detectSilence();
+                }
+              }}
+            />
+            <span className="toggle-field-title">自动剪除静音</span>
+          </span>
+          <span className="toggle-field-hint">
+            {detectingSilence
+              ? "正在分析音轨…"
+              : `已检测到 ${silentRanges.length} 段静音，共 ${silentSeconds.toFixed(1)} 秒。`}
+          </span>
+        </label>
+        {timeline.autoCutSilence && (
+          <>
+            <label>
+              静音处理
+              <select
+                value={timeline.silenceCutMode ?? "drop"}
+                onChange={(event) =>
+                  applyPatch({ silenceCutMode: event.target.value as SilenceCutMode })
+                }
+              >
+                <option value="drop">直接剪掉</option>
+                <option value="speed_up">4 倍速快进</option>
+              </select>
+            </label>
+            <label>
+              静音区间
+              <button type="button" onClick={detectSilence} disabled={detectingSilence}>
+                {detectingSilence ? "分析中" : "重新分析"}
+              </button>
+            </label>
+          </>
+        )}
+        <label className="toggle-field toggle-field--full">
+          <span className="toggle-field-main">
+            <input
+              type="checkbox"
+              checked={timeline.idleSpeedupEnabled ?? false}
+              onChange={(event) => applyPatch({ idleSpeedupEnabled: event.target.checked })}
+            />
+            <span className="toggle-field-title">空闲片段加速</span>
+          </span>
+          <span className="toggle-field-hint">
+            长时间无光标移动和按键的片段快进播放，并在右上角标注倍速。
+          </span>
+        </label>
+        {timeline.idleSpeedupEnabled && (
+          <>
+            <label>
+              快进倍速
+              <select
+                value={timeline.idleSpeed ?? 4}
+                onChange={(event) => applyPatch({ idleSpeed: Number(event.target.value) })}
+              >
+                <option value={2}>2×</option>
+                <option value={4}>4×</option>
+                <option value={8}>8×</option>
+                <option value={16}>16×</option>
+              </select>
+            </label>
+            <label>
+              空闲判定 {((timeline.idleMinMs ?? 3000) / 1000).toFixed(0)} 秒
+              <input
+                type="range"
+                min={1000}
+                max={30000}
+                step={1000}
+                value={timeline.idleMinMs ?? 3000}
+                onChange={(event) => applyPatch({ idleMinMs: Number(event.target.value) })}
+              />
+            </label>
+          </>
+        )}
+        <div className="stack toggle-field--full">
+          <span className="toggle-field-title">保留片段</span>
+          <span className="toggle-field-hint">
+            {segmentDraft.length === 0
+              ? "未设置时导出整个裁剪区间；添加片段可剪掉中间的失误。"
+              : "只导出以下片段（ms），片段之间的内容会被剪掉；可为每段单独设置倍速。"}
+          </span>
+          {segmentDraft.map((segment, index) => (
+            <div key={index} className="row gap">
+              <input
+                type="number"
+                min={0}
+                value={segment.startMs}
+                onChange={(event) => updateSegment(index, { startMs: Number(event.target.value) })}
+              />
+              <input
+                type="number"
+                min={0}
+                value={segment.endMs}
+                onChange={(event) => updateSegment(index, { endMs: Number(event.target.value) })}
+              />
+              <select
+                value={segment.speed ?? 1}
+                onChange={(event) => updateSegment(index, { speed: Number(event.target.value) })}
+              >
+                {segmentSpeeds.map((speed) => (
+                  <option key={speed} value={speed}>
+                    {speed}x
+                  </option>
+                ))}
+              </select>
+              <button
+                type="button"
+                onClick={() =>
+                  setSegmentDraft((prev) => prev.filter((_, current) => current !== index))
+                }
+              >
+                删除
+              </button>
+            </div>
+          ))}
+          <div className="row gap">
+            <button type="button" onClick={addSegment}>
+              添加片段
+            </button>
+            <button type="button" onClick={saveSegments}>
+              应用片段
+            </button>
+          </div>
+          {segmentError && <p className="note">{segmentError}</p>}
+        </div>
       </div>
     </div>
   );
@@ -0,0 +1,66 @@
+import { useEffect, useState } from "react";
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
+import type { TimelineThumbnail } from "../types/project";
+import { formatDuration } from "../utils/format";
+
+type TimelineFilmstripProps = {
+  projectId: string;
+  rawRecordingPath?: string;
+  onSeek: (tMs: number) => void;
+};
+
+const THUMBNAIL_COUNT = 24;
+
+export function TimelineFilmstrip({ projectId, rawRecordingPath, onSeek }: TimelineFilmstripProps) {
+  const [thumbnails, setThumbnails] = useState<TimelineThumbnail[]>([]);
+  const [loading, setLoading] = useState(false);
+
+  useEffect(() => {
+    if (!rawRecordingPath) {
+      setThumbnails([]);
+      return;
+    }
+    let cancelled = false;
+    setLoading(true);
+    void invoke<TimelineThumbnail[]>("generate_timeline_thumbnails", {
+      projectId,
+      count: THUMBNAIL_COUNT
+    })
+      .then((rows) => {
+        if (!cancelled) setThumbnails(rows);
+      })
+      .catch(() => {
+        if (!cancelled) setThumbnails([]);
+      })
+      .finally(() => {
+        if (!cancelled) setLoading(false);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [projectId, rawRecordingPath]);
+
+  if (!rawRecordingPath) {
+    return null;
+  }
+
+  return (
+    <div className="timeline-filmstrip">
+      {loading && thumbnails.length === 0 ? (
+        <p className="note">正在生成缩略图…</p>
+      ) : (
+        thumbnails.map((thumb) => (
+          <button
+            key={thumb.path}
+            type="button"
+            className="timeline-filmstrip-frame"
+            title={formatDuration(thumb.tMs)}
+            onClick={() => onSeek(thumb.tMs)}
+          >
+            <img src={convertFileSrc(thumb.path)} alt={formatDuration(thumb.tMs)} />
+          </button>
+        ))
+      )}
+    </div>
+  );
+}
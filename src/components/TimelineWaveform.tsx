@@ -0,0 +1,78 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { AudioWaveform } from "../types/project";
+
+type TimelineWaveformProps = {
+  projectId: string;
+  rawRecordingPath?: string;
+  trimStartMs: number;
+  trimEndMs: number;
+  onSeek: (tMs: number) => void;
+};
+
+const SAMPLES_PER_SECOND = 50;
+const WAVEFORM_HEIGHT = 48;
+
+export function TimelineWaveform({
+  projectId,
+  rawRecordingPath,
+  trimStartMs,
+  trimEndMs,
+  onSeek
+}: TimelineWaveformProps) {
+  const [waveform, setWaveform] = useState<AudioWaveform | null>(null);
+
+  useEffect(() => {
+    if (!rawRecordingPath) {
+      setWaveform(null);
+      return;
+    }
+    let cancelled = false;
+    void invoke<AudioWaveform>("get_audio_waveform", {
+      projectId,
+      samplesPerSecond: SAMPLES_PER_SECOND
+    })
+      .then((data) => {
+        if (!cancelled) setWaveform(data);
+      })
+      .catch(() => {
+        if (!cancelled) setWaveform(null);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [projectId, rawRecordingPath]);
+
+  if (!waveform || waveform.peaks.length === 0) {
+    return null;
+  }
+
+  const count = waveform.peaks.length;
+  const durationMs = (count / waveform.samplesPerSecond) * 1000;
+  const half = WAVEFORM_HEIGHT / 2;
+  const path = waveform.peaks
+    .map((peak, index) => `M${index} ${half - peak * half}V${half + peak * half}`)
+    .join("");
+  const trimEnd = trimEndMs > trimStartMs ? trimEndMs : durationMs;
+  const toX = (tMs: number) => (Math.min(tMs, durationMs) / durationMs) * count;
+
+  return (
+    <svg
+      className="timeline-waveform"
+      viewBox={`0 0 ${count} ${WAVEFORM_HEIGHT}`}
+      preserveAspectRatio="none"
+      onClick={(event) => {
+        const rect = event.currentTarget.getBoundingClientRect();
+        onSeek(((event.clientX - rect.left) / rect.width) * durationMs);
+      }}
+    >
+      <rect
+        className="timeline-waveform-kept"
+        x={toX(trimStartMs)}
+        width={Math.max(0, toX(trimEnd) - toX(trimStartMs))}
+        height={WAVEFORM_HEIGHT}
+      />
+      <path d={path} className="timeline-waveform-peaks" />
+    </svg>
+  );
+}
@@ -0,0 +1,125 @@
+import { useEffect, useMemo, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { TimelineConfig, TranscriptWord, TranscriptWordRange } from "../types/project";
+import { Button } from "./ui/Button";
+import { normalizeInvokeError } from "../utils/tauriError";
+
+type TranscriptEditorPanelProps = {
+  projectId: string;
+  subtitlePath: string | null;
+  timeline: TimelineConfig;
+  onCut: (ranges: TranscriptWordRange[]) => Promise<void>;
+};
+
+function toRanges(selected: Set<number>): TranscriptWordRange[] {
+  const sorted = [...selected].sort((a, b) => a - b);
+  const ranges: TranscriptWordRange[] = [];
+  for (const index of sorted) {
+    const last = ranges[ranges.length - 1];
+    if (last && last.endIndex + 1 === index) {
+      last.endIndex = index;
+    } else {
+      ranges.push({ startIndex: index, endIndex: index });
+    }
+  }
+  return ranges;
+}
+
+export function TranscriptEditorPanel({
+  projectId,
+  subtitlePath,
+  timeline,
+  onCut
+}: TranscriptEditorPanelProps) {
+  const [words, setWords] = useState<TranscriptWord[]>([]);
+  const [selected, setSelected] = useState<Set<number>>(new Set());
+  const [anchor, setAnchor] = useState<number | null>(null);
+  const [pending, setPending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setSelected(new Set());
+    setAnchor(null);
+    invoke<TranscriptWord[]>("get_transcript_words", { projectId })
+      .then(setWords)
+      .catch(() => setWords([]));
+  }, [projectId, subtitlePath]);
+
+  const isKept = useMemo(() => {
+    const segments = timeline.segments ?? [];
+    return (word: TranscriptWord) => {
+      const mid = (word.startMs + word.endMs) / 2;
+      return segments.length > 0
+        ? segments.some((segment) => mid >= segment.startMs && mid < segment.endMs)
+        : mid >= timeline.trimStartMs && mid < timeline.trimEndMs;
+    };
+  }, [timeline.segments, timeline.trimStartMs, timeline.trimEndMs]);
+
+  if (words.length === 0) {
+    return null;
+  }
+
+  const toggle = (index: number, extend: boolean) => {
+    const next = new Set(selected);
+    if (extend && anchor !== null) {
+      for (let i = Math.min(anchor, index); i <= Math.max(anchor, index); i += 1) {
+        next.add(i);
+      }
+    } else if (next.has(index)) {
+      next.delete(index);
+    } else {
+      next.add(index);
+    }
+    setAnchor(index);
+    setSelected(next);
+  };
+
+  const cutSelected = async () => {
+    setPending(true);
+    setError(null);
+    try {
+      await onCut(toRanges(selected));
+      setSelected(new Set());
+      setAnchor(null);
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "TRANSCRIPT_RANGE_INVALID", "删除文字失败");
+      setError(parsed.suggestion ? `${parsed.message}：${parsed.suggestion}` : parsed.message);
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <details className="panel stack">
+      <summary>
+        <strong>文字稿剪辑</strong>
+      </summary>
+      <p className="note">点击选词，按住 Shift 连选；删除后对应画面从保留片段中剪掉，可撤销。</p>
+      {error && <p className="error">{error}</p>}
+      <p>
+        {words.map((word) => (
+          <span
+            key={word.index}
+            role="button"
+            tabIndex={0}
+            className={selected.has(word.index) ? "project-tag" : isKept(word) ? undefined : "muted"}
+            style={isKept(word) ? undefined : { textDecoration: "line-through" }}
+            onClick={(event) => toggle(word.index, event.shiftKey)}
+          >
+            {word.text}{" "}
+          </span>
+        ))}
+      </p>
+      <div className="row gap">
+        <Button
+          variant="danger"
+          onClick={() => void cutSelected()}
+          disabled={selected.size === 0}
+          loading={pending}
+        >
+          删除所选文字
+        </Button>
+      </div>
+    </details>
+  );
+}
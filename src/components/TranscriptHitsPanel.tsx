@@ -0,0 +1,56 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { ProjectSearchResult } from "../types/project";
+import { formatDuration } from "../utils/format";
+
+type TranscriptHitsPanelProps = {
+  search: string;
+  onOpen: (projectId: string) => void;
+};
+
+// 项目列表只按标题与备注过滤，字幕文字稿中的命中单独列出，附带出现的时间点。
+export function TranscriptHitsPanel({ search, onOpen }: TranscriptHitsPanelProps) {
+  const [results, setResults] = useState<ProjectSearchResult[]>([]);
+
+  useEffect(() => {
+    let cancelled = false;
+    invoke<ProjectSearchResult[]>("search_projects", { query: search })
+      .then((found) => {
+        if (!cancelled) setResults(found.filter((result) => result.transcriptHits.length > 0));
+      })
+      .catch(() => {
+        if (!cancelled) setResults([]);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [search]);
+
+  if (results.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="panel stack">
+      <strong>字幕中的匹配</strong>
+      <ul className="trash-list">
+        {results.flatMap(({ item, transcriptHits }) =>
+          transcriptHits.map((hit) => (
+            <li key={`${item.projectId}-${hit.startMs}`}>
+              <span>
+                {item.title?.trim() || `Project_${item.projectId.slice(0, 8)}`}
+                <small className="muted">
+                  {" "}
+                  · {formatDuration(hit.startMs)} · {hit.text}
+                </small>
+              </span>
+              <button type="button" className="project-tag" onClick={() => onOpen(item.projectId)}>
+                打开
+              </button>
+            </li>
+          ))
+        )}
+      </ul>
+    </div>
+  );
+}
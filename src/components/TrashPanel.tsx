@@ -0,0 +1,95 @@
+import { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import type { TrashedProject } from "../types/project";
+import { Button } from "./ui/Button";
+import { formatDateTime, formatDuration } from "../utils/format";
+import { normalizeInvokeError } from "../utils/tauriError";
+
+type TrashPanelProps = {
+  onRestored: () => Promise<void>;
+};
+
+export function TrashPanel({ onRestored }: TrashPanelProps) {
+  const [items, setItems] = useState<TrashedProject[]>([]);
+  const [pending, setPending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setItems(await invoke<TrashedProject[]>("list_trashed_projects"));
+    } catch (e) {
+      setError(normalizeInvokeError(e, "TRASH_IO_ERROR", "读取回收站失败").message);
+    }
+  }, []);
+
+  useEffect(() => {
+    void refresh();
+  }, [refresh]);
+
+  const run = async (action: () => Promise<unknown>) => {
+    setPending(true);
+    setError(null);
+    try {
+      await action();
+      await refresh();
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "TRASH_IO_ERROR", "回收站操作失败");
+      setError(parsed.suggestion ?? parsed.message);
+    } finally {
+      setPending(false);
+    }
+  };
+
+  const restore = (projectId: string) =>
+    run(async () => {
+      await invoke("restore_project", { projectId });
+      await onRestored();
+    });
+
+  const emptyTrash = () => {
+    if (!window.confirm("确认永久删除回收站中的全部项目吗？此操作不可恢复。")) return;
+    void run(() => invoke<number>("purge_trash", { olderThanDays: null }));
+  };
+
+  return (
+    <div className="panel stack trash-panel">
+      <div className="trash-panel-head">
+        <strong>回收站</strong>
+        <span className="muted">删除的项目保留 30 天后自动清理</span>
+        <Button
+          variant="danger"
+          onClick={emptyTrash}
+          disabled={pending || items.length === 0}
+          className="push-end"
+        >
+          清空回收站
+        </Button>
+      </div>
+      {error && <p className="error">{error}</p>}
+      {items.length === 0 ? (
+        <p className="note">回收站是空的。</p>
+      ) : (
+        <ul className="trash-list">
+          {items.map((item) => (
+            <li key={item.projectId}>
+              <span>
+                {item.title?.trim() || `Project_${item.projectId.slice(0, 8)}`}
+                <small className="muted">
+                  {" "}
+                  · {formatDuration(item.durationMs)} · 删除于 {formatDateTime(item.deletedAt)}
+                </small>
+              </span>
+              <Button
+                variant="outline"
+                onClick={() => void restore(item.projectId)}
+                disabled={pending}
+              >
+                恢复
+              </Button>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
@@ -1,12 +1,14 @@
 import { useEffect, useRef } from "react";
 import { listen, type EventCallback, type UnlistenFn } from "@tauri-apps/api/event";
+import type { AppEventMap, EventEnvelope } from "../types/project";
 
 /**
  * A safe hook to listen to Tauri events with automatic cleanup.
+ * Event names and payloads follow the backend's typed event contract.
  */
-export function useTauriEvent<T>(
-  eventName: string,
-  callback: EventCallback<T>,
+export function useTauriEvent<K extends keyof AppEventMap>(
+  eventName: K,
+  callback: EventCallback<EventEnvelope<AppEventMap[K]>>,
   deps: React.DependencyList = []
 ) {
   const callbackRef = useRef(callback);
@@ -21,7 +23,7 @@ export function useTauriEvent<T>(
     let disposed = false;
 
     const setupListener = async () => {
-      const cleanup = await listen<T>(eventName, (event) => {
+      const cleanup = await listen<EventEnvelope<AppEventMap[K]>>(eventName, (event) => {
         if (!disposed) {
           callbackRef.current(event);
         }
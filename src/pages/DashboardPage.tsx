@@ -1,31 +1,91 @@
-import { useMemo } from "react";
-import type { ProjectListItem } from "../types/project";
+import { useMemo, useState } from "react";
+import type {
+  Locale,
+  ProjectListItem,
+  ProjectListQuery,
+  ProjectSortKey,
+  ProjectStatus
+} from "../types/project";
 import { Button } from "../components/ui/Button";
 import { Icons } from "../components/ui/Icons";
 import { ProjectCard } from "../components/ProjectCard";
+import { RecoveryPanel } from "../components/RecoveryPanel";
+import { TranscriptHitsPanel } from "../components/TranscriptHitsPanel";
+import { TrashPanel } from "../components/TrashPanel";
+import { useSettingsStore } from "../stores/settingsStore";
 import { formatDateTime } from "../utils/format";
+import { normalizeInvokeError } from "../utils/tauriError";
+
+const statusOptions: Array<{ value: ProjectStatus; label: string }> = [
+  { value: "ready_to_edit", label: "待编辑" },
+  { value: "export_succeeded", label: "已完成" },
+  { value: "export_failed", label: "导出失败" },
+  { value: "exporting", label: "正在导出" },
+  { value: "recording", label: "录制中" }
+];
+
+const sortOptions: Array<{ value: ProjectSortKey; label: string }> = [
+  { value: "updated_desc", label: "最近修改" },
+  { value: "created_desc", label: "最近创建" },
+  { value: "title_asc", label: "标题" },
+  { value: "duration_desc", label: "时长" }
+];
+
+const localeOptions: Array<{ value: Locale; label: string }> = [
+  { value: "zh-CN", label: "简体中文" },
+  { value: "en-US", label: "English" }
+];
 
 type DashboardPageProps = {
   projects: ProjectListItem[];
+  total: number;
+  query: ProjectListQuery;
+  onQueryChange: (patch: Partial<ProjectListQuery>) => void;
   loading: boolean;
   error: string | null;
   onRefresh: () => Promise<void>;
+  onRebuildIndex: () => Promise<void>;
   onNewRecording: () => void;
   onOpenProject: (projectId: string) => void;
   onDeleteProject: (projectId: string) => Promise<void>;
   onRenameProject: (projectId: string, title: string) => Promise<void>;
+  onDuplicateProject: (projectId: string, copyAssets: boolean) => Promise<void>;
+  onUpdateProjectTags: (projectId: string, tags: string[]) => Promise<void>;
+  onUpdateProjectNotes: (projectId: string, notes: string) => Promise<void>;
+  onImportArchive: (path: string) => Promise<void>;
+  onImportVideo: (path: string) => Promise<void>;
 };
 
 export function DashboardPage({
   projects,
+  total,
+  query,
+  onQueryChange,
   loading,
   error,
   onRefresh,
+  onRebuildIndex,
   onNewRecording,
   onOpenProject,
   onDeleteProject,
-  onRenameProject
+  onRenameProject,
+  onDuplicateProject,
+  onUpdateProjectTags,
+  onUpdateProjectNotes,
+  onImportArchive,
+  onImportVideo
 }: DashboardPageProps) {
+  const [importing, setImporting] = useState(false);
+  const [importError, setImportError] = useState<string | null>(null);
+  const [showTrash, setShowTrash] = useState(false);
+  const [rebuilding, setRebuilding] = useState(false);
+  const [searchDraft, setSearchDraft] = useState(query.search ?? "");
+  const locale = useSettingsStore((state) => state.locale);
+  const saveLocale = useSettingsStore((state) => state.saveLocale);
+  const activeTags = query.tags ?? [];
+  const page = query.page ?? 1;
+  const pageCount = Math.max(1, Math.ceil(total / (query.pageSize ?? Math.max(total, 1))));
+
   const latestProjectTime = useMemo(() => {
     if (!projects.length) {
       return "暂无项目";
@@ -33,6 +93,37 @@ export function DashboardPage({
     return formatDateTime(projects[0].updatedAt);
   }, [projects]);
 
+  const handleImport = async (kind: "archive" | "video") => {
+    const path = window.prompt(
+      kind === "archive" ? "要导入的 .focuslens 文件路径" : "要导入的 MP4 视频路径"
+    );
+    if (!path?.trim()) return;
+    setImporting(true);
+    setImportError(null);
+    try {
+      await (kind === "archive" ? onImportArchive : onImportVideo)(path.trim());
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "IMPORT_FAILED", "导入失败");
+      setImportError(parsed.suggestion ? `${parsed.message}：${parsed.suggestion}` : parsed.message);
+    } finally {
+      setImporting(false);
+    }
+  };
+
+  // 手动改动项目目录后，列表可能与磁盘不一致，重建索引后重新读取。
+  const rebuildIndex = async () => {
+    setRebuilding(true);
+    setImportError(null);
+    try {
+      await onRebuildIndex();
+    } catch (e) {
+      const parsed = normalizeInvokeError(e, "PROJECT_INDEX_ERROR", "重建项目索引失败");
+      setImportError(parsed.suggestion ? `${parsed.message}：${parsed.suggestion}` : parsed.message);
+    } finally {
+      setRebuilding(false);
+    }
+  };
+
   return (
     <section className="dashboard-shell">
       <header className="dashboard-hero">
@@ -45,13 +136,100 @@ export function DashboardPage({
           <Button variant="outline" icon={<Icons.Refresh />} onClick={() => void onRefresh()} loading={loading}>
             刷新列表
           </Button>
+          <Button
+            variant="outline"
+            title="按磁盘上的项目重建列表索引"
+            onClick={() => void rebuildIndex()}
+            loading={rebuilding}
+          >
+            重建索引
+          </Button>
+          <Button variant="outline" icon={<Icons.Folder />} onClick={() => void handleImport("archive")} loading={importing}>
+            导入项目
+          </Button>
+          <Button variant="outline" icon={<Icons.Export />} onClick={() => void handleImport("video")} disabled={importing}>
+            导入视频
+          </Button>
+          <Button variant="outline" icon={<Icons.Delete />} onClick={() => setShowTrash((open) => !open)}>
+            {showTrash ? "收起回收站" : "回收站"}
+          </Button>
           <Button variant="primary" icon={<Icons.Record />} onClick={onNewRecording}>
             开始新录制
           </Button>
+          <select
+            value={locale}
+            title="错误提示语言"
+            onChange={(event) => void saveLocale(event.target.value as Locale)}
+          >
+            {localeOptions.map((option) => (
+              <option key={option.value} value={option.value}>
+                {option.label}
+              </option>
+            ))}
+          </select>
         </div>
       </header>
 
+      <RecoveryPanel onRecovered={onRefresh} />
+      {showTrash && <TrashPanel onRestored={onRefresh} />}
+
+      <div className="dashboard-toolbar">
+        <input
+          type="search"
+          placeholder="搜索标题或备注"
+          value={searchDraft}
+          onChange={(event) => setSearchDraft(event.target.value)}
+          onKeyDown={(event) => {
+            if (event.key === "Enter") {
+              onQueryChange({ search: searchDraft.trim() || null });
+            }
+          }}
+          onBlur={() => {
+            if ((query.search ?? "") !== searchDraft.trim()) {
+              onQueryChange({ search: searchDraft.trim() || null });
+            }
+          }}
+        />
+        <select
+          value={query.status ?? ""}
+          onChange={(event) =>
+            onQueryChange({ status: (event.target.value || null) as ProjectStatus | null })
+          }
+        >
+          <option value="">全部状态</option>
+          {statusOptions.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <select
+          value={query.sort ?? "updated_desc"}
+          onChange={(event) => onQueryChange({ sort: event.target.value as ProjectSortKey })}
+        >
+          {sortOptions.map((option) => (
+            <option key={option.value} value={option.value}>
+              按{option.label}排序
+            </option>
+          ))}
+        </select>
+        {activeTags.map((tag) => (
+          <button
+            key={tag}
+            type="button"
+            className="project-tag"
+            title="移除标签筛选"
+            onClick={() => onQueryChange({ tags: activeTags.filter((item) => item !== tag) })}
+          >
+            #{tag} ×
+          </button>
+        ))}
+        <span className="muted">共 {total} 个项目</span>
+      </div>
+
       {error && <p className="error">{error}</p>}
+      {importError && <p className="error">{importError}</p>}
+      {query.search && <TranscriptHitsPanel search={query.search} onOpen={onOpenProject} />}
 
       <div className="project-grid">
         {projects.map((project) => (
@@ -61,11 +239,41 @@ export function DashboardPage({
             onOpen={onOpenProject}
             onDelete={onDeleteProject}
             onRename={onRenameProject}
+            onDuplicate={onDuplicateProject}
+            onTagsChange={onUpdateProjectTags}
+            onNotesChange={onUpdateProjectNotes}
+            onTagClick={(tag) => {
+              if (!activeTags.includes(tag)) {
+                onQueryChange({ tags: [...activeTags, tag] });
+              }
+            }}
           />
         ))}
       </div>
 
-      {!loading && projects.length === 0 && (
+      {pageCount > 1 && (
+        <div className="dashboard-pager">
+          <Button
+            variant="outline"
+            disabled={page <= 1 || loading}
+            onClick={() => onQueryChange({ page: page - 1 })}
+          >
+            上一页
+          </Button>
+          <span className="muted">
+            第 {page} / {pageCount} 页
+          </span>
+          <Button
+            variant="outline"
+            disabled={page >= pageCount || loading}
+            onClick={() => onQueryChange({ page: page + 1 })}
+          >
+            下一页
+          </Button>
+        </div>
+      )}
+
+      {!loading && total === 0 && !query.search && !query.status && !activeTags.length && (
         <div className="empty-state dashboard-empty">
           <div className="empty-state-icon">
             <Icons.Record size={80} />
@@ -7,9 +7,11 @@ import { useSettingsStore } from "../stores/settingsStore";
 import { RecordingView } from "./studio/RecordingView";
 import { ReviewView } from "./studio/ReviewView";
 import type {
+  ExportPreset,
   ExportProfile,
   ExportStatus,
   HotkeySettings,
+  QualityGateStatus,
   RecordingProfile,
 } from "../types/project";
 
@@ -50,7 +52,8 @@ const exportStatusMap: Record<ExportStatus, string> = {
   running: "running",
   fallback: "running",
   success: "success",
-  failed: "failed"
+  failed: "failed",
+  cancelled: "idle"
 };
 
 const exportStatusText: Record<ExportStatus, string> = {
@@ -58,7 +61,8 @@ const exportStatusText: Record<ExportStatus, string> = {
   running: "正在导出",
   fallback: "软编兼容模式",
   success: "导出成功",
-  failed: "导出失败"
+  failed: "导出失败",
+  cancelled: "已取消"
 };
 
 export function StudioPage({
@@ -82,6 +86,7 @@ export function StudioPage({
   const {
     capability,
     audioDevices,
+    displays,
     hotkeys: savedHotkeys,
     saveHotkeys
   } = useSettingsStore();
@@ -92,10 +97,20 @@ export function StudioPage({
     loadProject,
     updateTimeline,
     updateCameraMotion,
+    updateSceneStyle,
+    updateKeystrokeOverlay,
+    updateAudioMix,
+    updateColorGrade,
+    detectSilentRanges,
+    cutTranscriptWords,
+    suggestChapters,
+    history,
+    stepHistory,
+    restoreRevision,
     flushUpdates
   } = useProjectStore();
 
-  const { taskId, status: exportStatus, progress, detail: exportDetail, error: exportError, startExport, retryExport } = useExportStore();
+  const { taskId, status: exportStatus, progress, detail: exportDetail, error: exportError, startExport, startExportWithPreset, retryExport, cancelExport } = useExportStore();
 
   const [recordingProfile, setRecordingProfile] = useState<RecordingProfile>(initialProfile);
   const [hotkeys, setHotkeys] = useState<HotkeySettings>(savedHotkeys);
@@ -107,11 +122,14 @@ export function StudioPage({
   const [exportProfile, setExportProfile] = useState<ExportProfile>(defaultExport);
   const [submittingExport, setSubmittingExport] = useState(false);
   const [checkingGate, setCheckingGate] = useState(false);
-  const [qualityGate, setQualityGate] = useState<{ passed: boolean; reasons: string[] } | null>(null);
+  const [qualityGate, setQualityGate] = useState<QualityGateStatus | null>(null);
   const [actionError, setActionError] = useState<string | null>(null);
   const [toast, setToast] = useState<string | null>(null);
   const [titleDirty, setTitleDirty] = useState(false);
   const [exportDirty, setExportDirty] = useState(false);
+  const [exportPresets, setExportPresets] = useState<ExportPreset[]>([]);
+  const [selectedPresetId, setSelectedPresetId] = useState<string | null>(null);
+  const [exportVerticalVariant, setExportVerticalVariant] = useState(false);
   const [hydratedProjectId, setHydratedProjectId] = useState<string | null>(null);
 
   useEffect(() => {
@@ -119,6 +137,13 @@ export function StudioPage({
     setRecordingProfile((prev) => ({ ...prev, hotkeys: savedHotkeys }));
   }, [savedHotkeys]);
 
+  useEffect(() => {
+    if (mode !== "review") return;
+    void invoke<ExportPreset[]>("list_export_presets")
+      .then(setExportPresets)
+      .catch(() => setExportPresets([]));
+  }, [mode]);
+
   useEffect(() => {
     if (mode !== "review" || !projectId) return;
     setLoadingProject(true);
@@ -209,7 +234,14 @@ export function StudioPage({
     try {
       await loadProject(projectId);
       await flushUpdates();
-      await startExport(projectId, exportProfile);
+      if (selectedPresetId) {
+        await startExportWithPreset(projectId, selectedPresetId);
+      } else {
+        const variants: ExportProfile[] = exportVerticalVariant
+          ? [{ ...exportProfile, resolution: "720p", aspectRatio: "9:16" }]
+          : [];
+        await startExport(projectId, exportProfile, variants);
+      }
       setExportDirty(false);
     } catch (submitError) {
       setActionError(`导出准备失败：${String(submitError)}`);
@@ -223,7 +255,7 @@ export function StudioPage({
     setCheckingGate(true);
     setActionError(null);
     try {
-      const result = await invoke<{ passed: boolean; reasons: string[] }>("validate_quality_gate", { projectId });
+      const result = await invoke<QualityGateStatus>("validate_quality_gate", { projectId });
       setQualityGate(result);
     } catch (gateError) {
       setActionError(`质量门槛检查失败：${String(gateError)}`);
@@ -245,6 +277,7 @@ export function StudioPage({
           hotkeys={hotkeys}
           setHotkeys={setHotkeys}
           audioDevices={audioDevices}
+          displays={displays}
           capability={capability}
           busyAction={busyAction}
           onRunAction={runAction}
@@ -268,7 +301,22 @@ export function StudioPage({
           manifest={manifest}
           updateTimeline={updateTimeline}
           updateCameraMotion={updateCameraMotion}
+          updateSceneStyle={updateSceneStyle}
+          updateKeystrokeOverlay={updateKeystrokeOverlay}
+          updateAudioMix={updateAudioMix}
+          updateColorGrade={updateColorGrade}
+          detectSilentRanges={detectSilentRanges}
+          onCutTranscriptWords={cutTranscriptWords}
+          onSuggestChapters={suggestChapters}
+          history={history}
+          onStepHistory={stepHistory}
+          onRestoreRevision={restoreRevision}
           exportProfile={exportProfile}
+          exportPresets={exportPresets}
+          selectedPresetId={selectedPresetId}
+          setSelectedPresetId={setSelectedPresetId}
+          exportVerticalVariant={exportVerticalVariant}
+          setExportVerticalVariant={setExportVerticalVariant}
           setExportProfilePatch={(patch) => {
             setExportDirty(true);
             setExportProfile(prev => ({ ...prev, ...patch }));
@@ -280,6 +328,7 @@ export function StudioPage({
           progress={progress}
           exportDetail={exportDetail}
           onRetryExport={retryExport}
+          onCancelExport={cancelExport}
           onStartExport={handleStartExport}
           onCheckQualityGate={handleCheckQualityGate}
           checkingGate={checkingGate}
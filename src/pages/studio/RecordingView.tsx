@@ -1,7 +1,16 @@
-import { RecordingProfile, RecordingRuntimeStatus, HotkeySettings } from "../../types/project";
+import { useEffect, useState } from "react";
+import {
+  DisplayInfo,
+  FRAME_RATE_PRESETS,
+  RecordingProfile,
+  RecordingRuntimeStatus,
+  HotkeySettings
+} from "../../types/project";
+import { CaptureTestPanel } from "../../components/CaptureTestPanel";
 import { Button } from "../../components/ui/Button";
 import { Icons } from "../../components/ui/Icons";
 import { StatusChip } from "../../components/ui/StatusChip";
+import { useTauriEvent } from "../../hooks/useTauriEvents";
 import { formatDuration } from "../../utils/format";
 
 type RecordingViewProps = {
@@ -14,6 +23,7 @@ type RecordingViewProps = {
   hotkeys: HotkeySettings;
   setHotkeys: React.Dispatch<React.SetStateAction<HotkeySettings>>;
   audioDevices: Array<{ id: string; label: string }>;
+  displays: DisplayInfo[];
   capability: { supportsSystemAudio: boolean } | null;
   busyAction: string | null;
   onRunAction: (action: any, task: () => Promise<void>) => Promise<void>;
@@ -51,6 +61,7 @@ export function RecordingView({
   hotkeys,
   setHotkeys,
   audioDevices,
+  displays,
   capability,
   busyAction,
   onRunAction,
@@ -61,6 +72,18 @@ export function RecordingView({
   onSaveHotkeys,
   onBackToDashboard
 }: RecordingViewProps) {
+  const [previewFrame, setPreviewFrame] = useState<string | null>(null);
+
+  useTauriEvent("recording/preview", (event) => {
+    setPreviewFrame(event.payload.dataUrl);
+  });
+
+  useEffect(() => {
+    if (status !== "recording" && status !== "paused") {
+      setPreviewFrame(null);
+    }
+  }, [status]);
+
   return (
     <div className="studio-shell">
       <header className="studio-topbar">
@@ -76,6 +99,9 @@ export function RecordingView({
 
       <div className="recording-layout">
         <div className="preview-canvas">
+          {previewFrame && (
+            <img className="preview-frame" src={previewFrame} alt="录制实时预览" />
+          )}
           <div className="preview-overlay preview-overlay-card glass">
             <div className="preview-overlay-head">
               <p className="preview-title preview-title-compact">实时预览</p>
@@ -126,6 +152,27 @@ export function RecordingView({
                 </select>
               </label>
             </div>
+            {recordingProfile.captureMode === "fullscreen" && displays.length > 1 && (
+              <label>
+                显示器
+                <select
+                  value={recordingProfile.displayId ?? "__all__"}
+                  onChange={(event) =>
+                    setRecordingProfile((prev) => ({
+                      ...prev,
+                      displayId: event.target.value === "__all__" ? null : event.target.value
+                    }))
+                  }
+                >
+                  <option value="__all__">全部屏幕</option>
+                  {displays.map((display) => (
+                    <option key={display.id} value={display.id}>
+                      {display.label}
+                    </option>
+                  ))}
+                </select>
+              </label>
+            )}
             <div className="form-grid-two">
               <label>
                 帧率
@@ -134,12 +181,15 @@ export function RecordingView({
                   onChange={(event) =>
                     setRecordingProfile((prev) => ({
                       ...prev,
-                      frameRate: Number(event.target.value) as 30 | 60
+                      frameRate: Number(event.target.value)
                     }))
                   }
                 >
-                  <option value={30}>30 FPS</option>
-                  <option value={60}>60 FPS</option>
+                  {FRAME_RATE_PRESETS.map((fps) => (
+                    <option key={fps} value={fps}>
+                      {fps} FPS
+                    </option>
+                  ))}
                 </select>
               </label>
               <label>
@@ -186,8 +236,31 @@ export function RecordingView({
                   : "当前系统暂不支持系统音频采集。"}
               </span>
             </label>
+            <label className="toggle-field toggle-field-spaced">
+              <span className="toggle-field-main">
+                <input
+                  type="checkbox"
+                  checked={recordingProfile.doNotDisturb ?? false}
+                  onChange={(event) =>
+                    setRecordingProfile((prev) => ({
+                      ...prev,
+                      doNotDisturb: event.target.checked
+                    }))
+                  }
+                />
+                <span className="toggle-field-title">录制时开启勿扰</span>
+              </span>
+              <span className="toggle-field-hint">
+                开始录制时屏蔽系统通知弹窗，停止后恢复原设置。
+              </span>
+            </label>
           </div>
 
+          <CaptureTestPanel
+            profile={{ ...recordingProfile, hotkeys }}
+            disabled={busyAction !== null || status === "recording" || status === "paused"}
+          />
+
           <div className="recording-hotkeys">
             <p className="eyebrow eyebrow-sm">快捷键</p>
             <div className="form-grid-two">
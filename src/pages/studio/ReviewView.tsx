@@ -1,10 +1,40 @@
-import { ExportProfile, ExportStatus } from "../../types/project";
+import { useRef, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import {
+  AudioDenoise,
+  AudioMix,
+  ColorGrade,
+  EditHistoryStatus,
+  ExportPreset,
+  ExportProfile,
+  ExportStatus,
+  KeystrokeOverlay,
+  OverlayCorner,
+  OverlayPosition,
+  QualityGateStatus,
+  SceneStyle,
+  TimecodeMode,
+  TimeRange,
+  TranscriptWordRange,
+  VideoDenoise
+} from "../../types/project";
+import { formatDuration } from "../../utils/format";
 import { Button } from "../../components/ui/Button";
 import { Icons } from "../../components/ui/Icons";
 import { StatusChip } from "../../components/ui/StatusChip";
 import { TimelineEditor } from "../../components/TimelineEditor";
+import { TimelineFilmstrip } from "../../components/TimelineFilmstrip";
+import { TimelineWaveform } from "../../components/TimelineWaveform";
 import { CameraMotionPanel } from "../../components/CameraMotionPanel";
+import { ScenePanel } from "../../components/ScenePanel";
+import { KeystrokeOverlayPanel } from "../../components/KeystrokeOverlayPanel";
+import { AudioMixPanel } from "../../components/AudioMixPanel";
+import { ColorGradePanel } from "../../components/ColorGradePanel";
 import { ExportProgressCard } from "../../components/ExportProgressCard";
+import { ManifestRevisionsPanel } from "../../components/ManifestRevisionsPanel";
+import { ChaptersPanel } from "../../components/ChaptersPanel";
+import { TranscriptEditorPanel } from "../../components/TranscriptEditorPanel";
+import { AnimationExportPanel } from "../../components/AnimationExportPanel";
 
 type ReviewViewProps = {
   projectId: string;
@@ -18,8 +48,23 @@ type ReviewViewProps = {
   manifest: any;
   updateTimeline: any;
   updateCameraMotion: any;
+  updateSceneStyle: (patch: Partial<SceneStyle>) => Promise<void>;
+  updateKeystrokeOverlay: (patch: Partial<KeystrokeOverlay>) => Promise<void>;
+  updateAudioMix: (patch: Partial<AudioMix>) => Promise<void>;
+  updateColorGrade: (patch: Partial<ColorGrade>) => Promise<void>;
+  detectSilentRanges: () => Promise<void>;
+  onCutTranscriptWords: (ranges: TranscriptWordRange[]) => Promise<void>;
+  onSuggestChapters: () => Promise<void>;
+  history: EditHistoryStatus;
+  onStepHistory: (direction: "undo" | "redo") => Promise<void>;
+  onRestoreRevision: (revisionId: string) => Promise<void>;
   exportProfile: ExportProfile;
   setExportProfilePatch: (patch: Partial<ExportProfile>) => void;
+  exportPresets: ExportPreset[];
+  selectedPresetId: string | null;
+  setSelectedPresetId: (presetId: string | null) => void;
+  exportVerticalVariant: boolean;
+  setExportVerticalVariant: (enabled: boolean) => void;
   exportStatus: ExportStatus;
   exportStatusText: Record<ExportStatus, string>;
   exportStatusMap: Record<ExportStatus, string>;
@@ -27,10 +72,11 @@ type ReviewViewProps = {
   progress: number;
   exportDetail: string;
   onRetryExport: () => Promise<void>;
+  onCancelExport: () => Promise<void>;
   onStartExport: () => Promise<void>;
   onCheckQualityGate: () => Promise<void>;
   checkingGate: boolean;
-  qualityGate: { passed: boolean; reasons: string[] } | null;
+  qualityGate: QualityGateStatus | null;
   actionError: string | null;
   submittingExport: boolean;
   onBackToDashboard: () => void;
@@ -49,8 +95,23 @@ export function ReviewView({
   manifest,
   updateTimeline,
   updateCameraMotion,
+  updateSceneStyle,
+  updateKeystrokeOverlay,
+  updateAudioMix,
+  updateColorGrade,
+  detectSilentRanges,
+  onCutTranscriptWords,
+  onSuggestChapters,
+  history,
+  onStepHistory,
+  onRestoreRevision,
   exportProfile,
   setExportProfilePatch,
+  exportPresets,
+  selectedPresetId,
+  exportVerticalVariant,
+  setExportVerticalVariant,
+  setSelectedPresetId,
   exportStatus,
   exportStatusText,
   exportStatusMap,
@@ -58,6 +119,7 @@ export function ReviewView({
   progress,
   exportDetail,
   onRetryExport,
+  onCancelExport,
   onStartExport,
   onCheckQualityGate,
   checkingGate,
@@ -70,6 +132,34 @@ export function ReviewView({
   const exportTaskActive = Boolean(
     taskId && (exportStatus === "queued" || exportStatus === "running" || exportStatus === "fallback")
   );
+  const videoRef = useRef<HTMLVideoElement>(null);
+  const seekPreview = (tMs: number) => {
+    if (videoRef.current) videoRef.current.currentTime = tMs / 1000;
+  };
+  const [stillHint, setStillHint] = useState<string | null>(null);
+  const captureStill = async () => {
+    const tMs = Math.round((videoRef.current?.currentTime ?? 0) * 1000);
+    try {
+      const path = await invoke<string>("extract_frame", { projectId, tMs });
+      setStillHint(`已保存截图：${path}`);
+    } catch (error) {
+      setStillHint(`截图失败：${String(error)}`);
+    }
+  };
+  const exportPoster = async () => {
+    const tMs = Math.round((videoRef.current?.currentTime ?? 0) * 1000);
+    try {
+      const path = await invoke<string>("export_poster", {
+        projectId,
+        tMs,
+        size: exportProfile.resolution
+      });
+      setStillHint(`已导出封面：${path}`);
+    } catch (error) {
+      setStillHint(`导出封面失败：${String(error)}`);
+    }
+  };
+  const selectedPreset = exportPresets.find((preset) => preset.id === selectedPresetId);
 
   const resolveOutputLabel = (resolution: ExportProfile["resolution"], fps: ExportProfile["fps"]) => {
     if (resolution === "1080p") {
@@ -127,7 +217,7 @@ export function ReviewView({
         <div className="review-left">
           <div className="video-shell video-shell-glass glass">
             {previewSrc ? (
-              <video controls src={previewSrc} className="review-video" />
+              <video ref={videoRef} controls src={previewSrc} className="review-video" />
             ) : (
               <div className="video-fallback">
                 <Icons.Folder size={64} className="review-fallback-icon" />
@@ -135,10 +225,86 @@ export function ReviewView({
               </div>
             )}
           </div>
+          {previewSrc && (
+            <div className="row gap">
+              <Button variant="outline" onClick={() => void captureStill()}>
+                截取当前帧
+              </Button>
+              <Button variant="outline" onClick={() => void exportPoster()}>
+                导出为封面
+              </Button>
+              {stillHint && <p className="note">{stillHint}</p>}
+            </div>
+          )}
+          {manifest && (
+            <>
+              <TimelineFilmstrip
+                projectId={projectId}
+                rawRecordingPath={manifest.artifacts.rawRecordingPath}
+                onSeek={seekPreview}
+              />
+              <TimelineWaveform
+                projectId={projectId}
+                rawRecordingPath={manifest.artifacts.rawRecordingPath}
+                trimStartMs={manifest.timeline.trimStartMs}
+                trimEndMs={manifest.timeline.trimEndMs}
+                onSeek={seekPreview}
+              />
+            </>
+          )}
           {manifest && (
             <div className="stack review-editor-stack">
-              <TimelineEditor timeline={manifest.timeline} onChange={updateTimeline} />
+              <div className="row gap">
+                <Button
+                  variant="outline"
+                  disabled={!history.canUndo}
+                  onClick={() => void onStepHistory("undo").catch(() => undefined)}
+                >
+                  撤销
+                </Button>
+                <Button
+                  variant="outline"
+                  disabled={!history.canRedo}
+                  onClick={() => void onStepHistory("redo").catch(() => undefined)}
+                >
+                  重做
+                </Button>
+              </div>
+              <ManifestRevisionsPanel projectId={projectId} onRestore={onRestoreRevision} />
+              <TimelineEditor
+                timeline={manifest.timeline}
+                onChange={updateTimeline}
+                onDetectSilence={detectSilentRanges}
+              />
+              <TranscriptEditorPanel
+                projectId={projectId}
+                subtitlePath={manifest.export?.subtitlePath ?? null}
+                timeline={manifest.timeline}
+                onCut={onCutTranscriptWords}
+              />
+              <ChaptersPanel
+                markers={manifest.timeline.markers ?? []}
+                onSuggest={onSuggestChapters}
+                onChange={(markers) => updateTimeline({ markers })}
+              />
+              <AnimationExportPanel projectId={projectId} timeline={manifest.timeline} />
               <CameraMotionPanel profile={manifest.cameraMotion} onChange={updateCameraMotion} />
+              {manifest.scene && (
+                <ScenePanel scene={manifest.scene} onChange={updateSceneStyle} />
+              )}
+              {manifest.keystrokeOverlay && (
+                <KeystrokeOverlayPanel
+                  overlay={manifest.keystrokeOverlay}
+                  hasKeyTrack={Boolean(manifest.artifacts.keyTrackPath)}
+                  onChange={updateKeystrokeOverlay}
+                />
+              )}
+              {manifest.audioMix && (
+                <AudioMixPanel mix={manifest.audioMix} onChange={updateAudioMix} />
+              )}
+              {manifest.colorGrade && (
+                <ColorGradePanel grade={manifest.colorGrade} onChange={updateColorGrade} />
+              )}
             </div>
           )}
         </div>
@@ -156,49 +322,283 @@ export function ReviewView({
 
               <div className="stack">
                 <label>
-                  质量预设
+                  发布渠道
                   <select
-                    value={resolveOutputLabel(exportProfile.resolution, exportProfile.fps)}
-                    onChange={(event) => {
-                      const value = event.target.value;
-                      if (value.includes("1080p / 60")) {
-                        setExportProfilePatch({
-                          resolution: "1080p",
-                          fps: 60,
-                          bitrateMbps: 12
-                        });
-                        return;
-                      }
-                      if (value.includes("1080p / 30")) {
+                    value={selectedPresetId ?? ""}
+                    onChange={(event) => setSelectedPresetId(event.target.value || null)}
+                  >
+                    <option value="">自定义</option>
+                    {exportPresets.map((preset) => (
+                      <option key={preset.id} value={preset.id}>
+                        {preset.name}
+                      </option>
+                    ))}
+                  </select>
+                </label>
+                {selectedPreset ? (
+                  <p className="note">{selectedPreset.description}</p>
+                ) : (
+                  <label>
+                    质量预设
+                    <select
+                      value={resolveOutputLabel(exportProfile.resolution, exportProfile.fps)}
+                      onChange={(event) => {
+                        const value = event.target.value;
+                        if (value.includes("1080p / 60")) {
+                          setExportProfilePatch({
+                            resolution: "1080p",
+                            fps: 60,
+                            bitrateMbps: 12
+                          });
+                          return;
+                        }
+                        if (value.includes("1080p / 30")) {
+                          setExportProfilePatch({
+                            resolution: "1080p",
+                            fps: 30,
+                            bitrateMbps: 8
+                          });
+                          return;
+                        }
+                        if (value.includes("720p / 60")) {
+                          setExportProfilePatch({
+                            resolution: "720p",
+                            fps: 60,
+                            bitrateMbps: 8
+                          });
+                          return;
+                        }
                         setExportProfilePatch({
-                          resolution: "1080p",
+                          resolution: "720p",
                           fps: 30,
-                          bitrateMbps: 8
+                          bitrateMbps: 4
                         });
-                        return;
+                      }}
+                    >
+                      <option value="1080p / 60fps / 12Mbps">极清 (1080p / 60fps)</option>
+                      <option value="1080p / 30fps / 8Mbps">高清 (1080p / 30fps)</option>
+                      <option value="720p / 60fps / 8Mbps">流畅 (720p / 60fps)</option>
+                      <option value="720p / 30fps / 4Mbps">体积优先 (720p / 30fps)</option>
+                    </select>
+                  </label>
+                )}
+
+                <label>
+                  音频降噪
+                  <select
+                    value={exportProfile.audioDenoise ?? "off"}
+                    onChange={(event) =>
+                      setExportProfilePatch({
+                        audioDenoise: event.target.value as AudioDenoise
+                      })
+                    }
+                  >
+                    <option value="off">关闭</option>
+                    <option value="light">轻度（风扇底噪）</option>
+                    <option value="strong">强力（键盘敲击）</option>
+                  </select>
+                </label>
+
+                <label>
+                  画面降噪
+                  <select
+                    value={exportProfile.videoDenoise ?? "off"}
+                    onChange={(event) =>
+                      setExportProfilePatch({
+                        videoDenoise: event.target.value as VideoDenoise
+                      })
+                    }
+                  >
+                    <option value="off">关闭</option>
+                    <option value="light">轻度</option>
+                    <option value="medium">中等</option>
+                    <option value="strong">强力（翻拍素材，导出较慢）</option>
+                  </select>
+                </label>
+
+                <label className="toggle-field toggle-field--full">
+                  <span className="toggle-field-main">
+                    <input
+                      type="checkbox"
+                      checked={exportProfile.clickSoundEnabled ?? false}
+                      onChange={(event) =>
+                        setExportProfilePatch({ clickSoundEnabled: event.target.checked })
                       }
-                      if (value.includes("720p / 60")) {
-                        setExportProfilePatch({
-                          resolution: "720p",
-                          fps: 60,
-                          bitrateMbps: 8
-                        });
-                        return;
+                    />
+                    <span className="toggle-field-title">点击音效</span>
+                  </span>
+                  <span className="toggle-field-hint">在每次鼠标点击处混入短促的点击声。</span>
+                </label>
+
+                {exportProfile.clickSoundEnabled && (
+                  <label>
+                    点击音量 {Math.round((exportProfile.clickSoundVolume ?? 0.6) * 100)}%
+                    <input
+                      type="range"
+                      min={0.1}
+                      max={1}
+                      step={0.05}
+                      value={exportProfile.clickSoundVolume ?? 0.6}
+                      onChange={(event) =>
+                        setExportProfilePatch({ clickSoundVolume: Number(event.target.value) })
                       }
-                      setExportProfilePatch({
-                        resolution: "720p",
-                        fps: 30,
-                        bitrateMbps: 4
-                      });
-                    }}
+                    />
+                  </label>
+                )}
+
+                <label className="toggle-field toggle-field--full">
+                  <span className="toggle-field-main">
+                    <input
+                      type="checkbox"
+                      checked={exportProfile.frameInterpolation ?? false}
+                      onChange={(event) =>
+                        setExportProfilePatch(
+                          event.target.checked
+                            ? { frameInterpolation: true, fps: 60 }
+                            : { frameInterpolation: false }
+                        )
+                      }
+                    />
+                    <span className="toggle-field-title">补帧到 60fps</span>
+                  </span>
+                  <span className="toggle-field-hint">
+                    30fps 录制插帧后光标移动更顺滑，导出耗时会成倍增加。
+                  </span>
+                </label>
+
+                <label className="toggle-field toggle-field--full">
+                  <span className="toggle-field-main">
+                    <input
+                      type="checkbox"
+                      checked={exportProfile.lowPriority ?? false}
+                      onChange={(event) =>
+                        setExportProfilePatch({ lowPriority: event.target.checked })
+                      }
+                    />
+                    <span className="toggle-field-title">后台导出</span>
+                  </span>
+                  <span className="toggle-field-hint">
+                    降低导出进程优先级并只占用一半 CPU 核心，导出较慢但不影响继续工作。
+                  </span>
+                </label>
+
+                <label>
+                  片头文件
+                  <input
+                    type="text"
+                    placeholder="可选，本地视频路径"
+                    value={exportProfile.introPath ?? ""}
+                    onChange={(event) =>
+                      setExportProfilePatch({ introPath: event.target.value || undefined })
+                    }
+                  />
+                </label>
+
+                <label>
+                  片尾文件
+                  <input
+                    type="text"
+                    placeholder="可选，本地视频路径"
+                    value={exportProfile.outroPath ?? ""}
+                    onChange={(event) =>
+                      setExportProfilePatch({ outroPath: event.target.value || undefined })
+                    }
+                  />
+                </label>
+
+                <label>
+                  字幕文件
+                  <input
+                    type="text"
+                    placeholder="可选，SRT/VTT/ASS 路径"
+                    value={exportProfile.subtitlePath ?? ""}
+                    onChange={(event) =>
+                      setExportProfilePatch({ subtitlePath: event.target.value || undefined })
+                    }
+                  />
+                </label>
+
+                {exportProfile.subtitlePath && (
+                  <>
+                    <label>
+                      字幕字号 {exportProfile.subtitleFontSize ?? 42}px
+                      <input
+                        type="range"
+                        min={20}
+                        max={96}
+                        step={2}
+                        value={exportProfile.subtitleFontSize ?? 42}
+                        onChange={(event) =>
+                          setExportProfilePatch({ subtitleFontSize: Number(event.target.value) })
+                        }
+                      />
+                    </label>
+                    <label>
+                      字幕位置
+                      <select
+                        value={exportProfile.subtitlePosition ?? "bottom"}
+                        onChange={(event) =>
+                          setExportProfilePatch({
+                            subtitlePosition: event.target.value as OverlayPosition
+                          })
+                        }
+                      >
+                        <option value="bottom">底部</option>
+                        <option value="top">顶部</option>
+                      </select>
+                    </label>
+                  </>
+                )}
+
+                <label>
+                  时间码
+                  <select
+                    value={exportProfile.timecode ?? "off"}
+                    onChange={(event) =>
+                      setExportProfilePatch({ timecode: event.target.value as TimecodeMode })
+                    }
                   >
-                    <option value="1080p / 60fps / 12Mbps">极清 (1080p / 60fps)</option>
-                    <option value="1080p / 30fps / 8Mbps">高清 (1080p / 30fps)</option>
-                    <option value="720p / 60fps / 8Mbps">流畅 (720p / 60fps)</option>
-                    <option value="720p / 30fps / 4Mbps">体积优先 (720p / 30fps)</option>
+                    <option value="off">不显示</option>
+                    <option value="elapsed">录制时长</option>
+                    <option value="wall_clock">录制时钟</option>
                   </select>
                 </label>
 
+                {(exportProfile.timecode ?? "off") !== "off" && (
+                  <label>
+                    时间码位置
+                    <select
+                      value={exportProfile.timecodeCorner ?? "top_left"}
+                      onChange={(event) =>
+                        setExportProfilePatch({
+                          timecodeCorner: event.target.value as OverlayCorner
+                        })
+                      }
+                    >
+                      <option value="top_left">左上角</option>
+                      <option value="top_right">右上角</option>
+                      <option value="bottom_left">左下角</option>
+                      <option value="bottom_right">右下角</option>
+                    </select>
+                  </label>
+                )}
+
+                {!selectedPreset && (
+                  <label className="toggle-field toggle-field--full">
+                    <span className="toggle-field-main">
+                      <input
+                        type="checkbox"
+                        checked={exportVerticalVariant}
+                        onChange={(event) => setExportVerticalVariant(event.target.checked)}
+                      />
+                      <span className="toggle-field-title">同时导出竖屏 720p</span>
+                    </span>
+                    <span className="toggle-field-hint">
+                      额外生成 9:16 版本，保存在 renders 目录下。
+                    </span>
+                  </label>
+                )}
+
                 <div className="row gap">
                   <Button
                     variant="primary"
@@ -226,6 +626,7 @@ export function ReviewView({
               progress={progress}
               detail={exportDetail}
               onRetry={onRetryExport}
+              onCancel={taskId ? onCancelExport : undefined}
             />
 
             {qualityGate && (
@@ -246,6 +647,9 @@ export function ReviewView({
                     ))}
                   </div>
                 )}
+                <QualityRangeList label="黑屏" ranges={qualityGate.blackRanges} />
+                <QualityRangeList label="画面冻结" ranges={qualityGate.frozenRanges} />
+                <QualityRangeList label="静音" ranges={qualityGate.silentRanges} />
               </div>
             )}
           </div>
@@ -254,3 +658,15 @@ export function ReviewView({
     </div>
   );
 }
+
+function QualityRangeList({ label, ranges }: { label: string; ranges: TimeRange[] }) {
+  if (ranges.length === 0) return null;
+  return (
+    <p className="muted quality-reason">
+      {label}：
+      {ranges
+        .map((range) => `${formatDuration(range.startMs)}-${formatDuration(range.endMs)}`)
+        .join("，")}
+    </p>
+  );
+}
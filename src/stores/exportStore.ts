@@ -20,6 +20,7 @@ function detailByStatus(status: ExportStatus): string {
   if (status === "running") return "导出处理中";
   if (status === "fallback") return "硬件编码不可用，已回退软件编码";
   if (status === "success") return "导出完成";
+  if (status === "cancelled") return "导出已取消";
   return "导出失败";
 }
 
@@ -27,6 +28,7 @@ function progressByStatus(status: ExportStatus): number {
   if (status === "queued") return 0;
   if (status === "running") return 45;
   if (status === "fallback") return 62;
+  if (status === "cancelled") return 0;
   return 100;
 }
 
@@ -38,8 +40,14 @@ type ExportStore = {
   progress: number;
   detail: string;
   error?: string;
-  startExport: (projectId: string, profile: ExportProfile) => Promise<void>;
+  startExport: (
+    projectId: string,
+    profile: ExportProfile,
+    variants?: ExportProfile[]
+  ) => Promise<void>;
+  startExportWithPreset: (projectId: string, presetId: string) => Promise<void>;
   retryExport: () => Promise<void>;
+  cancelExport: () => Promise<void>;
   setProgress: (
     taskId: string,
     status: ExportStatus,
@@ -87,7 +95,11 @@ async function pollExportStatus(
             ? snapshot.lastError?.message ?? "导出失败"
             : undefined
       });
-      if (snapshot.status === "success" || snapshot.status === "failed") {
+      if (
+        snapshot.status === "success" ||
+        snapshot.status === "failed" ||
+        snapshot.status === "cancelled"
+      ) {
         return;
       }
     } catch {
@@ -96,31 +108,43 @@ async function pollExportStatus(
   }
 }
 
+async function submitExport(
+  command: string,
+  args: Record<string, unknown>,
+  set: (partial: Partial<ExportStore>) => void,
+  get: () => ExportStore
+) {
+  try {
+    const taskId = await invoke<string>(command, args);
+    set({ taskId, status: "queued", progress: 0, detail: "导出队列中", error: undefined });
+    void pollExportStatus(taskId, set, get);
+  } catch (error) {
+    const parsed = normalizeInvokeError(
+      error,
+      "START_EXPORT_FAIL",
+      "导出任务创建失败"
+    );
+    set({
+      taskId: null,
+      status: "failed",
+      progress: 100,
+      detail: "导出任务创建失败",
+      error: parsed.message
+    });
+  }
+}
+
 export const useExportStore = create<ExportStore>((set, get) => ({
   taskId: null,
   status: "queued",
   progress: 0,
   detail: "等待导出",
   error: undefined,
-  startExport: async (projectId, profile) => {
-    try {
-      const taskId = await invoke<string>("start_export", { projectId, profile });
-      set({ taskId, status: "queued", progress: 0, detail: "导出队列中", error: undefined });
-      void pollExportStatus(taskId, set, get);
-    } catch (error) {
-      const parsed = normalizeInvokeError(
-        error,
-        "START_EXPORT_FAIL",
-        "导出任务创建失败"
-      );
-      set({
-        taskId: null,
-        status: "failed",
-        progress: 100,
-        detail: "导出任务创建失败",
-        error: parsed.message
-      });
-    }
+  startExport: async (projectId, profile, variants) => {
+    await submitExport("start_export", { projectId, profile, variants }, set, get);
+  },
+  startExportWithPreset: async (projectId, presetId) => {
+    await submitExport("start_export_with_preset", { projectId, presetId }, set, get);
   },
   retryExport: async () => {
     const { taskId } = get();
@@ -152,6 +176,19 @@ export const useExportStore = create<ExportStore>((set, get) => ({
       });
     }
   },
+  cancelExport: async () => {
+    const { taskId } = get();
+    if (!taskId) {
+      return;
+    }
+    try {
+      await invoke("cancel_export", { exportTaskId: taskId });
+      set({ status: "cancelled", progress: 0, detail: "导出已取消", error: undefined });
+    } catch (error) {
+      const parsed = normalizeInvokeError(error, "CANCEL_EXPORT_FAIL", "取消导出失败");
+      set({ error: parsed.message });
+    }
+  },
   setProgress: (taskId, status, progress, detail) => {
     const currentTaskId = get().taskId;
     if (currentTaskId && taskId !== currentTaskId) {
@@ -1,34 +1,81 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  AudioMix,
+  ColorGrade,
+  BlurCandidate,
+  BlurMode,
+  BlurRegion,
   CameraMotionProfile,
+  EditHistoryStatus,
+  KeystrokeOverlay,
+  LoadedProject,
   ProjectManifest,
-  TimelineConfig
+  SceneStyle,
+  ShapeAnnotation,
+  TextAnnotation,
+  TimeRange,
+  TimelineConfig,
+  TimelineMarker,
+  TimelineSegment,
+  TranscriptWordRange
 } from "../types/project";
 
 let projectWriteQueue: Promise<void> = Promise.resolve();
 let projectLoadSerial = 0;
 
+// 打码区域与文字标注按起点排序，与后端保存的顺序一致。
+function sortByStart<T extends { startMs: number }>(items: T[]) {
+  return [...items].sort((a, b) => a.startMs - b.startMs);
+}
+
 type ProjectStore = {
   currentProjectId: string | null;
   manifest: ProjectManifest | null;
+  history: EditHistoryStatus;
   loadProject: (projectId: string) => Promise<void>;
   updateTimeline: (patch: Partial<TimelineConfig>) => Promise<void>;
+  addBlurRegion: (region: Omit<BlurRegion, "id">) => Promise<void>;
+  updateBlurRegion: (region: BlurRegion) => Promise<void>;
+  removeBlurRegion: (regionId: string) => Promise<void>;
+  addMuteRange: (range: TimeRange) => Promise<void>;
+  removeMuteRange: (range: TimeRange) => Promise<void>;
+  addTextAnnotation: (annotation: Omit<TextAnnotation, "id">) => Promise<void>;
+  updateTextAnnotation: (annotation: TextAnnotation) => Promise<void>;
+  removeTextAnnotation: (annotationId: string) => Promise<void>;
+  addShapeAnnotation: (shape: Omit<ShapeAnnotation, "id">) => Promise<void>;
+  updateShapeAnnotation: (shape: ShapeAnnotation) => Promise<void>;
+  removeShapeAnnotation: (shapeId: string) => Promise<void>;
   updateCameraMotion: (patch: Partial<CameraMotionProfile>) => Promise<void>;
+  updateSceneStyle: (patch: Partial<SceneStyle>) => Promise<void>;
+  updateKeystrokeOverlay: (patch: Partial<KeystrokeOverlay>) => Promise<void>;
+  updateAudioMix: (patch: Partial<AudioMix>) => Promise<void>;
+  updateColorGrade: (patch: Partial<ColorGrade>) => Promise<void>;
+  detectSilentRanges: () => Promise<void>;
+  cutTranscriptWords: (ranges: TranscriptWordRange[]) => Promise<void>;
+  suggestChapters: () => Promise<void>;
+  detectSensitiveContent: (intervalMs?: number) => Promise<void>;
+  acceptBlurCandidate: (candidateId: string, mode?: BlurMode) => Promise<void>;
+  dismissBlurCandidate: (candidateId: string) => Promise<void>;
+  refreshHistory: () => Promise<void>;
+  stepHistory: (direction: "undo" | "redo") => Promise<void>;
+  restoreRevision: (revisionId: string) => Promise<void>;
   flushUpdates: () => Promise<void>;
 };
 
 export const useProjectStore = create<ProjectStore>((set, get) => ({
   currentProjectId: null,
   manifest: null,
+  history: { canUndo: false, canRedo: false },
   loadProject: async (projectId) => {
     const loadSerial = ++projectLoadSerial;
     set({ currentProjectId: projectId, manifest: null });
-    const manifest = await invoke<ProjectManifest>("load_project", { projectId });
+    const manifest = await invoke<LoadedProject>("load_project", { projectId });
     if (loadSerial !== projectLoadSerial || get().currentProjectId !== projectId) {
       return;
     }
     set({ currentProjectId: projectId, manifest });
+    void get().refreshHistory().catch(() => undefined);
   },
   updateTimeline: async (patch) => {
     const { currentProjectId } = get();
@@ -51,7 +98,372 @@ export const useProjectStore = create<ProjectStore>((set, get) => ({
           manifest: {
             ...latest,
             timeline: { ...latest.timeline, ...patch }
-          }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  addBlurRegion: async (region) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const added = await invoke<BlurRegion>("add_blur_region", {
+          projectId: targetProjectId,
+          ...region
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              blurRegions: sortByStart([...(latest.timeline.blurRegions ?? []), added])
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  updateBlurRegion: async (region) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const updated = await invoke<BlurRegion>("update_blur_region", {
+          projectId: targetProjectId,
+          region
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              blurRegions: sortByStart(
+                (latest.timeline.blurRegions ?? []).map((item) =>
+                  item.id === updated.id ? updated : item
+                )
+              )
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  removeBlurRegion: async (regionId) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke("remove_blur_region", { projectId: targetProjectId, regionId });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              blurRegions: sortByStart(
+                (latest.timeline.blurRegions ?? []).filter((item) => item.id !== regionId)
+              )
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  addMuteRange: async (range) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const muteRanges = await invoke<TimeRange[]>("add_mute_range", {
+          projectId: targetProjectId,
+          range
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: { ...latest, timeline: { ...latest.timeline, muteRanges } },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  removeMuteRange: async (range) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const muteRanges = await invoke<TimeRange[]>("remove_mute_range", {
+          projectId: targetProjectId,
+          range
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: { ...latest, timeline: { ...latest.timeline, muteRanges } },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  addTextAnnotation: async (annotation) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const { startMs, endMs, ...rest } = annotation;
+        const added = await invoke<TextAnnotation>("add_text_annotation", {
+          projectId: targetProjectId,
+          ...rest,
+          range: { startMs, endMs }
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              annotations: sortByStart([...(latest.timeline.annotations ?? []), added])
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  updateTextAnnotation: async (annotation) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const updated = await invoke<TextAnnotation>("update_text_annotation", {
+          projectId: targetProjectId,
+          annotation
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              annotations: sortByStart(
+                (latest.timeline.annotations ?? []).map((item) =>
+                  item.id === updated.id ? updated : item
+                )
+              )
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  removeTextAnnotation: async (annotationId) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke("remove_text_annotation", { projectId: targetProjectId, annotationId });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              annotations: sortByStart(
+                (latest.timeline.annotations ?? []).filter((item) => item.id !== annotationId)
+              )
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  addShapeAnnotation: async (shape) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const { startMs, endMs, ...rest } = shape;
+        const added = await invoke<ShapeAnnotation>("add_shape_annotation", {
+          projectId: targetProjectId,
+          ...rest,
+          range: { startMs, endMs }
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              shapes: sortByStart([...(latest.timeline.shapes ?? []), added])
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  updateShapeAnnotation: async (shape) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const updated = await invoke<ShapeAnnotation>("update_shape_annotation", {
+          projectId: targetProjectId,
+          shape
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              shapes: sortByStart(
+                (latest.timeline.shapes ?? []).map((item) =>
+                  item.id === updated.id ? updated : item
+                )
+              )
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  removeShapeAnnotation: async (shapeId) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke("remove_shape_annotation", { projectId: targetProjectId, shapeId });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: {
+              ...latest.timeline,
+              shapes: sortByStart(
+                (latest.timeline.shapes ?? []).filter((item) => item.id !== shapeId)
+              )
+            }
+          },
+          history: { canUndo: true, canRedo: false }
         });
       });
     await projectWriteQueue;
@@ -77,11 +489,357 @@ export const useProjectStore = create<ProjectStore>((set, get) => ({
           manifest: {
             ...latest,
             cameraMotion: { ...latest.cameraMotion, ...patch }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  updateSceneStyle: async (patch) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke("update_scene_style", { projectId: targetProjectId, patch });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest?.scene) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            scene: { ...latest.scene, ...patch }
+          }
+        });
+      });
+    await projectWriteQueue;
+  },
+  updateKeystrokeOverlay: async (patch) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke("update_keystroke_overlay", { projectId: targetProjectId, patch });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest?.keystrokeOverlay) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            keystrokeOverlay: { ...latest.keystrokeOverlay, ...patch }
+          }
+        });
+      });
+    await projectWriteQueue;
+  },
+  updateAudioMix: async (patch) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke("update_audio_mix", { projectId: targetProjectId, patch });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest?.audioMix) {
+          return;
+        }
+        // 空字符串表示移除背景音乐。
+        const musicPath =
+          patch.musicPath === undefined ? latest.audioMix.musicPath : patch.musicPath || undefined;
+        set({
+          manifest: {
+            ...latest,
+            audioMix: { ...latest.audioMix, ...patch, musicPath }
           }
         });
       });
     await projectWriteQueue;
   },
+  updateColorGrade: async (patch) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke("update_color_grade", { projectId: targetProjectId, patch });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest?.colorGrade) {
+          return;
+        }
+        // 空字符串表示移除 LUT。
+        const lutPath =
+          patch.lutPath === undefined ? latest.colorGrade.lutPath : patch.lutPath || undefined;
+        set({
+          manifest: {
+            ...latest,
+            colorGrade: { ...latest.colorGrade, ...patch, lutPath }
+          }
+        });
+      });
+    await projectWriteQueue;
+  },
+  detectSilentRanges: async () => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const silentRanges = await invoke<TimeRange[]>("detect_silent_ranges", {
+          projectId: targetProjectId
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: { ...latest.timeline, silentRanges }
+          }
+        });
+      });
+    await projectWriteQueue;
+  },
+  cutTranscriptWords: async (ranges) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const segments = await invoke<TimelineSegment[]>("cut_transcript_words", {
+          projectId: targetProjectId,
+          ranges
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: { ...latest.timeline, segments }
+          }
+        });
+        void get().refreshHistory().catch(() => undefined);
+      });
+    await projectWriteQueue;
+  },
+  suggestChapters: async () => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const markers = await invoke<TimelineMarker[]>("suggest_chapters", {
+          projectId: targetProjectId
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            timeline: { ...latest.timeline, markers }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  detectSensitiveContent: async (intervalMs) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const blurCandidates = await invoke<BlurCandidate[]>("detect_sensitive_content", {
+          projectId: targetProjectId,
+          intervalMs
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({ manifest: { ...latest, blurCandidates } });
+      });
+    await projectWriteQueue;
+  },
+  acceptBlurCandidate: async (candidateId, mode) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const region = await invoke<BlurRegion>("accept_blur_candidate", {
+          projectId: targetProjectId,
+          candidateId,
+          mode
+        });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            blurCandidates: (latest.blurCandidates ?? []).filter(
+              (candidate) => candidate.id !== candidateId
+            ),
+            timeline: {
+              ...latest.timeline,
+              blurRegions: sortByStart([...(latest.timeline.blurRegions ?? []), region])
+            }
+          },
+          history: { canUndo: true, canRedo: false }
+        });
+      });
+    await projectWriteQueue;
+  },
+  dismissBlurCandidate: async (candidateId) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke("dismiss_blur_candidate", { projectId: targetProjectId, candidateId });
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const latest = get().manifest;
+        if (!latest) {
+          return;
+        }
+        set({
+          manifest: {
+            ...latest,
+            blurCandidates: (latest.blurCandidates ?? []).filter(
+              (candidate) => candidate.id !== candidateId
+            )
+          }
+        });
+      });
+    await projectWriteQueue;
+  },
+  refreshHistory: async () => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const history = await invoke<EditHistoryStatus>("get_edit_history_status", {
+      projectId: currentProjectId
+    });
+    if (get().currentProjectId === currentProjectId) {
+      set({ history });
+    }
+  },
+  stepHistory: async (direction) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        const history = await invoke<EditHistoryStatus>(
+          direction === "undo" ? "undo_edit" : "redo_edit",
+          { projectId: targetProjectId }
+        );
+        if (get().currentProjectId !== targetProjectId) {
+          return;
+        }
+        const manifest = await invoke<LoadedProject>("load_project", {
+          projectId: targetProjectId
+        });
+        if (get().currentProjectId === targetProjectId) {
+          set({ history, manifest });
+        }
+      });
+    await projectWriteQueue;
+  },
+  restoreRevision: async (revisionId) => {
+    const { currentProjectId } = get();
+    if (!currentProjectId) {
+      return;
+    }
+    const targetProjectId = currentProjectId;
+    projectWriteQueue = projectWriteQueue
+      .catch(() => undefined)
+      .then(async () => {
+        await invoke<ProjectManifest>("restore_manifest_revision", {
+          projectId: targetProjectId,
+          revisionId
+        });
+        const manifest = await invoke<LoadedProject>("load_project", {
+          projectId: targetProjectId
+        });
+        if (get().currentProjectId === targetProjectId) {
+          set({ manifest });
+        }
+      });
+    await projectWriteQueue;
+  },
   flushUpdates: async () => {
     await projectWriteQueue;
   }
@@ -4,7 +4,8 @@ import type {
   AppError,
   RecordingProfile,
   RecordingRuntimeStatus,
-  RecordingStatusEvent
+  RecordingStatusEvent,
+  StopRecordingResult
 } from "../types/project";
 import { normalizeInvokeError } from "../utils/tauriError";
 
@@ -147,13 +148,13 @@ export const useRecordingStore = create<RecordingStore>((set, get) => ({
     }
     try {
       set({ detail: "正在停止录制，请稍候…", error: undefined });
-      const projectId = await invoke<string>("stop_recording", { sessionId });
+      const result = await invoke<StopRecordingResult>("stop_recording", { sessionId });
       set({
         status: "stopped",
-        projectId,
+        projectId: result.projectId,
         sessionId: null,
-        detail: "录制已完成",
-        error: undefined
+        detail: result.exportTaskId ? "录制已完成，已开始自动导出" : "录制已完成",
+        error: result.exportError ?? undefined
       });
     } catch (error) {
       const parsed = normalizeInvokeError(
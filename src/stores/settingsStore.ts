@@ -1,7 +1,10 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  AppSettings,
+  DisplayInfo,
   HotkeySettings,
+  Locale,
   PlatformCapability,
   RecordingDevice
 } from "../types/project";
@@ -9,24 +12,33 @@ import type {
 type SettingsStore = {
   capability: PlatformCapability | null;
   audioDevices: RecordingDevice[];
+  displays: DisplayInfo[];
   hotkeys: HotkeySettings;
+  locale: Locale;
   loadSettings: () => Promise<void>;
+  setAudioDevices: (devices: RecordingDevice[]) => void;
   saveHotkeys: (hotkeys: HotkeySettings) => Promise<void>;
+  saveLocale: (locale: Locale) => Promise<void>;
 };
 
 export const useSettingsStore = create<SettingsStore>((set) => ({
   capability: null,
   audioDevices: [],
+  displays: [],
   hotkeys: {
     startStop: "Ctrl+Shift+R",
     pauseResume: "Ctrl+Shift+P"
   },
+  locale: "zh-CN",
   loadSettings: async () => {
-    const [capabilityResult, audioDevicesResult, hotkeysResult] = await Promise.allSettled([
-      invoke<PlatformCapability>("get_platform_capability"),
-      invoke<RecordingDevice[]>("list_audio_input_devices"),
-      invoke<HotkeySettings>("load_hotkeys")
-    ]);
+    const [capabilityResult, audioDevicesResult, displaysResult, hotkeysResult, settingsResult] =
+      await Promise.allSettled([
+        invoke<PlatformCapability>("get_platform_capability"),
+        invoke<RecordingDevice[]>("list_audio_input_devices"),
+        invoke<DisplayInfo[]>("list_capture_displays"),
+        invoke<HotkeySettings>("load_hotkeys"),
+        invoke<AppSettings>("load_settings")
+      ]);
 
     set({
       capability: capabilityResult.status === "fulfilled" ? capabilityResult.value : null,
@@ -34,17 +46,25 @@ export const useSettingsStore = create<SettingsStore>((set) => ({
         audioDevicesResult.status === "fulfilled"
           ? audioDevicesResult.value
           : [],
+      displays: displaysResult.status === "fulfilled" ? displaysResult.value : [],
       hotkeys:
         hotkeysResult.status === "fulfilled"
           ? hotkeysResult.value
           : {
               startStop: "Ctrl+Shift+R",
               pauseResume: "Ctrl+Shift+P"
-            }
+            },
+      locale: settingsResult.status === "fulfilled" ? settingsResult.value.locale : "zh-CN"
     });
   },
+  setAudioDevices: (devices) => set({ audioDevices: devices }),
   saveHotkeys: async (hotkeys) => {
     await invoke("save_hotkeys", { hotkeys });
     set({ hotkeys });
+  },
+  saveLocale: async (locale) => {
+    const settings = await invoke<AppSettings>("load_settings");
+    await invoke("update_settings", { settings: { ...settings, locale } });
+    set({ locale });
   }
 }));
@@ -1,12 +1,14 @@
 export type CaptureMode = "fullscreen" | "window";
 export type Resolution = "1080p" | "720p";
+export type FrameRate = number;
+export const FRAME_RATE_PRESETS: FrameRate[] = [24, 25, 30, 48, 60];
 export type AspectRatio = "16:9" | "9:16" | "1:1";
 export type CameraIntensity = "low" | "medium" | "high";
 
 export type RecordingProfile = {
   captureMode: CaptureMode;
   windowTarget?: string;
-  frameRate: 30 | 60;
+  frameRate: FrameRate;
   resolution: Resolution;
   microphoneDeviceId?: string;
   systemAudioEnabled: boolean;
@@ -14,23 +16,121 @@ export type RecordingProfile = {
     startStop: string;
     pauseResume: string;
   };
+  recordingBitrateMbps?: number | null;
+  crf?: number;
+  preset?: EncoderPreset;
+  captureCursor?: boolean;
+  keystrokeCapture?: KeystrokeCaptureMode;
+  displayId?: string | null;
+  cursorSampleIntervalMs?: number;
+  adaptiveCursorSampling?: boolean;
+  doNotDisturb?: boolean;
 };
 
+export type KeystrokeCaptureMode = "off" | "full" | "masked";
+
+export type EncoderPreset =
+  | "ultrafast"
+  | "superfast"
+  | "veryfast"
+  | "faster"
+  | "fast"
+  | "medium";
+
+export type CameraMotionModel = "hybrid" | "spring";
+
+export type CameraFocusMode = "cursor" | "window";
+
+export type CameraMode = "zoomAndPan" | "panOnly" | "zoomOnly";
+
+export type CameraEasing =
+  | { type: "linear" }
+  | { type: "easeInOut" }
+  | { type: "cubicBezier"; x1: number; y1: number; x2: number; y2: number };
+
 export type CameraMotionProfile = {
   enabled: boolean;
   intensity: CameraIntensity;
   smoothing: number;
   maxZoom: number;
   idleThresholdMs: number;
+  zoomOnClick?: boolean;
+  clickHoldMs?: number;
+  model?: CameraMotionModel;
+  easing?: CameraEasing;
+  focusMode?: CameraFocusMode;
+  mode?: CameraMode;
 };
 
 export type ExportProfile = {
   format: "mp4";
   resolution: Resolution;
   bitrateMbps: number;
-  fps: 30 | 60;
+  fps: FrameRate;
   videoCodec: "h264";
   audioCodec: "aac";
+  audioDenoise?: AudioDenoise;
+  videoDenoise?: VideoDenoise;
+  frameInterpolation?: boolean;
+  clickSoundEnabled?: boolean;
+  clickSoundVolume?: number;
+  introPath?: string;
+  outroPath?: string;
+  subtitlePath?: string;
+  subtitleFontSize?: number;
+  subtitlePosition?: OverlayPosition;
+  aspectRatio?: AspectRatio;
+  lowPriority?: boolean;
+  timecode?: TimecodeMode;
+  timecodeCorner?: OverlayCorner;
+  maxSizeMb?: number;
+};
+
+export type TimelineThumbnail = {
+  tMs: number;
+  path: string;
+};
+
+export type EditHistoryStatus = {
+  canUndo: boolean;
+  canRedo: boolean;
+};
+
+export type AudioWaveform = {
+  samplesPerSecond: number;
+  peaks: number[];
+};
+
+export type ExportPreset = {
+  id: string;
+  name: string;
+  description: string;
+  profile: ExportProfile;
+  maxSizeMb?: number;
+};
+
+export type AudioDenoise = "off" | "light" | "strong";
+
+export type VideoDenoise = "off" | "light" | "medium" | "strong";
+
+export type AnimationFormat = "webp" | "apng";
+
+// 区间为源文件时间轴，loopCount 为 0 时无限循环；width、fps 缺省时分别为 800 与 15。
+export type AnimationExportOptions = {
+  format: AnimationFormat;
+  startMs: number;
+  endMs: number;
+  loopCount?: number;
+  width?: number;
+  fps?: number;
+};
+
+// 半径与羽化以 1080p 短边为基准的像素计，dim 为圈外压暗程度。
+export type CursorSpotlight = {
+  enabled: boolean;
+  radius: number;
+  dim: number;
+  feather: number;
 };
 
 export type TimelineConfig = {
@@ -38,31 +138,314 @@ export type TimelineConfig = {
   trimEndMs: number;
   aspectRatio: AspectRatio;
   cursorHighlightEnabled: boolean;
+  scrollIndicatorEnabled?: boolean;
+  fitMode?: FitMode;
+  cursorStyle?: CursorStyle;
+  cursorSize?: number;
+  spotlight?: CursorSpotlight;
+  autoCutSilence?: boolean;
+  silenceCutMode?: SilenceCutMode;
+  silentRanges?: TimeRange[];
+  idleSpeedupEnabled?: boolean;
+  idleSpeed?: number;
+  idleMinMs?: number;
+  segments?: TimelineSegment[];
+  blurRegions?: BlurRegion[];
+  muteRanges?: TimeRange[];
+  annotations?: TextAnnotation[];
+  shapes?: ShapeAnnotation[];
+  markers?: TimelineMarker[];
+};
+
+// 相对源画面的归一化矩形，取值 0..1。
+export type NormalizedRect = {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+};
+
+export type BlurMode = "blur" | "delogo";
+
+export type BlurRegion = {
+  id: string;
+  rect: NormalizedRect;
+  startMs: number;
+  endMs: number;
+  mode?: BlurMode;
+};
+
+// x/y 为文字框在画面剩余空间中的相对位置：0 贴左（上），1 贴右（下），0.5 居中。
+export type TextAnnotation = {
+  id: string;
+  text: string;
+  x: number;
+  y: number;
+  startMs: number;
+  endMs: number;
+  style?: AnnotationStyle;
+};
+
+export type AnnotationStyle = {
+  fontSize: number;
+  color: string;
+  background?: string | null;
+  backgroundOpacity: number;
+};
+
+export type ShapeKind = "arrow" | "rectangle" | "ellipse";
+
+// 相对最终画面的归一化坐标（0..1）。
+export type NormalizedPoint = {
+  x: number;
+  y: number;
+};
+
+// 矩形与椭圆以 from/to 为外接框对角；箭头由 from 指向 to。
+export type ShapeAnnotation = {
+  id: string;
+  kind: ShapeKind;
+  from: NormalizedPoint;
+  to: NormalizedPoint;
+  startMs: number;
+  endMs: number;
+  style?: ShapeStyle;
+};
+
+export type ShapeStyle = {
+  strokeColor: string;
+  strokeWidth: number;
+  fill?: string | null;
+  fillOpacity: number;
+};
+
+export type SensitiveKind = "email" | "api_key" | "card_number";
+
+// 敏感内容扫描提出的打码建议，preview 为脱敏后的识别文本。
+export type BlurCandidate = {
+  id: string;
+  kind: SensitiveKind;
+  rect: NormalizedRect;
+  startMs: number;
+  endMs: number;
+  preview: string;
+};
+
+export type SilenceCutMode = "drop" | "speed_up";
+
+export type TimeRange = {
+  startMs: number;
+  endMs: number;
+};
+
+export type MarkerSource = "manual" | "app_switch" | "transcript";
+
+// 章节标记；自动建议的 accepted 为 false，确认后才算正式章节。
+export type TimelineMarker = {
+  id: string;
+  tMs: number;
+  title: string;
+  source: MarkerSource;
+  accepted: boolean;
+};
+
+// speed 为该段播放倍速（0.5–4），缺省为 1。
+export type TimelineSegment = TimeRange & {
+  speed?: number;
+};
+
+export type QualityGateStatus = {
+  passed: boolean;
+  reasons: string[];
+  blackRanges: TimeRange[];
+  frozenRanges: TimeRange[];
+  silentRanges: TimeRange[];
+};
+
+export type CursorStyle = "arrow" | "halo";
+
+export type FitMode = "crop" | "pad" | "blur_background";
+
+export type SceneBackground =
+  | { type: "color"; color: string }
+  | { type: "gradient"; from: string; to: string }
+  | { type: "wallpaper"; path: string };
+
+export type SceneStyle = {
+  enabled: boolean;
+  background: SceneBackground;
+  cornerRadius: number;
+  padding: number;
+  shadow: number;
+};
+
+export type OverlayPosition = "bottom" | "top";
+
+export type OverlayCorner = "top_left" | "top_right" | "bottom_left" | "bottom_right";
+
+export type TimecodeMode = "off" | "elapsed" | "wall_clock";
+
+export type KeystrokeOverlay = {
+  enabled: boolean;
+  position: OverlayPosition;
+  fontSize: number;
+  opacity: number;
+  holdMs: number;
+};
+
+export type AudioMix = {
+  masterVolume: number;
+  micVolume: number;
+  systemVolume: number;
+  musicVolume: number;
+  musicPath?: string;
+};
+
+export type ColorGrade = {
+  brightness: number;
+  contrast: number;
+  saturation: number;
+  gamma: number;
+  lutPath?: string;
 };
 
 export type ProjectManifest = {
   schemaVersion: number;
   appVersion: string;
   title?: string | null;
+  tags?: string[];
+  notes?: string | null;
   createdAt: string;
   updatedAt: string;
+  recordingStartedAt?: string;
   recording: RecordingProfile;
   cameraMotion: CameraMotionProfile;
   export: ExportProfile;
   timeline: TimelineConfig;
+  scene?: SceneStyle;
+  keystrokeOverlay?: KeystrokeOverlay;
+  audioMix?: AudioMix;
+  colorGrade?: ColorGrade;
   artifacts: {
     rawRecordingPath?: string;
     cursorTrackPath?: string;
     lastExportPath?: string;
+    variantExportPaths?: string[];
+    captionPaths?: string[];
+    waveformPath?: string | null;
     exportLogPath?: string;
+    keyTrackPath?: string;
+    appTrackPath?: string;
+    refinedCursorTrackPath?: string | null;
+    editedCursorTrackPath?: string | null;
+    cursorTrackRevision?: number;
   };
   quality: {
     avOffsetMs: number;
     avgDropRate: number;
     peakDropRate: number;
+    blackRanges?: TimeRange[];
+    frozenRanges?: TimeRange[];
+    silentRanges?: TimeRange[];
+    analyzedDurationMs?: number;
   };
   status: ProjectStatus;
   lastError?: AppError | null;
+  uploads?: UploadRecord[];
+  blurCandidates?: BlurCandidate[];
+  exportHistory?: ExportHistoryEntry[];
+};
+
+/** `load_project` 的返回值，附带从 app_track.json 读取的前台应用切换，不写回清单。 */
+export type LoadedProject = ProjectManifest & {
+  appTrack?: AppFocusSegment[];
+};
+
+export type ExportUsage = {
+  label: string;
+  encoder: string;
+  cpuTimeMs: number;
+  wallTimeMs: number;
+  outputBytes: number;
+};
+
+export type ExportHistoryEntry = {
+  taskId: string;
+  finishedAt: string;
+  variants: ExportUsage[];
+};
+
+export type CursorRefineOptions = {
+  outlierJumpPx?: number;
+  maxGapMs?: number;
+  interpolationStepMs?: number;
+  smoothingWindow?: number;
+};
+
+export type CursorTrackPoint = {
+  tMs: number;
+  x: number;
+  y: number;
+  scrollDx?: number;
+  scrollDy?: number;
+  offscreen?: boolean;
+  click?: boolean;
+};
+
+export type TimeRange = {
+  startMs: number;
+  endMs: number;
+};
+
+export type QualityGateStatus = {
+  passed: boolean;
+  reasons: string[];
+  blackRanges: TimeRange[];
+  frozenRanges: TimeRange[];
+  silentRanges: TimeRange[];
+};
+
+export type MotionPathPoint = {
+  tMs: number;
+  x: number;
+  y: number;
+  zoom: number;
+};
+
+export type CameraMotionQuality = {
+  transitionLatencyMs: number;
+  idleJitterRatio: number;
+  path: MotionPathPoint[];
+};
+
+export type MotionPreviewProgressEvent = {
+  projectId: string;
+  status: "running" | "success" | "failed";
+  progress: number;
+};
+
+export type CursorTrackEdit =
+  | { type: "deleteSpan"; startMs: number; endMs: number }
+  | { type: "nudge"; tMs: number; dx: number; dy: number };
+
+export type CursorTrackSnapshot = {
+  revision: number;
+  points: CursorTrackPoint[];
+};
+
+export type AppFocusSegment = {
+  startMs: number;
+  endMs: number;
+  processName: string;
+  windowTitle: string;
+  windowRect?: WindowRect;
+};
+
+export type WindowRect = {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
 };
 
 export type ProjectStatus =
@@ -75,6 +458,8 @@ export type ProjectStatus =
 export type ProjectListItem = {
   projectId: string;
   title?: string | null;
+  tags: string[];
+  notes?: string | null;
   createdAt: string;
   updatedAt: string;
   status: ProjectStatus;
@@ -84,6 +469,63 @@ export type ProjectListItem = {
   rawPath?: string | null;
 };
 
+export type ProjectSortKey = "updated_desc" | "created_desc" | "title_asc" | "duration_desc";
+
+export type ProjectListQuery = {
+  page?: number;
+  pageSize?: number;
+  status?: ProjectStatus | null;
+  search?: string | null;
+  tags?: string[];
+  sort?: ProjectSortKey;
+};
+
+export type ProjectListPage = {
+  items: ProjectListItem[];
+  total: number;
+  page: number;
+  pageSize: number;
+};
+
+export type TranscriptCue = {
+  startMs: number;
+  endMs: number;
+  text: string;
+};
+
+export type TranscriptWord = {
+  index: number;
+  startMs: number;
+  endMs: number;
+  text: string;
+};
+
+// 首尾序号都包含在内。
+export type TranscriptWordRange = {
+  startIndex: number;
+  endIndex: number;
+};
+
+export type ProjectSearchResult = {
+  item: ProjectListItem;
+  transcriptHits: TranscriptCue[];
+};
+
+export type ManifestRevision = {
+  revisionId: string;
+  savedAt: string;
+  title?: string | null;
+  trimStartMs: number;
+  trimEndMs: number;
+};
+
+export type TrashedProject = {
+  projectId: string;
+  title?: string | null;
+  deletedAt: string;
+  durationMs: number;
+};
+
 export type RecoverableProject = {
   projectId: string;
   reason: string;
@@ -102,14 +544,32 @@ export type ExportStatus =
   | "running"
   | "fallback"
   | "success"
-  | "failed";
+  | "failed"
+  | "cancelled";
+
+export type ExportTaskSnapshot = {
+  taskId: string;
+  projectId: string;
+  status: ExportStatus;
+  retries: number;
+  lastError?: AppError;
+};
+
+export type ExportQueueSnapshot = {
+  maxConcurrent: number;
+  running: ExportTaskSnapshot[];
+  pending: ExportTaskSnapshot[];
+};
 
 export type AppError = {
   code: string;
   message: string;
   suggestion?: string;
+  params?: Record<string, string>;
 };
 
+export type Locale = "zh-CN" | "en-US";
+
 export type PlatformCapability = {
   platform: string;
   supportsScreenCapture: boolean;
@@ -117,6 +577,34 @@ export type PlatformCapability = {
   supportsMicrophone: boolean;
   supportsSystemAudio: boolean;
   systemAudioDegradeMessage?: string;
+  supportsKeystrokeCapture: boolean;
+  frameRatePresets: FrameRate[];
+};
+
+export type FfmpegSource = "env" | "managed" | "sidecar" | "system";
+
+export type FfmpegInstallStatus = {
+  source: FfmpegSource;
+  ffmpegPath: string;
+  ffprobePath: string;
+  managedInstalled: boolean;
+  downloadAvailable: boolean;
+  pinnedVersion?: string | null;
+};
+
+export type FfmpegInfo = {
+  path: string;
+  source: FfmpegSource;
+  available: boolean;
+  version?: string | null;
+  majorVersion?: number | null;
+  configuration: string[];
+  encoders: string[];
+  devices: string[];
+  filters: string[];
+  compatible: boolean;
+  errors: AppError[];
+  warnings: AppError[];
 };
 
 export type RecordingDevice = {
@@ -125,11 +613,111 @@ export type RecordingDevice = {
   kind: string;
 };
 
+export type DevicesChangedEvent = {
+  added: RecordingDevice[];
+  removed: RecordingDevice[];
+  devices: RecordingDevice[];
+};
+
+export type CaptureBounds = {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  scaleFactor: number;
+};
+
+export type DisplayInfo = {
+  id: string;
+  label: string;
+  primary: boolean;
+  bounds: CaptureBounds;
+};
+
 export type HotkeySettings = {
   startStop: string;
   pauseResume: string;
 };
 
+export type CleanupPolicy = {
+  autoPurgeTrash: boolean;
+  trashRetentionDays: number;
+};
+
+export type AppSettings = {
+  schemaVersion: number;
+  hotkeys: HotkeySettings;
+  exportPresets: ExportPreset[];
+  locale: Locale;
+  defaultRecordingProfile: RecordingProfile;
+  defaultExportPresetId?: string | null;
+  projectRoot?: string | null;
+  telemetryOptIn: boolean;
+  cleanup: CleanupPolicy;
+  autoExportAfterRecording: boolean;
+  s3?: S3Settings | null;
+  share: SharePolicy;
+  maxConcurrentExports: number;
+  encoderThreads: number;
+};
+
+export type SharePolicy = {
+  enabled: boolean;
+  port: number;
+  linkExpiryHours: number;
+};
+
+export type ShareLink = {
+  token: string;
+  projectId: string;
+  url: string;
+  createdAt: string;
+  expiresAt: string;
+};
+
+export type S3Settings = {
+  endpoint: string;
+  region: string;
+  bucket: string;
+  accessKeyId: string;
+  /** 读取设置时始终为空字符串；保存时留空表示沿用已保存的密钥。 */
+  secretAccessKey: string;
+  pathStyle: boolean;
+  keyPrefix: string;
+  linkExpiryHours: number;
+};
+
+export type StopRecordingResult = {
+  projectId: string;
+  exportTaskId?: string | null;
+  exportError?: AppError | null;
+};
+
+export type CaptureTestReport = {
+  ready: boolean;
+  width?: number | null;
+  height?: number | null;
+  fps?: number | null;
+  hasAudio: boolean;
+  meanVolumeDb?: number | null;
+  maxVolumeDb?: number | null;
+  errors: AppError[];
+  warnings: AppError[];
+};
+
+export type AudioLevelEvent = {
+  sessionId: string;
+  tMs: number;
+  rmsDb: number;
+  peakDb: number;
+};
+
+export type PreviewFrameEvent = {
+  sessionId: string;
+  tMs: number;
+  dataUrl: string;
+};
+
 export type RecordingStatusEvent = {
   sessionId: string;
   status: RecordingRuntimeStatus;
@@ -138,3 +726,73 @@ export type RecordingStatusEvent = {
   detail: string;
   degradeMessage?: string;
 };
+
+export type ExportProgressEvent = {
+  taskId: string;
+  status: ExportStatus;
+  progress: number;
+  detail: string;
+  variant?: { index: number; count: number; progress: number };
+  usage?: ExportUsage[];
+};
+
+export type ProjectArchiveProgressEvent = {
+  projectId: string;
+  progress: number;
+};
+
+export type RecoveryEvent = {
+  projectId: string;
+  status: "repaired" | "recovered";
+};
+
+export type FfmpegInstallProgressEvent = {
+  stage: "downloading" | "verifying" | "extracting" | "completed";
+  downloadedBytes: number;
+  totalBytes?: number | null;
+  progress: number;
+};
+
+export type UploadTarget = "youtube" | "googleDrive" | "s3";
+
+export type UploadStatus = "uploading" | "completed" | "failed";
+
+export type UploadRecord = {
+  target: UploadTarget;
+  status: UploadStatus;
+  filePath: string;
+  totalBytes: number;
+  sessionUri?: string | null;
+  remoteId?: string | null;
+  url?: string | null;
+  urlExpiresAt?: string | null;
+  startedAt: string;
+  completedAt?: string | null;
+  lastError?: AppError | null;
+};
+
+export type UploadProgressEvent = {
+  projectId: string;
+  target: UploadTarget;
+  uploadedBytes: number;
+  totalBytes: number;
+  progress: number;
+};
+
+// 与后端 domain/events.rs 的 EVENT_SCHEMA_VERSION 保持一致。
+export const EVENT_SCHEMA_VERSION = 1;
+
+export type EventEnvelope<T> = T & { schemaVersion: number };
+
+export type AppEventMap = {
+  "recording/status": RecordingStatusEvent;
+  "recording/audio-level": AudioLevelEvent;
+  "recording/preview": PreviewFrameEvent;
+  "export/progress": ExportProgressEvent;
+  "preview/progress": MotionPreviewProgressEvent;
+  "project/archive-progress": ProjectArchiveProgressEvent;
+  "recovery/status": RecoveryEvent;
+  "ffmpeg/install-progress": FfmpegInstallProgressEvent;
+  "upload/progress": UploadProgressEvent;
+  "devices/changed": DevicesChangedEvent;
+};
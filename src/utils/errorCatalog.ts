@@ -0,0 +1,1201 @@
+import type { AppError, Locale } from "../types/project";
+
+type CatalogEntry = {
+  message: string;
+  suggestion?: string;
+};
+
+type ErrorCatalog = Record<string, CatalogEntry>;
+
+const zhCN: ErrorCatalog = {
+  FFMPEG_NOT_FOUND: {
+    message: "未找到可用的 ffmpeg",
+    suggestion: "可在设置中一键安装 ffmpeg，或设置 FOCUSLENS_FFMPEG_PATH"
+  },
+  FFMPEG_VERSION_UNSUPPORTED: {
+    message: "ffmpeg 版本过旧（{version}.x），最低需要 {min}.0",
+    suggestion: "请升级 ffmpeg，或在设置中安装推荐版本"
+  },
+  FFMPEG_MISSING_ENCODER: {
+    message: "当前 ffmpeg 缺少编码器 {name}",
+    suggestion: "请改用包含 libx264 的完整构建（如 full/gpl 版本）"
+  },
+  FFMPEG_MISSING_DEVICE: {
+    message: "当前 ffmpeg 缺少采集设备 {name}",
+    suggestion: "请改用官方完整构建"
+  },
+  FFMPEG_MISSING_FILTER: {
+    message: "当前 ffmpeg 缺少滤镜 {name}",
+    suggestion: "请改用官方完整构建"
+  },
+  FFMPEG_INSTALL_UNAVAILABLE: {
+    message: "当前版本未提供 ffmpeg 下载",
+    suggestion: "请使用内置 ffmpeg 的安装包，或设置 FOCUSLENS_FFMPEG_PATH"
+  },
+  FFMPEG_INSTALL_BUSY: {
+    message: "ffmpeg 正在安装中",
+    suggestion: "请等待当前安装完成"
+  },
+  FFMPEG_DOWNLOAD_FAIL: {
+    message: "ffmpeg 下载失败",
+    suggestion: "检查网络连接后重试"
+  },
+  FFMPEG_CHECKSUM_MISMATCH: {
+    message: "ffmpeg 下载内容校验失败",
+    suggestion: "下载内容可能被篡改或不完整，请重试"
+  },
+  FFMPEG_INSTALL_FAIL: {
+    message: "ffmpeg 安装失败",
+    suggestion: "确认磁盘空间和应用数据目录权限"
+  },
+  FFMPEG_TIMEOUT: {
+    message: "ffmpeg 长时间无响应，已终止",
+    suggestion: "请检查输入文件，或调大超时配置后重试"
+  },
+  PROJECT_NOT_FOUND: {
+    message: "项目不存在：{projectId}",
+    suggestion: "先完成一次录制生成项目"
+  },
+  PROJECT_BUSY: {
+    message: "项目正在录制或导出中，暂时无法操作",
+    suggestion: "请等待录制或导出结束后重试"
+  },
+  INVALID_PROJECT_ID: {
+    message: "项目 ID 无效",
+    suggestion: "请使用列表中的项目，不要手工输入路径"
+  },
+  INVALID_FRAME_RATE: {
+    message: "帧率 {frameRate} 超出范围 {min}-{max}",
+    suggestion: "请选择 24/25/30/48/60 等常用帧率"
+  },
+  INVALID_NOTES: {
+    message: "备注过长",
+    suggestion: "备注最多 {max} 个字符"
+  },
+  EXPORT_TOO_MANY_VARIANTS: {
+    message: "导出版本过多",
+    suggestion: "一次最多附加 {max} 个导出版本"
+  },
+  EXPORT_PRESET_NOT_FOUND: {
+    message: "导出预设不存在：{presetId}",
+    suggestion: "请刷新预设列表后重试"
+  },
+  INVALID_PROJECT_ROOT: {
+    message: "项目目录必须是完整路径：{path}",
+    suggestion: "请选择一个完整的文件夹路径"
+  },
+  INVALID_CLEANUP_POLICY: {
+    message: "回收站保留天数无效",
+    suggestion: "保留天数至少为 1 天，或关闭自动清理"
+  },
+  EXPORT_TASK_NOT_FOUND: {
+    message: "导出任务不存在：{taskId}",
+    suggestion: "请重新发起导出"
+  },
+  EXPORT_ALREADY_ACTIVE: {
+    message: "该项目已有进行中的导出任务",
+    suggestion: "请等待当前导出完成或先取消"
+  },
+  NO_SPACE: {
+    message: "磁盘空间不足，导出失败",
+    suggestion: "释放空间后重试导出"
+  },
+  NO_PERMISSION: {
+    message: "导出路径无权限，无法写入目标文件",
+    suggestion: "请切换到有写入权限的路径后重试"
+  },
+  RECORDING_ALREADY_ACTIVE: {
+    message: "已有录制正在进行",
+    suggestion: "请先停止当前录制"
+  },
+  RECORDING_UNRECOVERABLE: {
+    message: "录制文件已损坏，无法修复",
+    suggestion: "该录制缺少可用的视频数据"
+  },
+  RECORDING_NEEDS_REPAIR: {
+    message: "录制文件不完整，需要先修复",
+    suggestion: "请点击“修复录制”后再恢复"
+  },
+  REVISION_NOT_FOUND: {
+    message: "历史版本不存在：{revisionId}",
+    suggestion: "请刷新修订列表后重试"
+  },
+  POSTER_EXPORT_FAILED: {
+    message: "封面导出失败",
+    suggestion: "请确认录制文件完整，且时间点在录制时长以内"
+  },
+  PROJECT_INDEX_ERROR: {
+    message: "项目索引读写失败",
+    suggestion: "请重建项目索引后重试"
+  },
+  ANIMATION_RANGE_INVALID: {
+    message: "动图区间无效",
+    suggestion: "请重新选择动图区间"
+  },
+  ANIMATION_TOO_LONG: {
+    message: "动图超过 {seconds} 秒上限",
+    suggestion: "更长的片段请导出为视频"
+  },
+  ANIMATION_LOOP_INVALID: {
+    message: "循环次数不能超过 {max}",
+    suggestion: "循环次数填 0 表示无限循环"
+  },
+  EXPORT_CAPTION_WRITE_FAIL: {
+    message: "字幕文件写入失败",
+    suggestion: "请检查 renders 目录的写入权限与磁盘空间"
+  },
+  TRANSCRIPT_NOT_FOUND: {
+    message: "项目没有文字稿",
+    suggestion: "请先在导出设置中挂载字幕文件"
+  },
+  TRANSCRIPT_RANGE_INVALID: {
+    message: "所选文字超出了文字稿范围",
+    suggestion: "文字稿已变化，请刷新后重新选择"
+  },
+  TRASH_ITEM_NOT_FOUND: {
+    message: "回收站中没有该项目：{projectId}",
+    suggestion: "请刷新回收站列表后重试"
+  },
+  UPLOAD_NOT_CONFIGURED: {
+    message: "当前版本未启用上传",
+    suggestion: "请使用已配置上传的正式版本"
+  },
+  UPLOAD_NOT_CONNECTED: {
+    message: "尚未连接 {target} 账号",
+    suggestion: "请先连接账号再上传"
+  },
+  UPLOAD_AUTH_EXPIRED: {
+    message: "上传授权已失效",
+    suggestion: "请重新连接账号"
+  },
+  UPLOAD_AUTH_DENIED: {
+    message: "已取消账号授权",
+    suggestion: "如需上传，请重新连接并允许访问"
+  },
+  UPLOAD_AUTH_TIMEOUT: {
+    message: "等待浏览器授权超时",
+    suggestion: "请重新连接并在浏览器中完成授权"
+  },
+  UPLOAD_AUTH_FAIL: {
+    message: "账号授权失败",
+    suggestion: "请稍后重试"
+  },
+  UPLOAD_NETWORK_ERROR: {
+    message: "上传时网络连接失败",
+    suggestion: "检查网络连接后重试，已上传部分会继续续传"
+  },
+  UPLOAD_FAIL: {
+    message: "上传失败",
+    suggestion: "稍后重试，已上传部分会继续续传"
+  },
+  UPLOAD_FORBIDDEN: {
+    message: "服务端拒绝了上传",
+    suggestion: "请确认账号有上传权限，或重新连接账号"
+  },
+  UPLOAD_QUOTA_EXCEEDED: {
+    message: "已达到上传配额",
+    suggestion: "请稍后再试"
+  },
+  UPLOAD_READ_FAIL: {
+    message: "无法读取导出文件",
+    suggestion: "请先完成导出，并确认文件仍然存在"
+  },
+  UPLOAD_ALREADY_ACTIVE: {
+    message: "该项目正在上传到 {target}",
+    suggestion: "请等待当前上传完成"
+  },
+  UPLOAD_TOKEN_WRITE_FAIL: {
+    message: "无法保存账号授权",
+    suggestion: "确认应用数据目录可写"
+  },
+  UPLOAD_NOT_FOUND: {
+    message: "该项目尚未上传到 {target}",
+    suggestion: "请先上传导出文件"
+  },
+  INVALID_S3_SETTINGS: {
+    message: "对象存储配置无效：{field}",
+    suggestion: "请填写完整的服务地址、区域、存储桶与访问密钥"
+  },
+  S3_BUCKET_NOT_FOUND: {
+    message: "找不到对象存储桶",
+    suggestion: "确认存储桶名称、区域与路径风格设置"
+  },
+  S3_CREDENTIALS_REJECTED: {
+    message: "对象存储拒绝了访问密钥",
+    suggestion: "确认访问密钥、私有密钥与区域设置"
+  },
+  SHARE_DISABLED: {
+    message: "局域网分享未开启",
+    suggestion: "请先在设置中开启局域网分享"
+  },
+  SHARE_SERVER_START_FAIL: {
+    message: "无法在端口 {port} 启动分享服务",
+    suggestion: "端口可能已被占用，请在设置中更换分享端口"
+  },
+  INVALID_SHARE_POLICY: {
+    message: "分享链接有效期无效",
+    suggestion: "分享链接有效期至少为 1 小时"
+  },
+  INVALID_BLUR_REGION: {
+    message: "打码区域无效",
+    suggestion: "请框选画面内的区域，并确保结束时间晚于开始时间"
+  },
+  INVALID_MUTE_RANGE: {
+    message: "静音区间无效",
+    suggestion: "请确保静音区间的结束时间晚于开始时间"
+  },
+  BLUR_REGION_NOT_FOUND: {
+    message: "找不到打码区域 {regionId}",
+    suggestion: "该区域可能已被删除，请刷新后重试"
+  },
+  OCR_ENGINE_NOT_FOUND: {
+    message: "未找到 OCR 引擎",
+    suggestion: "请安装 Tesseract OCR，或设置 FOCUSLENS_TESSERACT_PATH"
+  },
+  SENSITIVE_SCAN_FAILED: {
+    message: "敏感内容扫描失败",
+    suggestion: "确认录制文件完整、Tesseract OCR 可正常运行后重试"
+  },
+  BLUR_CANDIDATE_NOT_FOUND: {
+    message: "找不到打码建议 {candidateId}",
+    suggestion: "该建议可能已被处理，请刷新后重试"
+  },
+  INVALID_TEXT_ANNOTATION: {
+    message: "文字标注无效",
+    suggestion: "请检查标注文字、颜色与起止时间"
+  },
+  TEXT_ANNOTATION_NOT_FOUND: {
+    message: "找不到文字标注 {annotationId}",
+    suggestion: "该标注可能已被删除，请刷新后重试"
+  },
+  INVALID_SHAPE_ANNOTATION: {
+    message: "形状标注无效",
+    suggestion: "请在画面上拖出更大的形状，并检查颜色与起止时间"
+  },
+  SHAPE_ANNOTATION_NOT_FOUND: {
+    message: "找不到形状标注 {shapeId}",
+    suggestion: "该形状可能已被删除，请刷新后重试"
+  },
+  LUT_FILE_INVALID: {
+    message: "LUT 文件无效 {path}",
+    suggestion: "请选择已存在的 .cube LUT 文件"
+  },
+  MUSIC_FILE_NOT_FOUND: {
+    message: "找不到背景音乐文件 {path}",
+    suggestion: "请确认背景音乐文件路径"
+  },
+  CAPTURE_TEST_NO_VIDEO: {
+    message: "试录文件中没有可用的视频画面",
+    suggestion: "请检查屏幕录制权限与所选显示器或窗口"
+  },
+  CAPTURE_TEST_NO_AUDIO: {
+    message: "试录文件中没有音轨",
+    suggestion: "请检查麦克风权限与音频设备"
+  },
+  CAPTURE_TEST_LOW_FPS: {
+    message: "试录帧率 {fps} 低于目标 {target}",
+    suggestion: "请降低帧率或分辨率，或改用硬件编码"
+  },
+  CAPTURE_TEST_SILENT_AUDIO: {
+    message: "试录音轨没有声音",
+    suggestion: "请确认麦克风未静音，并检查系统的麦克风权限"
+  },
+  CAPTURE_TEST_DEGRADED: {
+    message: "{detail}"
+  },
+  INVALID_EXPORT_CONCURRENCY: {
+    message: "同时导出数量无效",
+    suggestion: "同时导出数量请设为 1~{max}"
+  },
+  INVALID_ENCODER_THREADS: {
+    message: "编码线程数无效",
+    suggestion: "编码线程数请设为 0（自动）~{max}"
+  },
+  PLATFORM_NOT_SUPPORTED: {
+    message: "当前平台不支持录制",
+    suggestion: "目前仅支持 Windows 与 macOS"
+  },
+  SESSION_NOT_FOUND: {
+    message: "录制会话不存在",
+    suggestion: "录制可能已结束，请刷新后重试"
+  },
+  INVALID_RECORDING_STATE: {
+    message: "当前录制状态不支持该操作",
+    suggestion: "请确认录制已开始，或等待当前操作完成"
+  },
+  RECORDING_START_FAIL: {
+    message: "录制启动失败",
+    suggestion: "请检查录制权限与采集设备"
+  },
+  RECORDING_STOP_FAIL: {
+    message: "录制停止失败",
+    suggestion: "录制进程可能已退出，请刷新项目列表确认录制文件"
+  },
+  RECORDING_PROCESS_IO: {
+    message: "无法向录制进程发送指令",
+    suggestion: "录制进程可能已退出，请停止后重新录制"
+  },
+  RECORDING_OUTPUT_MISSING: {
+    message: "录制未生成有效视频文件",
+    suggestion: "请检查麦克风/系统音频设备后重试录制"
+  },
+  RECORDING_JOIN_FAILED: {
+    message: "录制分段合并失败",
+    suggestion: "请确认录制文件完整后重试修复"
+  },
+  RECORDING_REPAIR_FAILED: {
+    message: "录制修复失败",
+    suggestion: "请重试，或保留原始文件并反馈问题"
+  },
+  RAW_RECORDING_MISSING: {
+    message: "原始录制文件不存在",
+    suggestion: "该项目没有可修复的录制文件"
+  },
+  RECOVERY_FAILED: {
+    message: "录制恢复失败",
+    suggestion: "请先修复录制后再恢复"
+  },
+  AUDIO_METER_START_FAIL: {
+    message: "无法启动音量监测",
+    suggestion: "不影响录制，可在录制结束后检查音轨"
+  },
+  DO_NOT_DISTURB_FAILED: {
+    message: "未能开启系统勿扰模式",
+    suggestion: "请手动开启系统的勿扰/专注模式"
+  },
+  HOTKEY_CONFLICT: {
+    message: "开始/停止与暂停/继续不能使用相同的快捷键",
+    suggestion: "请为两个操作设置不同的快捷键"
+  },
+  HOTKEY_REGISTER_FAIL: {
+    message: "快捷键注册失败",
+    suggestion: "请检查快捷键格式，或是否已被其他应用占用"
+  },
+  CURSOR_TRACK_MISSING: {
+    message: "项目缺少光标轨迹"
+  },
+  CURSOR_TRACK_READ_FAIL: {
+    message: "光标轨迹读取失败",
+    suggestion: "请确认项目文件完整"
+  },
+  CURSOR_TRACK_PARSE_FAIL: {
+    message: "光标轨迹文件已损坏",
+    suggestion: "请确认项目文件完整"
+  },
+  FFMPEG_EXEC_ERROR: {
+    message: "ffmpeg 运行失败",
+    suggestion: "确认 ffmpeg 安装状态并检查导出参数"
+  },
+  FFPROBE_EXEC_ERROR: {
+    message: "ffprobe 运行失败",
+    suggestion: "请确认 ffprobe 可用且媒体文件完整"
+  },
+  FFPROBE_PARSE_ERROR: {
+    message: "无法解析媒体信息",
+    suggestion: "请确认媒体文件完整"
+  },
+  THUMBNAIL_FAILED: {
+    message: "缩略图生成失败",
+    suggestion: "请确认录制文件完整可播放"
+  },
+  WAVEFORM_FAILED: {
+    message: "波形生成失败",
+    suggestion: "请确认录制文件包含音轨"
+  },
+  SILENCE_DETECT_FAILED: {
+    message: "静音分析失败",
+    suggestion: "请确认录制文件包含音轨"
+  },
+  FRAME_EXTRACT_FAILED: {
+    message: "截帧失败",
+    suggestion: "请确认录制文件完整，且时间点在录制时长以内"
+  },
+  FRAME_OUTPUT_INVALID: {
+    message: "截帧只能保存为 .png 文件",
+    suggestion: "请选择 .png 格式的保存路径"
+  },
+  FRAME_OUT_OF_RANGE: {
+    message: "时间点超出录制时长",
+    suggestion: "请选择录制时长以内的时间点"
+  },
+  CONTENT_ANALYSIS_FAILED: {
+    message: "画面内容分析失败",
+    suggestion: "请确认导出文件完整可播放"
+  },
+  IMPORT_VIDEO_INVALID: {
+    message: "无法导入该视频",
+    suggestion: "请选择包含视频轨的本地 .mp4 文件"
+  },
+  IMPORT_VIDEO_FAILED: {
+    message: "视频导入失败",
+    suggestion: "确认磁盘空间和路径权限"
+  },
+  EXPORT_CANCELLED: {
+    message: "导出已取消"
+  },
+  EXPORT_NOT_ACTIVE: {
+    message: "导出任务未在进行",
+    suggestion: "导出任务已结束，无需取消"
+  },
+  EXPORT_NOT_FOUND: {
+    message: "项目还没有导出文件",
+    suggestion: "请先完成一次导出"
+  },
+  EXPORT_FILE_MISSING: {
+    message: "导出文件不存在",
+    suggestion: "成片可能已被移动或删除，请重新导出"
+  },
+  EXPORT_QUEUE_MISMATCH: {
+    message: "导出队列已变化",
+    suggestion: "请刷新导出队列后重试"
+  },
+  EXPORT_TASK_FAIL: {
+    message: "导出任务异常退出",
+    suggestion: "请查看导出日志并重试"
+  },
+  EXPORT_CLIP_MISSING: {
+    message: "片头/片尾文件不存在",
+    suggestion: "请重新选择片头/片尾文件"
+  },
+  EXPORT_CLIP_INVALID: {
+    message: "片头/片尾文件无法读取",
+    suggestion: "请确认片头/片尾文件是可播放的视频"
+  },
+  EXPORT_SUBTITLE_MISSING: {
+    message: "字幕文件不存在",
+    suggestion: "请重新选择字幕文件"
+  },
+  EXPORT_SUBTITLE_UNSUPPORTED: {
+    message: "不支持的字幕格式",
+    suggestion: "请使用 SRT、VTT 或 ASS 字幕"
+  },
+  ENCODER_FAIL: {
+    message: "编码器初始化失败",
+    suggestion: "将自动回退软件编码，或检查本机编码器驱动"
+  },
+  IO_FAIL: {
+    message: "导出失败",
+    suggestion: "请查看导出日志并重试"
+  },
+  INVALID_EXPORT_STATE: {
+    message: "导出任务状态异常",
+    suggestion: "请刷新导出队列后重试"
+  },
+  PREVIEW_RANGE_INVALID: {
+    message: "预览区间无效",
+    suggestion: "请重新选择预览区间"
+  },
+  PREVIEW_TASK_FAIL: {
+    message: "预览生成异常退出",
+    suggestion: "请重新生成预览"
+  },
+  PROJECT_ASSET_MISSING: {
+    message: "项目文件缺失"
+  },
+  PROJECT_ALREADY_EXISTS: {
+    message: "项目已存在",
+    suggestion: "请先删除现有项目，再从回收站恢复"
+  },
+  PROJECT_DELETE_FAIL: {
+    message: "项目删除失败",
+    suggestion: "请关闭占用该项目文件的程序后重试"
+  },
+  PROJECT_LIST_READ_FAIL: {
+    message: "项目列表读取失败",
+    suggestion: "请检查项目目录是否可读"
+  },
+  TRASH_IO_ERROR: {
+    message: "回收站操作失败",
+    suggestion: "确认磁盘空间和路径权限"
+  },
+  INVALID_REVISION_ID: {
+    message: "历史版本 ID 无效",
+    suggestion: "请使用修订列表中的记录"
+  },
+  EDIT_HISTORY_EMPTY: {
+    message: "没有可撤销或重做的编辑"
+  },
+  INVALID_TIMELINE: {
+    message: "时间线设置无效"
+  },
+  INVALID_TAG: {
+    message: "标签无效"
+  },
+  INVALID_SCENE_COLOR: {
+    message: "背景颜色无效",
+    suggestion: "请使用 #RRGGBB 格式的颜色"
+  },
+  SCENE_WALLPAPER_MISSING: {
+    message: "壁纸图片不存在",
+    suggestion: "请重新选择壁纸图片"
+  },
+  MIGRATION_ERROR: {
+    message: "文件格式无效，无法升级",
+    suggestion: "项目或设置文件可能已损坏"
+  },
+  UNSUPPORTED_SCHEMA: {
+    message: "文件由更新版本的 FocusLens 创建",
+    suggestion: "请升级应用后重试"
+  },
+  UNSUPPORTED_ARCHIVE: {
+    message: "归档由更新版本的 FocusLens 创建",
+    suggestion: "请升级应用后重试"
+  },
+  ARCHIVE_NOT_FOUND: {
+    message: "无法打开归档文件",
+    suggestion: "确认归档文件路径是否正确"
+  },
+  ARCHIVE_INVALID: {
+    message: "不是有效的 FocusLens 归档",
+    suggestion: "请选择由 FocusLens 打包的 .focuslens 文件"
+  },
+  ARCHIVE_IO_ERROR: {
+    message: "归档读写失败",
+    suggestion: "确认磁盘空间和路径权限"
+  },
+  ARCHIVE_PATH_INVALID: {
+    message: "未选择打包路径",
+    suggestion: "请选择 .focuslens 文件的保存位置"
+  },
+  SETTINGS_READ_FAIL: {
+    message: "设置读取失败",
+    suggestion: "请确认应用数据目录可读"
+  },
+  SETTINGS_PARSE_FAIL: {
+    message: "设置文件已损坏",
+    suggestion: "可删除 settings.json 后重启应用，恢复默认设置"
+  },
+  SETTINGS_WRITE_FAIL: {
+    message: "设置保存失败",
+    suggestion: "确认磁盘空间和应用数据目录权限"
+  },
+  IO_ERROR: {
+    message: "文件读写失败",
+    suggestion: "确认磁盘空间和路径权限"
+  },
+  SERDE_ERROR: {
+    message: "数据格式无效",
+    suggestion: "相关文件可能已损坏"
+  },
+  SHELL_OPEN_FAILED: {
+    message: "无法打开系统文件管理器",
+    suggestion: "请手动打开导出目录"
+  },
+  EVENT_ERROR: {
+    message: "界面状态同步失败",
+    suggestion: "请重启应用后重试"
+  }
+};
+
+const enUS: ErrorCatalog = {
+  FFMPEG_NOT_FOUND: {
+    message: "No usable ffmpeg was found",
+    suggestion: "Install ffmpeg from Settings, or set FOCUSLENS_FFMPEG_PATH"
+  },
+  FFMPEG_VERSION_UNSUPPORTED: {
+    message: "ffmpeg {version}.x is too old; {min}.0 or newer is required",
+    suggestion: "Upgrade ffmpeg, or install the recommended build from Settings"
+  },
+  FFMPEG_MISSING_ENCODER: {
+    message: "This ffmpeg build lacks the {name} encoder",
+    suggestion: "Use a full (GPL) build that includes libx264"
+  },
+  FFMPEG_MISSING_DEVICE: {
+    message: "This ffmpeg build lacks the {name} capture device",
+    suggestion: "Use an official full build"
+  },
+  FFMPEG_MISSING_FILTER: {
+    message: "This ffmpeg build lacks the {name} filter",
+    suggestion: "Use an official full build"
+  },
+  FFMPEG_INSTALL_UNAVAILABLE: {
+    message: "This build does not offer an ffmpeg download",
+    suggestion: "Use an installer with bundled ffmpeg, or set FOCUSLENS_FFMPEG_PATH"
+  },
+  FFMPEG_INSTALL_BUSY: {
+    message: "ffmpeg is already being installed",
+    suggestion: "Wait for the current installation to finish"
+  },
+  FFMPEG_DOWNLOAD_FAIL: {
+    message: "Failed to download ffmpeg",
+    suggestion: "Check your network connection and try again"
+  },
+  FFMPEG_CHECKSUM_MISMATCH: {
+    message: "The ffmpeg download failed checksum verification",
+    suggestion: "The download may be corrupted or tampered with; try again"
+  },
+  FFMPEG_INSTALL_FAIL: {
+    message: "Failed to install ffmpeg",
+    suggestion: "Check free disk space and app data folder permissions"
+  },
+  FFMPEG_TIMEOUT: {
+    message: "ffmpeg stopped responding and was terminated",
+    suggestion: "Check the input file, or raise the timeout setting and try again"
+  },
+  PROJECT_NOT_FOUND: {
+    message: "Project not found: {projectId}",
+    suggestion: "Finish a recording first to create a project"
+  },
+  PROJECT_BUSY: {
+    message: "The project is recording or exporting",
+    suggestion: "Try again once recording or export has finished"
+  },
+  INVALID_PROJECT_ID: {
+    message: "Invalid project ID",
+    suggestion: "Pick a project from the list instead of typing a path"
+  },
+  INVALID_FRAME_RATE: {
+    message: "Frame rate {frameRate} is outside {min}-{max}",
+    suggestion: "Choose a common frame rate such as 24/25/30/48/60"
+  },
+  INVALID_NOTES: {
+    message: "Notes are too long",
+    suggestion: "Notes are limited to {max} characters"
+  },
+  EXPORT_TOO_MANY_VARIANTS: {
+    message: "Too many export variants",
+    suggestion: "At most {max} extra variants can be added per export"
+  },
+  EXPORT_PRESET_NOT_FOUND: {
+    message: "Export preset not found: {presetId}",
+    suggestion: "Refresh the preset list and try again"
+  },
+  INVALID_PROJECT_ROOT: {
+    message: "The project folder must be an absolute path: {path}",
+    suggestion: "Choose a full folder path"
+  },
+  INVALID_CLEANUP_POLICY: {
+    message: "Invalid trash retention period",
+    suggestion: "Keep items for at least 1 day, or turn off automatic cleanup"
+  },
+  EXPORT_TASK_NOT_FOUND: {
+    message: "Export task not found: {taskId}",
+    suggestion: "Start the export again"
+  },
+  EXPORT_ALREADY_ACTIVE: {
+    message: "This project already has an export in progress",
+    suggestion: "Wait for it to finish or cancel it first"
+  },
+  NO_SPACE: {
+    message: "Export failed: not enough disk space",
+    suggestion: "Free up some space and export again"
+  },
+  NO_PERMISSION: {
+    message: "Export failed: the output path is not writable",
+    suggestion: "Choose a location you have write access to"
+  },
+  RECORDING_ALREADY_ACTIVE: {
+    message: "A recording is already in progress",
+    suggestion: "Stop the current recording first"
+  },
+  RECORDING_UNRECOVERABLE: {
+    message: "The recording is damaged beyond repair",
+    suggestion: "The file contains no usable video data"
+  },
+  RECORDING_NEEDS_REPAIR: {
+    message: "The recording is incomplete and needs repair",
+    suggestion: "Use “Repair recording” before recovering it"
+  },
+  REVISION_NOT_FOUND: {
+    message: "Revision not found: {revisionId}",
+    suggestion: "Refresh the revision list and try again"
+  },
+  POSTER_EXPORT_FAILED: {
+    message: "Failed to export the cover image",
+    suggestion: "Make sure the recording is intact and the time is within its duration"
+  },
+  PROJECT_INDEX_ERROR: {
+    message: "Failed to read or write the project index",
+    suggestion: "Rebuild the project index and try again"
+  },
+  ANIMATION_RANGE_INVALID: {
+    message: "Invalid animation range",
+    suggestion: "Select the animation range again"
+  },
+  ANIMATION_TOO_LONG: {
+    message: "Animations are limited to {seconds} seconds",
+    suggestion: "Export longer clips as video instead"
+  },
+  ANIMATION_LOOP_INVALID: {
+    message: "Loop count cannot exceed {max}",
+    suggestion: "Use 0 to loop forever"
+  },
+  EXPORT_CAPTION_WRITE_FAIL: {
+    message: "Failed to write caption files",
+    suggestion: "Check write permission and free space for the renders folder"
+  },
+  TRANSCRIPT_NOT_FOUND: {
+    message: "This project has no transcript",
+    suggestion: "Attach a subtitle file in the export settings first"
+  },
+  TRANSCRIPT_RANGE_INVALID: {
+    message: "The selected words are outside the transcript",
+    suggestion: "The transcript has changed; refresh and select again"
+  },
+  TRASH_ITEM_NOT_FOUND: {
+    message: "Project {projectId} is not in the trash",
+    suggestion: "Refresh the trash list and try again"
+  },
+  UPLOAD_NOT_CONFIGURED: {
+    message: "Uploading is not enabled in this build",
+    suggestion: "Use an official build with uploading configured"
+  },
+  UPLOAD_NOT_CONNECTED: {
+    message: "No {target} account is connected",
+    suggestion: "Connect an account before uploading"
+  },
+  UPLOAD_AUTH_EXPIRED: {
+    message: "The upload authorization has expired",
+    suggestion: "Reconnect the account"
+  },
+  UPLOAD_AUTH_DENIED: {
+    message: "Account authorization was cancelled",
+    suggestion: "Reconnect and allow access to upload"
+  },
+  UPLOAD_AUTH_TIMEOUT: {
+    message: "Timed out waiting for browser authorization",
+    suggestion: "Reconnect and finish authorization in the browser"
+  },
+  UPLOAD_AUTH_FAIL: {
+    message: "Account authorization failed",
+    suggestion: "Try again later"
+  },
+  UPLOAD_NETWORK_ERROR: {
+    message: "A network error interrupted the upload",
+    suggestion: "Check your connection and retry; the upload will resume"
+  },
+  UPLOAD_FAIL: {
+    message: "Upload failed",
+    suggestion: "Try again later; the upload will resume where it stopped"
+  },
+  UPLOAD_FORBIDDEN: {
+    message: "The server rejected the upload",
+    suggestion: "Make sure the account can upload, or reconnect it"
+  },
+  UPLOAD_QUOTA_EXCEEDED: {
+    message: "The upload quota has been reached",
+    suggestion: "Try again later"
+  },
+  UPLOAD_READ_FAIL: {
+    message: "Cannot read the exported file",
+    suggestion: "Finish exporting first and make sure the file still exists"
+  },
+  UPLOAD_ALREADY_ACTIVE: {
+    message: "This project is already uploading to {target}",
+    suggestion: "Wait for the current upload to finish"
+  },
+  UPLOAD_TOKEN_WRITE_FAIL: {
+    message: "Failed to save the account authorization",
+    suggestion: "Check that the app data folder is writable"
+  },
+  UPLOAD_NOT_FOUND: {
+    message: "This project has not been uploaded to {target}",
+    suggestion: "Upload the export first"
+  },
+  INVALID_S3_SETTINGS: {
+    message: "Invalid object storage setting: {field}",
+    suggestion: "Fill in the endpoint, region, bucket and access keys"
+  },
+  S3_BUCKET_NOT_FOUND: {
+    message: "The storage bucket was not found",
+    suggestion: "Check the bucket name, region and path-style setting"
+  },
+  S3_CREDENTIALS_REJECTED: {
+    message: "The object storage rejected the access keys",
+    suggestion: "Check the access key, secret key and region"
+  },
+  SHARE_DISABLED: {
+    message: "LAN sharing is turned off",
+    suggestion: "Turn on LAN sharing in settings first"
+  },
+  SHARE_SERVER_START_FAIL: {
+    message: "Could not start the share server on port {port}",
+    suggestion: "The port may be in use; choose another share port in settings"
+  },
+  INVALID_SHARE_POLICY: {
+    message: "Invalid share link expiry",
+    suggestion: "Share links must be valid for at least 1 hour"
+  },
+  INVALID_BLUR_REGION: {
+    message: "Invalid blur region",
+    suggestion: "Select an area inside the frame and make sure it ends after it starts"
+  },
+  INVALID_MUTE_RANGE: {
+    message: "Invalid mute range",
+    suggestion: "Make sure the mute range ends after it starts"
+  },
+  BLUR_REGION_NOT_FOUND: {
+    message: "Blur region {regionId} not found",
+    suggestion: "The region may have been removed; refresh and try again"
+  },
+  OCR_ENGINE_NOT_FOUND: {
+    message: "OCR engine not found",
+    suggestion: "Install Tesseract OCR or set FOCUSLENS_TESSERACT_PATH"
+  },
+  SENSITIVE_SCAN_FAILED: {
+    message: "Sensitive content scan failed",
+    suggestion: "Make sure the recording is intact and Tesseract OCR runs, then try again"
+  },
+  BLUR_CANDIDATE_NOT_FOUND: {
+    message: "Blur suggestion {candidateId} not found",
+    suggestion: "It may have been handled already; refresh and try again"
+  },
+  INVALID_TEXT_ANNOTATION: {
+    message: "Invalid text annotation",
+    suggestion: "Check the annotation text, colors and start/end times"
+  },
+  TEXT_ANNOTATION_NOT_FOUND: {
+    message: "Text annotation {annotationId} not found",
+    suggestion: "The annotation may have been removed; refresh and try again"
+  },
+  INVALID_SHAPE_ANNOTATION: {
+    message: "Invalid shape annotation",
+    suggestion: "Drag out a larger shape and check its colors and start/end times"
+  },
+  SHAPE_ANNOTATION_NOT_FOUND: {
+    message: "Shape annotation {shapeId} not found",
+    suggestion: "The shape may have been removed; refresh and try again"
+  },
+  LUT_FILE_INVALID: {
+    message: "Invalid LUT file: {path}",
+    suggestion: "Choose an existing .cube LUT file"
+  },
+  MUSIC_FILE_NOT_FOUND: {
+    message: "Background music file not found: {path}",
+    suggestion: "Check the background music file path"
+  },
+  CAPTURE_TEST_NO_VIDEO: {
+    message: "The test recording contains no usable video",
+    suggestion: "Check screen recording permission and the selected display or window"
+  },
+  CAPTURE_TEST_NO_AUDIO: {
+    message: "The test recording has no audio track",
+    suggestion: "Check microphone permission and the audio device"
+  },
+  CAPTURE_TEST_LOW_FPS: {
+    message: "Test recording ran at {fps} fps, below the {target} fps target",
+    suggestion: "Lower the frame rate or resolution, or switch to hardware encoding"
+  },
+  CAPTURE_TEST_SILENT_AUDIO: {
+    message: "The test recording's audio is silent",
+    suggestion: "Make sure the microphone is not muted and check microphone permission"
+  },
+  CAPTURE_TEST_DEGRADED: {
+    message: "{detail}"
+  },
+  INVALID_EXPORT_CONCURRENCY: {
+    message: "Invalid number of concurrent exports",
+    suggestion: "Set concurrent exports between 1 and {max}"
+  },
+  INVALID_ENCODER_THREADS: {
+    message: "Invalid encoder thread count",
+    suggestion: "Set encoder threads between 0 (automatic) and {max}"
+  },
+  PLATFORM_NOT_SUPPORTED: {
+    message: "Recording is not supported on this platform",
+    suggestion: "FocusLens currently supports Windows and macOS"
+  },
+  SESSION_NOT_FOUND: {
+    message: "Recording session not found",
+    suggestion: "The recording may have ended; refresh and try again"
+  },
+  INVALID_RECORDING_STATE: {
+    message: "This action isn't available in the current recording state",
+    suggestion: "Make sure recording has started, or wait for the current action to finish"
+  },
+  RECORDING_START_FAIL: {
+    message: "Failed to start recording",
+    suggestion: "Check recording permissions and capture devices"
+  },
+  RECORDING_STOP_FAIL: {
+    message: "Failed to stop recording",
+    suggestion: "The recording process may have exited; refresh the project list to check the file"
+  },
+  RECORDING_PROCESS_IO: {
+    message: "Couldn't send a command to the recording process",
+    suggestion: "It may have exited; stop and record again"
+  },
+  RECORDING_OUTPUT_MISSING: {
+    message: "The recording produced no usable video file",
+    suggestion: "Check your microphone and system audio devices, then record again"
+  },
+  RECORDING_JOIN_FAILED: {
+    message: "Failed to join the recording parts",
+    suggestion: "Make sure the recording files are intact and retry the repair"
+  },
+  RECORDING_REPAIR_FAILED: {
+    message: "Failed to repair the recording",
+    suggestion: "Try again, or keep the original file and report the issue"
+  },
+  RAW_RECORDING_MISSING: {
+    message: "The original recording file is missing",
+    suggestion: "This project has no recording to repair"
+  },
+  RECOVERY_FAILED: {
+    message: "Failed to recover the recording",
+    suggestion: "Repair the recording first, then recover it"
+  },
+  AUDIO_METER_START_FAIL: {
+    message: "Couldn't start the audio level meter",
+    suggestion: "Recording is unaffected; check the audio track afterwards"
+  },
+  DO_NOT_DISTURB_FAILED: {
+    message: "Couldn't turn on Do Not Disturb",
+    suggestion: "Turn on Do Not Disturb or Focus manually"
+  },
+  HOTKEY_CONFLICT: {
+    message: "Start/stop and pause/resume can't share a shortcut",
+    suggestion: "Assign a different shortcut to each action"
+  },
+  HOTKEY_REGISTER_FAIL: {
+    message: "Failed to register the shortcut",
+    suggestion: "Check the shortcut format, or whether another app already uses it"
+  },
+  CURSOR_TRACK_MISSING: {
+    message: "This project has no cursor track",
+    suggestion: "Finish a recording first"
+  },
+  CURSOR_TRACK_READ_FAIL: {
+    message: "Failed to read the cursor track",
+    suggestion: "Make sure the project files are intact"
+  },
+  CURSOR_TRACK_PARSE_FAIL: {
+    message: "The cursor track file is corrupted",
+    suggestion: "Make sure the project files are intact"
+  },
+  FFMPEG_EXEC_ERROR: {
+    message: "ffmpeg failed to run",
+    suggestion: "Check the ffmpeg installation and export settings"
+  },
+  FFPROBE_EXEC_ERROR: {
+    message: "ffprobe failed to run",
+    suggestion: "Make sure ffprobe is available and the media file is intact"
+  },
+  FFPROBE_PARSE_ERROR: {
+    message: "Couldn't read the media information",
+    suggestion: "Make sure the media file is intact"
+  },
+  THUMBNAIL_FAILED: {
+    message: "Failed to generate thumbnails",
+    suggestion: "Make sure the recording is intact and playable"
+  },
+  WAVEFORM_FAILED: {
+    message: "Failed to generate the waveform",
+    suggestion: "Make sure the recording has an audio track"
+  },
+  SILENCE_DETECT_FAILED: {
+    message: "Silence detection failed",
+    suggestion: "Make sure the recording has an audio track"
+  },
+  FRAME_EXTRACT_FAILED: {
+    message: "Failed to capture the frame",
+    suggestion: "Make sure the recording is intact and the time is within its duration"
+  },
+  FRAME_OUTPUT_INVALID: {
+    message: "Frames can only be saved as .png files",
+    suggestion: "Choose a .png file to save to"
+  },
+  FRAME_OUT_OF_RANGE: {
+    message: "The time is past the end of the recording",
+    suggestion: "Choose a time within the recording"
+  },
+  CONTENT_ANALYSIS_FAILED: {
+    message: "Failed to analyze the video content",
+    suggestion: "Make sure the export is intact and playable"
+  },
+  IMPORT_VIDEO_INVALID: {
+    message: "This video can't be imported",
+    suggestion: "Choose a local .mp4 file with a video track"
+  },
+  IMPORT_VIDEO_FAILED: {
+    message: "Failed to import the video",
+    suggestion: "Check disk space and folder permissions"
+  },
+  EXPORT_CANCELLED: {
+    message: "Export cancelled"
+  },
+  EXPORT_NOT_ACTIVE: {
+    message: "The export isn't running",
+    suggestion: "It has already finished; there's nothing to cancel"
+  },
+  EXPORT_NOT_FOUND: {
+    message: "This project hasn't been exported yet",
+    suggestion: "Export it first"
+  },
+  EXPORT_FILE_MISSING: {
+    message: "The exported file is missing",
+    suggestion: "It may have been moved or deleted; export again"
+  },
+  EXPORT_QUEUE_MISMATCH: {
+    message: "The export queue has changed",
+    suggestion: "Refresh the export queue and try again"
+  },
+  EXPORT_TASK_FAIL: {
+    message: "The export task stopped unexpectedly",
+    suggestion: "Check the export log and try again"
+  },
+  EXPORT_CLIP_MISSING: {
+    message: "The intro or outro clip is missing",
+    suggestion: "Choose the intro or outro clip again"
+  },
+  EXPORT_CLIP_INVALID: {
+    message: "The intro or outro clip can't be read",
+    suggestion: "Make sure the intro and outro are playable videos"
+  },
+  EXPORT_SUBTITLE_MISSING: {
+    message: "The subtitle file is missing",
+    suggestion: "Choose the subtitle file again"
+  },
+  EXPORT_SUBTITLE_UNSUPPORTED: {
+    message: "Unsupported subtitle format",
+    suggestion: "Use SRT, VTT or ASS subtitles"
+  },
+  ENCODER_FAIL: {
+    message: "The encoder failed to initialize",
+    suggestion: "Export falls back to software encoding; otherwise check your graphics drivers"
+  },
+  IO_FAIL: {
+    message: "Export failed",
+    suggestion: "Check the export log and try again"
+  },
+  INVALID_EXPORT_STATE: {
+    message: "The export task is in an unexpected state",
+    suggestion: "Refresh the export queue and try again"
+  },
+  PREVIEW_RANGE_INVALID: {
+    message: "Invalid preview range",
+    suggestion: "Select the preview range again"
+  },
+  PREVIEW_TASK_FAIL: {
+    message: "Preview rendering stopped unexpectedly",
+    suggestion: "Render the preview again"
+  },
+  PROJECT_ASSET_MISSING: {
+    message: "A project file is missing",
+    suggestion: "Finish a recording first, or make sure the project files are intact"
+  },
+  PROJECT_ALREADY_EXISTS: {
+    message: "The project already exists",
+    suggestion: "Delete the existing project before restoring it from the trash"
+  },
+  PROJECT_DELETE_FAIL: {
+    message: "Failed to delete the project",
+    suggestion: "Close any program using the project files and try again"
+  },
+  PROJECT_LIST_READ_FAIL: {
+    message: "Failed to read the project list",
+    suggestion: "Make sure the project folder is readable"
+  },
+  TRASH_IO_ERROR: {
+    message: "Trash operation failed",
+    suggestion: "Check disk space and folder permissions"
+  },
+  INVALID_REVISION_ID: {
+    message: "Invalid revision ID",
+    suggestion: "Pick a revision from the list"
+  },
+  EDIT_HISTORY_EMPTY: {
+    message: "Nothing to undo or redo"
+  },
+  INVALID_TIMELINE: {
+    message: "Invalid timeline settings",
+    suggestion: "Check the segment ranges and speeds"
+  },
+  INVALID_TAG: {
+    message: "Invalid tag",
+    suggestion: "Tags can be up to 32 characters, with at most 20 per project"
+  },
+  INVALID_SCENE_COLOR: {
+    message: "Invalid background color",
+    suggestion: "Use a #RRGGBB color"
+  },
+  SCENE_WALLPAPER_MISSING: {
+    message: "The wallpaper image is missing",
+    suggestion: "Choose the wallpaper image again"
+  },
+  MIGRATION_ERROR: {
+    message: "Couldn't upgrade the file format",
+    suggestion: "The project or settings file may be corrupted"
+  },
+  UNSUPPORTED_SCHEMA: {
+    message: "The file was created by a newer version of FocusLens",
+    suggestion: "Update the app and try again"
+  },
+  UNSUPPORTED_ARCHIVE: {
+    message: "The archive was created by a newer version of FocusLens",
+    suggestion: "Update the app and try again"
+  },
+  ARCHIVE_NOT_FOUND: {
+    message: "Couldn't open the archive",
+    suggestion: "Check the archive path"
+  },
+  ARCHIVE_INVALID: {
+    message: "Not a valid FocusLens archive",
+    suggestion: "Choose a .focuslens file packed by FocusLens"
+  },
+  ARCHIVE_IO_ERROR: {
+    message: "Failed to read or write the archive",
+    suggestion: "Check disk space and folder permissions"
+  },
+  ARCHIVE_PATH_INVALID: {
+    message: "No archive location selected",
+    suggestion: "Choose where to save the .focuslens file"
+  },
+  SETTINGS_READ_FAIL: {
+    message: "Failed to read settings",
+    suggestion: "Make sure the app data folder is readable"
+  },
+  SETTINGS_PARSE_FAIL: {
+    message: "The settings file is corrupted",
+    suggestion: "Delete settings.json and restart the app to restore defaults"
+  },
+  SETTINGS_WRITE_FAIL: {
+    message: "Failed to save settings",
+    suggestion: "Check disk space and app data folder permissions"
+  },
+  IO_ERROR: {
+    message: "File read or write failed",
+    suggestion: "Check disk space and folder permissions"
+  },
+  SERDE_ERROR: {
+    message: "Invalid data format",
+    suggestion: "A related file may be corrupted"
+  },
+  SHELL_OPEN_FAILED: {
+    message: "Couldn't open the file manager",
+    suggestion: "Open the export folder manually"
+  },
+  EVENT_ERROR: {
+    message: "Failed to update the app window",
+    suggestion: "Restart the app and try again"
+  }
+};
+
+const catalogs: Record<Locale, ErrorCatalog> = {
+  "zh-CN": zhCN,
+  "en-US": enUS
+};
+
+function interpolate(template: string, params: Record<string, string> | undefined) {
+  return template.replace(/\{(\w+)\}/g, (placeholder, key: string) => params?.[key] ?? placeholder);
+}
+
+// 后端的建议文案均为中文，只在中文界面下作为兜底。
+function fallbackSuggestion(error: AppError, locale: Locale) {
+  return locale === "zh-CN" ? error.suggestion : undefined;
+}
+
+// 按错误码查本地化文案；未收录的错误码保留后端返回的原始文案。
+export function localizeError(error: AppError, locale: Locale): AppError {
+  const entry = catalogs[locale][error.code];
+  if (!entry) {
+    return { ...error, suggestion: fallbackSuggestion(error, locale) };
+  }
+  return {
+    ...error,
+    message: interpolate(entry.message, error.params),
+    suggestion: entry.suggestion
+      ? interpolate(entry.suggestion, error.params)
+      : fallbackSuggestion(error, locale)
+  };
+}
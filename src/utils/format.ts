@@ -1,3 +1,5 @@
+import type { ExportUsage } from "../types/project";
+
 export function formatDuration(durationMs: number) {
   const totalSeconds = Math.max(0, Math.floor(durationMs / 1000));
   const minutes = Math.floor(totalSeconds / 60);
@@ -18,3 +20,10 @@ export function formatDateTime(value: string) {
     minute: "2-digit"
   });
 }
+
+// 导出完成后的资源消耗摘要，如“h264_nvenc · CPU 12.3s · 用时 8.1s · 24.5 MB”。
+export function formatExportUsage(usage: ExportUsage) {
+  const seconds = (ms: number) => `${(ms / 1000).toFixed(1)}s`;
+  const megabytes = (usage.outputBytes / 1024 / 1024).toFixed(1);
+  return `${usage.encoder} · CPU ${seconds(usage.cpuTimeMs)} · 用时 ${seconds(usage.wallTimeMs)} · ${megabytes} MB`;
+}
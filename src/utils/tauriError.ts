@@ -1,4 +1,6 @@
+import { useSettingsStore } from "../stores/settingsStore";
 import type { AppError } from "../types/project";
+import { localizeError } from "./errorCatalog";
 
 function asRecord(value: unknown): Record<string, unknown> | null {
   if (typeof value === "object" && value !== null) {
@@ -19,8 +21,12 @@ function parseFromRecord(
       : null;
   const suggestion =
     typeof value.suggestion === "string" ? value.suggestion : undefined;
+  const params = asRecord(value.params) as Record<string, string> | null;
   if (message) {
-    return { code, message, suggestion };
+    return localizeError(
+      { code, message, suggestion, params: params ?? undefined },
+      useSettingsStore.getState().locale
+    );
   }
   return null;
 }